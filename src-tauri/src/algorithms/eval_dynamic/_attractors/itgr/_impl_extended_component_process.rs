@@ -50,6 +50,7 @@ impl Process for ExtendedComponentProcess {
                 scheduler.discard_variable(self.variable);
             }
 
+            scheduler.mark_variable_processed();
             true
         } else {
             false
@@ -9,6 +9,7 @@ impl Scheduler {
     pub fn new(initial: GraphColoredVertices, variables: Vec<VariableId>) -> Scheduler {
         Scheduler {
             active_variables: variables,
+            num_processed_variables: 0,
             universe: initial,
             processes: Vec::new(),
             to_discard: None,
@@ -31,6 +32,16 @@ impl Scheduler {
             });
     }
 
+    /// Record that all processes for one of the variables are finished.
+    pub fn mark_variable_processed(&mut self) {
+        self.num_processed_variables += 1;
+    }
+
+    /// Get the number of variables for which all processes are finished.
+    pub fn get_num_processed_variables(&self) -> usize {
+        self.num_processed_variables
+    }
+
     /// Remove given `set` from the universe of this scheduler.
     pub fn discard_vertices(&mut self, set: &GraphColoredVertices) {
         self.universe = self.universe.minus(set);
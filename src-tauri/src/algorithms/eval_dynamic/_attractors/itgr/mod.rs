@@ -20,11 +20,17 @@ mod _impl_scheduler;
 /// transitions in the graph (other variables are effectively constant).
 ///
 /// If cancelled, the result is still valid, but not necessarily complete.
-pub fn interleaved_transition_guided_reduction(
+///
+/// The `progress_callback` is called each time a variable is fully processed, with the current
+/// universe and a message containing a rough progress estimate (the percentage of processed
+/// variables) and the number of remaining states.
+pub fn interleaved_transition_guided_reduction<F: FnMut(&GraphColoredVertices, &str)>(
     graph: &SymbolicAsyncGraph,
     initial: GraphColoredVertices,
+    progress_callback: &mut F,
 ) -> (GraphColoredVertices, Vec<VariableId>) {
     let variables = graph.variables().collect::<Vec<_>>();
+    let num_variables = variables.len();
     let mut scheduler = Scheduler::new(initial, variables);
     for variable in graph.variables() {
         scheduler.spawn(ReachableProcess::new(
@@ -34,8 +40,20 @@ pub fn interleaved_transition_guided_reduction(
         ));
     }
 
+    let mut num_processed = 0;
     while !scheduler.is_done() {
         scheduler.step(graph);
+
+        if scheduler.get_num_processed_variables() > num_processed {
+            num_processed = scheduler.get_num_processed_variables();
+            let universe = scheduler.get_universe();
+            let msg = format!(
+                "ITGR processed {num_processed}/{num_variables} variables (~{}%), {} states remain.",
+                num_processed * 100 / num_variables,
+                universe.approx_cardinality()
+            );
+            progress_callback(universe, &msg);
+        }
     }
 
     scheduler.finalize()
@@ -62,6 +80,7 @@ trait Process {
 /// of unprocessed vertices and a list of remaining active variables.
 struct Scheduler {
     active_variables: Vec<VariableId>,
+    num_processed_variables: usize,
     universe: GraphColoredVertices,
     processes: Vec<(usize, Box<dyn Process>)>,
     to_discard: Option<GraphColoredVertices>,
@@ -8,22 +8,44 @@ mod xie_beerel;
 
 use crate::algorithms::eval_dynamic::_attractors::itgr::interleaved_transition_guided_reduction;
 use crate::algorithms::eval_dynamic::_attractors::xie_beerel::xie_beerel_attractors;
+use biodivine_lib_bdd::BddPartialValuation;
 use biodivine_lib_param_bn::{
     biodivine_std::traits::Set,
     symbolic_async_graph::{GraphColoredVertices, GraphColors, SymbolicAsyncGraph},
 };
+use rayon::prelude::*;
 
 /// Compute terminal SCCs, and sort all the colors according to how many attractors they have.
 /// Returns the vector, where on index i are all colors with i attractors.
+///
+/// See [sort_colors_by_attr_num_incremental] for a variant reporting partial results.
 pub fn sort_colors_by_attr_num<F: FnMut(&GraphColoredVertices, &str)>(
     graph: &SymbolicAsyncGraph,
     progress_callback: &mut F,
 ) -> Vec<GraphColors> {
+    sort_colors_by_attr_num_incremental(graph, progress_callback, &mut |_| {})
+}
+
+/// Variant of [sort_colors_by_attr_num] that streams partial results. The `result_callback` is
+/// invoked each time an attractor component is processed, with the current snapshot of colors
+/// sorted by the number of attractors found so far (on index i are colors with i attractors).
+///
+/// Each snapshot is a partition of all colors (no color is counted twice), and the counts only
+/// grow between consecutive snapshots. The last snapshot is equal to the final result.
+pub fn sort_colors_by_attr_num_incremental<F, R>(
+    graph: &SymbolicAsyncGraph,
+    progress_callback: &mut F,
+    result_callback: &mut R,
+) -> Vec<GraphColors>
+where
+    F: FnMut(&GraphColoredVertices, &str),
+    R: FnMut(&[GraphColors]) + Send + Sync,
+{
     // First, perform ITGR reduction.
     let initial = graph.mk_unit_colored_vertices();
     progress_callback(&initial, "Starting state space pre-pruning using the ITGR.");
-    // TODO: Add internal progress callback into the ITGR as well
-    let (universe, active_variables) = interleaved_transition_guided_reduction(graph, initial);
+    let (universe, active_variables) =
+        interleaved_transition_guided_reduction(graph, initial, progress_callback);
 
     let mut colors_by_num_attrs = Vec::new();
     colors_by_num_attrs.push(graph.mk_unit_colors());
@@ -35,7 +57,9 @@ pub fn sort_colors_by_attr_num<F: FnMut(&GraphColoredVertices, &str)>(
         &universe,
         &active_variables,
         |component| {
+            // the snapshot is only reported once the component is fully processed
             process_component(&mut colors_by_num_attrs, &component);
+            result_callback(&colors_by_num_attrs);
         },
         progress_callback,
     );
@@ -43,6 +67,145 @@ pub fn sort_colors_by_attr_num<F: FnMut(&GraphColoredVertices, &str)>(
     colors_by_num_attrs
 }
 
+/// Compute all attractors (terminal SCCs) of the graph, using the ITGR reduction followed
+/// by the Xie-Beerel algorithm. Each returned component is an attractor for a subset of colors.
+///
+/// This is mainly useful for graphs with a single color (concrete networks), where each
+/// component is simply a set of attractor states.
+pub fn attractor_components<F: FnMut(&GraphColoredVertices, &str)>(
+    graph: &SymbolicAsyncGraph,
+    progress_callback: &mut F,
+) -> Vec<GraphColoredVertices> {
+    let initial = graph.mk_unit_colored_vertices();
+    progress_callback(&initial, "Starting state space pre-pruning using the ITGR.");
+    let (universe, active_variables) =
+        interleaved_transition_guided_reduction(graph, initial, progress_callback);
+
+    let mut components = Vec::new();
+    progress_callback(&universe, "Starting attractor computation with Xie-Beerel.");
+    xie_beerel_attractors(
+        graph,
+        &universe,
+        &active_variables,
+        |component| components.push(component),
+        progress_callback,
+    );
+    components
+}
+
+/// Parallel version of [sort_colors_by_attr_num]. After the (sequential) ITGR reduction, the
+/// color space is split into disjoint partitions (by fixing values of some parameter
+/// variables), and Xie-Beerel is run on each partition in parallel using `rayon`. The results
+/// are then merged.
+///
+/// The number of partitions is the smallest power of two that is at least `num_partitions`
+/// (limited by the number of parameter variables). With `num_partitions <= 1`, this simply
+/// calls the sequential [sort_colors_by_attr_num].
+///
+/// Note that the progress callback is only called during the ITGR and before the parallel
+/// part starts.
+pub fn sort_colors_by_attr_num_parallel<F: FnMut(&GraphColoredVertices, &str)>(
+    graph: &SymbolicAsyncGraph,
+    num_partitions: usize,
+    progress_callback: &mut F,
+) -> Vec<GraphColors> {
+    if num_partitions <= 1 {
+        return sort_colors_by_attr_num(graph, progress_callback);
+    }
+
+    // First, perform ITGR reduction.
+    let initial = graph.mk_unit_colored_vertices();
+    progress_callback(&initial, "Starting state space pre-pruning using the ITGR.");
+    let (universe, active_variables) =
+        interleaved_transition_guided_reduction(graph, initial, progress_callback);
+
+    // Then split the remaining colors and run Xie-Beerel on each partition separately
+    progress_callback(
+        &universe,
+        "Starting parallel attractor computation with Xie-Beerel.",
+    );
+    let partitions = partition_colors(graph, &universe.colors(), num_partitions);
+    let partial_results: Vec<Vec<GraphColors>> = partitions
+        .par_iter()
+        .map(|partition| {
+            let partition_universe = universe.intersect_colors(partition);
+            let mut colors_by_num_attrs = vec![graph.mk_unit_colors()];
+            xie_beerel_attractors(
+                graph,
+                &partition_universe,
+                &active_variables,
+                |component| {
+                    process_component(&mut colors_by_num_attrs, &component);
+                },
+                &mut |_, _| {},
+            );
+            colors_by_num_attrs
+        })
+        .collect();
+
+    partial_results
+        .into_iter()
+        .fold(vec![graph.mk_unit_colors()], |acc, partial| {
+            merge_colors_by_num_attrs(graph, &acc, &partial)
+        })
+}
+
+/// Split the given set of colors into (at most) `num_partitions` disjoint non-empty subsets,
+/// by fixing values of the first few parameter variables.
+fn partition_colors(
+    graph: &SymbolicAsyncGraph,
+    colors: &GraphColors,
+    num_partitions: usize,
+) -> Vec<GraphColors> {
+    let context = graph.symbolic_context();
+    let split_vars: Vec<_> = context
+        .parameter_variables()
+        .iter()
+        .take(num_partitions.next_power_of_two().trailing_zeros() as usize)
+        .collect();
+
+    let mut partitions = vec![colors.clone()];
+    for var in split_vars {
+        let mut new_partitions = Vec::new();
+        for partition in partitions {
+            for value in [false, true] {
+                let valuation = BddPartialValuation::from_values(&[(*var, value)]);
+                let cube = context.bdd_variable_set().mk_conjunctive_clause(&valuation);
+                let sub_partition = partition.intersect(&partition.copy(cube));
+                if !sub_partition.is_empty() {
+                    new_partitions.push(sub_partition);
+                }
+            }
+        }
+        partitions = new_partitions;
+    }
+    partitions
+}
+
+/// Merge two vectors of colors sorted by number of attractors (both covering all colors),
+/// summing the attractor counts for each color. On index i of the result are colors for which
+/// the numbers of attractors in `first` and `second` add up to i.
+fn merge_colors_by_num_attrs(
+    graph: &SymbolicAsyncGraph,
+    first: &[GraphColors],
+    second: &[GraphColors],
+) -> Vec<GraphColors> {
+    let mut merged = vec![graph.mk_empty_colors(); first.len() + second.len() - 1];
+    for (i, first_colors) in first.iter().enumerate() {
+        for (j, second_colors) in second.iter().enumerate() {
+            let intersect = first_colors.intersect(second_colors);
+            if !intersect.is_empty() {
+                merged[i + j] = merged[i + j].union(&intersect);
+            }
+        }
+    }
+    // remove the trailing empty sets
+    while merged.len() > 1 && merged.last().unwrap().is_empty() {
+        merged.pop();
+    }
+    merged
+}
+
 /// Process a component found by Xie-Beerel (attractor component for a subset of colors).
 /// Update the `colors_by_num_attrs` so that on index i are all colors with i attractors,
 /// after taking the new component into account.
@@ -68,3 +231,84 @@ fn process_component(colors_by_num_attrs: &mut Vec<GraphColors>, component: &Gra
         colors_by_num_attrs[num_attrs] = colors_by_num_attrs[num_attrs].minus(&intersect)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::eval_dynamic::_attractors::itgr::interleaved_transition_guided_reduction;
+    use crate::algorithms::eval_dynamic::_attractors::{
+        sort_colors_by_attr_num, sort_colors_by_attr_num_incremental,
+        sort_colors_by_attr_num_parallel,
+    };
+    use biodivine_lib_param_bn::biodivine_std::traits::Set;
+    use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+    use biodivine_lib_param_bn::BooleanNetwork;
+
+    #[test]
+    /// Test that the parallel version of attractor counting gives the same results as the
+    /// sequential one.
+    fn test_parallel_sort_colors_by_attr_num() {
+        let aeon_str = "A -? B\nB -? A\nA -? A\nB -? B\nC -> A\nC -| C";
+        let bn = BooleanNetwork::try_from(aeon_str).unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+
+        let expected = sort_colors_by_attr_num(&graph, &mut |_, _| {});
+        assert!(expected.len() > 2);
+        for num_partitions in [1, 2, 4, 100] {
+            let result = sort_colors_by_attr_num_parallel(&graph, num_partitions, &mut |_, _| {});
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    /// Test that the incremental attractor counting reports consistent partial results.
+    fn test_incremental_sort_colors_by_attr_num() {
+        let aeon_str = "A -? B\nB -? A\nA -? A\nB -? B\nC -> A\nC -| C";
+        let bn = BooleanNetwork::try_from(aeon_str).unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+
+        let mut snapshots: Vec<Vec<GraphColors>> = Vec::new();
+        let result = sort_colors_by_attr_num_incremental(&graph, &mut |_, _| {}, &mut |snapshot| {
+            snapshots.push(snapshot.to_vec())
+        });
+        assert_eq!(result, sort_colors_by_attr_num(&graph, &mut |_, _| {}));
+        assert_eq!(snapshots.last(), Some(&result));
+
+        let all_colors = graph.mk_unit_colors();
+        for snapshot in snapshots.iter() {
+            // each snapshot is a partition of all colors
+            let mut covered = graph.mk_empty_colors();
+            for colors in snapshot {
+                assert!(covered.intersect(colors).is_empty());
+                covered = covered.union(colors);
+            }
+            assert_eq!(covered, all_colors);
+        }
+        // the number of attractors of each color never decreases
+        for window in snapshots.windows(2) {
+            for (i, colors) in window[1].iter().enumerate() {
+                let mut previously_more = window[0].iter().skip(i + 1);
+                assert!(previously_more.all(|c| c.intersect(colors).is_empty()));
+            }
+        }
+    }
+
+    #[test]
+    /// Test that the ITGR reports progress after each processed variable.
+    fn test_itgr_progress() {
+        let aeon_str = "A -? B\nB -? A\nA -? A\nB -? B\nC -> A\nC -| C";
+        let bn = BooleanNetwork::try_from(aeon_str).unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+
+        let mut messages = Vec::new();
+        let (universe, _) = interleaved_transition_guided_reduction(
+            &graph,
+            graph.mk_unit_colored_vertices(),
+            &mut |_, msg| messages.push(msg.to_string()),
+        );
+        assert_eq!(messages.len(), 3);
+        assert!(messages[0].starts_with("ITGR processed 1/3 variables (~33%)"));
+        assert!(messages[2].starts_with("ITGR processed 3/3 variables (~100%)"));
+        let expected_end = format!("{} states remain.", universe.approx_cardinality());
+        assert!(messages[2].ends_with(&expected_end));
+    }
+}
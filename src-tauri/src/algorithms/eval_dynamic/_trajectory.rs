@@ -1,5 +1,8 @@
+use crate::algorithms::eval_dynamic::prepare_graph::prepare_initial_vertices;
 use crate::algorithms::eval_dynamic::saturated_reachability::{reach_bwd, reachability_step};
-use crate::algorithms::eval_dynamic::utils::transform_obs_to_singleton_vertex;
+use crate::algorithms::eval_dynamic::utils::{
+    transform_dataset_to_vertex_set, transform_obs_to_singleton_vertex, transform_obs_to_vertex_set,
+};
 use crate::sketchbook::observations::Dataset;
 use biodivine_lib_param_bn::biodivine_std::traits::Set;
 use biodivine_lib_param_bn::symbolic_async_graph::{
@@ -7,6 +10,10 @@ use biodivine_lib_param_bn::symbolic_async_graph::{
 };
 use biodivine_lib_param_bn::VariableId;
 
+/// Scores (sums of observation weights) that differ by at most this value are considered the
+/// same when grouping colors by weighted reachability (to tolerate floating-point rounding).
+const SCORE_TOLERANCE: f64 = 1e-9;
+
 /// Compute colors where the whole `target` is back-reachable from `initial` inside `universe`.
 /// When target is a single state, this is simple. When target consists of multiple states,
 /// all of them must be reachable.
@@ -109,3 +116,145 @@ pub fn colors_with_trajectory<F: FnMut(&GraphColoredVertices, &str)>(
     }
     Ok(sat_colors)
 }
+
+/// Compute colors where at least `min_count` observations of the dataset are reachable, i.e.,
+/// there is a state from which each of these observations can be reached. Observations may
+/// contain missing values (reaching any state of the corresponding sub-space is sufficient).
+///
+/// Instead of going through all subsets of observations of size `min_count`, we compute the
+/// backward reachable set of each observation and count (up to `min_count`) how many of
+/// them contain each colored state.
+///
+/// Only states of the `initial_subspace` are counted (see [prepare_initial_vertices]).
+pub fn colors_where_obs_reachable<F: FnMut(&GraphColoredVertices, &str)>(
+    dataset: &Dataset,
+    min_count: usize,
+    initial_subspace: &[(String, bool)],
+    graph: &SymbolicAsyncGraph,
+    progress_callback: &mut F,
+) -> Result<GraphColors, String> {
+    if min_count > dataset.num_observations() {
+        return Err(format!(
+            "Cannot require {min_count} reachable observations, the dataset only has {}.",
+            dataset.num_observations()
+        ));
+    }
+    let var_names = dataset.variable_names();
+    let variables = graph.variables().collect::<Vec<_>>();
+    let universe = graph.unit_colored_vertices();
+
+    // item `i` contains all colored states from which at least `i` of the (so far processed)
+    // observations are reachable
+    let mut reach_at_least = vec![graph.mk_empty_colored_vertices(); min_count + 1];
+    reach_at_least[0] = prepare_initial_vertices(graph, initial_subspace)?;
+    for (index, obs) in dataset.observations().iter().enumerate() {
+        let obs_states = transform_obs_to_vertex_set(obs, &var_names, graph)?;
+        let msg = format!("Computing backward reachability from observation n.{index}.");
+        progress_callback(&obs_states, &msg);
+        let bwd_reach = reach_bwd(graph, &obs_states, universe, &variables, progress_callback);
+
+        // go from the top, so that each observation is counted only once per state
+        for count in (1..=min_count).rev() {
+            let newly_reaching = reach_at_least[count - 1].intersect(&bwd_reach);
+            reach_at_least[count] = reach_at_least[count].union(&newly_reaching);
+        }
+    }
+    Ok(reach_at_least[min_count].colors())
+}
+
+/// Compute colors where every observation of the `target_dataset` is reachable from some
+/// observation of the `initial_dataset`. Observations may contain missing values, in which
+/// case they represent sub-spaces (starting in or reaching any of their states is sufficient).
+///
+/// The initial set is encoded as a union of all initial observation sub-spaces. For each
+/// target observation, we compute its backward reachable set and keep the colors where it
+/// intersects the initial set.
+pub fn colors_where_reachable_from_dataset<F: FnMut(&GraphColoredVertices, &str)>(
+    initial_dataset: &Dataset,
+    target_dataset: &Dataset,
+    graph: &SymbolicAsyncGraph,
+    progress_callback: &mut F,
+) -> Result<GraphColors, String> {
+    let initial_states = transform_dataset_to_vertex_set(initial_dataset, graph)?;
+    let var_names = target_dataset.variable_names();
+    let variables = graph.variables().collect::<Vec<_>>();
+
+    let mut sat_colors = initial_states.colors();
+    for (index, obs) in target_dataset.observations().iter().enumerate() {
+        // colors that already failed do not need to be considered anymore
+        let universe = graph.unit_colored_vertices().intersect_colors(&sat_colors);
+        let target_states = transform_obs_to_vertex_set(obs, &var_names, graph)?;
+        let msg = format!("Computing backward reachability from target observation n.{index}.");
+        progress_callback(&target_states, &msg);
+
+        let bwd_reach = reach_bwd(
+            graph,
+            &target_states,
+            &universe,
+            &variables,
+            progress_callback,
+        );
+        sat_colors = bwd_reach.intersect(&initial_states).colors();
+    }
+    Ok(sat_colors)
+}
+
+/// Compute a score for each color, given by the total weight of the `target_dataset`
+/// observations that are reachable from some observation of the `initial_dataset` (see
+/// [colors_where_reachable_from_dataset] for the corresponding hard constraint).
+///
+/// The result is a partition of all colors of the `graph` into classes with the same score.
+/// Classes are sorted by decreasing score, and only non-empty classes are included.
+pub fn score_colors_by_weighted_reachability<F: FnMut(&GraphColoredVertices, &str)>(
+    initial_dataset: &Dataset,
+    target_dataset: &Dataset,
+    graph: &SymbolicAsyncGraph,
+    progress_callback: &mut F,
+) -> Result<Vec<(f64, GraphColors)>, String> {
+    let initial_states = transform_dataset_to_vertex_set(initial_dataset, graph)?;
+    let var_names = target_dataset.variable_names();
+    let variables = graph.variables().collect::<Vec<_>>();
+    let universe = graph.unit_colored_vertices();
+
+    let mut score_classes: Vec<(f64, GraphColors)> = vec![(0., graph.mk_unit_colors())];
+    for (index, obs) in target_dataset.observations().iter().enumerate() {
+        let target_states = transform_obs_to_vertex_set(obs, &var_names, graph)?;
+        let msg = format!("Computing backward reachability from target observation n.{index}.");
+        progress_callback(&target_states, &msg);
+        let bwd_reach = reach_bwd(
+            graph,
+            &target_states,
+            universe,
+            &variables,
+            progress_callback,
+        );
+        let reaching_colors = bwd_reach.intersect(&initial_states).colors();
+
+        // split each class into colors that reach the observation (and thus increase their
+        // score) and those that do not
+        let weight = obs.get_weight();
+        let mut new_classes: Vec<(f64, GraphColors)> = Vec::new();
+        for (score, colors) in score_classes {
+            let split = [
+                (score + weight, colors.intersect(&reaching_colors)),
+                (score, colors.minus(&reaching_colors)),
+            ];
+            for (new_score, new_colors) in split {
+                if new_colors.is_empty() {
+                    continue;
+                }
+                // merge classes with the same score to keep the partition small
+                match new_classes
+                    .iter_mut()
+                    .find(|(s, _)| (*s - new_score).abs() <= SCORE_TOLERANCE)
+                {
+                    Some((_, class_colors)) => *class_colors = class_colors.union(&new_colors),
+                    None => new_classes.push((new_score, new_colors)),
+                }
+            }
+        }
+        score_classes = new_classes;
+    }
+    score_classes.sort_by(|(s1, _), (s2, _)| s2.total_cmp(s1));
+    Ok(score_classes)
+}
@@ -1,7 +1,10 @@
-use crate::algorithms::eval_dynamic::processed_props::DataEncodingType;
+use crate::algorithms::eval_dynamic::processed_props::{DataEncodingType, ProcessedDynProp};
+use crate::sketchbook::data_structs::EncodingMetrics;
 use crate::sketchbook::ids::ObservationId;
 use crate::sketchbook::observations::{Dataset, Observation, VarValue};
 use crate::sketchbook::properties::HctlFormula;
+use biodivine_hctl_model_checker::preprocessing::hctl_tree::{HctlTreeNode, NodeType};
+use biodivine_hctl_model_checker::preprocessing::operator_enums::Atomic;
 use std::fmt::Write;
 
 /// Encode a dataset of observations as a single HCTL formula. The particular formula
@@ -15,6 +18,13 @@ use std::fmt::Write;
 ///    (see [mk_formula_trap_space_list]) that ensures each observation correspond to a trap space.
 /// d) Time-series dataset is encoded with a "reachability chain" formula,
 ///    (see [mk_formula_reachability_chain]) ensuring there is path between each consecutive observations.
+/// e) Steady-state dataset is encoded with a conjunction of "steady-state formulas"
+///    (see [mk_formula_steady_state_list]) that ensures no specified value of any observation is
+///    changed by the update functions.
+/// f) Unreachable dataset is encoded with a conjunction of "unreachability formulas"
+///    (see [mk_formula_unreachable_list]) that ensures no observation can be reached from outside.
+/// g) Not-attractor dataset is encoded with a conjunction of negated "attractor formulas"
+///    (see [mk_formula_not_attractor_list]) that ensures no observation contains an attractor.
 pub fn encode_dataset_hctl_str(
     dataset: &Dataset,
     observation_id: Option<ObservationId>,
@@ -26,20 +36,83 @@ pub fn encode_dataset_hctl_str(
         .map(|v| v.to_string())
         .collect::<Vec<String>>();
 
-    let encoded_observations = if let Some(obs_id) = observation_id {
-        let observation = dataset.get_obs(&obs_id)?;
-        vec![encode_observation_str(observation, &var_names)?]
-    } else {
-        let observations = dataset.observations();
-        encode_multiple_observations_str(observations, &var_names)?
-    };
+    let observations = select_observations(dataset, observation_id)?;
+    let encoded_observations = encode_multiple_observations_str(&observations, &var_names)?;
 
     match category {
         DataEncodingType::Attractor => Ok(mk_formula_attractor_list(&encoded_observations)),
         DataEncodingType::FixedPoint => Ok(mk_formula_fixed_point_list(&encoded_observations)),
+        DataEncodingType::SteadyState => {
+            let literals = observations
+                .iter()
+                .map(|obs| encode_observation_literals(obs, &var_names))
+                .collect::<Result<Vec<_>, String>>()?;
+            Ok(mk_formula_steady_state_list(
+                &encoded_observations,
+                &literals,
+            ))
+        }
         DataEncodingType::TrapSpace => Ok(mk_formula_trap_space_list(&encoded_observations)),
         DataEncodingType::TimeSeries => Ok(mk_formula_reachability_chain(&encoded_observations)),
+        DataEncodingType::Unreachable => {
+            Ok(mk_formula_unreachable_list(&encoded_observations, None))
+        }
+        DataEncodingType::NotAttractor => Ok(mk_formula_not_attractor_list(&encoded_observations)),
+    }
+}
+
+/// Encode unreachability of observations of a dataset as a single HCTL formula (see
+/// [mk_formula_unreachable_list]), only considering paths that start in the `initial_subspace`
+/// (a partial assignment of variables, given by their names). If the subspace is empty, paths
+/// may start anywhere, the same as with [encode_dataset_hctl_str].
+pub fn encode_dataset_unreachable_hctl_str(
+    dataset: &Dataset,
+    observation_id: Option<ObservationId>,
+    initial_subspace: &[(String, bool)],
+) -> Result<String, String> {
+    let observations = select_observations(dataset, observation_id)?;
+    let encoded_observations =
+        encode_multiple_observations_str(&observations, &dataset.variable_names())?;
+    let initial_space = encode_subspace_str(initial_subspace);
+    Ok(mk_formula_unreachable_list(
+        &encoded_observations,
+        initial_space.as_deref(),
+    ))
+}
+
+/// **(internal)** Get the observation with given ID from the dataset, or all its observations
+/// if no ID is given.
+fn select_observations(
+    dataset: &Dataset,
+    observation_id: Option<ObservationId>,
+) -> Result<Vec<Observation>, String> {
+    if let Some(obs_id) = observation_id {
+        Ok(vec![dataset.get_obs(&obs_id)?.clone()])
+    } else {
+        Ok(dataset.observations().clone())
+    }
+}
+
+/// **(internal)** Encode a sub-space given as a partial assignment of variables (given by their
+/// names) with a conjunction of literals. Returns `None` for an empty assignment.
+///
+/// `[(v1, false), (v3, true)]` would end up like `(~v1 & v3)`
+fn encode_subspace_str(subspace: &[(String, bool)]) -> Option<String> {
+    if subspace.is_empty() {
+        return None;
     }
+    let literals = subspace
+        .iter()
+        .map(|(var, value)| {
+            if *value {
+                var.clone()
+            } else {
+                format!("~{var}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" & ");
+    Some(format!("({literals})"))
 }
 
 /// Encode an observation by a (propositional) formula depicting the corresponding state/sub-space.
@@ -71,15 +144,33 @@ pub fn try_encode_multiple_observations(
 /// describing that observation.
 ///
 /// `00*1*1` would end up like `!v1 & !v2 & v4 & v6`
-fn encode_observation_str(
+pub(crate) fn encode_observation_str(
     observation: &Observation,
     prop_names: &[String],
 ) -> Result<String, String> {
+    let formula = encode_observation_literals(observation, prop_names)?.join(" & ");
+
+    // if observation corresponds to the whole space (all vars are '*'), we just use 'true'
+    let final_formula = if formula.is_empty() {
+        "(true)".to_string()
+    } else {
+        format!("({formula})")
+    };
+    Ok(final_formula)
+}
+
+/// Encode binarized observation as a list of literals, one for each specified value.
+///
+/// `00*1*1` would end up like `[!v1, !v2, v4, v6]`
+fn encode_observation_literals(
+    observation: &Observation,
+    prop_names: &[String],
+) -> Result<Vec<String>, String> {
     if observation.num_values() != prop_names.len() {
         return Err("Numbers of observation's values and propositions differs.".to_string());
     }
 
-    let formula: String = prop_names
+    let literals = prop_names
         .iter()
         .enumerate()
         .filter_map(|(i, prop)| match observation.get_values()[i] {
@@ -87,16 +178,8 @@ fn encode_observation_str(
             VarValue::False => Some(format!("~{prop}")),
             VarValue::Any => None,
         })
-        .collect::<Vec<_>>()
-        .join(" & ");
-
-    // if observation corresponds to the whole space (all vars are '*'), we just use 'true'
-    let final_formula = if formula.is_empty() {
-        "(true)".to_string()
-    } else {
-        format!("({formula})")
-    };
-    Ok(final_formula)
+        .collect();
+    Ok(literals)
 }
 
 /// Encode several observation vectors with conjunction formulae, one by one.
@@ -155,6 +238,34 @@ pub fn mk_formula_attractor_list(attractor_state_list: &[String]) -> String {
     format!("({formula})")
 }
 
+/// Create HCTL formula describing that given sub-space (observation) does not contain any state
+/// that is part of an attractor. It is a negation of the "attractor formula" (see
+/// [mk_formula_attractor]).
+///
+/// > `! EXISTS x. JUMP x. ({state} & (AG EF ({state} & x)))`
+///
+/// Arg `sub_space` is a formula encoding the sub-space of interest.
+pub fn mk_formula_not_attractor(sub_space: &str) -> String {
+    assert!(!sub_space.is_empty());
+    format!("~{}", mk_formula_attractor(sub_space))
+}
+
+/// Create HCTL formula describing that none of the sub-spaces (observations) in a list contain
+/// a state that is part of an attractor.
+/// It is essentially a conjunction of "not-attractor formulas" (see [mk_formula_not_attractor]).
+///
+/// > `NOT_ATTRACTOR({space1}) & ... & NOT_ATTRACTOR({spaceN})`
+pub fn mk_formula_not_attractor_list(sub_spaces_list: &[String]) -> String {
+    assert!(!sub_spaces_list.is_empty());
+
+    let formula = sub_spaces_list
+        .iter()
+        .map(|sub_space| mk_formula_not_attractor(sub_space))
+        .collect::<Vec<_>>()
+        .join(" & ");
+    format!("({formula})")
+}
+
 /// Create HCTL formula that prohibits existence of any attractor apart from the ones
 /// that contain some states from some of the specified sub-spaces (observations).
 ///
@@ -257,6 +368,50 @@ pub fn mk_formula_fixed_point_list(steady_state_list: &[String]) -> String {
     format!("({formula})")
 }
 
+/// Create HCTL formula describing that a sub-space (observation) is a steady state, i.e., no
+/// update function changes any of the specified values. There is one conjunct for each
+/// specified value (`literal`), requiring that the corresponding variable can not be flipped.
+///
+/// > `FORALL x. JUMP x. ({sub_space} => (~(EX ~({literal1})) & ... & ~(EX ~({literalN}))))`
+///
+/// Argument `sub_space` is a formula describing the sub-space of interest, and `literals` are
+/// its specified values. If there are no specified values, the formula is trivially true.
+pub fn mk_formula_steady_state(sub_space: &str, literals: &[String]) -> String {
+    assert!(!sub_space.is_empty());
+    if literals.is_empty() {
+        return "(true)".to_string();
+    }
+
+    let no_flips = literals
+        .iter()
+        .map(|literal| format!("~(EX ~({literal}))"))
+        .collect::<Vec<_>>()
+        .join(" & ");
+    format!("(V{{x}}: (@{{x}}: ({sub_space} => ({no_flips}))))")
+}
+
+/// Create HCTL formula describing that each sub-space (observation) in a list is a steady state.
+/// It is essentially a conjunction of "steady-state formulas" (see [mk_formula_steady_state]),
+/// with `literals_list` containing specified values of each sub-space.
+///
+/// > `STEADY_STATE({space1}) & ... & STEADY_STATE({spaceN})`
+pub fn mk_formula_steady_state_list(
+    sub_spaces_list: &[String],
+    literals_list: &[Vec<String>],
+) -> String {
+    assert!(!sub_spaces_list.is_empty());
+    assert_eq!(sub_spaces_list.len(), literals_list.len());
+
+    let formula = sub_spaces_list
+        .iter()
+        .zip(literals_list)
+        .map(|(sub_space, literals)| mk_formula_steady_state(sub_space, literals))
+        .collect::<Vec<_>>()
+        .join(" & ");
+
+    format!("({formula})")
+}
+
 /// Create HCTL formula that prohibits existence of any steady state apart from the ones
 /// that contained in the specified sub-spaces (observations).
 ///
@@ -308,6 +463,43 @@ pub fn mk_formula_reachability_pair(from_state: &str, to_state: &str, is_negativ
     format!("(3{{x}}: (@{{x}}: {from_state} & EF ({to_state})))")
 }
 
+/// Create HCTL formula describing that a sub-space (observation) can not be reached from any
+/// state outside of it (i.e., there is no path entering the sub-space).
+///
+/// > `! EXISTS x. JUMP x. (!{sub_space} & EF {sub_space})`
+///
+/// If the `initial_space` is given, only paths starting in it are considered.
+///
+/// > `! EXISTS x. JUMP x. ({initial_space} & !{sub_space} & EF {sub_space})`
+///
+/// Argument `sub_space` is a formula describing the sub-space of interest.
+pub fn mk_formula_unreachable(sub_space: &str, initial_space: Option<&str>) -> String {
+    assert!(!sub_space.is_empty());
+    if let Some(initial_space) = initial_space {
+        return format!("~(3{{x}}: (@{{x}}: {initial_space} & ~({sub_space}) & EF ({sub_space})))");
+    }
+    format!("~(3{{x}}: (@{{x}}: ~({sub_space}) & EF ({sub_space})))")
+}
+
+/// Create HCTL formula describing that none of the sub-spaces (observations) in a list can be
+/// reached from outside (optionally only from the `initial_space`). It is essentially a
+/// conjunction of "unreachability formulas" (see [mk_formula_unreachable]).
+///
+/// > `UNREACHABLE({space1}) & ... & UNREACHABLE({spaceN})`
+pub fn mk_formula_unreachable_list(
+    sub_spaces_list: &[String],
+    initial_space: Option<&str>,
+) -> String {
+    assert!(!sub_spaces_list.is_empty());
+
+    let formula = sub_spaces_list
+        .iter()
+        .map(|sub_space| mk_formula_unreachable(sub_space, initial_space))
+        .collect::<Vec<_>>()
+        .join(" & ");
+    format!("({formula})")
+}
+
 /// Create a formula describing the existence of path between any states of every two consecutive
 /// sub-spaces from the `states_sequence`, starting with the first one.
 ///
@@ -330,6 +522,84 @@ pub fn mk_formula_reachability_chain(states_sequence: &[String]) -> String {
     format!("(3{{x}}: (@{{x}}: {chain}))")
 }
 
+/// Compute metrics of an HCTL formula (see [EncodingMetrics]). The formula is only parsed
+/// syntactically, no symbolic structures are built. Literals are the atomic propositions
+/// (state variables, constants, and wild-card propositions are not counted).
+pub fn hctl_encoding_metrics(formula: &str) -> Result<EncodingMetrics, String> {
+    let formula = HctlFormula::try_from_str(formula)?;
+    let (num_operators, num_literals) = count_hctl_operators_and_literals(formula.tree());
+    let num_state_vars = formula.count_state_variables();
+    Ok(EncodingMetrics::new(
+        num_operators,
+        num_state_vars,
+        num_literals,
+    ))
+}
+
+/// Compute metrics of the encoding of a processed dynamic property (see [EncodingMetrics]).
+///
+/// HCTL properties are measured together with all their wild-card sub-properties. Properties
+/// that are evaluated by specialized algorithms (instead of model checking) are not encoded
+/// into HCTL, so only the literals of their (expanded) datasets are counted.
+pub fn dyn_prop_encoding_metrics(prop: &ProcessedDynProp) -> Result<EncodingMetrics, String> {
+    let metrics = match prop {
+        ProcessedDynProp::ProcessedHctlFormula(prop) => {
+            let mut metrics = hctl_encoding_metrics(&prop.formula)?;
+            for sub_prop in &prop.sub_properties {
+                metrics = metrics + dyn_prop_encoding_metrics(sub_prop)?;
+            }
+            metrics
+        }
+        ProcessedDynProp::ProcessedObservation(prop) => {
+            EncodingMetrics::new(0, 0, prop.obs.num_specified_values())
+        }
+        ProcessedDynProp::ProcessedTrapSpace(prop) => dataset_literals_metrics(&prop.dataset),
+        ProcessedDynProp::ProcessedFixedPoint(prop) => dataset_literals_metrics(&prop.dataset),
+        ProcessedDynProp::ProcessedSimpleTrajectory(prop) => {
+            dataset_literals_metrics(&prop.dataset)
+        }
+        ProcessedDynProp::ProcessedObsReachable(prop) => dataset_literals_metrics(&prop.dataset),
+        ProcessedDynProp::ProcessedReachableFromDataset(prop) => {
+            dataset_literals_metrics(&prop.initial_dataset)
+                + dataset_literals_metrics(&prop.target_dataset)
+        }
+        ProcessedDynProp::ProcessedAttrCount(_) => EncodingMetrics::default(),
+    };
+    Ok(metrics)
+}
+
+/// **(internal)** Metrics for a dataset that is not encoded into a formula, counting just the
+/// specified values of all its observations as literals.
+fn dataset_literals_metrics(dataset: &Dataset) -> EncodingMetrics {
+    let num_literals = dataset
+        .observations()
+        .iter()
+        .map(|obs| obs.num_specified_values())
+        .sum();
+    EncodingMetrics::new(0, 0, num_literals)
+}
+
+/// **(internal)** Recursively count the operators (including hybrid operators) and the atomic
+/// propositions in the HCTL syntax tree.
+fn count_hctl_operators_and_literals(tree: &HctlTreeNode) -> (usize, usize) {
+    match &tree.node_type {
+        NodeType::Terminal(Atomic::Prop(_)) => (0, 1),
+        NodeType::Terminal(_) => (0, 0),
+        NodeType::Unary(_, child) | NodeType::Hybrid(_, _, _, child) => {
+            let (num_operators, num_literals) = count_hctl_operators_and_literals(child);
+            (num_operators + 1, num_literals)
+        }
+        NodeType::Binary(_, left, right) => {
+            let (left_operators, left_literals) = count_hctl_operators_and_literals(left);
+            let (right_operators, right_literals) = count_hctl_operators_and_literals(right);
+            (
+                left_operators + right_operators + 1,
+                left_literals + right_literals,
+            )
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -397,6 +667,14 @@ mod tests {
             &mk_formula_exclusive_attractors(&attr_states),
             "(((3{x}: (@{x}: (a & b & ~c & (AG EF (a & b & ~c & {x}))))) & (3{x}: (@{x}: (a & b & c & (AG EF (a & b & c & {x})))))) & ~(3{x}: (@{x}: ~(AG EF ((a & b & ~c) | (a & b & c))))))",
         );
+        assert_eq!(
+            &mk_formula_not_attractor(&attr_states[0]),
+            "~(3{x}: (@{x}: (a & b & ~c & (AG EF (a & b & ~c & {x})))))",
+        );
+        assert_eq!(
+            &mk_formula_not_attractor_list(&attr_states),
+            "(~(3{x}: (@{x}: (a & b & ~c & (AG EF (a & b & ~c & {x}))))) & ~(3{x}: (@{x}: (a & b & c & (AG EF (a & b & c & {x}))))))",
+        );
     }
 
     #[test]
@@ -438,6 +716,26 @@ mod tests {
         );
     }
 
+    #[test]
+    /// Test generating formulae for explicit steady states (only specified values are fixed).
+    fn test_steady_state_encodings() {
+        let sub_spaces = vec!["a & ~b".to_string(), "c".to_string()];
+        let literals = vec![
+            vec!["a".to_string(), "~b".to_string()],
+            vec!["c".to_string()],
+        ];
+
+        assert_eq!(
+            &mk_formula_steady_state(&sub_spaces[0], &literals[0]),
+            "(V{x}: (@{x}: (a & ~b => (~(EX ~(a)) & ~(EX ~(~b))))))",
+        );
+        assert_eq!(&mk_formula_steady_state("true", &[]), "(true)");
+        assert_eq!(
+            &mk_formula_steady_state_list(&sub_spaces, &literals),
+            "((V{x}: (@{x}: (a & ~b => (~(EX ~(a)) & ~(EX ~(~b)))))) & (V{x}: (@{x}: (c => (~(EX ~(c)))))))",
+        );
+    }
+
     #[test]
     /// Test generating reachability formulae.
     fn test_reachability_encoding() {
@@ -459,5 +757,36 @@ mod tests {
             &mk_formula_reachability_chain(&states),
             "(3{x}: (@{x}: (a & b & ~c) & EF ((a & b & c) & EF (~a & b & c))))",
         );
+        assert_eq!(
+            &mk_formula_unreachable(&states[0], None),
+            "~(3{x}: (@{x}: ~(a & b & ~c) & EF (a & b & ~c)))",
+        );
+        assert_eq!(
+            &mk_formula_unreachable(&states[0], Some("(~a)")),
+            "~(3{x}: (@{x}: (~a) & ~(a & b & ~c) & EF (a & b & ~c)))",
+        );
+        assert_eq!(
+            &mk_formula_unreachable_list(&states[..2], None),
+            "(~(3{x}: (@{x}: ~(a & b & ~c) & EF (a & b & ~c))) & ~(3{x}: (@{x}: ~(a & b & c) & EF (a & b & c))))",
+        );
+    }
+
+    #[test]
+    /// Test computing metrics of encoded HCTL formulae.
+    fn test_hctl_encoding_metrics() {
+        let metrics = hctl_encoding_metrics("3{x}: @{x}: (a & ~b & AX {x})").unwrap();
+        assert_eq!(metrics, EncodingMetrics::new(6, 1, 2));
+
+        // each (fully specified) observation expands into one literal per variable
+        let states = vec!["a & b & ~c".to_string(), "a & b & c".to_string()];
+        let formula = mk_formula_attractor_list(&states);
+        let metrics = hctl_encoding_metrics(&formula).unwrap();
+        assert_eq!(metrics.num_literals, 12);
+        assert_eq!(metrics.num_state_vars, 1);
+
+        // wild-card propositions and constants are not literals
+        let metrics = hctl_encoding_metrics("%wild_card% & true").unwrap();
+        assert_eq!(metrics, EncodingMetrics::new(1, 0, 0));
+        assert!(hctl_encoding_metrics("a &").is_err());
     }
 }
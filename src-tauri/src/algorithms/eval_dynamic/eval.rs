@@ -1,13 +1,25 @@
 use std::collections::HashMap;
 
-use crate::algorithms::eval_dynamic::_attractors::sort_colors_by_attr_num;
+use crate::algorithms::eval_dynamic::_attractors::{
+    attractor_components, sort_colors_by_attr_num_parallel,
+};
 use crate::algorithms::eval_dynamic::_fixed_points::colors_where_fixed_points;
 use crate::algorithms::eval_dynamic::_trap_spaces::{
     colors_where_essential_traps, colors_where_minimal_traps,
 };
 use crate::algorithms::eval_dynamic::encode::encode_dataset_hctl_str;
-use crate::algorithms::eval_dynamic::prepare_graph::get_ts_extended_symbolic_graph;
-use crate::algorithms::eval_dynamic::processed_props::{DataEncodingType, ProcessedDynProp};
+use crate::algorithms::eval_dynamic::prepare_graph::{
+    get_ts_extended_symbolic_graph, prepare_graph_for_dynamic_hctl,
+};
+use crate::algorithms::eval_dynamic::processed_props::{
+    process_dyn_prop_single, DataEncodingType, ProcessedDynProp,
+};
+use crate::algorithms::eval_dynamic::utils::{dont_track_progress, never_interrupted};
+use crate::sketchbook::bn_utils::{assert_bn_fully_specified, bn_with_plain_regulations};
+use crate::sketchbook::ids::DynPropertyId;
+use crate::sketchbook::observations::Dataset;
+use crate::sketchbook::properties::DynProperty;
+use crate::sketchbook::Sketch;
 use biodivine_hctl_model_checker::model_checking::{
     _model_check_extended_formula_dirty, _model_check_formula_dirty,
 };
@@ -15,8 +27,12 @@ use biodivine_lib_param_bn::biodivine_std::traits::Set;
 use biodivine_lib_param_bn::symbolic_async_graph::{
     GraphColoredVertices, GraphColors, SymbolicAsyncGraph,
 };
+use biodivine_lib_param_bn::BooleanNetwork;
 
-use super::_trajectory::colors_with_trajectory;
+use super::_trajectory::{
+    colors_where_obs_reachable, colors_where_reachable_from_dataset, colors_with_trajectory,
+    score_colors_by_weighted_reachability,
+};
 use super::utils::transform_obs_to_vertex_set;
 
 /// Model check a property and get colors for which the property holds universally
@@ -44,11 +60,64 @@ fn model_check_colors_universal<F: FnMut(&GraphColoredVertices, &str)>(
     Ok(universal_colors)
 }
 
+/// Evaluate a single dynamic property on a concrete (fully specified) network `bn`, returning
+/// whether the property holds. The `sketch` is used to resolve datasets and observations
+/// referenced by the property.
+///
+/// Unlike the inference, which works with sets of colors, the graph here has a single color.
+/// The regulations of `bn` are not enforced (their monotonicity and essentiality is ignored).
+/// This can be useful to check the meaning of a property, or to verify a particular model.
+pub fn eval_dyn_prop_on_network(
+    dyn_prop: &DynProperty,
+    sketch: &Sketch,
+    bn: &BooleanNetwork,
+) -> Result<bool, String> {
+    assert_bn_fully_specified(bn)?;
+    let bn = bn_with_plain_regulations(bn);
+
+    let prop_id = DynPropertyId::new("property")?;
+    let processed_prop = process_dyn_prop_single(&prop_id, dyn_prop, sketch)?;
+    let processed_props = [processed_prop];
+    let graph = prepare_graph_for_dynamic_hctl(&bn, &processed_props, None)?;
+    let sat_colors = eval_dyn_prop(
+        &processed_props[0],
+        &graph,
+        1,
+        &mut dont_track_progress,
+        &never_interrupted,
+    )?;
+    Ok(!sat_colors.is_empty())
+}
+
+/// Compute attractors of a concrete (fully specified) network `bn`. Each attractor is returned as
+/// a set of states (with the single color of the network's graph), the states are ordered the
+/// same way as the variables of `bn`.
+///
+/// Same as with [eval_dyn_prop_on_network], the regulations of `bn` are not enforced.
+pub fn eval_attractors_on_network(
+    bn: &BooleanNetwork,
+) -> Result<Vec<GraphColoredVertices>, String> {
+    assert_bn_fully_specified(bn)?;
+    let bn = bn_with_plain_regulations(bn);
+    let graph = SymbolicAsyncGraph::new(&bn)?;
+    Ok(attractor_components(&graph, &mut dont_track_progress))
+}
+
 /// Evaluate given dynamic property given the symbolic transition graph.
+///
+/// Argument `attr_partitions` gives the number of color partitions processed in parallel
+/// when computing attractors (see [sort_colors_by_attr_num_parallel]). Use 1 to run the
+/// computation sequentially.
+///
+/// Argument `is_interrupted` is checked after every progress report made directly by this
+/// function (i.e., between the individual steps of the evaluation). Once it returns `true`,
+/// the evaluation stops with an error. Use [never_interrupted] to run the evaluation to the end.
 pub fn eval_dyn_prop<F: FnMut(&GraphColoredVertices, &str)>(
     dyn_prop: &ProcessedDynProp,
     graph: &SymbolicAsyncGraph,
+    attr_partitions: usize,
     progress_callback: &mut F,
+    is_interrupted: &dyn Fn() -> bool,
 ) -> Result<GraphColors, String> {
     // use this set for initial progress callbacks before the computation starts
     let initial = graph.empty_colored_vertices();
@@ -58,10 +127,12 @@ pub fn eval_dyn_prop<F: FnMut(&GraphColoredVertices, &str)>(
             // Each wild-card proposition gets a "context set" it will be evaluated as
             let mut context_sets: HashMap<String, GraphColoredVertices> = HashMap::new();
             for sub_prop in &prop.sub_properties {
-                progress_callback(
+                report_checkpoint(
+                    progress_callback,
                     initial,
                     &format!("Starting to evaluate sub-property {}.", sub_prop.id()),
-                );
+                    is_interrupted,
+                )?;
 
                 // There is special type of "Observation" template which can only be used
                 // as sub-property and has to be handled differently than the rest.
@@ -75,7 +146,13 @@ pub fn eval_dyn_prop<F: FnMut(&GraphColoredVertices, &str)>(
                 } else {
                     // Otherwise it is a normal kind of property and we can handle it in a standard way
                     // Evaluation with [eval_dyn_prop] gives universal sat colors, we just convert the types
-                    let sat_colors = eval_dyn_prop(sub_prop, graph, progress_callback)?;
+                    let sat_colors = eval_dyn_prop(
+                        sub_prop,
+                        graph,
+                        attr_partitions,
+                        progress_callback,
+                        is_interrupted,
+                    )?;
                     let colored_vertices =
                         GraphColoredVertices::new(sat_colors.into_bdd(), graph.symbolic_context());
                     context_sets.insert(sub_prop.id().to_string(), colored_vertices);
@@ -83,7 +160,12 @@ pub fn eval_dyn_prop<F: FnMut(&GraphColoredVertices, &str)>(
             }
 
             // use a version of HCTL model checking for extended formulae
-            progress_callback(initial, "Starting computation using HCTL model checker.");
+            report_checkpoint(
+                progress_callback,
+                initial,
+                "Starting computation using HCTL model checker.",
+                is_interrupted,
+            )?;
             model_check_colors_universal(graph, &prop.formula, context_sets, progress_callback)
         }
         ProcessedDynProp::ProcessedAttrCount(prop) => {
@@ -92,9 +174,14 @@ pub fn eval_dyn_prop<F: FnMut(&GraphColoredVertices, &str)>(
 
             // compute full attractors (on remaining colors) and get colors with correct n. of attrs
             let initial = graph.empty_colored_vertices();
-            progress_callback(initial, "Starting attractor computation.");
+            report_checkpoint(
+                progress_callback,
+                initial,
+                "Starting attractor computation.",
+                is_interrupted,
+            )?;
             let colors_per_num_attrs: Vec<GraphColors> =
-                sort_colors_by_attr_num(graph, progress_callback);
+                sort_colors_by_attr_num_parallel(graph, attr_partitions, progress_callback);
             let mut sat_colors = graph.mk_empty_colors();
             for (num_attrs, color_set) in colors_per_num_attrs.iter().enumerate() {
                 if num_attrs >= prop.minimal && num_attrs <= prop.maximal {
@@ -107,10 +194,12 @@ pub fn eval_dyn_prop<F: FnMut(&GraphColoredVertices, &str)>(
             // custom implementation (can definitely be made more efficient if needed)
 
             // get colors where all the observations are (general) trap spaces
-            progress_callback(
+            report_checkpoint(
+                progress_callback,
                 initial,
                 "Starting computing trap spaces using model checker.",
-            );
+                is_interrupted,
+            )?;
             let trap_space_formula =
                 encode_dataset_hctl_str(&prop.dataset, None, DataEncodingType::TrapSpace)?;
             let mut sat_colors = model_check_colors_universal(
@@ -134,10 +223,20 @@ pub fn eval_dyn_prop<F: FnMut(&GraphColoredVertices, &str)>(
 
                 // note that all minimal TSs are non-percolable
                 sat_colors = if prop.minimal {
-                    progress_callback(initial, "Starting minimal trap spaces computation.");
+                    report_checkpoint(
+                        progress_callback,
+                        initial,
+                        "Starting minimal trap spaces computation.",
+                        is_interrupted,
+                    )?;
                     colors_where_minimal_traps(observations, &var_names, &space_graph, &space_ctx)
                 } else {
-                    progress_callback(initial, "Starting essential trap spaces computation.");
+                    report_checkpoint(
+                        progress_callback,
+                        initial,
+                        "Starting essential trap spaces computation.",
+                        is_interrupted,
+                    )?;
                     colors_where_essential_traps(observations, &var_names, &space_graph, &space_ctx)
                 };
 
@@ -156,19 +255,197 @@ pub fn eval_dyn_prop<F: FnMut(&GraphColoredVertices, &str)>(
             let observations = prop.dataset.observations().clone();
             let var_names = prop.dataset.variable_names();
 
-            progress_callback(initial, "Starting to compute fixed points.");
+            report_checkpoint(
+                progress_callback,
+                initial,
+                "Starting to compute fixed points.",
+                is_interrupted,
+            )?;
             let sat_colors = colors_where_fixed_points(observations, &var_names, graph);
             Ok(sat_colors)
         }
         ProcessedDynProp::ProcessedSimpleTrajectory(prop) => {
-            progress_callback(
+            report_checkpoint(
+                progress_callback,
                 initial,
                 "Starting to compute trajectory using reachability-based algorithm.",
-            );
+                is_interrupted,
+            )?;
             colors_with_trajectory(&prop.dataset, graph, progress_callback)
         }
+        ProcessedDynProp::ProcessedObsReachable(prop) => {
+            report_checkpoint(
+                progress_callback,
+                initial,
+                "Starting to count reachable observations using reachability-based algorithm.",
+                is_interrupted,
+            )?;
+            colors_where_obs_reachable(
+                &prop.dataset,
+                prop.min_count,
+                &prop.initial_subspace,
+                graph,
+                progress_callback,
+            )
+        }
+        ProcessedDynProp::ProcessedReachableFromDataset(prop) => {
+            report_checkpoint(
+                progress_callback,
+                initial,
+                "Starting to evaluate reachability between datasets using reachability-based algorithm.",
+                is_interrupted,
+            )?;
+            colors_where_reachable_from_dataset(
+                &prop.initial_dataset,
+                &prop.target_dataset,
+                graph,
+                progress_callback,
+            )
+        }
         ProcessedDynProp::ProcessedObservation(..) => {
             unreachable!("Observation cant be evaluated as a top-level property.")
         }
     }
 }
+
+/// **(internal)** Report progress of the evaluation, and check whether the evaluation should be
+/// interrupted afterwards (see [eval_dyn_prop]). If so, return `Err`.
+fn report_checkpoint<F: FnMut(&GraphColoredVertices, &str)>(
+    progress_callback: &mut F,
+    colored_set: &GraphColoredVertices,
+    msg: &str,
+    is_interrupted: &dyn Fn() -> bool,
+) -> Result<(), String> {
+    progress_callback(colored_set, msg);
+    if is_interrupted() {
+        return Err("Evaluation was interrupted.".to_string());
+    }
+    Ok(())
+}
+
+/// Evaluate the weighted (fuzzy) reachability between two datasets. Instead of requiring that
+/// every observation of `target_dataset` is reachable from some observation of `initial_dataset`,
+/// each color gets a score given by the total weight of the reachable target observations (see
+/// [crate::sketchbook::observations::Observation::get_weight]). This is useful for noisy data.
+///
+/// Note that this mode does not prune any candidates, it only ranks them. The result is a
+/// partition of all colors of the `graph` into classes with the same score, sorted by
+/// decreasing score.
+pub fn eval_weighted_reachability<F: FnMut(&GraphColoredVertices, &str)>(
+    initial_dataset: &Dataset,
+    target_dataset: &Dataset,
+    graph: &SymbolicAsyncGraph,
+    progress_callback: &mut F,
+) -> Result<Vec<(f64, GraphColors)>, String> {
+    let initial = graph.empty_colored_vertices();
+    progress_callback(
+        initial,
+        "Starting to score candidates by weighted reachability between datasets.",
+    );
+    score_colors_by_weighted_reachability(initial_dataset, target_dataset, graph, progress_callback)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::eval_dynamic::eval::{
+        eval_dyn_prop_on_network, eval_weighted_reachability,
+    };
+    use crate::algorithms::eval_dynamic::utils::dont_track_progress;
+    use crate::sketchbook::ids::DatasetId;
+    use crate::sketchbook::observations::{Dataset, Observation};
+    use crate::sketchbook::properties::dynamic_props::AbsenceMode;
+    use crate::sketchbook::properties::DynProperty;
+    use crate::sketchbook::Sketch;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use std::collections::BTreeMap;
+
+    #[test]
+    /// Test evaluating dynamic properties on concrete networks.
+    fn test_eval_dyn_prop_on_network() {
+        let mut sketch = Sketch::from_aeon("A -> B\nB -| A").unwrap();
+        let obs = Observation::try_from_str("01", "o").unwrap();
+        let dataset = Dataset::new("d", vec![obs], vec!["A", "B"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+
+        // negative cycle without fixed points, and a network with a fixed point `01`
+        let bn_cycle = BooleanNetwork::try_from("A -> B\nB -| A\n$A: !B\n$B: A").unwrap();
+        let bn_fixed = BooleanNetwork::try_from("A -> B\nB -| A\n$A: !B\n$B: true").unwrap();
+
+        let generic = DynProperty::try_mk_generic("p", "3{x}: @{x}: AX {x}").unwrap();
+        assert!(!eval_dyn_prop_on_network(&generic, &sketch, &bn_cycle).unwrap());
+        assert!(eval_dyn_prop_on_network(&generic, &sketch, &bn_fixed).unwrap());
+
+        let dataset_id = DatasetId::new("d").unwrap();
+        let template = DynProperty::mk_fixed_point("p", Some(dataset_id), None);
+        assert!(!eval_dyn_prop_on_network(&template, &sketch, &bn_cycle).unwrap());
+        assert!(eval_dyn_prop_on_network(&template, &sketch, &bn_fixed).unwrap());
+
+        // the network must be fully specified
+        let bn_param = BooleanNetwork::try_from("A -> B\nB -| A\n$A: !B").unwrap();
+        assert!(eval_dyn_prop_on_network(&generic, &sketch, &bn_param).is_err());
+    }
+
+    #[test]
+    /// Test that absence of unreachable observations only considers paths from the initial
+    /// subspace of the sketch.
+    fn test_eval_unreachable_from_initial_subspace() {
+        let mut sketch = Sketch::from_aeon("A -> A\nA -> B").unwrap();
+        let obs = Observation::try_from_str("11", "o").unwrap();
+        let dataset = Dataset::new("d", vec![obs], vec!["A", "B"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+        let dataset_id = DatasetId::new("d").unwrap();
+        let prop = DynProperty::mk_observation_absent(
+            "p",
+            Some(dataset_id),
+            None,
+            AbsenceMode::Unreachable,
+        );
+
+        // `A` is constant, so `11` is reachable (from `10`) only if `A` is initially true
+        let bn = BooleanNetwork::try_from("A -> A\nA -> B\n$A: A\n$B: A").unwrap();
+        assert!(!eval_dyn_prop_on_network(&prop, &sketch, &bn).unwrap());
+        let subspace = BTreeMap::from([("A".to_string(), false)]);
+        sketch.set_initial_subspace_by_str(&subspace).unwrap();
+        assert!(eval_dyn_prop_on_network(&prop, &sketch, &bn).unwrap());
+        let subspace = BTreeMap::from([("A".to_string(), true)]);
+        sketch.set_initial_subspace_by_str(&subspace).unwrap();
+        assert!(!eval_dyn_prop_on_network(&prop, &sketch, &bn).unwrap());
+    }
+
+    #[test]
+    /// Test scoring colors by weighted reachability between datasets.
+    fn test_eval_weighted_reachability() {
+        // `A` is constant, and `B` is either `A` or `!A` (two colors)
+        let bn = BooleanNetwork::try_from("A -> A\nA -? B\n$A: A").unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+        let initial_obs = Observation::try_from_str("10", "i").unwrap();
+        let initial = Dataset::new("d1", vec![initial_obs], vec!["A", "B"]).unwrap();
+        let target_obs = vec![
+            Observation::try_from_str("11", "o1")
+                .unwrap()
+                .with_weight(2.)
+                .unwrap(),
+            Observation::try_from_str("00", "o2")
+                .unwrap()
+                .with_weight(0.5)
+                .unwrap(),
+            Observation::try_from_str("10", "o3").unwrap(),
+        ];
+        let target = Dataset::new("d2", target_obs, vec!["A", "B"]).unwrap();
+
+        // for `B = A`, both `11` and `10` are reachable, for `B = !A` only `10` (itself)
+        let ranking =
+            eval_weighted_reachability(&initial, &target, &graph, &mut dont_track_progress)
+                .unwrap();
+        let scores: Vec<f64> = ranking.iter().map(|(score, _)| *score).collect();
+        assert_eq!(scores, vec![3., 1.]);
+        assert!(ranking.iter().all(|(_, c)| c.approx_cardinality() == 1.));
+    }
+}
@@ -1,10 +1,11 @@
+use crate::algorithms::eval_dynamic::eval::eval_dyn_prop;
 use crate::algorithms::eval_dynamic::processed_props::ProcessedDynProp;
+use crate::sketchbook::properties::HctlFormula;
 
-use biodivine_hctl_model_checker::mc_utils::collect_unique_hctl_vars;
-use biodivine_hctl_model_checker::preprocessing::parser::parse_and_minimize_extended_formula;
 use biodivine_lib_bdd::Bdd;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
 use biodivine_lib_param_bn::symbolic_async_graph::{
-    GraphColoredVertices, SymbolicAsyncGraph, SymbolicContext,
+    GraphColoredVertices, GraphColors, SymbolicAsyncGraph, SymbolicContext,
 };
 use biodivine_lib_param_bn::trap_spaces::SymbolicSpaceContext;
 use biodivine_lib_param_bn::BooleanNetwork;
@@ -30,15 +31,12 @@ use std::collections::HashMap;
 /// checking contexts.
 pub fn prepare_graph_for_dynamic_hctl(
     bn: &BooleanNetwork,
-    dyn_props: &Vec<ProcessedDynProp>,
+    dyn_props: &[ProcessedDynProp],
     unit: Option<(&Bdd, &SymbolicContext)>,
 ) -> Result<SymbolicAsyncGraph, String> {
     let mut num_hctl_vars = 0;
-    // context needed to process formulas
-    let plain_context = SymbolicContext::new(bn).unwrap();
-
     for prop in dyn_props {
-        let num_vars_prop = count_num_hctl_vars_single(prop, &plain_context)?;
+        let num_vars_prop = count_num_hctl_vars_single(prop)?;
         num_hctl_vars = max(num_hctl_vars, num_vars_prop)
     }
 
@@ -48,21 +46,95 @@ pub fn prepare_graph_for_dynamic_hctl(
     get_hctl_extended_symbolic_graph(bn, num_hctl_vars as u16, unit)
 }
 
+/// A closure evaluating a single dynamic property on a shared symbolic graph.
+///
+/// The arguments are the graph to evaluate on, the number of attractor partitions, a progress
+/// callback, and an interruption check (see [eval_dyn_prop]). The graph must use the symbolic
+/// context of the graph prepared by [prepare_shared_dyn_evaluation], but its unit set can be
+/// further restricted (e.g., by results of previously evaluated properties).
+pub trait DynPropEvaluator:
+    Fn(
+    &SymbolicAsyncGraph,
+    usize,
+    &mut dyn FnMut(&GraphColoredVertices, &str),
+    &dyn Fn() -> bool,
+) -> Result<GraphColors, String>
+{
+}
+
+impl<F> DynPropEvaluator for F where
+    F: Fn(
+        &SymbolicAsyncGraph,
+        usize,
+        &mut dyn FnMut(&GraphColoredVertices, &str),
+        &dyn Fn() -> bool,
+    ) -> Result<GraphColors, String>
+{
+}
+
+/// Prepare evaluation of all the given dynamic properties using a single shared symbolic graph.
+///
+/// The graph is built only once, with the number of extra HCTL variables computed up front as
+/// the maximum across all properties (see [prepare_graph_for_dynamic_hctl]). Together with the
+/// graph, we return an evaluation closure for each property (paired with the property ID, in
+/// the order of `dyn_props`), all of which can be evaluated on the shared graph.
+pub fn prepare_shared_dyn_evaluation(
+    bn: &BooleanNetwork,
+    dyn_props: &[ProcessedDynProp],
+    unit: Option<(&Bdd, &SymbolicContext)>,
+) -> Result<(SymbolicAsyncGraph, Vec<(String, impl DynPropEvaluator)>), String> {
+    let graph = prepare_graph_for_dynamic_hctl(bn, dyn_props, unit)?;
+    let evaluators = dyn_props
+        .iter()
+        .map(|prop| {
+            let prop_id = prop.id().to_string();
+            let prop = prop.clone();
+            let evaluator = move |graph: &SymbolicAsyncGraph,
+                                  attr_partitions: usize,
+                                  mut callback: &mut dyn FnMut(&GraphColoredVertices, &str),
+                                  is_interrupted: &dyn Fn() -> bool| {
+                eval_dyn_prop(&prop, graph, attr_partitions, &mut callback, is_interrupted)
+            };
+            (prop_id, evaluator)
+        })
+        .collect();
+    Ok((graph, evaluators))
+}
+
+/// Prepare the set of initial vertices for reachability-based properties, given by the sketch's
+/// initial subspace (a partial assignment of variables, given by their names).
+///
+/// The set contains all states of the subspace (with all valid colours of the `graph`). If the
+/// subspace is empty, there is no restriction and the whole universe is returned.
+pub fn prepare_initial_vertices(
+    graph: &SymbolicAsyncGraph,
+    initial_subspace: &[(String, bool)],
+) -> Result<GraphColoredVertices, String> {
+    let context = graph.symbolic_context();
+    let mut initial_vertices = graph.mk_unit_colored_vertices();
+    for (var_name, value) in initial_subspace {
+        let var_id = context
+            .find_network_variable(var_name)
+            .ok_or(format!("Variable {var_name} is invalid."))?;
+        initial_vertices = initial_vertices.intersect(&graph.fix_network_variable(var_id, *value));
+    }
+    Ok(initial_vertices)
+}
+
 /// Count the number of HCTL variables needed for evaluation of a single property.
 /// If the property contains some sub-properties, compute recursively as maximum.
-pub fn count_num_hctl_vars_single(
-    prop: &ProcessedDynProp,
-    context: &SymbolicContext,
-) -> Result<usize, String> {
+///
+/// For HCTL formulas, this is the maximal nesting depth of quantifiers (see
+/// [HctlFormula::count_state_variables]).
+pub fn count_num_hctl_vars_single(prop: &ProcessedDynProp) -> Result<usize, String> {
     let num_hctl_vars = match &prop {
         ProcessedDynProp::ProcessedHctlFormula(p) => {
             // process the main formula
-            let tree = parse_and_minimize_extended_formula(context, &p.formula)?;
-            let mut num_vars = collect_unique_hctl_vars(tree.clone()).len();
+            let mut num_vars = HctlFormula::try_from_str(&p.formula)?.count_state_variables();
 
             // and also process all potential sub-properties (these will be handled individually too)
             for sub_prop in p.sub_properties.iter() {
-                let num_sub_prop_vars = count_num_hctl_vars_single(sub_prop, context)?;
+                let num_sub_prop_vars = count_num_hctl_vars_single(sub_prop)?;
                 num_vars = max(num_vars, num_sub_prop_vars);
             }
             num_vars
@@ -73,6 +145,10 @@ pub fn count_num_hctl_vars_single(
         ProcessedDynProp::ProcessedSimpleTrajectory(..) => 0,
         // no need for any additional variables for a fixed point property
         ProcessedDynProp::ProcessedFixedPoint(..) => 0,
+        // no need for any additional variables for a reachable observations property
+        ProcessedDynProp::ProcessedObsReachable(..) => 0,
+        // no need for any additional variables for a reachability between datasets
+        ProcessedDynProp::ProcessedReachableFromDataset(..) => 0,
         // this one is handled entirely later during evaluation
         ProcessedDynProp::ProcessedTrapSpace(..) => 0,
         // no need for any additional variables for an observation sub-property
@@ -140,8 +216,10 @@ mod tests {
     use biodivine_lib_param_bn::symbolic_async_graph::{SymbolicAsyncGraph, SymbolicContext};
     use biodivine_lib_param_bn::BooleanNetwork;
 
+    use crate::algorithms::eval_dynamic::eval::eval_dyn_prop;
     use crate::algorithms::eval_dynamic::prepare_graph::{
-        get_hctl_extended_symbolic_graph, prepare_graph_for_dynamic_hctl,
+        get_hctl_extended_symbolic_graph, prepare_graph_for_dynamic_hctl, prepare_initial_vertices,
+        prepare_shared_dyn_evaluation,
     };
     use crate::algorithms::eval_dynamic::processed_props::ProcessedDynProp;
 
@@ -180,4 +258,55 @@ mod tests {
         let graph_hctl = prepare_graph_for_dynamic_hctl(&bn, &property_list, None).unwrap();
         assert_eq!(graph_hctl_expected.unit_colors(), graph_hctl.unit_colors());
     }
+
+    #[test]
+    /// Test preparing a shared graph with evaluation closures for several properties.
+    fn test_prepare_shared_evaluation() {
+        let bn = BooleanNetwork::try_from("a -> a").unwrap();
+        let property_list = vec![
+            ProcessedDynProp::mk_hctl("p1", "3{x}: 3{y}: (@{x}: AX {x}) & (@{y}: AX {y})", vec![]),
+            ProcessedDynProp::mk_attr_count("p2", 2, 2),
+        ];
+        let (graph, evaluators) = prepare_shared_dyn_evaluation(&bn, &property_list, None).unwrap();
+        // two HCTL variables are needed (the maximum over all properties)
+        let expected_graph = get_hctl_extended_symbolic_graph(&bn, 2, None).unwrap();
+        assert_eq!(
+            graph.symbolic_context().bdd_variable_set().num_vars(),
+            expected_graph
+                .symbolic_context()
+                .bdd_variable_set()
+                .num_vars()
+        );
+
+        // both properties are evaluated on the same graph, and compared to direct evaluation
+        let ids: Vec<&str> = evaluators.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["p1", "p2"]);
+        for ((_, evaluator), prop) in evaluators.iter().zip(property_list.iter()) {
+            let shared_result = evaluator(&graph, 1, &mut |_, _| {}, &|| false).unwrap();
+            let direct_result = eval_dyn_prop(prop, &graph, 1, &mut |_, _| {}, &|| false).unwrap();
+            assert_eq!(shared_result, direct_result);
+            // interrupted evaluation ends with an error
+            assert!(evaluator(&graph, 1, &mut |_, _| {}, &|| true).is_err());
+        }
+    }
+
+    #[test]
+    /// Test preparing the initial vertex set from a (partial) initial subspace.
+    fn test_prepare_initial_vertices() {
+        let bn = BooleanNetwork::try_from("a -> a\nb -> b").unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+
+        // empty subspace gives the whole universe
+        let initial = prepare_initial_vertices(&graph, &[]).unwrap();
+        assert_eq!(&initial, graph.unit_colored_vertices());
+
+        // only states with `a` true and `b` false remain
+        let subspace = vec![("a".to_string(), true), ("b".to_string(), false)];
+        let initial = prepare_initial_vertices(&graph, &subspace).unwrap();
+        assert_eq!(initial.vertices().approx_cardinality(), 1.0);
+        assert_eq!(initial.colors(), graph.mk_unit_colors());
+
+        let invalid_subspace = vec![("c".to_string(), true)];
+        assert!(prepare_initial_vertices(&graph, &invalid_subspace).is_err());
+    }
 }
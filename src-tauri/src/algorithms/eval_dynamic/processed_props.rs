@@ -1,8 +1,10 @@
-use crate::algorithms::eval_dynamic::encode::encode_dataset_hctl_str;
+use crate::algorithms::eval_dynamic::encode::{
+    encode_dataset_hctl_str, encode_dataset_unreachable_hctl_str, encode_observation_str,
+};
 use crate::sketchbook::ids::DynPropertyId;
 use crate::sketchbook::observations::{Dataset, Observation};
 use crate::sketchbook::properties::dynamic_props::{
-    DynPropertyType, WildCardProposition, WildCardType,
+    AbsenceMode, DynPropertyType, WildCardProposition, WildCardType,
 };
 use crate::sketchbook::properties::DynProperty;
 use crate::sketchbook::Sketch;
@@ -13,8 +15,11 @@ use crate::sketchbook::Sketch;
 pub enum DataEncodingType {
     Attractor,
     FixedPoint,
+    SteadyState,
     TrapSpace,
     TimeSeries,
+    Unreachable,
+    NotAttractor,
 }
 
 /// Property requiring that a particular extended HCTL formula is satisfied.
@@ -83,6 +88,34 @@ pub struct ProcessedSimpleTrajectory {
     pub dataset: Dataset,
 }
 
+/// Property requiring that at least `min_count` observations of a particular dataset are
+/// reachable (there is a state from which each of them can be reached).
+///
+/// The initial state must lie in the `initial_subspace` (a partial assignment of variables,
+/// given by their names). If the subspace is empty, any state can be initial.
+///
+/// This is evaluated by counting the reachable observations symbolically, which is more efficient
+/// than encoding it as an HCTL disjunction over all subsets of observations of size `min_count`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProcessedObsReachable {
+    pub id: String,
+    pub dataset: Dataset,
+    pub min_count: usize,
+    pub initial_subspace: Vec<(String, bool)>,
+}
+
+/// Property requiring that every observation of the `target_dataset` is reachable from some
+/// observation of the `initial_dataset`.
+///
+/// Observations may be partially specified, in which case they correspond to sub-spaces. This is
+/// evaluated using saturated reachability, instead of encoding it as an HCTL formula.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProcessedReachableFromDataset {
+    pub id: String,
+    pub initial_dataset: Dataset,
+    pub target_dataset: Dataset,
+}
+
 /// Enum for processed variants of dynamic properties.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ProcessedDynProp {
@@ -91,6 +124,8 @@ pub enum ProcessedDynProp {
     ProcessedHctlFormula(ProcessedHctlFormula),
     ProcessedSimpleTrajectory(ProcessedSimpleTrajectory),
     ProcessedFixedPoint(ProcessedFixedPoint),
+    ProcessedObsReachable(ProcessedObsReachable),
+    ProcessedReachableFromDataset(ProcessedReachableFromDataset),
     /// This one is just for sub-properties.
     ProcessedObservation(ProcessedObservation),
 }
@@ -170,6 +205,47 @@ impl ProcessedDynProp {
         Ok(ProcessedDynProp::ProcessedSimpleTrajectory(property))
     }
 
+    /// Create `ProcessedDynProp` instance requiring that at least `min_count` observations
+    /// of the dataset are reachable.
+    ///
+    /// The count must not exceed the number of observations in the dataset. The initial state
+    /// is restricted to the `initial_subspace` (empty subspace means no restriction).
+    pub fn mk_obs_reachable(
+        id: &str,
+        dataset: Dataset,
+        min_count: usize,
+        initial_subspace: Vec<(String, bool)>,
+    ) -> Result<ProcessedDynProp, String> {
+        if min_count > dataset.num_observations() {
+            return Err(format!(
+                "Property {id} requires {min_count} reachable observations, but the dataset only has {}.",
+                dataset.num_observations()
+            ));
+        }
+        let property = ProcessedObsReachable {
+            id: id.to_string(),
+            dataset,
+            min_count,
+            initial_subspace,
+        };
+        Ok(ProcessedDynProp::ProcessedObsReachable(property))
+    }
+
+    /// Create `ProcessedDynProp` instance requiring that all observations of the `target_dataset`
+    /// are reachable from some observation of the `initial_dataset`.
+    pub fn mk_reachable_from_dataset(
+        id: &str,
+        initial_dataset: Dataset,
+        target_dataset: Dataset,
+    ) -> ProcessedDynProp {
+        let property = ProcessedReachableFromDataset {
+            id: id.to_string(),
+            initial_dataset,
+            target_dataset,
+        };
+        ProcessedDynProp::ProcessedReachableFromDataset(property)
+    }
+
     /// Create observation `ProcessedDynProp` instance.
     pub fn mk_obs(id: &str, obs: Observation, var_names: Vec<String>) -> ProcessedDynProp {
         let property = ProcessedObservation {
@@ -188,6 +264,8 @@ impl ProcessedDynProp {
             ProcessedDynProp::ProcessedTrapSpace(prop) => &prop.id,
             ProcessedDynProp::ProcessedSimpleTrajectory(prop) => &prop.id,
             ProcessedDynProp::ProcessedFixedPoint(prop) => &prop.id,
+            ProcessedDynProp::ProcessedObsReachable(prop) => &prop.id,
+            ProcessedDynProp::ProcessedReachableFromDataset(prop) => &prop.id,
             ProcessedDynProp::ProcessedObservation(prop) => &prop.id,
         }
     }
@@ -197,6 +275,11 @@ impl ProcessedDynProp {
 /// `ProcessedDynProp` variants. That usually means encoding them into HCTL, or doing
 /// some other preprocessing.
 pub fn process_dynamic_props(sketch: &Sketch) -> Result<Vec<ProcessedDynProp>, String> {
+    // fail early if any wild-card references a missing dataset/observation
+    sketch
+        .properties
+        .assert_wild_cards_resolvable(&sketch.observations)?;
+
     let mut dynamic_props = sketch.properties.dyn_props().collect::<Vec<_>>();
     // sort properties by IDs for deterministic computation times (and get rid of the IDs)
     dynamic_props.sort_by(|(a_id, _), (b_id, _)| a_id.cmp(b_id));
@@ -216,7 +299,7 @@ pub fn process_dynamic_props(sketch: &Sketch) -> Result<Vec<ProcessedDynProp>, S
 /// special cases. The HCTL encoding is the default, but can result in slower non-optimized
 /// evaluation. If there is an optimized evaluation algorithm (better than HCTL model checking),
 /// a different variant should be used.
-fn process_dyn_prop_single(
+pub(crate) fn process_dyn_prop_single(
     id: &DynPropertyId,
     dyn_prop: &DynProperty,
     sketch: &Sketch,
@@ -262,15 +345,42 @@ fn process_dyn_prop_single(
             ProcessedDynProp::mk_fixed_point(id.as_str(), dataset)
         }
         // encode attractors with HCTL formula
-        DynPropertyType::HasAttractor(prop) => {
+        DynPropertyType::HasAttractor(_) => {
             // TODO: if we have whole dataset, instead of using conjunction, try encoding as multiple properties
+            let formula = encode_dyn_prop_hctl_str(dyn_prop, sketch)?;
+            ProcessedDynProp::mk_hctl(id.as_str(), &formula, Vec::new()) // no wild-cards
+        }
+        // reachability of observations handled as a special case (counting them symbolically)
+        DynPropertyType::ObservationsReachable(prop) => {
             let dataset_id = prop.dataset.clone().unwrap();
-            let dataset = sketch.observations.get_dataset(&dataset_id)?;
-            let formula = encode_dataset_hctl_str(
+            let dataset = sketch.observations.get_dataset(&dataset_id)?.clone();
+            ProcessedDynProp::mk_obs_reachable(
+                id.as_str(),
                 dataset,
-                prop.observation.clone(),
-                DataEncodingType::Attractor,
-            )?;
+                prop.min_count,
+                get_initial_subspace_by_names(sketch),
+            )?
+        }
+        // reachability between two datasets handled as a special case (saturated reachability)
+        DynPropertyType::ReachableFromDataset(prop) => {
+            let initial_id = prop.initial_dataset.clone().unwrap();
+            let target_id = prop.target_dataset.clone().unwrap();
+            let initial_dataset = sketch.observations.get_dataset(&initial_id)?.clone();
+            let target_dataset = sketch.observations.get_dataset(&target_id)?.clone();
+            ProcessedDynProp::mk_reachable_from_dataset(
+                id.as_str(),
+                initial_dataset,
+                target_dataset,
+            )
+        }
+        // encode absence of observations with HCTL formula (complement of reachability/attractors)
+        DynPropertyType::ObservationAbsent(_) => {
+            let formula = encode_dyn_prop_hctl_str(dyn_prop, sketch)?;
+            ProcessedDynProp::mk_hctl(id.as_str(), &formula, Vec::new()) // no wild-cards
+        }
+        // encode steady states with HCTL formula
+        DynPropertyType::SteadyState(_) => {
+            let formula = encode_dyn_prop_hctl_str(dyn_prop, sketch)?;
             ProcessedDynProp::mk_hctl(id.as_str(), &formula, Vec::new()) // no wild-cards
         }
         // encode time series with HCTL formula
@@ -291,7 +401,7 @@ fn process_dyn_prop_single(
                 ProcessedDynProp::mk_simple_trajectory(id.as_str(), dataset.clone()).unwrap()
             } else {
                 // TODO: also optimize the computation for the base case to avoid pure model checking
-                let formula = encode_dataset_hctl_str(dataset, None, DataEncodingType::TimeSeries)?;
+                let formula = encode_dyn_prop_hctl_str(dyn_prop, sketch)?;
                 ProcessedDynProp::mk_hctl(id.as_str(), &formula, Vec::new())
                 // no wild-cards
             }
@@ -357,3 +467,201 @@ pub fn process_wild_cards(
 
     Ok(processed_props)
 }
+
+/// Encode a given dynamic property into an HCTL formula string, the same way as it is done by
+/// [process_dyn_prop_single] for properties evaluated via model checking. Properties evaluated
+/// by optimized algorithms (fixed points, simple trajectories, general trap spaces) are encoded
+/// using the equivalent HCTL templates. Wild-card propositions of generic properties are
+/// replaced by their HCTL encodings too.
+///
+/// Some properties can only be evaluated by specialized algorithms (attractor count, minimal or
+/// non-percolable trap spaces, reachability of observations or between datasets). These have no
+/// HCTL encoding, and an error is returned for them (or for formulas with such wild-cards).
+pub(crate) fn encode_dyn_prop_hctl_str(
+    dyn_prop: &DynProperty,
+    sketch: &Sketch,
+) -> Result<String, String> {
+    dyn_prop.assert_required_fields_filled()?;
+    let no_encoding_err = |description: &str| -> Result<String, String> {
+        Err(format!(
+            "Property `{}` ({description}) is evaluated by a specialized algorithm, and it has no HCTL encoding.",
+            dyn_prop.get_name()
+        ))
+    };
+
+    match dyn_prop.get_prop_data() {
+        DynPropertyType::GenericDynProp(prop) => {
+            let mut formula = prop.processed_formula.as_str().to_string();
+            for wild_card in &prop.wild_cards {
+                let encoded = encode_wild_card_hctl_str(wild_card, sketch)?;
+                let wild_card_str = format!("%{}%", wild_card.processed_string());
+                formula = formula.replace(&wild_card_str, &format!("({encoded})"));
+            }
+            Ok(formula)
+        }
+        DynPropertyType::AttractorCount(_) => no_encoding_err("attractor count"),
+        DynPropertyType::ObservationsReachable(_) => no_encoding_err("reachable observations"),
+        DynPropertyType::ReachableFromDataset(_) => no_encoding_err("reachability from dataset"),
+        DynPropertyType::ExistsTrapSpace(prop) if prop.minimal || prop.nonpercolable => {
+            no_encoding_err("minimal or non-percolable trap spaces")
+        }
+        DynPropertyType::ExistsTrapSpace(prop) => {
+            let dataset = sketch
+                .observations
+                .get_dataset(prop.dataset.as_ref().unwrap())?;
+            encode_dataset_hctl_str(
+                dataset,
+                prop.observation.clone(),
+                DataEncodingType::TrapSpace,
+            )
+        }
+        DynPropertyType::ExistsFixedPoint(prop) => {
+            let dataset = sketch
+                .observations
+                .get_dataset(prop.dataset.as_ref().unwrap())?;
+            encode_dataset_hctl_str(
+                dataset,
+                prop.observation.clone(),
+                DataEncodingType::FixedPoint,
+            )
+        }
+        DynPropertyType::HasAttractor(prop) => {
+            let dataset = sketch
+                .observations
+                .get_dataset(prop.dataset.as_ref().unwrap())?;
+            encode_dataset_hctl_str(
+                dataset,
+                prop.observation.clone(),
+                DataEncodingType::Attractor,
+            )
+        }
+        DynPropertyType::SteadyState(prop) => {
+            let dataset = sketch
+                .observations
+                .get_dataset(prop.dataset.as_ref().unwrap())?;
+            encode_dataset_hctl_str(
+                dataset,
+                prop.observation.clone(),
+                DataEncodingType::SteadyState,
+            )
+        }
+        DynPropertyType::ObservationAbsent(prop) => {
+            let dataset = sketch
+                .observations
+                .get_dataset(prop.dataset.as_ref().unwrap())?;
+            match prop.mode {
+                // only paths from the initial subspace are relevant (same as for reachability)
+                AbsenceMode::Unreachable => encode_dataset_unreachable_hctl_str(
+                    dataset,
+                    prop.observation.clone(),
+                    &get_initial_subspace_by_names(sketch),
+                ),
+                AbsenceMode::NotAttractor => encode_dataset_hctl_str(
+                    dataset,
+                    prop.observation.clone(),
+                    DataEncodingType::NotAttractor,
+                ),
+            }
+        }
+        DynPropertyType::ExistsTrajectory(prop) => {
+            let dataset = sketch
+                .observations
+                .get_dataset(prop.dataset.as_ref().unwrap())?;
+            encode_dataset_hctl_str(dataset, None, DataEncodingType::TimeSeries)
+        }
+    }
+}
+
+/// **(internal)** Get the initial subspace of the sketch, with variables given by their names.
+fn get_initial_subspace_by_names(sketch: &Sketch) -> Vec<(String, bool)> {
+    sketch
+        .get_initial_subspace()
+        .iter()
+        .map(|(var_id, value)| (var_id.to_string(), *value))
+        .collect()
+}
+
+/// Encode a wild-card proposition into an HCTL formula string, see [encode_dyn_prop_hctl_str].
+fn encode_wild_card_hctl_str(
+    wild_card_prop: &WildCardProposition,
+    sketch: &Sketch,
+) -> Result<String, String> {
+    let id = wild_card_prop.processed_string();
+    // lets create `DynProperty` instance with the same meaning as the wild-card (if needed)
+    let temp_prop = match wild_card_prop.get_prop_data() {
+        WildCardType::Observation(data_id, obs_id) => {
+            let dataset = sketch.observations.get_dataset(data_id)?;
+            let observation = dataset.get_obs(obs_id)?;
+            return encode_observation_str(observation, &dataset.variable_names());
+        }
+        WildCardType::Trajectory(data_id) => DynProperty::mk_trajectory(&id, Some(data_id.clone())),
+        WildCardType::Attractors(data_id, obs_id) => {
+            DynProperty::mk_has_attractor(&id, Some(data_id.clone()), obs_id.clone())
+        }
+        WildCardType::FixedPoints(data_id, obs_id) => {
+            DynProperty::mk_fixed_point(&id, Some(data_id.clone()), obs_id.clone())
+        }
+        WildCardType::TrapSpaces(data_id, obs_id, is_minimal, is_non_percolable) => {
+            DynProperty::mk_trap_space(
+                &id,
+                Some(data_id.clone()),
+                obs_id.clone(),
+                *is_minimal,
+                *is_non_percolable,
+            )
+        }
+        WildCardType::AttractorCount(minimal, maximal) => {
+            DynProperty::try_mk_attractor_count(&id, *minimal, *maximal)?
+        }
+    };
+    encode_dyn_prop_hctl_str(&temp_prop, sketch)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::eval_dynamic::encode::{
+        mk_formula_fixed_point_list, mk_formula_reachability_chain,
+    };
+    use crate::sketchbook::ids::DatasetId;
+    use crate::sketchbook::observations::{Dataset, Observation};
+    use crate::sketchbook::properties::{DynProperty, HctlFormula};
+    use crate::sketchbook::Sketch;
+
+    #[test]
+    /// Test expanding template dynamic properties (and wild-card propositions) into HCTL.
+    fn test_dyn_prop_to_hctl_formula() {
+        let mut sketch = Sketch::from_aeon("A -> B\nB -| A").unwrap();
+        let obs1 = Observation::try_from_str("10", "o1").unwrap();
+        let obs2 = Observation::try_from_str("1*", "o2").unwrap();
+        let dataset = Dataset::new("d", vec![obs1, obs2], vec!["A", "B"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+        let dataset_id = DatasetId::new("d").unwrap();
+        let encoded_obs = vec!["(A & ~B)".to_string(), "(A)".to_string()];
+        let fixed_points = mk_formula_fixed_point_list(&encoded_obs);
+        let trajectory = mk_formula_reachability_chain(&encoded_obs);
+
+        // template properties are encoded via HCTL templates
+        let prop = DynProperty::mk_fixed_point("p", Some(dataset_id.clone()), None);
+        let expected = HctlFormula::try_from_str(&fixed_points).unwrap();
+        assert_eq!(prop.to_hctl_formula(&sketch).unwrap(), expected);
+        let prop = DynProperty::mk_trajectory("p", Some(dataset_id));
+        let expected = HctlFormula::try_from_str(&trajectory).unwrap();
+        assert_eq!(prop.to_hctl_formula(&sketch).unwrap(), expected);
+
+        // wild-card propositions are expanded as well
+        let formula = "%fixed_points(d)% & EF %d, o1%";
+        let prop = DynProperty::try_mk_generic("p", formula).unwrap();
+        let expected_str = format!("({fixed_points}) & EF ((A & ~B))");
+        let expected = HctlFormula::try_from_str(&expected_str).unwrap();
+        assert_eq!(prop.to_hctl_formula(&sketch).unwrap(), expected);
+
+        // attractor count has no HCTL encoding (not even as a wild-card)
+        let prop = DynProperty::try_mk_attractor_count("p", 1, 2).unwrap();
+        assert!(prop.to_hctl_formula(&sketch).is_err());
+        let prop = DynProperty::try_mk_generic("p", "%attractor_count(1, 2)%").unwrap();
+        assert!(prop.to_hctl_formula(&sketch).is_err());
+    }
+}
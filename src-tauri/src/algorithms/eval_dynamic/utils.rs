@@ -1,4 +1,4 @@
-use crate::sketchbook::observations::{Observation, VarValue};
+use crate::sketchbook::observations::{Dataset, Observation, VarValue};
 use biodivine_lib_param_bn::biodivine_std::traits::Set;
 use biodivine_lib_param_bn::symbolic_async_graph::{GraphColoredVertices, SymbolicAsyncGraph};
 use biodivine_lib_param_bn::trap_spaces::{NetworkColoredSpaces, SymbolicSpaceContext};
@@ -8,6 +8,11 @@ use biodivine_lib_param_bn::Space;
 /// A default progress callback that does nothing.
 pub(crate) fn dont_track_progress(_progress: &GraphColoredVertices, _msg: &str) {}
 
+/// A default interruption check that never interrupts the computation.
+pub(crate) fn never_interrupted() -> bool {
+    false
+}
+
 /// Transform an `observation` into a singleton "NetworkColoredSpaces" instance,
 /// i.e., a singleton space with all the valid colors of the `graph`.
 ///
@@ -102,6 +107,22 @@ pub fn transform_obs_to_vertex_set(
     Ok(set)
 }
 
+/// Transform all observations of a `dataset` into a corresponding set of vertices of the STG,
+/// i.e., a union of sub-spaces given by the individual observations. The set is returned as
+/// `GraphColoredVertices` set, with all valid colours of the `graph`.
+pub fn transform_dataset_to_vertex_set(
+    dataset: &Dataset,
+    graph: &SymbolicAsyncGraph,
+) -> Result<GraphColoredVertices, String> {
+    let var_names = dataset.variable_names();
+    let mut set = graph.mk_empty_colored_vertices();
+    for obs in dataset.observations() {
+        let obs_set = transform_obs_to_vertex_set(obs, &var_names, graph)?;
+        set = set.union(&obs_set);
+    }
+    Ok(set)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::algorithms::eval_dynamic::utils::{
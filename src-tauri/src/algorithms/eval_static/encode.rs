@@ -1,6 +1,11 @@
+use crate::algorithms::fo_logic::fol_tree::{FolTreeNode, NodeType};
+use crate::algorithms::fo_logic::operator_enums::Atom;
 use crate::algorithms::fo_logic::utils::get_implicit_function_name;
+use crate::sketchbook::data_structs::EncodingMetrics;
 use crate::sketchbook::model::Essentiality;
 use crate::sketchbook::model::Monotonicity;
+use crate::sketchbook::observations::VarValue;
+use crate::sketchbook::Sketch;
 
 use biodivine_lib_param_bn::BooleanNetwork;
 
@@ -146,14 +151,114 @@ pub fn encode_essentiality(
     }
 }
 
+/// Create a FOL formula encoding that two arguments of an uninterpreted function (given by
+/// their indices) are not both essential.
+pub fn encode_mutually_exclusive_inputs(
+    number_inputs: usize,
+    index_1: usize,
+    index_2: usize,
+    fn_name: &str,
+) -> String {
+    assert_ne!(index_1, index_2);
+    let essential_1 = encode_essentiality(number_inputs, index_1, fn_name, Essentiality::True);
+    let essential_2 = encode_essentiality(number_inputs, index_2, fn_name, Essentiality::True);
+    format!("!({essential_1} & {essential_2})")
+}
+
 /// Create a FOL formula encoding that particular formula must hold if "context" formula holds.
 pub fn encode_property_in_context(context_formula: &str, property_formula: &str) -> String {
     format!("(({context_formula}) => ({property_formula}))")
 }
 
+/// Create a FOL formula encoding an observation macro `@dataset_id:obs_id`, i.e., a conjunction
+/// of literals given by the specified values of the observation (unspecified values are skipped).
+///
+/// The literals are FOL variables named after the corresponding model variables, so they
+/// must be quantified in the formula that references the observation.
+///
+/// Returns `Err` if the dataset or the observation do not exist, or if some of the dataset's
+/// variables is not a valid model variable.
+pub fn encode_observation_macro(
+    dataset_id: &str,
+    obs_id: &str,
+    sketch: &Sketch,
+) -> Result<String, String> {
+    let dataset_id = sketch.observations.get_dataset_id(dataset_id)?;
+    let dataset = sketch.observations.get_dataset(&dataset_id)?;
+    let obs_id = dataset.get_obs_id_by_str(obs_id)?;
+    let observation = dataset.get_obs(&obs_id)?;
+
+    if let Some(var_id) = dataset
+        .variables()
+        .iter()
+        .find(|var_id| !sketch.model.is_valid_var_id(var_id))
+    {
+        return Err(format!(
+            "Variable `{var_id}` of dataset `{dataset_id}` is not a variable of the model."
+        ));
+    }
+
+    let literals: Vec<String> = observation
+        .get_values()
+        .iter()
+        .zip(dataset.variables())
+        .filter_map(|(value, var_id)| match value {
+            VarValue::True => Some(var_id.to_string()),
+            VarValue::False => Some(format!("!{var_id}")),
+            VarValue::Any => None,
+        })
+        .collect();
+
+    if literals.is_empty() {
+        Ok("true".to_string())
+    } else {
+        Ok(format!("({})", literals.join(" & ")))
+    }
+}
+
+/// Compute metrics of a (parsed) FOL formula (see [EncodingMetrics]), without building any
+/// symbolic structures. Operators include quantifiers, and literals are occurrences of FOL
+/// variables. Observation macros are expected to be already expanded (otherwise, they are
+/// not counted).
+pub fn fol_encoding_metrics(tree: &FolTreeNode) -> EncodingMetrics {
+    let (num_operators, num_literals) = count_fol_operators_and_literals(tree);
+    let num_state_vars = tree.collect_quantified_fol_vars().len();
+    EncodingMetrics::new(num_operators, num_state_vars, num_literals)
+}
+
+/// **(internal)** Recursively count the operators (including quantifiers) and the variable
+/// occurrences in the FOL syntax tree. Function symbols are not counted as operators, but
+/// their arguments are traversed.
+fn count_fol_operators_and_literals(tree: &FolTreeNode) -> (usize, usize) {
+    match &tree.node_type {
+        NodeType::Terminal(Atom::Var(_)) => (0, 1),
+        NodeType::Terminal(_) => (0, 0),
+        NodeType::Unary(_, child) | NodeType::Quantifier(_, _, child) => {
+            let (num_operators, num_literals) = count_fol_operators_and_literals(child);
+            (num_operators + 1, num_literals)
+        }
+        NodeType::Binary(_, left, right) => {
+            let (left_operators, left_literals) = count_fol_operators_and_literals(left);
+            let (right_operators, right_literals) = count_fol_operators_and_literals(right);
+            (
+                left_operators + right_operators + 1,
+                left_literals + right_literals,
+            )
+        }
+        NodeType::Function(_, args) => args
+            .iter()
+            .map(|arg| count_fol_operators_and_literals(arg))
+            .fold((0, 0), |(ops, lits), (arg_ops, arg_lits)| {
+                (ops + arg_ops, lits + arg_lits)
+            }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algorithms::fo_logic::parser::parse_fol_formula;
+    use crate::sketchbook::observations::{Dataset, Observation};
 
     #[test]
     /// Test encoding of monotonicity for regulations.
@@ -264,6 +369,51 @@ mod tests {
         assert_eq!(&fol_formula, expected);
     }
 
+    #[test]
+    /// Test encoding of mutually exclusive uninterpreted fn inputs.
+    fn test_encoding_mutually_exclusive_inputs() {
+        // encode that first and third of three inputs of "f" are not both essential
+        let fol_formula = encode_mutually_exclusive_inputs(3, 0, 2, "f");
+        let expected = "!((\\exists x_1, x_2: f(0, x_1, x_2) ^ f(1, x_1, x_2)) & (\\exists x_0, x_1: f(x_0, x_1, 0) ^ f(x_0, x_1, 1)))";
+        assert_eq!(&fol_formula, expected);
+
+        // encode it for a function with just two inputs
+        let fol_formula = encode_mutually_exclusive_inputs(2, 1, 0, "g");
+        let expected =
+            "!((\\exists x_0: g(x_0, 0) ^ g(x_0, 1)) & (\\exists x_1: g(0, x_1) ^ g(1, x_1)))";
+        assert_eq!(&fol_formula, expected);
+    }
+
+    #[test]
+    /// Test encoding of observation macros (both valid and invalid references).
+    fn test_encoding_observation_macro() {
+        let mut sketch = Sketch::from_aeon("A -> B\nB -| C\nC -> A").unwrap();
+        let obs1 = Observation::try_from_str("1*0", "o1").unwrap();
+        let obs2 = Observation::try_from_str("***", "o2").unwrap();
+        let dataset = Dataset::new("d", vec![obs1, obs2], vec!["A", "B", "C"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+
+        let formula = encode_observation_macro("d", "o1", &sketch).unwrap();
+        assert_eq!(&formula, "(A & !C)");
+        let formula = encode_observation_macro("d", "o2", &sketch).unwrap();
+        assert_eq!(&formula, "true");
+
+        assert!(encode_observation_macro("d", "o3", &sketch).is_err());
+        assert!(encode_observation_macro("e", "o1", &sketch).is_err());
+
+        // dataset with a variable that is not in the model
+        let obs = Observation::try_from_str("11", "o1").unwrap();
+        let dataset = Dataset::new("e", vec![obs], vec!["A", "X"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("e", dataset)
+            .unwrap();
+        assert!(encode_observation_macro("e", "o1", &sketch).is_err());
+    }
+
     #[test]
     /// Test encoding a property formula that must be true in a context given by another formula.
     fn test_encode_in_context() {
@@ -274,4 +424,15 @@ mod tests {
         let expected = "((true) => (!(g(0) ^ g(1))))";
         assert_eq!(&fol_formula, expected);
     }
+
+    #[test]
+    /// Test computing metrics of FOL formulae.
+    fn test_fol_encoding_metrics() {
+        let tree = parse_fol_formula("\\exists x, y: (f(x, y) & !y)").unwrap();
+        let metrics = fol_encoding_metrics(&tree);
+        assert_eq!(metrics, EncodingMetrics::new(4, 2, 3));
+
+        let tree = parse_fol_formula("true | false").unwrap();
+        assert_eq!(fol_encoding_metrics(&tree), EncodingMetrics::new(1, 0, 0));
+    }
 }
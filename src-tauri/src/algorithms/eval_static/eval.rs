@@ -1,7 +1,19 @@
-use crate::algorithms::eval_static::processed_props::ProcessedStatProp;
+use crate::algorithms::eval_static::prepare_graph::prepare_graph_for_static_fol;
+use crate::algorithms::eval_static::processed_props::{
+    process_static_prop_single, ProcessedStatProp,
+};
 use crate::algorithms::fo_logic::eval_wrappers::eval_formula_dirty;
+use crate::algorithms::fo_logic::fol_tree::FolTreeNode;
+use crate::algorithms::fo_logic::utils::get_implicit_function_name;
+use crate::sketchbook::bn_utils::{assert_bn_fully_specified, bn_with_plain_regulations};
+use crate::sketchbook::ids::StatPropertyId;
+use crate::sketchbook::properties::StatProperty;
+use crate::sketchbook::Sketch;
 use biodivine_lib_param_bn::biodivine_std::traits::Set;
 use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+use biodivine_lib_param_bn::BooleanNetwork;
+use rayon::prelude::*;
+use std::collections::HashMap;
 
 /// Evaluate given static property.
 ///
@@ -19,3 +31,193 @@ pub fn eval_static_prop(
     let results = eval_formula_dirty(&formula, graph, base_var_name)?;
     Ok(results.colors().intersect(&initial_unit_colors))
 }
+
+/// Evaluate all given static properties one by one, and return the intersection of their
+/// results (colors satisfying all properties).
+///
+/// This is the sequential counterpart of [eval_static_props_parallel], useful for debugging
+/// and as a reference when testing the parallel version.
+pub fn eval_static_props(
+    static_props: &[ProcessedStatProp],
+    graph: &SymbolicAsyncGraph,
+    base_var_name: &str,
+) -> Result<GraphColors, String> {
+    let mut sat_colors = graph.mk_unit_colors();
+    for static_prop in static_props {
+        let prop_colors = eval_static_prop(static_prop, graph, base_var_name)?;
+        sat_colors = sat_colors.intersect(&prop_colors);
+    }
+    Ok(sat_colors)
+}
+
+/// Evaluate all given static properties in parallel (using `rayon`), and return the
+/// intersection of their results (colors satisfying all properties).
+///
+/// See [eval_each_static_prop_parallel] for details on the parallel evaluation.
+pub fn eval_static_props_parallel(
+    static_props: &[ProcessedStatProp],
+    graph: &SymbolicAsyncGraph,
+    base_var_name: &str,
+) -> Result<GraphColors, String> {
+    let results = eval_each_static_prop_parallel(static_props, graph, base_var_name)?;
+    Ok(results
+        .iter()
+        .fold(graph.mk_unit_colors(), |acc, colors| acc.intersect(colors)))
+}
+
+/// Evaluate all given static properties in parallel (using `rayon`), and return the results
+/// of the individual properties (in the order of `static_props`).
+///
+/// Each worker thread evaluates the properties on its own clone of the `graph`. The symbolic
+/// structures of [biodivine_lib_param_bn] do not share any global BDD manager (each BDD owns
+/// its nodes), so the clones are fully independent and no additional synchronization is needed.
+pub fn eval_each_static_prop_parallel(
+    static_props: &[ProcessedStatProp],
+    graph: &SymbolicAsyncGraph,
+    base_var_name: &str,
+) -> Result<Vec<GraphColors>, String> {
+    static_props
+        .par_iter()
+        .map_init(
+            || graph.clone(),
+            |graph_clone, static_prop| eval_static_prop(static_prop, graph_clone, base_var_name),
+        )
+        .collect::<Result<_, String>>()
+}
+
+/// Evaluate a single static property on a concrete (fully specified) network `bn`, returning
+/// whether the property holds. The `sketch` is used to resolve observations and expressions of
+/// uninterpreted functions referenced by the property.
+///
+/// Unlike the inference, which works with sets of colors, the graph here has a single color.
+/// The regulations of `bn` are not enforced (their monotonicity and essentiality is ignored),
+/// but regulation properties may only reference regulations present in `bn`.
+pub fn eval_static_prop_on_network(
+    stat_prop: &StatProperty,
+    sketch: &Sketch,
+    bn: &BooleanNetwork,
+) -> Result<bool, String> {
+    assert_bn_fully_specified(bn)?;
+    let bn = bn_with_plain_regulations(bn);
+    let base_var = bn
+        .variables()
+        .next()
+        .ok_or("Network is empty.".to_string())?;
+    let base_var_name = bn.get_variable_name(base_var).clone();
+
+    // regulation properties are encoded using the regulators of the target in the network
+    if let Ok((Some(regulator), Some(target))) = stat_prop.clone().get_regulator_and_target() {
+        let graph = bn.as_graph();
+        let regulation = graph
+            .find_variable(regulator.as_str())
+            .zip(graph.find_variable(target.as_str()))
+            .and_then(|(r, t)| graph.find_regulation(r, t));
+        if regulation.is_none() {
+            return Err(format!(
+                "Regulation `{regulator}` -> `{target}` is not present in the network."
+            ));
+        }
+    }
+
+    // collect expressions of uninterpreted fns, and all the implicit update fn symbols
+    let mut fn_expressions: HashMap<String, Option<FolTreeNode>> = sketch
+        .model
+        .propagate_expressions_through_uninterpreted_fns()?
+        .into_iter()
+        .map(|(fn_id, expression)| (fn_id.to_string(), expression.map(FolTreeNode::from_fn_tree)))
+        .collect();
+    for var in bn.variables() {
+        let fn_name = get_implicit_function_name(bn.get_variable_name(var));
+        fn_expressions.insert(fn_name, None);
+    }
+
+    let prop_id = StatPropertyId::new("property")?;
+    let processed_prop = process_static_prop_single(
+        &prop_id,
+        stat_prop,
+        sketch,
+        &bn,
+        &fn_expressions,
+        &base_var_name,
+    )?;
+    let processed_props = vec![processed_prop];
+    let graph = prepare_graph_for_static_fol(&bn, &processed_props, &base_var_name, None)?;
+    let sat_colors = eval_static_prop(&processed_props[0], &graph, &base_var_name)?;
+    Ok(!sat_colors.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::eval_static::eval::{
+        eval_static_prop_on_network, eval_static_props, eval_static_props_parallel,
+    };
+    use crate::algorithms::eval_static::prepare_graph::prepare_graph_for_static_fol;
+    use crate::algorithms::eval_static::processed_props::process_static_props;
+    use crate::inference::inference_solver::InferenceSolver;
+    use crate::sketchbook::model::Monotonicity;
+    use crate::sketchbook::properties::StatProperty;
+    use crate::sketchbook::Sketch;
+    use biodivine_lib_param_bn::biodivine_std::traits::Set;
+    use biodivine_lib_param_bn::BooleanNetwork;
+
+    #[test]
+    /// Test evaluating static properties on concrete networks.
+    fn test_eval_static_prop_on_network() {
+        let sketch = Sketch::from_aeon("A -> B\nB -| A").unwrap();
+        let bn_pos = BooleanNetwork::try_from("A -> B\nB -| A\n$A: !B\n$B: A").unwrap();
+        let bn_neg = BooleanNetwork::try_from("A -> B\nB -| A\n$A: !B\n$B: !A").unwrap();
+
+        // generic FOL property referencing an update function
+        let generic = StatProperty::try_mk_generic("p", "f_B(1) & !f_B(0)").unwrap();
+        assert!(eval_static_prop_on_network(&generic, &sketch, &bn_pos).unwrap());
+        assert!(!eval_static_prop_on_network(&generic, &sketch, &bn_neg).unwrap());
+
+        // regulation template property (regulations of the network itself are not enforced)
+        let var_a = sketch.model.get_var_id("A").unwrap();
+        let var_b = sketch.model.get_var_id("B").unwrap();
+        let template = StatProperty::mk_regulation_monotonic(
+            "p",
+            Some(var_a.clone()),
+            Some(var_b.clone()),
+            Monotonicity::Activation,
+        );
+        assert!(eval_static_prop_on_network(&template, &sketch, &bn_pos).unwrap());
+        assert!(!eval_static_prop_on_network(&template, &sketch, &bn_neg).unwrap());
+
+        // regulations missing in the network and partially specified networks are rejected
+        let bn_no_reg = BooleanNetwork::try_from("B -| A\n$A: !B\n$B: true").unwrap();
+        assert!(eval_static_prop_on_network(&template, &sketch, &bn_no_reg).is_err());
+        let bn_param = BooleanNetwork::try_from("A -> B\nB -| A\n$A: !B").unwrap();
+        assert!(eval_static_prop_on_network(&generic, &sketch, &bn_param).is_err());
+    }
+
+    #[test]
+    /// Test that evaluating static properties in parallel gives the same results as the
+    /// sequential evaluation.
+    fn test_eval_static_props_parallel() {
+        let mut sketch = Sketch::from_aeon("A -> A\nA -> B\nB -| A\nB -? B").unwrap();
+        let stat_props = [
+            ("p1", "f_B(1, 0) | !f_A(1, 1)"),
+            ("p2", "\\exists x: f_B(x, 1)"),
+            ("p3", "f_A(0, 0) => f_B(0, 0)"),
+        ];
+        for (id, formula) in stat_props {
+            let stat_prop = StatProperty::try_mk_generic("", formula).unwrap();
+            sketch.properties.add_static_by_str(id, stat_prop).unwrap();
+        }
+
+        let (bn, fn_expressions) = InferenceSolver::extract_bn(&sketch).unwrap();
+        let base_var = bn.variables().next().unwrap();
+        let base_var_name = bn.get_variable_name(base_var).clone();
+        let processed_props =
+            process_static_props(&sketch, &bn, fn_expressions, &base_var_name).unwrap();
+        let graph =
+            prepare_graph_for_static_fol(&bn, &processed_props, &base_var_name, None).unwrap();
+
+        let sequential = eval_static_props(&processed_props, &graph, &base_var_name).unwrap();
+        let parallel =
+            eval_static_props_parallel(&processed_props, &graph, &base_var_name).unwrap();
+        assert!(!sequential.is_empty());
+        assert_eq!(sequential.as_bdd(), parallel.as_bdd());
+    }
+}
@@ -1,8 +1,10 @@
 use crate::algorithms::eval_static::encode::*;
 use crate::algorithms::fo_logic::fol_tree::FolTreeNode;
-use crate::algorithms::fo_logic::parser::parse_and_minimize_fol_formula;
+use crate::algorithms::fo_logic::parser::{parse_and_minimize_fol_formula, parse_fol_formula};
+use crate::sketchbook::ids::StatPropertyId;
 use crate::sketchbook::model::FnTree;
 use crate::sketchbook::properties::static_props::StatPropertyType;
+use crate::sketchbook::properties::StatProperty;
 use crate::sketchbook::Sketch;
 
 use biodivine_lib_param_bn::BooleanNetwork;
@@ -67,110 +69,231 @@ pub fn process_static_props(
 
     let mut processed_props = Vec::new();
     for (id, stat_prop) in static_props {
-        // Everything is currently encoded into first-order logic formula
-        let parsed_fol_expression = match stat_prop.get_prop_data() {
-            StatPropertyType::GenericStatProp(prop) => {
-                // Take the formula and fully process it (may not have been fully processed before)
-                let formula = prop.processed_formula.as_str();
-                let parsed_tree = parse_and_minimize_fol_formula(formula, base_var_name)?;
-                // Replace pruned fn symbols in the formula with their expressions
-                parsed_tree.substitute_fns_with_expressions(&fn_expressions)
-            }
-            StatPropertyType::RegulationEssential(prop)
-            | StatPropertyType::RegulationEssentialContext(prop) => {
-                let input_name = prop.input.clone().unwrap();
-                let target_name = prop.target.clone().unwrap();
-                // Encode the regulation property into FOL formula
-                let mut formula = encode_regulation_essentiality(
-                    input_name.as_str(),
-                    target_name.as_str(),
-                    prop.clone().value,
-                    bn,
-                );
-                // Add context formula if provided
-                if let Some(context_formula) = &prop.context {
-                    formula = encode_property_in_context(context_formula, &formula);
-                }
-                // TODO: maybe we need to run the substitution here because of the context formula as well?
-                parse_and_minimize_fol_formula(&formula, base_var_name)?
-            }
-            StatPropertyType::RegulationMonotonic(prop)
-            | StatPropertyType::RegulationMonotonicContext(prop) => {
-                let input_name = prop.input.clone().unwrap();
-                let target_name = prop.target.clone().unwrap();
-                // Encode the regulation property into FOL formula
-                let mut formula = encode_regulation_monotonicity(
-                    input_name.as_str(),
-                    target_name.as_str(),
-                    prop.clone().value,
-                    bn,
-                );
-                // Add context formula if provided
-                if let Some(context_formula) = &prop.context {
-                    formula = encode_property_in_context(context_formula, &formula);
-                }
-                // TODO: maybe we need to run the substitution here because of the context formula as well?
-                parse_and_minimize_fol_formula(&formula, base_var_name)?
-            }
-            StatPropertyType::FnInputEssential(prop)
-            | StatPropertyType::FnInputEssentialContext(prop) => {
-                let fn_id = prop.target.clone().unwrap();
-                // Encode the function property into FOL formula. If the function symbol
-                // has specified expression for substitution, replace it after.
-                let input_idx = prop.input_index.unwrap();
-                let number_inputs = sketch.model.get_uninterpreted_fn_arity(&fn_id)?;
-                let mut formula = encode_essentiality(
-                    number_inputs,
-                    input_idx,
-                    fn_id.as_str(),
-                    prop.clone().value,
-                );
-                // Add context formula if provided
-                if let Some(context_formula) = &prop.context {
-                    formula = encode_property_in_context(context_formula, &formula);
-                }
-                let parsed_tree = parse_and_minimize_fol_formula(&formula, base_var_name)?;
-                // Replace pruned fn symbols in the formula with their expressions
-                parsed_tree.substitute_fns_with_expressions(&fn_expressions)
-            }
-            StatPropertyType::FnInputMonotonic(prop)
-            | StatPropertyType::FnInputMonotonicContext(prop) => {
-                let fn_id = prop.target.clone().unwrap();
-                // Encode the function property into FOL formula. If the function symbol
-                // has specified expression for substitution, replace it after.
-                let input_idx = prop.input_index.unwrap();
-                let number_inputs = sketch.model.get_uninterpreted_fn_arity(&fn_id)?;
-                let mut formula = encode_monotonicity(
-                    number_inputs,
-                    input_idx,
-                    fn_id.as_str(),
-                    prop.clone().value,
-                );
-                // Add context formula if provided
-                if let Some(context_formula) = &prop.context {
-                    formula = encode_property_in_context(context_formula, &formula);
-                }
-                let parsed_tree = parse_and_minimize_fol_formula(&formula, base_var_name)?;
-                // Replace pruned fn symbols in the formula with their expressions
-                parsed_tree.substitute_fns_with_expressions(&fn_expressions)
-            }
-        };
-        let new_prop = ProcessedStatProp::mk_fol(id.as_str(), parsed_fol_expression);
+        let new_prop =
+            process_static_prop_single(id, stat_prop, sketch, bn, &fn_expressions, base_var_name)?;
         processed_props.push(new_prop);
     }
 
     Ok(processed_props)
 }
 
+/// Process a single static property into its FOL-encoded version, see [process_static_props]
+/// for details. Here, the `fn_expressions` are already converted into FOL trees.
+pub(crate) fn process_static_prop_single(
+    id: &StatPropertyId,
+    stat_prop: &StatProperty,
+    sketch: &Sketch,
+    bn: &BooleanNetwork,
+    fn_expressions: &HashMap<String, Option<FolTreeNode>>,
+    base_var_name: &str,
+) -> Result<ProcessedStatProp, String> {
+    // Everything is currently encoded into first-order logic formula
+    let formula = encode_static_prop_str(stat_prop, sketch, bn)?;
+    let parsed_fol_expression = match stat_prop.get_prop_data() {
+        StatPropertyType::GenericStatProp(_) => {
+            // Take the formula and fully process it (may not have been fully processed before),
+            // including the expansion of observation macros
+            let parsed_tree = parse_and_expand_fol_formula(&formula, sketch, base_var_name)?;
+            // Replace pruned fn symbols in the formula with their expressions
+            parsed_tree.substitute_fns_with_expressions(fn_expressions)
+        }
+        StatPropertyType::RegulationEssential(_)
+        | StatPropertyType::RegulationEssentialContext(_) => {
+            // TODO: maybe we need to run the substitution here because of the context formula as well?
+            parse_and_minimize_fol_formula(&formula, base_var_name)?
+        }
+        StatPropertyType::RegulationMonotonic(_)
+        | StatPropertyType::RegulationMonotonicContext(_) => {
+            // TODO: maybe we need to run the substitution here because of the context formula as well?
+            parse_and_minimize_fol_formula(&formula, base_var_name)?
+        }
+        StatPropertyType::FnInputEssential(_)
+        | StatPropertyType::FnInputEssentialContext(_)
+        | StatPropertyType::FnInputMonotonic(_)
+        | StatPropertyType::FnInputMonotonicContext(_)
+        | StatPropertyType::FnInputsMutuallyExclusive(_) => {
+            let parsed_tree = parse_and_minimize_fol_formula(&formula, base_var_name)?;
+            // Replace pruned fn symbols in the formula with their expressions
+            parsed_tree.substitute_fns_with_expressions(fn_expressions)
+        }
+    };
+    // Substituted expressions (or constant arguments of templates) often make some sub-formulas
+    // trivial, so we simplify the formula before it is encoded symbolically
+    let simplified_expression = parsed_fol_expression.simplify_constants();
+    Ok(ProcessedStatProp::mk_fol(
+        id.as_str(),
+        simplified_expression,
+    ))
+}
+
+/// Encode a single static property into a FOL formula string (including its context formula,
+/// if there is one). For generic properties, the processed formula is returned as it is, with
+/// observation macros not yet expanded. Function symbols are not substituted with expressions.
+///
+/// The `bn` is used to determine the order of regulators of each variable (i.e., arguments of
+/// the implicit update functions). The property must be fully filled, and an error is returned
+/// if it references an invalid regulation or function argument.
+fn encode_static_prop_str(
+    stat_prop: &StatProperty,
+    sketch: &Sketch,
+    bn: &BooleanNetwork,
+) -> Result<String, String> {
+    let formula = match stat_prop.get_prop_data() {
+        StatPropertyType::GenericStatProp(prop) => prop.processed_formula.as_str().to_string(),
+        StatPropertyType::RegulationEssential(prop)
+        | StatPropertyType::RegulationEssentialContext(prop) => {
+            let input_name = prop.input.clone().unwrap();
+            let target_name = prop.target.clone().unwrap();
+            assert_regulation_in_bn(input_name.as_str(), target_name.as_str(), bn)?;
+            // Encode the regulation property into FOL formula
+            let formula = encode_regulation_essentiality(
+                input_name.as_str(),
+                target_name.as_str(),
+                prop.clone().value,
+                bn,
+            );
+            add_context(&prop.context, formula)
+        }
+        StatPropertyType::RegulationMonotonic(prop)
+        | StatPropertyType::RegulationMonotonicContext(prop) => {
+            let input_name = prop.input.clone().unwrap();
+            let target_name = prop.target.clone().unwrap();
+            assert_regulation_in_bn(input_name.as_str(), target_name.as_str(), bn)?;
+            // Encode the regulation property into FOL formula
+            let formula = encode_regulation_monotonicity(
+                input_name.as_str(),
+                target_name.as_str(),
+                prop.clone().value,
+                bn,
+            );
+            add_context(&prop.context, formula)
+        }
+        StatPropertyType::FnInputEssential(prop)
+        | StatPropertyType::FnInputEssentialContext(prop) => {
+            let fn_id = prop.target.clone().unwrap();
+            let input_idx = prop.input_index.unwrap();
+            let number_inputs = sketch.model.get_uninterpreted_fn_arity(&fn_id)?;
+            assert_fn_index_valid(input_idx, number_inputs, fn_id.as_str())?;
+            let formula =
+                encode_essentiality(number_inputs, input_idx, fn_id.as_str(), prop.clone().value);
+            add_context(&prop.context, formula)
+        }
+        StatPropertyType::FnInputMonotonic(prop)
+        | StatPropertyType::FnInputMonotonicContext(prop) => {
+            let fn_id = prop.target.clone().unwrap();
+            let input_idx = prop.input_index.unwrap();
+            let number_inputs = sketch.model.get_uninterpreted_fn_arity(&fn_id)?;
+            assert_fn_index_valid(input_idx, number_inputs, fn_id.as_str())?;
+            let formula =
+                encode_monotonicity(number_inputs, input_idx, fn_id.as_str(), prop.clone().value);
+            add_context(&prop.context, formula)
+        }
+        StatPropertyType::FnInputsMutuallyExclusive(prop) => {
+            let fn_id = prop.target.clone().unwrap();
+            let (idx_1, idx_2) = (prop.input_index_1.unwrap(), prop.input_index_2.unwrap());
+            let number_inputs = sketch.model.get_uninterpreted_fn_arity(&fn_id)?;
+            assert_fn_index_valid(idx_1, number_inputs, fn_id.as_str())?;
+            assert_fn_index_valid(idx_2, number_inputs, fn_id.as_str())?;
+            encode_mutually_exclusive_inputs(number_inputs, idx_1, idx_2, fn_id.as_str())
+        }
+    };
+    Ok(formula)
+}
+
+/// Wrap the property formula into the context formula, if it is provided.
+fn add_context(context: &Option<String>, formula: String) -> String {
+    match context {
+        Some(context_formula) => encode_property_in_context(context_formula, &formula),
+        None => formula,
+    }
+}
+
+/// Check that there is a regulation `input -> target` in the given BN.
+fn assert_regulation_in_bn(input: &str, target: &str, bn: &BooleanNetwork) -> Result<(), String> {
+    let graph = bn.as_graph();
+    match (graph.find_variable(input), graph.find_variable(target)) {
+        (Some(input_var), Some(target_var)) if bn.regulators(target_var).contains(&input_var) => {
+            Ok(())
+        }
+        _ => Err(format!("Regulation `{input} -> {target}` does not exist.")),
+    }
+}
+
+/// Check that `index` is a valid argument index of a function `fn_id` with `arity` arguments.
+fn assert_fn_index_valid(index: usize, arity: usize, fn_id: &str) -> Result<(), String> {
+    if index < arity {
+        Ok(())
+    } else {
+        Err(format!(
+            "Function `{fn_id}` has {arity} arguments, index {index} is out of range."
+        ))
+    }
+}
+
+/// Expand a single static property into a FOL formula, the same way as it is done by
+/// [process_static_prop_single], so that it can be shown to the user. Templates are encoded into
+/// FOL (see [encode_static_prop_str]), and observation macros are expanded.
+///
+/// Unlike [process_static_prop_single], variables are not renamed to a canonical form and
+/// function symbols are not substituted with their expressions, so the formula stays readable.
+pub(crate) fn expand_static_prop_fol(
+    stat_prop: &StatProperty,
+    sketch: &Sketch,
+) -> Result<FolTreeNode, String> {
+    stat_prop.assert_fully_filled()?;
+    let bn = sketch.model.to_bn_with_plain_regulations();
+    let formula = encode_static_prop_str(stat_prop, sketch, &bn)?;
+    parse_and_expand_fol_macros(&formula, sketch)
+}
+
+/// Parse a FOL formula string, expand all its observation macros (see [encode_observation_macro]),
+/// and then validate and rename the variables (see [parse_and_minimize_fol_formula]).
+///
+/// The macros must be expanded before the variables are validated, since the expansions
+/// reference FOL variables named after the model variables.
+fn parse_and_expand_fol_formula(
+    formula: &str,
+    sketch: &Sketch,
+    base_var_name: &str,
+) -> Result<FolTreeNode, String> {
+    parse_and_expand_fol_macros(formula, sketch)?
+        .validate_and_rename_vars(base_var_name)
+        .map_err(|e| format!("Error during FOL formula processing: {e}"))
+}
+
+/// Parse a FOL formula string and expand all its observation macros (see
+/// [encode_observation_macro]). The variables are not validated or renamed, so the result
+/// does not depend on a particular `BooleanNetwork` instance.
+pub(crate) fn parse_and_expand_fol_macros(
+    formula: &str,
+    sketch: &Sketch,
+) -> Result<FolTreeNode, String> {
+    let tree = parse_fol_formula(formula)?;
+    let mut expansions = HashMap::new();
+    for (dataset_id, obs_id) in tree.collect_observation_macros() {
+        let encoded_obs = encode_observation_macro(&dataset_id, &obs_id, sketch)?;
+        expansions.insert((dataset_id, obs_id), parse_fol_formula(&encoded_obs)?);
+    }
+    Ok(tree.expand_observation_macros(&expansions))
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::algorithms::eval_static::encode::{
+        encode_monotonicity, encode_property_in_context, encode_regulation_monotonicity,
+        fol_encoding_metrics,
+    };
     use crate::algorithms::eval_static::processed_props::{
         process_static_props, ProcessedStatProp,
     };
     use crate::algorithms::fo_logic::fol_tree::FolTreeNode;
     use crate::algorithms::fo_logic::operator_enums::{Quantifier, UnaryOp};
+    use crate::algorithms::fo_logic::parser::parse_and_minimize_fol_formula;
     use crate::inference::inference_solver::InferenceSolver;
-    use crate::sketchbook::model::{BinaryOp, Essentiality};
+    use crate::sketchbook::ids::VarId;
+    use crate::sketchbook::model::{BinaryOp, Essentiality, Monotonicity};
+    use crate::sketchbook::observations::{Dataset, Observation};
+    use crate::sketchbook::properties::FirstOrderFormula;
     use crate::sketchbook::{properties::StatProperty, Sketch};
 
     #[test]
@@ -228,6 +351,46 @@ mod tests {
         assert_eq!(processed_props[0], prop_expected);
     }
 
+    #[test]
+    /// Test processing of a FOL property that references an observation via macro.
+    fn test_process_fol_prop_with_observation() {
+        // Create basic sketch with single variable `A`, fn symbol `f`, and dataset `d`
+        let aeon_str = "A-??A\n$A:f(A)";
+        let mut sketch = Sketch::from_aeon(aeon_str).unwrap();
+        let obs = Observation::try_from_str("0", "o").unwrap();
+        let dataset = Dataset::new("d", vec![obs], vec!["A"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+
+        // Add FOL property referencing the observation (FOL variable `A` is quantified)
+        let prop = StatProperty::try_mk_generic("p1", "\\forall A: @d:o => f(A)").unwrap();
+        sketch.properties.add_static_by_str("p1", prop).unwrap();
+
+        // Process the static properties and check results - the observation is expanded into
+        // the literal `!A`, and then the variable's name is standardized
+        let (bn, expressions_map) = InferenceSolver::extract_bn(&sketch).unwrap();
+        let processed_props = process_static_props(&sketch, &bn, expressions_map, "A").unwrap();
+        let formula_expected = FolTreeNode::mk_quantifier(
+            FolTreeNode::mk_binary(
+                FolTreeNode::mk_unary(FolTreeNode::mk_variable("A_extra_0"), UnaryOp::Not),
+                FolTreeNode::mk_function("f", vec![FolTreeNode::mk_variable("A_extra_0")], false),
+                BinaryOp::Imp,
+            ),
+            "A_extra_0",
+            Quantifier::Forall,
+        );
+        let prop_expected = ProcessedStatProp::mk_fol("p1", formula_expected);
+        assert_eq!(processed_props[0], prop_expected);
+
+        // referencing a non-existing observation fails
+        let prop = StatProperty::try_mk_generic("p2", "\\forall A: @d:x => f(A)").unwrap();
+        sketch.properties.add_static_by_str("p2", prop).unwrap();
+        let (bn, expressions_map) = InferenceSolver::extract_bn(&sketch).unwrap();
+        assert!(process_static_props(&sketch, &bn, expressions_map, "A").is_err());
+    }
+
     #[test]
     /// Test processing of a simple template function property that does not require function
     /// expression substitutions.
@@ -306,15 +469,112 @@ mod tests {
         sketch.properties.add_static_by_str("p1", prop).unwrap();
 
         // Process the static properties and check results - function symbol in the resulting
-        // FOL formula must get replaced by its expression
+        // FOL formula must get replaced by its expression, which makes the formula `!0 ^ !1`
+        // trivially true after the simplification
         let (bn, expressions_map) = InferenceSolver::extract_bn(&sketch).unwrap();
         let processed_props = process_static_props(&sketch, &bn, expressions_map, "A").unwrap();
-        let formula_expected = FolTreeNode::mk_binary(
-            FolTreeNode::mk_unary(FolTreeNode::mk_constant(false), UnaryOp::Not),
-            FolTreeNode::mk_unary(FolTreeNode::mk_constant(true), UnaryOp::Not),
-            BinaryOp::Xor,
-        );
+        let formula_expected = FolTreeNode::mk_constant(true);
         let prop_expected = ProcessedStatProp::mk_fol("p1", formula_expected);
         assert_eq!(processed_props[0], prop_expected);
     }
+
+    #[test]
+    /// Test that properties made redundant by function expressions are simplified before
+    /// the encoding (making the encoding smaller), while properties of functions without
+    /// expressions stay intact.
+    fn test_process_props_simplified() {
+        // Sketch with fn symbols `f` (with expression `var0 | var1`) and `g` (no expression)
+        let aeon_str = "A -?? A\nB -?? A\nA -?? B\n$A: f(A, B)\n$B: g(A)";
+        let mut sketch = Sketch::from_aeon(aeon_str).unwrap();
+        sketch
+            .model
+            .set_uninterpreted_fn_expression_by_str("f", "var0 | var1")
+            .unwrap();
+        let fn_f = sketch.model.get_uninterpreted_fn_id("f").unwrap();
+        let fn_g = sketch.model.get_uninterpreted_fn_id("g").unwrap();
+
+        // Monotonicity of `f` is redundant (implied by its expression), of `g` it is not
+        let act = Monotonicity::Activation;
+        let prop_f = StatProperty::mk_fn_input_monotonic("p1", Some(0), Some(fn_f), act);
+        let prop_g = StatProperty::mk_fn_input_monotonic("p2", Some(0), Some(fn_g), act);
+        sketch.properties.add_static_by_str("p1", prop_f).unwrap();
+        sketch.properties.add_static_by_str("p2", prop_g).unwrap();
+        let (bn, expressions_map) = InferenceSolver::extract_bn(&sketch).unwrap();
+        let processed_props = process_static_props(&sketch, &bn, expressions_map, "A").unwrap();
+
+        // The substituted (but not simplified) formula for `f` is a tautology, which is
+        // simplified into a constant with no operators at all
+        let formula_f = "\\forall x: (0 | x) => (1 | x)";
+        let unsimplified_f = parse_and_minimize_fol_formula(formula_f, "A").unwrap();
+        let metrics_before = fol_encoding_metrics(&unsimplified_f);
+        let metrics_after = fol_encoding_metrics(&processed_props[0].formula);
+        assert_eq!(metrics_before.num_operators, 4);
+        assert_eq!(metrics_after.num_operators, 0);
+        assert_eq!(metrics_after.num_state_vars, 0);
+        assert_eq!(processed_props[0].formula, FolTreeNode::mk_constant(true));
+
+        // The formula for `g` is kept as it is, with the free symbol intact
+        let formula_g = encode_monotonicity(1, 0, "g", act);
+        let unsimplified_g = parse_and_minimize_fol_formula(&formula_g, "A").unwrap();
+        assert_eq!(processed_props[1].formula, unsimplified_g);
+        assert_eq!(
+            fol_encoding_metrics(&processed_props[1].formula),
+            fol_encoding_metrics(&unsimplified_g)
+        );
+    }
+
+    #[test]
+    /// Test expanding static properties into FOL formulas (for display), without substituting
+    /// function expressions or renaming variables.
+    fn test_stat_prop_to_fol_formula() {
+        let aeon_str = "A -> B\nB -| A\n$A:f(B)";
+        let mut sketch = Sketch::from_aeon(aeon_str).unwrap();
+        sketch
+            .model
+            .set_uninterpreted_fn_expression_by_str("f", "!var0")
+            .unwrap();
+        let obs = Observation::try_from_str("0*", "o").unwrap();
+        let dataset = Dataset::new("d", vec![obs], vec!["A", "B"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+        let (var_a, var_b) = (VarId::new("A").unwrap(), VarId::new("B").unwrap());
+
+        // observation macros are expanded
+        let prop = StatProperty::try_mk_generic("p", "\\forall A: @d:o => f(A)").unwrap();
+        let expected = FirstOrderFormula::try_from_str("\\forall A: (!A) => f(A)").unwrap();
+        assert_eq!(prop.to_fol_formula(&sketch).unwrap(), expected);
+
+        // templates are encoded the same way as during the processing, including the context
+        let prop = StatProperty::mk_regulation_monotonic_context(
+            "p",
+            Some(var_a.clone()),
+            Some(var_b.clone()),
+            Monotonicity::Activation,
+            "f_B(true)".to_string(),
+        );
+        let bn = sketch.model.to_bn_with_plain_regulations();
+        let formula = encode_regulation_monotonicity("A", "B", Monotonicity::Activation, &bn);
+        let formula = encode_property_in_context("f_B(true)", &formula);
+        let expected = FirstOrderFormula::try_from_str(&formula).unwrap();
+        assert_eq!(prop.to_fol_formula(&sketch).unwrap(), expected);
+
+        // referencing non-existing regulation fails (instead of panicking)
+        let prop = StatProperty::mk_regulation_essential(
+            "p",
+            Some(var_b),
+            Some(var_a),
+            Essentiality::True,
+        );
+        assert!(prop.to_fol_formula(&sketch).is_ok());
+        let var_c = VarId::new("C").unwrap();
+        let prop = StatProperty::mk_regulation_essential(
+            "p",
+            Some(var_c),
+            Some(VarId::new("A").unwrap()),
+            Essentiality::True,
+        );
+        assert!(prop.to_fol_formula(&sketch).is_err());
+    }
 }
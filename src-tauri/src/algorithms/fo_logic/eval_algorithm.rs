@@ -14,6 +14,9 @@ pub fn eval_node(node: FolTreeNode, graph: &SymbolicAsyncGraph) -> GraphColoredV
             Atom::True => graph.mk_unit_colored_vertices(),
             Atom::False => graph.mk_empty_colored_vertices(),
             Atom::Var(name) => eval_variable(graph, name.as_str()),
+            Atom::Observation(..) => {
+                unreachable!("Observation macros must be expanded before the evaluation.")
+            }
         },
         NodeType::Unary(op, child) => match op {
             UnaryOp::Not => eval_neg(graph, &eval_node(*child, graph)),
@@ -96,6 +96,16 @@ impl FolTreeNode {
         Self::mk_atom(Atom::Var(var_name.to_string()))
     }
 
+    /// Create a [FolTreeNode] representing an observation macro.
+    ///
+    /// See also [NodeType::Terminal] and [Atom::Observation].
+    pub fn mk_observation(dataset_id: &str, obs_id: &str) -> FolTreeNode {
+        Self::mk_atom(Atom::Observation(
+            dataset_id.to_string(),
+            obs_id.to_string(),
+        ))
+    }
+
     /// A helper function which creates a new [FolTreeNode] for the given [Atom] value.
     fn mk_atom(atom: Atom) -> FolTreeNode {
         FolTreeNode {
@@ -330,6 +340,76 @@ impl FolTreeNode {
         }
     }
 
+    /// Create a copy of this [FolTreeNode] with all observation macros substituted with their
+    /// expansions, given by `expansions` mapping (pairs of dataset ID and observation ID are
+    /// mapped to the corresponding expressions). Macros that are not in the mapping remain
+    /// untouched.
+    ///
+    /// The expansion of an observation typically references FOL variables named after the model
+    /// variables. Therefore, the expansion must be done before the variables are validated and
+    /// renamed (see [FolTreeNode::validate_and_rename_vars]).
+    pub fn expand_observation_macros(
+        &self,
+        expansions: &HashMap<(String, String), FolTreeNode>,
+    ) -> FolTreeNode {
+        match &self.node_type {
+            NodeType::Terminal(Atom::Observation(dataset_id, obs_id)) => {
+                let key = (dataset_id.clone(), obs_id.clone());
+                expansions.get(&key).cloned().unwrap_or(self.clone())
+            }
+            NodeType::Terminal(_) => self.clone(),
+            NodeType::Unary(op, child) => {
+                let node = child.expand_observation_macros(expansions);
+                FolTreeNode::mk_unary(node, *op)
+            }
+            NodeType::Binary(op, left, right) => {
+                let node1 = left.expand_observation_macros(expansions);
+                let node2 = right.expand_observation_macros(expansions);
+                FolTreeNode::mk_binary(node1, node2, *op)
+            }
+            NodeType::Quantifier(op, quantified_var, child) => {
+                let node = child.expand_observation_macros(expansions);
+                FolTreeNode::mk_quantifier(node, quantified_var, *op)
+            }
+            NodeType::Function(fn_symbol, child_nodes) => {
+                let new_children = child_nodes
+                    .iter()
+                    .map(|node| node.expand_observation_macros(expansions))
+                    .collect();
+                FolTreeNode::mk_function(&fn_symbol.name, new_children, fn_symbol.is_update_fn)
+            }
+        }
+    }
+
+    /// Compute the set of all unique observation macros in the formula tree, each given by
+    /// a pair of dataset ID and observation ID.
+    pub fn collect_observation_macros(&self) -> HashSet<(String, String)> {
+        let mut seen_macros = HashSet::new();
+        self.collect_observation_macros_recursive(&mut seen_macros);
+        seen_macros
+    }
+
+    fn collect_observation_macros_recursive(&self, seen_macros: &mut HashSet<(String, String)>) {
+        match &self.node_type {
+            NodeType::Terminal(Atom::Observation(dataset_id, obs_id)) => {
+                seen_macros.insert((dataset_id.clone(), obs_id.clone()));
+            }
+            NodeType::Terminal(_) => {}
+            NodeType::Unary(_, child) | NodeType::Quantifier(_, _, child) => {
+                child.collect_observation_macros_recursive(seen_macros);
+            }
+            NodeType::Binary(_, left, right) => {
+                left.collect_observation_macros_recursive(seen_macros);
+                right.collect_observation_macros_recursive(seen_macros);
+            }
+            NodeType::Function(_, child_nodes) => {
+                for child in child_nodes {
+                    child.collect_observation_macros_recursive(seen_macros);
+                }
+            }
+        }
+    }
+
     /// Compute the set of all uniquely named FOL variables in the formula tree.
     ///
     /// Variable names are collected from the quantifiers `exists` and `forall` (which is sufficient,
@@ -451,7 +531,8 @@ impl FolTreeNode {
                     let renamed_var = renaming_map.get(name.as_str()).unwrap();
                     Ok(FolTreeNode::mk_variable(renamed_var))
                 }
-                // constants are always automatically fine
+                // constants are always automatically fine (and so are the observation macros,
+                // their expansions are validated separately)
                 _ => Ok(self.clone()),
             },
             // just dive one level deeper for unary nodes, and rename string
@@ -512,6 +593,124 @@ impl FolTreeNode {
     }
 }
 
+/// Simplification of formulas by partial evaluation.
+impl FolTreeNode {
+    /// Create a simplified copy of this [FolTreeNode] that is semantically equivalent. The
+    /// simplification is purely syntactic, and it consists of:
+    ///  - folding Boolean constants (e.g., `x & 1` to `x`, or `x | 1` to `1`)
+    ///  - removing double negations
+    ///  - simplifying binary operations with syntactically equal operands (e.g., `x => x` to `1`)
+    ///  - removing quantifiers whose variable does not appear in the sub-formula
+    ///
+    /// Such trivial sub-formulas typically appear after function symbols are substituted with
+    /// their expressions. Function symbols without expressions are left intact (only their
+    /// arguments are simplified).
+    pub fn simplify_constants(&self) -> FolTreeNode {
+        match &self.node_type {
+            NodeType::Terminal(_) => self.clone(),
+            NodeType::Unary(UnaryOp::Not, child) => Self::mk_negation(child.simplify_constants()),
+            NodeType::Binary(op, left, right) => Self::mk_simplified_binary(
+                left.simplify_constants(),
+                right.simplify_constants(),
+                *op,
+            ),
+            NodeType::Quantifier(op, var, child) => {
+                let child = child.simplify_constants();
+                // the domain is non-empty, so the quantifier can be dropped if var is not used
+                if child.contains_variable(var) {
+                    FolTreeNode::mk_quantifier(child, var, *op)
+                } else {
+                    child
+                }
+            }
+            NodeType::Function(fn_symbol, args) => {
+                let args = args.iter().map(|arg| arg.simplify_constants()).collect();
+                FolTreeNode::mk_function(&fn_symbol.name, args, fn_symbol.is_update_fn)
+            }
+        }
+    }
+
+    /// **(internal)** If this node is a Boolean constant, return its value.
+    fn as_constant(&self) -> Option<bool> {
+        match &self.node_type {
+            NodeType::Terminal(Atom::True) => Some(true),
+            NodeType::Terminal(Atom::False) => Some(false),
+            _ => None,
+        }
+    }
+
+    /// **(internal)** Check whether a variable with the given name appears anywhere in the tree.
+    fn contains_variable(&self, var_name: &str) -> bool {
+        match &self.node_type {
+            NodeType::Terminal(Atom::Var(name)) => name == var_name,
+            NodeType::Terminal(_) => false,
+            NodeType::Unary(_, child) | NodeType::Quantifier(_, _, child) => {
+                child.contains_variable(var_name)
+            }
+            NodeType::Binary(_, left, right) => {
+                left.contains_variable(var_name) || right.contains_variable(var_name)
+            }
+            NodeType::Function(_, args) => args.iter().any(|arg| arg.contains_variable(var_name)),
+        }
+    }
+
+    /// **(internal)** Negate an (already simplified) formula, folding constants and
+    /// removing double negations.
+    fn mk_negation(child: FolTreeNode) -> FolTreeNode {
+        match child.node_type {
+            NodeType::Terminal(Atom::True) => FolTreeNode::mk_constant(false),
+            NodeType::Terminal(Atom::False) => FolTreeNode::mk_constant(true),
+            NodeType::Unary(UnaryOp::Not, inner) => *inner,
+            _ => FolTreeNode::mk_unary(child, UnaryOp::Not),
+        }
+    }
+
+    /// **(internal)** Create a binary operation on two (already simplified) formulas, folding
+    /// constants and operations with syntactically equal operands.
+    fn mk_simplified_binary(left: FolTreeNode, right: FolTreeNode, op: BinaryOp) -> FolTreeNode {
+        match (op, left.as_constant(), right.as_constant()) {
+            (_, Some(l), Some(r)) => {
+                let value = match op {
+                    BinaryOp::And => l && r,
+                    BinaryOp::Or => l || r,
+                    BinaryOp::Xor => l != r,
+                    BinaryOp::Imp => !l || r,
+                    BinaryOp::Iff => l == r,
+                };
+                FolTreeNode::mk_constant(value)
+            }
+            (BinaryOp::And, Some(false), _) | (BinaryOp::And, _, Some(false)) => {
+                FolTreeNode::mk_constant(false)
+            }
+            (BinaryOp::Or, Some(true), _)
+            | (BinaryOp::Or, _, Some(true))
+            | (BinaryOp::Imp, Some(false), _)
+            | (BinaryOp::Imp, _, Some(true)) => FolTreeNode::mk_constant(true),
+            (BinaryOp::And, Some(true), _)
+            | (BinaryOp::Or, Some(false), _)
+            | (BinaryOp::Xor, Some(false), _)
+            | (BinaryOp::Iff, Some(true), _)
+            | (BinaryOp::Imp, Some(true), _) => right,
+            (BinaryOp::And, _, Some(true))
+            | (BinaryOp::Or, _, Some(false))
+            | (BinaryOp::Xor, _, Some(false))
+            | (BinaryOp::Iff, _, Some(true)) => left,
+            (BinaryOp::Xor, Some(true), _) | (BinaryOp::Iff, Some(false), _) => {
+                Self::mk_negation(right)
+            }
+            (BinaryOp::Xor, _, Some(true))
+            | (BinaryOp::Iff, _, Some(false))
+            | (BinaryOp::Imp, _, Some(false)) => Self::mk_negation(left),
+            _ if left == right => match op {
+                BinaryOp::And | BinaryOp::Or => left,
+                BinaryOp::Imp | BinaryOp::Iff => FolTreeNode::mk_constant(true),
+                BinaryOp::Xor => FolTreeNode::mk_constant(false),
+            },
+            _ => FolTreeNode::mk_binary(left, right, op),
+        }
+    }
+}
+
 impl FolTreeNode {
     pub fn as_str(&self) -> &str {
         self.formula_str.as_str()
@@ -680,4 +879,30 @@ mod tests {
         let formula = parse_fol_formula("f(x)").unwrap();
         assert!(formula.validate_and_rename_vars("var").is_err());
     }
+
+    #[test]
+    /// Test simplifying formulas by folding constants and removing redundant quantifiers.
+    fn simplify_constants() {
+        let cases = [
+            ("\\forall x: ((0 | x) => (1 | x))", "1"),
+            ("\\exists x: (x & 0) | f(x, !!1)", "(\\exists x: f(x, 1))"),
+            (
+                "\\forall x: \\exists y: (x ^ 1) <=> (y & 1)",
+                "(\\forall x: (\\exists y: ((!x) <=> y)))",
+            ),
+            ("\\forall x: \\exists y: f(x) => f(x)", "1"),
+            (
+                "\\forall x: \\exists y: f(x) ^ (0 <=> y)",
+                "(\\forall x: (\\exists y: (f(x) ^ (!y))))",
+            ),
+            (
+                "\\exists x: g(x) & (x | !x)",
+                "(\\exists x: (g(x) & (x | (!x))))",
+            ),
+        ];
+        for (formula, expected) in cases {
+            let tree = parse_fol_formula(formula).unwrap();
+            assert_eq!(tree.simplify_constants().as_str(), expected);
+        }
+    }
 }
@@ -18,12 +18,16 @@ pub enum Quantifier {
     Forall, // 'V' or "\forall"
 }
 
-/// Enum for atomic terms: variables and constants.
+/// Enum for atomic terms: variables, constants, and observation macros.
+///
+/// Observation macro `@dataset_id:obs_id` is a high-level proposition that is expanded into
+/// a conjunction of literals before the formula is evaluated.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub enum Atom {
-    Var(String), // A variable
-    True,        // A true constant
-    False,       // A false constant
+    Var(String),                 // A variable
+    True,                        // A true constant
+    False,                       // A false constant
+    Observation(String, String), // A reference to an observation (dataset ID, observation ID)
 }
 
 /// A named function symbol with a boolean flag whether the symbol is an explicit
@@ -80,6 +84,7 @@ impl fmt::Display for Atom {
             Atom::Var(name) => write!(f, "{name}"),
             Atom::True => write!(f, "1"),
             Atom::False => write!(f, "0"),
+            Atom::Observation(dataset_id, obs_id) => write!(f, "@{dataset_id}:{obs_id}"),
         }
     }
 }
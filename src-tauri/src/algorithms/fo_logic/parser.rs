@@ -204,6 +204,9 @@ fn parse_8_terms_and_parentheses(tokens: &[FolToken]) -> Result<FolTreeNode, Str
                 FolToken::Atomic(Atom::False) => {
                     return Ok(FolTreeNode::mk_constant(false));
                 }
+                FolToken::Atomic(Atom::Observation(dataset_id, obs_id)) => {
+                    return Ok(FolTreeNode::mk_observation(dataset_id, obs_id));
+                }
                 FolToken::Function(fn_symbol, arguments) => {
                     let mut arg_expression_nodes = Vec::new();
                     for inner in arguments {
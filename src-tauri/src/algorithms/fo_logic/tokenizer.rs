@@ -67,6 +67,11 @@ fn try_tokenize_recursive(
             }
             // '>' is invalid as a start of a token
             '>' => return Err("Unexpected '>'.".to_string()),
+            // observation macro in format "@dataset_id:obs_id"
+            '@' => {
+                let (dataset_id, obs_id) = collect_observation_macro(input_chars)?;
+                output.push(FolToken::Atomic(Atom::Observation(dataset_id, obs_id)));
+            }
 
             // "3" can be either short for exist quantifier or part of some name
             '3' if !is_valid_in_name_optional(input_chars.peek()) => {
@@ -251,6 +256,25 @@ fn collect_vars_from_operator(
     Ok(variables)
 }
 
+/// Retrieve the dataset and observation IDs of an observation macro "@dataset_id:obs_id".
+/// The "@" is already consumed by the caller.
+fn collect_observation_macro(
+    input_chars: &mut Peekable<Chars>,
+) -> Result<(String, String), String> {
+    let dataset_id = collect_name(input_chars)?;
+    if dataset_id.is_empty() {
+        return Err("Dataset ID can't be empty in observation macro.".to_string());
+    }
+    if Some(':') != input_chars.next() {
+        return Err(format!("Expected ':' after '@{dataset_id}'."));
+    }
+    let obs_id = collect_name(input_chars)?;
+    if obs_id.is_empty() {
+        return Err("Observation ID can't be empty in observation macro.".to_string());
+    }
+    Ok((dataset_id, obs_id))
+}
+
 /// Retrieve the arguments of a function, process everything from "(" up to ")".
 /// Function name is consumed by caller.
 fn collect_fn_arguments(input_chars: &mut Peekable<Chars>) -> Result<Vec<FolToken>, String> {
@@ -292,6 +316,7 @@ impl fmt::Display for FolToken {
             FolToken::Binary(BinaryOp::Iff) => write!(f, "<=>"),
             FolToken::Quantifier(op, var) => write!(f, "{op:?} {var}:"),
             FolToken::Atomic(Atom::Var(name)) => write!(f, "{name}"),
+            FolToken::Atomic(obs @ Atom::Observation(..)) => write!(f, "{obs}"),
             FolToken::Atomic(constant) => write!(f, "{constant:?}"),
             FolToken::Function(name, _) => write!(f, "{name}(...)"),
             FolToken::TokenList(_) => write!(f, "TokenList"), // debug purposes only
@@ -355,6 +380,13 @@ mod tests {
             ),]
         );
 
+        let valid5 = "\\forall A: @data:obs_1 => f(A)".to_string();
+        let tokens5 = try_tokenize_formula(valid5).unwrap();
+        assert_eq!(
+            tokens5[1],
+            FolToken::Atomic(Atom::Observation("data".to_string(), "obs_1".to_string()))
+        );
+
         let valid4 = "\\exists x, y, z: true".to_string();
         let tokens4 = try_tokenize_formula(valid4).unwrap();
         assert_eq!(
@@ -381,7 +413,7 @@ mod tests {
     fn tokenize_invalid_formulae() {
         let invalid_formulae = vec![
             "x1 )", "( x1", "x1 <> x2", "x1 >= x2", "x1 <= x2", "\\ex x", "\\fora x", "f(x,)",
-            "f(x,", "f(x", "f(x x))",
+            "f(x,", "f(x", "f(x x))", "@data", "@data:", "@:obs",
         ];
 
         for formula in invalid_formulae {
@@ -1,6 +1,9 @@
+use crate::algorithms::fo_logic::fol_tree::{FolTreeNode, NodeType};
+use crate::sketchbook::model::ModelState;
 use biodivine_lib_param_bn::symbolic_async_graph::{SymbolicAsyncGraph, SymbolicContext};
 use biodivine_lib_param_bn::BooleanNetwork;
 use regex::Regex;
+use std::collections::BTreeSet;
 
 /// Internally, all FOL variables are encoded using an arbitrary BN variable name, and
 /// an offset. For example, if `x` is chosen as a base, all FOL variables are transformed
@@ -103,3 +106,117 @@ pub fn check_update_fn_support(bn: &BooleanNetwork, var_name: &str, num_regulato
         false
     }
 }
+
+/// Collect all function symbol applications in the given FOL formula tree, as pairs of the
+/// symbol name and the number of its arguments.
+///
+/// Unlike [FolTreeNode::collect_unique_fn_symbols], this does not fail if a symbol is applied
+/// with different numbers of arguments (each such application is collected).
+pub fn collect_fn_applications(tree: &FolTreeNode) -> BTreeSet<(String, usize)> {
+    let mut applications = BTreeSet::new();
+    collect_fn_applications_recursive(tree, &mut applications);
+    applications
+}
+
+/// **(internal)** Recursive helper for [collect_fn_applications].
+fn collect_fn_applications_recursive(
+    tree: &FolTreeNode,
+    applications: &mut BTreeSet<(String, usize)>,
+) {
+    match &tree.node_type {
+        NodeType::Terminal(_) => {}
+        NodeType::Unary(_, child) | NodeType::Quantifier(_, _, child) => {
+            collect_fn_applications_recursive(child, applications);
+        }
+        NodeType::Binary(_, left, right) => {
+            collect_fn_applications_recursive(left, applications);
+            collect_fn_applications_recursive(right, applications);
+        }
+        NodeType::Function(fn_symbol, child_nodes) => {
+            applications.insert((fn_symbol.name.clone(), child_nodes.len()));
+            for child in child_nodes {
+                collect_fn_applications_recursive(child, applications);
+            }
+        }
+    }
+}
+
+/// Check that all function symbols applied in the given FOL formula tree are valid in the
+/// `model`. Each symbol must either correspond to an uninterpreted function of the model with
+/// the same arity, or to an (implicit) update function of a model variable with the same number
+/// of regulators.
+///
+/// The check is purely syntactic, so symbols of uninterpreted functions with expressions are
+/// validated as well (before the expressions would be substituted).
+///
+/// Returns a message for each invalid application (empty if all symbols are valid).
+pub fn find_invalid_fn_symbols(tree: &FolTreeNode, model: &ModelState) -> Vec<String> {
+    let mut issues = Vec::new();
+    for (fn_name, arity) in collect_fn_applications(tree) {
+        // Check if the name corresponds to an (implicit) update function symbol for some variable
+        if let Ok(var) = get_var_from_implicit(&fn_name) {
+            // If this is an update fn symbol, the corresponding variable must exist,
+            // and that the arity of the function is correct.
+            if let Ok(valid_var_id) = model.get_var_id(&var) {
+                // safe to unwrap, the variable is valid
+                let update_fn_arity = model.regulators(&valid_var_id).unwrap().len();
+                if update_fn_arity != arity {
+                    issues.push(format!(
+                        "Update function symbol `{fn_name}` is used with incorrect arity \
+                        ({arity} instead of {update_fn_arity})."
+                    ));
+                }
+            } else {
+                issues.push(format!(
+                    "There is no variable corresponding to update function `{fn_name}`."
+                ));
+            }
+        } else if let Ok(valid_fn_id) = model.get_uninterpreted_fn_id(&fn_name) {
+            // If this is not update fn symbol, it must be correspond to an uninterpreted function.
+            // safe to unwrap, the function is valid
+            let fn_arity = model.get_uninterpreted_fn_arity(&valid_fn_id).unwrap();
+            if fn_arity != arity {
+                issues.push(format!(
+                    "Function symbol `{fn_name}` is used with incorrect arity \
+                    ({arity} instead of {fn_arity})."
+                ));
+            }
+        } else {
+            issues.push(format!(
+                "Function `{fn_name}` with arity {arity} not found in the model."
+            ));
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::fo_logic::parser::parse_fol_formula;
+    use crate::algorithms::fo_logic::utils::{collect_fn_applications, find_invalid_fn_symbols};
+    use crate::sketchbook::Sketch;
+
+    #[test]
+    /// Test validating function symbols of FOL formulas against the model.
+    fn test_find_invalid_fn_symbols() {
+        // function `f` has an expression, but its symbol is still valid
+        let mut sketch = Sketch::from_aeon("A -> A\nA -> B\n$A: f(A)\n$B: g(A)").unwrap();
+        sketch
+            .model
+            .set_uninterpreted_fn_expression_by_str("f", "var0")
+            .unwrap();
+
+        let tree = parse_fol_formula("f(1) & g(0) & f_B(1) & f_A(1)").unwrap();
+        assert!(find_invalid_fn_symbols(&tree, &sketch.model).is_empty());
+
+        // wrong arities, missing function, and update function of missing variable
+        let tree = parse_fol_formula("f(1, 0) & f(1) & h(1) & f_C(1) & f_B(1, 1)").unwrap();
+        assert_eq!(collect_fn_applications(&tree).len(), 5);
+        let issues = find_invalid_fn_symbols(&tree, &sketch.model);
+        assert_eq!(issues.len(), 4);
+        assert!(issues.contains(
+            &"Function symbol `f` is used with incorrect arity (2 instead of 1).".to_string()
+        ));
+        assert!(issues.contains(&"Function `h` with arity 1 not found in the model.".to_string()));
+    }
+}
@@ -4,3 +4,5 @@ pub mod eval_dynamic;
 pub mod eval_static;
 /// Parsing and evaluation of first-order formulas.
 pub mod fo_logic;
+/// Heuristics and utilities for ordering variables in the symbolic encoding.
+pub mod variable_ordering;
@@ -1,4 +1,4 @@
-use crate::app::event::Event;
+use crate::app::event::{Event, UserAction};
 use crate::app::DynError;
 
 /// A [Consumed] object describes possible outcomes of trying to consume an [Event]
@@ -15,7 +15,8 @@ pub enum Consumed {
     /// At the moment, each event must be reversible by a single event. If this is not the
     /// case, you can "restart" the evaluation process with a new, more granular event chain
     /// by returning [Consumed::Restart]. It is the responsibility of the session state to
-    /// record this whole chain as a single reversible action.
+    /// record this whole chain as a single reversible action. Alternatively, if the event was
+    /// already performed in several steps, use [Consumed::ReversibleGroup].
     ///
     /// However, note that this does not *guarantee* that the action will be saved to the
     /// undo stack. If the payloads for the `(perform, reverse)` actions are too large,
@@ -27,6 +28,19 @@ pub enum Consumed {
         perform_reverse: (Event, Event),
     },
 
+    /// Event was successfully consumed as a compound operation of several reversible steps
+    /// (possibly touching several components of the state), resulting in the provided list
+    /// of `state_changes`.
+    ///
+    /// The whole operation is saved to the back-stack as a single `(perform, reverse)` pair of
+    /// actions. The `reverse` action contains reverse events of all the steps in the opposite
+    /// order, so that each of them is applied to the state right after its step was performed.
+    /// Use [Consumed::merge_reversible] to create this result from the individual steps.
+    ReversibleGroup {
+        state_changes: Vec<Event>,
+        perform_reverse: (UserAction, UserAction),
+    },
+
     /// Action was successfully consumed, resulting in the given `state_change` [Event].
     ///
     /// However, the action is irreversible. This means the undo stack should be either
@@ -63,3 +77,146 @@ pub enum Consumed {
     /// The action was consumed, but the application state did not change.
     NoChange,
 }
+
+impl Consumed {
+    /// Merge results of several reversible steps (in the order in which they were performed)
+    /// into a single result, reversible as a whole (see [Consumed::ReversibleGroup]).
+    ///
+    /// The steps can be [Consumed::Reversible], [Consumed::ReversibleGroup] (nested groups are
+    /// flattened), or [Consumed::NoChange] (these are skipped). If there is no actual change,
+    /// [Consumed::NoChange] is returned, and a single reversible step is returned as is.
+    ///
+    /// Returns `Err` if some step is not reversible. Note that the steps were already performed
+    /// at this point, so the caller is responsible for the state in such case.
+    pub fn merge_reversible(results: Vec<Consumed>) -> Result<Consumed, String> {
+        let mut results: Vec<Consumed> = results
+            .into_iter()
+            .filter(|result| !matches!(result, Consumed::NoChange))
+            .collect();
+        // a single simple step does not need to be grouped
+        if results.is_empty() {
+            return Ok(Consumed::NoChange);
+        } else if results.len() == 1 && matches!(results[0], Consumed::Reversible { .. }) {
+            return Ok(results.remove(0));
+        }
+
+        let mut state_changes = Vec::new();
+        let mut perform_events = Vec::new();
+        // reverse events of individual steps, in the order of the steps
+        let mut reverse_steps: Vec<Vec<Event>> = Vec::new();
+        for result in results {
+            match result {
+                Consumed::Reversible {
+                    state_change,
+                    perform_reverse: (perform, reverse),
+                } => {
+                    state_changes.push(state_change);
+                    perform_events.push(perform);
+                    reverse_steps.push(vec![reverse]);
+                }
+                Consumed::ReversibleGroup {
+                    state_changes: group_changes,
+                    perform_reverse: (perform, reverse),
+                } => {
+                    state_changes.extend(group_changes);
+                    perform_events.extend(perform.events);
+                    reverse_steps.push(reverse.events);
+                }
+                _ => return Err("Only reversible results can be merged into a group.".to_string()),
+            }
+        }
+
+        // the last step must be reversed first, as the earlier reverse events expect the state
+        // right after their own step
+        let reverse_events = reverse_steps.into_iter().rev().flatten().collect();
+        Ok(Consumed::ReversibleGroup {
+            state_changes,
+            perform_reverse: (
+                UserAction {
+                    events: perform_events,
+                },
+                UserAction {
+                    events: reverse_events,
+                },
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::event::Event;
+    use crate::app::state::_state_atomic::AtomicState;
+    use crate::app::state::{Consumed, SessionState};
+
+    #[test]
+    fn test_merge_reversible() {
+        let (mut state_a, mut state_b) = (AtomicState::from(1), AtomicState::from(1));
+        let set_a = Event::build(&["a"], Some("2"));
+        let set_b = Event::build(&["b"], Some("3"));
+        let set_a_again = Event::build(&["a"], Some("4"));
+        let results = vec![
+            state_a.perform_event(&set_a, &[]).unwrap(),
+            state_b.perform_event(&set_b, &[]).unwrap(),
+            state_b.perform_event(&set_b, &[]).unwrap(), // no change
+            state_a.perform_event(&set_a_again, &[]).unwrap(),
+        ];
+
+        let merged = Consumed::merge_reversible(results).unwrap();
+        let Consumed::ReversibleGroup {
+            state_changes,
+            perform_reverse: (perform, reverse),
+        } = merged
+        else {
+            panic!("Merged result should be a reversible group.");
+        };
+        assert_eq!(state_changes.len(), 3);
+        assert_eq!(perform.events, vec![set_a, set_b, set_a_again]);
+        // the last step is reversed first, so `a` ends up with its original value
+        let reverse_payloads: Vec<Option<String>> =
+            reverse.events.iter().map(|e| e.payload.clone()).collect();
+        let expected = vec![
+            Some("2".to_string()),
+            Some("1".to_string()),
+            Some("1".to_string()),
+        ];
+        assert_eq!(reverse_payloads, expected);
+        assert_eq!(reverse.events[0].path, vec!["a"]);
+        assert_eq!(reverse.events[1].path, vec!["b"]);
+
+        // nested groups are flattened, in the correct order
+        let results = vec![
+            state_b
+                .perform_event(&Event::build(&["b"], Some("5")), &[])
+                .unwrap(),
+            Consumed::ReversibleGroup {
+                state_changes,
+                perform_reverse: (perform, reverse),
+            },
+        ];
+        let Consumed::ReversibleGroup {
+            perform_reverse: (perform, reverse),
+            ..
+        } = Consumed::merge_reversible(results).unwrap()
+        else {
+            panic!("Merged result should be a reversible group.");
+        };
+        assert_eq!(perform.events.len(), 4);
+        assert_eq!(reverse.events.len(), 4);
+        assert_eq!(reverse.events[3].payload, Some("3".to_string()));
+
+        // trivial cases, and irreversible steps
+        let result = state_a
+            .perform_event(&Event::build(&["a"], Some("6")), &[])
+            .unwrap();
+        let merged = Consumed::merge_reversible(vec![result, Consumed::NoChange]).unwrap();
+        assert!(matches!(merged, Consumed::Reversible { .. }));
+        let merged = Consumed::merge_reversible(vec![Consumed::NoChange]).unwrap();
+        assert!(matches!(merged, Consumed::NoChange));
+        let irreversible = Consumed::Irreversible {
+            state_change: Event::build(&["a"], None),
+            reset: true,
+        };
+        assert!(Consumed::merge_reversible(vec![irreversible]).is_err());
+    }
+}
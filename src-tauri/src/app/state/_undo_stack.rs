@@ -3,9 +3,21 @@ use crate::app::state::{Consumed, SessionState};
 use crate::app::{AeonError, DynError};
 use crate::debug;
 use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
 pub const DEFAULT_EVENT_LIMIT: usize = 1 << 16; // ~64k
 pub const DEFAULT_PAYLOAD_LIMIT: usize = 1 << 28; // 256MB
+pub const DEFAULT_COALESCE_WINDOW: Duration = Duration::from_millis(500);
+
+/// Names of events (last segments of their paths) that can be coalesced into a single stack
+/// entry when performed repeatedly in a short succession (e.g., position updates while a node
+/// is being dragged), see [UndoStack::do_action].
+const COALESCIBLE_EVENTS: [&str; 1] = ["update_position"];
+
+/// The maximal number of actions merged into the entry of a single group (see
+/// [UndoStack::begin_group]). Once reached, the group is closed automatically, so that a group
+/// that is never closed does not keep growing forever.
+const GROUP_ACTION_LIMIT: usize = 1 << 10;
 
 /// The items on the undo/redo stack are pairs of events: one performs the actual action
 /// while the other reverses said action.
@@ -48,6 +60,23 @@ pub struct UndoStack {
     /// just track the `undo_stack`.
     current_payload_size: usize,
 
+    /// Set once some entry was dropped from the bottom of the `undo_stack` due to the limits.
+    /// The oldest remaining entry then marks a hard boundary - the history before it is lost.
+    truncated: bool,
+
+    /// The number of currently open (possibly nested) groups, see [UndoStack::begin_group].
+    group_depth: usize,
+    /// Set once the currently open group has its entry on top of the `undo_stack`.
+    group_started: bool,
+    /// The number of actions recorded in the entry of the currently open group.
+    group_len: usize,
+
+    /// Maximal time between two compatible actions that are coalesced into a single entry.
+    coalesce_window: Duration,
+    /// The time the entry on top of the `undo_stack` was last created or extended by an action
+    /// (`None` if new actions must not be coalesced into it, e.g., after un-doing).
+    last_action_time: Option<Instant>,
+
     undo_stack: VecDeque<UndoStackEntry>,
     redo_stack: VecDeque<UndoStackEntry>,
 }
@@ -58,6 +87,12 @@ impl UndoStack {
             event_limit,
             payload_limit,
             current_payload_size: 0,
+            truncated: false,
+            group_depth: 0,
+            group_started: false,
+            group_len: 0,
+            coalesce_window: DEFAULT_COALESCE_WINDOW,
+            last_action_time: None,
             undo_stack: VecDeque::with_capacity(event_limit),
             redo_stack: VecDeque::with_capacity(event_limit),
         }
@@ -71,9 +106,12 @@ impl UndoStack {
         !self.redo_stack.is_empty()
     }
 
-    /// Remove all elements from the [UndoStack].
+    /// Remove all elements from the [UndoStack]. This also closes all open groups.
     pub fn clear(&mut self) {
         self.current_payload_size = 0;
+        self.truncated = false;
+        self.close_all_groups();
+        self.last_action_time = None;
         self.undo_stack.clear();
         self.redo_stack.clear();
     }
@@ -88,9 +126,88 @@ impl UndoStack {
         self.redo_stack.len()
     }
 
+    /// The total number of entries tracked by this stack (both un-doable and re-doable).
+    pub fn len(&self) -> usize {
+        self.undo_len() + self.redo_len()
+    }
+
+    /// Check whether there are no entries in this stack at all.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check whether some old entries were dropped due to the stack limits, i.e., whether the
+    /// oldest entry on the stack is a hard boundary beyond which nothing can be un-done.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// The maximal number of entries this stack can track.
+    pub fn event_limit(&self) -> usize {
+        self.event_limit
+    }
+
+    /// Change the maximal number of entries this stack can track. If there are currently more
+    /// entries, the oldest ones are dropped (first from the `undo_stack`, then the most distant
+    /// ones from the `redo_stack`).
+    pub fn set_event_limit(&mut self, event_limit: usize) {
+        self.event_limit = event_limit;
+        while self.len() > self.event_limit && self.drop_undo_event().is_some() {
+            debug!("Event limit lowered. Dropping the oldest undo action.");
+        }
+        while self.len() > self.event_limit && self.redo_stack.pop_front().is_some() {
+            debug!("Event limit lowered. Dropping the most distant redo action.");
+        }
+    }
+
+    /// Change the maximal time between two compatible actions that are coalesced into a single
+    /// entry (see [Self::do_action]). Zero window disables coalescing.
+    pub fn set_coalesce_window(&mut self, coalesce_window: Duration) {
+        self.coalesce_window = coalesce_window;
+    }
+
+    /// Start a group of actions. All actions performed until the group is closed (see
+    /// [Self::end_group]) are merged into a single entry, so that they are un-done (or re-done)
+    /// in one step. Groups can be nested, in which case the outermost group is used.
+    ///
+    /// All open groups are closed once un-doing (or re-doing), or once the group reaches
+    /// [GROUP_ACTION_LIMIT] actions.
+    pub fn begin_group(&mut self) {
+        self.group_depth += 1;
+    }
+
+    /// Close the most recently opened group of actions (see [Self::begin_group]). Does nothing
+    /// if there is no open group.
+    pub fn end_group(&mut self) {
+        self.group_depth = self.group_depth.saturating_sub(1);
+        if self.group_depth == 0 {
+            self.close_all_groups();
+        }
+    }
+
+    /// Internal function to close all open groups of actions (see [Self::begin_group]).
+    fn close_all_groups(&mut self) {
+        self.group_depth = 0;
+        self.group_started = false;
+        self.group_len = 0;
+    }
+
+    /// Check whether there is an open group of actions (see [Self::begin_group]).
+    pub fn is_grouping(&self) -> bool {
+        self.group_depth > 0
+    }
+
     /// Notify the undo stack that a new action has been performed. This creates a new stack
     /// entry for this action. Furthermore, it erases any available "redo" actions.
     ///
+    /// If there is an open group (see [Self::begin_group]), only its first action creates a new
+    /// entry, and all the following actions are merged into it.
+    ///
+    /// Outside of groups, consecutive compatible actions (the same event from
+    /// [COALESCIBLE_EVENTS] with the same path and target variable) performed within the
+    /// coalescing window are also merged. The entry then performs the latest action, but its
+    /// reverse still restores the original state.
+    ///
     /// Returns `true` if the events were successfully saved, or `false` if an error occurred,
     /// e.g. due to excessive payload size.
     #[must_use]
@@ -98,6 +215,14 @@ impl UndoStack {
         // Items from the `redo_stack` are no longer relevant.
         self.redo_stack.clear();
 
+        if self.group_started {
+            return self.merge_into_group(perform, reverse);
+        }
+        // Actions of a group must not be coalesced into an entry from before the group.
+        if !self.is_grouping() && self.try_coalesce(&perform) {
+            return true;
+        }
+
         // Drop events even the stack is too deep.
         while self.undo_stack.len() >= self.event_limit {
             let Some(event) = self.drop_undo_event() else {
@@ -145,7 +270,73 @@ impl UndoStack {
             reverse_action: reverse,
         });
         self.current_payload_size += additional_payload;
+        self.group_started = self.is_grouping();
+        self.group_len = 1;
+        self.last_action_time = Some(Instant::now());
+
+        true
+    }
+
+    /// Internal function to coalesce a new action into the entry on top of the `undo_stack`, if
+    /// both are compatible (see [Self::do_action]). Only the "perform" part of the entry is
+    /// replaced, the original reverse action is kept. Returns `true` if the action was coalesced.
+    fn try_coalesce(&mut self, perform: &UserAction) -> bool {
+        let Some(last_time) = self.last_action_time else {
+            return false;
+        };
+        if last_time.elapsed() >= self.coalesce_window {
+            return false;
+        }
+        let Some(entry) = self.undo_stack.back_mut() else {
+            return false;
+        };
+        let new_key = coalescing_key(perform);
+        if new_key.is_none() || new_key != coalescing_key(&entry.perform_action) {
+            return false;
+        }
+
+        self.current_payload_size -= entry.perform_action.byte_size();
+        self.current_payload_size += perform.byte_size();
+        entry.perform_action = perform.clone();
+        self.last_action_time = Some(Instant::now());
+        true
+    }
+
+    /// Internal function to merge a new action into the entry of the currently open group (the
+    /// top of the `undo_stack`). The reverse events of the new action are performed first.
+    fn merge_into_group(&mut self, perform: UserAction, reverse: UserAction) -> bool {
+        // Drop older events if the payloads are too big (but never the group's own entry).
+        let additional_payload = perform.byte_size() + reverse.byte_size();
+        while self.current_payload_size + additional_payload >= self.payload_limit
+            && self.undo_stack.len() > 1
+        {
+            let event = self.drop_undo_event().unwrap();
+            debug!(
+                "Payload size exceeded. Dropping action with {} events.",
+                event.perform_action.events.len()
+            );
+        }
+        if self.current_payload_size + additional_payload >= self.payload_limit {
+            debug!("Cannot merge new undo item into a group. Group payload too large.");
+            return false;
+        }
+
+        let Some(entry) = self.undo_stack.back_mut() else {
+            // The group's entry was removed, start a new one.
+            self.group_started = false;
+            return self.do_action(perform, reverse);
+        };
+        entry.perform_action.events.extend(perform.events);
+        let mut reverse_events = reverse.events;
+        reverse_events.append(&mut entry.reverse_action.events);
+        entry.reverse_action.events = reverse_events;
+        self.current_payload_size += additional_payload;
 
+        self.group_len += 1;
+        if self.group_len >= GROUP_ACTION_LIMIT {
+            debug!("Group action limit reached. Closing all open groups.");
+            self.close_all_groups();
+        }
         true
     }
 
@@ -155,6 +346,9 @@ impl UndoStack {
     #[must_use]
     pub fn undo_action(&mut self) -> Option<UserAction> {
         let entry = self.undo_stack.pop_back()?;
+        // Actions performed after this point cannot be merged into the un-done entry.
+        self.close_all_groups();
+        self.last_action_time = None;
 
         let result = Some(entry.reverse_action.clone());
         self.current_payload_size -= entry.payload_size();
@@ -168,6 +362,8 @@ impl UndoStack {
     #[must_use]
     pub fn redo_action(&mut self) -> Option<UserAction> {
         let entry = self.redo_stack.pop_back()?;
+        self.close_all_groups();
+        self.last_action_time = None;
 
         let result = Some(entry.perform_action.clone());
         self.current_payload_size += entry.payload_size();
@@ -175,18 +371,56 @@ impl UndoStack {
         result
     }
 
-    /// Internal function to drop an [UndoStackEntry] from the `undo_stack`.
+    /// Internal function to drop an [UndoStackEntry] from the `undo_stack`. This makes the
+    /// stack truncated (the history before the remaining entries is lost).
     fn drop_undo_event(&mut self) -> Option<UndoStackEntry> {
         let entry = self.undo_stack.pop_front()?;
+        self.truncated = true;
         self.current_payload_size -= entry.payload_size();
         assert!(self.current_payload_size < self.payload_limit);
         Some(entry)
     }
 }
 
+/// Internal function to compute the key identifying actions that can be coalesced together
+/// (see [UndoStack::do_action]). Only actions consisting of a single event from
+/// [COALESCIBLE_EVENTS] have a key, which is the event's path and the `variable` field of its
+/// payload (if present).
+fn coalescing_key(action: &UserAction) -> Option<(Vec<String>, Option<String>)> {
+    let [event] = action.events.as_slice() else {
+        return None;
+    };
+    let event_name = event.path.last()?;
+    if !COALESCIBLE_EVENTS.contains(&event_name.as_str()) {
+        return None;
+    }
+    let variable = event
+        .payload
+        .as_ref()
+        .and_then(|payload| serde_json::from_str::<serde_json::Value>(payload).ok())
+        .and_then(|value| value.get("variable")?.as_str().map(|v| v.to_string()));
+    Some((event.path.clone(), variable))
+}
+
 impl SessionState for UndoStack {
-    fn perform_event(&mut self, _event: &Event, _at_path: &[&str]) -> Result<Consumed, DynError> {
-        AeonError::throw("`UndoStack` cannot consume events.")
+    /// The only events `UndoStack` consumes are opening and closing groups of actions (see
+    /// [UndoStack::begin_group]). These events themselves bypass the stack.
+    fn perform_event(&mut self, event: &Event, at_path: &[&str]) -> Result<Consumed, DynError> {
+        if event.payload.is_some() {
+            return AeonError::throw("This event to `UndoStack` must carry empty payload.");
+        }
+        match at_path {
+            ["begin_group"] => self.begin_group(),
+            ["end_group"] => self.end_group(),
+            _ => {
+                return AeonError::throw(format!("`UndoStack` cannot consume path `{at_path:?}`."))
+            }
+        }
+        let payload = serde_json::to_string(&self.is_grouping())?;
+        Ok(Consumed::Irreversible {
+            state_change: Event::build(&["undo_stack", "is_grouping"], Some(&payload)),
+            reset: false,
+        })
     }
 
     fn refresh(&self, full_path: &[String], at_path: &[&str]) -> Result<Event, DynError> {
@@ -201,6 +435,18 @@ impl SessionState for UndoStack {
                 path: full_path.to_vec(),
                 payload: serde_json::to_string(&self.can_redo()).ok(),
             }),
+            ["len"] => Ok(Event {
+                path: full_path.to_vec(),
+                payload: serde_json::to_string(&self.len()).ok(),
+            }),
+            ["is_truncated"] => Ok(Event {
+                path: full_path.to_vec(),
+                payload: serde_json::to_string(&self.is_truncated()).ok(),
+            }),
+            ["is_grouping"] => Ok(Event {
+                path: full_path.to_vec(),
+                payload: serde_json::to_string(&self.is_grouping()).ok(),
+            }),
             _ => AeonError::throw(format!("`UndoStack` has no path `{at_path:?}`.")),
         }
     }
@@ -215,7 +461,8 @@ impl Default for UndoStack {
 #[cfg(test)]
 mod tests {
     use crate::app::event::{Event, UserAction};
-    use crate::app::state::_undo_stack::UndoStack;
+    use crate::app::state::_undo_stack::{UndoStack, GROUP_ACTION_LIMIT};
+    use std::time::Duration;
 
     #[test]
     pub fn test_normal_behaviour() {
@@ -286,6 +533,165 @@ mod tests {
         assert_eq!(Some(e3.clone()), stack.redo_action());
     }
 
+    #[test]
+    pub fn test_event_limit_eviction() {
+        let actions: Vec<UserAction> = (0..10)
+            .map(|i| Event::build(&["path"], Some(&format!("payload {i}"))).into())
+            .collect();
+        let mut stack = UndoStack::new(3, 1024);
+
+        // Push past the cap - the stack stops growing, and becomes truncated.
+        for action in actions.iter() {
+            assert!(stack.do_action(action.clone(), action.clone()));
+            assert!(stack.len() <= 3);
+        }
+        assert_eq!(3, stack.len());
+        assert!(stack.is_truncated());
+
+        // The most recent actions can still be un-done (and re-done), but not the older ones.
+        assert_eq!(Some(actions[9].clone()), stack.undo_action());
+        assert_eq!(Some(actions[8].clone()), stack.undo_action());
+        assert_eq!(Some(actions[7].clone()), stack.undo_action());
+        assert!(!stack.can_undo());
+        assert_eq!(None, stack.undo_action());
+        assert_eq!(3, stack.len());
+        assert_eq!(Some(actions[7].clone()), stack.redo_action());
+        assert!(stack.can_redo());
+
+        // Lowering the limit drops the oldest undo entry first, then the most distant redo.
+        stack.set_event_limit(2);
+        assert_eq!((0, 2), (stack.undo_len(), stack.redo_len()));
+        stack.set_event_limit(1);
+        assert_eq!(1, stack.len());
+        assert_eq!(Some(actions[8].clone()), stack.redo_action());
+
+        // Clearing the stack also resets the truncation flag.
+        stack.clear();
+        assert!(stack.is_empty());
+        assert!(!stack.is_truncated());
+    }
+
+    #[test]
+    pub fn test_grouping() {
+        let action = |i: usize| -> UserAction {
+            Event::build(&["path"], Some(&format!("payload {i}"))).into()
+        };
+        let mut stack = UndoStack::default();
+        assert!(stack.do_action(action(0), action(10)));
+
+        // three actions in a (nested) group become a single entry
+        stack.begin_group();
+        assert!(stack.do_action(action(1), action(11)));
+        stack.begin_group();
+        assert!(stack.do_action(action(2), action(12)));
+        stack.end_group();
+        assert!(stack.is_grouping());
+        assert!(stack.do_action(action(3), action(13)));
+        stack.end_group();
+        assert!(!stack.is_grouping());
+        assert_eq!(2, stack.undo_len());
+
+        // the whole group is un-done (in reverse order) and re-done at once
+        let group_perform = UserAction {
+            events: [action(1), action(2), action(3)]
+                .map(|a| a.events[0].clone())
+                .to_vec(),
+        };
+        let group_reverse = UserAction {
+            events: [action(13), action(12), action(11)]
+                .map(|a| a.events[0].clone())
+                .to_vec(),
+        };
+        assert_eq!(Some(group_reverse), stack.undo_action());
+        assert_eq!(Some(group_perform), stack.redo_action());
+
+        // actions after the group are separate entries again
+        assert!(stack.do_action(action(4), action(14)));
+        assert_eq!(3, stack.undo_len());
+        // un-doing closes the open group, new actions are not merged into the un-done entry
+        stack.begin_group();
+        assert!(stack.do_action(action(5), action(15)));
+        assert_eq!(Some(action(15)), stack.undo_action());
+        assert!(!stack.is_grouping());
+        assert!(stack.do_action(action(6), action(16)));
+        assert!(stack.do_action(action(7), action(17)));
+        assert_eq!(Some(action(17)), stack.undo_action());
+        assert_eq!(Some(action(16)), stack.undo_action());
+        assert_eq!(Some(action(14)), stack.undo_action());
+    }
+
+    #[test]
+    /// Test that groups are not merged with coalesced entries, and that unclosed groups are
+    /// closed once they reach the action limit.
+    pub fn test_grouping_bounds() {
+        let position = |x: usize| -> UserAction {
+            let payload = format!("{{\"layout\":\"l\",\"variable\":\"a\",\"px\":{x},\"py\":0}}");
+            Event::build(&["layout", "l", "update_position"], Some(&payload)).into()
+        };
+        let action = |i: usize| -> UserAction {
+            Event::build(&["path"], Some(&format!("payload {i}"))).into()
+        };
+        let mut stack = UndoStack::default();
+
+        // the group does not continue the coalescible entry from before the group
+        assert!(stack.do_action(position(1), position(0)));
+        stack.begin_group();
+        assert!(stack.do_action(position(2), position(1)));
+        assert!(stack.do_action(action(1), action(11)));
+        stack.end_group();
+        assert_eq!(2, stack.undo_len());
+        assert_eq!(
+            Some(UserAction {
+                events: vec![action(11).events[0].clone(), position(1).events[0].clone()],
+            }),
+            stack.undo_action()
+        );
+        assert_eq!(Some(position(0)), stack.undo_action());
+
+        // a group that is never closed is closed automatically once it reaches the limit
+        stack.begin_group();
+        for i in 0..GROUP_ACTION_LIMIT {
+            assert!(stack.do_action(action(i), action(i)));
+        }
+        assert!(!stack.is_grouping());
+        assert!(stack.do_action(action(0), action(0)));
+        assert_eq!(2, stack.undo_len());
+    }
+
+    #[test]
+    pub fn test_coalescing() {
+        let position = |var: &str, x: usize| -> UserAction {
+            let payload =
+                format!("{{\"layout\":\"l\",\"variable\":\"{var}\",\"px\":{x},\"py\":0}}");
+            Event::build(&["layout", "l", "update_position"], Some(&payload)).into()
+        };
+        let mut stack = UndoStack::default();
+
+        // many position updates of the same variable collapse into a single entry
+        for x in 1..=20 {
+            assert!(stack.do_action(position("a", x), position("a", x - 1)));
+        }
+        assert_eq!(1, stack.undo_len());
+        // updates of a different variable (or other actions) start a new entry
+        assert!(stack.do_action(position("b", 1), position("b", 0)));
+        assert!(stack.do_action(position("b", 2), position("b", 1)));
+        assert_eq!(2, stack.undo_len());
+
+        // the entry performs the latest update, but reverses to the original position
+        assert_eq!(Some(position("b", 0)), stack.undo_action());
+        assert_eq!(Some(position("a", 0)), stack.undo_action());
+        assert_eq!(Some(position("a", 20)), stack.redo_action());
+
+        // actions are not coalesced into entries that were re-done
+        assert!(stack.do_action(position("a", 21), position("a", 20)));
+        assert_eq!(2, stack.undo_len());
+
+        // with zero window, nothing is coalesced
+        stack.set_coalesce_window(Duration::ZERO);
+        assert!(stack.do_action(position("a", 22), position("a", 21)));
+        assert_eq!(3, stack.undo_len());
+    }
+
     #[test]
     pub fn test_extreme_limits() {
         let e1: UserAction = Event::build(&[], None).into();
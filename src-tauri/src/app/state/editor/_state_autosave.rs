@@ -0,0 +1,300 @@
+use crate::app::event::Event;
+use crate::app::state::{Consumed, SessionHelper, SessionState};
+use crate::app::{AeonError, DynError};
+use crate::sketchbook::Sketch;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Default minimal number of seconds between two consecutive autosaves.
+const DEFAULT_AUTOSAVE_INTERVAL_SECS: u64 = 30;
+/// Prefix of the names of all autosave files.
+const AUTOSAVE_FILE_PREFIX: &str = "autosave_";
+/// Name of the file recording the time of the last explicit save (in the autosave directory).
+const LAST_SAVE_FILE_NAME: &str = "last_explicit_save";
+
+/// Configuration of the autosave mechanism (set from the frontend).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutosaveConfig {
+    /// Directory where the autosave files are written. The autosave is disabled if `None`.
+    pub directory: Option<String>,
+    /// Minimal number of seconds between two consecutive autosaves.
+    pub interval_secs: u64,
+}
+
+impl Default for AutosaveConfig {
+    /// Default config with the autosave disabled.
+    fn default() -> AutosaveConfig {
+        AutosaveConfig {
+            directory: None,
+            interval_secs: DEFAULT_AUTOSAVE_INTERVAL_SECS,
+        }
+    }
+}
+
+/// The state of the autosave mechanism of an editor session.
+///
+/// The sketch is periodically written to a timestamped file in the native JSON format (see
+/// [Sketch::to_custom_json]), so that it can be recovered via the standard import. Only the
+/// latest autosave file of each session is kept. Explicit saves are recorded too, so that we
+/// can detect autosaves newer than the last explicit save when a new session starts.
+#[derive(Default)]
+pub struct AutosaveState {
+    config: AutosaveConfig,
+    last_autosave: Option<Instant>,
+    last_autosave_file: Option<PathBuf>,
+    /// Set if some change was not autosaved due to throttling (see [Self::flush]).
+    pending: bool,
+    /// Set once the autosave directory was checked for autosaves to recover (this happens
+    /// automatically when the autosave is enabled for the first time in the session).
+    recovery_checked: bool,
+}
+
+impl AutosaveState {
+    /// Get the current autosave configuration.
+    pub fn config(&self) -> &AutosaveConfig {
+        &self.config
+    }
+
+    /// Set new autosave configuration. The throttling interval starts anew.
+    pub fn set_config(&mut self, config: AutosaveConfig) {
+        self.config = config;
+        self.last_autosave = None;
+    }
+
+    /// Write the sketch into a new autosave file, unless the autosave is disabled or the last
+    /// autosave happened less than `interval_secs` ago. The previous autosave file of this
+    /// session is removed. If the autosave is throttled, the change is kept pending until the
+    /// next autosave (see [Self::flush]).
+    ///
+    /// Returns the path to the new autosave file (or `None` if nothing was written).
+    pub fn autosave(
+        &mut self,
+        session_id: &str,
+        sketch: &Sketch,
+    ) -> Result<Option<PathBuf>, String> {
+        let Some(directory) = self.config.directory.as_ref() else {
+            return Ok(None);
+        };
+        let interval = Duration::from_secs(self.config.interval_secs);
+        if let Some(last_autosave) = self.last_autosave {
+            if last_autosave.elapsed() < interval {
+                self.pending = true;
+                return Ok(None);
+            }
+        }
+
+        fs::create_dir_all(directory).map_err(|e| e.to_string())?;
+        // files are named by their timestamps, so two autosaves must not share a millisecond
+        // (the new file would be removed as the previous one)
+        let previous_timestamp = self
+            .last_autosave_file
+            .as_deref()
+            .and_then(parse_autosave_timestamp);
+        let mut timestamp = now_millis();
+        while previous_timestamp == Some(timestamp) {
+            thread::sleep(Duration::from_millis(1));
+            timestamp = now_millis();
+        }
+        let file_name = format!("{AUTOSAVE_FILE_PREFIX}{session_id}_{timestamp}.json");
+        let file_path = Path::new(directory).join(file_name);
+        fs::write(&file_path, sketch.to_custom_json()).map_err(|e| e.to_string())?;
+
+        // the older autosave is not needed anymore (it is fine if it was already removed)
+        if let Some(previous_file) = self.last_autosave_file.replace(file_path.clone()) {
+            let _ = fs::remove_file(previous_file);
+        }
+        self.last_autosave = Some(Instant::now());
+        self.pending = false;
+        Ok(Some(file_path))
+    }
+
+    /// Write the trailing autosave, if some change was not autosaved due to throttling (and
+    /// the throttling interval has already passed). This should be invoked periodically, so
+    /// that the latest changes are saved even if no further action is performed.
+    ///
+    /// Returns the path to the new autosave file (or `None` if nothing was written).
+    pub fn flush(&mut self, session_id: &str, sketch: &Sketch) -> Result<Option<PathBuf>, String> {
+        if !self.pending {
+            return Ok(None);
+        }
+        self.autosave(session_id, sketch)
+    }
+
+    /// Record that the sketch was explicitly saved (at the current time). Nothing happens if
+    /// the autosave is disabled.
+    pub fn record_explicit_save(&self) -> Result<(), String> {
+        let Some(directory) = self.config.directory.as_ref() else {
+            return Ok(());
+        };
+        fs::create_dir_all(directory).map_err(|e| e.to_string())?;
+        let marker_path = Path::new(directory).join(LAST_SAVE_FILE_NAME);
+        fs::write(marker_path, now_millis().to_string()).map_err(|e| e.to_string())
+    }
+
+    /// Find the latest autosave file that is newer than the last explicit save, if there is any.
+    /// Autosave files written by this instance are ignored.
+    pub fn find_recovery(&self) -> Result<Option<PathBuf>, String> {
+        let Some(directory) = self.config.directory.as_ref() else {
+            return Ok(None);
+        };
+        let directory = Path::new(directory);
+        if !directory.is_dir() {
+            return Ok(None);
+        }
+
+        // if there is no valid record of an explicit save, any autosave is newer
+        let last_save = fs::read_to_string(directory.join(LAST_SAVE_FILE_NAME))
+            .ok()
+            .and_then(|content| content.trim().parse::<u128>().ok())
+            .unwrap_or(0);
+
+        let mut latest: Option<(u128, PathBuf)> = None;
+        for entry in fs::read_dir(directory).map_err(|e| e.to_string())? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if self.last_autosave_file.as_ref() == Some(&path) {
+                continue;
+            }
+            let Some(timestamp) = parse_autosave_timestamp(&path) else {
+                continue;
+            };
+            if timestamp > last_save && latest.as_ref().is_none_or(|(t, _)| timestamp > *t) {
+                latest = Some((timestamp, path));
+            }
+        }
+        Ok(latest.map(|(_, path)| path))
+    }
+}
+
+impl SessionHelper for AutosaveState {}
+
+impl SessionState for AutosaveState {
+    fn perform_event(&mut self, event: &Event, at_path: &[&str]) -> Result<Consumed, DynError> {
+        let component = "autosave";
+        if Self::starts_with("set_config", at_path).is_some() {
+            Self::assert_path_length(at_path, 1, component)?;
+            let payload = Self::clone_payload_str(event, component)?;
+            let config: AutosaveConfig = serde_json::from_str(&payload)?;
+
+            // when the autosave is enabled for the first time (at the start of the session),
+            // the config is set and then the autosaves are checked for recovery (restarted
+            // events are performed from last to first)
+            if config.directory.is_some() && !self.recovery_checked {
+                self.recovery_checked = true;
+                let check_event = Event::build(&["autosave", "check_recovery"], None);
+                return Ok(Consumed::Restart(vec![check_event, event.clone()]));
+            }
+            self.set_config(config);
+
+            let config_json = serde_json::to_string(&self.config)?;
+            let state_change = Event::build(&["autosave", "config"], Some(&config_json));
+            // configuration is not part of the sketch, there is nothing to undo
+            Ok(Consumed::Irreversible {
+                state_change,
+                reset: false,
+            })
+        } else if Self::starts_with("check_recovery", at_path).is_some() {
+            Self::assert_path_length(at_path, 1, component)?;
+            Self::assert_payload_empty(event, component)?;
+            match self.find_recovery()? {
+                Some(path) => {
+                    let path_json = serde_json::to_string(&path.to_string_lossy())?;
+                    let state_change =
+                        Event::build(&["autosave", "recovery_available"], Some(&path_json));
+                    Ok(Consumed::Irreversible {
+                        state_change,
+                        reset: false,
+                    })
+                }
+                None => Ok(Consumed::NoChange),
+            }
+        } else {
+            Self::invalid_path_error_specific(at_path, component)
+        }
+    }
+
+    fn refresh(&self, full_path: &[String], at_path: &[&str]) -> Result<Event, DynError> {
+        if Self::starts_with("config", at_path).is_some() {
+            let config_json = serde_json::to_string(&self.config)?;
+            Ok(Event {
+                path: full_path.to_vec(),
+                payload: Some(config_json),
+            })
+        } else {
+            AeonError::throw(format!("`autosave` cannot refresh path `{at_path:?}`."))
+        }
+    }
+}
+
+/// **(internal)** Current time as the number of milliseconds since the UNIX epoch.
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+/// **(internal)** Parse the timestamp from a name of an autosave file (in the format
+/// `autosave_<session_id>_<timestamp>.json`), or return `None` if it is not an autosave file.
+fn parse_autosave_timestamp(path: &Path) -> Option<u128> {
+    let file_name = path.file_name()?.to_str()?;
+    let stem = file_name
+        .strip_prefix(AUTOSAVE_FILE_PREFIX)?
+        .strip_suffix(".json")?;
+    let (_, timestamp) = stem.rsplit_once('_')?;
+    timestamp.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::state::editor::{AutosaveConfig, AutosaveState};
+    use crate::sketchbook::Sketch;
+    use std::fs;
+
+    #[test]
+    /// Test writing (throttled) autosaves, and finding autosaves newer than the explicit save.
+    fn test_autosave_and_recovery() {
+        let directory =
+            std::env::temp_dir().join(format!("sketchbook_test_autosave_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        let config = AutosaveConfig {
+            directory: Some(directory.to_string_lossy().to_string()),
+            interval_secs: 3600,
+        };
+        let sketch = Sketch::from_aeon("A -> A").unwrap();
+
+        // autosave is disabled by default
+        let mut autosave = AutosaveState::default();
+        assert_eq!(autosave.autosave("editor", &sketch).unwrap(), None);
+
+        // first autosave is written, and it reloads as the same sketch
+        autosave.set_config(config.clone());
+        let path = autosave.autosave("editor", &sketch).unwrap().unwrap();
+        let reloaded = Sketch::from_custom_json(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(reloaded, sketch);
+        // the next one is throttled (and stays pending until the interval passes)
+        assert_eq!(autosave.autosave("editor", &sketch).unwrap(), None);
+        assert_eq!(autosave.flush("editor", &sketch).unwrap(), None);
+        autosave.set_config(config.clone());
+        let trailing_path = autosave.flush("editor", &sketch).unwrap().unwrap();
+        assert!(!path.exists());
+        // nothing is pending anymore
+        autosave.set_config(config.clone());
+        assert_eq!(autosave.flush("editor", &sketch).unwrap(), None);
+
+        // a new session can recover the autosave, until there is an explicit save
+        let mut new_session_autosave = AutosaveState::default();
+        new_session_autosave.set_config(config);
+        assert_eq!(
+            new_session_autosave.find_recovery().unwrap(),
+            Some(trailing_path)
+        );
+        // autosaves from the same millisecond as the explicit save are covered by it
+        new_session_autosave.record_explicit_save().unwrap();
+        assert_eq!(new_session_autosave.find_recovery().unwrap(), None);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
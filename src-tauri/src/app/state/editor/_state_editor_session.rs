@@ -1,10 +1,11 @@
 use crate::app::event::{Event, SessionMessage, StateChange};
 use crate::app::state::_undo_stack::UndoStack;
-use crate::app::state::editor::TabBarState;
+use crate::app::state::editor::{AutosaveState, TabBarState};
 use crate::app::state::{Consumed, SessionHelper, SessionState, StackSession};
 use crate::app::{AeonError, DynError};
 use crate::debug;
 use crate::sketchbook::Sketch;
+use std::path::PathBuf;
 
 /// The state of one editor session.
 ///
@@ -15,6 +16,7 @@ pub struct EditorSession {
     undo_stack: UndoStack,
     tab_bar: TabBarState,
     sketch: Sketch,
+    autosave: AutosaveState,
 }
 
 impl EditorSession {
@@ -24,6 +26,7 @@ impl EditorSession {
             undo_stack: UndoStack::default(),
             tab_bar: TabBarState::default(),
             sketch: Sketch::default(),
+            autosave: AutosaveState::default(),
         }
     }
 }
@@ -74,6 +77,24 @@ impl StackSession for EditorSession {
     fn undo_stack(&self) -> &UndoStack {
         &self.undo_stack
     }
+
+    /// Autosave the sketch (the autosave is throttled, see [AutosaveState::autosave]). Failing
+    /// to autosave must not affect the action itself, so errors are only logged.
+    fn after_reversible_action(&mut self) {
+        let result = self.autosave.autosave(&self.id, &self.sketch);
+        self.log_autosave_result(result);
+    }
+}
+
+impl EditorSession {
+    /// **(internal)** Log the result of an autosave attempt.
+    fn log_autosave_result(&self, result: Result<Option<PathBuf>, String>) {
+        match result {
+            Ok(Some(path)) => debug!("Sketch autosaved to {}.", path.display()),
+            Ok(None) => {}
+            Err(e) => debug!("Autosave of session {} failed: {e}", self.id),
+        }
+    }
 }
 
 impl SessionHelper for EditorSession {}
@@ -84,8 +105,27 @@ impl SessionState for EditorSession {
             self.undo_stack.perform_event(event, at_path)
         } else if let Some(at_path) = Self::starts_with("tab_bar", at_path) {
             self.tab_bar.perform_event(event, at_path)
-        } else if let Some(at_path) = Self::starts_with("sketch", at_path) {
-            self.sketch.perform_event(event, at_path)
+        } else if let Some(at_path) = Self::starts_with("autosave", at_path) {
+            // the trailing autosave needs the sketch, the rest is handled by the autosave state
+            if Self::starts_with("flush", at_path).is_some() {
+                Self::assert_path_length(at_path, 1, "autosave")?;
+                Self::assert_payload_empty(event, "autosave")?;
+                let result = self.autosave.flush(&self.id, &self.sketch);
+                self.log_autosave_result(result);
+                return Ok(Consumed::NoChange);
+            }
+            self.autosave.perform_event(event, at_path)
+        } else if let Some(sketch_path) = Self::starts_with("sketch", at_path) {
+            let result = self.sketch.perform_event(event, sketch_path)?;
+            // explicit saves are recorded, so that older autosaves are not offered for recovery
+            if let Consumed::Irreversible { state_change, .. } = &result {
+                if state_change.path == ["sketch", "sketch_saved"] {
+                    if let Err(e) = self.autosave.record_explicit_save() {
+                        debug!("Failed to record explicit save of session {}: {e}", self.id);
+                    }
+                }
+            }
+            Ok(result)
         } else {
             Self::invalid_path_error_generic(at_path)
         }
@@ -96,6 +136,8 @@ impl SessionState for EditorSession {
             self.undo_stack.refresh(full_path, at_path)
         } else if let Some(at_path) = Self::starts_with("tab_bar", at_path) {
             self.tab_bar.refresh(full_path, at_path)
+        } else if let Some(at_path) = Self::starts_with("autosave", at_path) {
+            self.autosave.refresh(full_path, at_path)
         } else if let Some(at_path) = Self::starts_with("sketch", at_path) {
             self.sketch.refresh(full_path, at_path)
         } else {
@@ -103,3 +145,159 @@ impl SessionState for EditorSession {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::app::event::{Event, UserAction};
+    use crate::app::state::editor::EditorSession;
+    use crate::app::state::StackSession;
+    use crate::sketchbook::model::ModelState;
+    use crate::sketchbook::Sketch;
+    use std::fs;
+
+    #[test]
+    /// Test that a compound action (variable removal, which is restarted with many sub-events)
+    /// is un-done and re-done in a single step.
+    fn test_undo_grouped_removal() {
+        let mut session = EditorSession::new("editor");
+        session.sketch = Sketch::from_aeon("A -> B\nB -| A\nA -> A").unwrap();
+        let var_a = session.sketch.model.get_var_id("A").unwrap();
+
+        let remove: UserAction =
+            Event::build(&["sketch", "model", "variable", "A", "remove"], None).into();
+        session.perform_action(&remove).unwrap();
+        assert!(!session.sketch.model.is_valid_var_id(&var_a));
+        assert_eq!(session.sketch.model.num_regulations(), 0);
+        assert_eq!(session.undo_stack.undo_len(), 1);
+
+        let undo: UserAction = Event::build(&["undo_stack", "undo"], None).into();
+        session.perform_action(&undo).unwrap();
+        assert!(session.sketch.model.is_valid_var_id(&var_a));
+        assert_eq!(session.sketch.model.num_regulations(), 3);
+        assert_eq!(session.undo_stack.undo_len(), 0);
+
+        let redo: UserAction = Event::build(&["undo_stack", "redo"], None).into();
+        session.perform_action(&redo).unwrap();
+        assert!(!session.sketch.model.is_valid_var_id(&var_a));
+        assert_eq!(session.undo_stack.undo_len(), 1);
+    }
+
+    #[test]
+    /// Test grouping several separate actions via events to the undo stack.
+    fn test_undo_explicit_group() {
+        let mut session = EditorSession::new("editor");
+        session.sketch = Sketch::from_aeon("A -> B\nB -| A\nC -> C").unwrap();
+        let action = |path: &[&str]| -> UserAction { Event::build(path, None).into() };
+
+        session
+            .perform_action(&action(&["undo_stack", "begin_group"]))
+            .unwrap();
+        for var in ["A", "C"] {
+            let path = ["sketch", "model", "variable", var, "remove"];
+            session.perform_action(&action(&path)).unwrap();
+        }
+        session
+            .perform_action(&action(&["undo_stack", "end_group"]))
+            .unwrap();
+        assert_eq!(session.sketch.model.num_vars(), 1);
+        assert_eq!(session.undo_stack.undo_len(), 1);
+
+        session
+            .perform_action(&action(&["undo_stack", "undo"]))
+            .unwrap();
+        assert_eq!(session.sketch.model.num_vars(), 3);
+        assert_eq!(session.sketch.model.num_regulations(), 3);
+    }
+
+    #[test]
+    /// Test that many position updates of a node (e.g., during dragging) are un-done at once.
+    fn test_undo_coalesced_positions() {
+        let mut session = EditorSession::new("editor");
+        session.sketch = Sketch::from_aeon("A -> B\nB -| A").unwrap();
+        let model = &session.sketch.model;
+        let layout_id = ModelState::get_default_layout_id();
+        let var_a = model.get_var_id("A").unwrap();
+        let orig_position = model.get_node_position(&layout_id, &var_a).unwrap().clone();
+
+        let path = [
+            "sketch",
+            "model",
+            "layout",
+            layout_id.as_str(),
+            "update_position",
+        ];
+        for i in 1..=30 {
+            let payload = format!(
+                "{{\"layout\":\"{layout_id}\",\"variable\":\"A\",\"px\":{i}.0,\"py\":{i}.0}}"
+            );
+            let update: UserAction = Event::build(&path, Some(&payload)).into();
+            session.perform_action(&update).unwrap();
+        }
+        assert_eq!(session.undo_stack.undo_len(), 1);
+
+        let undo: UserAction = Event::build(&["undo_stack", "undo"], None).into();
+        session.perform_action(&undo).unwrap();
+        let model = &session.sketch.model;
+        let position = model.get_node_position(&layout_id, &var_a).unwrap();
+        assert_eq!(position, &orig_position);
+        assert!(!session.undo_stack.can_undo());
+    }
+
+    #[test]
+    /// Test that recovery is offered when the autosave is enabled, and that explicit saves and
+    /// un-done actions are handled by the autosave.
+    fn test_autosave_in_session() {
+        let directory = std::env::temp_dir().join(format!(
+            "sketchbook_test_session_autosave_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&directory);
+        let directory_str = directory.to_string_lossy().to_string();
+        let config_payload = format!(
+            "{{\"directory\":{},\"interval_secs\":0}}",
+            serde_json::to_string(&directory_str).unwrap()
+        );
+        let set_config: UserAction =
+            Event::build(&["autosave", "set_config"], Some(&config_payload)).into();
+        let remove: UserAction =
+            Event::build(&["sketch", "model", "variable", "A", "remove"], None).into();
+        let undo: UserAction = Event::build(&["undo_stack", "undo"], None).into();
+
+        // the first session autosaves both the action and its undo
+        let mut session = EditorSession::new("editor_1");
+        session.sketch = Sketch::from_aeon("A -> B\nB -| A").unwrap();
+        session.perform_action(&set_config).unwrap();
+        session.perform_action(&remove).unwrap();
+        session.perform_action(&undo).unwrap();
+        let autosave_file = fs::read_dir(&directory).unwrap().next().unwrap().unwrap();
+        let autosaved = fs::read_to_string(autosave_file.path()).unwrap();
+        assert_eq!(
+            Sketch::from_custom_json(&autosaved).unwrap(),
+            session.sketch
+        );
+
+        // a new session is offered the recovery right when the autosave is enabled
+        let mut new_session = EditorSession::new("editor_2");
+        let state_change = new_session.perform_action(&set_config).unwrap();
+        let paths: Vec<String> = state_change
+            .events
+            .iter()
+            .map(|e| e.path.join("/"))
+            .collect();
+        assert_eq!(
+            paths,
+            vec!["autosave/config", "autosave/recovery_available"]
+        );
+
+        // once the sketch is explicitly saved, the recovery is not offered anymore
+        let save_path = directory.join("saved.json").to_string_lossy().to_string();
+        let export: UserAction =
+            Event::build(&["sketch", "export_sketch"], Some(&save_path)).into();
+        new_session.perform_action(&export).unwrap();
+        let check: UserAction = Event::build(&["autosave", "check_recovery"], None).into();
+        let state_change = new_session.perform_action(&check).unwrap();
+        assert!(state_change.events.is_empty());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
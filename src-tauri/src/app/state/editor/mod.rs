@@ -1,7 +1,10 @@
+/// Declares [AutosaveState]: the state of the periodic autosave of the edited sketch.
+mod _state_autosave;
 /// Declares [EditorSession]: the root state object of the sketchbook editor.
 mod _state_editor_session;
 /// Declares [TabBarState]: the state object of the main tab navigation element.
 mod _state_tab_bar;
 
+pub use _state_autosave::{AutosaveConfig, AutosaveState};
 pub use _state_editor_session::EditorSession;
 pub use _state_tab_bar::TabBarState;
@@ -152,6 +152,8 @@ pub trait StackSession: SessionState {
                     };
                     let mut state_change = self.perform_categorized_action(&action, true)?;
                     self.append_stack_updates(&mut state_change.events);
+                    // un-doing (or re-doing) changes the state the same way as other actions
+                    self.after_reversible_action();
                     return Ok(state_change);
                 }
             }
@@ -177,7 +179,7 @@ pub trait StackSession: SessionState {
         // The events representing successful state changes.
         let mut state_changes: Vec<Event> = Vec::new();
         // The events that can be used to create a redo stack entry if the action is reversible.
-        let mut reverse: Option<Vec<(Event, Event)>> =
+        let mut reverse: Option<Vec<(UserAction, UserAction)>> =
             if ignore_stack { None } else { Some(Vec::new()) };
         let mut reset_stack = false;
 
@@ -205,6 +207,17 @@ pub trait StackSession: SessionState {
                     state_changes.push(state_change);
                     if let Some(reverse) = reverse.as_mut() {
                         // If we can reverse this action, save the events.
+                        let (perform, reverse_event) = perform_reverse;
+                        reverse.push((perform.into(), reverse_event.into()));
+                    }
+                }
+                Consumed::ReversibleGroup {
+                    state_changes: group_changes,
+                    perform_reverse,
+                } => {
+                    state_changes.extend(group_changes);
+                    if let Some(reverse) = reverse.as_mut() {
+                        // The whole group is saved as a single pair of (compound) actions.
                         reverse.push(perform_reverse);
                     }
                 }
@@ -264,18 +277,22 @@ pub trait StackSession: SessionState {
                 let mut perform = Vec::new();
                 let mut reverse = Vec::new();
                 for (p, r) in events {
-                    perform.push(p);
-                    reverse.push(r);
+                    perform.extend(p.events);
+                    reverse.push(r.events);
                 }
                 // Obviously, the "reverse" events need to be execute in the opposite order
-                // compared to the "perform" events.
+                // compared to the "perform" events (the reverse events of a group are already
+                // in the correct order).
                 reverse.reverse();
                 let perform = UserAction { events: perform };
-                let reverse = UserAction { events: reverse };
+                let reverse = UserAction {
+                    events: reverse.into_iter().flatten().collect(),
+                };
                 if !self.undo_stack_mut().do_action(perform, reverse) {
                     // TODO: Not much we can do here, maybe except issuing a warning.
                     self.undo_stack_mut().clear();
                 }
+                self.after_reversible_action();
 
                 // Notify about the changes in the stack state.
                 // TODO: Maybe we don't need to emit this always.
@@ -294,6 +311,11 @@ pub trait StackSession: SessionState {
         })
     }
 
+    /// A hook invoked after each successful reversible action (i.e., an action that was
+    /// recorded to the undo stack), and after each un-done or re-done action. Does nothing by
+    /// default.
+    fn after_reversible_action(&mut self) {}
+
     fn append_stack_updates(&self, state_changes: &mut Vec<Event>) {
         let can_undo = serde_json::to_string(&self.undo_stack().can_undo());
         let can_redo = serde_json::to_string(&self.undo_stack().can_redo());
@@ -1,7 +1,10 @@
 use super::utils::load_test_sketch;
-use crate::inference::_test_inference::utils::add_dyn_prop_and_infer;
+use crate::inference::_test_inference::utils::{add_dyn_prop_and_infer, run_inference_of_type};
+use crate::inference::inference_type::InferenceType::TrapSpaceInference;
+use crate::sketchbook::properties::dynamic_props::AbsenceMode;
 use crate::sketchbook::properties::shortcuts::*;
 use crate::sketchbook::properties::DynProperty;
+use std::collections::BTreeMap;
 
 #[test]
 /// Test inference using the test model with generic properties in HCTL.
@@ -129,6 +132,30 @@ fn inference_template_fixed_point_wildcard() {
     }
 }
 
+#[test]
+/// Test inference using the test model with steady-state template properties.
+/// For fully specified observations, results match fixed-point properties, and for partially
+/// specified observations, they match (general) trap-space properties.
+fn inference_template_steady_state() {
+    for sketch_idx in [1, 2] {
+        // 1111 is a steady state
+        let sketch = load_test_sketch(sketch_idx);
+        let id = "steady_state_1111";
+        let data_id = sketch.observations.get_dataset_id("data_fp").unwrap();
+        let obs_id = sketch.observations.get_obs_id("data_fp", "ones").unwrap();
+        let property = DynProperty::mk_steady_state(id, Some(data_id), Some(obs_id));
+        assert_eq!(add_dyn_prop_and_infer(sketch, property, id), 4);
+
+        // values A, B, C are never flipped in 111*
+        let sketch = load_test_sketch(sketch_idx);
+        let id = "steady_state_111X";
+        let data_id = sketch.observations.get_dataset_id("data_mts").unwrap();
+        let obs_id = sketch.observations.get_obs_id("data_mts", "abc").unwrap();
+        let property = DynProperty::mk_steady_state(id, Some(data_id), Some(obs_id));
+        assert_eq!(add_dyn_prop_and_infer(sketch, property, id), 4);
+    }
+}
+
 #[test]
 /// Test inference using the test model with attractor template properties.
 fn inference_template_attractor() {
@@ -220,6 +247,33 @@ fn inference_template_trap_space_wildcard() {
     }
 }
 
+#[test]
+/// Test the trap-space inference mode. Results for trap-space properties should match the full
+/// inference (see [inference_template_trap_space]), and other properties should be rejected.
+fn inference_trap_space_mode() {
+    for sketch_idx in [1, 2] {
+        let mut sketch = load_test_sketch(sketch_idx);
+        let data_id = sketch.observations.get_dataset_id("data_mts").unwrap();
+        let obs_id = sketch.observations.get_obs_id("data_mts", "abc").unwrap();
+        let property = DynProperty::mk_trap_space("ts", Some(data_id), Some(obs_id), true, true);
+        sketch
+            .properties
+            .add_dynamic_by_str("has_111X_mts", property)
+            .unwrap();
+        let results = run_inference_of_type(sketch.clone(), TrapSpaceInference).unwrap();
+        assert_eq!(results.num_sat_networks, 2);
+
+        // attractor-based properties are not supported in this mode
+        let property = mk_hctl_prop("3{x}: @{x}: AG EF {x}").unwrap();
+        sketch
+            .properties
+            .add_dynamic_by_str("has_attr", property)
+            .unwrap();
+        let err = run_inference_of_type(sketch, TrapSpaceInference).unwrap_err();
+        assert!(err.contains("`has_attr` is not supported in trap-space inference mode"));
+    }
+}
+
 #[test]
 /// Test inference using the test model with trajectory template properties.
 fn inference_template_trajectory() {
@@ -253,3 +307,164 @@ fn inference_template_trajectory_wildcard() {
         assert_eq!(add_dyn_prop_and_infer(sketch, property, id), 16);
     }
 }
+
+#[test]
+/// Test inference using the test model with reachable observations template properties.
+/// Results are compared with equivalent properties in HCTL (conjunctions of reachability
+/// of all observations).
+fn inference_template_obs_reachable() {
+    for sketch_idx in [1, 2] {
+        // a single observation is always reachable (from its own state), so all candidates remain
+        let sketch = load_test_sketch(sketch_idx);
+        let id = "reach_one";
+        let data_id = sketch.observations.get_dataset_id("data_fp").unwrap();
+        let property = DynProperty::try_mk_obs_reachable(id, Some(data_id), 1).unwrap();
+        assert_eq!(add_dyn_prop_and_infer(sketch, property, id), 32);
+
+        // both fixed-point observations must be reachable from some state
+        let sketch = load_test_sketch(sketch_idx);
+        let id = "reach_fp";
+        let data_id = sketch.observations.get_dataset_id("data_fp").unwrap();
+        let property = DynProperty::try_mk_obs_reachable(id, Some(data_id), 2).unwrap();
+        let num_template = add_dyn_prop_and_infer(sketch, property, id);
+        let sketch = load_test_sketch(sketch_idx);
+        let formula = "3{x}: @{x}: (EF (A & B & C & D) & EF (~A & ~B & ~C & ~D))";
+        let property = mk_hctl_prop(formula).unwrap();
+        assert_eq!(add_dyn_prop_and_infer(sketch, property, id), num_template);
+
+        // all time-series observations must be reachable from some state (in any order)
+        let sketch = load_test_sketch(sketch_idx);
+        let id = "reach_time_series";
+        let data_id = sketch
+            .observations
+            .get_dataset_id("data_time_series")
+            .unwrap();
+        let property = DynProperty::try_mk_obs_reachable(id, Some(data_id), 4).unwrap();
+        let num_template = add_dyn_prop_and_infer(sketch, property, id);
+        let sketch = load_test_sketch(sketch_idx);
+        let formula = "3{x}: @{x}: (EF (A & ~B & ~C & ~D) & EF (A & B & ~C & ~D) & EF (A & B & C & ~D) & EF (A & B & C & D))";
+        let property = mk_hctl_prop(formula).unwrap();
+        assert_eq!(add_dyn_prop_and_infer(sketch, property, id), num_template);
+        // this is weaker than requiring a trajectory through the observations (in the given order)
+        assert!(num_template >= 16);
+
+        // the count can not exceed the size of the dataset
+        let mut sketch = load_test_sketch(sketch_idx);
+        let data_id = sketch.observations.get_dataset_id("data_fp").unwrap();
+        let property = DynProperty::try_mk_obs_reachable(id, Some(data_id), 3).unwrap();
+        sketch
+            .properties
+            .add_dynamic_by_str("too_many", property)
+            .unwrap();
+        assert!(sketch.assert_consistency().is_err());
+    }
+}
+
+#[test]
+/// Test inference using the test model with reachable observations template properties, where
+/// the initial states are restricted by the sketch's initial subspace. Results are compared with
+/// equivalent properties in HCTL (these are not affected by the initial subspace).
+fn inference_obs_reachable_initial_subspace() {
+    for sketch_idx in [1, 2] {
+        // both fixed-point observations must be reachable from some state with `A` and `B` false
+        let mut sketch = load_test_sketch(sketch_idx);
+        let subspace = BTreeMap::from([("A".to_string(), false), ("B".to_string(), false)]);
+        sketch.set_initial_subspace_by_str(&subspace).unwrap();
+        let id = "reach_fp";
+        let data_id = sketch.observations.get_dataset_id("data_fp").unwrap();
+        let property = DynProperty::try_mk_obs_reachable(id, Some(data_id), 2).unwrap();
+        let num_template = add_dyn_prop_and_infer(sketch, property, id);
+        let sketch = load_test_sketch(sketch_idx);
+        let formula = "3{x}: @{x}: (~A & ~B & EF (A & B & C & D) & EF (~A & ~B & ~C & ~D))";
+        let property = mk_hctl_prop(formula).unwrap();
+        assert_eq!(add_dyn_prop_and_infer(sketch, property, id), num_template);
+    }
+}
+
+#[test]
+/// Test inference using the test model with template properties requiring reachability of one
+/// dataset from another. Results are compared with equivalent properties in HCTL.
+fn inference_template_reachable_from_dataset() {
+    for sketch_idx in [1, 2] {
+        // every fixed-point observation must be reachable from some time-series observation
+        let sketch = load_test_sketch(sketch_idx);
+        let id = "fp_from_time_series";
+        let initial_id = sketch
+            .observations
+            .get_dataset_id("data_time_series")
+            .unwrap();
+        let target_id = sketch.observations.get_dataset_id("data_fp").unwrap();
+        let property =
+            DynProperty::mk_reachable_from_dataset(id, Some(initial_id), Some(target_id));
+        let num_template = add_dyn_prop_and_infer(sketch, property, id);
+        let sketch = load_test_sketch(sketch_idx);
+        let init = "((A & ~B & ~C & ~D) | (A & B & ~C & ~D) | (A & B & C & ~D) | (A & B & C & D))";
+        let formula = format!(
+            "(3{{x}}: @{{x}}: {init} & EF (A & B & C & D)) & (3{{x}}: @{{x}}: {init} & EF (~A & ~B & ~C & ~D))"
+        );
+        let property = mk_hctl_prop(&formula).unwrap();
+        assert_eq!(add_dyn_prop_and_infer(sketch, property, id), num_template);
+
+        // a dataset is always reachable from itself
+        let sketch = load_test_sketch(sketch_idx);
+        let id = "fp_from_fp";
+        let data_id = sketch.observations.get_dataset_id("data_fp").unwrap();
+        let property =
+            DynProperty::mk_reachable_from_dataset(id, Some(data_id.clone()), Some(data_id));
+        assert_eq!(add_dyn_prop_and_infer(sketch, property, id), 32);
+
+        // both datasets must be filled
+        let mut sketch = load_test_sketch(sketch_idx);
+        let data_id = sketch.observations.get_dataset_id("data_fp").unwrap();
+        let property = DynProperty::mk_reachable_from_dataset(id, Some(data_id), None);
+        sketch
+            .properties
+            .add_dynamic_by_str("incomplete", property)
+            .unwrap();
+        assert!(sketch.assert_consistency().is_err());
+    }
+}
+
+#[test]
+/// Test inference using the test model with template properties requiring absence of an
+/// observation. Results are compared with equivalent properties in HCTL, and with the
+/// complementary attractor template.
+fn inference_template_observation_absent() {
+    for sketch_idx in [1, 2] {
+        // state 1111 can not be reached from any other state
+        let sketch = load_test_sketch(sketch_idx);
+        let id = "unreachable_1111";
+        let data_id = sketch.observations.get_dataset_id("data_fp").unwrap();
+        let obs_id = sketch.observations.get_obs_id("data_fp", "ones").unwrap();
+        let mode = AbsenceMode::Unreachable;
+        let property = DynProperty::mk_observation_absent(id, Some(data_id), Some(obs_id), mode);
+        let num_template = add_dyn_prop_and_infer(sketch, property, id);
+        let sketch = load_test_sketch(sketch_idx);
+        let formula = "~(3{x}: @{x}: ~(A & B & C & D) & EF (A & B & C & D))";
+        let property = mk_hctl_prop(formula).unwrap();
+        assert_eq!(add_dyn_prop_and_infer(sketch, property, id), num_template);
+
+        // state 1111 is not part of an attractor (complement of `inference_template_attractor`)
+        let sketch = load_test_sketch(sketch_idx);
+        let id = "not_in_attractor_1111";
+        let data_id = sketch.observations.get_dataset_id("data_fp").unwrap();
+        let obs_id = sketch.observations.get_obs_id("data_fp", "ones").unwrap();
+        let mode = AbsenceMode::NotAttractor;
+        let property = DynProperty::mk_observation_absent(id, Some(data_id), Some(obs_id), mode);
+        assert_eq!(add_dyn_prop_and_infer(sketch, property, id), 32 - 19);
+
+        // combined with the attractor template for the same observation, nothing is satisfiable
+        let mut sketch = load_test_sketch(sketch_idx);
+        let data_id = sketch.observations.get_dataset_id("data_fp").unwrap();
+        let obs_id = sketch.observations.get_obs_id("data_fp", "ones").unwrap();
+        let attr_property =
+            DynProperty::mk_has_attractor("attr", Some(data_id.clone()), Some(obs_id.clone()));
+        sketch
+            .properties
+            .add_dynamic_by_str("in_attractor_1111", attr_property)
+            .unwrap();
+        let mode = AbsenceMode::NotAttractor;
+        let property = DynProperty::mk_observation_absent(id, Some(data_id), Some(obs_id), mode);
+        assert_eq!(add_dyn_prop_and_infer(sketch, property, id), 0);
+    }
+}
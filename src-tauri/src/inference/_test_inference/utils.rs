@@ -62,6 +62,19 @@ pub(super) fn run_inference_check_statuses(
     results.unwrap()
 }
 
+/// Wrapper to check sketch consistency, and run the given type of inference on the sketch.
+/// Unlike [run_inference], the result is returned as is (it can be an error).
+pub(super) fn run_inference_of_type(
+    sketch: Sketch,
+    inference_type: InferenceType,
+) -> Result<InferenceResults, String> {
+    assert!(sketch.assert_consistency().is_ok());
+
+    let (send_channel, _rec_channel): (Sender<String>, Receiver<String>) = mpsc::channel();
+    let mut solver = InferenceSolver::new(send_channel);
+    solver.run_inference_modular(inference_type, sketch, true, true)
+}
+
 /// Wrapper to apply an event, and if the result is `Consumed::Restart`, apply
 /// all the subsequent sub-events.
 pub(super) fn apply_event_fully(sketch: &mut Sketch, event: &Event, at_path: &[&str]) {
@@ -0,0 +1,113 @@
+use crate::sketchbook::model::TruthTable;
+use crate::sketchbook::JsonSerde;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Interpretation of all parameters of a single candidate network (a single color), i.e.,
+/// how the uninterpreted functions and free update functions of the sketch were resolved.
+///
+/// Uninterpreted functions are keyed by their IDs (which are used as parameter names in the
+/// network), and each is resolved into a full truth table (in the same format as given by
+/// [crate::sketchbook::model::UninterpretedFn::truth_table], with all outputs determined).
+/// Free (unspecified) update functions are keyed by the IDs of their variables, and each is
+/// resolved into an expression (in DNF). Functions that are not used in the network (and thus
+/// not part of the symbolic context) are not included.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CandidateAssignment {
+    pub uninterpreted_fns: BTreeMap<String, TruthTable>,
+    pub update_fns: BTreeMap<String, String>,
+}
+
+impl JsonSerde<'_> for CandidateAssignment {}
+
+/// Extract the parameter assignment of a single candidate, given as a singleton color set
+/// (e.g., obtained by sampling). The symbolic context of the `graph` is used to decode the
+/// values of all function tables.
+///
+/// Returns `Err` if the color set is not a singleton.
+pub fn extract_candidate_assignment(
+    color: &GraphColors,
+    graph: &SymbolicAsyncGraph,
+) -> Result<CandidateAssignment, String> {
+    if !color.is_singleton() {
+        return Err("Parameter assignment can only be extracted for a single color.".to_string());
+    }
+    let ctx = graph.symbolic_context();
+    let valuation = color.as_bdd().sat_witness().unwrap();
+
+    let mut uninterpreted_fns = BTreeMap::new();
+    for param in ctx.network_parameters() {
+        let mut truth_table: TruthTable = ctx
+            .get_explicit_function_table(param)
+            .into_iter()
+            .map(|(row, bdd_var)| (row, Some(valuation[bdd_var])))
+            .collect();
+        truth_table.sort();
+        uninterpreted_fns.insert(ctx.get_network_parameter_name(param), truth_table);
+    }
+
+    // free update functions are resolved the same way as when the witness network is created
+    let witness = graph.pick_witness(color);
+    let mut update_fns = BTreeMap::new();
+    for var in ctx.network_implicit_parameters() {
+        let var_name = ctx.get_network_variable_name(var);
+        let update_fn = witness.get_update_function(var).as_ref().ok_or(format!(
+            "Update function of `{var_name}` could not be resolved."
+        ))?;
+        update_fns.insert(var_name, update_fn.to_string(&witness));
+    }
+
+    Ok(CandidateAssignment {
+        uninterpreted_fns,
+        update_fns,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inference::candidate_assignment::extract_candidate_assignment;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use biodivine_lib_param_bn::BooleanNetwork;
+
+    #[test]
+    /// Test extracting the interpretation of uninterpreted and free update functions of a
+    /// single candidate.
+    fn test_extract_candidate_assignment() {
+        // `f` must be monotone and depend on both arguments, i.e., it is either AND or OR
+        let aeon_str = "a -> a\nb -> a\nb ->? b\n$a: f(a, b)";
+        let bn = BooleanNetwork::try_from(aeon_str).unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+        let color = graph.mk_unit_colors().pick_singleton();
+
+        let assignment = extract_candidate_assignment(&color, &graph).unwrap();
+        let f_table = assignment.uninterpreted_fns.get("f").unwrap();
+        let rows: Vec<Vec<bool>> = f_table.iter().map(|(row, _)| row.clone()).collect();
+        let expected_rows = vec![
+            vec![false, false],
+            vec![false, true],
+            vec![true, false],
+            vec![true, true],
+        ];
+        assert_eq!(rows, expected_rows);
+        let outputs: Vec<Option<bool>> = f_table.iter().map(|(_, out)| *out).collect();
+        let and_outputs = vec![Some(false), Some(false), Some(false), Some(true)];
+        let or_outputs = vec![Some(false), Some(true), Some(true), Some(true)];
+        assert!(outputs == and_outputs || outputs == or_outputs);
+
+        // only `b` has a free update function, and it matches the witness network
+        assert_eq!(assignment.update_fns.len(), 1);
+        let witness = graph.pick_witness(&color);
+        let var_b = witness.as_graph().find_variable("b").unwrap();
+        let expected_b = witness.get_update_function(var_b).as_ref().unwrap();
+        assert_eq!(assignment.update_fns["b"], expected_b.to_string(&witness));
+    }
+
+    #[test]
+    /// Test that the assignment cannot be extracted for a set of several candidates.
+    fn test_extract_assignment_not_singleton() {
+        let bn = BooleanNetwork::try_from("a -> a\nb -> a\n$a: f(a, b)").unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+        assert!(extract_candidate_assignment(&graph.mk_unit_colors(), &graph).is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::algorithms::eval_dynamic::eval::eval_attractors_on_network;
+use crate::sketchbook::JsonSerde;
+use biodivine_lib_param_bn::biodivine_std::bitvector::BitVector;
+use biodivine_lib_param_bn::BooleanNetwork;
+use serde::{Deserialize, Serialize};
+
+/// Default maximal number of attractor states sent to the frontend (in total, over all
+/// attractors of the network).
+pub const DEFAULT_MAX_ATTRACTOR_STATES: usize = 100;
+
+/// Structure for receiving data about attractor computation for a single candidate network
+/// from the frontend. The network is given in the AEON format, and must be fully specified.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AttractorsRequest {
+    pub network: String,
+    #[serde(default = "default_max_states")]
+    pub max_states: usize,
+}
+
+impl JsonSerde<'_> for AttractorsRequest {}
+
+/// **(internal)** Default value of [AttractorsRequest::max_states] (for serde).
+fn default_max_states() -> usize {
+    DEFAULT_MAX_ATTRACTOR_STATES
+}
+
+/// States of a single attractor of a candidate network. Each state is a list of values of
+/// the network's variables (in the order given by [CandidateAttractors::variables]).
+///
+/// Only a bounded number of states is listed, the total number of states of the attractor is
+/// given by `num_states` (as a string, since it can be arbitrarily large).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AttractorStates {
+    pub num_states: String,
+    pub states: Vec<Vec<bool>>,
+}
+
+/// All attractors of a candidate network, with their (bounded number of) states.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CandidateAttractors {
+    pub variables: Vec<String>,
+    pub attractors: Vec<AttractorStates>,
+}
+
+impl JsonSerde<'_> for CandidateAttractors {}
+
+/// Compute attractors of a candidate network (a fully specified network with a single color),
+/// and decode their states into lists of variable values.
+///
+/// At most `max_states` states are listed in total. Attractors are processed in order, and once
+/// the limit is reached, the remaining attractors only report their number of states.
+pub fn compute_candidate_attractors(
+    bn: &BooleanNetwork,
+    max_states: usize,
+) -> Result<CandidateAttractors, String> {
+    let variables = bn
+        .variables()
+        .map(|v| bn.get_variable_name(v).clone())
+        .collect();
+
+    let mut remaining = max_states;
+    let mut attractors = Vec::new();
+    for component in eval_attractors_on_network(bn)? {
+        let vertices = component.vertices();
+        let states: Vec<Vec<bool>> = vertices
+            .iter()
+            .take(remaining)
+            .map(|state| state.values())
+            .collect();
+        remaining -= states.len();
+        attractors.push(AttractorStates {
+            num_states: vertices.exact_cardinality().to_string(),
+            states,
+        });
+    }
+    Ok(CandidateAttractors {
+        variables,
+        attractors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inference::candidate_attractors::{compute_candidate_attractors, AttractorStates};
+    use biodivine_lib_param_bn::BooleanNetwork;
+
+    #[test]
+    /// Test computing attractor states of a network with two (oscillating) attractors.
+    fn test_candidate_attractors() {
+        // `A` and `B` form a switch with two fixed points, `C` oscillates (independently)
+        let aeon_str = "A -| B\nB -| A\nC -| C\n$A: !B\n$B: !A\n$C: !C";
+        let bn = BooleanNetwork::try_from(aeon_str).unwrap();
+
+        let result = compute_candidate_attractors(&bn, 10).unwrap();
+        assert_eq!(result.variables, vec!["A", "B", "C"]);
+        // the order of attractors (and their states) is not important here
+        let mut attractors = result.attractors;
+        attractors.iter_mut().for_each(|a| a.states.sort());
+        attractors.sort_by(|a, b| a.states.cmp(&b.states));
+        let expected = vec![
+            AttractorStates {
+                num_states: "2".to_string(),
+                states: vec![vec![false, true, false], vec![false, true, true]],
+            },
+            AttractorStates {
+                num_states: "2".to_string(),
+                states: vec![vec![true, false, false], vec![true, false, true]],
+            },
+        ];
+        assert_eq!(attractors, expected);
+
+        // with a limit, only some states are listed, but the counts remain
+        let result = compute_candidate_attractors(&bn, 3).unwrap();
+        let num_listed: usize = result.attractors.iter().map(|a| a.states.len()).sum();
+        assert_eq!(num_listed, 3);
+        assert!(result.attractors.iter().all(|a| a.num_states == "2"));
+    }
+
+    #[test]
+    /// Test that attractors can only be computed for fully specified networks.
+    fn test_candidate_attractors_unspecified() {
+        let bn = BooleanNetwork::try_from("A -> B\nB -> A\n$A: B").unwrap();
+        assert!(compute_candidate_attractors(&bn, 10).is_err());
+    }
+}
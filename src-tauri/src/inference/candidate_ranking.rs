@@ -0,0 +1,144 @@
+use crate::algorithms::eval_dynamic::eval::eval_weighted_reachability;
+use crate::algorithms::eval_dynamic::utils::dont_track_progress;
+use crate::sketchbook::{JsonSerde, Sketch};
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+use serde::{Deserialize, Serialize};
+
+/// Structure for receiving data about ranking of candidates by weighted (fuzzy) reachability
+/// from the frontend. Both datasets are given by their IDs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WeightedRankingRequest {
+    pub initial_dataset: String,
+    pub target_dataset: String,
+}
+
+impl JsonSerde<'_> for WeightedRankingRequest {}
+
+/// A class of candidates with the same score. The number of candidates is given as a string,
+/// since it can be arbitrarily large.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScoreClass {
+    pub score: f64,
+    pub num_candidates: String,
+}
+
+/// Ranking of candidates by weighted reachability, given by classes of candidates with the same
+/// score (sorted by decreasing score).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CandidateRanking {
+    pub classes: Vec<ScoreClass>,
+}
+
+impl JsonSerde<'_> for CandidateRanking {}
+
+/// Rank the candidates `sat_colors` by the weighted reachability between two datasets of the
+/// sketch (see [eval_weighted_reachability]). Only non-empty classes of candidates are listed.
+///
+/// The datasets are processed to match the model's variables first (see
+/// [Sketch::with_processed_datasets]).
+pub fn rank_candidates_by_weighted_reachability(
+    request: &WeightedRankingRequest,
+    sketch: &Sketch,
+    graph: &SymbolicAsyncGraph,
+    sat_colors: &GraphColors,
+) -> Result<CandidateRanking, String> {
+    let sketch = sketch.with_processed_datasets();
+    let initial_dataset = sketch
+        .observations
+        .get_dataset_by_str(&request.initial_dataset)?;
+    let target_dataset = sketch
+        .observations
+        .get_dataset_by_str(&request.target_dataset)?;
+
+    let score_classes = eval_weighted_reachability(
+        initial_dataset,
+        target_dataset,
+        graph,
+        &mut dont_track_progress,
+    )?;
+    let classes = score_classes
+        .into_iter()
+        .map(|(score, colors)| (score, colors.intersect(sat_colors)))
+        .filter(|(_, colors)| !colors.is_empty())
+        .map(|(score, colors)| ScoreClass {
+            score,
+            num_candidates: colors.exact_cardinality().to_string(),
+        })
+        .collect();
+    Ok(CandidateRanking { classes })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inference::candidate_ranking::{
+        rank_candidates_by_weighted_reachability, ScoreClass, WeightedRankingRequest,
+    };
+    use crate::sketchbook::observations::{Dataset, Observation};
+    use crate::sketchbook::Sketch;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+
+    #[test]
+    /// Test ranking candidates of a sketch by weighted reachability between two datasets.
+    fn test_rank_candidates() {
+        // `A` is constant, and `B` is either `A` or `!A` (two candidates)
+        let mut sketch = Sketch::from_aeon("A -> A\nA -? B\n$A: A").unwrap();
+        let initial_obs = Observation::try_from_str("10", "i").unwrap();
+        let initial = Dataset::new("d1", vec![initial_obs], vec!["A", "B"]).unwrap();
+        let target_obs = vec![
+            Observation::try_from_str("11", "o1")
+                .unwrap()
+                .with_weight(0.5)
+                .unwrap(),
+            Observation::try_from_str("10", "o2").unwrap(),
+        ];
+        let target = Dataset::new("d2", target_obs, vec!["A", "B"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d1", initial)
+            .unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d2", target)
+            .unwrap();
+
+        let bn = sketch.model.to_bn();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+        let request = WeightedRankingRequest {
+            initial_dataset: "d1".to_string(),
+            target_dataset: "d2".to_string(),
+        };
+        let all_colors = graph.mk_unit_colors();
+        let ranking =
+            rank_candidates_by_weighted_reachability(&request, &sketch, &graph, &all_colors)
+                .unwrap();
+        let expected = vec![
+            ScoreClass {
+                score: 1.5,
+                num_candidates: "1".to_string(),
+            },
+            ScoreClass {
+                score: 1.,
+                num_candidates: "1".to_string(),
+            },
+        ];
+        assert_eq!(ranking.classes, expected);
+
+        // only the given candidates are ranked (empty classes are skipped)
+        let no_colors = graph.mk_empty_colors();
+        let ranking =
+            rank_candidates_by_weighted_reachability(&request, &sketch, &graph, &no_colors)
+                .unwrap();
+        assert!(ranking.classes.is_empty());
+
+        // datasets must exist
+        let request = WeightedRankingRequest {
+            initial_dataset: "d1".to_string(),
+            target_dataset: "unknown".to_string(),
+        };
+        assert!(
+            rank_candidates_by_weighted_reachability(&request, &sketch, &graph, &all_colors)
+                .is_err()
+        );
+    }
+}
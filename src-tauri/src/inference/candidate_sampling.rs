@@ -2,6 +2,7 @@ use biodivine_lib_bdd::BddPartialValuation;
 use biodivine_lib_param_bn::biodivine_std::traits::Set;
 use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
 use biodivine_lib_param_bn::BooleanNetwork;
+use num_bigint::BigUint;
 
 use rand::prelude::StdRng;
 use rand::SeedableRng;
@@ -10,6 +11,15 @@ use std::io::Write;
 use std::path::Path;
 use zip::write::{FileOptions, ZipWriter};
 
+/// Get the exact number of candidate networks in the given set of colors.
+///
+/// The count is computed symbolically (as the cardinality of the color set), no networks are
+/// materialized. This is cheap compared to [download_witnesses], and can be used to check the
+/// number of candidates before sampling them.
+pub fn count_candidates(color_set: &GraphColors) -> BigUint {
+    color_set.exact_cardinality()
+}
+
 /// Randomly select a color from the given set of colors.
 /// This is a workaround that should be modified in the future.
 pub fn pick_random_color(
@@ -31,9 +41,76 @@ pub fn pick_random_color(
     color_set.copy(singleton_bdd)
 }
 
+/// Deterministically pick a single color from the given set of colors, and materialize the
+/// corresponding fully specified witness network.
+///
+/// Returns `Err` if the color set is empty (i.e., there are no satisfying networks).
+pub fn sample_one_witness(
+    colors: &GraphColors,
+    graph: &SymbolicAsyncGraph,
+) -> Result<BooleanNetwork, String> {
+    if colors.is_empty() {
+        return Err("Cannot sample a witness network, there are no satisfying colors.".to_string());
+    }
+    // The `GraphColors::pick_singleton` should be deterministic.
+    let witness_color = colors.pick_singleton();
+    Ok(graph.pick_witness(&witness_color))
+}
+
+/// Randomly sample (up to) `n` distinct colors from the given set, and materialize the
+/// corresponding fully specified networks. If there are less than `n` colors, all of them
+/// are sampled.
+///
+/// The sampling uses a seeded `StdRng` generator. Given the same `seed`, set of colors, and
+/// graph, the resulting networks are always the same (and in the same order).
+/// Note that the sequence of `StdRng` is not guaranteed to stay the same across versions of
+/// the `rand` crate.
+pub fn sample_networks(
+    colors: &GraphColors,
+    graph: &SymbolicAsyncGraph,
+    n: usize,
+    seed: u64,
+) -> Vec<BooleanNetwork> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut remaining_colors = colors.clone();
+    let mut networks = Vec::new();
+    while networks.len() < n && !remaining_colors.is_empty() {
+        let witness_color = pick_random_color(&mut rng, graph, &remaining_colors);
+        assert!(witness_color.is_singleton());
+        // remove the color from the set, so that all sampled colors are distinct
+        remaining_colors = remaining_colors.minus(&witness_color);
+        networks.push(graph.pick_witness(&witness_color));
+    }
+    networks
+}
+
+/// Deterministically pick (up to) `n` distinct colors from the given set, and materialize the
+/// corresponding fully specified networks. If there are less than `n` colors, all of them
+/// are picked.
+pub fn pick_networks(
+    colors: &GraphColors,
+    graph: &SymbolicAsyncGraph,
+    n: usize,
+) -> Vec<BooleanNetwork> {
+    let mut remaining_colors = colors.clone();
+    let mut networks = Vec::new();
+    while networks.len() < n && !remaining_colors.is_empty() {
+        // The `GraphColors::pick_singleton` should be deterministic.
+        let witness_color = remaining_colors.pick_singleton();
+        remaining_colors = remaining_colors.minus(&witness_color);
+        networks.push(graph.pick_witness(&witness_color));
+    }
+    networks
+}
+
+/// Sample `witness_count` candidate networks from the given set of colors, and write them
+/// into a zip archive at `path` (as `candidate_<i>.aeon` files).
+///
+/// If `seed` is given, the networks are sampled randomly (see [sample_networks]). Otherwise,
+/// the sampling is deterministic (see [pick_networks]).
 pub fn download_witnesses(
     path: &str,
-    mut color_set: GraphColors,
+    color_set: GraphColors,
     bn: &BooleanNetwork,
     witness_count: usize,
     seed: Option<u64>,
@@ -49,34 +126,88 @@ pub fn download_witnesses(
     let archive = File::create(archive_path).map_err(|e| format!("{e:?}"))?;
     let mut zip_writer = ZipWriter::new(archive);
 
-    let mut i = 0;
-    let mut random_state: Option<StdRng> = seed.map(StdRng::seed_from_u64);
-
     // collect `num_witnesses` networks
-    while i < witness_count && !color_set.is_empty() {
-        // get singleton color for the witness
-        let witness_color = if let Some(std_rng) = random_state.as_mut() {
-            // For random networks, we need to be a bit more creative... (although, support for
-            // this in lib-param-bn would be nice).
-            pick_random_color(std_rng, &graph, &color_set)
-        } else {
-            // The `GraphColors::pick_singleton` should be deterministic.
-            color_set.pick_singleton()
-        };
-        assert!(witness_color.is_singleton());
+    let witnesses = if let Some(seed) = seed {
+        sample_networks(&color_set, &graph, witness_count, seed)
+    } else {
+        pick_networks(&color_set, &graph, witness_count)
+    };
 
-        // remove the color from the set
-        color_set = color_set.minus(&witness_color);
-        i += 1;
-
-        // Write the network into the zip.
-        let file_content = graph.pick_witness(&witness_color).to_string();
+    // Write the networks into the zip.
+    for (i, witness) in witnesses.iter().enumerate() {
         zip_writer
-            .start_file(format!("candidate_{i}.aeon"), FileOptions::default())
+            .start_file(format!("candidate_{}.aeon", i + 1), FileOptions::default())
             .map_err(|e| format!("{e:?}"))?;
-        writeln!(zip_writer, "{file_content}").map_err(|e| format!("{e:?}"))?;
+        writeln!(zip_writer, "{witness}").map_err(|e| format!("{e:?}"))?;
     }
 
     zip_writer.finish().map_err(|e| format!("{e:?}"))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::inference::candidate_sampling::{
+        count_candidates, sample_networks, sample_one_witness,
+    };
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use num_bigint::BigUint;
+
+    #[test]
+    /// Test exact counting of candidate networks in a color set.
+    fn test_count_candidates() {
+        // `f_a` can be either `a & b` or `a | b`, `f_b` can only be `a` (monotonic and essential)
+        let bn = BooleanNetwork::try_from("a -> a\nb -> a\na -> b").unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+        let colors = graph.mk_unit_colors();
+        assert_eq!(count_candidates(&colors), BigUint::from(2u32));
+        assert_eq!(count_candidates(&graph.mk_empty_colors()), BigUint::ZERO);
+    }
+
+    #[test]
+    /// Test deterministic sampling of a single fully specified witness network.
+    fn test_sample_one_witness() {
+        let bn = BooleanNetwork::try_from("a -> a\nb -> a\na -> b").unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+        let colors = graph.mk_unit_colors();
+
+        // the witness is fully specified and deterministic
+        let witness = sample_one_witness(&colors, &graph).unwrap();
+        assert_eq!(witness.num_parameters(), 0);
+        assert!(witness
+            .variables()
+            .all(|v| witness.get_update_function(v).is_some()));
+        let witness_again = sample_one_witness(&colors, &graph).unwrap();
+        assert_eq!(witness.to_string(), witness_again.to_string());
+
+        assert!(sample_one_witness(&graph.mk_empty_colors(), &graph).is_err());
+    }
+
+    #[test]
+    /// Test seeded sampling of distinct candidate networks.
+    fn test_sample_networks() {
+        // `f_a` has several monotonic essential variants (with three regulators)
+        let bn = BooleanNetwork::try_from("a -> a\nb -> a\nc -> a\na -> b\na -> c").unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+        let colors = graph.mk_unit_colors();
+        let to_strings = |networks: Vec<BooleanNetwork>| {
+            networks.iter().map(|n| n.to_string()).collect::<Vec<_>>()
+        };
+
+        // same seed gives the same networks, and all the sampled networks are distinct
+        let sample = to_strings(sample_networks(&colors, &graph, 3, 42));
+        assert_eq!(sample.len(), 3);
+        assert_eq!(sample, to_strings(sample_networks(&colors, &graph, 3, 42)));
+        let mut distinct = sample.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), 3);
+
+        // asking for more networks than there are colors gives all of them
+        let num_colors = colors.exact_cardinality();
+        let all = sample_networks(&colors, &graph, 100, 42);
+        assert_eq!(BigUint::from(all.len()), num_colors);
+        assert!(sample_networks(&graph.mk_empty_colors(), &graph, 3, 42).is_empty());
+    }
+}
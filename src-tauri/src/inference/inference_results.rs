@@ -1,4 +1,6 @@
-use crate::inference::inference_status::InferenceStatusReport;
+use crate::inference::inference_status::{
+    CandidateRefinement, InferenceStatus, InferenceStatusReport,
+};
 use crate::inference::inference_type::InferenceType;
 use crate::inference::update_fn_details::MAX_UPDATE_FN_COUNT;
 use crate::sketchbook::JsonSerde;
@@ -45,6 +47,21 @@ impl InferenceResults {
         }
     }
 
+    /// Get IDs of all properties that were skipped because their evaluation exceeded the
+    /// per-property timeout (as recorded in the progress statuses).
+    pub fn timed_out_properties(&self) -> Vec<String> {
+        collect_timed_out_properties(&self.progress_statuses)
+    }
+
+    /// Get records on how the set of candidates was refined by each evaluated property, in the
+    /// order of evaluation (as recorded in the progress statuses).
+    pub fn candidate_refinements(&self) -> Vec<&CandidateRefinement> {
+        self.progress_statuses
+            .iter()
+            .filter_map(|report| report.refinement.as_ref())
+            .collect()
+    }
+
     /// Append string to the end of current metadata.
     pub fn extend_summary(&mut self, new_message: &str) {
         self.summary_message.push_str(new_message);
@@ -82,6 +99,25 @@ impl InferenceResults {
             output.push_str(&format!("{var}: {count_display}\n"));
         }
 
+        let refinements = self.candidate_refinements();
+        if !refinements.is_empty() {
+            output.push_str("--------------\n");
+            output.push_str("Number of candidates after each property:\n");
+            output.push_str("--------------\n");
+            for refinement in refinements {
+                let eliminated = refinement
+                    .num_eliminated()
+                    .map(|num| num.to_string())
+                    .unwrap_or_else(|| "?".to_string());
+                output.push_str(&format!(
+                    "{}: {} -> {} ({eliminated} eliminated)\n",
+                    refinement.prop_id,
+                    refinement.num_candidates_before,
+                    refinement.num_candidates_after
+                ));
+            }
+        }
+
         output.push_str("--------------\n");
         output.push_str("Detailed progress report:\n");
         output.push_str("--------------\n");
@@ -94,13 +130,27 @@ impl InferenceResults {
     }
 }
 
+/// Collect IDs of all properties that were skipped because their evaluation exceeded the
+/// per-property timeout, as recorded in the given progress statuses.
+pub(crate) fn collect_timed_out_properties(statuses: &[InferenceStatusReport]) -> Vec<String> {
+    statuses
+        .iter()
+        .filter_map(|report| match &report.status {
+            InferenceStatus::PropertyTimeout(prop_id) => Some(prop_id.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
     use std::time::Duration;
 
     use crate::inference::inference_results::InferenceResults;
-    use crate::inference::inference_status::{InferenceStatus, InferenceStatusReport};
+    use crate::inference::inference_status::{
+        CandidateRefinement, InferenceStatus, InferenceStatusReport,
+    };
     use crate::inference::inference_type::InferenceType;
 
     #[test]
@@ -140,4 +190,37 @@ mod tests {
         assert!(report.contains("Started"));
         assert!(report.contains("Finished"));
     }
+
+    #[test]
+    /// Test collecting and reporting candidate refinements recorded in the status updates.
+    fn test_candidate_refinements() {
+        let refinement = CandidateRefinement::new("p1", "8", "3");
+        let inference_results = InferenceResults::new(
+            InferenceType::FullInference,
+            3,
+            Duration::from_millis(10),
+            "",
+            vec![
+                InferenceStatusReport::new(
+                    InferenceStatus::StartedStatic("p1".to_string()),
+                    Some("8".to_string()),
+                    5,
+                    "Started p1",
+                ),
+                InferenceStatusReport::new(
+                    InferenceStatus::EvaluatedStatic("p1".to_string()),
+                    Some("3".to_string()),
+                    10,
+                    "Finished p1",
+                )
+                .with_refinement(Some(refinement.clone())),
+            ],
+            HashMap::new(),
+        );
+
+        assert_eq!(inference_results.candidate_refinements(), vec![&refinement]);
+        assert_eq!(refinement.num_eliminated(), Some(5u32.into()));
+        let report = inference_results.format_to_report();
+        assert!(report.contains("p1: 8 -> 3 (5 eliminated)"));
+    }
 }
@@ -1,33 +1,64 @@
-use crate::algorithms::eval_dynamic::eval::eval_dyn_prop;
-use crate::algorithms::eval_dynamic::prepare_graph::prepare_graph_for_dynamic_hctl;
+use crate::algorithms::eval_dynamic::prepare_graph::{
+    prepare_shared_dyn_evaluation, DynPropEvaluator,
+};
 use crate::algorithms::eval_dynamic::processed_props::{process_dynamic_props, ProcessedDynProp};
-use crate::algorithms::eval_static::eval::eval_static_prop;
+use crate::algorithms::eval_static::eval::{eval_each_static_prop_parallel, eval_static_prop};
 use crate::algorithms::eval_static::prepare_graph::prepare_graph_for_static_fol;
 use crate::algorithms::eval_static::processed_props::{process_static_props, ProcessedStatProp};
 use crate::algorithms::fo_logic::utils::get_implicit_function_name;
+use crate::algorithms::variable_ordering::VariableOrdering;
 use crate::debug;
-use crate::inference::inference_results::InferenceResults;
+use crate::inference::inference_results::{collect_timed_out_properties, InferenceResults};
 use crate::inference::inference_status::InferenceStatus;
 use crate::inference::inference_type::InferenceType;
+use crate::sketchbook::ids::{DynPropertyId, StatPropertyId};
 use crate::sketchbook::model::FnTree;
 use crate::sketchbook::{JsonSerde, Sketch};
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
 use biodivine_lib_param_bn::symbolic_async_graph::{
     GraphColoredVertices, GraphColors, SymbolicAsyncGraph,
 };
 use biodivine_lib_param_bn::BooleanNetwork;
 use num_bigint::BigUint;
 use num_traits::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::mpsc::Sender;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
 use std::sync::Arc;
 use std::thread;
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 use tauri::async_runtime::RwLock;
 
-use super::inference_status::InferenceStatusReport;
+use super::inference_status::{CandidateRefinement, InferenceStatusReport};
 use super::update_fn_details::num_update_fn_variants_per_var;
 
+/// Default soft limit on the number of BDD nodes of the symbolic sets processed by the solver.
+/// Exceeding it does not stop the computation, but the user is warned that it may run out of
+/// memory.
+pub const DEFAULT_BDD_SIZE_LIMIT: usize = 10_000_000;
+
+/// Configuration of the per-property timeout (see [InferenceSolver::set_property_timeout]).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PropertyTimeoutConfig {
+    /// Maximal wall-clock time (in milliseconds) of evaluating a single property.
+    pub timeout_ms: u64,
+    /// Whether to abort the whole computation once the timeout is exceeded (otherwise, the
+    /// property is skipped).
+    pub abort_on_timeout: bool,
+}
+
+impl JsonSerde<'_> for PropertyTimeoutConfig {}
+
+/// **(internal)** Reference to a static or dynamic property of a sketch (the two kinds of
+/// properties have separate ID namespaces).
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PropertyRef {
+    Static(StatPropertyId),
+    Dynamic(DynPropertyId),
+}
+
 /// Object encompassing the process of the BN inference computation.
 ///
 /// It tracks the intermediate results and low-level structures, and it provides hooks to the
@@ -62,6 +93,24 @@ pub struct InferenceSolver {
     results: Option<InferenceResults>,
     /// Potential error message (if computation finishes with error).
     error_message: Option<String>,
+    /// Soft limit on the number of BDD nodes of the processed symbolic sets. Once exceeded, a
+    /// warning status is reported (only once per computation). There is no limit if `None`.
+    bdd_size_limit: Option<usize>,
+    /// Peak number of BDD nodes of the processed symbolic sets (remaining candidates and
+    /// intermediate results reported by the model checker) observed so far.
+    peak_bdd_size: AtomicUsize,
+    /// Flag signalling that exceeding the BDD size limit was already reported.
+    bdd_limit_reported: AtomicBool,
+    /// Optional timeout for evaluation of a single dynamic property. There is no timeout if
+    /// `None`.
+    property_timeout: Option<PropertyTimeoutConfig>,
+    /// Flag to evaluate static properties in parallel (see [Self::set_parallel_static_eval]).
+    parallel_static_eval: bool,
+    /// Flag to split attractor computation into parallel partitions (see
+    /// [Self::set_parallel_attractors]).
+    parallel_attractors: bool,
+    /// Ordering of variables in the symbolic encoding (see [Self::set_variable_ordering]).
+    variable_ordering: VariableOrdering,
 }
 
 /// Object encompassing a finished (successful) BN inference computation with all
@@ -98,9 +147,61 @@ impl InferenceSolver {
             sender_channel,
             results: None,
             error_message: None,
+            bdd_size_limit: Some(DEFAULT_BDD_SIZE_LIMIT),
+            peak_bdd_size: AtomicUsize::new(0),
+            bdd_limit_reported: AtomicBool::new(false),
+            property_timeout: None,
+            parallel_static_eval: false,
+            parallel_attractors: true,
+            variable_ordering: VariableOrdering::Default,
         }
     }
 
+    /// Set the soft limit on the number of BDD nodes of the processed symbolic sets. Use `None`
+    /// to disable the limit.
+    pub fn set_bdd_size_limit(&mut self, bdd_size_limit: Option<usize>) {
+        self.bdd_size_limit = bdd_size_limit;
+    }
+
+    /// Set the timeout for evaluation of a single dynamic property. Use `None` to disable it
+    /// (the default).
+    ///
+    /// The timeout is checked whenever the evaluation reports progress. Once exceeded, the
+    /// property is either skipped (recording [InferenceStatus::PropertyTimeout]), or the whole
+    /// computation is aborted with an error (if `abort_on_timeout` is set).
+    pub fn set_property_timeout(&mut self, property_timeout: Option<PropertyTimeoutConfig>) {
+        self.property_timeout = property_timeout;
+    }
+
+    /// Set whether static properties are evaluated in parallel. By default, they are evaluated
+    /// sequentially, which allows to report progress after each property and to stop once
+    /// the sketch is found unsatisfiable.
+    pub fn set_parallel_static_eval(&mut self, parallel_static_eval: bool) {
+        self.parallel_static_eval = parallel_static_eval;
+    }
+
+    /// Set whether attractors are computed in parallel, splitting the candidate colors into
+    /// one partition per available thread. By default, the parallel computation is used. The
+    /// sequential computation processes all colors at once, which gives deterministic progress
+    /// reports and lower memory usage.
+    pub fn set_parallel_attractors(&mut self, parallel_attractors: bool) {
+        self.parallel_attractors = parallel_attractors;
+    }
+
+    /// Set the ordering of network variables used for the symbolic encoding (see
+    /// [VariableOrdering]). The BDD sizes can be very sensitive to the ordering. By default, the
+    /// variables are ordered alphabetically.
+    ///
+    /// Note that the colors of the results are encoded using the same ordering.
+    pub fn set_variable_ordering(&mut self, variable_ordering: VariableOrdering) {
+        self.variable_ordering = variable_ordering;
+    }
+
+    /// Get the peak number of BDD nodes of the processed symbolic sets observed so far.
+    pub fn peak_bdd_size(&self) -> usize {
+        self.peak_bdd_size.load(Ordering::SeqCst)
+    }
+
     /// Reference getter for a Boolean network.
     pub fn bn(&self) -> Result<&BooleanNetwork, String> {
         if let Some(bn) = &self.bn {
@@ -170,6 +271,9 @@ impl InferenceSolver {
             InferenceStatus::Error => {
                 Err("Computation failed to finish because there was an error.".to_string())
             }
+            InferenceStatus::Cancelled => {
+                Err("Computation failed to finish because it was cancelled.".to_string())
+            }
             _ => Err("Computation not yet finished.".to_string()),
         }
     }
@@ -181,6 +285,10 @@ impl InferenceSolver {
     /// be reset, the window was closed, or some other reason), we instead forcibly stop the
     /// computation. Destroying the channel can thus actually be used as another way to stop the
     /// asynchronous computation, since one does not need to acquire lock over the whole solver.
+    ///
+    /// Statuses reporting that a property was evaluated also carry a record on how the property
+    /// refined the set of candidates (see [Self::candidate_refinement]). This re-uses the numbers
+    /// of candidates that are computed for every status anyway, so no extra work is needed.
     fn update_status(&mut self, status: InferenceStatus) {
         // starting time must be saved before any statuses are added
         let start_time = self.start_time().unwrap();
@@ -192,11 +300,19 @@ impl InferenceSolver {
             .current_candidate_colors()
             .ok()
             .map(|x| x.exact_cardinality().to_string());
+        let bdd_size = self
+            .current_candidate_colors()
+            .ok()
+            .map(|x| x.symbolic_size());
         let message = self.format_status_message(&status, duration_millis, candidates_num.clone());
         debug!("{message}");
 
+        let refinement = self.candidate_refinement(&status, candidates_num.as_deref());
+
         let status_report =
-            InferenceStatusReport::new(status.clone(), candidates_num, duration_millis, &message);
+            InferenceStatusReport::new(status.clone(), candidates_num, duration_millis, &message)
+                .with_bdd_size(bdd_size)
+                .with_refinement(refinement);
         let status_json = status_report.to_json_str();
         self.status_updates.push(status_report);
 
@@ -205,11 +321,37 @@ impl InferenceSolver {
         if self.sender_channel.send(status_json).is_err() {
             self.cancel();
         }
+        self.record_bdd_size(bdd_size);
+    }
+
+    /// If the `status` reports that a property was evaluated, prepare a record on how it refined
+    /// the set of candidates. The number of candidates before the evaluation is taken from the
+    /// latest recorded status (which reports the start of the evaluation, or the evaluation of
+    /// the previous property when static properties are evaluated in parallel).
+    fn candidate_refinement(
+        &self,
+        status: &InferenceStatus,
+        num_candidates_after: Option<&str>,
+    ) -> Option<CandidateRefinement> {
+        let prop_id = match status {
+            InferenceStatus::EvaluatedStatic(id) | InferenceStatus::EvaluatedDynamic(id) => id,
+            _ => return None,
+        };
+        let num_candidates_before = self.status_updates.last()?.num_candidates.as_ref()?;
+        Some(CandidateRefinement::new(
+            prop_id,
+            num_candidates_before,
+            num_candidates_after?,
+        ))
     }
 
     /// Version of [Self::update_status] for sending internal update to frontend. The status
     /// is not pushed to the status updates stack on backend.
-    fn update_internal_status(&self, status: InferenceStatus) {
+    ///
+    /// Argument `bdd_size` is the size of the symbolic set currently processed (like an
+    /// intermediate result of the model checker). If not provided, the size of the set of
+    /// remaining candidates is used.
+    fn update_internal_status(&self, status: InferenceStatus, bdd_size: Option<usize>) {
         // starting time must be saved before any statuses are added
         let start_time = self.start_time().unwrap();
         let now = SystemTime::now();
@@ -220,11 +362,17 @@ impl InferenceSolver {
             .current_candidate_colors()
             .ok()
             .map(|x| x.exact_cardinality().to_string());
+        let bdd_size = bdd_size.or_else(|| {
+            self.current_candidate_colors()
+                .ok()
+                .map(|x| x.symbolic_size())
+        });
         let message = self.format_status_message(&status, duration_millis, candidates_num.clone());
         debug!("{message}");
 
         let status_report =
-            InferenceStatusReport::new(status.clone(), candidates_num, duration_millis, &message);
+            InferenceStatusReport::new(status.clone(), candidates_num, duration_millis, &message)
+                .with_bdd_size(bdd_size);
         let status_json = status_report.to_json_str();
 
         // send JSON string to the channel so it can be send to the frontend later
@@ -232,6 +380,24 @@ impl InferenceSolver {
         if self.sender_channel.send(status_json).is_err() {
             self.cancel();
         }
+        if !matches!(status, InferenceStatus::BddSizeLimitExceeded(..)) {
+            self.record_bdd_size(bdd_size);
+        }
+    }
+
+    /// Record the size (number of BDD nodes) of a currently processed symbolic set, updating the
+    /// peak size. If the size exceeds the soft limit for the first time, a warning status is sent.
+    fn record_bdd_size(&self, bdd_size: Option<usize>) {
+        let Some(bdd_size) = bdd_size else {
+            return;
+        };
+        self.peak_bdd_size.fetch_max(bdd_size, Ordering::SeqCst);
+        if let Some(limit) = self.bdd_size_limit {
+            if bdd_size > limit && !self.bdd_limit_reported.swap(true, Ordering::SeqCst) {
+                let status = InferenceStatus::BddSizeLimitExceeded(bdd_size);
+                self.update_internal_status(status, Some(bdd_size));
+            }
+        }
     }
 
     /// Format a computation status message.
@@ -276,11 +442,19 @@ impl InferenceSolver {
             InferenceStatus::EvaluatedAllStatic => "Evaluated all static properties.".to_string(),
             InferenceStatus::EvaluatedAllDynamic => "Evaluated all dynamic properties.".to_string(),
             InferenceStatus::InternalProgress(msg) => format!("Internal solver status: {msg}"),
+            InferenceStatus::BddSizeLimitExceeded(size) => {
+                let limit = self.bdd_size_limit.unwrap_or_default();
+                format!("Symbolic representation reached {size} BDD nodes, exceeding the soft limit of {limit} nodes. The computation may run out of memory, consider cancelling it and narrowing the sketch.")
+            }
+            InferenceStatus::PropertyTimeout(id) => {
+                format!("Evaluation of dynamic property `{id}` exceeded the timeout, the property was skipped.")
+            }
             InferenceStatus::DetectedUnsat => "Found that sketch is unsatisfiable.".to_string(),
             InferenceStatus::FinishedSuccessfully => {
                 "Successfully finished computation.".to_string()
             }
             InferenceStatus::Error => "Encountered error during computation.".to_string(),
+            InferenceStatus::Cancelled => "Computation was cancelled.".to_string(),
         };
         if matches!(status, InferenceStatus::InternalProgress(..)) {
             format!("---> {comp_time}ms: {msg}{candidates_str}")
@@ -339,20 +513,30 @@ impl InferenceSolver {
                     Err("Computation ended up with an internal error.".to_string())
                 }
             }
+            InferenceStatus::Cancelled => Err("Computation was cancelled.".to_string()),
             _ => Err("Computation not yet finished.".to_string()),
         }
     }
 
-    /// Check if computation finished (by success or error).
+    /// Check if computation finished (by success, error, or cancellation).
     pub fn is_finished(&self) -> bool {
         // there is always at least 1 status, we can unwrap
         let last_status = self.status_updates.last().unwrap();
         matches!(
             last_status.status,
-            InferenceStatus::FinishedSuccessfully | InferenceStatus::Error
+            InferenceStatus::FinishedSuccessfully
+                | InferenceStatus::Error
+                | InferenceStatus::Cancelled
         )
     }
 
+    /// Get a shared reference to the cancellation flag of this solver. Setting the flag cancels
+    /// the computation (at the next checkpoint), and it can be done without acquiring a lock
+    /// over the solver.
+    pub fn cancellation_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.should_stop)
+    }
+
     /// Number of dynamic properties that were already successfully evaluated.
     pub fn num_finished_dyn_props(&self) -> u64 {
         self.status_updates.iter().fold(0, |accum, status| {
@@ -382,8 +566,9 @@ impl InferenceSolver {
     /// This wraps the [Self::run_inference_modular] to also log potential errors.
     ///
     /// The argument `inference_type` specifies which kind of inference should be used.
-    /// Currently, we support full inference with all properties, and partial inferences with only
-    /// static or only dynamic properties.
+    /// Currently, we support full inference with all properties, partial inferences with only
+    /// static or only dynamic properties, and inference restricted to trap-space properties
+    /// (see [InferenceType::TrapSpaceInference]).
     ///
     /// The results are saved to specific fields of the provided solver and can be retrieved later.
     /// They are also returned, which is now used for logging later.
@@ -393,8 +578,12 @@ impl InferenceSolver {
         inference_type: InferenceType,
     ) -> Result<InferenceResults, String> {
         {
-            let solver = solver.read().await;
-            solver.check_cancellation()?; // Early check before starting
+            // Early check before starting (the solver must still end up in a finished state)
+            let mut solver = solver.write().await;
+            if let Err(e) = solver.check_cancellation() {
+                solver.finish_cancelled();
+                return Err(e);
+            }
         }
 
         // Currently, we use this "write lock" to lock the solver for the whole inference.
@@ -412,12 +601,19 @@ impl InferenceSolver {
             InferenceType::DynamicInference => {
                 solver_write.run_inference_modular(inference_type, sketch, false, true)
             }
+            InferenceType::TrapSpaceInference => {
+                solver_write.run_inference_modular(inference_type, sketch, true, true)
+            }
         };
 
-        // if computation ends with an error, log it
+        // if computation ends with an error, log it (cancellation is reported separately)
         if let Err(msg) = &results {
-            solver_write.error_message = Some(msg.clone());
-            solver_write.update_status(InferenceStatus::Error);
+            if solver_write.check_cancellation().is_err() {
+                solver_write.finish_cancelled();
+            } else {
+                solver_write.error_message = Some(msg.clone());
+                solver_write.update_status(InferenceStatus::Error);
+            }
         }
 
         // Lets drop the lock for a bit to allow the potential cancellation thread to achieve a lock.
@@ -436,11 +632,27 @@ impl InferenceSolver {
     }
 
     /// Set the cancellation flag. The actual cancellation does not happen immediately,
-    /// we currently only allow cancelling only at certain checkpoints during computation.
+    /// we currently only allow cancelling only at certain checkpoints during computation (between
+    /// the individual properties, and at progress reports during evaluation of dynamic properties).
     pub fn cancel(&self) {
         //debug!("`InferenceSolver` has received cancellation flag.");
         self.should_stop.store(true, Ordering::SeqCst);
     }
+
+    /// Finish a cancelled computation - drop the symbolic graph and all partial results, and
+    /// update the status to [InferenceStatus::Cancelled].
+    fn finish_cancelled(&mut self) {
+        self.graph = None;
+        self.static_props = None;
+        self.dynamic_props = None;
+        self.raw_sat_colors = None;
+        self.results = None;
+        // the computation might have been cancelled even before it started
+        if self.start_time.is_none() {
+            self.start_time = Some(SystemTime::now());
+        }
+        self.update_status(InferenceStatus::Cancelled);
+    }
 }
 
 /// Methods related to actual inference computation.
@@ -452,6 +664,10 @@ impl InferenceSolver {
     /// includes all uninterpreted functions (after propagating the expressions). We also include
     /// all "implicit" function symbols for update functions. These may or may not appear anywhere
     /// in the BN, but they can be used to reference update functions within static properties.
+    ///
+    /// Frozen update functions are only validated here - fully specified functions are encoded
+    /// as concrete functions (without parameters) whether frozen or not. If some frozen function
+    /// still contains uninterpreted function symbols after substitution, we return `Err`.
     pub(crate) fn extract_bn(
         sketch: &Sketch,
     ) -> Result<(BooleanNetwork, HashMap<String, Option<FnTree>>), String> {
@@ -469,6 +685,25 @@ impl InferenceSolver {
         // There should not be any completely redundant function symbols (not allowed by consistency check).
         let mut bn = bn.prune_unused_parameters();
 
+        // Frozen update functions must be concrete after the substitution, so that they are
+        // encoded as constants and do not introduce any parameters.
+        // Inputs are not checked, as their update expressions are ignored anyway.
+        for (var_id, update_fn) in sketch.model.update_fns() {
+            if !update_fn.is_frozen() || sketch.model.get_variable(var_id)?.is_input() {
+                continue;
+            }
+            let var = bn.as_graph().find_variable(var_id.as_str()).unwrap();
+            let is_concrete = bn
+                .get_update_function(var)
+                .as_ref()
+                .is_some_and(|f| f.collect_parameters().is_empty());
+            if !is_concrete {
+                return Err(format!(
+                    "Update function of `{var_id}` is frozen, but it is not fully specified"
+                ));
+            }
+        }
+
         // Add expressions "f_varName(regulator_1, ..., regulator_M)" instead of all empty updates.
         // This gets us rid of "implicit" update functions, leaving us with only "explicit" parameters.
         for var in bn.variables().clone() {
@@ -500,8 +735,14 @@ impl InferenceSolver {
     /// Evaluate previously collected static properties, and restrict the unit set of the
     /// graph to the set of valid colors.
     ///
-    /// If we discover that sketch is unsat early, skip the rest.
+    /// If we discover that sketch is unsat early, skip the rest. If the parallel evaluation is
+    /// enabled (see [Self::set_parallel_static_eval]), all properties are evaluated at once
+    /// instead.
     fn eval_static(&mut self, base_var_name: &str) -> Result<(), String> {
+        if self.parallel_static_eval {
+            return self.eval_static_parallel(base_var_name);
+        }
+
         for stat_property in self.stat_props()?.clone() {
             self.check_cancellation()?; // check if cancellation flag was set during computation
 
@@ -524,18 +765,84 @@ impl InferenceSolver {
         Ok(())
     }
 
+    /// Evaluate previously collected static properties in parallel (see
+    /// [eval_each_static_prop_parallel]), and restrict the unit set of the graph to the set of
+    /// colors valid for all of them.
+    ///
+    /// All properties are evaluated on the current graph first. Their results are then applied
+    /// one property at a time (in the same order as in the sequential evaluation), so that the
+    /// refinement of candidates is recorded for each property.
+    ///
+    /// If we discover that sketch is unsat early, skip the rest.
+    fn eval_static_parallel(&mut self, base_var_name: &str) -> Result<(), String> {
+        self.check_cancellation()?; // check if cancellation flag was set during computation
+
+        let stat_props = self.stat_props()?.clone();
+        let all_inferred_colors =
+            eval_each_static_prop_parallel(&stat_props, self.graph()?, base_var_name)
+                .map_err(|e| format!("Failed evaluating static properties: {e}."))?;
+        for (stat_property, inferred_colors) in stat_props.iter().zip(all_inferred_colors) {
+            self.check_cancellation()?; // check if cancellation flag was set during computation
+
+            let prop_id = stat_property.id().to_string();
+            self.update_status(InferenceStatus::StartedStatic(prop_id.clone()));
+            // results were computed on the original graph, so they must be combined with
+            // restrictions of the previous properties (restricting replaces the unit set)
+            let valid_colors = inferred_colors.intersect(&self.graph()?.mk_unit_colors());
+            let colored_vertices = GraphColoredVertices::new(
+                valid_colors.into_bdd(),
+                self.graph()?.symbolic_context(),
+            );
+            let new_graph: SymbolicAsyncGraph = self.graph()?.restrict(&colored_vertices);
+            self.graph = Some(new_graph);
+            self.update_status(InferenceStatus::EvaluatedStatic(prop_id));
+            if self.check_if_finished_unsat(true)? {
+                return Ok(());
+            }
+        }
+        self.update_status(InferenceStatus::EvaluatedAllStatic);
+        Ok(())
+    }
+
     /// Evaluate previously collected dynamic properties, and restrict the unit set of the
     /// graph to the set of valid colors.
     ///
+    /// Argument `evaluators` gives the evaluation closure for each property (all sharing the
+    /// current graph, see [prepare_shared_dyn_evaluation]). Argument `attr_partitions` gives the
+    /// number of color partitions processed in parallel when computing attractors (only relevant
+    /// for attractor-based properties).
+    ///
+    /// If the per-property timeout is set (see [Self::set_property_timeout]), it is checked at
+    /// each progress report of the evaluation, and the evaluation is interrupted at its next
+    /// checkpoint once the timeout is exceeded. Properties exceeding it are either skipped, or
+    /// the computation is aborted.
+    ///
     /// If we discover that sketch is unsat early, skip the rest.
-    fn eval_dynamic(&mut self) -> Result<(), String> {
-        for dyn_property in self.dyn_props()?.clone() {
+    fn eval_dynamic<E: DynPropEvaluator>(
+        &mut self,
+        evaluators: Vec<(String, E)>,
+        attr_partitions: usize,
+    ) -> Result<(), String> {
+        for (prop_id, evaluator) in evaluators {
             self.check_cancellation()?; // check if cancellation flag was set during computation
-            let prop_id = dyn_property.id().to_string();
             self.update_status(InferenceStatus::StartedDynamic(prop_id.clone()));
+            let prop_start = Instant::now();
+            let timeout = self.property_timeout;
 
             // prepare a callback that will be used to report progress of the underlying model-checking computation
+            // once the property takes too long, the callback raises a flag, and the evaluation
+            // stops at its next checkpoint
+            let timed_out = Cell::new(false);
             let mut progress_callback = |colored_set: &GraphColoredVertices, msg: &str| {
+                if let Some(timeout) = timeout {
+                    if prop_start.elapsed() > Duration::from_millis(timeout.timeout_ms) {
+                        timed_out.set(true);
+                    }
+                }
+                // once interrupted or cancelled, there is no point in reporting (or computing) progress details
+                if timed_out.get() || self.check_cancellation().is_err() {
+                    return;
+                }
                 // the progress message should contain BDD size info only when relevant
                 let msg = if colored_set.exact_cardinality() > BigUint::ZERO {
                     format!("{msg} Current BDD size: {}", colored_set.symbolic_size(),)
@@ -543,12 +850,39 @@ impl InferenceSolver {
                     msg.to_string()
                 };
                 let new_status = InferenceStatus::InternalProgress(msg);
-                self.update_internal_status(new_status);
+                self.update_internal_status(new_status, Some(colored_set.symbolic_size()));
+            };
+            // the evaluation is also interrupted once the computation is cancelled
+            let is_interrupted = || timed_out.get() || self.check_cancellation().is_err();
+
+            let graph = self.graph()?;
+            let eval_result = evaluator(
+                graph,
+                attr_partitions,
+                &mut progress_callback,
+                &is_interrupted,
+            );
+            self.check_cancellation()?; // discard the result if cancelled during the evaluation
+            let inferred_colors: GraphColors = match eval_result {
+                Ok(colors) => colors,
+                Err(_) if timed_out.get() => {
+                    // timeout is always set if the evaluation was interrupted
+                    let timeout = self.property_timeout.unwrap();
+                    if timeout.abort_on_timeout {
+                        return Err(format!(
+                            "Evaluation of dynamic property {prop_id} exceeded the timeout of {}ms.",
+                            timeout.timeout_ms
+                        ));
+                    }
+                    self.update_status(InferenceStatus::PropertyTimeout(prop_id));
+                    continue;
+                }
+                Err(e) => {
+                    return Err(format!(
+                        "Failed evaluating dynamic property {prop_id}: {e}."
+                    ));
+                }
             };
-
-            let inferred_colors: GraphColors =
-                eval_dyn_prop(&dyn_property, self.graph()?, &mut progress_callback)
-                    .map_err(|e| format!("Failed evaluating dynamic property {prop_id}: {e}."))?;
             let colored_vertices = GraphColoredVertices::new(
                 inferred_colors.into_bdd(),
                 self.graph()?.symbolic_context(),
@@ -592,6 +926,11 @@ impl InferenceSolver {
         let (bn, fn_expressions) = Self::extract_bn(&sketch).map_err(|e| {
             format!("Failed extracting BooleanNetwork instance from the sketch: {e}.")
         })?;
+        // Re-order the BN variables, which determines the order of variables in all symbolic encodings
+        let bn = self
+            .variable_ordering
+            .apply(&bn)
+            .map_err(|e| format!("Failed applying the variable ordering: {e}."))?;
         // Select a BN variable (can be random) that will be used as a base for adding extra symbolic
         // variables (that we need later to encode FOL vars)
         let base_var = bn.variables().collect::<Vec<_>>()[0];
@@ -603,11 +942,16 @@ impl InferenceSolver {
         // Pre-process dynamic properties into a version more suitable for the computation
         let dynamic_props = process_dynamic_props(&sketch)
             .map_err(|e| format!("Failed pre-processing dynamic properties: {e}."))?;
+        // In trap-space mode, make sure we never reach the attractor-based algorithms
+        if inference_type == InferenceType::TrapSpaceInference {
+            assert_trap_space_props_only(&dynamic_props)?;
+        }
 
         self.bn = Some(bn);
         self.static_props = Some(static_props);
         self.dynamic_props = Some(dynamic_props);
         self.update_status(InferenceStatus::ProcessedInputs);
+        self.check_cancellation()?;
 
         /* >> STEP 2: evaluation of static properties */
 
@@ -621,6 +965,7 @@ impl InferenceSolver {
                     })?,
             );
             self.update_status(InferenceStatus::GeneratedContextStatic);
+            self.check_cancellation()?;
             let msg = format!(
                 "N. of candidates before evaluating any properties: {}\n",
                 self.current_candidate_colors()?.exact_cardinality()
@@ -642,25 +987,42 @@ impl InferenceSolver {
             /* >> STEP 3A: make symbolic transition graph for HCTL evaluation with restricted unit BDD */
             let old_unit_bdd = self.current_candidate_colors()?.into_bdd();
             let old_context = self.graph()?.symbolic_context();
-            self.graph = Some(
-                prepare_graph_for_dynamic_hctl(
-                    self.bn()?,
-                    self.dyn_props()?,
-                    Some((&old_unit_bdd, old_context)),
-                )
-                .map_err(|e| {
-                    format!("Failed preparing symbolic encoding for dynamic properties: {e}.")
-                })?,
-            );
+            // the graph is shared by all dynamic properties, each gets its evaluation closure
+            let (dyn_graph, evaluators) = prepare_shared_dyn_evaluation(
+                self.bn()?,
+                self.dyn_props()?,
+                Some((&old_unit_bdd, old_context)),
+            )
+            .map_err(|e| {
+                format!("Failed preparing symbolic encoding for dynamic properties: {e}.")
+            })?;
+            self.graph = Some(dyn_graph);
             self.update_status(InferenceStatus::GeneratedContextDynamic);
+            self.check_cancellation()?;
 
             /* >> STEP 3B: actually evaluate dynamic properties */
-            self.eval_dynamic()?; // proper error messages inside
+            // trap-space properties do not use attractor computation, no need for partitioning
+            let attr_partitions = if !self.parallel_attractors
+                || inference_type == InferenceType::TrapSpaceInference
+            {
+                1
+            } else {
+                rayon::current_num_threads()
+            };
+            self.eval_dynamic(evaluators, attr_partitions)?; // proper error messages inside
             let msg = format!(
                 "N. of candidates after evaluating dynamic props: {}\n",
                 self.current_candidate_colors()?.approx_cardinality()
             );
             summary_msg.push_str(&msg);
+            let timed_out = collect_timed_out_properties(&self.status_updates);
+            if !timed_out.is_empty() {
+                let msg = format!(
+                    "Dynamic properties skipped due to timeout: {}\n",
+                    timed_out.join(", ")
+                );
+                summary_msg.push_str(&msg);
+            }
         }
 
         /* >> STEP 4: process and save results */
@@ -713,6 +1075,164 @@ impl InferenceSolver {
     }
 }
 
+/// Methods for explaining unsatisfiable results.
+impl InferenceSolver {
+    /// Find a minimal subset of properties of the `sketch` that are already contradictory
+    /// together, and return their IDs. This can only be used after the inference over this
+    /// `sketch` finished with no satisfying candidates.
+    ///
+    /// The subset is searched for by delta debugging (see [ddmin]), re-running the inference for
+    /// various subsets of properties (with the same settings as this solver). This can be very
+    /// expensive, but the cancellation flag of this solver is respected by all the sub-runs.
+    ///
+    /// The result is 1-minimal, i.e., removing any of the properties makes the rest satisfiable
+    /// (but there may be other, smaller, conflicting subsets). Note that properties skipped due
+    /// to timeout are considered satisfiable.
+    pub fn find_minimal_unsat_properties(&self, sketch: &Sketch) -> Result<Vec<String>, String> {
+        let results = self
+            .results
+            .as_ref()
+            .ok_or_else(|| "Inference did not finish successfully yet.".to_string())?;
+        if results.num_sat_networks != 0 {
+            return Err("Sketch is satisfiable, there are no conflicting properties.".to_string());
+        }
+        let analysis_type = results.analysis_type.clone();
+
+        let mut properties: Vec<PropertyRef> = sketch
+            .properties
+            .stat_props()
+            .map(|(id, _)| PropertyRef::Static(id.clone()))
+            .chain(
+                sketch
+                    .properties
+                    .dyn_props()
+                    .map(|(id, _)| PropertyRef::Dynamic(id.clone())),
+            )
+            .collect();
+        // sort properties to make the search deterministic (static ones first)
+        properties.sort_by_key(|prop| match prop {
+            PropertyRef::Static(id) => (0, id.to_string()),
+            PropertyRef::Dynamic(id) => (1, id.to_string()),
+        });
+
+        let is_unsat =
+            |subset: &[PropertyRef]| self.is_unsat_with_properties(sketch, subset, &analysis_type);
+        let conflicting = ddmin(properties, is_unsat)?;
+        let conflicting_ids = conflicting
+            .iter()
+            .map(|prop| match prop {
+                PropertyRef::Static(id) => id.to_string(),
+                PropertyRef::Dynamic(id) => id.to_string(),
+            })
+            .collect();
+        Ok(conflicting_ids)
+    }
+
+    /// **(internal)** Run the inference over the `sketch` restricted to the given `properties`
+    /// (using a new solver with the same settings and cancellation flag), and check whether
+    /// there are no satisfying candidates.
+    fn is_unsat_with_properties(
+        &self,
+        sketch: &Sketch,
+        properties: &[PropertyRef],
+        analysis_type: &InferenceType,
+    ) -> Result<bool, String> {
+        self.check_cancellation()?;
+
+        let mut restricted_sketch = sketch.clone();
+        for (id, _) in sketch.properties.stat_props() {
+            if !properties.contains(&PropertyRef::Static(id.clone())) {
+                restricted_sketch.properties.remove_static(id)?;
+            }
+        }
+        for (id, _) in sketch.properties.dyn_props() {
+            if !properties.contains(&PropertyRef::Dynamic(id.clone())) {
+                restricted_sketch.properties.remove_dynamic(id)?;
+            }
+        }
+
+        // progress of the sub-runs is not reported anywhere (but the channel must stay alive)
+        let (sender, _receiver) = mpsc::channel();
+        let mut solver = InferenceSolver::new(sender);
+        solver.should_stop = Arc::clone(&self.should_stop);
+        solver.set_bdd_size_limit(self.bdd_size_limit);
+        solver.set_property_timeout(self.property_timeout);
+        solver.set_parallel_static_eval(self.parallel_static_eval);
+        solver.set_parallel_attractors(self.parallel_attractors);
+        solver.set_variable_ordering(self.variable_ordering.clone());
+        let results =
+            solver.run_inference_modular(analysis_type.clone(), restricted_sketch, true, true)?;
+        Ok(results.num_sat_networks == 0)
+    }
+}
+
+/// Delta debugging (ddmin) search for a 1-minimal subset of `items` that passes the `test`,
+/// assuming that all the `items` together pass it. The test returns `Err` if the search should
+/// be stopped (e.g., when cancelled).
+///
+/// The current set is split into chunks, and we try to reduce it to a single chunk, or to
+/// a complement of a single chunk. If neither works, the granularity is doubled, until the
+/// chunks are single items.
+fn ddmin<T: Clone>(
+    items: Vec<T>,
+    mut test: impl FnMut(&[T]) -> Result<bool, String>,
+) -> Result<Vec<T>, String> {
+    let mut current = items;
+    let mut granularity = 2;
+    while current.len() >= 2 {
+        let chunk_size = current.len().div_ceil(granularity);
+        let chunks: Vec<Vec<T>> = current.chunks(chunk_size).map(|c| c.to_vec()).collect();
+
+        let mut reduced = None;
+        for chunk in chunks.iter() {
+            if test(chunk)? {
+                reduced = Some((chunk.clone(), 2));
+                break;
+            }
+        }
+        // with only two chunks, complements were already tested as chunks
+        if reduced.is_none() && chunks.len() > 2 {
+            for i in 0..chunks.len() {
+                let complement: Vec<T> = chunks
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .flat_map(|(_, chunk)| chunk.iter().cloned())
+                    .collect();
+                if test(&complement)? {
+                    reduced = Some((complement, usize::max(granularity - 1, 2)));
+                    break;
+                }
+            }
+        }
+
+        match reduced {
+            Some((subset, new_granularity)) => {
+                current = subset;
+                granularity = new_granularity;
+            }
+            None if granularity >= current.len() => break,
+            None => granularity = usize::min(granularity * 2, current.len()),
+        }
+    }
+    Ok(current)
+}
+
+/// Check that all (processed) dynamic properties are trap-space properties, which are the only
+/// ones supported in [InferenceType::TrapSpaceInference] mode. Otherwise, return an error
+/// naming the first unsupported property.
+fn assert_trap_space_props_only(dyn_props: &[ProcessedDynProp]) -> Result<(), String> {
+    for prop in dyn_props {
+        if !matches!(prop, ProcessedDynProp::ProcessedTrapSpace(_)) {
+            return Err(format!(
+                "Dynamic property `{}` is not supported in trap-space inference mode. Only trap-space properties can be used.",
+                prop.id()
+            ));
+        }
+    }
+    Ok(())
+}
+
 /// Check if InferenceStatus requires number of remaining candidates when reporting about
 /// progress.
 fn requires_candidate_num(status: &InferenceStatus) -> bool {
@@ -725,7 +1245,9 @@ fn requires_candidate_num(status: &InferenceStatus) -> bool {
             | InferenceStatus::EvaluatedAllDynamic
             | InferenceStatus::DetectedUnsat
             | InferenceStatus::Error
+            | InferenceStatus::Cancelled
             | InferenceStatus::InternalProgress(..)
+            | InferenceStatus::BddSizeLimitExceeded(..)
     )
 }
 
@@ -733,9 +1255,271 @@ fn requires_candidate_num(status: &InferenceStatus) -> bool {
 mod tests {
     use std::{collections::HashMap, vec};
 
-    use crate::inference::inference_solver::InferenceSolver;
+    use crate::algorithms::variable_ordering::VariableOrdering;
+    use crate::inference::inference_solver::{ddmin, InferenceSolver, PropertyTimeoutConfig};
+    use crate::inference::inference_status::{InferenceStatus, InferenceStatusReport};
+    use crate::inference::inference_type::InferenceType;
     use crate::sketchbook::model::FnTree;
-    use crate::sketchbook::Sketch;
+    use crate::sketchbook::properties::{DynProperty, StatProperty};
+    use crate::sketchbook::{JsonSerde, Sketch};
+    use std::sync::atomic::Ordering;
+    use std::sync::{mpsc, Arc};
+    use tauri::async_runtime::{block_on, RwLock};
+
+    #[test]
+    /// Test that evaluating static properties in parallel gives the same results as the
+    /// sequential evaluation.
+    fn test_parallel_static_eval() {
+        let mut sketch = Sketch::from_aeon("A -> A\nA -> B\nB -| A\nB -? B").unwrap();
+        let stat_prop = StatProperty::try_mk_generic("", "f_B(1, 0) | !f_A(1, 1)").unwrap();
+        sketch.properties.add_static_by_str("p", stat_prop).unwrap();
+        let stat_prop = StatProperty::try_mk_generic("", "f_B(0, 0)").unwrap();
+        sketch.properties.add_static_by_str("q", stat_prop).unwrap();
+        let inference_type = InferenceType::FullInference;
+
+        let run_inference = |parallel: bool| {
+            let (sender, _receiver) = mpsc::channel();
+            let mut solver = InferenceSolver::new(sender);
+            solver.set_parallel_static_eval(parallel);
+            let solver = Arc::new(RwLock::new(solver));
+            let result = InferenceSolver::run_inference_async(
+                solver,
+                sketch.clone(),
+                inference_type.clone(),
+            );
+            block_on(result).unwrap()
+        };
+
+        let sequential_results = run_inference(false);
+        let parallel_results = run_inference(true);
+        assert!(sequential_results.num_sat_networks > 0);
+        assert_eq!(
+            sequential_results.num_sat_networks,
+            parallel_results.num_sat_networks
+        );
+
+        // each evaluated property reports how it refined the candidates (the same in both modes)
+        let refinements = sequential_results.candidate_refinements();
+        assert!(refinements.iter().any(|r| r.prop_id == "p"));
+        assert!(refinements.iter().any(|r| r.prop_id == "q"));
+        assert!(refinements.iter().all(|r| r.num_eliminated().is_some()));
+        assert_eq!(refinements, parallel_results.candidate_refinements());
+    }
+
+    #[test]
+    /// Test that computing attractors in parallel gives the same results as the sequential
+    /// computation.
+    fn test_parallel_attractors() {
+        let mut sketch = Sketch::from_aeon("A -> B\nB -| C\nC -> A\nC -? C").unwrap();
+        let dyn_prop = DynProperty::try_mk_generic("", "3{x}: @{x}: AX {x}").unwrap();
+        sketch.properties.add_dynamic_by_str("p", dyn_prop).unwrap();
+        let dyn_prop = DynProperty::try_mk_attractor_count("", 1, 2).unwrap();
+        sketch.properties.add_dynamic_by_str("q", dyn_prop).unwrap();
+
+        let run_inference = |parallel: bool| {
+            let (sender, _receiver) = mpsc::channel();
+            let mut solver = InferenceSolver::new(sender);
+            solver.set_parallel_attractors(parallel);
+            let solver = Arc::new(RwLock::new(solver));
+            let result = InferenceSolver::run_inference_async(
+                solver,
+                sketch.clone(),
+                InferenceType::FullInference,
+            );
+            block_on(result).unwrap()
+        };
+
+        let sequential_results = run_inference(false);
+        let parallel_results = run_inference(true);
+        assert!(sequential_results.num_sat_networks > 0);
+        assert_eq!(
+            sequential_results.num_sat_networks,
+            parallel_results.num_sat_networks
+        );
+    }
+
+    #[test]
+    /// Test that the variable ordering does not affect the inference results.
+    fn test_variable_ordering() {
+        let mut sketch = Sketch::from_aeon("A -> B\nB -| C\nC -> A\nC -? C").unwrap();
+        let dyn_prop = DynProperty::try_mk_generic("", "3{x}: @{x}: AX {x}").unwrap();
+        sketch.properties.add_dynamic_by_str("p", dyn_prop).unwrap();
+
+        let orderings = [
+            VariableOrdering::Default,
+            VariableOrdering::Proximity,
+            VariableOrdering::Custom(vec!["C".into(), "B".into(), "A".into()]),
+        ];
+        let mut num_sat_networks = Vec::new();
+        for ordering in orderings {
+            let (sender, _receiver) = mpsc::channel();
+            let mut solver = InferenceSolver::new(sender);
+            solver.set_variable_ordering(ordering);
+            let inference_type = InferenceType::FullInference;
+            let results = solver.run_inference_modular(inference_type, sketch.clone(), true, true);
+            num_sat_networks.push(results.unwrap().num_sat_networks);
+        }
+        assert!(num_sat_networks
+            .iter()
+            .all(|num| *num == num_sat_networks[0]));
+
+        // invalid orderings are reported
+        let (sender, _receiver) = mpsc::channel();
+        let mut solver = InferenceSolver::new(sender);
+        solver.set_variable_ordering(VariableOrdering::Custom(vec!["A".into()]));
+        let inference_type = InferenceType::FullInference;
+        assert!(solver
+            .run_inference_modular(inference_type, sketch, true, true)
+            .is_err());
+    }
+
+    #[test]
+    /// Test the delta debugging search on a simple predicate.
+    fn test_ddmin() {
+        // subsets containing both 3 and 7 pass
+        let items: Vec<usize> = (0..10).collect();
+        let test = |subset: &[usize]| Ok(subset.contains(&3) && subset.contains(&7));
+        assert_eq!(ddmin(items, test).unwrap(), vec![3, 7]);
+
+        // errors stop the search
+        let items: Vec<usize> = (0..10).collect();
+        let test = |_: &[usize]| Err("Computation was cancelled.".to_string());
+        assert!(ddmin(items, test).is_err());
+    }
+
+    #[test]
+    /// Test finding a minimal set of conflicting properties of an unsatisfiable sketch.
+    fn test_find_minimal_unsat_properties() {
+        let mut sketch = Sketch::from_aeon("A -> A\nA -> B\nB -| A").unwrap();
+        let props = [
+            ("has_fixed_point", "3{x}: @{x}: AX {x}"),
+            ("has_attractor", "3{x}: @{x}: AG EF {x}"),
+            ("no_fixed_point", "~(3{x}: @{x}: AX {x})"),
+        ];
+        for (id, formula) in props {
+            let dyn_prop = DynProperty::try_mk_generic("", formula).unwrap();
+            sketch.properties.add_dynamic_by_str(id, dyn_prop).unwrap();
+        }
+
+        let (sender, _receiver) = mpsc::channel();
+        let mut solver = InferenceSolver::new(sender);
+        // the search can only be used after the inference finished
+        assert!(solver.find_minimal_unsat_properties(&sketch).is_err());
+
+        let inference_type = InferenceType::FullInference;
+        let results = solver.run_inference_modular(inference_type, sketch.clone(), true, true);
+        assert_eq!(results.unwrap().num_sat_networks, 0);
+        let conflicting = solver.find_minimal_unsat_properties(&sketch).unwrap();
+        assert_eq!(conflicting, vec!["has_fixed_point", "no_fixed_point"]);
+    }
+
+    #[test]
+    /// Test that a cancelled computation ends with `Cancelled` status and releases the results.
+    fn test_cancelled_inference() {
+        let (sender, _receiver) = mpsc::channel();
+        let solver = InferenceSolver::new(sender);
+        solver.cancellation_flag().store(true, Ordering::SeqCst);
+        let solver = Arc::new(RwLock::new(solver));
+
+        let sketch = Sketch::from_aeon("A -> A\nA -> B").unwrap();
+        let inference_type = InferenceType::FullInference;
+        let result =
+            InferenceSolver::run_inference_async(Arc::clone(&solver), sketch, inference_type);
+        assert!(block_on(result).is_err());
+
+        let solver = block_on(solver.read());
+        assert!(solver.is_finished());
+        let last_status = &solver.status_updates.last().unwrap().status;
+        assert_eq!(last_status, &InferenceStatus::Cancelled);
+        assert!(solver.graph().is_err());
+        assert!(solver.to_finished_solver().is_err());
+    }
+
+    #[test]
+    /// Test that properties exceeding the per-property timeout are skipped (or abort the
+    /// computation, if configured).
+    fn test_property_timeout() {
+        let mut sketch = Sketch::from_aeon("A -> A\nA -> B\nB -| A").unwrap();
+        let dyn_prop = DynProperty::try_mk_generic("", "3{x}: @{x}: AX {x}").unwrap();
+        sketch.properties.add_dynamic_by_str("p", dyn_prop).unwrap();
+        let inference_type = InferenceType::FullInference;
+
+        // with zero timeout, the property is interrupted right at the first checkpoint
+        let run_with_timeout = |abort_on_timeout: bool| {
+            let (sender, _receiver) = mpsc::channel();
+            let mut solver = InferenceSolver::new(sender);
+            let timeout = PropertyTimeoutConfig {
+                timeout_ms: 0,
+                abort_on_timeout,
+            };
+            solver.set_property_timeout(Some(timeout));
+            let solver = Arc::new(RwLock::new(solver));
+            let result = InferenceSolver::run_inference_async(
+                solver,
+                sketch.clone(),
+                inference_type.clone(),
+            );
+            block_on(result)
+        };
+
+        // the property is skipped, so only the (static) regulation properties are applied
+        let results = run_with_timeout(false).unwrap();
+        assert_eq!(results.timed_out_properties(), vec!["p".to_string()]);
+        assert_eq!(results.num_sat_networks, 2);
+        assert!(results
+            .summary_message
+            .contains("skipped due to timeout: p"));
+
+        let error = run_with_timeout(true).unwrap_err();
+        assert!(error.contains("exceeded the timeout"));
+    }
+
+    #[test]
+    /// Test that the BDD size is reported with status updates, and that exceeding the soft
+    /// limit is reported exactly once.
+    fn test_bdd_size_limit() {
+        let (sender, receiver) = mpsc::channel();
+        let mut solver = InferenceSolver::new(sender);
+        solver.set_bdd_size_limit(Some(0));
+        let solver = Arc::new(RwLock::new(solver));
+
+        let sketch = Sketch::from_aeon("A -> A\nA -> B\nB -| A").unwrap();
+        let inference_type = InferenceType::FullInference;
+        let result =
+            InferenceSolver::run_inference_async(Arc::clone(&solver), sketch, inference_type);
+        assert!(block_on(result).is_ok());
+
+        let reports: Vec<InferenceStatusReport> = receiver
+            .try_iter()
+            .map(|json| InferenceStatusReport::from_json_str(&json).unwrap())
+            .collect();
+        let num_warnings = reports
+            .iter()
+            .filter(|r| matches!(r.status, InferenceStatus::BddSizeLimitExceeded(..)))
+            .count();
+        assert_eq!(num_warnings, 1);
+        let solver = block_on(solver.read());
+        let last_report = solver.status_updates.last().unwrap();
+        assert!(last_report.bdd_size.is_some());
+        assert!(solver.peak_bdd_size() > 0);
+    }
+
+    #[test]
+    /// Test that input variables are extracted as constants without parameters (even if they
+    /// have an update function which is frozen but not fully specified).
+    fn test_extract_bn_inputs() {
+        let mut sketch = Sketch::from_aeon("B -> A\nA -> B\nB -| B\n$A: f(B)").unwrap();
+        let var_a = sketch.model.get_var_id("A").unwrap();
+        sketch.model.set_update_fn_frozen(&var_a, true).unwrap();
+        assert!(InferenceSolver::extract_bn(&sketch).is_err());
+        sketch.model.set_var_input(&var_a, true).unwrap();
+
+        let (bn, _) = InferenceSolver::extract_bn(&sketch).unwrap();
+        assert_eq!(bn.num_parameters(), 1); // only the implicit function of `B`
+        let bn_var_a = bn.as_graph().find_variable("A").unwrap();
+        let update_fn = bn.get_update_function(bn_var_a).as_ref().unwrap();
+        assert_eq!(update_fn.to_string(&bn), "A");
+    }
 
     #[test]
     /// We have a sketch with variables `A` and `B`, and functions `f`, `g`, `h`.
@@ -2,21 +2,38 @@ use crate::app::event::Event;
 use crate::app::state::{Consumed, SessionHelper, SessionState};
 use crate::app::{AeonError, DynError};
 use crate::debug;
-use crate::inference::candidate_sampling::download_witnesses;
+use crate::inference::candidate_assignment::extract_candidate_assignment;
+use crate::inference::candidate_attractors::{compute_candidate_attractors, AttractorsRequest};
+use crate::inference::candidate_ranking::{
+    rank_candidates_by_weighted_reachability, WeightedRankingRequest,
+};
+use crate::inference::candidate_sampling::{
+    count_candidates, download_witnesses, sample_one_witness,
+};
 use crate::inference::inference_results::InferenceResults;
 use crate::inference::inference_solver::FinishedInferenceSolver;
-use crate::inference::inference_solver::InferenceSolver;
+use crate::inference::inference_solver::{
+    InferenceSolver, PropertyTimeoutConfig, DEFAULT_BDD_SIZE_LIMIT,
+};
+use crate::inference::inference_status::InferenceStatus;
 use crate::inference::inference_type::InferenceType;
+use crate::inference::runs_comparison::{
+    check_shared_context, compare_candidate_sets, RunsComparison,
+};
 use crate::inference::sampling_data::SamplingData;
 use crate::sketchbook::data_structs::SketchData;
 use crate::sketchbook::{JsonSerde, Sketch};
+use biodivine_lib_param_bn::BooleanNetwork;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::sync::mpsc::{Receiver, Sender};
 use std::sync::Arc;
 use tauri::async_runtime::RwLock;
 
 use super::inference_status::InferenceStatusReport;
-use super::results_export::export_results;
+use super::results_export::{
+    export_candidates_streaming, export_results, CANDIDATE_EXPORT_BATCH_SIZE,
+};
 
 /// InferenceState manages the main functionalities and computations of inference session.
 /// That inludes boths the components that are exchanged with frontend,
@@ -29,13 +46,30 @@ pub struct InferenceState {
     sketch_received: bool,
     /// Potential inference solver instance.
     solver: Option<Arc<RwLock<InferenceSolver>>>,
+    /// Cancellation flag of the potential solver instance (shared, so that it can be set even
+    /// while the solver is locked by the running computation).
+    cancel_flag: Option<Arc<AtomicBool>>,
     /// Potential channel to receive (text) updates from the solver instance.
     receiver_channel: Option<Receiver<String>>,
     /// Copy of already finished inference solver instance, used to work with full inference results.
     /// If the inference ends with error, the error message is stored instead.
     finished_solver: Option<Result<FinishedInferenceSolver, String>>,
+    /// Copy of the finished solver instance of the previous successful inference run (if any),
+    /// so that results of two consecutive runs can be compared.
+    previous_solver: Option<FinishedInferenceSolver>,
     /// Potential simplified processed results of the inference.
     results: Option<InferenceResults>,
+    /// Soft limit on the number of BDD nodes used by the solver (see
+    /// [InferenceSolver::set_bdd_size_limit]). There is no limit if `None`.
+    bdd_size_limit: Option<usize>,
+    /// Optional timeout for evaluation of a single property (see
+    /// [InferenceSolver::set_property_timeout]). There is no timeout if `None`.
+    property_timeout: Option<PropertyTimeoutConfig>,
+    /// Flag to evaluate static properties in parallel (see
+    /// [InferenceSolver::set_parallel_static_eval]).
+    parallel_static_eval: bool,
+    /// Flag to compute attractors in parallel (see [InferenceSolver::set_parallel_attractors]).
+    parallel_attractors: bool,
 }
 
 impl InferenceState {
@@ -48,9 +82,15 @@ impl InferenceState {
             sketch: Sketch::default(),
             sketch_received: false,
             solver: None,
+            cancel_flag: None,
             finished_solver: None,
+            previous_solver: None,
             results: None,
             receiver_channel: None,
+            bdd_size_limit: Some(DEFAULT_BDD_SIZE_LIMIT),
+            property_timeout: None,
+            parallel_static_eval: false,
+            parallel_attractors: true,
         }
     }
 
@@ -60,9 +100,15 @@ impl InferenceState {
             sketch,
             sketch_received: true,
             solver: None,
+            cancel_flag: None,
             finished_solver: None,
+            previous_solver: None,
             results: None,
             receiver_channel: None,
+            bdd_size_limit: Some(DEFAULT_BDD_SIZE_LIMIT),
+            property_timeout: None,
+            parallel_static_eval: false,
+            parallel_attractors: true,
         }
     }
 
@@ -72,6 +118,55 @@ impl InferenceState {
         self.sketch_received = true;
     }
 
+    /// Set the soft limit on the number of BDD nodes used by the solver. Exceeding the limit
+    /// results in a warning to the user. Use `None` to disable the limit.
+    ///
+    /// The limit is applied to computations started after this change.
+    pub fn set_bdd_size_limit(&mut self, bdd_size_limit: Option<usize>) {
+        self.bdd_size_limit = bdd_size_limit;
+    }
+
+    /// Get the soft limit on the number of BDD nodes used by the solver.
+    pub fn get_bdd_size_limit(&self) -> Option<usize> {
+        self.bdd_size_limit
+    }
+
+    /// Set the timeout for evaluation of a single property. Use `None` to disable it.
+    ///
+    /// The timeout is applied to computations started after this change.
+    pub fn set_property_timeout(&mut self, property_timeout: Option<PropertyTimeoutConfig>) {
+        self.property_timeout = property_timeout;
+    }
+
+    /// Get the timeout for evaluation of a single property.
+    pub fn get_property_timeout(&self) -> Option<PropertyTimeoutConfig> {
+        self.property_timeout
+    }
+
+    /// Set whether static properties are evaluated in parallel.
+    ///
+    /// The setting is applied to computations started after this change.
+    pub fn set_parallel_static_eval(&mut self, parallel_static_eval: bool) {
+        self.parallel_static_eval = parallel_static_eval;
+    }
+
+    /// Check whether static properties are evaluated in parallel.
+    pub fn get_parallel_static_eval(&self) -> bool {
+        self.parallel_static_eval
+    }
+
+    /// Set whether attractors are computed in parallel.
+    ///
+    /// The setting is applied to computations started after this change.
+    pub fn set_parallel_attractors(&mut self, parallel_attractors: bool) {
+        self.parallel_attractors = parallel_attractors;
+    }
+
+    /// Check whether attractors are computed in parallel.
+    pub fn get_parallel_attractors(&self) -> bool {
+        self.parallel_attractors
+    }
+
     /// Get reference to the sketch data of this `InferenceState`.
     pub fn get_sketch(&self) -> &Sketch {
         &self.sketch
@@ -101,6 +196,7 @@ impl InferenceState {
     /// At the same time, all the inference-related fields of this `InferenceState` are reset.
     /// That is solver and results. The sketch stays the same.
     pub fn initiate_reset(&mut self) {
+        self.cancel_inference();
         if let Some(solver) = &self.solver {
             let solver: Arc<RwLock<InferenceSolver>> = Arc::clone(solver);
 
@@ -116,11 +212,26 @@ impl InferenceState {
             });
         }
         self.solver = None;
+        self.cancel_flag = None;
         self.receiver_channel = None;
         self.finished_solver = None;
         self.results = None;
     }
 
+    /// If a computation solver is running, set its cancellation flag. The computation stops at the
+    /// next checkpoint and the solver reports [super::inference_status::InferenceStatus::Cancelled].
+    ///
+    /// Unlike [Self::initiate_reset], the solver is kept, so that its final status can be fetched.
+    /// Returns false if there is no solver to cancel.
+    pub fn cancel_inference(&self) -> bool {
+        if let Some(cancel_flag) = &self.cancel_flag {
+            cancel_flag.store(true, Ordering::SeqCst);
+            true
+        } else {
+            false
+        }
+    }
+
     /// Check if the inference solver finished its computation. If so, clone the important parts
     /// of the solver into `Self.finished_solver` field (so we can easily access it).
     ///
@@ -159,6 +270,32 @@ impl InferenceState {
         }
     }
 
+    /// Compare the candidate set of the current (finished) inference run with the previous
+    /// successful run. At most `num_examples` networks removed by the current run are
+    /// materialized. See [compare_candidate_sets] for details.
+    ///
+    /// Both runs must share the same symbolic context (see [check_shared_context]).
+    pub fn compare_with_previous_run(&self, num_examples: usize) -> Result<RunsComparison, String> {
+        let Some(Ok(current_solver)) = &self.finished_solver else {
+            return Err(
+                "Results of the current inference run were not fetched yet (or were erronous)."
+                    .to_string(),
+            );
+        };
+        let Some(previous_solver) = &self.previous_solver else {
+            return Err(
+                "There is no previous successful inference run to compare with.".to_string(),
+            );
+        };
+        check_shared_context(&previous_solver.graph, &current_solver.graph)?;
+        compare_candidate_sets(
+            &previous_solver.sat_colors,
+            &current_solver.sat_colors,
+            &previous_solver.graph,
+            num_examples,
+        )
+    }
+
     /// Check if the results were already fetched from the solver.
     pub fn check_if_results_fetched(&mut self) -> bool {
         self.finished_solver.is_some()
@@ -204,12 +341,22 @@ impl InferenceState {
             return AeonError::throw("Cannot run inference on empty sketch.");
         }
 
+        // Keep the results of the last successful run, so that they can be compared with the new ones
+        if let Some(Ok(finished_solver)) = self.finished_solver.take() {
+            self.previous_solver = Some(finished_solver);
+        }
         self.initiate_reset(); // Reset the state before starting new inference
 
         let (progress_sender, progress_receiver): (Sender<String>, Receiver<String>) =
             mpsc::channel();
         self.receiver_channel = Some(progress_receiver);
-        let solver = Arc::new(RwLock::new(InferenceSolver::new(progress_sender)));
+        let mut solver = InferenceSolver::new(progress_sender);
+        solver.set_bdd_size_limit(self.bdd_size_limit);
+        solver.set_property_timeout(self.property_timeout);
+        solver.set_parallel_static_eval(self.parallel_static_eval);
+        solver.set_parallel_attractors(self.parallel_attractors);
+        self.cancel_flag = Some(solver.cancellation_flag());
+        let solver = Arc::new(RwLock::new(solver));
         self.solver = Some(Arc::clone(&solver));
 
         // Process datasets in sketch, so that the variables match exactly with the model
@@ -284,6 +431,19 @@ impl SessionState for InferenceState {
                     reset: true,
                 })
             }
+            Some(&"run_trap_space_inference") => {
+                Self::assert_payload_empty(event, "inference")?;
+                debug!(
+                    "Event `run_trap_space_inference` received. Starting inference restricted to trap-space properties."
+                );
+
+                self.start_inference(InferenceType::TrapSpaceInference)?; // Start inference and handle asynchronously
+                let state_change = Event::build(&["inference", "inference_running"], Some("true"));
+                Ok(Consumed::Irreversible {
+                    state_change,
+                    reset: true,
+                })
+            }
             Some(&"get_inference_results") => {
                 // Note that this event can be used to retrieve results of any running inference, be it
                 // full inference, static check, or dynamic check.
@@ -322,10 +482,22 @@ impl SessionState for InferenceState {
                     let payload = serde_json::to_string(&progress_updates).unwrap();
                     let state_change =
                         Event::build(&["inference", "computation_update"], Some(&payload));
-                    Ok(Consumed::Irreversible {
-                        state_change,
-                        reset: true,
-                    })
+                    // exceeding the BDD size limit is additionally reported as a warning
+                    let limit_warning = progress_updates.iter().find(|report| {
+                        matches!(report.status, InferenceStatus::BddSizeLimitExceeded(..))
+                    });
+                    if let Some(report) = limit_warning {
+                        Ok(Consumed::IrreversibleWithWarning {
+                            state_change,
+                            reset: true,
+                            warning: report.message.clone(),
+                        })
+                    } else {
+                        Ok(Consumed::Irreversible {
+                            state_change,
+                            reset: true,
+                        })
+                    }
                 } else {
                     Ok(Consumed::NoChange)
                 }
@@ -343,6 +515,71 @@ impl SessionState for InferenceState {
                     reset: true,
                 })
             }
+            Some(&"cancel") => {
+                Self::assert_payload_empty(event, component)?;
+
+                // this only sets the cancellation flag, the solver then finishes with `Cancelled`
+                // status that is reported via `get_inference_results` as usual
+                if !self.cancel_inference() {
+                    return AeonError::throw("There is no inference computation to cancel.");
+                }
+                let state_change =
+                    Event::build(&["inference", "cancellation_requested"], Some("true"));
+                Ok(Consumed::Irreversible {
+                    state_change,
+                    reset: true,
+                })
+            }
+            Some(&"set_bdd_size_limit") => {
+                let payload = Self::clone_payload_str(event, component)?;
+                let bdd_size_limit = serde_json::from_str::<Option<usize>>(&payload)?;
+                self.set_bdd_size_limit(bdd_size_limit);
+
+                let payload = serde_json::to_string(&bdd_size_limit).unwrap();
+                let state_change = Event::build(&["inference", "bdd_size_limit"], Some(&payload));
+                Ok(Consumed::Irreversible {
+                    state_change,
+                    reset: false,
+                })
+            }
+            Some(&"set_property_timeout") => {
+                let payload = Self::clone_payload_str(event, component)?;
+                let timeout = serde_json::from_str::<Option<PropertyTimeoutConfig>>(&payload)?;
+                self.set_property_timeout(timeout);
+
+                let payload = serde_json::to_string(&timeout).unwrap();
+                let state_change = Event::build(&["inference", "property_timeout"], Some(&payload));
+                Ok(Consumed::Irreversible {
+                    state_change,
+                    reset: false,
+                })
+            }
+            Some(&"set_parallel_static_eval") => {
+                let payload = Self::clone_payload_str(event, component)?;
+                let parallel_static_eval = serde_json::from_str::<bool>(&payload)?;
+                self.set_parallel_static_eval(parallel_static_eval);
+
+                let payload = serde_json::to_string(&parallel_static_eval).unwrap();
+                let state_change =
+                    Event::build(&["inference", "parallel_static_eval"], Some(&payload));
+                Ok(Consumed::Irreversible {
+                    state_change,
+                    reset: false,
+                })
+            }
+            Some(&"set_parallel_attractors") => {
+                let payload = Self::clone_payload_str(event, component)?;
+                let parallel_attractors = serde_json::from_str::<bool>(&payload)?;
+                self.set_parallel_attractors(parallel_attractors);
+
+                let payload = serde_json::to_string(&parallel_attractors).unwrap();
+                let state_change =
+                    Event::build(&["inference", "parallel_attractors"], Some(&payload));
+                Ok(Consumed::Irreversible {
+                    state_change,
+                    reset: false,
+                })
+            }
             Some(&"sample_networks") => {
                 let payload = Self::clone_payload_str(event, component)?;
                 let sampling_data = SamplingData::from_json_str(&payload)?;
@@ -362,6 +599,56 @@ impl SessionState for InferenceState {
                     )
                 }
             }
+            Some(&"compare_with_previous_run") => {
+                let payload = Self::clone_payload_str(event, component)?;
+                let num_examples = serde_json::from_str::<usize>(&payload)?;
+                let comparison = self.compare_with_previous_run(num_examples)?;
+
+                let payload = comparison.to_json_str();
+                let state_change = Event::build(&["inference", "runs_comparison"], Some(&payload));
+                Ok(Consumed::Irreversible {
+                    state_change,
+                    reset: false,
+                })
+            }
+            Some(&"candidate_attractors") => {
+                let payload = Self::clone_payload_str(event, component)?;
+                let request = AttractorsRequest::from_json_str(&payload)?;
+                let bn = BooleanNetwork::try_from(request.network.as_str())?;
+                let attractors = compute_candidate_attractors(&bn, request.max_states)?;
+
+                let payload = attractors.to_json_str();
+                let state_change =
+                    Event::build(&["inference", "candidate_attractors"], Some(&payload));
+                Ok(Consumed::Irreversible {
+                    state_change,
+                    reset: false,
+                })
+            }
+            Some(&"rank_by_weighted_reachability") => {
+                let payload = Self::clone_payload_str(event, component)?;
+                let request = WeightedRankingRequest::from_json_str(&payload)?;
+
+                if let Some(Ok(solver)) = &self.finished_solver {
+                    let ranking = rank_candidates_by_weighted_reachability(
+                        &request,
+                        &self.sketch,
+                        &solver.graph,
+                        &solver.sat_colors,
+                    )?;
+                    let payload = ranking.to_json_str();
+                    let state_change =
+                        Event::build(&["inference", "weighted_ranking"], Some(&payload));
+                    Ok(Consumed::Irreversible {
+                        state_change,
+                        reset: false,
+                    })
+                } else {
+                    AeonError::throw(
+                        "Cannot rank candidates because inference results were not fetched yet (or were erronous).",
+                    )
+                }
+            }
             Some(&"dump_full_results") => {
                 let archive_name = Self::clone_payload_str(event, component)?;
 
@@ -374,6 +661,24 @@ impl SessionState for InferenceState {
                     )
                 }
             }
+            Some(&"export_all_candidates") => {
+                let archive_name = Self::clone_payload_str(event, component)?;
+
+                if let Some(Ok(solver)) = &self.finished_solver {
+                    export_candidates_streaming(
+                        &archive_name,
+                        &solver.sat_colors,
+                        &solver.bn,
+                        CANDIDATE_EXPORT_BATCH_SIZE,
+                        &mut |_, msg| debug!("{msg}"),
+                    )?;
+                    Ok(Consumed::NoChange {})
+                } else {
+                    AeonError::throw(
+                        "Cannot export candidate networks, inference results were not fetched yet (or were erronous).",
+                    )
+                }
+            }
             _ => Self::invalid_path_error_generic(at_path),
         }
     }
@@ -391,6 +696,52 @@ impl SessionState for InferenceState {
                     payload: Some(sketch_data.to_json_str()),
                 })
             }
+            Some(&"num_candidates") => {
+                Self::assert_path_length(at_path, 1, component_name)?;
+                if let Some(Ok(solver)) = &self.finished_solver {
+                    // the count can be arbitrarily large, so it is sent as a string
+                    let num_candidates = count_candidates(&solver.sat_colors).to_string();
+                    Ok(Event {
+                        path: full_path.to_vec(),
+                        payload: Some(num_candidates),
+                    })
+                } else {
+                    AeonError::throw(
+                        "Cannot count candidates because inference results were not fetched yet (or were erronous).",
+                    )
+                }
+            }
+            Some(&"witness_network") => {
+                Self::assert_path_length(at_path, 1, component_name)?;
+                if let Some(Ok(solver)) = &self.finished_solver {
+                    // a single (deterministically chosen) witness network in the AEON format
+                    let witness = sample_one_witness(&solver.sat_colors, &solver.graph)?;
+                    Ok(Event {
+                        path: full_path.to_vec(),
+                        payload: Some(witness.to_string()),
+                    })
+                } else {
+                    AeonError::throw(
+                        "Cannot sample a witness network because inference results were not fetched yet (or were erronous).",
+                    )
+                }
+            }
+            Some(&"candidate_assignment") => {
+                Self::assert_path_length(at_path, 1, component_name)?;
+                if let Some(Ok(solver)) = &self.finished_solver {
+                    // the same (deterministically chosen) candidate as the witness network
+                    let color = solver.sat_colors.pick_singleton();
+                    let assignment = extract_candidate_assignment(&color, &solver.graph)?;
+                    Ok(Event {
+                        path: full_path.to_vec(),
+                        payload: Some(assignment.to_json_str()),
+                    })
+                } else {
+                    AeonError::throw(
+                        "Cannot extract a candidate assignment because inference results were not fetched yet (or were erronous).",
+                    )
+                }
+            }
             _ => Self::invalid_path_error_generic(at_path),
         }
     }
@@ -398,6 +749,7 @@ impl SessionState for InferenceState {
 
 #[cfg(test)]
 mod tests {
+    use crate::inference::inference_solver::{PropertyTimeoutConfig, DEFAULT_BDD_SIZE_LIMIT};
     use crate::inference::inference_state::InferenceState;
     use crate::inference::inference_type::InferenceType::*;
     use crate::sketchbook::Sketch;
@@ -419,6 +771,8 @@ mod tests {
         // check that we cant get any progress or fetch results as there is no computation
         assert!(!inference_state.try_fetch_results());
         assert!(inference_state.try_get_solver_progress().is_err());
+        // there are no runs to compare
+        assert!(inference_state.compare_with_previous_run(1).is_err());
 
         // check that inference on empty sketch fails
         let result = inference_state.start_inference(DynamicInference);
@@ -427,6 +781,31 @@ mod tests {
         // set new non-empty sketch data
         let valid_sketch = Sketch::from_aeon("a -> a").unwrap();
         inference_state.set_sketch(valid_sketch);
-        assert_eq!(inference_state.get_sketch().model.num_vars(), 1)
+        assert_eq!(inference_state.get_sketch().model.num_vars(), 1);
+
+        // the BDD size limit can be changed or disabled
+        assert_eq!(
+            inference_state.get_bdd_size_limit(),
+            Some(DEFAULT_BDD_SIZE_LIMIT)
+        );
+        inference_state.set_bdd_size_limit(None);
+        assert_eq!(inference_state.get_bdd_size_limit(), None);
+
+        // there is no property timeout by default
+        assert_eq!(inference_state.get_property_timeout(), None);
+        let timeout = PropertyTimeoutConfig {
+            timeout_ms: 1000,
+            abort_on_timeout: false,
+        };
+        inference_state.set_property_timeout(Some(timeout));
+        assert_eq!(inference_state.get_property_timeout(), Some(timeout));
+
+        // static properties are evaluated sequentially and attractors in parallel by default
+        assert!(!inference_state.get_parallel_static_eval());
+        assert!(inference_state.get_parallel_attractors());
+        inference_state.set_parallel_static_eval(true);
+        inference_state.set_parallel_attractors(false);
+        assert!(inference_state.get_parallel_static_eval());
+        assert!(!inference_state.get_parallel_attractors());
     }
 }
@@ -1,5 +1,7 @@
 use crate::sketchbook::JsonSerde;
+use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 /// Status of the inference computation.
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -28,12 +30,56 @@ pub enum InferenceStatus {
     EvaluatedAllDynamic,
     /// Internal progress message (such as progress data from the model checker).
     InternalProgress(String),
+    /// Symbolic representation exceeded the soft limit on its size (with the current number
+    /// of BDD nodes). The computation continues, but it may run out of memory.
+    BddSizeLimitExceeded(usize),
+    /// Evaluation of a particular dynamic property exceeded the per-property timeout, and the
+    /// property was skipped.
+    PropertyTimeout(String),
     /// Detected that sketch is unsatisfiable (can happen at the end or during computation).
     DetectedUnsat,
     /// Computation is successfully finished.
     FinishedSuccessfully,
-    /// Computation is finished but unsuccessful (cancellation or processing error).
+    /// Computation is finished but unsuccessful (processing error).
     Error,
+    /// Computation was cancelled before it finished.
+    Cancelled,
+}
+
+/// Record on how evaluating a single property refined the set of candidates. The numbers of
+/// candidates are represented as strings, since they can be arbitrarily large.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CandidateRefinement {
+    /// ID of the property that was just evaluated.
+    pub prop_id: String,
+    /// Number of remaining candidates before the property was evaluated.
+    pub num_candidates_before: String,
+    /// Number of remaining candidates after the property was evaluated.
+    pub num_candidates_after: String,
+}
+
+impl CandidateRefinement {
+    /// Create new `CandidateRefinement` given all the details.
+    pub fn new(prop_id: &str, num_before: &str, num_after: &str) -> CandidateRefinement {
+        CandidateRefinement {
+            prop_id: prop_id.to_string(),
+            num_candidates_before: num_before.to_string(),
+            num_candidates_after: num_after.to_string(),
+        }
+    }
+
+    /// Number of candidates eliminated by the property (or `None` if the numbers of candidates
+    /// are not valid non-negative integers).
+    pub fn num_eliminated(&self) -> Option<BigUint> {
+        let num_before = BigUint::from_str(&self.num_candidates_before).ok()?;
+        let num_after = BigUint::from_str(&self.num_candidates_after).ok()?;
+        // the set of candidates can only shrink, but let's be safe
+        if num_after > num_before {
+            Some(BigUint::ZERO)
+        } else {
+            Some(num_before - num_after)
+        }
+    }
 }
 
 /// Report on status of the computation, together with few details and a timestamp.
@@ -48,6 +94,12 @@ pub struct InferenceStatusReport {
     pub comp_time: u128,
     /// Message to be shown at the frontend.
     pub message: String,
+    /// Optional approximate size (number of BDD nodes) of the symbolic sets currently processed
+    /// by the solver, used as an estimate of memory usage.
+    pub bdd_size: Option<usize>,
+    /// Optional record on how the set of candidates was refined by the property that was just
+    /// evaluated (only relevant for statuses reporting that a property was evaluated).
+    pub refinement: Option<CandidateRefinement>,
 }
 
 impl JsonSerde<'_> for InferenceStatusReport {}
@@ -65,6 +117,23 @@ impl InferenceStatusReport {
             num_candidates,
             comp_time,
             message: message.to_string(),
+            bdd_size: None,
+            refinement: None,
         }
     }
+
+    /// Set the approximate size (number of BDD nodes) of the processed symbolic sets.
+    pub fn with_bdd_size(mut self, bdd_size: Option<usize>) -> InferenceStatusReport {
+        self.bdd_size = bdd_size;
+        self
+    }
+
+    /// Set the record on how the set of candidates was refined by the evaluated property.
+    pub fn with_refinement(
+        mut self,
+        refinement: Option<CandidateRefinement>,
+    ) -> InferenceStatusReport {
+        self.refinement = refinement;
+        self
+    }
 }
@@ -5,4 +5,11 @@ pub enum InferenceType {
     FullInference,
     StaticInference,
     DynamicInference,
+    /// Inference with all static properties, but with dynamic properties restricted to trap-space
+    /// queries. This skips the attractor computation entirely.
+    ///
+    /// Supported dynamic properties are `ExistsTrapSpace` templates (general, minimal, or
+    /// non-percolable trap spaces). Any other dynamic property (generic HCTL, fixed points,
+    /// attractors, attractor count, trajectories) makes the inference fail with an error.
+    TrapSpaceInference,
 }
@@ -1,3 +1,9 @@
+/// Structure and utilities to extract parameter assignment of a single candidate network.
+pub mod candidate_assignment;
+/// Utilities to compute attractor states of a single candidate network.
+pub mod candidate_attractors;
+/// Utilities to rank candidates by weighted (fuzzy) reachability between datasets.
+pub mod candidate_ranking;
 /// Utilities to sample and download networks.
 /// Some functionality is taken from our repository [biodivine-bn-classifier].
 pub mod candidate_sampling;
@@ -15,8 +21,12 @@ pub mod inference_status;
 pub mod inference_type;
 /// Utilities to download results.
 pub mod results_export;
+/// Utilities to compare candidate sets of two inference runs.
+pub mod runs_comparison;
 /// Struct with details regarding candidate sampling.
 pub mod sampling_data;
+/// Human-readable summary of inference results (in JSON or Markdown).
+pub mod summary_report;
 
 /// Utilities to explore canditate update functions.
 mod update_fn_details;
@@ -1,17 +1,25 @@
 use crate::inference::inference_solver::FinishedInferenceSolver;
+use crate::inference::summary_report::InferenceSummaryReport;
 use crate::inference::update_fn_details::get_update_fn_variants;
-use crate::sketchbook::Sketch;
+use crate::sketchbook::{JsonSerde, Sketch};
 
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+use biodivine_lib_param_bn::BooleanNetwork;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 use zip::write::{FileOptions, ZipWriter};
 
+/// Number of candidate networks exported in a single batch by [export_candidates_streaming].
+pub const CANDIDATE_EXPORT_BATCH_SIZE: usize = 100;
+
 /// Export archive with complete results to the given path.
 /// The output archive is tailored for a case where sketch is satisfiable.
 ///
 /// The results archive include:
 /// - a summary report (basically information tracked by the `InferenceResults` struct)
+/// - a short summary (see [InferenceSummaryReport]) in both Markdown and JSON formats
 /// - original sketch in JSON format for replicability in Sketchbook
 /// - BDD with satisfying colors
 /// - a PSBN model derived from the sketch (in aeon format) that can be used as a context for the BDD
@@ -47,6 +55,15 @@ pub fn export_results(
     let formatted_report = finished_solver.results.format_to_report();
     write_to_zip("report.txt", &mut zip_writer, formatted_report)?;
 
+    // write the short summary (both as Markdown and JSON)
+    let summary = InferenceSummaryReport::new(&finished_solver.results, original_sketch);
+    write_to_zip("summary.md", &mut zip_writer, summary.to_markdown())?;
+    write_to_zip(
+        "summary.json",
+        &mut zip_writer,
+        summary.to_pretty_json_str(),
+    )?;
+
     // create directory with update function variants per variable
     zip_writer
         .add_directory("admissible_update_functions/", FileOptions::default())
@@ -66,6 +83,61 @@ pub fn export_results(
     Ok(())
 }
 
+/// Export all candidate networks given by the `colors` set into a zip archive at the given path,
+/// each network in a separate `candidate_{i}.aeon` file. The colors must be valid for the
+/// symbolic context derived from the given `bn` (e.g., the results of the inference solver).
+///
+/// The candidates are processed in batches of `batch_size` networks, and each network is written
+/// to the archive right after it is instantiated. This way, the memory usage stays bounded
+/// regardless of the number of candidates. After each batch, the `progress_callback` is called
+/// with the set of remaining (not yet exported) colors and a progress message.
+pub fn export_candidates_streaming<F: FnMut(&GraphColors, &str)>(
+    path: &str,
+    colors: &GraphColors,
+    bn: &BooleanNetwork,
+    batch_size: usize,
+    progress_callback: &mut F,
+) -> Result<(), String> {
+    if batch_size == 0 {
+        return Err("Batch size must be a positive number.".to_string());
+    }
+    let graph = SymbolicAsyncGraph::new(bn)?;
+
+    // Prepare the archive first
+    let archive_path = Path::new(path);
+    // If there are some non existing dirs in path, create them.
+    if let Some(prefix) = archive_path.parent() {
+        std::fs::create_dir_all(prefix).map_err(|e| format!("{e:?}"))?;
+    }
+    // Create a zip writer for the desired archive.
+    let archive = File::create(archive_path).map_err(|e| format!("{e:?}"))?;
+    let mut zip_writer = ZipWriter::new(archive);
+
+    let mut remaining_colors = colors.clone();
+    let mut num_exported = 0;
+    while !remaining_colors.is_empty() {
+        for _ in 0..batch_size {
+            if remaining_colors.is_empty() {
+                break;
+            }
+            // The `GraphColors::pick_singleton` should be deterministic.
+            let candidate_color = remaining_colors.pick_singleton();
+            remaining_colors = remaining_colors.minus(&candidate_color);
+            num_exported += 1;
+
+            // Write the network into the zip right away.
+            let file_name = format!("candidate_{num_exported}.aeon");
+            let file_content = graph.pick_witness(&candidate_color).to_string();
+            write_to_zip(&file_name, &mut zip_writer, file_content)?;
+        }
+        let msg = format!("Exported {num_exported} candidate networks.");
+        progress_callback(&remaining_colors, &msg);
+    }
+
+    zip_writer.finish().map_err(|e| format!("{e:?}"))?;
+    Ok(())
+}
+
 /// Helper function to write string into a zip archive file.
 fn write_to_zip(
     file_name: &str,
@@ -88,3 +160,40 @@ pub fn get_update_fn_variants_from_solver(
 ) -> Result<Vec<String>, String> {
     get_update_fn_variants(&solver.sat_colors, &solver.bn, var_name)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::inference::results_export::export_candidates_streaming;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use biodivine_lib_param_bn::BooleanNetwork;
+    use std::fs::File;
+    use zip::ZipArchive;
+
+    #[test]
+    /// Test exporting all candidate networks in several batches.
+    fn test_export_candidates_streaming() {
+        // there are 2 candidate functions for `A` (`A & !B` and `A | !B`)
+        let bn = BooleanNetwork::try_from("A -> A\nB -| A\nA -> B\n$B: A").unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+        let colors = graph.mk_unit_colors();
+        assert_eq!(colors.approx_cardinality(), 2.0);
+
+        // the directory does not exist yet, it is created during the export
+        let directory =
+            std::env::temp_dir().join(format!("sketchbook_test_export_{}", std::process::id()));
+        let path = directory.join("candidates.zip");
+        let path = path.to_str().unwrap();
+        let mut num_callbacks = 0;
+        let mut progress_callback = |_: &_, _: &str| num_callbacks += 1;
+        export_candidates_streaming(path, &colors, &bn, 1, &mut progress_callback).unwrap();
+        assert_eq!(num_callbacks, 2);
+
+        let archive = ZipArchive::new(File::open(path).unwrap()).unwrap();
+        assert_eq!(archive.len(), 2);
+        assert!(archive.file_names().any(|name| name == "candidate_2.aeon"));
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        // zero batch size is not allowed
+        assert!(export_candidates_streaming(path, &colors, &bn, 0, &mut |_, _| {}).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::inference::candidate_sampling::pick_networks;
+use crate::sketchbook::JsonSerde;
+use biodivine_lib_param_bn::biodivine_std::traits::Set;
+use biodivine_lib_param_bn::symbolic_async_graph::{GraphColors, SymbolicAsyncGraph};
+use serde::{Deserialize, Serialize};
+
+/// Comparison of candidate sets of two inference runs (a "previous" and a "current" one).
+///
+/// The candidate counts can be arbitrarily large, so they are kept as strings (same as
+/// when sending the number of candidates to the frontend). The removed example networks are
+/// in the AEON format.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunsComparison {
+    /// Number of candidates satisfying the current run, but not the previous one.
+    pub num_added: String,
+    /// Number of candidates satisfying the previous run, but not the current one.
+    pub num_removed: String,
+    /// Number of candidates satisfying both runs.
+    pub num_retained: String,
+    /// Several (deterministically chosen) networks that were removed by the current run.
+    pub removed_examples: Vec<String>,
+}
+
+impl JsonSerde<'_> for RunsComparison {}
+
+/// Check that two symbolic graphs (of two inference runs) share the same symbolic context, i.e.,
+/// their color sets can be directly combined using set operations.
+///
+/// This is the case when both runs were computed over the same model (and thus the same
+/// parameter space), and the symbolic encoding uses the same BDD variables. Results of
+/// inference runs are converted into the canonical context (without extra variables needed
+/// for HCTL properties), so this holds for any two successful runs over the same model.
+pub fn check_shared_context(
+    previous_graph: &SymbolicAsyncGraph,
+    current_graph: &SymbolicAsyncGraph,
+) -> Result<(), String> {
+    let (previous_ctx, current_ctx) = (
+        previous_graph.symbolic_context(),
+        current_graph.symbolic_context(),
+    );
+    if previous_graph.as_network() != current_graph.as_network() {
+        return Err("Cannot compare inference runs computed over different models.".to_string());
+    }
+    if previous_ctx.bdd_variable_set().num_vars() != current_ctx.bdd_variable_set().num_vars()
+        || previous_ctx.parameter_variables() != current_ctx.parameter_variables()
+    {
+        return Err("Cannot compare inference runs with different symbolic encodings.".to_string());
+    }
+    Ok(())
+}
+
+/// Compare candidate sets of two inference runs, computing the numbers of added, removed, and
+/// retained candidates. At most `num_examples` of the removed candidates are materialized
+/// into example networks (using the `graph`).
+///
+/// Both color sets must come from runs sharing the same symbolic context (see
+/// [check_shared_context]), and the `graph` must be one of these. The number of BDD variables
+/// of both color sets is checked against the `graph`, other violations are not detected.
+pub fn compare_candidate_sets(
+    previous_colors: &GraphColors,
+    current_colors: &GraphColors,
+    graph: &SymbolicAsyncGraph,
+    num_examples: usize,
+) -> Result<RunsComparison, String> {
+    let num_vars = graph.symbolic_context().bdd_variable_set().num_vars();
+    if previous_colors.as_bdd().num_vars() != num_vars
+        || current_colors.as_bdd().num_vars() != num_vars
+    {
+        return Err("Compared candidate sets do not share the symbolic context.".to_string());
+    }
+
+    let added = current_colors.minus(previous_colors);
+    let removed = previous_colors.minus(current_colors);
+    let retained = previous_colors.intersect(current_colors);
+    let removed_examples = pick_networks(&removed, graph, num_examples)
+        .iter()
+        .map(|bn| bn.to_string())
+        .collect();
+
+    Ok(RunsComparison {
+        num_added: added.exact_cardinality().to_string(),
+        num_removed: removed.exact_cardinality().to_string(),
+        num_retained: retained.exact_cardinality().to_string(),
+        removed_examples,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inference::runs_comparison::{check_shared_context, compare_candidate_sets};
+    use biodivine_lib_param_bn::biodivine_std::traits::Set;
+    use biodivine_lib_param_bn::symbolic_async_graph::SymbolicAsyncGraph;
+    use biodivine_lib_param_bn::BooleanNetwork;
+
+    #[test]
+    /// Test comparing candidate sets of two runs over the same model (in both directions).
+    fn test_compare_candidate_sets() {
+        // two runs over the same model, the second one eliminates a single candidate
+        let bn = BooleanNetwork::try_from("a -> a\nb -> a\nc -> a\na -> b\na -> c").unwrap();
+        let previous_graph = SymbolicAsyncGraph::new(&bn).unwrap();
+        let current_graph = SymbolicAsyncGraph::new(&bn).unwrap();
+        let previous_colors = previous_graph.mk_unit_colors();
+        let removed_color = current_graph.mk_unit_colors().pick_singleton();
+        let current_colors = current_graph.mk_unit_colors().minus(&removed_color);
+        assert!(check_shared_context(&previous_graph, &current_graph).is_ok());
+
+        let num_all = previous_colors.exact_cardinality();
+        let comparison =
+            compare_candidate_sets(&previous_colors, &current_colors, &previous_graph, 3).unwrap();
+        assert_eq!(comparison.num_added, "0");
+        assert_eq!(comparison.num_removed, "1");
+        assert_eq!(comparison.num_retained, (num_all - 1u32).to_string());
+        // only a single network can be materialized, and it is the removed one
+        let removed_network = current_graph.pick_witness(&removed_color).to_string();
+        assert_eq!(comparison.removed_examples, vec![removed_network]);
+
+        // comparing the runs the other way around
+        let comparison =
+            compare_candidate_sets(&current_colors, &previous_colors, &current_graph, 3).unwrap();
+        assert_eq!(comparison.num_added, "1");
+        assert_eq!(comparison.num_removed, "0");
+        assert!(comparison.removed_examples.is_empty());
+    }
+
+    #[test]
+    /// Test that candidate sets over different symbolic contexts cannot be compared.
+    fn test_compare_different_contexts() {
+        let bn = BooleanNetwork::try_from("a -> a\nb -> a\na -> b").unwrap();
+        let other_bn = BooleanNetwork::try_from("a -> a").unwrap();
+        let graph = SymbolicAsyncGraph::new(&bn).unwrap();
+        let other_graph = SymbolicAsyncGraph::new(&other_bn).unwrap();
+        assert!(check_shared_context(&graph, &other_graph).is_err());
+
+        let (colors, other_colors) = (graph.mk_unit_colors(), other_graph.mk_unit_colors());
+        assert!(compare_candidate_sets(&colors, &other_colors, &graph, 1).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use crate::inference::inference_results::InferenceResults;
+use crate::inference::inference_type::InferenceType;
+use crate::sketchbook::{JsonSerde, Sketch};
+use serde::{Deserialize, Serialize};
+
+/// Summary of how a single evaluated property refined the set of candidates.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PropertySummary {
+    pub prop_id: String,
+    pub num_candidates_before: String,
+    pub num_candidates_after: String,
+    /// Number of eliminated candidates (`None` if it could not be computed).
+    pub num_eliminated: Option<String>,
+}
+
+/// Human-readable summary of finished inference, meant to be shared (e.g., pasted into
+/// a methods section of a paper or into an issue). It can be serialized to JSON, or rendered
+/// as a Markdown document (see [InferenceSummaryReport::to_markdown]).
+///
+/// Unlike the full report (see [InferenceResults::format_to_report]), it does not include
+/// the detailed progress messages.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InferenceSummaryReport {
+    pub analysis_type: InferenceType,
+    pub num_sat_networks: u128,
+    /// Number of free parameters of the sketch (see [Sketch::count_free_parameters]).
+    pub num_free_params: u64,
+    /// Total computation time in milliseconds.
+    pub comp_time: u128,
+    /// Evaluated properties, in the order of evaluation.
+    pub properties: Vec<PropertySummary>,
+    /// IDs of properties skipped due to the per-property timeout.
+    pub timed_out_properties: Vec<String>,
+}
+
+impl JsonSerde<'_> for InferenceSummaryReport {}
+
+impl InferenceSummaryReport {
+    /// Create a summary from the inference `results` and the `sketch` that was analysed.
+    ///
+    /// The total runtime is taken from the last progress status (falling back to the time
+    /// recorded in the results if there are no statuses).
+    pub fn new(results: &InferenceResults, sketch: &Sketch) -> InferenceSummaryReport {
+        let properties = results
+            .candidate_refinements()
+            .into_iter()
+            .map(|refinement| PropertySummary {
+                prop_id: refinement.prop_id.clone(),
+                num_candidates_before: refinement.num_candidates_before.clone(),
+                num_candidates_after: refinement.num_candidates_after.clone(),
+                num_eliminated: refinement.num_eliminated().map(|num| num.to_string()),
+            })
+            .collect();
+        let comp_time = results
+            .progress_statuses
+            .last()
+            .map_or(results.comp_time, |report| report.comp_time);
+
+        InferenceSummaryReport {
+            analysis_type: results.analysis_type.clone(),
+            num_sat_networks: results.num_sat_networks,
+            num_free_params: sketch.count_free_parameters(),
+            comp_time,
+            properties,
+            timed_out_properties: results.timed_out_properties(),
+        }
+    }
+
+    /// Render the summary as a Markdown document.
+    pub fn to_markdown(&self) -> String {
+        let mut output = String::new();
+        output.push_str("# Inference summary\n\n");
+        output.push_str(&format!("- Analysis type: {:?}\n", self.analysis_type));
+        output.push_str(&format!(
+            "- Number of satisfying candidates: {}\n",
+            self.num_sat_networks
+        ));
+        output.push_str(&format!(
+            "- Number of free parameters of the sketch: {}\n",
+            self.num_free_params
+        ));
+        output.push_str(&format!(
+            "- Total computation time: {} milliseconds\n",
+            self.comp_time
+        ));
+
+        output.push_str("\n## Evaluated properties\n\n");
+        if self.properties.is_empty() {
+            output.push_str("No properties were evaluated.\n");
+        } else {
+            output.push_str("| Property | Candidates before | Candidates after | Eliminated |\n");
+            output.push_str("| --- | --- | --- | --- |\n");
+            for prop in &self.properties {
+                output.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    prop.prop_id,
+                    prop.num_candidates_before,
+                    prop.num_candidates_after,
+                    prop.num_eliminated.as_deref().unwrap_or("?")
+                ));
+            }
+        }
+
+        if !self.timed_out_properties.is_empty() {
+            output.push_str(&format!(
+                "\nProperties skipped due to timeout: {}\n",
+                self.timed_out_properties.join(", ")
+            ));
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::inference::inference_results::InferenceResults;
+    use crate::inference::inference_status::{
+        CandidateRefinement, InferenceStatus, InferenceStatusReport,
+    };
+    use crate::inference::inference_type::InferenceType;
+    use crate::inference::summary_report::InferenceSummaryReport;
+    use crate::sketchbook::{JsonSerde, Sketch};
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    #[test]
+    /// Test creating the inference summary and rendering it as Markdown and JSON.
+    fn test_summary_report() {
+        let sketch = Sketch::from_aeon("A -> B\nB -| A").unwrap();
+        let results = InferenceResults::new(
+            InferenceType::FullInference,
+            3,
+            Duration::from_millis(10),
+            "",
+            vec![
+                InferenceStatusReport::new(
+                    InferenceStatus::EvaluatedStatic("p1".to_string()),
+                    Some("3".to_string()),
+                    8,
+                    "Finished p1",
+                )
+                .with_refinement(Some(CandidateRefinement::new("p1", "8", "3"))),
+                InferenceStatusReport::new(
+                    InferenceStatus::PropertyTimeout("p2".to_string()),
+                    Some("3".to_string()),
+                    9,
+                    "Skipped p2",
+                ),
+                InferenceStatusReport::new(InferenceStatus::FinishedSuccessfully, None, 12, ""),
+            ],
+            HashMap::new(),
+        );
+
+        let summary = InferenceSummaryReport::new(&results, &sketch);
+        assert_eq!(summary.num_sat_networks, 3);
+        assert_eq!(summary.num_free_params, sketch.count_free_parameters());
+        assert_eq!(summary.comp_time, 12);
+        assert_eq!(summary.properties.len(), 1);
+        assert_eq!(summary.properties[0].num_eliminated, Some("5".to_string()));
+        assert_eq!(summary.timed_out_properties, vec!["p2".to_string()]);
+
+        let markdown = summary.to_markdown();
+        assert!(markdown.contains("- Number of satisfying candidates: 3\n"));
+        assert!(markdown.contains("- Total computation time: 12 milliseconds\n"));
+        assert!(markdown.contains("| p1 | 8 | 3 | 5 |\n"));
+        assert!(markdown.contains("Properties skipped due to timeout: p2\n"));
+
+        let summary_json = summary.to_json_str();
+        assert_eq!(
+            InferenceSummaryReport::from_json_str(&summary_json).unwrap(),
+            summary
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use crate::sketchbook::ids::{DatasetId, DynPropertyId, StatPropertyId};
+use crate::sketchbook::model::ModelState;
+use crate::sketchbook::observations::Dataset;
+use crate::sketchbook::properties::{DynProperty, StatProperty};
+use crate::sketchbook::Sketch;
+use std::collections::{HashMap, HashSet};
+
+/// Snapshot of all sketch components at the time of the last successful consistency check.
+///
+/// Comparing the current sketch with the snapshot gives us the components that changed since
+/// then (and need to be validated again). See [Sketch::assert_consistency_incremental].
+#[derive(Clone, Debug)]
+pub(super) struct ConsistencyCache {
+    model: ModelState,
+    datasets: HashMap<DatasetId, Dataset>,
+    dyn_props: HashMap<DynPropertyId, DynProperty>,
+    stat_props: HashMap<StatPropertyId, StatProperty>,
+}
+
+impl ConsistencyCache {
+    /// Make a snapshot of all components of the given sketch.
+    pub(super) fn from_sketch(sketch: &Sketch) -> ConsistencyCache {
+        ConsistencyCache {
+            model: sketch.model.clone(),
+            datasets: sketch
+                .observations
+                .datasets()
+                .map(|(id, dataset)| (id.clone(), dataset.clone()))
+                .collect(),
+            dyn_props: sketch
+                .properties
+                .dyn_props()
+                .map(|(id, prop)| (id.clone(), prop.clone()))
+                .collect(),
+            stat_props: sketch
+                .properties
+                .stat_props()
+                .map(|(id, prop)| (id.clone(), prop.clone()))
+                .collect(),
+        }
+    }
+
+    /// Check whether the model is the same as in the snapshot.
+    pub(super) fn is_model_unchanged(&self, model: &ModelState) -> bool {
+        &self.model == model
+    }
+
+    /// Collect IDs of all datasets that were added, removed, or modified since the snapshot.
+    pub(super) fn changed_datasets(&self, sketch: &Sketch) -> HashSet<DatasetId> {
+        let mut changed: HashSet<DatasetId> = sketch
+            .observations
+            .datasets()
+            .filter(|(id, dataset)| self.datasets.get(*id) != Some(*dataset))
+            .map(|(id, _)| id.clone())
+            .collect();
+        let removed = self
+            .datasets
+            .keys()
+            .filter(|id| !sketch.observations.is_valid_dataset_id(id));
+        changed.extend(removed.cloned());
+        changed
+    }
+
+    /// Check whether the dynamic property is the same as in the snapshot.
+    pub(super) fn is_dyn_prop_unchanged(&self, id: &DynPropertyId, prop: &DynProperty) -> bool {
+        self.dyn_props.get(id) == Some(prop)
+    }
+
+    /// Check whether the static property is the same as in the snapshot.
+    pub(super) fn is_stat_prop_unchanged(&self, id: &StatPropertyId, prop: &StatProperty) -> bool {
+        self.stat_props.get(id) == Some(prop)
+    }
+}
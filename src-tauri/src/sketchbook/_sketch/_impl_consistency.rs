@@ -1,14 +1,51 @@
-use crate::sketchbook::ids::{DatasetId, ObservationId, UninterpretedFnId, VarId};
+use crate::algorithms::eval_dynamic::encode::dyn_prop_encoding_metrics;
+use crate::algorithms::eval_dynamic::processed_props::process_dyn_prop_single;
+use crate::algorithms::eval_static::encode::{encode_observation_macro, fol_encoding_metrics};
+use crate::algorithms::eval_static::processed_props::parse_and_expand_fol_macros;
+use crate::sketchbook::data_structs::EncodingMetrics;
+use crate::sketchbook::ids::{DatasetId, DynPropertyId, ObservationId, UninterpretedFnId, VarId};
+use crate::sketchbook::model::Monotonicity;
+use crate::sketchbook::observations::DatasetOrdering;
 use crate::sketchbook::properties::dynamic_props::{
-    DynPropertyType, WildCardProposition, WildCardType,
+    DynPropertyType, WildCardProposition, WildCardType, MAX_HCTL_STATE_VARS,
 };
 use crate::sketchbook::properties::static_props::StatPropertyType;
 use crate::sketchbook::properties::{DynProperty, FirstOrderFormula, HctlFormula, StatProperty};
-use crate::sketchbook::Sketch;
+use crate::sketchbook::{IssueSeverity, Sketch};
+use biodivine_lib_bdd::BddVariableSet;
 use std::collections::HashSet;
 
+use super::ConsistencyCache;
+
+/// Number of operators of an encoded property formula, above which we report a warning that
+/// the property may be slow to evaluate.
+const LARGE_ENCODING_NUM_OPERATORS: usize = 1000;
+
+/// List of `(regulator, target)` variable pairs.
+type RegulationPairs = Vec<(VarId, VarId)>;
+
+/// List of `(regulator, target, declared sign, actual sign)` tuples. The actual sign is `None`
+/// if the update function does not depend on the regulator at all.
+type SignConflicts = Vec<(VarId, VarId, Monotonicity, Option<Monotonicity>)>;
+
+/// List of `(variable, undeclared regulators)` pairs.
+type UndeclaredRegulators = Vec<(VarId, Vec<VarId>)>;
+
 /// Utilities to perform consistency checks.
 impl Sketch {
+    /// Severity with which issues regarding declared regulators of variables are reported
+    /// (see [Self::find_undeclared_regulators] and [Self::find_constant_fns_with_regulators]).
+    pub fn get_regulator_check_severity(&self) -> IssueSeverity {
+        self.regulator_check_severity
+    }
+
+    /// Set severity with which issues regarding declared regulators of variables are reported.
+    /// Since the result of the consistency check may change, the incremental check starts anew.
+    pub fn set_regulator_check_severity(&mut self, severity: IssueSeverity) {
+        self.regulator_check_severity = severity;
+        self.consistency_cache = None;
+    }
+
     /// Assert that the sketch is consistent, return error otherwise.
     /// See [Self::run_consistency_check] for details on which criteria are checked.
     pub fn assert_consistency(&self) -> Result<(), String> {
@@ -19,6 +56,71 @@ impl Sketch {
         }
     }
 
+    /// Incremental version of [Self::assert_consistency]. Only the components that changed since
+    /// the last successful check (and the components depending on them) are validated again.
+    ///
+    /// If the model changed (or there was no successful check yet), all components depend on it,
+    /// and the full check is performed. Otherwise, we validate only properties that changed or that
+    /// reference a dataset that changed (static properties via observation macros). We also check
+    /// that variables of the changed datasets, and of all datasets referenced by the changed
+    /// properties, match the model (see [Self::assert_datasets_match_model]).
+    pub fn assert_consistency_incremental(&mut self) -> Result<(), String> {
+        let cache = match &self.consistency_cache {
+            Some(cache) if cache.is_model_unchanged(&self.model) => cache,
+            _ => {
+                self.assert_consistency()?;
+                self.consistency_cache = Some(ConsistencyCache::from_sketch(self));
+                return Ok(());
+            }
+        };
+
+        let changed_datasets = cache.changed_datasets(self);
+        // variables of changed datasets, and of datasets referenced by changed properties (which
+        // may have been unused so far), must match the model
+        let mut datasets_to_check = changed_datasets.clone();
+        for (prop_id, prop) in self.properties.dyn_props() {
+            let prop_changed = !cache.is_dyn_prop_unchanged(prop_id, prop);
+            let dataset_changed = changed_datasets
+                .iter()
+                .any(|dataset_id| prop.references_dataset(dataset_id));
+            if prop_changed || dataset_changed {
+                self.assert_dynamic_prop_valid(prop_id, prop)
+                    .map_err(|e| format!("Sketch is not consistent: {e}"))?;
+            }
+            if prop_changed {
+                let wild_card_datasets = prop
+                    .get_wild_cards()
+                    .into_iter()
+                    .filter_map(|wild_card| wild_card.referenced_data().map(|(d, _)| d.clone()));
+                datasets_to_check.extend(prop.get_all_datasets());
+                datasets_to_check.extend(wild_card_datasets);
+            }
+        }
+        for (prop_id, prop) in self.properties.stat_props() {
+            let prop_changed = !cache.is_stat_prop_unchanged(prop_id, prop);
+            let prop_datasets = prop.get_all_datasets();
+            let dataset_changed = prop_datasets
+                .iter()
+                .any(|dataset_id| changed_datasets.contains(dataset_id));
+            if prop_changed || dataset_changed {
+                self.assert_static_prop_valid(prop)
+                    .map_err(|e| format!("Sketch is not consistent: {e}"))?;
+            }
+            if prop_changed {
+                datasets_to_check.extend(prop_datasets);
+            }
+        }
+        let mut datasets_to_check: Vec<DatasetId> = datasets_to_check.into_iter().collect();
+        datasets_to_check.sort();
+        for dataset_id in datasets_to_check.iter() {
+            self.assert_dataset_matches_model(dataset_id)
+                .map_err(|e| format!("Sketch is not consistent: {e}"))?;
+        }
+
+        self.consistency_cache = Some(ConsistencyCache::from_sketch(self));
+        Ok(())
+    }
+
     /// General check that all components of the sketch are consistent together.
     /// Returns a flag whether the sketch is consistent, a main message summarizing potential
     /// major issues (or stating there are none), and a message with warnings. Warnings
@@ -31,11 +133,24 @@ impl Sketch {
     ///
     /// This should include:
     /// - check that model is not empty, and there are no problems in function expressions
+    /// - check that the initial subspace references valid network variables
     /// - check that dataset variables are valid network variables and vice versa
     /// - check that various template properties reference valid variables and data
     /// - check that HCTL formulas only use valid variables as atomic propositions
     /// - check that FOL formulas only use valid function symbols
     pub fn run_consistency_check(&self) -> (bool, String, String) {
+        self.run_consistency_check_with(false)
+    }
+
+    /// Variant of [Self::run_consistency_check] with optional additional checks.
+    ///
+    /// If `check_regulation_usage` is set, we also report (as warnings) declared regulations that
+    /// are not used in fully specified update functions. See [Self::find_unused_regulations] for
+    /// details. This is opt-in, since sketches may be loose intentionally.
+    pub fn run_consistency_check_with(
+        &self,
+        check_regulation_usage: bool,
+    ) -> (bool, String, String) {
         let mut all_consitent = true;
         let mut main_message = String::new();
         // A message with less important issues, will be sent as warnings.
@@ -44,7 +159,7 @@ impl Sketch {
 
         // we divide the code by different components to avoid replication
         let componets_results = vec![
-            self.check_model(),
+            self.check_model(check_regulation_usage),
             self.check_datasets(),
             self.check_static(),
             self.check_dynamic(),
@@ -69,9 +184,17 @@ impl Sketch {
     /// and a separate message with warnings.
     ///
     /// We currently ensure that the network is not empty, there are no redundant (unused)
-    /// function symbols, and that expressions of uninterpreted functions are not defined
-    /// recursively.
-    fn check_model(&self) -> (bool, String, String) {
+    /// function symbols, that expressions of uninterpreted functions are not defined
+    /// recursively, that frozen update functions are fully specified, that fully specified
+    /// update functions do not contradict signs of their regulations, and that the initial
+    /// subspace only references valid variables. Input variables with incoming regulations
+    /// (other than self-regulations) are reported as warnings, as these regulations are
+    /// ignored during inference. Update functions referencing variables that
+    /// are not their declared regulators, and constant update functions of variables with
+    /// declared regulators are reported as warnings or errors, depending on the configured
+    /// severity (see [Self::set_regulator_check_severity]). If `check_regulation_usage` is set,
+    /// declared regulations unused in update functions are reported as warnings too.
+    fn check_model(&self, check_regulation_usage: bool) -> (bool, String, String) {
         let mut consitent = true;
         let mut message = String::new();
         message += "MODEL:\n";
@@ -104,42 +227,260 @@ impl Sketch {
             }
         }
 
+        // Check that all frozen update functions are fully specified (without any uninterpreted
+        // function symbols). Errors are again only caused by recursive definitions (already reported).
+        if let Ok(non_concrete_vars) = self.model.find_non_concrete_frozen_update_fns() {
+            for var_id in non_concrete_vars {
+                consitent = false;
+                let issue = format!(
+                    "> ISSUE: Update function of `{var_id}` is frozen, but it is not fully specified (it contains uninterpreted function symbols).\n"
+                );
+                message += &issue;
+            }
+        }
+
         // TODO: Maybe allow the redundant unused functions? We already check if these symbols are not
         //       used in static properties, and we prune the rest later, so it should be fine.
 
-        // TODO: We can consider adding a check whether update fn expressions match regulation
-        //       properties (essentially a partial check for some static properties)
+        // Check that fully specified update functions do not contradict declared monotonicity of
+        // their regulations (such a sketch would have no satisfying candidates).
+        for (regulator, target, declared, actual) in self.find_regulation_sign_conflicts() {
+            consitent = false;
+            let actual_str = match actual {
+                Some(sign) => format!("is actually `{}` in it", sign.as_str_full()),
+                None => "does not depend on it".to_string(),
+            };
+            let issue = format!(
+                "> ISSUE: Regulation `{regulator} -> {target}` is declared as `{}`, but update function of `{target}` {actual_str}.\n",
+                declared.as_str_full()
+            );
+            message += &issue;
+        }
+
+        // Check that the initial subspace only references valid variables (variables may have been
+        // removed from the model after the subspace was set)
+        for var_id in self.initial_subspace.keys() {
+            if !self.model.is_valid_var_id(var_id) {
+                consitent = false;
+                let issue = format!(
+                    "> ISSUE: Initial subspace references variable `{var_id}` that is not in the model.\n"
+                );
+                message += &issue;
+            }
+        }
+
+        // Inputs are free constants, so their incoming regulations are ignored during inference
+        let mut warnings = String::new();
+        for (var_id, variable) in self.model.variables() {
+            if !variable.is_input() {
+                continue;
+            }
+            let has_regulators = self
+                .model
+                .regulators(var_id)
+                .is_ok_and(|regulators| regulators.iter().any(|r| *r != var_id));
+            if has_regulators {
+                let warning = format!(
+                    "> ISSUE: Variable `{var_id}` is an input, but it has incoming regulations (these are ignored).\n"
+                );
+                warnings += &warning;
+            }
+        }
+
+        // Update functions should only reference declared regulators, and variables with declared
+        // regulators should not have constant update functions
+        let mut regulator_issues = Vec::new();
+        for (var_id, undeclared) in self.find_undeclared_regulators() {
+            let undeclared_str: Vec<String> = undeclared.iter().map(|v| format!("`{v}`")).collect();
+            regulator_issues.push(format!(
+                "> ISSUE: Update function of `{var_id}` references variables that are not its \
+                declared regulators: {}.\n",
+                undeclared_str.join(", ")
+            ));
+        }
+        for var_id in self.find_constant_fns_with_regulators() {
+            regulator_issues.push(format!(
+                "> ISSUE: Variable `{var_id}` has declared regulators, but its update function \
+                is constant.\n"
+            ));
+        }
+        for issue in regulator_issues {
+            match self.regulator_check_severity {
+                IssueSeverity::Warning => warnings += &issue,
+                IssueSeverity::Error => {
+                    consitent = false;
+                    message += &issue;
+                }
+            }
+        }
+
+        // Optionally, check that declared regulations are used in fully specified update fns
+        // (variables used without a regulation are already reported above)
+        if check_regulation_usage {
+            for (regulator, target) in self.find_unused_regulations() {
+                let warning = format!(
+                    "> ISSUE: Regulation `{regulator} -> {target}` is declared, but update \
+                    function of `{target}` does not depend on `{regulator}`.\n"
+                );
+                warnings += &warning;
+            }
+        }
+        if !warnings.is_empty() {
+            warnings = format!("MODEL:\n{warnings}");
+        }
+        (consitent, message, warnings)
+    }
+
+    /// Find variables whose update functions reference variables that are not their declared
+    /// regulators. Update functions of input variables are ignored.
+    ///
+    /// Returns a list of `(variable, undeclared regulators)` pairs, sorted by variable IDs (the
+    /// lists of undeclared regulators are sorted too).
+    pub fn find_undeclared_regulators(&self) -> UndeclaredRegulators {
+        let mut result = Vec::new();
+        for (var_id, update_fn) in self.model.update_fns() {
+            // var_id is a valid variable, we can unwrap
+            if self.model.get_variable(var_id).unwrap().is_input() {
+                continue;
+            }
+            let regulators = self.model.regulators(var_id).unwrap();
+            let mut undeclared: Vec<VarId> = update_fn
+                .collect_variables()
+                .into_iter()
+                .filter(|v| !regulators.contains(&v))
+                .collect();
+            if !undeclared.is_empty() {
+                undeclared.sort();
+                result.push((var_id.clone(), undeclared));
+            }
+        }
+        result.sort();
+        result
+    }
+
+    /// Find variables that have declared regulators, but their update function is constant.
+    /// Input variables are ignored. Returns a sorted list of variable IDs.
+    pub fn find_constant_fns_with_regulators(&self) -> Vec<VarId> {
+        let mut result: Vec<VarId> = self
+            .model
+            .update_fns()
+            .filter(|(var_id, update_fn)| {
+                // var_id is a valid variable, we can unwrap
+                !self.model.get_variable(var_id).unwrap().is_input()
+                    && update_fn.as_constant().is_some()
+                    && !self.model.regulators(var_id).unwrap().is_empty()
+            })
+            .map(|(var_id, _)| var_id.clone())
+            .collect();
+        result.sort();
+        result
+    }
+
+    /// Find declared regulations that are not used in the update function of their target. Only
+    /// variables with fully specified update functions (non-empty expression with no
+    /// uninterpreted function symbols) are considered.
+    ///
+    /// Returns a sorted list of `(regulator, target)` pairs. Variables used in update functions
+    /// without a corresponding regulation are reported by [Self::find_undeclared_regulators].
+    pub fn find_unused_regulations(&self) -> RegulationPairs {
+        let mut unused_regs = Vec::new();
+        for (target, update_fn) in self.model.update_fns() {
+            if update_fn.has_empty_expression() || !update_fn.collect_fn_symbols().is_empty() {
+                continue;
+            }
+            let used_vars = update_fn.collect_variables();
+            // target is a valid variable, we can unwrap
+            for regulator in self.model.regulators(target).unwrap() {
+                if !used_vars.contains(regulator) {
+                    unused_regs.push((regulator.clone(), target.clone()));
+                }
+            }
+        }
+        unused_regs.sort();
+        unused_regs
+    }
+
+    /// Compare declared monotonicity of regulations with the actual monotonicity of the fully
+    /// specified update functions (non-empty expression with no uninterpreted function symbols)
+    /// of their targets. Update functions of input variables are ignored.
+    ///
+    /// Returns a sorted list of contradicting regulations as `(regulator, target, declared sign,
+    /// actual sign)` tuples. The actual sign is `None` if the update function does not depend on
+    /// the regulator (this only contradicts `Dual` regulations; declared essentiality is not
+    /// considered here).
+    pub fn find_regulation_sign_conflicts(&self) -> SignConflicts {
+        let var_names: Vec<&str> = self.model.variables().map(|(v, _)| v.as_str()).collect();
+        let bdd_vars = BddVariableSet::new(&var_names);
+
+        let mut conflicts = Vec::new();
+        for regulation in self.model.regulations() {
+            let (regulator, target) = (regulation.get_regulator(), regulation.get_target());
+            let declared = *regulation.get_sign();
+            let is_input = self.model.get_variable(target).is_ok_and(|v| v.is_input());
+            if declared == Monotonicity::Unknown || is_input {
+                continue;
+            }
+            // target is a valid variable, we can unwrap
+            let update_fn = self.model.get_update_fn(target).unwrap();
+            let Some(fn_bdd) = update_fn
+                .get_fn_tree()
+                .as_ref()
+                .and_then(|t| t.to_bdd(&bdd_vars))
+            else {
+                continue;
+            };
 
-        // At the moment, there are no warnings here
-        (consitent, message, String::new())
+            // compare the function restricted to the regulator being `false` and `true`
+            let bdd_var = bdd_vars.var_by_name(regulator.as_str()).unwrap();
+            let fn_low = fn_bdd.var_restrict(bdd_var, false);
+            let fn_high = fn_bdd.var_restrict(bdd_var, true);
+            let increasing = fn_low.and_not(&fn_high).is_false();
+            let decreasing = fn_high.and_not(&fn_low).is_false();
+            let actual = match (increasing, decreasing) {
+                (true, true) => None,
+                (true, false) => Some(Monotonicity::Activation),
+                (false, true) => Some(Monotonicity::Inhibition),
+                (false, false) => Some(Monotonicity::Dual),
+            };
+            let is_conflict = match declared {
+                Monotonicity::Activation => !increasing,
+                Monotonicity::Inhibition => !decreasing,
+                Monotonicity::Dual => increasing || decreasing,
+                Monotonicity::Unknown => false,
+            };
+            if is_conflict {
+                conflicts.push((regulator.clone(), target.clone(), declared, actual));
+            }
+        }
+        conflicts.sort_by(|(r1, t1, ..), (r2, t2, ..)| (r1, t1).cmp(&(r2, t2)));
+        conflicts
     }
 
     /// Part of the consistency check responsible for the 'observations' (datasets) component.
     /// Returns bool (whether datasets are consistent), a formated message with error issues,
     /// and a separate message with warnings.
     ///
-    /// Currently only warnings are sent to the user, there are no hard error situations.
     /// We check that variables in datasets and in the network exactly match, and that all
-    /// datasets are used within some dynamic property.
+    /// datasets are used within some property.
     ///
-    /// The issues are only reported as warnings since they are handled automatically before
-    /// inference.
+    /// Datasets used by some property must not contain variables missing in the network (see
+    /// [Self::assert_datasets_match_model]), this is an error. The remaining issues are only
+    /// reported as warnings since they are handled automatically before inference.
     fn check_datasets(&self) -> (bool, String, String) {
+        let mut consistent = true;
         let mut message = String::new();
         message += "DATASETS:\n";
-
-        // We only report issues using warnings here
         let mut warnings = String::new();
 
         for (dataset_id, dataset) in self.observations.datasets() {
-            // 1) Check that all dataset variables are part of the network
-            let mut invalid_variables = Vec::new();
-            for var_id in dataset.variables() {
-                if !self.model.is_valid_var_id(var_id) {
-                    invalid_variables.push(var_id.to_string());
-                }
-            }
-            if !invalid_variables.is_empty() {
+            // 1) Check that all variables of datasets used by some property are part of the
+            //    network, other datasets are only reported using warnings
+            if let Err(e) = self.assert_dataset_matches_model(dataset_id) {
+                consistent = false;
+                let issue = format!("> ISSUE: {e}\n");
+                message += &issue;
+            } else if let Some(invalid_variables) = self.find_invalid_dataset_variables(dataset_id)
+            {
                 let invalid_vars_str = invalid_variables.join(", ");
                 let warning_inner =
                     format!("Following dataset variables are not part of the network and will be ignored for the inference: {invalid_vars_str}");
@@ -162,52 +503,126 @@ impl Sketch {
                 warnings += &warning;
             }
 
-            // Check if the dataset is used within some dynamic prop,
+            // Check if the dataset is used within some property,
             // If not, create a warning
             if !self.is_dataset_used(dataset_id) {
                 let warning =
-                    format!("> ISSUE with dataset `{dataset_id}`: This dataset is not linked to any dynamic property.\n");
+                    format!("> ISSUE with dataset `{dataset_id}`: This dataset is not linked to any property.\n");
                 warnings += &warning;
             }
         }
 
-        (true, message, warnings)
+        (consistent, message, warnings)
+    }
+
+    /// Assert that every dataset referenced by some property only contains variables
+    /// that are part of the model. Datasets that are not referenced by any property are skipped,
+    /// since they are not used during inference. If not, return error listing the datasets and
+    /// their invalid variables.
+    pub fn assert_datasets_match_model(&self) -> Result<(), String> {
+        let mut dataset_ids: Vec<&DatasetId> =
+            self.observations.datasets().map(|(id, _)| id).collect();
+        dataset_ids.sort();
+        let errors: Vec<String> = dataset_ids
+            .into_iter()
+            .filter_map(|id| self.assert_dataset_matches_model(id).err())
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join(" "))
+        }
+    }
+
+    /// **(internal)** Assert that dataset (if referenced by some property) only contains
+    /// variables that are part of the model. See [Self::assert_datasets_match_model].
+    fn assert_dataset_matches_model(&self, dataset_id: &DatasetId) -> Result<(), String> {
+        if !self.is_dataset_used(dataset_id) {
+            return Ok(());
+        }
+        match self.find_invalid_dataset_variables(dataset_id) {
+            Some(invalid_variables) => Err(format!(
+                "Dataset `{dataset_id}` is used by some property, but following dataset variables are not part of the network: {}.",
+                invalid_variables.join(", ")
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// **(internal)** Collect variables of a dataset that are not part of the model (in the order
+    /// of the dataset). Returns `None` if there are no such variables (or the dataset does not
+    /// exist).
+    fn find_invalid_dataset_variables(&self, dataset_id: &DatasetId) -> Option<Vec<String>> {
+        let dataset = self.observations.get_dataset(dataset_id).ok()?;
+        let invalid_variables: Vec<String> = dataset
+            .variables()
+            .iter()
+            .filter(|var_id| !self.model.is_valid_var_id(var_id))
+            .map(|var_id| var_id.to_string())
+            .collect();
+        if invalid_variables.is_empty() {
+            None
+        } else {
+            Some(invalid_variables)
+        }
     }
 
     /// Part of the consistency check responsible for the 'static properties' component.
     /// Returns bool (whether a static properties are consistent), a formated message with error issues,
     /// and a separate message with warnings.
+    ///
+    /// Valid generic properties that expand into large formulas (see [EncodingMetrics]) are
+    /// reported as warnings.
     fn check_static(&self) -> (bool, String, String) {
         let mut message = String::new();
         message += "STATIC PROPERTIES:\n";
+        let mut warnings = String::new();
 
         let mut stat_err_found = false;
         for (prop_id, prop) in self.properties.stat_props() {
             if let Err(e) = self.assert_static_prop_valid(prop) {
                 message = append_property_issue(&e, prop_id.as_str(), message);
                 stat_err_found = true;
+            } else if let StatPropertyType::GenericStatProp(generic_prop) = prop.get_prop_data() {
+                // the property is valid, so the expansion should not fail
+                if let Ok(tree) = parse_and_expand_fol_macros(&generic_prop.raw_formula, self) {
+                    let metrics = fol_encoding_metrics(&tree);
+                    warnings = append_large_encoding_issue(&metrics, prop_id.as_str(), warnings);
+                }
             }
         }
-        // At the moment, there are no warnings here
-        (!stat_err_found, message, String::new())
+        if !warnings.is_empty() {
+            warnings = format!("STATIC PROPERTIES:\n{warnings}");
+        }
+        (!stat_err_found, message, warnings)
     }
 
     /// Part of the consistency check responsible for the 'dynamic properties' component.
     /// Returns bool (whether a dynamic properties are consistent), a formated message with error issues,
     /// and a separate message with warnings.
+    ///
+    /// Valid properties that are encoded into large formulas (see [EncodingMetrics]) are
+    /// reported as warnings.
     fn check_dynamic(&self) -> (bool, String, String) {
         let mut message = String::new();
         message += "DYNAMIC PROPERTIES:\n";
+        let mut warnings = String::new();
 
         let mut dyn_err_found = false;
         for (prop_id, prop) in self.properties.dyn_props() {
-            if let Err(e) = self.assert_dynamic_prop_valid(prop) {
+            if let Err(e) = self.assert_dynamic_prop_valid(prop_id, prop) {
                 message = append_property_issue(&e, prop_id.as_str(), message);
                 dyn_err_found = true;
+            } else if let Ok(metrics) = process_dyn_prop_single(prop_id, prop, self)
+                .and_then(|processed_prop| dyn_prop_encoding_metrics(&processed_prop))
+            {
+                warnings = append_large_encoding_issue(&metrics, prop_id.as_str(), warnings);
             }
         }
-        // At the moment, there are no warnings here
-        (!dyn_err_found, message, String::new())
+        if !warnings.is_empty() {
+            warnings = format!("DYNAMIC PROPERTIES:\n{warnings}");
+        }
+        (!dyn_err_found, message, warnings)
     }
 
     /// Check if all fields of the static property are filled and have valid values.
@@ -242,6 +657,14 @@ impl Sketch {
                 for (fn_id, _) in functions_referenced.iter() {
                     self.assert_fn_symbol_not_redundant(fn_id, &unused_functions)?;
                 }
+                // Observation macros must reference valid observations (over model variables)
+                let observations_referenced = generic_prop
+                    .processed_formula
+                    .tree()
+                    .collect_observation_macros();
+                for (dataset_id, obs_id) in observations_referenced.iter() {
+                    encode_observation_macro(dataset_id, obs_id, self)?;
+                }
             }
             StatPropertyType::FnInputEssential(p)
             | StatPropertyType::FnInputEssentialContext(p) => {
@@ -259,6 +682,18 @@ impl Sketch {
                 self.assert_context_valid_or_none(p.context.as_ref())?;
                 self.assert_fn_symbol_not_redundant(fn_id.as_str(), &unused_functions)?;
             }
+            StatPropertyType::FnInputsMutuallyExclusive(p) => {
+                let fn_id = p.target.as_ref().unwrap();
+                let (idx_1, idx_2) = (p.input_index_1.unwrap(), p.input_index_2.unwrap());
+                self.assert_fn_valid_in_model(fn_id)?;
+                self.assert_fn_index_valid(idx_1, fn_id)?;
+                self.assert_fn_index_valid(idx_2, fn_id)?;
+                if idx_1 == idx_2 {
+                    let msg = format!("Mutually exclusive inputs of `{fn_id}` must differ.");
+                    return Err(msg);
+                }
+                self.assert_fn_symbol_not_redundant(fn_id.as_str(), &unused_functions)?;
+            }
             StatPropertyType::RegulationEssential(p)
             | StatPropertyType::RegulationEssentialContext(p) => {
                 self.assert_var_valid_in_model(p.target.as_ref().unwrap())?;
@@ -277,7 +712,14 @@ impl Sketch {
 
     /// Check if all required fields of a dynamic property are filled and have valid
     /// values. If not, return appropriate message.
-    fn assert_dynamic_prop_valid(&self, prop: &DynProperty) -> Result<(), String> {
+    ///
+    /// For generic properties, this also checks that the formula does not require more HCTL
+    /// state variables than [MAX_HCTL_STATE_VARS].
+    fn assert_dynamic_prop_valid(
+        &self,
+        prop_id: &DynPropertyId,
+        prop: &DynProperty,
+    ) -> Result<(), String> {
         // First make sure all required fields are filled (this is currently just the
         // dataset ID for most kinds of properties). Other fields either have default
         // values (attractor count) or can remain empty (observation counts).
@@ -291,8 +733,15 @@ impl Sketch {
                     generic_prop.processed_formula.as_str(),
                     &self.model,
                 )?;
+                let num_state_vars = generic_prop.processed_formula.count_state_variables();
+                if num_state_vars > MAX_HCTL_STATE_VARS {
+                    return Err(format!(
+                        "Formula of property `{prop_id}` requires {num_state_vars} HCTL state variables (nested quantifiers), but at most {MAX_HCTL_STATE_VARS} are supported."
+                    ));
+                }
 
-                // We also need to check wild cards
+                // We also need to check wild cards (first that they reference existing data)
+                prop.assert_wild_cards_resolvable(&self.observations)?;
                 for wild_card_proposition in generic_prop.wild_cards.iter() {
                     self.assert_wild_card_prop_valid(wild_card_proposition)?;
                 }
@@ -305,13 +754,43 @@ impl Sketch {
                 self.assert_dataset_valid_and_nonempty(p.dataset.as_ref().unwrap())?;
                 self.assert_obs_valid_or_none(p.dataset.as_ref().unwrap(), p.observation.as_ref())?;
             }
-            DynPropertyType::ExistsTrajectory(p) => {
+            DynPropertyType::SteadyState(p) => {
                 self.assert_dataset_valid_and_nonempty(p.dataset.as_ref().unwrap())?;
+                self.assert_obs_valid_or_none(p.dataset.as_ref().unwrap(), p.observation.as_ref())?;
+            }
+            DynPropertyType::ExistsTrajectory(p) => {
+                let dataset_id = p.dataset.as_ref().unwrap();
+                self.assert_dataset_valid_and_nonempty(dataset_id)?;
+                self.assert_dataset_time_series(dataset_id, "trajectory")?;
+            }
+            DynPropertyType::ObservationsReachable(p) => {
+                let dataset_id = p.dataset.as_ref().unwrap();
+                self.assert_dataset_valid_and_nonempty(dataset_id)?;
+                let num_observations = self
+                    .observations
+                    .get_dataset(dataset_id)?
+                    .num_observations();
+                if p.min_count > num_observations {
+                    return Err(format!(
+                        "Number of reachable observations ({}) exceeds the size of dataset `{dataset_id}` ({num_observations}).",
+                        p.min_count
+                    ));
+                }
             }
             DynPropertyType::ExistsTrapSpace(p) => {
                 self.assert_dataset_valid_and_nonempty(p.dataset.as_ref().unwrap())?;
                 self.assert_obs_valid_or_none(p.dataset.as_ref().unwrap(), p.observation.as_ref())?;
             }
+            DynPropertyType::ReachableFromDataset(p) => {
+                let initial_dataset = p.initial_dataset.as_ref().unwrap();
+                let target_dataset = p.target_dataset.as_ref().unwrap();
+                self.assert_dataset_valid_and_nonempty(initial_dataset)?;
+                self.assert_dataset_valid_and_nonempty(target_dataset)?;
+            }
+            DynPropertyType::ObservationAbsent(p) => {
+                self.assert_dataset_valid_and_nonempty(p.dataset.as_ref().unwrap())?;
+                self.assert_obs_valid_or_none(p.dataset.as_ref().unwrap(), p.observation.as_ref())?;
+            }
             DynPropertyType::AttractorCount(_) => {} // no fields that can be invalid
         }
         Ok(())
@@ -327,6 +806,7 @@ impl Sketch {
             }
             WildCardType::Trajectory(data_id) => {
                 self.assert_dataset_valid_and_nonempty(data_id)?;
+                self.assert_dataset_time_series(data_id, "trajectory")?;
             }
             WildCardType::Attractors(data_id, obs_id) => {
                 self.assert_dataset_valid_and_nonempty(data_id)?;
@@ -425,6 +905,26 @@ impl Sketch {
         }
     }
 
+    /// Check whether dataset is a time series, as required by the given template. If not,
+    /// return error with a proper message.
+    ///
+    /// Currently, only trajectory templates require a particular ordering (a time series), the
+    /// remaining templates work with both kinds of datasets.
+    fn assert_dataset_time_series(
+        &self,
+        dataset_id: &DatasetId,
+        template: &str,
+    ) -> Result<(), String> {
+        let ordering = self.observations.get_dataset(dataset_id)?.get_ordering();
+        if ordering != DatasetOrdering::TimeSeries {
+            return Err(format!(
+                "Template `{template}` requires dataset `{dataset_id}` to be a time series, but \
+                it is {ordering}."
+            ));
+        }
+        Ok(())
+    }
+
     /// Check whether observation is valid in a dataset. If not, return error with a proper message.
     /// If observation is None, that is also fine.
     fn assert_obs_valid_or_none(
@@ -442,18 +942,11 @@ impl Sketch {
         Ok(())
     }
 
-    /// Check if the given dataset is used within any of the dyn properties.
-    /// We expect dataset ID is already checked as valid.
+    /// Check if the given dataset is used within any of the properties - dynamic properties
+    /// (either in their dataset fields, or in wild-card propositions), or observation macros of
+    /// static properties. We expect dataset ID is already checked as valid.
     fn is_dataset_used(&self, dataset_id: &DatasetId) -> bool {
-        for (_, dyn_prop) in self.properties.dyn_props() {
-            // If property has dataset subfield and it is filled, we check it for match
-            if let Ok(Some(prop_dataset)) = dyn_prop.get_dataset() {
-                if &prop_dataset == dataset_id {
-                    return true;
-                }
-            }
-        }
-        false
+        self.collect_referenced_datasets().contains(dataset_id)
     }
 }
 
@@ -464,12 +957,27 @@ fn append_property_issue(description: &str, prop_id: &str, mut log: String) -> S
     log
 }
 
+/// Utility to add a warning to the log if the property is encoded into a large formula
+/// (with more than [LARGE_ENCODING_NUM_OPERATORS] operators).
+fn append_large_encoding_issue(metrics: &EncodingMetrics, prop_id: &str, log: String) -> String {
+    if metrics.num_operators <= LARGE_ENCODING_NUM_OPERATORS {
+        return log;
+    }
+    let description = format!(
+        "Property is encoded into a large formula ({} operators, {} state variables, {} literals), its evaluation may be slow.",
+        metrics.num_operators, metrics.num_state_vars, metrics.num_literals
+    );
+    append_property_issue(&description, prop_id, log)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::sketchbook::ids::DatasetId;
-    use crate::sketchbook::observations::{Dataset, Observation};
+    use crate::sketchbook::ids::{DatasetId, DynPropertyId, UninterpretedFnId, VarId};
+    use crate::sketchbook::model::Monotonicity;
+    use crate::sketchbook::observations::{Dataset, DatasetOrdering, Observation};
     use crate::sketchbook::properties::{DynProperty, StatProperty};
-    use crate::sketchbook::Sketch;
+    use crate::sketchbook::{IssueSeverity, Sketch};
+    use std::collections::BTreeMap;
     use std::fs::File;
     use std::io::Read;
 
@@ -520,6 +1028,66 @@ mod tests {
         assert!(sketch.assert_consistency().is_err());
     }
 
+    #[test]
+    /// Test that consistency check reports issues if a frozen update function is not fully
+    /// specified.
+    fn consistency_frozen_update_fn() {
+        let mut sketch = Sketch::from_aeon("A -> A\nA -> B\n$A:f(A)\n$B:A").unwrap();
+        let (var_a, var_b) = (VarId::new("A").unwrap(), VarId::new("B").unwrap());
+        sketch.model.set_update_fn_frozen(&var_b, true).unwrap();
+        assert!(sketch.assert_consistency().is_ok());
+
+        sketch.model.set_update_fn_frozen(&var_a, true).unwrap();
+        let (consistent, message, _) = sketch.run_consistency_check();
+        assert!(!consistent);
+        assert!(message.contains("Update function of `A` is frozen"));
+
+        // once the function symbol has an expression, the update function becomes concrete
+        sketch
+            .model
+            .set_uninterpreted_fn_expression_by_str("f", "!var0")
+            .unwrap();
+        assert!(sketch.assert_consistency().is_ok());
+    }
+
+    #[test]
+    /// Test that consistency check warns about input variables with incoming regulations.
+    fn consistency_input_with_regulators() {
+        let mut sketch = Sketch::from_aeon("A -> A\nA -> B\n$A:A").unwrap();
+        let (var_a, var_b) = (VarId::new("A").unwrap(), VarId::new("B").unwrap());
+        // self-regulation of an input is fine
+        sketch.model.set_var_input(&var_a, true).unwrap();
+        let (consistent, _, warnings) = sketch.run_consistency_check();
+        assert!(consistent);
+        assert!(warnings.is_empty());
+
+        sketch.model.set_var_input(&var_b, true).unwrap();
+        let (consistent, _, warnings) = sketch.run_consistency_check();
+        assert!(consistent);
+        assert!(warnings.contains("Variable `B` is an input, but it has incoming regulations"));
+    }
+
+    #[test]
+    /// Test that consistency check reports initial subspace referencing removed variables.
+    fn consistency_initial_subspace() {
+        let mut sketch = Sketch::from_aeon("A -> A\nB -> B").unwrap();
+        let subspace = BTreeMap::from([("A".to_string(), true), ("B".to_string(), false)]);
+        sketch.set_initial_subspace_by_str(&subspace).unwrap();
+        assert!(sketch.assert_consistency().is_ok());
+
+        // variables that are not in the model can not be set directly
+        let invalid_subspace = BTreeMap::from([("C".to_string(), true)]);
+        assert!(sketch
+            .set_initial_subspace_by_str(&invalid_subspace)
+            .is_err());
+
+        // but they can become invalid after removing them from the model
+        sketch.model.remove_var(&VarId::new("B").unwrap()).unwrap();
+        let (consistent, message, _) = sketch.run_consistency_check();
+        assert!(!consistent);
+        assert!(message.contains("Initial subspace references variable `B`"));
+    }
+
     #[test]
     /// Test that consistency check reports issues if a HCTL/FOL property references variable not
     /// present in the model.
@@ -547,6 +1115,21 @@ mod tests {
             .add_static_by_str("p", stat_prop)
             .unwrap();
         assert!(sketch_copy.assert_consistency().is_err());
+
+        // fol applying function f with wrong arity (and non-existing function g), all the
+        // issues are reported together with the property ID
+        let fol_formula = "f(1, 1) & g(1)";
+        let stat_prop = StatProperty::try_mk_generic("", fol_formula).unwrap();
+        let mut sketch_copy = sketch.clone();
+        sketch_copy
+            .properties
+            .add_static_by_str("p", stat_prop)
+            .unwrap();
+        let (consistent, message, _) = sketch_copy.run_consistency_check();
+        assert!(!consistent);
+        assert!(message.contains("ISSUE with property `p`"));
+        assert!(message.contains("`f` is used with incorrect arity (2 instead of 1)"));
+        assert!(message.contains("Function `g` with arity 1 not found in the model."));
     }
 
     #[test]
@@ -570,6 +1153,27 @@ mod tests {
         assert!(sketch.assert_static_prop_valid(&static_prop).is_err());
     }
 
+    #[test]
+    /// Test that input indices of a mutually exclusive fn inputs property are validated against
+    /// the function's arity.
+    fn consistency_mutually_exclusive_inputs() {
+        let sketch = Sketch::from_aeon("A -> A\nB -> A\n$A:f(A, B)").unwrap();
+        let fn_id = UninterpretedFnId::new("f").unwrap();
+        let mk_prop = |idx_1, idx_2| {
+            let target = Some(fn_id.clone());
+            StatProperty::mk_fn_inputs_mutually_exclusive("p", Some(idx_1), Some(idx_2), target)
+        };
+
+        assert!(sketch.assert_static_prop_valid(&mk_prop(0, 1)).is_ok());
+        // index out of range of the function's arity
+        assert!(sketch.assert_static_prop_valid(&mk_prop(0, 2)).is_err());
+        // same input used twice
+        assert!(sketch.assert_static_prop_valid(&mk_prop(1, 1)).is_err());
+        // missing index
+        let prop = StatProperty::mk_fn_inputs_mutually_exclusive("p", Some(0), None, None);
+        assert!(sketch.assert_static_prop_valid(&prop).is_err());
+    }
+
     #[test]
     /// Test that property consistency check reports issues if an empty dataset is used
     /// within a property.
@@ -591,13 +1195,288 @@ mod tests {
             .add_dynamic_by_str("p", dyn_prop.clone())
             .unwrap();
 
-        assert!(sketch.assert_dynamic_prop_valid(&dyn_prop).is_err());
+        let prop_id = DynPropertyId::new("p").unwrap();
+        assert!(sketch
+            .assert_dynamic_prop_valid(&prop_id, &dyn_prop)
+            .is_err());
+        assert!(sketch.assert_consistency().is_err());
+    }
+
+    #[test]
+    /// Test that trajectory properties are only allowed over time-series datasets.
+    fn consistency_trajectory_with_unordered_dataset() {
+        let mut sketch = Sketch::from_aeon("A -> A\n").unwrap();
+        let obs = Observation::new_full_ones(1, "o").unwrap();
+        let dataset = Dataset::new("d", vec![obs], vec!["A"])
+            .unwrap()
+            .with_ordering(DatasetOrdering::Unordered);
+        let dataset_id = DatasetId::new("d").unwrap();
+        sketch
+            .observations
+            .add_dataset(dataset_id.clone(), dataset)
+            .unwrap();
+
+        let dyn_prop = DynProperty::mk_trajectory("p", Some(dataset_id.clone()));
+        let generic_prop = DynProperty::try_mk_generic("", "%trajectory(d)%").unwrap();
+        let prop_id = DynPropertyId::new("p").unwrap();
+        assert!(sketch
+            .assert_dynamic_prop_valid(&prop_id, &dyn_prop)
+            .is_err());
+        assert!(sketch
+            .assert_dynamic_prop_valid(&prop_id, &generic_prop)
+            .is_err());
+
+        // after switching to a time series, both properties are fine
+        sketch
+            .observations
+            .set_dataset_ordering(&dataset_id, DatasetOrdering::TimeSeries)
+            .unwrap();
+        assert!(sketch
+            .assert_dynamic_prop_valid(&prop_id, &dyn_prop)
+            .is_ok());
+        assert!(sketch
+            .assert_dynamic_prop_valid(&prop_id, &generic_prop)
+            .is_ok());
+    }
+
+    #[test]
+    /// Test that unresolved wild-card propositions are reported, naming the property and
+    /// the wild-card.
+    fn consistency_unresolved_wild_cards() {
+        let mut sketch = Sketch::from_aeon("A -> A\n").unwrap();
+        let mock_obs = Observation::new_full_ones(1, "o").unwrap();
+        let dataset = Dataset::new("d", vec![mock_obs], vec!["A"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+
+        let valid_prop = DynProperty::try_mk_generic("", "EF %d, o% & %trajectory(d)%").unwrap();
+        sketch
+            .properties
+            .add_dynamic_by_str("p1", valid_prop)
+            .unwrap();
+        assert!(sketch
+            .properties
+            .assert_wild_cards_resolvable(&sketch.observations)
+            .is_ok());
+        assert!(sketch.assert_consistency().is_ok());
+
+        let prop_bad_obs = DynProperty::try_mk_generic("", "EF %d, oo%").unwrap();
+        sketch
+            .properties
+            .add_dynamic_by_str("p2", prop_bad_obs)
+            .unwrap();
+        let prop_bad_dataset = DynProperty::try_mk_generic("", "AG %attractors(e)%").unwrap();
+        sketch
+            .properties
+            .add_dynamic_by_str("p3", prop_bad_dataset)
+            .unwrap();
+        let err = sketch
+            .properties
+            .assert_wild_cards_resolvable(&sketch.observations)
+            .unwrap_err();
+        let expected = "Property `p2`: Wild-card `%d, oo%` references unknown observation `oo` in dataset `d`.\n\
+            Property `p3`: Wild-card `%attractors(e)%` references unknown dataset `e`.";
+        assert_eq!(err, expected);
+        assert!(sketch.assert_consistency().is_err());
+    }
+
+    #[test]
+    /// Test that the incremental consistency check detects issues in changed components.
+    fn consistency_incremental() {
+        let mut sketch = Sketch::from_aeon("A -> A\n$A:f(A)").unwrap();
+        assert!(sketch.assert_consistency_incremental().is_ok());
+
+        // new property referencing non-existing variable
+        let dyn_prop = DynProperty::try_mk_generic("", "B").unwrap();
+        sketch.properties.add_dynamic_by_str("p", dyn_prop).unwrap();
+        assert!(sketch.assert_consistency_incremental().is_err());
+        let prop_id = sketch.properties.get_dyn_prop_id("p").unwrap();
+        sketch.properties.set_dyn_formula(&prop_id, "A").unwrap();
+        assert!(sketch.assert_consistency_incremental().is_ok());
+
+        // modified dataset makes a (previously checked) property referencing it invalid
+        let mock_obs = Observation::new_full_ones(1, "o").unwrap();
+        let dataset = Dataset::new("d", vec![mock_obs], vec!["A"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+        let dataset_id = DatasetId::new("d").unwrap();
+        let dyn_prop = DynProperty::mk_trajectory("t", Some(dataset_id.clone()));
+        sketch.properties.add_dynamic_by_str("t", dyn_prop).unwrap();
+        assert!(sketch.assert_consistency_incremental().is_ok());
+        sketch.observations.remove_dataset(&dataset_id).unwrap();
+        let dataset = Dataset::new_empty("d", vec!["A"]).unwrap();
+        sketch
+            .observations
+            .add_dataset(dataset_id, dataset)
+            .unwrap();
+        assert!(sketch.assert_consistency_incremental().is_err());
+
+        // the same for a static property referencing the modified dataset via observation macro
+        let mut sketch = Sketch::from_aeon("A -> A\n$A:f(A)").unwrap();
+        let mock_obs = Observation::new_full_ones(1, "o").unwrap();
+        let dataset = Dataset::new("d", vec![mock_obs], vec!["A"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+        let stat_prop = StatProperty::try_mk_generic("q", "@d:o").unwrap();
+        sketch.properties.add_static_by_str("q", stat_prop).unwrap();
+        assert!(sketch.assert_consistency_incremental().is_ok());
+        let dataset_id = DatasetId::new("d").unwrap();
+        sketch.observations.remove_dataset(&dataset_id).unwrap();
+        let dataset = Dataset::new_empty("d", vec!["A"]).unwrap();
+        sketch
+            .observations
+            .add_dataset(dataset_id, dataset)
+            .unwrap();
+        assert!(sketch.assert_consistency_incremental().is_err());
+
+        // model changes trigger the full check
+        let mut sketch = Sketch::from_aeon("A -> A\n$A:f(A)").unwrap();
+        assert!(sketch.assert_consistency_incremental().is_ok());
+        sketch
+            .model
+            .add_empty_uninterpreted_fn_by_str("g", "g", 2)
+            .unwrap();
+        assert!(sketch.assert_consistency_incremental().is_err());
+    }
+
+    #[test]
+    /// Test that incremental check agrees with the full one when a new property starts to
+    /// reference an (unchanged) dataset with variables that are not part of the model.
+    fn consistency_incremental_newly_referenced_dataset() {
+        let mut sketch = Sketch::from_aeon("A -> A\n$A:f(A)").unwrap();
+        let mock_obs = Observation::new_full_ones(2, "o").unwrap();
+        let dataset = Dataset::new("d", vec![mock_obs], vec!["A", "B"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+        // the dataset is not used yet, so it does not matter that `B` is not in the model
+        assert!(sketch.assert_consistency().is_ok());
+        assert!(sketch.assert_consistency_incremental().is_ok());
+
+        let dataset_id = DatasetId::new("d").unwrap();
+        let dyn_prop = DynProperty::mk_trajectory("t", Some(dataset_id));
+        sketch.properties.add_dynamic_by_str("t", dyn_prop).unwrap();
         assert!(sketch.assert_consistency().is_err());
+        assert!(sketch.assert_consistency_incremental().is_err());
     }
 
     #[test]
-    /// Test that consistency check succeeds but returns warnings if a dataset contains
-    /// variables not present in the model and the other way around.
+    /// Test that the optional regulation check reports regulations unused in update functions.
+    fn consistency_regulation_usage() {
+        let aeon_str = "A -> B\nB -> B\nC -> B\nC -> A\n$B: A & B\n$A: C";
+        let sketch = Sketch::from_aeon(aeon_str).unwrap();
+
+        let c_to_b = (VarId::new("C").unwrap(), VarId::new("B").unwrap());
+        assert_eq!(sketch.find_unused_regulations(), vec![c_to_b]);
+
+        // the check is only performed when requested, and it only produces warnings
+        let (consistent, _, warnings) = sketch.run_consistency_check();
+        assert!(consistent);
+        assert!(warnings.is_empty());
+        let (consistent, _, warnings) = sketch.run_consistency_check_with(true);
+        assert!(consistent);
+        assert!(warnings.contains("Regulation `C -> B` is declared"));
+    }
+
+    #[test]
+    /// Test that consistency check reports update functions referencing undeclared regulators
+    /// and constant update functions with declared regulators, with configurable severity.
+    fn consistency_declared_regulators() {
+        let mut sketch = Sketch::from_aeon("A -> B\nB -> C\nC -| C\n$C: !C").unwrap();
+        let var = |id: &str| VarId::new(id).unwrap();
+        sketch.model.set_update_fn(&var("A"), "B & C").unwrap();
+        sketch
+            .model
+            .set_update_fn_constant(&var("B"), true)
+            .unwrap();
+        let expected = vec![(var("A"), vec![var("B"), var("C")])];
+        assert_eq!(sketch.find_undeclared_regulators(), expected);
+        assert_eq!(sketch.find_constant_fns_with_regulators(), vec![var("B")]);
+
+        // by default, the issues are only reported as warnings
+        assert_eq!(
+            sketch.get_regulator_check_severity(),
+            IssueSeverity::Warning
+        );
+        let (consistent, _, warnings) = sketch.run_consistency_check();
+        assert!(consistent);
+        let expected_warning =
+            "Update function of `A` references variables that are not its declared regulators: \
+            `B`, `C`.";
+        assert!(warnings.contains(expected_warning));
+        assert!(warnings.contains(
+            "Variable `B` has declared regulators, but its update function is constant."
+        ));
+        assert!(sketch.assert_consistency_incremental().is_ok());
+
+        // with error severity, the sketch becomes inconsistent (even for the incremental check)
+        sketch.set_regulator_check_severity(IssueSeverity::Error);
+        let (consistent, message, warnings) = sketch.run_consistency_check();
+        assert!(!consistent);
+        assert!(message.contains("Update function of `A` references variables"));
+        assert!(!warnings.contains("Update function of `A` references variables"));
+        assert!(sketch.assert_consistency_incremental().is_err());
+
+        // inputs are ignored
+        sketch.model.set_var_input(&var("A"), true).unwrap();
+        sketch.model.set_var_input(&var("B"), true).unwrap();
+        assert!(sketch.find_undeclared_regulators().is_empty());
+        assert!(sketch.find_constant_fns_with_regulators().is_empty());
+    }
+
+    #[test]
+    /// Test that consistency check reports regulations with signs contradicting fully specified
+    /// update functions.
+    fn consistency_regulation_signs() {
+        let aeon_str = "A -> B\nC -| B\nA -> A\nC -> C\nB -> C\n$B: !A & !C\n$C: C | B";
+        let mut sketch = Sketch::from_aeon(aeon_str).unwrap();
+        let var_a = VarId::new("A").unwrap();
+        let var_b = VarId::new("B").unwrap();
+        let var_c = VarId::new("C").unwrap();
+        let expected = vec![(
+            var_a.clone(),
+            var_b.clone(),
+            Monotonicity::Activation,
+            Some(Monotonicity::Inhibition),
+        )];
+        assert_eq!(sketch.find_regulation_sign_conflicts(), expected);
+        let (consistent, message, _) = sketch.run_consistency_check();
+        assert!(!consistent);
+        assert!(message.contains("Regulation `A -> B` is declared as `Activation`, but update function of `B` is actually `Inhibition` in it"));
+
+        // fix the sign, but declare a monotonic regulation as dual
+        sketch
+            .model
+            .change_regulation_sign(&var_a, &var_b, &Monotonicity::Inhibition)
+            .unwrap();
+        sketch
+            .model
+            .change_regulation_sign(&var_b, &var_c, &Monotonicity::Dual)
+            .unwrap();
+        let expected = vec![(
+            var_b,
+            var_c.clone(),
+            Monotonicity::Dual,
+            Some(Monotonicity::Activation),
+        )];
+        assert_eq!(sketch.find_regulation_sign_conflicts(), expected);
+
+        // functions that are not fully specified are ignored
+        sketch.model.set_update_fn(&var_c, "").unwrap();
+        assert!(sketch.find_regulation_sign_conflicts().is_empty());
+        assert!(sketch.assert_consistency().is_ok());
+    }
+
+    #[test]
+    /// Test that consistency check fails if a used dataset contains variables not present in
+    /// the model, and that it returns warnings if the dataset misses some model variables.
     fn consistency_dataset() {
         // Build a simple sketch with two variables A, B
         let mut sketch = Sketch::from_aeon("A -> A\nB -> B").unwrap();
@@ -618,10 +1497,10 @@ mod tests {
             .observations
             .add_dataset_by_str("dataset", dataset)
             .unwrap();
-        let (consistent, _, warnings) = sketch_copy.run_consistency_check();
-        assert!(consistent);
-        assert!(warnings.contains("Following dataset variables are not part of the network"));
-        assert!(warnings.contains(": C"));
+        let (consistent, message, _) = sketch_copy.run_consistency_check();
+        assert!(!consistent);
+        assert!(message.contains("following dataset variables are not part of the network: C."));
+        assert!(sketch_copy.assert_datasets_match_model().is_err());
 
         // Dataset missing variable B
         let mock_obs = Observation::new_full_ones(1, "o").unwrap();
@@ -648,4 +1527,93 @@ mod tests {
         assert!(consistent);
         assert!(warnings.is_empty());
     }
+
+    #[test]
+    /// Test that datasets not used by any property are skipped when checking that dataset
+    /// variables match the model (only a warning is reported).
+    fn consistency_unused_dataset() {
+        let mut sketch = Sketch::from_aeon("A -> A\nB -> B").unwrap();
+        let mock_obs = Observation::new_full_ones(3, "o").unwrap();
+        let dataset = Dataset::new("d", vec![mock_obs], vec!["A", "B", "C"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("dataset", dataset)
+            .unwrap();
+
+        assert!(sketch.assert_datasets_match_model().is_ok());
+        let (consistent, _, warnings) = sketch.run_consistency_check();
+        assert!(consistent);
+        assert!(warnings.contains("Following dataset variables are not part of the network"));
+        assert!(warnings.contains(": C"));
+
+        // datasets referenced only by observation macros of static properties are used as well
+        let mut sketch_with_macro = sketch.clone();
+        let stat_prop = StatProperty::try_mk_generic("q", "@dataset:o").unwrap();
+        sketch_with_macro
+            .properties
+            .add_static_by_str("q", stat_prop)
+            .unwrap();
+        assert!(sketch_with_macro.assert_datasets_match_model().is_err());
+        let (_, _, warnings) = sketch_with_macro.run_consistency_check();
+        assert!(!warnings.contains("not linked to any property"));
+
+        // once the dataset is used, the extra variable is an error, naming the dataset
+        let dataset_id = DatasetId::new("dataset").unwrap();
+        let dyn_prop = DynProperty::mk_trajectory("p", Some(dataset_id));
+        sketch.properties.add_dynamic_by_str("p", dyn_prop).unwrap();
+        let err = sketch.assert_datasets_match_model().unwrap_err();
+        assert!(err.contains("Dataset `dataset`"));
+        assert!(err.ends_with(": C."));
+        assert!(sketch.assert_consistency().is_err());
+    }
+
+    #[test]
+    /// Test that formulas with too deeply nested quantifiers are reported, naming the property
+    /// and both the required and supported number of HCTL state variables.
+    fn consistency_hctl_var_limit() {
+        let mut sketch = Sketch::from_aeon("a -> a").unwrap();
+        let formula = "3{x}: 3{y}: 3{z}: 3{u}: 3{v}: 3{w}: !{s}: (@{x}: AX {s})";
+        let property = DynProperty::try_mk_generic("p", formula).unwrap();
+        sketch
+            .properties
+            .add_dynamic_by_str("deep_prop", property)
+            .unwrap();
+
+        assert!(sketch.assert_consistency().is_err());
+        let (is_consistent, message, _) = sketch.run_consistency_check();
+        assert!(!is_consistent);
+        assert!(message.contains("`deep_prop` requires 7 HCTL state variables"));
+        assert!(message.contains("at most 6 are supported"));
+    }
+
+    #[test]
+    /// Test that properties encoded into large formulas are reported as warnings.
+    fn consistency_large_encoding() {
+        let mut sketch = Sketch::from_aeon("a -> a").unwrap();
+        let small_prop = DynProperty::try_mk_generic("small", "3{x}: @{x}: AX a").unwrap();
+        sketch
+            .properties
+            .add_dynamic_by_str("small_prop", small_prop)
+            .unwrap();
+        let (is_consistent, _, warnings) = sketch.run_consistency_check();
+        assert!(is_consistent);
+        assert!(warnings.is_empty());
+
+        // balanced conjunction with 1023 operators (and 1024 literals)
+        let mut formula = "a".to_string();
+        for _ in 0..10 {
+            formula = format!("({formula}) & ({formula})");
+        }
+        let large_prop = DynProperty::try_mk_generic("large", &formula).unwrap();
+        sketch
+            .properties
+            .add_dynamic_by_str("large_prop", large_prop)
+            .unwrap();
+        let (is_consistent, _, warnings) = sketch.run_consistency_check();
+        assert!(is_consistent);
+        assert!(warnings.contains("DYNAMIC PROPERTIES:"));
+        assert!(warnings.contains("`large_prop`: Property is encoded into a large formula"));
+        assert!(warnings.contains("(1023 operators, 0 state variables, 1024 literals)"));
+        assert!(!warnings.contains("small_prop"));
+    }
 }
@@ -0,0 +1,186 @@
+use crate::sketchbook::data_structs::{ComponentDiff, ModifiedComponent, SketchData, SketchDiff};
+use crate::sketchbook::Sketch;
+use std::collections::BTreeMap;
+
+/// Comparing two versions of a sketch.
+impl Sketch {
+    /// Compute a structured report of differences between this sketch (the original version)
+    /// and the `other` sketch (the new version).
+    ///
+    /// The report covers variables, update functions, regulations, uninterpreted functions,
+    /// datasets, and properties, matched by their IDs. See [SketchDiff] for details. Layouts
+    /// and the sketch annotation are not compared.
+    pub fn diff(&self, other: &Sketch) -> SketchDiff {
+        let old = SketchData::new_from_sketch(self);
+        let new = SketchData::new_from_sketch(other);
+
+        // update functions of variables present in both sketches are compared separately
+        let old_fns: BTreeMap<String, String> = old
+            .model
+            .variables
+            .iter()
+            .map(|v| (v.id.clone(), v.update_fn.clone()))
+            .collect();
+        let update_fns = new
+            .model
+            .variables
+            .iter()
+            .filter_map(|v| {
+                let old_fn = old_fns.get(&v.id)?;
+                (*old_fn != v.update_fn).then(|| ModifiedComponent {
+                    id: v.id.clone(),
+                    old: old_fn.clone(),
+                    new: v.update_fn.clone(),
+                })
+            })
+            .collect();
+
+        SketchDiff {
+            variables: diff_components(
+                old.model.variables,
+                new.model.variables,
+                |v| v.id.clone(),
+                |v1, v2| {
+                    v1.name != v2.name
+                        || v1.annotation != v2.annotation
+                        || v1.is_input != v2.is_input
+                        || v1.frozen != v2.frozen
+                },
+            ),
+            update_fns,
+            regulations: diff_components(
+                old.model.regulations,
+                new.model.regulations,
+                |r| format!("({}, {})", r.regulator, r.target),
+                |r1, r2| r1 != r2,
+            ),
+            uninterpreted_fns: diff_components(
+                old.model.uninterpreted_fns,
+                new.model.uninterpreted_fns,
+                |f| f.id.clone(),
+                |f1, f2| f1 != f2,
+            ),
+            datasets: diff_components(
+                old.datasets,
+                new.datasets,
+                |d| d.id.clone(),
+                |d1, d2| d1 != d2,
+            ),
+            stat_properties: diff_components(
+                old.stat_properties,
+                new.stat_properties,
+                |p| p.id.clone(),
+                |p1, p2| p1 != p2,
+            ),
+            dyn_properties: diff_components(
+                old.dyn_properties,
+                new.dyn_properties,
+                |p| p.id.clone(),
+                |p1, p2| p1 != p2,
+            ),
+        }
+    }
+}
+
+/// **(internal)** Compute differences between two lists of components. Components are matched
+/// using IDs given by `get_id`, and the `is_modified` predicate decides whether a component
+/// present in both lists was modified. Results are sorted by the IDs.
+fn diff_components<T, I, M>(old: Vec<T>, new: Vec<T>, get_id: I, is_modified: M) -> ComponentDiff<T>
+where
+    I: Fn(&T) -> String,
+    M: Fn(&T, &T) -> bool,
+{
+    let mut old: BTreeMap<String, T> = old.into_iter().map(|c| (get_id(&c), c)).collect();
+    let mut diff = ComponentDiff {
+        added: Vec::new(),
+        removed: Vec::new(),
+        modified: Vec::new(),
+    };
+
+    let new: BTreeMap<String, T> = new.into_iter().map(|c| (get_id(&c), c)).collect();
+    for (id, new_component) in new {
+        match old.remove(&id) {
+            Some(old_component) if is_modified(&old_component, &new_component) => {
+                diff.modified.push(ModifiedComponent {
+                    id,
+                    old: old_component,
+                    new: new_component,
+                });
+            }
+            Some(_) => {}
+            None => diff.added.push(new_component),
+        }
+    }
+    // all remaining original components were removed
+    diff.removed = old.into_values().collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::VarId;
+    use crate::sketchbook::observations::Dataset;
+    use crate::sketchbook::properties::StatProperty;
+    use crate::sketchbook::Sketch;
+
+    #[test]
+    /// Test computing differences between two versions of a sketch.
+    fn test_sketch_diff() {
+        let sketch = Sketch::from_aeon("A -> B\nB -| A\n$A: !B\n$B: A").unwrap();
+        assert!(sketch.diff(&sketch.clone()).is_empty());
+
+        let mut other = Sketch::from_aeon("A -> B\nB -> A\nC -| A\n$A: B & !C\n$B: A").unwrap();
+        let var_a = VarId::new("A").unwrap();
+        other.model.set_var_name(&var_a, "name_a").unwrap();
+        let dataset = Dataset::new_empty("d", vec!["A", "B", "C"]).unwrap();
+        other.observations.add_dataset_by_str("d", dataset).unwrap();
+        let prop = StatProperty::try_mk_generic("p", "f_A(1, 0, 0)").unwrap();
+        other.properties.add_static_by_str("p", prop).unwrap();
+
+        let diff = sketch.diff(&other);
+        assert!(!diff.is_empty());
+
+        // variable `C` added, variable `A` renamed
+        assert_eq!(diff.variables.added.len(), 1);
+        assert_eq!(diff.variables.added[0].id, "C");
+        assert!(diff.variables.removed.is_empty());
+        assert_eq!(diff.variables.modified.len(), 1);
+        assert_eq!(diff.variables.modified[0].new.name, "name_a");
+
+        // update function of `A` changed
+        assert_eq!(diff.update_fns.len(), 1);
+        assert_eq!(diff.update_fns[0].old, "!B");
+        assert_eq!(diff.update_fns[0].new, "B & !C");
+
+        // regulation `C -| A` added, `B -| A` changed to `B -> A`
+        assert_eq!(diff.regulations.added.len(), 1);
+        assert_eq!(diff.regulations.modified.len(), 1);
+        assert_eq!(diff.regulations.modified[0].id, "(B, A)");
+
+        // dataset and property added (plus generated regulation properties changed)
+        assert_eq!(diff.datasets.added.len(), 1);
+        assert!(diff.stat_properties.added.iter().any(|p| p.id == "p"));
+
+        // the diff in reverse direction swaps added and removed components
+        let reverse_diff = other.diff(&sketch);
+        assert_eq!(reverse_diff.variables.removed, diff.variables.added);
+        assert_eq!(reverse_diff.datasets.removed, diff.datasets.added);
+
+        // changing the input or frozen flag modifies the variable (not its update function)
+        let var_b = VarId::new("B").unwrap();
+        let mut input_sketch = sketch.clone();
+        input_sketch.model.set_var_input(&var_b, true).unwrap();
+        let diff = sketch.diff(&input_sketch);
+        assert_eq!(diff.variables.modified.len(), 1);
+        assert!(diff.variables.modified[0].new.is_input);
+        let mut frozen_sketch = sketch.clone();
+        frozen_sketch
+            .model
+            .set_update_fn_frozen(&var_b, true)
+            .unwrap();
+        let diff = sketch.diff(&frozen_sketch);
+        assert_eq!(diff.variables.modified.len(), 1);
+        assert!(diff.variables.modified[0].new.frozen);
+        assert!(diff.update_fns.is_empty());
+    }
+}
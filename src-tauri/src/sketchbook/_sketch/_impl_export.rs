@@ -3,6 +3,7 @@ use biodivine_lib_param_bn::ModelAnnotation;
 use crate::sketchbook::data_structs::{
     DatasetData, DynPropertyData, SketchData, StatPropertyData, UninterpretedFnData, VariableData,
 };
+use crate::sketchbook::utils::gzip_compress;
 use crate::sketchbook::{JsonSerde, Sketch};
 use std::fs::File;
 use std::io::Write;
@@ -27,6 +28,25 @@ impl Sketch {
             .map_err(|e| e.to_string())?;
         Ok(())
     }
+
+    /// Convert the sketch instance into a gzip-compressed custom JSON string. This keeps large
+    /// sketches (e.g., with many observations) small on disk.
+    ///
+    /// The compressed data can be imported using [Sketch::from_custom_json_bytes].
+    pub fn to_json_gz(&self) -> Result<Vec<u8>, String> {
+        gzip_compress(&self.to_custom_json())
+    }
+
+    /// Export the sketch instance into a gzip-compressed custom JSON model format.
+    ///
+    /// See [Sketch::to_json_gz] for details on the actual conversion.
+    pub fn export_to_custom_json_gz(&self, filepath: &str) -> Result<(), String> {
+        let compressed_json = self.to_json_gz()?;
+        let mut file = File::create(filepath).map_err(|e| e.to_string())?;
+        file.write_all(&compressed_json)
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
 }
 
 impl Sketch {
@@ -39,6 +59,9 @@ impl Sketch {
     /// via model annotations. Currently the annotations are given in json simply as
     ///   #!entity_type: ID: #`json_string`#
     /// These entities can be variables, functions, static/dynamic properties, and datasets.
+    /// Annotations of variables and functions are part of their JSON data. The (non-empty)
+    /// annotation of the sketch itself is given as a JSON-encoded string:
+    ///   #!annotation: #`json_string`#
     ///
     /// Note that Sketchbook supports more options for regulation monotonicity/essentiality
     /// than the standard AEON format allows. These specialized (e.g., dual) regulations are
@@ -88,6 +111,12 @@ impl Sketch {
             annotation.ensure_value(&["function", fn_id.as_str()], &fn_data_json);
         }
 
+        // Annotation of the whole sketch (JSON-encoded, so that multi-line text stays on one line)
+        if !self.annotation.is_empty() {
+            let annotation_json = serde_json::to_string(&self.annotation).unwrap();
+            annotation.ensure_value(&["annotation"], &annotation_json);
+        }
+
         // Push the annotations to the aeon string
         let annotation_str = annotation.to_string();
         aeon_str.push_str(&annotation_str);
@@ -7,17 +7,20 @@ use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
 use crate::sketchbook::observations::ObservationManager;
 use crate::sketchbook::properties::shortcuts::*;
 use crate::sketchbook::properties::{DynProperty, StatProperty};
+use crate::sketchbook::utils::{decode_maybe_gzipped, read_maybe_gzipped_file};
 use crate::sketchbook::{JsonSerde, Sketch};
 use biodivine_lib_param_bn::{BooleanNetwork, ModelAnnotation};
 use regex::Regex;
-use std::fs::File;
-use std::io::Read;
 
 impl Sketch {
     /// Create sketch instance from a custom JSON model format.
     ///
-    /// See [SketchData::from_json_str] for details on the actual parsing.
+    /// The structure of the JSON is first checked using [SketchData::validate_schema], so that
+    /// all structural problems are reported at once. See [SketchData::from_json_str] for details
+    /// on the actual parsing.
     pub fn from_custom_json(json_str: &str) -> Result<Sketch, String> {
+        SketchData::validate_schema(json_str)
+            .map_err(|errors| format!("Invalid sketch data:\n{}", errors.join("\n")))?;
         // parse the JSON to intermediate SketchData first
         let sketch_data = SketchData::from_json_str(json_str)?;
         let mut sketch = Sketch::new_from_sketch_data(&sketch_data)?;
@@ -25,6 +28,16 @@ impl Sketch {
         Ok(sketch)
     }
 
+    /// Create sketch instance from raw contents of a file in the custom JSON model format.
+    ///
+    /// The contents may be gzip-compressed (see [Sketch::to_json_gz]), which is detected using
+    /// the gzip magic bytes, and the data are transparently decompressed before parsing. See
+    /// [Sketch::from_custom_json] for details on the actual parsing.
+    pub fn from_custom_json_bytes(data: &[u8]) -> Result<Sketch, String> {
+        let json_str = decode_maybe_gzipped(data, false)?;
+        Self::from_custom_json(&json_str)
+    }
+
     /// Create sketch instance from a customized version of AEON model format.
     /// The original part of the AEON format (compatible with other biodivine tools) includes:
     /// - variable IDs
@@ -37,6 +50,8 @@ impl Sketch {
     ///   #!entity_type: ID: #`json_string`#
     /// These annotations either cover additional information (complementing variables and
     /// functions), or completely new components like static/dynamic properties and datasets.
+    /// The annotation of the whole sketch is given as a JSON-encoded string:
+    ///   #!annotation: #`json_string`#
     ///
     /// If there are inconsistencies between annotations and pure AEON format, the annotations
     /// take precedence. This may arise since Sketchbook provides more types of regulations
@@ -71,6 +86,9 @@ impl Sketch {
         let datasets = Self::extract_entities(&aeon_annotations, "dataset")?;
         let stat_props = Self::extract_entities(&aeon_annotations, "static_property")?;
         let dyn_props = Self::extract_entities(&aeon_annotations, "dynamic_property")?;
+        if let Some(annotation) = Self::extract_sketch_annotation(&aeon_annotations)? {
+            sketch.set_annotation(&annotation);
+        }
 
         // The annotations may contain some additional function symbols of the sketch (that
         // were not part of any update expressions). We must first add these function symbols
@@ -99,6 +117,9 @@ impl Sketch {
             let var_id = sketch.model.get_var_id(&id)?;
             sketch.model.set_raw_var(&var_id, var_data.to_var()?)?;
             sketch.model.set_update_fn(&var_id, &var_data.update_fn)?;
+            sketch
+                .model
+                .set_update_fn_frozen(&var_id, var_data.frozen)?;
         }
 
         // Datasets have to be added from scratch
@@ -302,6 +323,22 @@ impl Sketch {
         }
     }
 
+    /// Extract the annotation of the whole sketch from an `.aeon` model annotation object, if
+    /// it is present. The annotation is expected to appear as a JSON-encoded string:
+    ///   #!annotation: #`"ANNOTATION"`#
+    fn extract_sketch_annotation(annotations: &ModelAnnotation) -> Result<Option<String>, String> {
+        let Some(annotation_node) = annotations.get_child(&["annotation"]) else {
+            return Ok(None);
+        };
+        if !annotation_node.children().is_empty() {
+            return Err("Sketch annotation contains nested values.".to_string());
+        }
+        let value = annotation_node.value().map(|v| v.as_str()).unwrap_or("");
+        let annotation = serde_json::from_str::<String>(value)
+            .map_err(|e| format!("Sketch annotation is not a valid JSON string: {e}"))?;
+        Ok(Some(annotation))
+    }
+
     /// Given a `ModelAnnotation` node corresponding to a particular entity type (like 'variable'),
     /// collect all entities of given type from the child nodes.
     ///
@@ -349,13 +386,10 @@ impl Sketch {
     }
 
     /// Load dataset from a provided CSV file path, and add it (with provided id/name)
-    /// directly to this sketch.
+    /// directly to this sketch. The file may be gzip-compressed.
     pub fn load_dataset(&mut self, dataset_id: &str, csv_path: &str) -> Result<(), String> {
-        // Load file contents
-        let mut file = File::open(csv_path).map_err(|e| e.to_string())?;
-        let mut csv_string = String::new();
-        file.read_to_string(&mut csv_string)
-            .map_err(|e| e.to_string())?;
+        // Load file contents (decompressing them if needed)
+        let csv_string = read_maybe_gzipped_file(csv_path)?;
 
         // Process the CSV data into `Dataset` instance and add it to the sketch
         let parsed_dataset =
@@ -418,6 +452,29 @@ mod tests {
         }
     }
 
+    #[test]
+    /// Test that annotations of variables, functions, and the sketch itself survive the export
+    /// to AEON format and the subsequent import.
+    fn aeon_annotations_round_trip() {
+        let mut sketch = Sketch::from_aeon("A -> B\nB -| A\n$A: f(B)").unwrap();
+        let var_a = sketch.model.get_var_id("A").unwrap();
+        let fn_f = sketch.model.get_uninterpreted_fn_id("f").unwrap();
+        sketch
+            .model
+            .set_var_annot(&var_a, "Variable A:\n  with `#special`# chars ")
+            .unwrap();
+        sketch.model.set_fn_annot(&fn_f, "Function f.").unwrap();
+        sketch.set_annotation("Sketch annotation:\n\n - several lines\n");
+
+        let aeon_str = sketch.to_aeon();
+        let imported_sketch = Sketch::from_aeon(&aeon_str).unwrap();
+        assert_eq!(imported_sketch, sketch);
+        assert_eq!(imported_sketch.get_annotation(), sketch.get_annotation());
+
+        // the exported model is still readable by other tools (that ignore the annotations)
+        assert!(biodivine_lib_param_bn::BooleanNetwork::try_from(aeon_str.as_str()).is_ok());
+    }
+
     #[test]
     /// Test that importing the same data from aeon and json format results in the same sketch.
     /// This test involves two models with various non-aeon-compatible regulations (such as
@@ -439,4 +496,24 @@ mod tests {
         let sketch2 = Sketch::from_custom_json(&json_contents).unwrap();
         assert_eq!(sketch1, sketch2);
     }
+
+    #[test]
+    /// Test that importing a sketch from compressed and uncompressed JSON results in the
+    /// same sketch.
+    fn gzip_import_round_trip() {
+        let mut json_sketch_file = File::open("../data/test_data/test_sketch_1.json").unwrap();
+        let mut json_contents = String::new();
+        json_sketch_file.read_to_string(&mut json_contents).unwrap();
+        let sketch = Sketch::from_custom_json(&json_contents).unwrap();
+
+        let compressed = sketch.to_json_gz().unwrap();
+        assert!(compressed.len() < sketch.to_custom_json().len());
+        let sketch_from_plain = Sketch::from_custom_json_bytes(json_contents.as_bytes()).unwrap();
+        let sketch_from_gz = Sketch::from_custom_json_bytes(&compressed).unwrap();
+        assert_eq!(sketch_from_plain, sketch);
+        assert_eq!(sketch_from_gz, sketch);
+
+        // corrupted compressed data are reported as an error
+        assert!(Sketch::from_custom_json_bytes(&compressed[..compressed.len() / 2]).is_err());
+    }
 }
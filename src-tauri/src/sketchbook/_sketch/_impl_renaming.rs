@@ -0,0 +1,489 @@
+use crate::app::state::{Consumed, SessionState};
+use crate::sketchbook::_sketch::_impl_session_state::{
+    RENAME_DATASET_REFERENCES_PATH, RENAME_VARIABLES_PATH,
+};
+use crate::sketchbook::data_structs::{ChangeIdData, SketchData};
+use crate::sketchbook::event_utils::{
+    make_reversible, make_state_change, mk_obs_event, mk_sketch_event,
+};
+use crate::sketchbook::ids::{DatasetId, VarId};
+use crate::sketchbook::utils::assert_ids_unique;
+use crate::sketchbook::{JsonSerde, Manager, Sketch};
+use std::collections::{HashMap, HashSet};
+
+/// Renaming variables and datasets across all components of the sketch.
+///
+/// The renaming is performed in several reversible steps, and the result is the merged
+/// [Consumed::ReversibleGroup] that can be un-done as a whole.
+impl Sketch {
+    /// Rename a dataset, changing its ID in the observation manager and in all properties that
+    /// reference it (template properties, wild-card propositions in generic HCTL formulas, and
+    /// observation macros in generic FOL formulas).
+    ///
+    /// The dataset itself is renamed first (via the event of the observation manager), and then
+    /// all references to it are updated. Returns the merged result of both steps.
+    ///
+    /// Returns `Err` if the original dataset does not exist, or the new ID is already used. The
+    /// operation is atomic - if any part of the renaming fails, the sketch is left unchanged.
+    pub fn rename_dataset(&mut self, old: &DatasetId, new: DatasetId) -> Result<Consumed, String> {
+        if old == &new {
+            return Ok(Consumed::NoChange);
+        }
+
+        // work on a copy of the sketch, so that we can simply drop it if anything fails
+        let mut sketch = self.clone();
+        let obs_at_path = [old.as_str(), "set_id"];
+        let obs_event = mk_obs_event(&obs_at_path, Some(new.as_str()));
+        let dataset_step = sketch
+            .observations
+            .perform_event(&obs_event, &obs_at_path)
+            .map_err(|e| e.to_string())?;
+        let references_step = sketch.rename_dataset_references(old, &new)?;
+        let result = Consumed::merge_reversible(vec![dataset_step, references_step])?;
+
+        *self = sketch;
+        Ok(result)
+    }
+
+    /// Update references to a dataset in all properties (the dataset itself is not renamed).
+    /// This is a reversible step of [Self::rename_dataset].
+    pub(super) fn rename_dataset_references(
+        &mut self,
+        old: &DatasetId,
+        new: &DatasetId,
+    ) -> Result<Consumed, String> {
+        self.properties.set_dataset_id_everywhere(old, new)?;
+
+        let sketch_data = SketchData::new_from_sketch(self);
+        let state_change = make_state_change(&["sketch", "set_all"], &sketch_data);
+        let change = ChangeIdData::new(old.as_str(), new.as_str());
+        let event = mk_sketch_event(
+            &[RENAME_DATASET_REFERENCES_PATH],
+            Some(&change.to_json_str()),
+        );
+        let reverse_change = ChangeIdData::new(new.as_str(), old.as_str());
+        let reverse_payload = reverse_change.to_json_str();
+        let reverse_event =
+            mk_sketch_event(&[RENAME_DATASET_REFERENCES_PATH], Some(&reverse_payload));
+        Ok(make_reversible(state_change, &event, reverse_event))
+    }
+
+    /// Rename several variables at once, as given by the `mapping` (original ID -> new ID).
+    /// The renaming is applied across the whole sketch - the model (variables, regulations,
+    /// layouts, update functions), datasets, and properties (both the regulation properties
+    /// and variables referenced in HCTL/FOL formulas), and the initial subspace.
+    ///
+    /// The mapping can also contain chains or cycles (e.g., swapping IDs of two variables). The
+    /// mapping must be injective and all new IDs must be either unused or freed by the renaming
+    /// itself.
+    ///
+    /// Variables are renamed one by one (each step is reversible by itself), always renaming
+    /// a variable whose new ID is free. If there is no such variable (the rest of the mapping
+    /// consists of cycles), one variable is first moved to a temporary ID. Returns the merged
+    /// result of all the steps.
+    ///
+    /// The operation is atomic - if any part of the renaming fails, `Err` is returned and the
+    /// sketch is left unchanged.
+    pub fn rename_variables(
+        &mut self,
+        mapping: &HashMap<VarId, VarId>,
+    ) -> Result<Consumed, String> {
+        // identity mappings do not need to be handled at all
+        let mapping: HashMap<VarId, VarId> = mapping
+            .iter()
+            .filter(|(original_id, new_id)| original_id != new_id)
+            .map(|(original_id, new_id)| (original_id.clone(), new_id.clone()))
+            .collect();
+        if mapping.is_empty() {
+            return Ok(Consumed::NoChange);
+        }
+        self.assert_valid_renaming(&mapping)?;
+
+        // work on a copy of the sketch, so that we can simply drop it if anything fails
+        let mut sketch = self.clone();
+
+        // sorted, so that the steps are deterministic
+        let mut pending: Vec<(VarId, VarId)> = mapping.clone().into_iter().collect();
+        pending.sort();
+        let mut steps = Vec::new();
+        while !pending.is_empty() {
+            let free_idx = pending
+                .iter()
+                .position(|(_, new_id)| !sketch.model.is_valid_var_id(new_id));
+            let (original_id, new_id) = match free_idx {
+                Some(idx) => pending.remove(idx),
+                None => {
+                    // break the cycle by moving the variable to a temporary ID first
+                    let (original_id, new_id) = pending.remove(0);
+                    let temp_id = sketch.generate_temporary_var_id(&mapping, &original_id);
+                    pending.push((temp_id.clone(), new_id));
+                    (original_id, temp_id)
+                }
+            };
+            steps.push(sketch.rename_variable_step(&original_id, &new_id)?);
+        }
+        let mut result = Consumed::merge_reversible(steps)?;
+        // each step sends the whole sketch data, so only the last one is needed
+        if let Consumed::ReversibleGroup { state_changes, .. } = &mut result {
+            state_changes.drain(..state_changes.len() - 1);
+        }
+
+        *self = sketch;
+        Ok(result)
+    }
+
+    /// **(internal)** Rename a single variable (see [Self::rename_variable_everywhere]), and
+    /// make IDs of the generated regulation properties consistent. This is a reversible step of
+    /// [Self::rename_variables] (the new ID must not be used).
+    fn rename_variable_step(
+        &mut self,
+        original_id: &VarId,
+        new_id: &VarId,
+    ) -> Result<Consumed, String> {
+        self.rename_variable_everywhere(original_id, new_id)?;
+        self.properties.make_generated_reg_prop_ids_consistent()?;
+
+        let sketch_data = SketchData::new_from_sketch(self);
+        let state_change = make_state_change(&["sketch", "set_all"], &sketch_data);
+        let change = vec![ChangeIdData::new(original_id.as_str(), new_id.as_str())];
+        let payload = serde_json::to_string(&change).map_err(|e| e.to_string())?;
+        let event = mk_sketch_event(&[RENAME_VARIABLES_PATH], Some(&payload));
+        let reverse_change = vec![ChangeIdData::new(new_id.as_str(), original_id.as_str())];
+        let payload = serde_json::to_string(&reverse_change).map_err(|e| e.to_string())?;
+        let reverse_event = mk_sketch_event(&[RENAME_VARIABLES_PATH], Some(&payload));
+        Ok(make_reversible(state_change, &event, reverse_event))
+    }
+
+    /// **(internal)** Check that the renaming given by `mapping` (without identity entries) can
+    /// be performed, i.e., all original variables exist, new IDs are unique, and new IDs do not
+    /// collide with variables that are not being renamed.
+    fn assert_valid_renaming(&self, mapping: &HashMap<VarId, VarId>) -> Result<(), String> {
+        for original_id in mapping.keys() {
+            if !self.model.is_valid_var_id(original_id) {
+                return Err(format!("Variable with ID `{original_id}` does not exist."));
+            }
+        }
+        let new_ids: Vec<&VarId> = mapping.values().collect();
+        assert_ids_unique(&new_ids)?;
+        for (original_id, new_id) in mapping.iter() {
+            if self.model.is_valid_var_id(new_id) && !mapping.contains_key(new_id) {
+                return Err(format!(
+                    "Can't rename `{original_id}` to `{new_id}`, variable `{new_id}` already exists."
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// **(internal)** Generate a temporary ID for a variable being renamed. The ID does not
+    /// collide with any existing variable (in the model or datasets), or with any new ID in the
+    /// `mapping`.
+    fn generate_temporary_var_id(
+        &self,
+        mapping: &HashMap<VarId, VarId>,
+        original_id: &VarId,
+    ) -> VarId {
+        let new_ids: HashSet<&VarId> = mapping.values().collect();
+        let is_taken = |sketch: &Sketch, id: &VarId| {
+            sketch.model.is_valid_var_id(id)
+                || new_ids.contains(id)
+                || sketch
+                    .observations
+                    .datasets()
+                    .any(|(_, dataset)| dataset.variables().contains(id))
+        };
+        let ideal_id = format!("tmp_{original_id}");
+        self.generate_id(&ideal_id, &is_taken, self.model.num_vars(), None)
+    }
+
+    /// **(internal)** Rename a single variable in all components of the sketch - the model,
+    /// all datasets containing the variable, properties, and the initial subspace.
+    ///
+    /// IDs of the generated regulation properties are not updated by this method.
+    fn rename_variable_everywhere(
+        &mut self,
+        original_id: &VarId,
+        new_id: &VarId,
+    ) -> Result<(), String> {
+        self.model.set_var_id(original_id, new_id.clone())?;
+
+        let affected_datasets: Vec<DatasetId> = self
+            .observations
+            .datasets()
+            .filter(|(_, dataset)| dataset.variables().contains(original_id))
+            .map(|(dataset_id, _)| dataset_id.clone())
+            .collect();
+        for dataset_id in affected_datasets {
+            self.observations
+                .set_var_id(&dataset_id, original_id, new_id.clone())?;
+        }
+
+        if let Some(value) = self.initial_subspace.remove(original_id) {
+            self.initial_subspace.insert(new_id.clone(), value);
+        }
+
+        self.properties.set_var_id_everywhere(original_id, new_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::event::Event;
+    use crate::app::state::{Consumed, SessionState};
+    use crate::sketchbook::ids::{DatasetId, StatPropertyId, VarId};
+    use crate::sketchbook::observations::Dataset;
+    use crate::sketchbook::properties::{DynProperty, StatProperty};
+    use crate::sketchbook::Sketch;
+    use std::collections::{BTreeMap, HashMap};
+
+    /// Prepare a simple sketch with variables `a`, `b`, `c`, a dataset, and several properties
+    /// referencing the variables.
+    fn prepare_sketch() -> Sketch {
+        let aeon_str = "a -> b\nb -| a\nc -> a\n$a: !b & c\n$b: a";
+        let mut sketch = Sketch::from_aeon(aeon_str).unwrap();
+        let dataset = Dataset::new_empty("d", vec!["a", "b", "c"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d", dataset)
+            .unwrap();
+
+        let dyn_prop = DynProperty::try_mk_generic("p", "3{x}: @{x}: (a & EF (b & {x}))").unwrap();
+        sketch
+            .properties
+            .add_dynamic_by_str("dyn", dyn_prop)
+            .unwrap();
+        let stat_prop = StatProperty::try_mk_generic("p", "f_a(1, 0) & !f_b(0)").unwrap();
+        sketch
+            .properties
+            .add_static_by_str("stat", stat_prop)
+            .unwrap();
+        sketch
+    }
+
+    /// Build the renaming mapping from string pairs.
+    fn mk_mapping(pairs: &[(&str, &str)]) -> HashMap<VarId, VarId> {
+        pairs
+            .iter()
+            .map(|(a, b)| (VarId::new(a).unwrap(), VarId::new(b).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    /// Test swapping IDs of two variables across the sketch.
+    fn test_rename_swap() {
+        let mut sketch = prepare_sketch();
+        let initial_subspace = BTreeMap::from([("a".to_string(), true), ("c".to_string(), false)]);
+        sketch
+            .set_initial_subspace_by_str(&initial_subspace)
+            .unwrap();
+        let mapping = mk_mapping(&[("a", "b"), ("b", "a")]);
+        sketch.rename_variables(&mapping).unwrap();
+        assert!(sketch.assert_consistency().is_ok());
+
+        // regulations and update functions are swapped
+        let expected_model = Sketch::from_aeon("b -> a\na -| b\nc -> b\n$b: !a & c\n$a: b")
+            .unwrap()
+            .model;
+        assert_eq!(sketch.model.regulations().count(), 3);
+        for reg in expected_model.regulations() {
+            assert!(sketch.model.regulations().any(|r| r == reg));
+        }
+        let fn_a = sketch.model.get_update_fn_string(&VarId::new("a").unwrap());
+        assert_eq!(fn_a.unwrap(), "b");
+
+        // formulas are updated
+        let dyn_prop = sketch.properties.get_dyn_prop_id("dyn").unwrap();
+        let dyn_prop = sketch.properties.get_dyn_prop(&dyn_prop).unwrap();
+        let expected = DynProperty::try_mk_generic("p", "3{x}: @{x}: (b & EF (a & {x}))").unwrap();
+        assert_eq!(dyn_prop, &expected);
+        let stat_prop = sketch.properties.get_stat_prop_id("stat").unwrap();
+        let stat_prop = sketch.properties.get_stat_prop(&stat_prop).unwrap();
+        let expected = StatProperty::try_mk_generic("p", "f_b(1, 0) & !f_a(0)").unwrap();
+        assert_eq!(stat_prop, &expected);
+
+        // initial subspace is updated
+        let expected = BTreeMap::from([
+            (VarId::new("b").unwrap(), true),
+            (VarId::new("c").unwrap(), false),
+        ]);
+        assert_eq!(sketch.get_initial_subspace(), &expected);
+
+        // generated regulation properties have consistent IDs
+        let prop_id = StatPropertyId::new("monotonicity_b_a").unwrap();
+        let prop = sketch.properties.get_stat_prop(&prop_id).unwrap();
+        let (regulator, target) = prop.clone().get_regulator_and_target().unwrap();
+        assert_eq!(regulator, Some(VarId::new("b").unwrap()));
+        assert_eq!(target, Some(VarId::new("a").unwrap()));
+    }
+
+    #[test]
+    /// Test that invalid renamings are rejected and the sketch is left unchanged.
+    fn test_rename_invalid() {
+        let mut sketch = prepare_sketch();
+        let sketch_orig = sketch.clone();
+
+        // collision with a variable that is not renamed
+        let mapping = mk_mapping(&[("a", "c")]);
+        assert!(sketch.rename_variables(&mapping).is_err());
+        // two variables renamed to the same ID
+        let mapping = mk_mapping(&[("a", "x"), ("b", "x")]);
+        assert!(sketch.rename_variables(&mapping).is_err());
+        // renaming non-existing variable
+        let mapping = mk_mapping(&[("a", "x"), ("z", "y")]);
+        assert!(sketch.rename_variables(&mapping).is_err());
+
+        assert_eq!(sketch, sketch_orig);
+    }
+
+    #[test]
+    /// Test renaming a dataset, including references in template and generic properties.
+    fn test_rename_dataset() {
+        let mut sketch = prepare_sketch();
+        let dataset = Dataset::new_empty("e", vec!["a"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("e", dataset)
+            .unwrap();
+        let d = DatasetId::new("d").unwrap();
+        let template = DynProperty::mk_trajectory("t", Some(d.clone()));
+        sketch
+            .properties
+            .add_dynamic_by_str("template", template)
+            .unwrap();
+        let generic =
+            DynProperty::try_mk_generic("g", "%trajectory(d)% & %trajectory(e)%").unwrap();
+        sketch
+            .properties
+            .add_dynamic_by_str("generic", generic)
+            .unwrap();
+        let sketch_orig = sketch.clone();
+
+        // new ID is already used, or the dataset does not exist
+        assert!(sketch
+            .rename_dataset(&d, DatasetId::new("e").unwrap())
+            .is_err());
+        let unknown = DatasetId::new("unknown").unwrap();
+        assert!(sketch
+            .rename_dataset(&unknown, DatasetId::new("x").unwrap())
+            .is_err());
+        assert_eq!(sketch, sketch_orig);
+
+        let new_id = DatasetId::new("data").unwrap();
+        sketch.rename_dataset(&d, new_id.clone()).unwrap();
+        assert!(sketch.observations.get_dataset(&new_id).is_ok());
+        assert!(sketch.observations.get_dataset(&d).is_err());
+        let prop_id = sketch.properties.get_dyn_prop_id("template").unwrap();
+        let prop = sketch.properties.get_dyn_prop(&prop_id).unwrap();
+        assert_eq!(prop.get_dataset(), Ok(Some(new_id)));
+        let prop_id = sketch.properties.get_dyn_prop_id("generic").unwrap();
+        let prop = sketch.properties.get_dyn_prop(&prop_id).unwrap();
+        let expected =
+            DynProperty::try_mk_generic("g", "%trajectory(data)% & %trajectory(e)%").unwrap();
+        assert_eq!(prop, &expected);
+        let observations = &sketch.observations;
+        assert!(sketch
+            .properties
+            .assert_wild_cards_resolvable(observations)
+            .is_ok());
+    }
+
+    #[test]
+    /// Test renaming a dataset referenced by observation macros in a generic static property.
+    fn test_rename_dataset_in_macros() {
+        let mut sketch = prepare_sketch();
+        let formula = "@d:o1 & !@d_2:o1 & (@d:o2 | f_a(1, 0))";
+        let stat_prop = StatProperty::try_mk_generic("m", formula).unwrap();
+        sketch
+            .properties
+            .add_static_by_str("macros", stat_prop)
+            .unwrap();
+
+        let d = DatasetId::new("d").unwrap();
+        sketch
+            .rename_dataset(&d, DatasetId::new("data").unwrap())
+            .unwrap();
+        let prop_id = sketch.properties.get_stat_prop_id("macros").unwrap();
+        let prop = sketch.properties.get_stat_prop(&prop_id).unwrap();
+        let expected_formula = "@data:o1 & !@d_2:o1 & (@data:o2 | f_a(1, 0))";
+        let expected = StatProperty::try_mk_generic("m", expected_formula).unwrap();
+        assert_eq!(prop, &expected);
+    }
+
+    /// Perform the given events on the sketch (the events have full paths, starting with
+    /// `sketch`).
+    fn perform_all(sketch: &mut Sketch, events: &[Event]) {
+        for event in events {
+            let at_path: Vec<&str> = event.path[1..].iter().map(|s| s.as_str()).collect();
+            sketch.perform_event(event, &at_path).unwrap();
+        }
+    }
+
+    #[test]
+    /// Test that swapping variables is a merged group of steps, and that its reverse events
+    /// restore the sketch step by step (and re-do it again).
+    fn test_rename_variables_undo() {
+        let mut sketch = prepare_sketch();
+        let sketch_orig = sketch.clone();
+        let mapping = mk_mapping(&[("a", "b"), ("b", "a")]);
+        let result = sketch.rename_variables(&mapping).unwrap();
+        let Consumed::ReversibleGroup {
+            state_changes,
+            perform_reverse: (perform, reverse),
+        } = result
+        else {
+            panic!("Swapping variables should result in a group of steps.");
+        };
+        // `a` is moved to a temporary ID, then `b` -> `a`, and the temporary ID -> `b`
+        assert_eq!(state_changes.len(), 1);
+        assert_eq!(perform.events.len(), 3);
+        assert_eq!(reverse.events.len(), 3);
+        let sketch_renamed = sketch.clone();
+
+        // partially restored sketch has the original `a` under the temporary ID
+        perform_all(&mut sketch, &reverse.events[..1]);
+        assert_eq!(sketch.model.num_vars(), 3);
+        assert!(sketch.model.get_var_id("a").is_ok());
+        assert!(sketch.model.get_var_id("b").is_err());
+        assert!(sketch.assert_consistency().is_ok());
+
+        perform_all(&mut sketch, &reverse.events[1..]);
+        assert_eq!(sketch, sketch_orig);
+        perform_all(&mut sketch, &perform.events);
+        assert_eq!(sketch, sketch_renamed);
+    }
+
+    #[test]
+    /// Test that renaming a dataset is a merged group of steps, and that its reverse events
+    /// restore the sketch step by step.
+    fn test_rename_dataset_undo() {
+        let mut sketch = prepare_sketch();
+        let d = DatasetId::new("d").unwrap();
+        let template = DynProperty::mk_trajectory("t", Some(d.clone()));
+        sketch
+            .properties
+            .add_dynamic_by_str("template", template)
+            .unwrap();
+        let sketch_orig = sketch.clone();
+
+        let new_id = DatasetId::new("data").unwrap();
+        let result = sketch.rename_dataset(&d, new_id.clone()).unwrap();
+        let Consumed::ReversibleGroup {
+            perform_reverse: (_, reverse),
+            ..
+        } = result
+        else {
+            panic!("Renaming a dataset should result in a group of steps.");
+        };
+        assert_eq!(reverse.events.len(), 2);
+
+        // references are restored first, while the dataset still has the new ID
+        perform_all(&mut sketch, &reverse.events[..1]);
+        assert!(sketch.observations.get_dataset(&new_id).is_ok());
+        let prop_id = sketch.properties.get_dyn_prop_id("template").unwrap();
+        let prop = sketch.properties.get_dyn_prop(&prop_id).unwrap();
+        assert_eq!(prop.get_dataset(), Ok(Some(d.clone())));
+
+        perform_all(&mut sketch, &reverse.events[1..]);
+        assert_eq!(sketch, sketch_orig);
+    }
+}
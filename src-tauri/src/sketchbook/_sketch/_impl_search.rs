@@ -0,0 +1,140 @@
+use crate::sketchbook::data_structs::{AnnotationComponent, AnnotationHit};
+use crate::sketchbook::Sketch;
+
+/// **(internal)** Number of characters included in a snippet on each side of the match.
+const SNIPPET_CONTEXT: usize = 20;
+
+/// Searching annotations across the whole sketch.
+impl Sketch {
+    /// Search for the `query` in annotations of the sketch - annotation of the whole sketch,
+    /// and annotations of all variables and uninterpreted functions. The search is a
+    /// case-insensitive substring search.
+    ///
+    /// Each matching annotation results in a single hit (with a snippet around its first match).
+    /// The sketch annotation goes first, then variables and functions, each sorted by ID. An
+    /// empty query matches nothing.
+    pub fn search_annotations(&self, query: &str) -> Vec<AnnotationHit> {
+        let mut hits = Vec::new();
+        if query.is_empty() {
+            return hits;
+        }
+
+        if let Some(snippet) = find_snippet(&self.annotation, query) {
+            hits.push(AnnotationHit {
+                component: AnnotationComponent::Sketch,
+                id: None,
+                snippet,
+            });
+        }
+
+        let mut variables: Vec<_> = self.model.variables().collect();
+        variables.sort_by_key(|(var_id, _)| *var_id);
+        for (var_id, variable) in variables {
+            if let Some(snippet) = find_snippet(variable.get_annotation(), query) {
+                hits.push(AnnotationHit {
+                    component: AnnotationComponent::Variable,
+                    id: Some(var_id.to_string()),
+                    snippet,
+                });
+            }
+        }
+
+        let mut functions: Vec<_> = self.model.uninterpreted_fns().collect();
+        functions.sort_by_key(|(fn_id, _)| *fn_id);
+        for (fn_id, function) in functions {
+            if let Some(snippet) = find_snippet(function.get_annotation(), query) {
+                hits.push(AnnotationHit {
+                    component: AnnotationComponent::UninterpretedFn,
+                    id: Some(fn_id.to_string()),
+                    snippet,
+                });
+            }
+        }
+        hits
+    }
+}
+
+/// **(internal)** Find the first case-insensitive occurrence of `query` in `text`, and return
+/// a snippet of `text` around it (with `...` marking the truncated parts, and line breaks
+/// replaced by spaces). Return `None` if there is no occurrence.
+fn find_snippet(text: &str, query: &str) -> Option<String> {
+    // lowercase the text, remembering the original char index of each byte of the result
+    // (lowercasing can change the length of some chars)
+    let chars: Vec<char> = text.chars().collect();
+    let mut lowered = String::new();
+    let mut char_indices = Vec::new();
+    for (i, c) in chars.iter().enumerate() {
+        let len_before = lowered.len();
+        lowered.extend(c.to_lowercase());
+        char_indices.resize(char_indices.len() + lowered.len() - len_before, i);
+    }
+
+    let query = query.to_lowercase();
+    let position = lowered.find(&query)?;
+    let match_start = char_indices[position];
+    let match_end = char_indices[position + query.len() - 1] + 1;
+
+    let start = match_start.saturating_sub(SNIPPET_CONTEXT);
+    let end = usize::min(match_end + SNIPPET_CONTEXT, chars.len());
+    let mut snippet: String = chars[start..end]
+        .iter()
+        .map(|c| if *c == '\n' || *c == '\r' { ' ' } else { *c })
+        .collect();
+    if start > 0 {
+        snippet = format!("...{snippet}");
+    }
+    if end < chars.len() {
+        snippet.push_str("...");
+    }
+    Some(snippet)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::data_structs::AnnotationComponent;
+    use crate::sketchbook::ids::{UninterpretedFnId, VarId};
+    use crate::sketchbook::Sketch;
+
+    #[test]
+    /// Test case-insensitive search across annotations of all sketch components.
+    fn test_search_annotations() {
+        let mut sketch = Sketch::from_aeon("a -> b\nb -| a\n$a: f(b)").unwrap();
+        sketch.set_annotation("Model of the P53 pathway.");
+        let var_a = VarId::new("a").unwrap();
+        let var_b = VarId::new("b").unwrap();
+        let fn_f = UninterpretedFnId::new("f").unwrap();
+        let long_annot =
+            "This variable represents the concentration of p53 protein in the nucleus.";
+        sketch.model.set_var_annot(&var_a, long_annot).unwrap();
+        sketch
+            .model
+            .set_var_annot(&var_b, "Unrelated\nnote")
+            .unwrap();
+        sketch
+            .model
+            .set_fn_annot(&fn_f, "Activated by P53")
+            .unwrap();
+
+        let hits = sketch.search_annotations("p53");
+        assert_eq!(hits.len(), 3);
+        assert_eq!(hits[0].component, AnnotationComponent::Sketch);
+        assert_eq!(hits[0].id, None);
+        assert_eq!(hits[0].snippet, "Model of the P53 pathway.");
+        assert_eq!(hits[1].component, AnnotationComponent::Variable);
+        assert_eq!(hits[1].id, Some("a".to_string()));
+        assert_eq!(
+            hits[1].snippet,
+            "...he concentration of p53 protein in the nucl..."
+        );
+        assert_eq!(hits[2].component, AnnotationComponent::UninterpretedFn);
+        assert_eq!(hits[2].id, Some("f".to_string()));
+
+        // line breaks are removed from snippets
+        let hits = sketch.search_annotations("NOTE");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].snippet, "Unrelated note");
+
+        assert!(sketch.search_annotations("").is_empty());
+        assert!(sketch.search_annotations("missing").is_empty());
+    }
+}
@@ -1,12 +1,13 @@
 use crate::app::event::Event;
 use crate::app::state::{Consumed, SessionHelper, SessionState};
-use crate::app::DynError;
-use crate::sketchbook::data_structs::SketchData;
+use crate::app::{AeonError, DynError};
+use crate::sketchbook::data_structs::{ChangeIdData, SketchData};
 use crate::sketchbook::event_utils::{make_reversible, make_state_change};
-use crate::sketchbook::{JsonSerde, Sketch};
+use crate::sketchbook::ids::{DatasetId, VarId};
+use crate::sketchbook::utils::read_maybe_gzipped_file;
+use crate::sketchbook::{IssueSeverity, JsonSerde, Sketch};
 use base64::prelude::*;
-use std::fs::File;
-use std::io::Read;
+use std::collections::{BTreeMap, HashMap};
 
 /* Constants for event path segments for various events. */
 
@@ -34,12 +35,26 @@ const IMPORT_SBML_PATH: &str = "import_sbml";
 const CHECK_CONSISTENCY_PATH: &str = "check_consistency";
 // get number of parameters of the PSBN
 const GET_NUM_PSBN_PARAMS_PATH: &str = "get_num_psbn_params";
+// get number of free parameters of the PSBN (considering regulation types)
+const GET_NUM_FREE_PARAMS_PATH: &str = "get_num_free_params";
 // assert that various components of sketch are consistent together
 const ASSERT_CONSISTENCY_PATH: &str = "assert_consistency";
+// set severity of consistency issues regarding declared regulators of variables
+const SET_REGULATOR_CHECK_SEVERITY_PATH: &str = "set_regulator_check_severity";
 // set annotation for the sketch
 const SET_ANNOTATION_PATH: &str = "set_annotation";
+// set initial subspace (partial assignment of variables) for reachability-based properties
+const SET_INITIAL_SUBSPACE_PATH: &str = "set_initial_subspace";
+// rename several variables at once across the whole sketch
+pub(super) const RENAME_VARIABLES_PATH: &str = "rename_variables";
+// rename a dataset across the whole sketch
+const RENAME_DATASET_PATH: &str = "rename_dataset";
+// update references to a dataset in all properties (a step of renaming the dataset)
+pub(super) const RENAME_DATASET_REFERENCES_PATH: &str = "rename_dataset_references";
 // refresh the whole sketch
 const GET_WHOLE_SKETCH_PATH: &str = "get_whole_sketch";
+// get aggregate statistics of the sketch components
+const GET_STATISTICS_PATH: &str = "get_statistics";
 
 impl SessionHelper for Sketch {}
 
@@ -63,8 +78,19 @@ impl SessionState for Sketch {
             })
         } else if Self::starts_with(EXPORT_SKETCH_PATH, at_path).is_some() {
             let path = Self::clone_payload_str(event, "sketch")?;
-            self.export_to_custom_json(&path)?;
-            Ok(Consumed::NoChange)
+            // files with the `.gz` extension are compressed
+            if path.ends_with(".gz") {
+                self.export_to_custom_json_gz(&path)?;
+            } else {
+                self.export_to_custom_json(&path)?;
+            }
+            // the sketch itself does not change, but the (successful) save is reported
+            let path_json = serde_json::to_string(&path)?;
+            let state_change = Event::build(&["sketch", "sketch_saved"], Some(&path_json));
+            Ok(Consumed::Irreversible {
+                state_change,
+                reset: false,
+            })
         } else if Self::starts_with(EXPORT_AEON_PATH, at_path).is_some() {
             let path = Self::clone_payload_str(event, "sketch")?;
             self.export_to_aeon(&path)?;
@@ -85,10 +111,8 @@ impl SessionState for Sketch {
             Ok(Consumed::NoChange)
         } else if Self::starts_with(IMPORT_SKETCH_PATH, at_path).is_some() {
             let file_path = Self::clone_payload_str(event, "sketch")?;
-            // read the file contents
-            let mut file = File::open(file_path)?;
-            let mut contents = String::new();
-            file.read_to_string(&mut contents)?;
+            // read the file contents (decompressing them if needed)
+            let contents = read_maybe_gzipped_file(&file_path)?;
 
             // parse the SketchData, modify the sketch
             let new_sketch = Sketch::from_custom_json(&contents)?;
@@ -103,10 +127,8 @@ impl SessionState for Sketch {
             })
         } else if Self::starts_with(IMPORT_AEON_PATH, at_path).is_some() {
             let file_path = Self::clone_payload_str(event, "sketch")?;
-            // read the file contents
-            let mut file = File::open(file_path)?;
-            let mut contents = String::new();
-            file.read_to_string(&mut contents)?;
+            // read the file contents (decompressing them if needed)
+            let contents = read_maybe_gzipped_file(&file_path)?;
 
             // parse AEON format (extended with custom annotations)
             let new_sketch = Sketch::from_aeon(&contents)?;
@@ -121,10 +143,8 @@ impl SessionState for Sketch {
             })
         } else if Self::starts_with(IMPORT_SBML_PATH, at_path).is_some() {
             let file_path = Self::clone_payload_str(event, "sketch")?;
-            // read the file contents
-            let mut file = File::open(file_path)?;
-            let mut contents = String::new();
-            file.read_to_string(&mut contents)?;
+            // read the file contents (decompressing them if needed)
+            let contents = read_maybe_gzipped_file(&file_path)?;
 
             // parse the SBML format (only psbn, no additional properties or datasets)
             let new_sketch = Sketch::from_sbml(&contents)?;
@@ -138,7 +158,13 @@ impl SessionState for Sketch {
                 reset: true,
             })
         } else if Self::starts_with(CHECK_CONSISTENCY_PATH, at_path).is_some() {
-            let (success, main_message, warn_message) = self.run_consistency_check();
+            // optional payload (bool) enables checking regulations against update functions
+            let check_regulation_usage = match &event.payload {
+                Some(payload) => serde_json::from_str::<bool>(payload)?,
+                None => false,
+            };
+            let (success, main_message, warn_message) =
+                self.run_consistency_check_with(check_regulation_usage);
             let results = if success {
                 "No major issues with the sketch were discovered!".to_string()
             } else {
@@ -188,10 +214,79 @@ impl SessionState for Sketch {
             reverse_event.payload = Some(orig_annotation);
 
             Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with(SET_INITIAL_SUBSPACE_PATH, at_path).is_some() {
+            // get the payload - json string encoding a map from variable IDs to their values
+            let payload = Self::clone_payload_str(event, "sketch")?;
+            let new_subspace: BTreeMap<String, bool> = serde_json::from_str(&payload)?;
+            let orig_subspace: BTreeMap<String, bool> = self
+                .get_initial_subspace()
+                .iter()
+                .map(|(var_id, value)| (var_id.to_string(), *value))
+                .collect();
+            if new_subspace == orig_subspace {
+                return Ok(Consumed::NoChange);
+            }
+
+            // set the subspace and prepare state-change + reverse events
+            self.set_initial_subspace_by_str(&new_subspace)?;
+            let payload = serde_json::to_string(&new_subspace).unwrap();
+            let state_change = Event::build(&["sketch", "set_initial_subspace"], Some(&payload));
+            let mut reverse_event = event.clone();
+            reverse_event.payload = Some(serde_json::to_string(&orig_subspace).unwrap());
+
+            Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with(RENAME_VARIABLES_PATH, at_path).is_some() {
+            // get the payload - json string encoding a list of ID change data
+            let payload = Self::clone_payload_str(event, "sketch")?;
+            let changes: Vec<ChangeIdData> = serde_json::from_str(&payload)?;
+            let mut mapping = HashMap::new();
+            for change in changes {
+                let original_id = VarId::new(&change.original_id)?;
+                let new_id = VarId::new(&change.new_id)?;
+                if mapping.insert(original_id, new_id).is_some() {
+                    return AeonError::throw(format!(
+                        "Variable `{}` can't be renamed more than once.",
+                        change.original_id
+                    ));
+                }
+            }
+
+            // the renaming is performed in several reversible steps (merged into one result)
+            Ok(self.rename_variables(&mapping)?)
+        } else if Self::starts_with(RENAME_DATASET_PATH, at_path).is_some() {
+            // get the payload - json string encoding the ID change data
+            let payload = Self::clone_payload_str(event, "sketch")?;
+            let change = ChangeIdData::from_json_str(&payload)?;
+            let original_id = DatasetId::new(&change.original_id)?;
+            let new_id = DatasetId::new(&change.new_id)?;
+
+            // the renaming is performed in several reversible steps (merged into one result)
+            Ok(self.rename_dataset(&original_id, new_id)?)
+        } else if Self::starts_with(RENAME_DATASET_REFERENCES_PATH, at_path).is_some() {
+            // get the payload - json string encoding the ID change data
+            let payload = Self::clone_payload_str(event, "sketch")?;
+            let change = ChangeIdData::from_json_str(&payload)?;
+            let original_id = DatasetId::new(&change.original_id)?;
+            let new_id = DatasetId::new(&change.new_id)?;
+            Ok(self.rename_dataset_references(&original_id, &new_id)?)
+        } else if Self::starts_with(SET_REGULATOR_CHECK_SEVERITY_PATH, at_path).is_some() {
+            let payload = Self::clone_payload_str(event, "sketch")?;
+            let severity: IssueSeverity = serde_json::from_str(&payload)?;
+            self.set_regulator_check_severity(severity);
+
+            let payload = serde_json::to_string(&severity)?;
+            let state_change =
+                Event::build(&["sketch", "regulator_check_severity"], Some(&payload));
+            // the setting is not part of the sketch data, there is nothing to undo
+            Ok(Consumed::Irreversible {
+                state_change,
+                reset: false,
+            })
         } else if Self::starts_with(ASSERT_CONSISTENCY_PATH, at_path).is_some() {
             // This is a "synthetic" event that either returns an error, or Consumed::NoChange
             // Use `CHECK_CONSISTENCY_PATH` to also send detailed message to frontend
-            self.assert_consistency()?;
+            // Only components changed since the last successful check are validated
+            self.assert_consistency_incremental()?;
             Ok(Consumed::NoChange)
         } else {
             Self::invalid_path_error_generic(at_path)
@@ -206,12 +301,24 @@ impl SessionState for Sketch {
             self.observations.refresh(full_path, at_path)
         } else if let Some(at_path) = Self::starts_with(PROPERTIES_PATH, at_path) {
             self.properties.refresh(full_path, at_path)
+        } else if Self::starts_with(GET_NUM_FREE_PARAMS_PATH, at_path).is_some() {
+            let num_free_params = self.count_free_parameters();
+            Ok(Event {
+                path: full_path.to_vec(),
+                payload: Some(serde_json::to_string(&num_free_params)?),
+            })
         } else if Self::starts_with(GET_WHOLE_SKETCH_PATH, at_path).is_some() {
             let sketch_data = SketchData::new_from_sketch(self);
             Ok(Event {
                 path: full_path.to_vec(),
                 payload: Some(sketch_data.to_json_str()),
             })
+        } else if Self::starts_with(GET_STATISTICS_PATH, at_path).is_some() {
+            let stats = self.statistics();
+            Ok(Event {
+                path: full_path.to_vec(),
+                payload: Some(stats.to_json_str()),
+            })
         } else {
             Self::invalid_path_error_generic(at_path)
         }
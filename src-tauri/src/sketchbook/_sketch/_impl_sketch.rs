@@ -1,12 +1,14 @@
-use crate::sketchbook::data_structs::SketchData;
-use crate::sketchbook::ids::VarId;
+use crate::sketchbook::data_structs::{SketchData, SketchStats};
+use crate::sketchbook::ids::{DatasetId, UninterpretedFnId, VarId};
 use crate::sketchbook::model::ModelState;
 use crate::sketchbook::observations::{Dataset, ObservationManager};
+use crate::sketchbook::properties::dynamic_props::SimpleDynPropertyType;
+use crate::sketchbook::properties::static_props::SimpleStatPropertyType;
 use crate::sketchbook::properties::{DynProperty, PropertyManager, StatProperty};
-use crate::sketchbook::Sketch;
+use crate::sketchbook::{IssueSeverity, Sketch};
 
-use biodivine_lib_param_bn::symbolic_async_graph::SymbolicContext;
-use std::collections::HashSet;
+use biodivine_lib_param_bn::symbolic_async_graph::{SymbolicAsyncGraph, SymbolicContext};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Utility functions for creating or modifying sketch instances.
 impl Sketch {
@@ -59,28 +61,39 @@ impl Sketch {
     /// Create a new `Sketch` instance given a corresponding `SketchData` object.
     pub fn new_from_sketch_data(sketch_data: &SketchData) -> Result<Sketch, String> {
         let (model, obs_manager, prop_manager) = Self::components_from_sketch_data(sketch_data)?;
-        Ok(Sketch {
+        let mut sketch = Sketch {
             model,
             observations: obs_manager,
             properties: prop_manager,
             annotation: sketch_data.annotation.clone(),
-        })
+            initial_subspace: BTreeMap::new(),
+            consistency_cache: None,
+            regulator_check_severity: IssueSeverity::default(),
+        };
+        sketch.set_initial_subspace_by_str(&sketch_data.initial_subspace)?;
+        Ok(sketch)
     }
 
     /// Modify this `Sketch` instance by loading all its components from a corresponding
     /// `SketchData` instance. The original sketch information is forgotten.
     pub fn modify_from_sketch_data(&mut self, sketch_data: &SketchData) -> Result<(), String> {
         let (model, obs_manager, prop_manager) = Self::components_from_sketch_data(sketch_data)?;
+        let initial_subspace = Self::initial_subspace_from_strs(&sketch_data.initial_subspace)?;
+        Self::assert_subspace_vars_valid(&model, &initial_subspace)?;
         self.model = model;
         self.observations = obs_manager;
         self.properties = prop_manager;
+        self.initial_subspace = initial_subspace;
         Ok(())
     }
 
     /// Modify this `Sketch` instance by loading all its components from a different
     /// `Sketch` instance. The original sketch information is forgotten.
     pub fn modify_from_sketch(&mut self, other_sketch: &Sketch) {
+        // the severity setting is not part of the sketch data, so we keep it
+        let regulator_check_severity = self.regulator_check_severity;
         *self = other_sketch.clone();
+        self.regulator_check_severity = regulator_check_severity;
     }
 
     /// Modify this `Sketch` instance to a default (empty) settings.
@@ -89,6 +102,7 @@ impl Sketch {
         self.observations = ObservationManager::default();
         self.properties = PropertyManager::default();
         self.annotation = String::new();
+        self.initial_subspace = BTreeMap::new();
     }
 
     /// Get a copy of this sketch with pre-processed datasets so that they match the
@@ -143,16 +157,88 @@ impl Sketch {
         modified_sketch
     }
 
+    /// Get a copy of this sketch with components that are not needed for the inference pruned
+    /// out, which reduces the encoding cost. The original sketch is not modified.
+    ///
+    /// Datasets not referenced by any property (dynamic properties and observation macros in
+    /// static properties) are removed. Uninterpreted functions with specified expressions that
+    /// are not referenced by any static property are inlined into all update functions and
+    /// expressions of other functions, and then removed (see
+    /// [ModelState::inline_uninterpreted_fns]). If the function expressions cannot be propagated
+    /// (e.g., they contain cycles), the functions are kept unchanged.
+    pub fn prepared_for_inference(&self) -> Sketch {
+        let mut prepared_sketch = self.clone();
+
+        let referenced_datasets = self.collect_referenced_datasets();
+        for (dataset_id, _) in self.observations.datasets() {
+            if !referenced_datasets.contains(dataset_id) {
+                // Can safely unwrap, the dataset ID must be valid in the sketch
+                prepared_sketch
+                    .observations
+                    .remove_dataset(dataset_id)
+                    .unwrap();
+            }
+        }
+
+        let inlined_fns: HashSet<UninterpretedFnId> = self
+            .model
+            .uninterpreted_fns()
+            .filter(|(fn_id, uninterpreted_fn)| {
+                !uninterpreted_fn.has_empty_expression()
+                    && self.properties.stat_properties_using_fn(fn_id).is_empty()
+            })
+            .map(|(fn_id, _)| fn_id.clone())
+            .collect();
+        let mut prepared_model = prepared_sketch.model.clone();
+        if prepared_model
+            .inline_uninterpreted_fns(&inlined_fns)
+            .is_ok()
+        {
+            prepared_sketch.model = prepared_model;
+        }
+
+        prepared_sketch
+    }
+
+    /// **(internal)** Collect IDs of all datasets referenced by some property, either by
+    /// a dynamic property (see [PropertyManager::dyn_properties_using_dataset]), or by an
+    /// observation macro in a generic static property.
+    pub(super) fn collect_referenced_datasets(&self) -> HashSet<DatasetId> {
+        let mut referenced_datasets: HashSet<DatasetId> = self
+            .observations
+            .datasets()
+            .map(|(dataset_id, _)| dataset_id)
+            .filter(|dataset_id| {
+                !self
+                    .properties
+                    .dyn_properties_using_dataset(dataset_id)
+                    .is_empty()
+            })
+            .cloned()
+            .collect();
+        for (_, prop) in self.properties.stat_props() {
+            referenced_datasets.extend(prop.get_all_datasets());
+        }
+        referenced_datasets
+    }
+
     /// Get annotation string.
     pub fn get_annotation(&self) -> &str {
         &self.annotation
     }
 
+    /// Get the initial subspace (partial assignment of variables) restricting the initial states
+    /// of reachability-based properties. Empty subspace means there is no restriction.
+    pub fn get_initial_subspace(&self) -> &BTreeMap<VarId, bool> {
+        &self.initial_subspace
+    }
+
     /// Get number of BN "parameters", e.g., number of symbolic variables needed to encode
     /// the uninterprete functions. The number of interpretations should be 2^{num_parameters}.
     ///
     /// All unused function symbols are pruned first, and function expressions are substituted
-    /// in before computing the number of params.
+    /// in before computing the number of params. Input variables are treated as constants
+    /// (they do not introduce any parameters).
     pub fn get_num_parameters(&self) -> usize {
         let bn = self.model.to_bn_with_plain_regulations();
         // remove all unused function symbols, as these would cause problems later
@@ -161,16 +247,265 @@ impl Sketch {
         context.num_parameter_variables()
     }
 
+    /// Get number of "free" BN parameters, i.e., the number of function table entries (of both
+    /// uninterpreted functions and unspecified update functions) that are not fixed by the
+    /// monotonicity and essentiality of regulations. Such entries can be both true and false
+    /// in some valid interpretations, and together they give the degrees of freedom of the sketch.
+    ///
+    /// Like [Self::get_num_parameters], the unused function symbols are pruned first and function
+    /// expressions are substituted, and inputs are treated as constants. The regulation
+    /// constraints are applied the same way as when
+    /// building the symbolic parameter space. If the constraints cannot be satisfied at all,
+    /// there are no valid interpretations and the result is 0.
+    pub fn count_free_parameters(&self) -> u64 {
+        let bn = self.model.to_bn_with_constant_inputs();
+        // remove all unused function symbols, as these would cause problems later
+        let bn = bn.prune_unused_parameters();
+        let Ok(graph) = SymbolicAsyncGraph::new(&bn) else {
+            // regulation constraints are unsatisfiable
+            return 0;
+        };
+        let unit_colors = graph.unit_colors().as_bdd();
+        let free_params = graph
+            .symbolic_context()
+            .parameter_variables()
+            .iter()
+            .filter(|var| {
+                !unit_colors.var_restrict(**var, true).is_false()
+                    && !unit_colors.var_restrict(**var, false).is_false()
+            })
+            .count();
+        free_params as u64
+    }
+
+    /// Collect aggregate statistics about all components of the sketch (see [SketchStats]).
+    ///
+    /// Note that this includes computing the number of free parameters, which requires
+    /// building the symbolic parameter space.
+    pub fn statistics(&self) -> SketchStats {
+        let mut regulations_by_sign = HashMap::new();
+        for regulation in self.model.regulations() {
+            *regulations_by_sign
+                .entry(*regulation.get_sign())
+                .or_insert(0) += 1;
+        }
+        let mut fns_by_arity = HashMap::new();
+        for (_, uninterpreted_fn) in self.model.uninterpreted_fns() {
+            *fns_by_arity
+                .entry(uninterpreted_fn.get_arity())
+                .or_insert(0) += 1;
+        }
+        let observations_by_dataset = self
+            .observations
+            .datasets()
+            .map(|(id, dataset)| (id.as_str().to_string(), dataset.num_observations()))
+            .collect();
+        let mut dyn_properties_by_kind = HashMap::new();
+        for (_, prop) in self.properties.dyn_props() {
+            let kind = SimpleDynPropertyType::from(prop.get_prop_data().clone());
+            *dyn_properties_by_kind.entry(kind).or_insert(0) += 1;
+        }
+        let mut stat_properties_by_kind = HashMap::new();
+        for (_, prop) in self.properties.stat_props() {
+            let kind = SimpleStatPropertyType::from(prop.get_prop_data().clone());
+            *stat_properties_by_kind.entry(kind).or_insert(0) += 1;
+        }
+
+        SketchStats {
+            num_variables: self.model.num_vars(),
+            num_regulations: self.model.num_regulations(),
+            regulations_by_sign,
+            num_uninterpreted_fns: self.model.num_uninterpreted_fns(),
+            fns_by_arity,
+            num_datasets: self.observations.num_datasets(),
+            observations_by_dataset,
+            num_dyn_properties: self.properties.num_dyn_properties(),
+            dyn_properties_by_kind,
+            num_stat_properties: self.properties.num_stat_properties(),
+            stat_properties_by_kind,
+            num_free_params: self.count_free_parameters(),
+        }
+    }
+
     /// Set annotation string.
     pub fn set_annotation(&mut self, annotation: &str) {
         self.annotation = annotation.to_string()
     }
+
+    /// Set the initial subspace (partial assignment of variables) restricting the initial states
+    /// of reachability-based properties. Use an empty subspace to remove the restriction.
+    ///
+    /// All variables of the subspace must be valid variables of the model.
+    pub fn set_initial_subspace(
+        &mut self,
+        initial_subspace: BTreeMap<VarId, bool>,
+    ) -> Result<(), String> {
+        Self::assert_subspace_vars_valid(&self.model, &initial_subspace)?;
+        self.initial_subspace = initial_subspace;
+        Ok(())
+    }
+
+    /// Set the initial subspace, given as a mapping from string variable IDs to their values.
+    ///
+    /// See [Self::set_initial_subspace] for details.
+    pub fn set_initial_subspace_by_str(
+        &mut self,
+        initial_subspace: &BTreeMap<String, bool>,
+    ) -> Result<(), String> {
+        let initial_subspace = Self::initial_subspace_from_strs(initial_subspace)?;
+        self.set_initial_subspace(initial_subspace)
+    }
+
+    /// **(internal)** Convert a subspace with string variable IDs into a subspace with `VarId`s.
+    fn initial_subspace_from_strs(
+        initial_subspace: &BTreeMap<String, bool>,
+    ) -> Result<BTreeMap<VarId, bool>, String> {
+        initial_subspace
+            .iter()
+            .map(|(var_id, value)| Ok((VarId::new(var_id)?, *value)))
+            .collect()
+    }
+
+    /// **(internal)** Check that all variables of the subspace are valid variables of the `model`.
+    fn assert_subspace_vars_valid(
+        model: &ModelState,
+        initial_subspace: &BTreeMap<VarId, bool>,
+    ) -> Result<(), String> {
+        for var_id in initial_subspace.keys() {
+            if !model.is_valid_var_id(var_id) {
+                return Err(format!(
+                    "Initial subspace references variable `{var_id}` that is not in the model."
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::sketchbook::observations::Dataset;
+    use crate::sketchbook::ids::DatasetId;
+    use crate::sketchbook::model::Monotonicity;
+    use crate::sketchbook::observations::{Dataset, Observation};
+    use crate::sketchbook::properties::dynamic_props::SimpleDynPropertyType;
+    use crate::sketchbook::properties::static_props::SimpleStatPropertyType;
+    use crate::sketchbook::properties::{DynProperty, StatProperty};
     use crate::sketchbook::Sketch;
+    use std::collections::HashMap;
+
+    #[test]
+    /// Test collecting aggregate statistics of the sketch.
+    fn sketch_statistics() {
+        // regulation properties are generated for both (fully specified) regulations
+        let aeon_str = "A -> B\nB -| A\nA -?? A\n$B: f(A)";
+        let mut sketch = Sketch::from_aeon(aeon_str).unwrap();
+        let observations = vec![
+            Observation::try_from_str("10", "o1").unwrap(),
+            Observation::try_from_str("11", "o2").unwrap(),
+        ];
+        let dataset = Dataset::new("d1", observations, vec!["A", "B"]).unwrap();
+        sketch
+            .observations
+            .add_dataset_by_str("d1", dataset)
+            .unwrap();
+        let dataset_id = DatasetId::new("d1").unwrap();
+        let trajectory = DynProperty::mk_trajectory("t", Some(dataset_id));
+        sketch
+            .properties
+            .add_dynamic_by_str("t", trajectory)
+            .unwrap();
+
+        let stats = sketch.statistics();
+        assert_eq!(stats.num_variables, 2);
+        assert_eq!(stats.num_regulations, 3);
+        let expected_signs = HashMap::from([
+            (Monotonicity::Activation, 1),
+            (Monotonicity::Inhibition, 1),
+            (Monotonicity::Unknown, 1),
+        ]);
+        assert_eq!(stats.regulations_by_sign, expected_signs);
+        assert_eq!(stats.num_uninterpreted_fns, 1);
+        assert_eq!(stats.fns_by_arity, HashMap::from([(1, 1)]));
+        assert_eq!(stats.num_datasets, 1);
+        assert_eq!(
+            stats.observations_by_dataset,
+            HashMap::from([("d1".to_string(), 2)])
+        );
+        assert_eq!(stats.num_dyn_properties, 1);
+        assert_eq!(
+            stats.dyn_properties_by_kind,
+            HashMap::from([(SimpleDynPropertyType::ExistsTrajectory, 1)])
+        );
+        assert_eq!(stats.num_stat_properties, 4);
+        let expected_stat_kinds = HashMap::from([
+            (SimpleStatPropertyType::RegulationEssential, 2),
+            (SimpleStatPropertyType::RegulationMonotonic, 2),
+        ]);
+        assert_eq!(stats.stat_properties_by_kind, expected_stat_kinds);
+        assert_eq!(stats.num_free_params, sketch.count_free_parameters());
+    }
+
+    #[test]
+    /// Test counting free parameters, taking into account the types of regulations.
+    fn counting_free_parameters() {
+        // unspecified regulations, all 4 table entries of the implicit fn of B are free
+        let sketch = Sketch::from_aeon("A -?? B\nC -?? B\nA -?? A\nC -?? C").unwrap();
+        assert_eq!(sketch.count_free_parameters(), 8);
+        assert_eq!(sketch.get_num_parameters(), 8);
+
+        // monotonic essential regulations fix the lowest and highest entry of B's table
+        let sketch = Sketch::from_aeon("A -> B\nC -> B\nA -?? A\nC -?? C").unwrap();
+        assert_eq!(sketch.count_free_parameters(), 6);
+        assert_eq!(sketch.get_num_parameters(), 8);
+
+        // fully specified update functions have no free parameters
+        let sketch = Sketch::from_aeon("A -> B\nC -> B\n$B: A & C\n$A: true\n$C: false");
+        assert_eq!(sketch.unwrap().count_free_parameters(), 0);
+    }
+
+    #[test]
+    /// Test pruning unused datasets and inlining function expressions before inference.
+    fn preparing_for_inference() {
+        let aeon_str = "A -> B\nB -> A\n$A: f(B)\n$B: g(A)";
+        let mut sketch = Sketch::from_aeon(aeon_str).unwrap();
+        sketch
+            .model
+            .set_uninterpreted_fn_expression_by_str("f", "!var0")
+            .unwrap();
+        sketch
+            .model
+            .set_uninterpreted_fn_expression_by_str("g", "var0")
+            .unwrap();
+        for dataset_id in ["d1", "d2", "d3"] {
+            let dataset = Dataset::new_empty(dataset_id, vec!["A", "B"]).unwrap();
+            sketch
+                .observations
+                .add_dataset_by_str(dataset_id, dataset)
+                .unwrap();
+        }
+        // dataset `d1` is referenced by a dynamic property, `d2` by a static one
+        let d1 = DatasetId::new("d1").unwrap();
+        let dyn_prop = DynProperty::mk_trajectory("p", Some(d1.clone()));
+        sketch.properties.add_dynamic_by_str("p", dyn_prop).unwrap();
+        let stat_prop = StatProperty::try_mk_generic("q", "\\exists A, B: @d2:o1 => g(A)");
+        sketch
+            .properties
+            .add_static_by_str("q", stat_prop.unwrap())
+            .unwrap();
+        let sketch_orig = sketch.clone();
+
+        let prepared = sketch.prepared_for_inference();
+        assert_eq!(sketch, sketch_orig);
+        assert_eq!(prepared.observations.num_datasets(), 2);
+        assert!(prepared.observations.get_dataset(&d1).is_ok());
+        assert!(prepared.observations.get_dataset_id("d3").is_err());
+        // `f` is inlined, but `g` is kept since a property references it
+        assert_eq!(prepared.model.num_uninterpreted_fns(), 1);
+        assert!(prepared.model.get_uninterpreted_fn_id("g").is_ok());
+        let var_a = prepared.model.get_var_id("A").unwrap();
+        assert_eq!(prepared.model.get_update_fn_string(&var_a).unwrap(), "!B");
+        assert_eq!(prepared.properties, sketch.properties);
+    }
 
     #[test]
     /// Test that after processing datasets, they all have matching
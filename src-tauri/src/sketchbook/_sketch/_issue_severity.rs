@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Severity with which a particular kind of consistency issue is reported.
+///
+/// Warnings are only reported to the user, while errors make the sketch inconsistent (and thus
+/// block the inference).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IssueSeverity {
+    #[default]
+    Warning,
+    Error,
+}
@@ -1,30 +1,67 @@
+use crate::sketchbook::ids::VarId;
 use crate::sketchbook::model::ModelState;
 use crate::sketchbook::observations::ObservationManager;
 use crate::sketchbook::properties::PropertyManager;
 use crate::sketchbook::Manager;
+use _consistency_cache::ConsistencyCache;
+use std::collections::BTreeMap;
 
+pub use _issue_severity::IssueSeverity;
+
+/// **(internal)** Snapshot of sketch components used for incremental consistency checks.
+mod _consistency_cache;
 /// **(internal)** Utilities to check consistency of `Sketch` instances.
 mod _impl_consistency;
+/// **(internal)** Computing differences between two sketches.
+mod _impl_diff;
 /// **(internal)** Exporting sketch in various formats.
 mod _impl_export;
 /// **(internal)** Importing sketch in various formats.
 mod _impl_import;
+/// **(internal)** Renaming variables across the whole sketch.
+mod _impl_renaming;
+/// **(internal)** Searching annotations across the whole sketch.
+mod _impl_search;
 /// **(internal)** Implementation of event-based API for the [crate::app::state::SessionState] trait.
 mod _impl_session_state;
 /// **(internal)** Utility methods for `Sketch`.
 mod _impl_sketch;
+/// **(internal)** Definition of `IssueSeverity`.
+mod _issue_severity;
 
 /// Object encompassing all of the individual modules of the Boolean network sketch.
 ///
 /// Most of the actual functionality is implemented by the modules themselves, `Sketch`
 /// currently only distributes events and handles situations when cooperation between
 /// modules is needed.
-#[derive(Clone, Debug, PartialEq)]
+///
+/// The optional `initial_subspace` is a partial assignment of (some) variables, restricting
+/// the initial states of reachability-based properties (see
+/// [crate::algorithms::eval_dynamic::prepare_graph::prepare_initial_vertices]). Empty subspace
+/// means there is no restriction.
+///
+/// The sketch also keeps a snapshot of its components from the last successful consistency
+/// check (used for incremental checks), and the severity of consistency issues regarding
+/// declared regulators of variables. Neither is considered when comparing sketches.
+#[derive(Clone, Debug)]
 pub struct Sketch {
     pub model: ModelState,
     pub observations: ObservationManager,
     pub properties: PropertyManager,
     pub annotation: String,
+    initial_subspace: BTreeMap<VarId, bool>,
+    consistency_cache: Option<ConsistencyCache>,
+    regulator_check_severity: IssueSeverity,
+}
+
+impl PartialEq for Sketch {
+    fn eq(&self, other: &Self) -> bool {
+        self.model == other.model
+            && self.observations == other.observations
+            && self.properties == other.properties
+            && self.annotation == other.annotation
+            && self.initial_subspace == other.initial_subspace
+    }
 }
 
 impl Manager for Sketch {}
@@ -37,6 +74,9 @@ impl Default for Sketch {
             observations: ObservationManager::default(),
             properties: PropertyManager::default(),
             annotation: String::default(),
+            initial_subspace: BTreeMap::default(),
+            consistency_cache: None,
+            regulator_check_severity: IssueSeverity::default(),
         }
     }
 }
@@ -142,6 +142,101 @@ fn test_set_update_fn() {
     check_reverse(&mut model, &model_orig, result, &reverse_at_path);
 }
 
+#[test]
+/// Test freezing variable's update function via event.
+fn test_set_update_fn_frozen() {
+    let variables = vec![("a", "a_name"), ("b", "b_name")];
+    let mut model = ModelState::new_with_vars(variables).unwrap();
+    let var_a = model.get_var_id("a").unwrap();
+    model.set_update_fn(&var_a, "a => b").unwrap();
+    let model_orig = model.clone();
+
+    // test freezing event (setting the same value again does nothing)
+    let at_path = ["variable", var_a.as_str(), "set_update_fn_frozen"];
+    let event = mk_model_event(&at_path, Some("false"));
+    let result = model.perform_event(&event, &at_path).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+    let event = mk_model_event(&at_path, Some("true"));
+    let result = model.perform_event(&event, &at_path).unwrap();
+
+    // check that update fn was frozen, and test the reverse event
+    assert!(model.get_update_fn(&var_a).unwrap().is_frozen());
+    check_reverse(&mut model, &model_orig, result, &at_path);
+}
+
+#[test]
+/// Test setting variable's update function to a constant via event.
+fn test_set_update_fn_constant() {
+    let variables = vec![("a", "a_name"), ("b", "b_name")];
+    let mut model = ModelState::new_with_vars(variables).unwrap();
+    let var_a = model.get_var_id("a").unwrap();
+    model.set_update_fn(&var_a, "a => b").unwrap();
+    let model_orig = model.clone();
+
+    // test the event
+    let at_path = ["variable", var_a.as_str(), "set_update_fn_constant"];
+    let event = mk_model_event(&at_path, Some("true"));
+    let result = model.perform_event(&event, &at_path).unwrap();
+
+    // check that update fn was set correctly, and test the reverse event (restoring the
+    // original expression)
+    assert_eq!(model.get_update_fn_string(&var_a).unwrap(), "true");
+    let reverse_at_path = ["variable", var_a.as_str(), "set_update_fn"];
+    check_reverse(&mut model, &model_orig, result, &reverse_at_path);
+
+    // setting the same constant again does nothing
+    model.set_update_fn_constant(&var_a, true).unwrap();
+    let result = model.perform_event(&event, &at_path).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+}
+
+#[test]
+/// Test resetting variable's update function via event.
+fn test_clear_update_fn() {
+    let variables = vec![("a", "a_name"), ("b", "b_name")];
+    let mut model = ModelState::new_with_vars(variables).unwrap();
+    let var_a = model.get_var_id("a").unwrap();
+    model.set_update_fn(&var_a, "a => b").unwrap();
+    let model_orig = model.clone();
+
+    // test the event
+    let at_path = ["variable", var_a.as_str(), "clear_update_fn"];
+    let event = mk_model_event(&at_path, None);
+    let result = model.perform_event(&event, &at_path).unwrap();
+
+    // check that update fn was reset, and test the reverse event (restoring the original
+    // expression)
+    assert_eq!(model.get_update_fn_string(&var_a).unwrap(), "");
+    let reverse_at_path = ["variable", var_a.as_str(), "set_update_fn"];
+    check_reverse(&mut model, &model_orig, result, &reverse_at_path);
+
+    // clearing an already empty function does nothing
+    model.clear_update_fn(&var_a).unwrap();
+    let result = model.perform_event(&event, &at_path).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+}
+
+#[test]
+/// Test marking variable as an input via event.
+fn test_set_var_input() {
+    let variables = vec![("a", "a_name"), ("b", "b_name")];
+    let mut model = ModelState::new_with_vars(variables).unwrap();
+    let var_a = model.get_var_id("a").unwrap();
+    let model_orig = model.clone();
+
+    // test the event (setting the same value again does nothing)
+    let at_path = ["variable", var_a.as_str(), "set_var_input"];
+    let event = mk_model_event(&at_path, Some("false"));
+    let result = model.perform_event(&event, &at_path).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+    let event = mk_model_event(&at_path, Some("true"));
+    let result = model.perform_event(&event, &at_path).unwrap();
+
+    // check that variable is an input, and test the reverse event
+    assert!(model.get_variable(&var_a).unwrap().is_input());
+    check_reverse(&mut model, &model_orig, result, &at_path);
+}
+
 #[test]
 /// Test that several kinds of invalid operations fail successfully.
 fn test_invalid_var_events() {
@@ -220,6 +315,47 @@ fn test_change_reg_sign_essentiality() {
     check_reverse(&mut model, &model_orig, result, &reverse_at_path);
 }
 
+#[test]
+/// Test changing regulation's annotation via event (and that it is kept when other
+/// properties of the regulation change).
+fn test_set_reg_annotation() {
+    let variables = vec![("a", "a_name"), ("b", "b_name")];
+    let mut model = ModelState::new_with_vars(variables).unwrap();
+    model.add_regulation_by_str("a -> b").unwrap();
+
+    // test event for changing regulation's annotation
+    let at_path = ["regulation", "a", "b", "set_annotation"];
+    let event = mk_model_event(&at_path, Some("Doe et al. (2020)"));
+    let result = model.perform_event(&event, &at_path).unwrap();
+    let reg = model.get_regulation_by_str("a", "b").unwrap();
+    assert_eq!(reg.get_annotation(), "Doe et al. (2020)");
+    assert_eq!(model.num_regulations(), 1);
+
+    // changing the sign keeps the annotation
+    model
+        .change_regulation_sign_by_str("a", "b", &Monotonicity::Inhibition)
+        .unwrap();
+    let reg = model.get_regulation_by_str("a", "b").unwrap();
+    assert_eq!(reg.get_annotation(), "Doe et al. (2020)");
+
+    // annotations are not part of the model's equality, so check the reverse event directly
+    let Consumed::Reversible {
+        perform_reverse: (_, reverse),
+        ..
+    } = result
+    else {
+        panic!("Setting the annotation should be a reversible event.")
+    };
+    model.perform_event(&reverse, &at_path).unwrap();
+    let reg = model.get_regulation_by_str("a", "b").unwrap();
+    assert_eq!(reg.get_annotation(), "");
+
+    // setting the same annotation again does not change anything
+    let event = mk_model_event(&at_path, Some(""));
+    let result = model.perform_event(&event, &at_path).unwrap();
+    assert!(matches!(result, Consumed::NoChange));
+}
+
 #[test]
 /// Test removing regulation via (raw) event.
 fn test_remove_reg_simple() {
@@ -259,6 +395,32 @@ fn test_change_position() {
     check_reverse(&mut model, &model_orig, result, &at_path);
 }
 
+#[test]
+/// Test automatically arranging a layout via event.
+fn test_auto_arrange_layout() {
+    let variables = vec![("a", "a"), ("b", "b"), ("c", "c")];
+    let mut model = ModelState::new_with_vars(variables).unwrap();
+    model
+        .add_multiple_regulations(vec!["a -> b", "b -| c"])
+        .unwrap();
+    let layout_id = ModelState::get_default_layout_id();
+    let model_orig = model.clone();
+
+    // test auto-arrange event (all nodes are initially at the same position)
+    let at_path = ["layout", layout_id.as_str(), "auto_arrange"];
+    let event = mk_model_event(&at_path, None);
+    let result = model.perform_event(&event, &at_path).unwrap();
+
+    // check the nodes were moved apart, and test reverse event
+    let var_a = model.get_var_id("a").unwrap();
+    let var_b = model.get_var_id("b").unwrap();
+    let position_a = model.get_node_position(&layout_id, &var_a).unwrap();
+    let position_b = model.get_node_position(&layout_id, &var_b).unwrap();
+    assert_ne!(position_a, position_b);
+    let reverse_at_path = ["layout", layout_id.as_str(), "update_all_positions"];
+    check_reverse(&mut model, &model_orig, result, &reverse_at_path);
+}
+
 #[test]
 /// Test changing monotonicity and essentiality of uninterpreted function's argument via event.
 fn test_change_fn_arg_monotonicity_essentiality() {
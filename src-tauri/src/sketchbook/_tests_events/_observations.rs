@@ -132,6 +132,64 @@ fn test_remove_observations() {
     assert_eq!(modified_dataset.get_obs_id(0).as_str(), "o2");
 }
 
+#[test]
+/// Test adding and removing batches of observations via events, including batches that
+/// are invalid only partially (these must not change the dataset at all).
+fn test_observation_batches() {
+    let d1 = prepare_dataset_3v_2o();
+    let mut manager = ObservationManager::from_datasets(vec![("d1", d1)]).unwrap();
+    let d1_id = manager.get_dataset_id("d1").unwrap();
+    let manager_orig = manager.clone();
+
+    // add a batch of two observations
+    let obs3 = Observation::try_from_str("1*1", "o3").unwrap();
+    let obs4 = Observation::try_from_str("010", "o4").unwrap();
+    let batch = vec![
+        ObservationData::from_obs(&obs3, &d1_id),
+        ObservationData::from_obs(&obs4, &d1_id),
+    ];
+    let payload = serde_json::to_string(&batch).unwrap();
+    let full_path = ["observations", "d1", "add_batch"];
+    let event = Event::build(&full_path, Some(&payload));
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    let modified_dataset = manager.get_dataset_by_str("d1").unwrap();
+    assert_eq!(modified_dataset.num_observations(), 4);
+    assert_eq!(modified_dataset.get_obs_id(3).as_str(), "o4");
+    check_reverse(&mut manager, &manager_orig, result, &["d1", "set_content"]);
+
+    // adding a batch with a single invalid observation (already used ID) fails as a whole
+    let obs_duplicate = Observation::try_from_str("111", "o1").unwrap();
+    let batch = vec![
+        ObservationData::from_obs(&obs3, &d1_id),
+        ObservationData::from_obs(&obs_duplicate, &d1_id),
+    ];
+    let payload = serde_json::to_string(&batch).unwrap();
+    let event = Event::build(&full_path, Some(&payload));
+    assert!(manager.perform_event(&event, &full_path[1..]).is_err());
+    assert_eq!(manager, manager_orig);
+
+    // remove a batch of observations (in a different order than they are in the dataset)
+    let payload = serde_json::to_string(&["o2", "o1"]).unwrap();
+    let full_path = ["observations", "d1", "remove_batch"];
+    let event = Event::build(&full_path, Some(&payload));
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    assert_eq!(
+        manager.get_dataset_by_str("d1").unwrap().num_observations(),
+        0
+    );
+    // reverse restores the original observations, including their order
+    check_reverse(&mut manager, &manager_orig, result, &["d1", "set_content"]);
+    let restored_dataset = manager.get_dataset_by_str("d1").unwrap();
+    assert_eq!(restored_dataset.get_obs_id(0).as_str(), "o1");
+    assert_eq!(restored_dataset.get_obs_id(1).as_str(), "o2");
+
+    // removing a batch with a single invalid (non-existing) ID fails as a whole
+    let payload = serde_json::to_string(&["o1", "o5"]).unwrap();
+    let event = Event::build(&full_path, Some(&payload));
+    assert!(manager.perform_event(&event, &full_path[1..]).is_err());
+    assert_eq!(manager, manager_orig);
+}
+
 #[test]
 /// Test setting various dataset fields via events.
 fn test_set_observation_fields() {
@@ -165,6 +223,50 @@ fn test_set_observation_fields() {
         result,
         &["d1", "o1", "set_data"],
     );
+
+    // 3) event to set observation's metadata (via its "data")
+    let obs_original = manager.get_obs_by_str("d1", "o1").unwrap();
+    let new_obs = obs_original.clone().with_metadata("experiment 7");
+    let new_obs_data = ObservationData::from_obs(&new_obs, &d1_id);
+    let full_path = ["observations", "d1", "o1", "set_data"];
+    let event = Event::build(&full_path, Some(&new_obs_data.to_json_str()));
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    let obs_modified = manager.get_obs_by_str("d1", "o1").unwrap();
+    assert_eq!(obs_modified.get_metadata(), Some("experiment 7"));
+    check_reverse(
+        &mut manager,
+        &manager_orig,
+        result,
+        &["d1", "o1", "set_data"],
+    );
+}
+
+#[test]
+/// Test exporting a dataset to a CSV file via events, with and without observation IDs.
+fn test_export_dataset() {
+    let d1 = prepare_dataset_3v_2o();
+    let mut manager = ObservationManager::from_datasets(vec![("d1", d1)]).unwrap();
+    let csv_path = std::env::temp_dir().join(format!(
+        "sketchbook_test_export_dataset_{}.csv",
+        std::process::id()
+    ));
+    let path_json = serde_json::to_string(&csv_path.to_string_lossy()).unwrap();
+    let full_path = ["observations", "d1", "export"];
+
+    // observation IDs are included by default
+    let payload = format!("{{\"path\":{path_json}}}");
+    let event = Event::build(&full_path, Some(payload.as_str()));
+    manager.perform_event(&event, &full_path[1..]).unwrap();
+    let exported = std::fs::read_to_string(&csv_path).unwrap();
+    assert_eq!(exported, "ID,a,b,c\no1,*,1,1\no2,0,0,0\n");
+
+    let payload = format!("{{\"path\":{path_json},\"include_ids\":false}}");
+    let event = Event::build(&full_path, Some(payload.as_str()));
+    manager.perform_event(&event, &full_path[1..]).unwrap();
+    let exported = std::fs::read_to_string(&csv_path).unwrap();
+    assert_eq!(exported, "a,b,c\n*,1,1\n0,0,0\n");
+
+    std::fs::remove_file(&csv_path).unwrap();
 }
 
 #[test]
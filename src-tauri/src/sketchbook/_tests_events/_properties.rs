@@ -0,0 +1,49 @@
+use crate::app::event::Event;
+use crate::app::state::SessionState;
+use crate::sketchbook::_tests_events::check_reverse;
+use crate::sketchbook::properties::{DynProperty, PropertyManager, StatProperty};
+
+#[test]
+/// Test duplicating dynamic and static properties via events.
+fn test_duplicate_properties() {
+    let mut manager = PropertyManager::new_empty();
+    let dyn_prop = DynProperty::try_mk_generic("p", "AG EF x").unwrap();
+    manager.add_dynamic_by_str("p", dyn_prop.clone()).unwrap();
+    let stat_prop = StatProperty::try_mk_generic("q", "f(1) | g(0)").unwrap();
+    manager.add_static_by_str("q", stat_prop.clone()).unwrap();
+    let manager_orig = manager.clone();
+
+    // perform dynamic property duplicate event
+    let full_path = ["properties", "dynamic", "p", "duplicate"];
+    let event = Event::build(&full_path, None);
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    // check the copy was added (with modified name), test reverse action
+    assert_eq!(manager.num_dyn_properties(), 2);
+    let copy_id = manager.get_dyn_prop_id("p_1").unwrap();
+    let copy = manager.get_dyn_prop(&copy_id).unwrap();
+    assert_eq!(copy.get_name(), "p (copy)");
+    assert_eq!(copy.get_prop_data(), dyn_prop.get_prop_data());
+    check_reverse(
+        &mut manager,
+        &manager_orig,
+        result,
+        &["dynamic", "p_1", "remove"],
+    );
+
+    // perform static property duplicate event
+    let full_path = ["properties", "static", "q", "duplicate"];
+    let event = Event::build(&full_path, None);
+    let result = manager.perform_event(&event, &full_path[1..]).unwrap();
+    // check the copy was added (with modified name), test reverse action
+    assert_eq!(manager.num_stat_properties(), 2);
+    let copy_id = manager.get_stat_prop_id("q_1").unwrap();
+    let copy = manager.get_stat_prop(&copy_id).unwrap();
+    assert_eq!(copy.get_name(), "q (copy)");
+    assert_eq!(copy.get_prop_data(), stat_prop.get_prop_data());
+    check_reverse(
+        &mut manager,
+        &manager_orig,
+        result,
+        &["static", "q_1", "remove"],
+    );
+}
@@ -0,0 +1,102 @@
+use crate::app::event::Event;
+use crate::app::state::{Consumed, SessionState};
+use crate::sketchbook::_tests_events::check_reverse;
+use crate::sketchbook::data_structs::ChangeIdData;
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::observations::Dataset;
+use crate::sketchbook::properties::DynProperty;
+use crate::sketchbook::{IssueSeverity, Sketch};
+use std::collections::BTreeMap;
+
+#[test]
+/// Test renaming several variables at once via event.
+fn test_rename_variables() {
+    let mut sketch = Sketch::from_aeon("a -> b\nb -| a\nc -> a\n$a: !b & c\n$b: a").unwrap();
+    let dataset = Dataset::new_empty("d", vec!["a", "b", "c"]).unwrap();
+    sketch
+        .observations
+        .add_dataset_by_str("d", dataset)
+        .unwrap();
+    let dyn_prop = DynProperty::try_mk_generic("p", "AG (a => EF c)").unwrap();
+    sketch
+        .properties
+        .add_dynamic_by_str("dyn", dyn_prop)
+        .unwrap();
+    let sketch_orig = sketch.clone();
+
+    // perform the rename event (swapping `a` and `b`, renaming `c` to `x`)
+    let changes = vec![
+        ChangeIdData::new("a", "b"),
+        ChangeIdData::new("b", "a"),
+        ChangeIdData::new("c", "x"),
+    ];
+    let payload = serde_json::to_string(&changes).unwrap();
+    let full_path = ["sketch", "rename_variables"];
+    let event = Event::build(&full_path, Some(payload.as_str()));
+    let result = sketch.perform_event(&event, &full_path[1..]).unwrap();
+
+    // check variables were renamed everywhere, test reverse action
+    assert!(sketch.model.is_valid_var_id_str("x"));
+    assert!(!sketch.model.is_valid_var_id_str("c"));
+    let dataset = sketch.observations.get_dataset_by_str("d").unwrap();
+    assert_eq!(dataset.variable_names(), vec!["b", "a", "x"]);
+    let prop_id = sketch.properties.get_dyn_prop_id("dyn").unwrap();
+    let prop = sketch.properties.get_dyn_prop(&prop_id).unwrap();
+    let expected_prop = DynProperty::try_mk_generic("p", "AG (b => EF x)").unwrap();
+    assert_eq!(prop, &expected_prop);
+    check_reverse(&mut sketch, &sketch_orig, result, &["rename_variables"]);
+}
+
+#[test]
+/// Test that renaming the same variable twice via event fails (without changing the sketch).
+fn test_rename_variables_duplicate() {
+    let mut sketch = Sketch::from_aeon("a -> b\nb -| a").unwrap();
+    let sketch_orig = sketch.clone();
+
+    let changes = vec![ChangeIdData::new("a", "x"), ChangeIdData::new("a", "y")];
+    let payload = serde_json::to_string(&changes).unwrap();
+    let full_path = ["sketch", "rename_variables"];
+    let event = Event::build(&full_path, Some(payload.as_str()));
+    assert!(sketch.perform_event(&event, &full_path[1..]).is_err());
+    assert_eq!(sketch, sketch_orig);
+}
+
+#[test]
+/// Test setting severity of issues regarding declared regulators via event.
+fn test_set_regulator_check_severity() {
+    // `b` has a declared regulator, but a constant update function
+    let mut sketch = Sketch::from_aeon("a -> b").unwrap();
+    let var_b = VarId::new("b").unwrap();
+    sketch.model.set_update_fn_constant(&var_b, true).unwrap();
+    assert!(sketch.assert_consistency().is_ok());
+
+    let full_path = ["sketch", "set_regulator_check_severity"];
+    let event = Event::build(&full_path, Some("\"Error\""));
+    let result = sketch.perform_event(&event, &full_path[1..]).unwrap();
+    assert!(matches!(result, Consumed::Irreversible { .. }));
+    assert_eq!(sketch.get_regulator_check_severity(), IssueSeverity::Error);
+    assert!(sketch.assert_consistency().is_err());
+}
+
+#[test]
+/// Test setting the initial subspace via event.
+fn test_set_initial_subspace() {
+    let mut sketch = Sketch::from_aeon("a -> b\nb -> a").unwrap();
+    let sketch_orig = sketch.clone();
+
+    // perform the event, check the subspace was set, and test reverse action
+    let full_path = ["sketch", "set_initial_subspace"];
+    let event = Event::build(&full_path, Some("{\"a\": true, \"b\": false}"));
+    let result = sketch.perform_event(&event, &full_path[1..]).unwrap();
+    let expected = BTreeMap::from([
+        (VarId::new("a").unwrap(), true),
+        (VarId::new("b").unwrap(), false),
+    ]);
+    assert_eq!(sketch.get_initial_subspace(), &expected);
+    check_reverse(&mut sketch, &sketch_orig, result, &["set_initial_subspace"]);
+
+    // subspace with invalid variables is rejected
+    let event = Event::build(&full_path, Some("{\"c\": true}"));
+    assert!(sketch.perform_event(&event, &full_path[1..]).is_err());
+    assert_eq!(sketch, sketch_orig);
+}
@@ -4,6 +4,10 @@ use crate::app::state::{Consumed, SessionState};
 mod _model;
 /// **(internal)** Tests for the event-based API of `ObservationManager`.
 mod _observations;
+/// **(internal)** Tests for the event-based API of `PropertyManager`.
+mod _properties;
+/// **(internal)** Tests for the event-based API of `Sketch`.
+mod _sketch;
 
 /// Given a state of a manager class *after* a particular event is performed (`state_after_event`),
 /// check that by performing a reverse event, we get precisely the original state (`orig_state`).
@@ -12,7 +16,8 @@ mod _observations;
 /// `ObservationManager`, or `PropertyManager`.
 ///
 /// - `result` is the result of the original event (carrying the reverse variant)
-/// - `at_path` is the relative path for the reverse event
+/// - `at_path` is the relative path for the reverse event (for a group of reverse events, the
+///   relative path of the first one, which determines the prefix stripped from all of them)
 fn check_reverse<T: SessionState + std::fmt::Debug + PartialEq>(
     state_after_event: &mut T,
     orig_state: &T,
@@ -27,6 +32,22 @@ fn check_reverse<T: SessionState + std::fmt::Debug + PartialEq>(
             state_after_event.perform_event(&reverse, at_path).unwrap();
             assert_eq!(state_after_event, orig_state);
         }
+        Consumed::ReversibleGroup {
+            perform_reverse: (_, reverse),
+            ..
+        } => {
+            let prefix_len = reverse.events[0].path.len() - at_path.len();
+            for event in reverse.events {
+                let event_path: Vec<&str> = event.path[prefix_len..]
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect();
+                state_after_event
+                    .perform_event(&event, &event_path)
+                    .unwrap();
+            }
+            assert_eq!(state_after_event, orig_state);
+        }
         _ => panic!(),
     }
 }
@@ -1,5 +1,6 @@
 use crate::sketchbook::model::{Essentiality, Monotonicity};
 use biodivine_lib_param_bn::Monotonicity as Lib_Pbn_Monotonicity;
+use biodivine_lib_param_bn::{BooleanNetwork, RegulatoryGraph};
 
 /// Utility to convert monotonicity enum used by `lib_param_bn` into the type used here.
 ///
@@ -51,3 +52,53 @@ pub fn essentiality_to_bool(essentiality: Essentiality) -> bool {
         Essentiality::False => false,
     }
 }
+
+/// Check that a `BooleanNetwork` is fully specified, i.e., each variable has an update function
+/// and there are no parameters (uninterpreted function symbols) in these functions.
+pub fn assert_bn_fully_specified(bn: &BooleanNetwork) -> Result<(), String> {
+    for var in bn.variables() {
+        let var_name = bn.get_variable_name(var);
+        let Some(update_fn) = bn.get_update_function(var) else {
+            return Err(format!("Update function of `{var_name}` is not specified."));
+        };
+        if !update_fn.collect_parameters().is_empty() {
+            return Err(format!(
+                "Update function of `{var_name}` contains uninterpreted function symbols."
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Make a copy of a `BooleanNetwork` with the same variables, parameters and update functions,
+/// but with all regulations made non-observable and with unknown monotonicity. This way, the
+/// regulations do not impose any restrictions on the update functions.
+pub fn bn_with_plain_regulations(bn: &BooleanNetwork) -> BooleanNetwork {
+    let var_names = bn
+        .variables()
+        .map(|v| bn.get_variable_name(v).clone())
+        .collect();
+    let mut graph = RegulatoryGraph::new(var_names);
+    for reg in bn.as_graph().regulations() {
+        let regulator = bn.get_variable_name(reg.regulator);
+        let target = bn.get_variable_name(reg.target);
+        // this can't fail, since the regulation is valid in the original network
+        graph
+            .add_regulation(regulator, target, false, None)
+            .unwrap();
+    }
+
+    let mut plain_bn = BooleanNetwork::new(graph);
+    for param in bn.parameters() {
+        let param = bn.get_parameter(param);
+        plain_bn
+            .add_parameter(param.get_name(), param.get_arity())
+            .unwrap();
+    }
+    // variables and parameters have the same IDs in both networks
+    for var in bn.variables() {
+        let update_fn = bn.get_update_function(var).clone();
+        plain_bn.set_update_function(var, update_fn).unwrap();
+    }
+    plain_bn
+}
@@ -0,0 +1,22 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Kind of a sketch component whose annotation matched a search query.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AnnotationComponent {
+    Sketch,
+    Variable,
+    UninterpretedFn,
+}
+
+/// Structure for sending data about a single annotation matching a search query to the
+/// frontend. The `id` is the ID of the annotated component (`None` for the annotation of the
+/// whole sketch), and `snippet` is a short part of the annotation around the match.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationHit {
+    pub component: AnnotationComponent,
+    pub id: Option<String>,
+    pub snippet: String,
+}
+
+impl JsonSerde<'_> for AnnotationHit {}
@@ -0,0 +1,55 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure for sending a summary of how completely a model is specified to the frontend.
+///
+/// It counts the signs (monotonicity) and essentialities of regulations and of arguments of
+/// uninterpreted functions that are still `Unknown`. These are precisely the properties the
+/// inference leaves unconstrained. The `percent_specified` gives the ratio of all signs and
+/// essentialities that are specified (or 100 if there is nothing to specify).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompletenessReport {
+    pub num_regulations: usize,
+    pub num_regulations_unknown_sign: usize,
+    pub num_regulations_unknown_essentiality: usize,
+    pub num_fn_args: usize,
+    pub num_fn_args_unknown_sign: usize,
+    pub num_fn_args_unknown_essentiality: usize,
+    pub percent_specified: f64,
+}
+
+impl JsonSerde<'_> for CompletenessReport {}
+
+impl CompletenessReport {
+    /// Create new `CompletenessReport` from the given counts, computing the percentage of
+    /// specified signs and essentialities.
+    pub fn new(
+        num_regulations: usize,
+        num_regulations_unknown_sign: usize,
+        num_regulations_unknown_essentiality: usize,
+        num_fn_args: usize,
+        num_fn_args_unknown_sign: usize,
+        num_fn_args_unknown_essentiality: usize,
+    ) -> CompletenessReport {
+        // each regulation and argument has both sign and essentiality to be specified
+        let num_total = 2 * (num_regulations + num_fn_args);
+        let num_unknown = num_regulations_unknown_sign
+            + num_regulations_unknown_essentiality
+            + num_fn_args_unknown_sign
+            + num_fn_args_unknown_essentiality;
+        let percent_specified = if num_total == 0 {
+            100.0
+        } else {
+            100.0 * (num_total - num_unknown) as f64 / num_total as f64
+        };
+        CompletenessReport {
+            num_regulations,
+            num_regulations_unknown_sign,
+            num_regulations_unknown_essentiality,
+            num_fn_args,
+            num_fn_args_unknown_sign,
+            num_fn_args_unknown_essentiality,
+            percent_specified,
+        }
+    }
+}
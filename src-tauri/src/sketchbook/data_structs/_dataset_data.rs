@@ -1,13 +1,14 @@
 use crate::sketchbook::data_structs::ObservationData;
 use crate::sketchbook::ids::DatasetId;
-use crate::sketchbook::observations::{Dataset, Observation};
+use crate::sketchbook::observations::{Dataset, DatasetOrdering, Observation};
 use crate::sketchbook::JsonSerde;
 use serde::{Deserialize, Serialize};
 
 /// Structure for sending data about `Dataset` .
 ///
 /// Some fields simplified compared to original typesafe versions (e.g., pure `Strings` are used
-/// instead of more complex typesafe structs) to allow for easier (de)serialization.
+/// instead of more complex typesafe structs) to allow for easier (de)serialization. If the
+/// `ordering` is missing (e.g., in older sketch files), the dataset is considered a time series.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct DatasetData {
     pub name: String,
@@ -15,10 +16,12 @@ pub struct DatasetData {
     pub annotation: String,
     pub observations: Vec<ObservationData>,
     pub variables: Vec<String>,
+    #[serde(default)]
+    pub ordering: DatasetOrdering,
 }
 
 /// Structure for sending *metadata* about `Dataset`. This includes name, id, variable names,
-/// but excludes all observations.
+/// and ordering, but excludes all observations.
 ///
 /// Some fields simplified compared to original typesafe versions (e.g., pure `Strings` are used
 /// instead of more complex typesafe structs) to allow for easier (de)serialization.
@@ -28,6 +31,8 @@ pub struct DatasetMetaData {
     pub id: String,
     pub annotation: String,
     pub variables: Vec<String>,
+    #[serde(default)]
+    pub ordering: DatasetOrdering,
 }
 
 impl JsonSerde<'_> for DatasetData {}
@@ -50,6 +55,7 @@ impl DatasetData {
             annotation,
             observations,
             variables,
+            ordering: dataset.get_ordering(),
         }
     }
 
@@ -62,7 +68,9 @@ impl DatasetData {
             .map(|o| o.to_observation())
             .collect::<Result<Vec<Observation>, String>>()?;
         let variables = self.variables.iter().map(|v| v.as_str()).collect();
-        Ok(Dataset::new(&self.name, observations, variables)?.with_annotation(&self.annotation))
+        Ok(Dataset::new(&self.name, observations, variables)?
+            .with_annotation(&self.annotation)
+            .with_ordering(self.ordering))
     }
 }
 
@@ -75,6 +83,7 @@ impl DatasetMetaData {
             id: id.to_string(),
             annotation: dataset.get_annotation().to_string(),
             variables,
+            ordering: dataset.get_ordering(),
         }
     }
 }
@@ -83,7 +92,8 @@ impl DatasetMetaData {
 mod tests {
     use crate::sketchbook::data_structs::DatasetData;
     use crate::sketchbook::ids::DatasetId;
-    use crate::sketchbook::observations::{Dataset, Observation};
+    use crate::sketchbook::observations::{Dataset, DatasetOrdering, Observation};
+    use crate::sketchbook::JsonSerde;
 
     #[test]
     /// Test converting between `Dataset` and `DatasetData`.
@@ -96,5 +106,18 @@ mod tests {
         let dataset_after = dataset_data.to_dataset().unwrap();
 
         assert_eq!(dataset_before, dataset_after);
+
+        // the ordering is kept as well
+        let dataset_before = dataset_before.with_ordering(DatasetOrdering::Unordered);
+        let dataset_data = DatasetData::from_dataset(&dataset_id, &dataset_before);
+        assert_eq!(dataset_data.to_dataset().unwrap(), dataset_before);
+    }
+
+    #[test]
+    /// Test that datasets without ordering (older format) are loaded as time series.
+    fn test_missing_ordering() {
+        let json = r#"{"name":"d","id":"d","annotation":"","observations":[],"variables":["a"]}"#;
+        let dataset_data = DatasetData::from_json_str(json).unwrap();
+        assert_eq!(dataset_data.ordering, DatasetOrdering::TimeSeries);
     }
 }
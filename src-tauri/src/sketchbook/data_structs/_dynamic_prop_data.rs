@@ -1,7 +1,7 @@
 use crate::sketchbook::ids::{DatasetId, DynPropertyId, ObservationId};
 use crate::sketchbook::properties::dynamic_props;
 use crate::sketchbook::JsonSerde;
-use dynamic_props::{DynProperty, DynPropertyType};
+use dynamic_props::{AbsenceMode, DynProperty, DynPropertyType};
 use serde::{Deserialize, Serialize};
 
 /// Simplified variant to carry data regarding [dynamic_props::GenericDynProp] dynamic property.
@@ -46,6 +46,35 @@ pub struct HasAttractorData {
     pub observation: Option<String>,
 }
 
+/// Simplified variant to carry data regarding [dynamic_props::ObservationsReachable] dynamic property.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ObservationsReachableData {
+    pub dataset: Option<String>,
+    pub min_count: usize,
+}
+
+/// Simplified variant to carry data regarding [dynamic_props::SteadyState] dynamic property.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SteadyStateData {
+    pub dataset: Option<String>,
+    pub observation: Option<String>,
+}
+
+/// Simplified variant to carry data regarding [dynamic_props::ReachableFromDataset] dynamic property.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReachableFromDatasetData {
+    pub initial_dataset: Option<String>,
+    pub target_dataset: Option<String>,
+}
+
+/// Simplified variant to carry data regarding [dynamic_props::ObservationAbsent] dynamic property.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ObservationAbsentData {
+    pub dataset: Option<String>,
+    pub observation: Option<String>,
+    pub mode: AbsenceMode,
+}
+
 /// Enum covering all variants of dynamic properties and their necessary data.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "variant")]
@@ -56,6 +85,10 @@ pub enum DynPropertyTypeData {
     ExistsTrajectory(ExistsTrajectoryData),
     AttractorCount(AttractorCountData),
     HasAttractor(HasAttractorData),
+    ObservationsReachable(ObservationsReachableData),
+    SteadyState(SteadyStateData),
+    ReachableFromDataset(ReachableFromDatasetData),
+    ObservationAbsent(ObservationAbsentData),
 }
 
 /// Structure for sending data about dynamic properties to the frontend.
@@ -126,6 +159,29 @@ impl DynPropertyData {
                     maximal: p.maximal,
                 })
             }
+            DynPropertyType::ObservationsReachable(p) => {
+                DynPropertyTypeData::ObservationsReachable(ObservationsReachableData {
+                    dataset: p.dataset.as_ref().map(|i| i.to_string()),
+                    min_count: p.min_count,
+                })
+            }
+            DynPropertyType::SteadyState(p) => DynPropertyTypeData::SteadyState(SteadyStateData {
+                dataset: p.dataset.as_ref().map(|i| i.to_string()),
+                observation: p.observation.as_ref().map(|o| o.to_string()),
+            }),
+            DynPropertyType::ReachableFromDataset(p) => {
+                DynPropertyTypeData::ReachableFromDataset(ReachableFromDatasetData {
+                    initial_dataset: p.initial_dataset.as_ref().map(|i| i.to_string()),
+                    target_dataset: p.target_dataset.as_ref().map(|i| i.to_string()),
+                })
+            }
+            DynPropertyType::ObservationAbsent(p) => {
+                DynPropertyTypeData::ObservationAbsent(ObservationAbsentData {
+                    dataset: p.dataset.as_ref().map(|i| i.to_string()),
+                    observation: p.observation.as_ref().map(|o| o.to_string()),
+                    mode: p.mode,
+                })
+            }
         };
         Self::new_raw(id.as_str(), name, variant, annot)
     }
@@ -171,6 +227,39 @@ impl DynPropertyData {
                 DynProperty::try_mk_attractor_count(name, p.minimal, p.maximal)?
                     .with_annotation(annot)
             }
+            DynPropertyTypeData::ObservationsReachable(p) => {
+                let dataset = p.dataset.as_ref().and_then(|t| DatasetId::new(t).ok());
+                DynProperty::try_mk_obs_reachable(name, dataset, p.min_count)?
+                    .with_annotation(annot)
+            }
+            DynPropertyTypeData::SteadyState(p) => {
+                let dataset = p.dataset.as_ref().and_then(|t| DatasetId::new(t).ok());
+                let obs = p
+                    .observation
+                    .as_ref()
+                    .and_then(|t| ObservationId::new(t).ok());
+                DynProperty::mk_steady_state(name, dataset, obs).with_annotation(annot)
+            }
+            DynPropertyTypeData::ReachableFromDataset(p) => {
+                let initial = p
+                    .initial_dataset
+                    .as_ref()
+                    .and_then(|t| DatasetId::new(t).ok());
+                let target = p
+                    .target_dataset
+                    .as_ref()
+                    .and_then(|t| DatasetId::new(t).ok());
+                DynProperty::mk_reachable_from_dataset(name, initial, target).with_annotation(annot)
+            }
+            DynPropertyTypeData::ObservationAbsent(p) => {
+                let dataset = p.dataset.as_ref().and_then(|t| DatasetId::new(t).ok());
+                let obs = p
+                    .observation
+                    .as_ref()
+                    .and_then(|t| ObservationId::new(t).ok());
+                DynProperty::mk_observation_absent(name, dataset, obs, p.mode)
+                    .with_annotation(annot)
+            }
         };
         Ok(property)
     }
@@ -0,0 +1,44 @@
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+use std::ops::Add;
+
+/// Structure summarizing the size of a property after it is encoded into a formula (HCTL for
+/// dynamic properties, FOL for static properties). It is computed purely syntactically, before
+/// any symbolic structures are built, and can be used to spot properties that blow up.
+///
+/// It counts logical operators (including quantifiers), state variables needed for evaluation
+/// (HCTL state variables or quantified FOL variables), and literals (atomic propositions or
+/// variables), most of which usually come from expanding observations of datasets.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct EncodingMetrics {
+    pub num_operators: usize,
+    pub num_state_vars: usize,
+    pub num_literals: usize,
+}
+
+impl JsonSerde<'_> for EncodingMetrics {}
+
+impl EncodingMetrics {
+    /// Create new `EncodingMetrics` from the given counts.
+    pub fn new(num_operators: usize, num_state_vars: usize, num_literals: usize) -> Self {
+        EncodingMetrics {
+            num_operators,
+            num_state_vars,
+            num_literals,
+        }
+    }
+}
+
+impl Add for EncodingMetrics {
+    type Output = EncodingMetrics;
+
+    /// Combine metrics of two independently evaluated parts of a property. Operators and literals
+    /// are summed, while state variables can be shared by both parts (so the maximum is taken).
+    fn add(self, other: EncodingMetrics) -> EncodingMetrics {
+        EncodingMetrics {
+            num_operators: self.num_operators + other.num_operators,
+            num_state_vars: usize::max(self.num_state_vars, other.num_state_vars),
+            num_literals: self.num_literals + other.num_literals,
+        }
+    }
+}
@@ -16,8 +16,17 @@ pub struct ChangeArgEssentialData {
     pub essentiality: Essentiality,
 }
 
+/// Structure for sending data about reordering of uninterpreted fn's arguments. The `i`-th new
+/// argument is the `permutation[i]`-th original argument.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReorderArgsData {
+    pub fn_id: String,
+    pub permutation: Vec<usize>,
+}
+
 impl JsonSerde<'_> for ChangeArgMonotoneData {}
 impl JsonSerde<'_> for ChangeArgEssentialData {}
+impl JsonSerde<'_> for ReorderArgsData {}
 
 impl ChangeArgMonotoneData {
     /// Create new `ChangeArgMonotoneData` object given the arguments index and its new monotonicity.
@@ -32,3 +41,13 @@ impl ChangeArgEssentialData {
         ChangeArgEssentialData { idx, essentiality }
     }
 }
+
+impl ReorderArgsData {
+    /// Create new `ReorderArgsData` object given the function's ID and the permutation.
+    pub fn new(fn_id: &str, permutation: &[usize]) -> ReorderArgsData {
+        ReorderArgsData {
+            fn_id: fn_id.to_string(),
+            permutation: permutation.to_vec(),
+        }
+    }
+}
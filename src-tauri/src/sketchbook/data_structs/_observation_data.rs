@@ -5,9 +5,11 @@ use serde::{Deserialize, Serialize};
 
 /// Structure for sending data about `Observation` to the frontend.
 ///
-/// Contains also ID of the corresponding dataset. Some fields are further simplified compared to
-/// original typesafe versions (e.g., pure `Strings` are used instead of more complex typesafe
-/// structs) to allow for easier (de)serialization.
+/// Contains also ID of the corresponding dataset, an optional weight of the observation
+/// (`None` if the weight is not specified, i.e., it is the default 1.0), and optional metadata
+/// describing its provenance. Some fields are further simplified compared to original typesafe
+/// versions (e.g., pure `Strings` are used instead of more complex typesafe structs) to allow for
+/// easier (de)serialization.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ObservationData {
     pub id: String,
@@ -15,6 +17,10 @@ pub struct ObservationData {
     pub annotation: String,
     pub dataset: String,
     pub values: String,
+    #[serde(default)]
+    pub weight: Option<f64>,
+    #[serde(default)]
+    pub metadata: Option<String>,
 }
 
 impl JsonSerde<'_> for ObservationData {}
@@ -34,27 +40,37 @@ impl ObservationData {
             annotation: annot.to_string(),
             dataset: dataset_id.to_string(),
             values: values.to_string(),
+            weight: None,
+            metadata: None,
         }
     }
 
     /// Create new `ObservationData` instance given a reference to a observation, and ID of
     /// its dataset.
     pub fn from_obs(obs: &Observation, dataset_id: &DatasetId) -> ObservationData {
-        ObservationData::new(
+        let mut obs_data = ObservationData::new(
             obs.get_id().as_str(),
             obs.get_name(),
             obs.get_annotation(),
             dataset_id.as_str(),
             &obs.to_values_string(),
-        )
+        );
+        obs_data.weight = obs.has_weight().then(|| obs.get_weight());
+        obs_data.metadata = obs.get_metadata().map(|m| m.to_string());
+        obs_data
     }
 
     /// Extract the corresponding `Observation` from the `ObservationData`.
     /// Internal syntax check is done just to make sure that the data are valid.
     pub fn to_observation(&self) -> Result<Observation, String> {
-        Ok(Observation::try_from_str(&self.values.clone(), &self.id)?
+        let mut obs = Observation::try_from_str(&self.values.clone(), &self.id)?
             .with_name(&self.name)
-            .with_annotation(&self.annotation))
+            .with_annotation(&self.annotation);
+        obs.set_metadata(self.metadata.as_deref());
+        match self.weight {
+            Some(weight) => obs.with_weight(weight),
+            None => Ok(obs),
+        }
     }
 }
 
@@ -73,5 +89,20 @@ mod tests {
         let obs_after = obs_data.to_observation().unwrap();
 
         assert_eq!(obs_before, obs_after);
+
+        // weights are kept too
+        let obs_before = obs_before.with_weight(0.5).unwrap();
+        let obs_data = ObservationData::from_obs(&obs_before, &dataset_id);
+        assert_eq!(obs_data.weight, Some(0.5));
+        assert_eq!(obs_data.to_observation().unwrap(), obs_before);
+
+        // and so is the metadata
+        let obs_before = obs_before.with_metadata("experiment 42, 2024-03-01");
+        let obs_data = ObservationData::from_obs(&obs_before, &dataset_id);
+        assert_eq!(
+            obs_data.metadata.as_deref(),
+            Some("experiment 42, 2024-03-01")
+        );
+        assert_eq!(obs_data.to_observation().unwrap(), obs_before);
     }
 }
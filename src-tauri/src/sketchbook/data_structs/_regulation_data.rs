@@ -13,6 +13,8 @@ pub struct RegulationData {
     pub target: String,
     pub sign: Monotonicity,
     pub essential: Essentiality,
+    #[serde(default)]
+    pub annotation: String,
 }
 
 impl JsonSerde<'_> for RegulationData {}
@@ -30,9 +32,16 @@ impl RegulationData {
             target: target_id.to_string(),
             essential,
             sign,
+            annotation: String::new(),
         }
     }
 
+    /// Update the `annotation` property.
+    pub fn with_annotation(mut self, annotation: &str) -> RegulationData {
+        self.annotation = annotation.to_string();
+        self
+    }
+
     /// Create new `RegulationData` object given a `regulation`.
     pub fn from_reg(regulation: &Regulation) -> RegulationData {
         RegulationData::new(
@@ -41,6 +50,7 @@ impl RegulationData {
             *regulation.get_essentiality(),
             *regulation.get_sign(),
         )
+        .with_annotation(regulation.get_annotation())
     }
 
     /// Try to create new `RegulationData` object given a string encoding a regulation.
@@ -56,6 +66,7 @@ impl RegulationData {
             VarId::new(&self.target)?,
             self.essential,
             self.sign,
-        ))
+        )
+        .with_annotation(&self.annotation))
     }
 }
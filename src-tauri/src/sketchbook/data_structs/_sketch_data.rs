@@ -1,11 +1,45 @@
 use crate::sketchbook::data_structs::{DatasetData, DynPropertyData, ModelData, StatPropertyData};
+use crate::sketchbook::ids::VarId;
 use crate::sketchbook::model::ModelState;
 use crate::sketchbook::observations::ObservationManager;
 use crate::sketchbook::properties::PropertyManager;
 use crate::sketchbook::{JsonSerde, Sketch};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// **(internal)** Serialized names of all variants of dynamic properties.
+const DYN_PROPERTY_VARIANTS: [&str; 10] = [
+    "GenericDynProp",
+    "ExistsFixedPoint",
+    "ExistsTrapSpace",
+    "ExistsTrajectory",
+    "AttractorCount",
+    "HasAttractor",
+    "ObservationsReachable",
+    "SteadyState",
+    "ReachableFromDataset",
+    "ObservationAbsent",
+];
+
+/// **(internal)** Serialized names of all variants of static properties.
+const STAT_PROPERTY_VARIANTS: [&str; 10] = [
+    "GenericStatProp",
+    "RegulationEssential",
+    "RegulationEssentialContext",
+    "FnInputEssential",
+    "FnInputEssentialContext",
+    "RegulationMonotonic",
+    "RegulationMonotonicContext",
+    "FnInputMonotonic",
+    "FnInputMonotonicContext",
+    "FnInputsMutuallyExclusive",
+];
 
 /// Structure for sending/exporting data about the whole Sketch.
+///
+/// The `initial_subspace` maps variable IDs to their fixed values (it is optional, so that
+/// older sketch files can still be loaded).
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SketchData {
     pub model: ModelData,
@@ -13,6 +47,8 @@ pub struct SketchData {
     pub dyn_properties: Vec<DynPropertyData>,
     pub stat_properties: Vec<StatPropertyData>,
     pub annotation: String,
+    #[serde(default)]
+    pub initial_subspace: BTreeMap<String, bool>,
 }
 
 impl JsonSerde<'_> for SketchData {}
@@ -24,6 +60,7 @@ impl SketchData {
         observations: &ObservationManager,
         properties: &PropertyManager,
         annotation: &str,
+        initial_subspace: &BTreeMap<VarId, bool>,
     ) -> SketchData {
         let datasets = observations
             .datasets()
@@ -44,6 +81,64 @@ impl SketchData {
             dyn_properties,
             stat_properties,
             annotation: annotation.to_string(),
+            initial_subspace: initial_subspace
+                .iter()
+                .map(|(var_id, value)| (var_id.to_string(), *value))
+                .collect(),
+        }
+    }
+
+    /// Check the structure of a JSON string before it is parsed into `SketchData`.
+    ///
+    /// This is a lightweight check of the main structural expectations: all required top-level
+    /// (and model) keys are present, the optional initial subspace maps variables to booleans,
+    /// components like variables or properties are arrays of
+    /// objects with string fields `id` and `name`, and properties are of known variants. Unlike
+    /// the deserialization itself (see `from_json_str`), this does not stop at the first problem,
+    /// but returns a list of human-readable messages for all the problems found.
+    ///
+    /// Passing this check does not guarantee successful parsing (e.g., fields of individual
+    /// property variants are not checked).
+    pub fn validate_schema(json: &str) -> Result<(), Vec<String>> {
+        let value: Value =
+            serde_json::from_str(json).map_err(|e| vec![format!("Invalid JSON: {e}")])?;
+        let Some(root) = value.as_object() else {
+            return Err(vec!["Sketch data must be a JSON object.".to_string()]);
+        };
+
+        let mut errors = Vec::new();
+        check_string_fields(root, "sketch", &["annotation"], &mut errors);
+        check_initial_subspace(root, &mut errors);
+        if let Some(model) = get_required_object(root, "model", &mut errors) {
+            for (path, var) in get_object_array(model, "model.variables", &mut errors) {
+                check_string_fields(var, &path, &["id", "name"], &mut errors);
+            }
+            for (path, reg) in get_object_array(model, "model.regulations", &mut errors) {
+                check_string_fields(reg, &path, &["regulator", "target"], &mut errors);
+            }
+            for (path, f) in get_object_array(model, "model.uninterpreted_fns", &mut errors) {
+                check_string_fields(f, &path, &["id", "name"], &mut errors);
+            }
+            for (path, layout) in get_object_array(model, "model.layouts", &mut errors) {
+                check_string_fields(layout, &path, &["id", "name"], &mut errors);
+            }
+        }
+        for (path, dataset) in get_object_array(root, "datasets", &mut errors) {
+            check_string_fields(dataset, &path, &["id", "name"], &mut errors);
+        }
+        for (path, prop) in get_object_array(root, "dyn_properties", &mut errors) {
+            check_string_fields(prop, &path, &["id", "name"], &mut errors);
+            check_variant(prop, &path, &DYN_PROPERTY_VARIANTS, &mut errors);
+        }
+        for (path, prop) in get_object_array(root, "stat_properties", &mut errors) {
+            check_string_fields(prop, &path, &["id", "name"], &mut errors);
+            check_variant(prop, &path, &STAT_PROPERTY_VARIANTS, &mut errors);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
         }
     }
 
@@ -54,6 +149,257 @@ impl SketchData {
             &sketch.observations,
             &sketch.properties,
             &sketch.annotation,
+            sketch.get_initial_subspace(),
         )
     }
 }
+
+/// **(internal)** Get the last segment of a dot-separated `path`.
+fn last_key(path: &str) -> &str {
+    path.rsplit('.').next().unwrap_or(path)
+}
+
+/// **(internal)** Get an object stored under the last key of `path`, or record an error if it
+/// is missing or not an object.
+fn get_required_object<'a>(
+    parent: &'a Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<String>,
+) -> Option<&'a Map<String, Value>> {
+    match parent.get(last_key(path)) {
+        None => errors.push(format!("Missing required key `{path}`.")),
+        Some(Value::Object(object)) => return Some(object),
+        Some(_) => errors.push(format!("`{path}` must be an object.")),
+    }
+    None
+}
+
+/// **(internal)** Get all objects in an array stored under the last key of `path` (together with
+/// their paths). Record an error if the array is missing, or for each of its non-object items.
+fn get_object_array<'a>(
+    parent: &'a Map<String, Value>,
+    path: &str,
+    errors: &mut Vec<String>,
+) -> Vec<(String, &'a Map<String, Value>)> {
+    let items = match parent.get(last_key(path)) {
+        None => {
+            errors.push(format!("Missing required key `{path}`."));
+            return Vec::new();
+        }
+        Some(Value::Array(items)) => items,
+        Some(_) => {
+            errors.push(format!("`{path}` must be an array."));
+            return Vec::new();
+        }
+    };
+
+    let mut objects = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        let item_path = format!("{path}[{i}]");
+        if let Value::Object(object) = item {
+            objects.push((item_path, object));
+        } else {
+            errors.push(format!("`{item_path}` must be an object."));
+        }
+    }
+    objects
+}
+
+/// **(internal)** Record an error for each of the `fields` that is missing in `object` or is
+/// not a string.
+fn check_string_fields(
+    object: &Map<String, Value>,
+    path: &str,
+    fields: &[&str],
+    errors: &mut Vec<String>,
+) {
+    for field in fields {
+        match object.get(*field) {
+            None => errors.push(format!("`{path}` is missing required field `{field}`.")),
+            Some(Value::String(_)) => {}
+            Some(_) => errors.push(format!("Field `{field}` of `{path}` must be a string.")),
+        }
+    }
+}
+
+/// **(internal)** Record an error if the optional `initial_subspace` is present, but it is not
+/// an object mapping variables to boolean values.
+fn check_initial_subspace(root: &Map<String, Value>, errors: &mut Vec<String>) {
+    match root.get("initial_subspace") {
+        None => {}
+        Some(Value::Object(subspace)) => {
+            for (var, value) in subspace {
+                if !value.is_boolean() {
+                    errors.push(format!(
+                        "Value of `initial_subspace.{var}` must be a boolean."
+                    ));
+                }
+            }
+        }
+        Some(_) => errors.push("`initial_subspace` must be an object.".to_string()),
+    }
+}
+
+/// **(internal)** Record an error if the `variant` field of a property object is missing or is
+/// not one of the `known_variants`.
+fn check_variant(
+    object: &Map<String, Value>,
+    path: &str,
+    known_variants: &[&str],
+    errors: &mut Vec<String>,
+) {
+    match object.get("variant") {
+        None => errors.push(format!("`{path}` is missing required field `variant`.")),
+        Some(Value::String(variant)) if known_variants.contains(&variant.as_str()) => {}
+        Some(variant) => errors.push(format!(
+            "`{path}` has unknown variant {variant} (expected one of: {}).",
+            known_variants.join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::data_structs::_sketch_data::{
+        DYN_PROPERTY_VARIANTS, STAT_PROPERTY_VARIANTS,
+    };
+    use crate::sketchbook::data_structs::{DynPropertyData, SketchData, StatPropertyData};
+    use crate::sketchbook::ids::{DynPropertyId, StatPropertyId};
+    use crate::sketchbook::model::{Essentiality, Monotonicity};
+    use crate::sketchbook::properties::dynamic_props::AbsenceMode;
+    use crate::sketchbook::properties::{DynProperty, StatProperty};
+    use crate::sketchbook::{JsonSerde, Sketch};
+    use std::fs::File;
+    use std::io::Read;
+
+    #[test]
+    /// Test that serialized sketches pass the schema validation.
+    fn test_validate_schema_valid() {
+        let mut json = String::new();
+        let mut file = File::open("../data/test_data/test_sketch_1.json").unwrap();
+        file.read_to_string(&mut json).unwrap();
+        assert!(SketchData::validate_schema(&json).is_ok());
+        let json = SketchData::new_from_sketch(&Sketch::default()).to_json_str();
+        assert!(SketchData::validate_schema(&json).is_ok());
+    }
+
+    #[test]
+    /// Test that properties of all variants pass the schema validation, and that the known
+    /// variant names are exactly the serialized ones.
+    fn test_validate_schema_all_variants() {
+        let dyn_props = [
+            DynProperty::try_mk_generic("p", "true").unwrap(),
+            DynProperty::mk_fixed_point("p", None, None),
+            DynProperty::mk_trap_space("p", None, None, false, false),
+            DynProperty::mk_trajectory("p", None),
+            DynProperty::try_mk_attractor_count("p", 1, 1).unwrap(),
+            DynProperty::mk_has_attractor("p", None, None),
+            DynProperty::try_mk_obs_reachable("p", None, 1).unwrap(),
+            DynProperty::mk_steady_state("p", None, None),
+            DynProperty::mk_reachable_from_dataset("p", None, None),
+            DynProperty::mk_observation_absent("p", None, None, AbsenceMode::Unreachable),
+        ];
+        let stat_props = [
+            StatProperty::try_mk_generic("s", "true").unwrap(),
+            StatProperty::mk_regulation_essential("s", None, None, Essentiality::True),
+            StatProperty::mk_regulation_essential_context(
+                "s",
+                None,
+                None,
+                Essentiality::True,
+                "true".to_string(),
+            ),
+            StatProperty::mk_fn_input_essential("s", None, None, Essentiality::True),
+            StatProperty::mk_fn_input_essential_context(
+                "s",
+                None,
+                None,
+                Essentiality::True,
+                "true".to_string(),
+            ),
+            StatProperty::mk_regulation_monotonic("s", None, None, Monotonicity::Activation),
+            StatProperty::mk_regulation_monotonic_context(
+                "s",
+                None,
+                None,
+                Monotonicity::Activation,
+                "true".to_string(),
+            ),
+            StatProperty::mk_fn_input_monotonic("s", None, None, Monotonicity::Activation),
+            StatProperty::mk_fn_input_monotonic_context(
+                "s",
+                None,
+                None,
+                Monotonicity::Activation,
+                "true".to_string(),
+            ),
+            StatProperty::mk_fn_inputs_mutually_exclusive("s", None, None, None),
+        ];
+
+        let mut sketch_data = SketchData::new_from_sketch(&Sketch::default());
+        for (i, prop) in dyn_props.iter().enumerate() {
+            let prop_id = DynPropertyId::new(&format!("p_{i}")).unwrap();
+            let prop_data = DynPropertyData::from_property(&prop_id, prop);
+            sketch_data.dyn_properties.push(prop_data);
+        }
+        for (i, prop) in stat_props.iter().enumerate() {
+            let prop_id = StatPropertyId::new(&format!("s_{i}")).unwrap();
+            let prop_data = StatPropertyData::from_property(&prop_id, prop);
+            sketch_data.stat_properties.push(prop_data);
+        }
+        let json = sketch_data.to_json_str();
+        assert!(SketchData::validate_schema(&json).is_ok());
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let dyn_variants: Vec<&str> = value["dyn_properties"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|prop| prop["variant"].as_str().unwrap())
+            .collect();
+        assert_eq!(dyn_variants, DYN_PROPERTY_VARIANTS);
+        let stat_variants: Vec<&str> = value["stat_properties"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|prop| prop["variant"].as_str().unwrap())
+            .collect();
+        assert_eq!(stat_variants, STAT_PROPERTY_VARIANTS);
+    }
+
+    #[test]
+    /// Test that all problems in an invalid sketch are reported.
+    fn test_validate_schema_invalid() {
+        assert!(SketchData::validate_schema("{\"model\": ").is_err());
+        assert_eq!(
+            SketchData::validate_schema("[]"),
+            Err(vec!["Sketch data must be a JSON object.".to_string()])
+        );
+
+        let json = r#"{
+            "model": {
+                "variables": [{"id": "A", "name": "A"}, {"id": "B"}, 3],
+                "regulations": [],
+                "uninterpreted_fns": [],
+                "layouts": {}
+            },
+            "datasets": [],
+            "dyn_properties": [{"id": "p", "name": "p", "variant": "Unknown"}],
+            "stat_properties": [{"id": "s", "name": 1, "variant": "GenericStatProp"}],
+            "initial_subspace": {"A": true, "B": 1}
+        }"#;
+        let expected = [
+            "`sketch` is missing required field `annotation`.",
+            "Value of `initial_subspace.B` must be a boolean.",
+            "`model.variables[2]` must be an object.",
+            "`model.variables[1]` is missing required field `name`.",
+            "`model.layouts` must be an array.",
+            "`dyn_properties[0]` has unknown variant \"Unknown\" (expected one of: GenericDynProp, ExistsFixedPoint, ExistsTrapSpace, ExistsTrajectory, AttractorCount, HasAttractor, ObservationsReachable, SteadyState, ReachableFromDataset, ObservationAbsent).",
+            "Field `name` of `stat_properties[0]` must be a string.",
+        ];
+        assert_eq!(
+            SketchData::validate_schema(json),
+            Err(expected.iter().map(|e| e.to_string()).collect())
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use crate::sketchbook::data_structs::{
+    DatasetData, DynPropertyData, RegulationData, StatPropertyData, UninterpretedFnData,
+    VariableData,
+};
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+
+/// Structure describing a single modified component of the sketch, given by its ID, together
+/// with both its original and its new value.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModifiedComponent<T> {
+    pub id: String,
+    pub old: T,
+    pub new: T,
+}
+
+/// Structure describing differences between two versions of a single kind of sketch components
+/// (e.g., variables or datasets). Components are matched by their IDs and sorted by them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ComponentDiff<T> {
+    pub added: Vec<T>,
+    pub removed: Vec<T>,
+    pub modified: Vec<ModifiedComponent<T>>,
+}
+
+/// Structure for sending a report of differences between two sketches.
+///
+/// Variables are considered modified if their name, annotation, input flag, or the frozen flag
+/// of their update function changed. Changes of update functions (of variables present in both
+/// sketches) are reported separately in `update_fns`.
+/// Regulations are identified by strings `(regulator, target)`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SketchDiff {
+    pub variables: ComponentDiff<VariableData>,
+    pub update_fns: Vec<ModifiedComponent<String>>,
+    pub regulations: ComponentDiff<RegulationData>,
+    pub uninterpreted_fns: ComponentDiff<UninterpretedFnData>,
+    pub datasets: ComponentDiff<DatasetData>,
+    pub stat_properties: ComponentDiff<StatPropertyData>,
+    pub dyn_properties: ComponentDiff<DynPropertyData>,
+}
+
+impl JsonSerde<'_> for SketchDiff {}
+
+impl<T> ComponentDiff<T> {
+    /// Check whether there are no differences at all.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+impl SketchDiff {
+    /// Check whether there are no differences at all (i.e., the compared sketches are
+    /// identical in all the compared components).
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+            && self.update_fns.is_empty()
+            && self.regulations.is_empty()
+            && self.uninterpreted_fns.is_empty()
+            && self.datasets.is_empty()
+            && self.stat_properties.is_empty()
+            && self.dyn_properties.is_empty()
+    }
+}
@@ -0,0 +1,32 @@
+use crate::sketchbook::model::Monotonicity;
+use crate::sketchbook::properties::dynamic_props::SimpleDynPropertyType;
+use crate::sketchbook::properties::static_props::SimpleStatPropertyType;
+use crate::sketchbook::JsonSerde;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Structure aggregating basic statistics about all components of a sketch, sent to the
+/// frontend at once (e.g., for a dashboard panel).
+///
+/// Apart from plain counts, it contains numbers of regulations by their sign, numbers
+/// of uninterpreted functions by their arity, numbers of observations of each dataset (by
+/// dataset ID), and numbers of properties by their template kind. Only non-zero counts are
+/// included in these maps. The `num_free_params` is computed the same way as in
+/// [crate::sketchbook::Sketch::count_free_parameters].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SketchStats {
+    pub num_variables: usize,
+    pub num_regulations: usize,
+    pub regulations_by_sign: HashMap<Monotonicity, usize>,
+    pub num_uninterpreted_fns: usize,
+    pub fns_by_arity: HashMap<usize, usize>,
+    pub num_datasets: usize,
+    pub observations_by_dataset: HashMap<String, usize>,
+    pub num_dyn_properties: usize,
+    pub dyn_properties_by_kind: HashMap<SimpleDynPropertyType, usize>,
+    pub num_stat_properties: usize,
+    pub stat_properties_by_kind: HashMap<SimpleStatPropertyType, usize>,
+    pub num_free_params: u64,
+}
+
+impl JsonSerde<'_> for SketchStats {}
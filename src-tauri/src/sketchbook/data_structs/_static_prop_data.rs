@@ -64,6 +64,15 @@ pub struct FnInputMonotonicData {
     pub context: Option<String>,
 }
 
+/// Simplified variant to carry data regarding [static_props::FnInputsMutuallyExclusive] static
+/// property.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FnInputsMutuallyExclusiveData {
+    pub input_1: Option<String>,
+    pub input_2: Option<String>,
+    pub target: Option<String>,
+}
+
 /// Enum covering all variants of static properties and their necessary data.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "variant")]
@@ -77,6 +86,7 @@ pub enum StatPropertyTypeData {
     RegulationMonotonicContext(RegulationMonotonicData),
     FnInputMonotonic(FnInputMonotonicData),
     FnInputMonotonicContext(FnInputMonotonicData),
+    FnInputsMutuallyExclusive(FnInputsMutuallyExclusiveData),
 }
 
 /// Structure for sending data about static properties to the frontend.
@@ -178,6 +188,13 @@ impl StatPropertyData {
                     context: p.context.clone(),
                 })
             }
+            StatPropertyType::FnInputsMutuallyExclusive(p) => {
+                StatPropertyTypeData::FnInputsMutuallyExclusive(FnInputsMutuallyExclusiveData {
+                    input_1: p.input_index_1.map(input_index_to_id),
+                    input_2: p.input_index_2.map(input_index_to_id),
+                    target: p.target.as_ref().map(|i| i.to_string()),
+                })
+            }
         };
         Self::new_raw(id.as_str(), name, variant, annot)
     }
@@ -228,6 +245,16 @@ impl StatPropertyData {
                 StatProperty::mk_fn_input_essential_context(name, input, target, p.value, context)
                     .with_annotation(annot)
             }
+            StatPropertyTypeData::FnInputsMutuallyExclusive(p) => {
+                let input_1 = input_id_to_index(&p.input_1)?;
+                let input_2 = input_id_to_index(&p.input_2)?;
+                let target = p
+                    .target
+                    .as_ref()
+                    .and_then(|t| UninterpretedFnId::new(t).ok());
+                StatProperty::mk_fn_inputs_mutually_exclusive(name, input_1, input_2, target)
+                    .with_annotation(annot)
+            }
             StatPropertyTypeData::RegulationMonotonic(p) => {
                 let input = p.input.as_ref().and_then(|i| VarId::new(i).ok());
                 let target = p.target.as_ref().and_then(|t| VarId::new(t).ok());
@@ -273,3 +300,30 @@ impl StatPropertyData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::data_structs::StatPropertyData;
+    use crate::sketchbook::ids::{StatPropertyId, UninterpretedFnId};
+    use crate::sketchbook::properties::StatProperty;
+    use crate::sketchbook::JsonSerde;
+
+    #[test]
+    /// Test converting a mutually exclusive fn inputs property to `StatPropertyData` and back,
+    /// including the JSON serialization.
+    fn test_converting_mutually_exclusive_inputs() {
+        let prop_id = StatPropertyId::new("p").unwrap();
+        let target = Some(UninterpretedFnId::new("f").unwrap());
+        let prop_before =
+            StatProperty::mk_fn_inputs_mutually_exclusive("p", Some(0), Some(2), target)
+                .with_annotation("annot");
+        let prop_data = StatPropertyData::from_property(&prop_id, &prop_before);
+        let json_str = prop_data.to_json_str();
+        assert!(json_str.contains("\"variant\":\"FnInputsMutuallyExclusive\""));
+        assert!(json_str.contains("\"input_2\":\"var2\""));
+
+        let prop_data = StatPropertyData::from_json_str(&json_str).unwrap();
+        let prop_after = prop_data.to_property().unwrap();
+        assert_eq!(prop_before, prop_after);
+    }
+}
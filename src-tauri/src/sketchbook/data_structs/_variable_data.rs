@@ -6,7 +6,9 @@ use serde::{Deserialize, Serialize};
 
 /// Structure for sending data about `Variable` and its `UpdateFn` to the frontend.
 ///
-/// `VariableData` contains similar fields as `Variable` and additional fields `id` and `update_fn`.
+/// `VariableData` contains similar fields as `Variable` (including `is_input`, see
+/// [Variable::is_input]) and additional fields `id`, `update_fn`, and `frozen` (whether the
+/// update function is frozen, see [UpdateFn::is_frozen]).
 /// Some fields simplified compared to original typesafe versions (e.g., pure `Strings` are used
 /// instead of more complex typesafe structs) to allow for easier (de)serialization.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -15,6 +17,10 @@ pub struct VariableData {
     pub name: String,
     pub annotation: String,
     pub update_fn: String,
+    #[serde(default)]
+    pub frozen: bool,
+    #[serde(default)]
+    pub is_input: bool,
 }
 
 /// Structure for receiving data about `Variable` and all of its `Layout` data from the frontend.
@@ -29,28 +35,37 @@ impl JsonSerde<'_> for VariableWithLayoutData {}
 
 impl VariableData {
     /// Create new `VariableData` object given a variable's `name` and `id` string slices.
+    /// The update function is not frozen, and the variable is not an input.
     pub fn new(id: &str, name: &str, annotation: &str, update_fn: &str) -> VariableData {
         VariableData {
             id: id.to_string(),
             name: name.to_string(),
             annotation: annotation.to_string(),
             update_fn: update_fn.to_string(),
+            frozen: false,
+            is_input: false,
         }
     }
 
     /// Create new `VariableData` object given a reference to a variable, its update function,
     /// and its id.
     pub fn from_var(var_id: &VarId, variable: &Variable, update_fn: &UpdateFn) -> VariableData {
-        VariableData::new(
-            var_id.as_str(),
-            variable.get_name(),
-            variable.get_annotation(),
-            update_fn.get_fn_expression(),
-        )
+        VariableData {
+            frozen: update_fn.is_frozen(),
+            is_input: variable.is_input(),
+            ..VariableData::new(
+                var_id.as_str(),
+                variable.get_name(),
+                variable.get_annotation(),
+                update_fn.get_fn_expression(),
+            )
+        }
     }
 
     /// Extract new `Variable` instance from this data.
     pub fn to_var(&self) -> Result<Variable, String> {
-        Ok(Variable::new(&self.name)?.with_annotation(&self.annotation))
+        Ok(Variable::new(&self.name)?
+            .with_annotation(&self.annotation)
+            .with_input(self.is_input))
     }
 }
@@ -1,9 +1,15 @@
+/// **(internal)** Definition and utility methods for `AnnotationHit`.
+mod _annotation_hit;
+/// **(internal)** Definition and utility methods for `CompletenessReport`.
+mod _completeness_report;
 /// **(internal)** Definition and utility methods for `DatasetData` and `DatasetMetaData`.
 mod _dataset_data;
 /// **(internal)** Definition and utility methods for all kinds of dynamic properties.
 mod _dynamic_prop_data;
-/// **(internal)** Definition and utility methods for `ChangeArgMonotoneData`
-/// and `ChangeArgEssentialData`.
+/// **(internal)** Definition and utility methods for `EncodingMetrics`.
+mod _encoding_metrics;
+/// **(internal)** Definition and utility methods for `ChangeArgMonotoneData`,
+/// `ChangeArgEssentialData`, and `ReorderArgsData`.
 mod _fn_arg_change_data;
 /// **(internal)** Definition and utility methods for `ChangeIdData`.
 mod _id_change_data;
@@ -19,6 +25,10 @@ mod _observation_data;
 mod _regulation_data;
 /// **(internal)** Definition and utility methods for `SketchData`.
 mod _sketch_data;
+/// **(internal)** Definition and utility methods for `SketchDiff` and its components.
+mod _sketch_diff;
+/// **(internal)** Definition and utility methods for `SketchStats`.
+mod _sketch_stats;
 /// **(internal)** Definition and utility methods for all kinds of static properties.
 mod _static_prop_data;
 /// **(internal)** Definition and utility methods for `UninterpretedFnData`.
@@ -26,9 +36,12 @@ mod _uninterpreted_fn_data;
 /// **(internal)** Definition and utility methods for `VariableData`.
 mod _variable_data;
 
+pub use _annotation_hit::{AnnotationComponent, AnnotationHit};
+pub use _completeness_report::CompletenessReport;
 pub use _dataset_data::{DatasetData, DatasetMetaData};
 pub use _dynamic_prop_data::{DynPropertyData, DynPropertyTypeData};
-pub use _fn_arg_change_data::{ChangeArgEssentialData, ChangeArgMonotoneData};
+pub use _encoding_metrics::EncodingMetrics;
+pub use _fn_arg_change_data::{ChangeArgEssentialData, ChangeArgMonotoneData, ReorderArgsData};
 pub use _id_change_data::ChangeIdData;
 pub use _layout_data::{LayoutData, LayoutMetaData};
 pub use _layout_node_data::{LayoutNodeData, LayoutNodeDataPrototype};
@@ -36,6 +49,8 @@ pub use _model_data::ModelData;
 pub use _observation_data::ObservationData;
 pub use _regulation_data::RegulationData;
 pub use _sketch_data::SketchData;
+pub use _sketch_diff::{ComponentDiff, ModifiedComponent, SketchDiff};
+pub use _sketch_stats::SketchStats;
 pub use _static_prop_data::{StatPropertyData, StatPropertyTypeData};
 pub use _uninterpreted_fn_data::UninterpretedFnData;
 pub use _variable_data::{VariableData, VariableWithLayoutData};
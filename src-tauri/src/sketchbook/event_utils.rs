@@ -81,6 +81,14 @@ pub(crate) fn mk_stat_prop_state_change<'a, T: JsonSerde<'a>>(
     make_state_change(&full_path, payload)
 }
 
+/// Prepare event for the `sketch` itself, given `at_path` - a path suffix used at the sketch
+/// level, and a `payload`.
+pub(crate) fn mk_sketch_event(at_path: &[&str], payload: Option<&str>) -> Event {
+    let mut full_path = vec!["sketch"];
+    full_path.extend_from_slice(at_path);
+    Event::build(&full_path, payload)
+}
+
 /// Prepare event for the `model` component of the `sketch`, given `at_path` - a path suffix
 /// used at the model level, and a `payload`.
 pub(crate) fn mk_model_event(at_path: &[&str], payload: Option<&str>) -> Event {
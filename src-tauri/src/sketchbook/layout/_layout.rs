@@ -1,9 +1,16 @@
 use crate::sketchbook::data_structs::LayoutNodeData;
 use crate::sketchbook::ids::VarId;
 use crate::sketchbook::layout::{LayoutNode, LayoutNodeIterator, NodePosition};
+use crate::sketchbook::model::Regulation;
 use crate::sketchbook::utils::assert_name_valid;
 use crate::sketchbook::Manager;
 use std::collections::{HashMap, HashSet};
+use std::f32::consts::PI;
+
+/// Ideal distance between two nodes connected by a regulation (used by `auto_arrange`).
+const AUTO_LAYOUT_EDGE_LENGTH: f32 = 150.;
+/// Number of iterations of the force-directed algorithm (used by `auto_arrange`).
+const AUTO_LAYOUT_ITERATIONS: usize = 200;
 
 /// Structure to capture all the layout data regarding one particular layout of the regulations
 /// editor.
@@ -172,6 +179,129 @@ impl Layout {
         Ok(())
     }
 
+    /// Automatically arrange the nodes of this layout using a simple Fruchterman-Reingold
+    /// force-directed algorithm, with `regulations` as edges (regulations between variables
+    /// that have no node in this layout are ignored).
+    ///
+    /// The computation is seeded from the current node positions (nodes sharing the same
+    /// position are first spread around a circle) and runs a fixed number of iterations. The
+    /// result is deterministic for a given input, and its center stays at the center of the
+    /// original layout.
+    pub fn auto_arrange(&mut self, regulations: &HashSet<Regulation>) {
+        let num_nodes = self.nodes.len();
+        if num_nodes < 2 {
+            return;
+        }
+
+        // sort the variables, so that the computation does not depend on the map ordering
+        let mut variables: Vec<VarId> = self.nodes.keys().cloned().collect();
+        variables.sort();
+        let indices: HashMap<&VarId, usize> =
+            variables.iter().enumerate().map(|(i, v)| (v, i)).collect();
+        let k = AUTO_LAYOUT_EDGE_LENGTH;
+
+        // initial positions, spreading nodes at the same position around a circle
+        let original_positions: Vec<(f32, f32)> = variables
+            .iter()
+            .map(|var_id| (self.nodes[var_id].get_px(), self.nodes[var_id].get_py()))
+            .collect();
+        let center = Self::centroid(&original_positions);
+        let mut positions: Vec<(f32, f32)> = Vec::with_capacity(num_nodes);
+        for (i, &(mut px, mut py)) in original_positions.iter().enumerate() {
+            if positions.contains(&(px, py)) {
+                let angle = 2. * PI * (i as f32) / (num_nodes as f32);
+                px += k * angle.cos();
+                py += k * angle.sin();
+            }
+            positions.push((px, py));
+        }
+
+        // undirected edges without self-loops (and without duplicates)
+        let mut edges: Vec<(usize, usize)> = regulations
+            .iter()
+            .filter_map(|reg| {
+                let i = *indices.get(reg.get_regulator())?;
+                let j = *indices.get(reg.get_target())?;
+                (i != j).then_some((i.min(j), i.max(j)))
+            })
+            .collect();
+        edges.sort();
+        edges.dedup();
+
+        // the temperature limits the maximal displacement, and cools down linearly
+        let initial_temperature = k * (num_nodes as f32).sqrt();
+        for iteration in 0..AUTO_LAYOUT_ITERATIONS {
+            let mut displacements = vec![(0f32, 0f32); num_nodes];
+            // repulsive forces between all pairs of nodes
+            for i in 0..num_nodes {
+                for j in (i + 1)..num_nodes {
+                    let (dx, dy, dist) = Self::delta(positions[i], positions[j], i, j);
+                    let force = k * k / dist;
+                    let (fx, fy) = (dx / dist * force, dy / dist * force);
+                    displacements[i].0 += fx;
+                    displacements[i].1 += fy;
+                    displacements[j].0 -= fx;
+                    displacements[j].1 -= fy;
+                }
+            }
+            // attractive forces between nodes connected by an edge
+            for &(i, j) in edges.iter() {
+                let (dx, dy, dist) = Self::delta(positions[i], positions[j], i, j);
+                let force = dist * dist / k;
+                let (fx, fy) = (dx / dist * force, dy / dist * force);
+                displacements[i].0 -= fx;
+                displacements[i].1 -= fy;
+                displacements[j].0 += fx;
+                displacements[j].1 += fy;
+            }
+
+            let remaining = (AUTO_LAYOUT_ITERATIONS - iteration) as f32;
+            let temperature = initial_temperature * remaining / AUTO_LAYOUT_ITERATIONS as f32;
+            for (position, (disp_x, disp_y)) in positions.iter_mut().zip(displacements) {
+                let length = (disp_x * disp_x + disp_y * disp_y).sqrt();
+                if length > 0. {
+                    let limited = length.min(temperature);
+                    position.0 += disp_x / length * limited;
+                    position.1 += disp_y / length * limited;
+                }
+            }
+        }
+
+        // move the result back to the original center
+        let new_center = Self::centroid(&positions);
+        for (var_id, (px, py)) in variables.iter().zip(positions) {
+            let new_x = px - new_center.0 + center.0;
+            let new_y = py - new_center.1 + center.1;
+            self.nodes
+                .get_mut(var_id)
+                .unwrap()
+                .change_position(new_x, new_y);
+        }
+    }
+
+    /// **(internal)** Compute the difference vector between two positions (of nodes with
+    /// indices `i` and `j`) and its length. If the positions coincide, a small deterministic
+    /// vector based on the indices is used instead, so that the nodes can be separated.
+    fn delta(pos_i: (f32, f32), pos_j: (f32, f32), i: usize, j: usize) -> (f32, f32, f32) {
+        let (dx, dy) = (pos_i.0 - pos_j.0, pos_i.1 - pos_j.1);
+        let dist = (dx * dx + dy * dy).sqrt();
+        if dist > 0.01 {
+            (dx, dy, dist)
+        } else {
+            let angle = (i * 31 + j * 17) as f32;
+            (0.01 * angle.cos(), 0.01 * angle.sin(), 0.01)
+        }
+    }
+
+    /// **(internal)** Compute the centroid of the given positions.
+    fn centroid(positions: &[(f32, f32)]) -> (f32, f32) {
+        let count = positions.len().max(1) as f32;
+        let sum = positions
+            .iter()
+            .fold((0., 0.), |acc, p| (acc.0 + p.0, acc.1 + p.1));
+        (sum.0 / count, sum.1 / count)
+    }
+
     /// Change id of a variable with `original_id` to `new_id`.
     pub fn change_node_id(&mut self, original_id: &VarId, new_id: VarId) -> Result<(), String> {
         self.assert_valid_variable(original_id)?;
@@ -242,6 +372,8 @@ impl Layout {
 mod tests {
     use crate::sketchbook::ids::VarId;
     use crate::sketchbook::layout::{Layout, LayoutNode};
+    use crate::sketchbook::model::Regulation;
+    use std::collections::HashSet;
 
     #[test]
     fn test_layout_basics() {
@@ -292,4 +424,45 @@ mod tests {
         assert_eq!(layout.get_num_nodes(), 0);
         assert_eq!(layout_2.get_num_nodes(), 1);
     }
+
+    #[test]
+    /// Test automatic arrangement of nodes based on the regulatory graph.
+    fn test_auto_arrange() {
+        let vars = ["a", "b", "c", "d"];
+        let var_ids: Vec<VarId> = vars.iter().map(|v| VarId::new(v).unwrap()).collect();
+        let regulations: HashSet<Regulation> = ["a -> b", "b -> c", "c -| a", "d -> d"]
+            .iter()
+            .map(|r| Regulation::try_from_string(r).unwrap())
+            .collect();
+
+        // all nodes start at the same position
+        let mut layout = Layout::new_with_vars_default("layout", var_ids.clone()).unwrap();
+        layout.auto_arrange(&regulations);
+
+        // all nodes are now at different positions, and the center is kept at the origin
+        let positions: Vec<(f32, f32)> = var_ids
+            .iter()
+            .map(|v| {
+                let node = layout.get_node(v).unwrap();
+                (node.get_px(), node.get_py())
+            })
+            .collect();
+        for i in 0..positions.len() {
+            for j in (i + 1)..positions.len() {
+                let (dx, dy) = (
+                    positions[i].0 - positions[j].0,
+                    positions[i].1 - positions[j].1,
+                );
+                assert!((dx * dx + dy * dy).sqrt() > 10.);
+            }
+        }
+        let center_x: f32 = positions.iter().map(|p| p.0).sum::<f32>() / 4.;
+        let center_y: f32 = positions.iter().map(|p| p.1).sum::<f32>() / 4.;
+        assert!(center_x.abs() < 0.1 && center_y.abs() < 0.1);
+
+        // the result is deterministic
+        let mut layout_2 = Layout::new_with_vars_default("layout", var_ids.clone()).unwrap();
+        layout_2.auto_arrange(&regulations);
+        assert_eq!(layout, layout_2);
+    }
 }
@@ -31,7 +31,7 @@ mod utils;
 #[cfg(test)]
 mod _tests_events;
 
-pub use crate::sketchbook::_sketch::Sketch;
+pub use crate::sketchbook::_sketch::{IssueSeverity, Sketch};
 
 /// Trait that implements `to_json_str` and `from_json_str` wrappers to serialize and
 /// deserialize objects, utilizing [serde_json].
@@ -1,5 +1,6 @@
 use crate::sketchbook::ids::{UninterpretedFnId, VarId};
 use crate::sketchbook::model::{BinaryOp, ModelState};
+use biodivine_lib_bdd::{Bdd, BddVariableSet};
 use biodivine_lib_param_bn::{BooleanNetwork, FnUpdate};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -230,55 +231,59 @@ impl FnTree {
     /// these two variants can never appear in the same tree, since one is used within update
     /// functions, and the other within uninterpreted functions.
     pub fn collect_variables(&self) -> HashSet<VarId> {
-        fn r_arguments(function: &FnTree, args: &mut HashSet<VarId>) {
-            match function {
-                FnTree::Const(_) => (),
-                FnTree::Var(id) => {
-                    args.insert(id.clone());
-                }
-                FnTree::PlaceholderVar(id) => {
-                    args.insert(id.clone());
-                }
-                FnTree::UninterpretedFn(_, p_args) => {
-                    for fun in p_args {
-                        r_arguments(fun, args);
-                    }
-                }
-                FnTree::Not(inner) => r_arguments(inner, args),
-                FnTree::Binary(_, l, r) => {
-                    r_arguments(l, args);
-                    r_arguments(r, args);
-                }
-            };
-        }
-        let mut vars = HashSet::new();
-        r_arguments(self, &mut vars);
-        vars
+        let mut collector = SymbolCollector::default();
+        self.fold(&mut collector);
+        collector.vars
     }
 
     /// Return a set of all uninterpreted functions (parameters) that are used in this function.
     pub fn collect_fn_symbols(&self) -> HashSet<UninterpretedFnId> {
-        fn r_parameters(function: &FnTree, params: &mut HashSet<UninterpretedFnId>) {
-            match function {
-                FnTree::Const(_) => (),
-                FnTree::Var(_) => (),
-                FnTree::PlaceholderVar(_) => (),
-                FnTree::UninterpretedFn(id, args) => {
-                    params.insert(id.clone());
-                    for fun in args {
-                        r_parameters(fun, params);
-                    }
-                }
-                FnTree::Not(inner) => r_parameters(inner, params),
-                FnTree::Binary(_, l, r) => {
-                    r_parameters(l, params);
-                    r_parameters(r, params);
-                }
-            };
+        let mut collector = SymbolCollector::default();
+        self.fold(&mut collector);
+        collector.fn_symbols
+    }
+
+    /// Build a BDD representing this (fully specified) function, using BDD variables from the
+    /// given set. The BDD variables must be named by the IDs of the network variables.
+    ///
+    /// Returns `None` if the function is not fully specified (contains uninterpreted functions
+    /// or placeholder variables), or if some variable is missing in `bdd_vars`.
+    pub fn to_bdd(&self, bdd_vars: &BddVariableSet) -> Option<Bdd> {
+        match self {
+            FnTree::Const(value) => Some(if *value {
+                bdd_vars.mk_true()
+            } else {
+                bdd_vars.mk_false()
+            }),
+            FnTree::Var(id) => bdd_vars
+                .var_by_name(id.as_str())
+                .map(|v| bdd_vars.mk_var(v)),
+            FnTree::PlaceholderVar(_) => None,
+            FnTree::UninterpretedFn(..) => None,
+            FnTree::Not(inner) => inner.to_bdd(bdd_vars).map(|bdd| bdd.not()),
+            FnTree::Binary(op, l, r) => {
+                let (l, r) = (l.to_bdd(bdd_vars)?, r.to_bdd(bdd_vars)?);
+                let result = match op {
+                    BinaryOp::And => l.and(&r),
+                    BinaryOp::Or => l.or(&r),
+                    BinaryOp::Xor => l.xor(&r),
+                    BinaryOp::Imp => l.imp(&r),
+                    BinaryOp::Iff => l.iff(&r),
+                };
+                Some(result)
+            }
         }
-        let mut params = HashSet::new();
-        r_parameters(self, &mut params);
-        params
+    }
+
+    /// Evaluate this expression of an uninterpreted function, given the values of its formal
+    /// arguments (placeholder variables `var0`, `var1`, ...).
+    ///
+    /// Applications of (other) uninterpreted functions have unknown values, which are propagated
+    /// using three-valued (Kleene) logic. Returns `None` if the result is not determined, i.e.,
+    /// it depends on some of these unknown values. Network variables and placeholders without
+    /// a given value are also unknown.
+    pub fn eval_placeholders(&self, arg_values: &[bool]) -> Option<bool> {
+        self.fold(&mut PartialEvaluator { arg_values })
     }
 
     /// Create a new copy of this function tree, but substitute all occurances of a given
@@ -287,30 +292,11 @@ impl FnTree {
     /// This method only considers `network variables` (the variables that appear in update
     /// functions). It ignores `placeholder variables` (that appear in uninterpreted functions).
     pub fn change_var_id(&self, old_id: &VarId, new_id: &VarId) -> FnTree {
-        match self {
-            FnTree::Const(_) => self.clone(),
-            FnTree::Var(id) => {
-                if id == old_id {
-                    FnTree::Var(new_id.clone())
-                } else {
-                    self.clone()
-                }
-            }
-            FnTree::PlaceholderVar(_) => self.clone(),
-            FnTree::UninterpretedFn(id, args) => {
-                let new_args = args
-                    .iter()
-                    .map(|it| it.change_var_id(old_id, new_id))
-                    .collect::<Vec<_>>();
-                FnTree::UninterpretedFn(id.clone(), new_args)
-            }
-            FnTree::Not(inner) => FnTree::Not(Box::new((*inner).change_var_id(old_id, new_id))),
-            FnTree::Binary(op, l, r) => FnTree::Binary(
-                *op,
-                Box::new((*l).change_var_id(old_id, new_id)),
-                Box::new((*r).change_var_id(old_id, new_id)),
-            ),
-        }
+        let mut renamer = TreeRenamer {
+            var_ids: Some((old_id, new_id)),
+            fn_ids: None,
+        };
+        self.fold(&mut renamer)
     }
 
     /// Create a new copy of this function tree, but substitute all occurances of given
@@ -320,6 +306,20 @@ impl FnTree {
     /// change will be consistent with the rest of the sketch. This is just a syntactic
     /// substitution.
     pub fn change_fn_id(&self, old_id: &UninterpretedFnId, new_id: &UninterpretedFnId) -> FnTree {
+        let mut renamer = TreeRenamer {
+            var_ids: None,
+            fn_ids: Some((old_id, new_id)),
+        };
+        self.fold(&mut renamer)
+    }
+
+    /// Create a new copy of this function tree, but reorder arguments of all applications of
+    /// the given uninterpreted function according to the `permutation`. The `i`-th argument of
+    /// each new application is the `permutation[i]`-th argument of the original one.
+    ///
+    /// You must ensure the permutation is valid with respect to the function's arity. This is
+    /// just a syntactic transformation.
+    pub fn permute_fn_args(&self, fn_id: &UninterpretedFnId, permutation: &[usize]) -> FnTree {
         match self {
             FnTree::Const(_) => self.clone(),
             FnTree::Var(_) => self.clone(),
@@ -327,19 +327,25 @@ impl FnTree {
             FnTree::UninterpretedFn(id, args) => {
                 let transformed_args = args
                     .iter()
-                    .map(|it| it.change_fn_id(old_id, new_id))
+                    .map(|it| it.permute_fn_args(fn_id, permutation))
                     .collect::<Vec<_>>();
-                if old_id == id {
-                    FnTree::UninterpretedFn(new_id.clone(), transformed_args)
+                if fn_id == id {
+                    let permuted_args = permutation
+                        .iter()
+                        .map(|&idx| transformed_args[idx].clone())
+                        .collect();
+                    FnTree::UninterpretedFn(id.clone(), permuted_args)
                 } else {
                     FnTree::UninterpretedFn(id.clone(), transformed_args)
                 }
             }
-            FnTree::Not(inner) => FnTree::Not(Box::new((*inner).change_fn_id(old_id, new_id))),
+            FnTree::Not(inner) => {
+                FnTree::Not(Box::new((*inner).permute_fn_args(fn_id, permutation)))
+            }
             FnTree::Binary(op, l, r) => FnTree::Binary(
                 *op,
-                Box::new((*l).change_fn_id(old_id, new_id)),
-                Box::new((*r).change_fn_id(old_id, new_id)),
+                Box::new((*l).permute_fn_args(fn_id, permutation)),
+                Box::new((*r).permute_fn_args(fn_id, permutation)),
             ),
         }
     }
@@ -447,11 +453,303 @@ impl FnTree {
     }
 }
 
+/// A visitor used to fold a [FnTree] bottom-up into a value of type `T` (see [FnTree::fold]).
+///
+/// Each hook is called with the already folded values of the node's children, so the
+/// implementations do not need to handle the recursion themselves.
+pub trait FnTreeFolder<T> {
+    /// Fold a Boolean constant.
+    fn fold_const(&mut self, value: bool) -> T;
+    /// Fold a network variable.
+    fn fold_var(&mut self, var_id: &VarId) -> T;
+    /// Fold a placeholder variable (formal argument of an uninterpreted function).
+    fn fold_placeholder(&mut self, var_id: &VarId) -> T;
+    /// Fold an application of uninterpreted function given its folded arguments.
+    fn fold_fn(&mut self, fn_id: &UninterpretedFnId, args: Vec<T>) -> T;
+    /// Fold a negation given its folded operand.
+    fn fold_not(&mut self, inner: T) -> T;
+    /// Fold a binary operation given its folded operands.
+    fn fold_binary(&mut self, op: BinaryOp, left: T, right: T) -> T;
+}
+
+/// Generic traversal of function expressions.
+impl FnTree {
+    /// Fold this tree bottom-up using the given [FnTreeFolder]. Children are always folded
+    /// before their parent (arguments of uninterpreted functions from left to right, and left
+    /// operands of binary operations before the right ones).
+    pub fn fold<T>(&self, folder: &mut impl FnTreeFolder<T>) -> T {
+        match self {
+            FnTree::Const(value) => folder.fold_const(*value),
+            FnTree::Var(id) => folder.fold_var(id),
+            FnTree::PlaceholderVar(id) => folder.fold_placeholder(id),
+            FnTree::UninterpretedFn(id, args) => {
+                let folded_args = args.iter().map(|arg| arg.fold(folder)).collect();
+                folder.fold_fn(id, folded_args)
+            }
+            FnTree::Not(inner) => {
+                let folded_inner = inner.fold(folder);
+                folder.fold_not(folded_inner)
+            }
+            FnTree::Binary(op, l, r) => {
+                let folded_l = l.fold(folder);
+                let folded_r = r.fold(folder);
+                folder.fold_binary(*op, folded_l, folded_r)
+            }
+        }
+    }
+}
+
+/// **(internal)** Folder collecting all variables (both network and placeholder variables)
+/// and all uninterpreted function symbols used in a tree.
+#[derive(Default)]
+struct SymbolCollector {
+    vars: HashSet<VarId>,
+    fn_symbols: HashSet<UninterpretedFnId>,
+}
+
+impl FnTreeFolder<()> for SymbolCollector {
+    fn fold_const(&mut self, _value: bool) {}
+
+    fn fold_var(&mut self, var_id: &VarId) {
+        self.vars.insert(var_id.clone());
+    }
+
+    fn fold_placeholder(&mut self, var_id: &VarId) {
+        self.vars.insert(var_id.clone());
+    }
+
+    fn fold_fn(&mut self, fn_id: &UninterpretedFnId, _args: Vec<()>) {
+        self.fn_symbols.insert(fn_id.clone());
+    }
+
+    fn fold_not(&mut self, _inner: ()) {}
+
+    fn fold_binary(&mut self, _op: BinaryOp, _left: (), _right: ()) {}
+}
+
+/// **(internal)** Folder evaluating a tree in three-valued logic, given the values of
+/// placeholder variables (see [FnTree::eval_placeholders]). Unknown values are `None`.
+struct PartialEvaluator<'a> {
+    arg_values: &'a [bool],
+}
+
+impl FnTreeFolder<Option<bool>> for PartialEvaluator<'_> {
+    fn fold_const(&mut self, value: bool) -> Option<bool> {
+        Some(value)
+    }
+
+    fn fold_var(&mut self, _var_id: &VarId) -> Option<bool> {
+        None
+    }
+
+    fn fold_placeholder(&mut self, var_id: &VarId) -> Option<bool> {
+        let index = var_id.as_str().strip_prefix("var")?.parse::<usize>().ok()?;
+        self.arg_values.get(index).copied()
+    }
+
+    fn fold_fn(&mut self, _fn_id: &UninterpretedFnId, _args: Vec<Option<bool>>) -> Option<bool> {
+        None
+    }
+
+    fn fold_not(&mut self, inner: Option<bool>) -> Option<bool> {
+        inner.map(|value| !value)
+    }
+
+    fn fold_binary(
+        &mut self,
+        op: BinaryOp,
+        left: Option<bool>,
+        right: Option<bool>,
+    ) -> Option<bool> {
+        match (op, left, right) {
+            (BinaryOp::And, Some(false), _) | (BinaryOp::And, _, Some(false)) => Some(false),
+            (BinaryOp::Or, Some(true), _) | (BinaryOp::Or, _, Some(true)) => Some(true),
+            (BinaryOp::Imp, Some(false), _) | (BinaryOp::Imp, _, Some(true)) => Some(true),
+            (_, Some(l), Some(r)) => Some(match op {
+                BinaryOp::And => l && r,
+                BinaryOp::Or => l || r,
+                BinaryOp::Xor => l ^ r,
+                BinaryOp::Imp => !l || r,
+                BinaryOp::Iff => l == r,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// **(internal)** Folder building a copy of a tree, with the given network variable and/or
+/// uninterpreted function renamed (as `(old ID, new ID)` pairs). Placeholder variables are
+/// never renamed.
+struct TreeRenamer<'a> {
+    var_ids: Option<(&'a VarId, &'a VarId)>,
+    fn_ids: Option<(&'a UninterpretedFnId, &'a UninterpretedFnId)>,
+}
+
+impl FnTreeFolder<FnTree> for TreeRenamer<'_> {
+    fn fold_const(&mut self, value: bool) -> FnTree {
+        FnTree::Const(value)
+    }
+
+    fn fold_var(&mut self, var_id: &VarId) -> FnTree {
+        match self.var_ids {
+            Some((old_id, new_id)) if old_id == var_id => FnTree::Var(new_id.clone()),
+            _ => FnTree::Var(var_id.clone()),
+        }
+    }
+
+    fn fold_placeholder(&mut self, var_id: &VarId) -> FnTree {
+        FnTree::PlaceholderVar(var_id.clone())
+    }
+
+    fn fold_fn(&mut self, fn_id: &UninterpretedFnId, args: Vec<FnTree>) -> FnTree {
+        match self.fn_ids {
+            Some((old_id, new_id)) if old_id == fn_id => {
+                FnTree::UninterpretedFn(new_id.clone(), args)
+            }
+            _ => FnTree::UninterpretedFn(fn_id.clone(), args),
+        }
+    }
+
+    fn fold_not(&mut self, inner: FnTree) -> FnTree {
+        FnTree::Not(Box::new(inner))
+    }
+
+    fn fold_binary(&mut self, op: BinaryOp, left: FnTree, right: FnTree) -> FnTree {
+        FnTree::Binary(op, Box::new(left), Box::new(right))
+    }
+}
+
+/// Simplification of function expressions.
+impl FnTree {
+    /// Create a simplified copy of this function tree. The simplified tree is semantically
+    /// equivalent to the original one.
+    ///
+    /// We apply standard Boolean simplifications bottom-up: constant folding, double-negation
+    /// elimination, idempotence, complementation (`x & !x`), and absorption (`x & (x | y)`).
+    /// Applications of uninterpreted functions (including their arguments) are left untouched.
+    pub fn simplify(&self) -> FnTree {
+        match self {
+            FnTree::Const(_) | FnTree::Var(_) | FnTree::PlaceholderVar(_) => self.clone(),
+            FnTree::UninterpretedFn(..) => self.clone(),
+            FnTree::Not(inner) => Self::simplify_not(inner.simplify()),
+            FnTree::Binary(op, l, r) => Self::simplify_binary(*op, l.simplify(), r.simplify()),
+        }
+    }
+
+    /// **(internal)** Simplify negation of an (already simplified) tree.
+    fn simplify_not(inner: FnTree) -> FnTree {
+        match inner {
+            FnTree::Const(value) => FnTree::Const(!value),
+            FnTree::Not(inner_inner) => *inner_inner,
+            other => FnTree::Not(Box::new(other)),
+        }
+    }
+
+    /// **(internal)** Simplify binary operation applied to two (already simplified) trees.
+    fn simplify_binary(op: BinaryOp, l: FnTree, r: FnTree) -> FnTree {
+        // constant folding (for both constant and non-constant operands)
+        match (op, &l, &r) {
+            (BinaryOp::And, FnTree::Const(true), _) => return r,
+            (BinaryOp::And, _, FnTree::Const(true)) => return l,
+            (BinaryOp::And, FnTree::Const(false), _) | (BinaryOp::And, _, FnTree::Const(false)) => {
+                return FnTree::Const(false)
+            }
+            (BinaryOp::Or, FnTree::Const(false), _) => return r,
+            (BinaryOp::Or, _, FnTree::Const(false)) => return l,
+            (BinaryOp::Or, FnTree::Const(true), _) | (BinaryOp::Or, _, FnTree::Const(true)) => {
+                return FnTree::Const(true)
+            }
+            (BinaryOp::Xor, FnTree::Const(false), _) => return r,
+            (BinaryOp::Xor, _, FnTree::Const(false)) => return l,
+            (BinaryOp::Xor, FnTree::Const(true), _) => return Self::simplify_not(r),
+            (BinaryOp::Xor, _, FnTree::Const(true)) => return Self::simplify_not(l),
+            (BinaryOp::Iff, FnTree::Const(true), _) => return r,
+            (BinaryOp::Iff, _, FnTree::Const(true)) => return l,
+            (BinaryOp::Iff, FnTree::Const(false), _) => return Self::simplify_not(r),
+            (BinaryOp::Iff, _, FnTree::Const(false)) => return Self::simplify_not(l),
+            (BinaryOp::Imp, FnTree::Const(false), _) | (BinaryOp::Imp, _, FnTree::Const(true)) => {
+                return FnTree::Const(true)
+            }
+            (BinaryOp::Imp, FnTree::Const(true), _) => return r,
+            (BinaryOp::Imp, _, FnTree::Const(false)) => return Self::simplify_not(l),
+            _ => {}
+        }
+
+        // idempotence (and similar rules for identical operands)
+        if l == r {
+            return match op {
+                BinaryOp::And | BinaryOp::Or => l,
+                BinaryOp::Xor => FnTree::Const(false),
+                BinaryOp::Imp | BinaryOp::Iff => FnTree::Const(true),
+            };
+        }
+
+        // complementation (operand and its negation)
+        if l.is_negation_of(&r) || r.is_negation_of(&l) {
+            match op {
+                BinaryOp::And | BinaryOp::Iff => return FnTree::Const(false),
+                BinaryOp::Or | BinaryOp::Xor => return FnTree::Const(true),
+                BinaryOp::Imp => return r,
+            }
+        }
+
+        // absorption
+        if (op == BinaryOp::And && (r.has_operand(BinaryOp::Or, &l)))
+            || (op == BinaryOp::Or && (r.has_operand(BinaryOp::And, &l)))
+        {
+            return l;
+        }
+        if (op == BinaryOp::And && (l.has_operand(BinaryOp::Or, &r)))
+            || (op == BinaryOp::Or && (l.has_operand(BinaryOp::And, &r)))
+        {
+            return r;
+        }
+
+        FnTree::Binary(op, Box::new(l), Box::new(r))
+    }
+
+    /// **(internal)** Check if this tree is a negation of the given tree.
+    fn is_negation_of(&self, other: &FnTree) -> bool {
+        matches!(self, FnTree::Not(inner) if inner.as_ref() == other)
+    }
+
+    /// **(internal)** Check if this tree is a binary operation `op` with `operand` as one of
+    /// its (direct) operands.
+    fn has_operand(&self, op: BinaryOp, operand: &FnTree) -> bool {
+        matches!(self, FnTree::Binary(inner_op, l, r) if *inner_op == op && (l.as_ref() == operand || r.as_ref() == operand))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::sketchbook::model::{FnTree, ModelState};
+    use crate::sketchbook::ids::{UninterpretedFnId, VarId};
+    use crate::sketchbook::model::{BinaryOp, FnTree, FnTreeFolder, ModelState};
     use std::collections::HashSet;
 
+    /// Simple folder computing the depth of a tree.
+    struct DepthFolder;
+
+    impl FnTreeFolder<usize> for DepthFolder {
+        fn fold_const(&mut self, _value: bool) -> usize {
+            1
+        }
+        fn fold_var(&mut self, _var_id: &VarId) -> usize {
+            1
+        }
+        fn fold_placeholder(&mut self, _var_id: &VarId) -> usize {
+            1
+        }
+        fn fold_fn(&mut self, _fn_id: &UninterpretedFnId, args: Vec<usize>) -> usize {
+            args.into_iter().max().unwrap_or(0) + 1
+        }
+        fn fold_not(&mut self, inner: usize) -> usize {
+            inner + 1
+        }
+        fn fold_binary(&mut self, _op: BinaryOp, left: usize, right: usize) -> usize {
+            left.max(right) + 1
+        }
+    }
+
     #[test]
     /// Test parsing of a valid update function's expression.
     fn test_valid_update_fn() {
@@ -566,6 +864,60 @@ mod tests {
         assert_eq!(modified_tree.to_string(&model, None), "a & g(a)");
     }
 
+    #[test]
+    /// Test folding trees using a custom folder, and the collection methods based on folding.
+    fn test_fold() {
+        let mut model = ModelState::new_with_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("f", "f", 2)
+            .unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("g", "g", 1)
+            .unwrap();
+
+        let fn_tree = FnTree::try_from_str("a | !f(b, g(true))", &model, None).unwrap();
+        assert_eq!(fn_tree.fold(&mut DepthFolder), 5);
+        assert_eq!(FnTree::Const(false).fold(&mut DepthFolder), 1);
+
+        let expected_vars = HashSet::from([VarId::new("a").unwrap(), VarId::new("b").unwrap()]);
+        assert_eq!(fn_tree.collect_variables(), expected_vars);
+        let expected_fns = HashSet::from([
+            UninterpretedFnId::new("f").unwrap(),
+            UninterpretedFnId::new("g").unwrap(),
+        ]);
+        assert_eq!(fn_tree.collect_fn_symbols(), expected_fns);
+    }
+
+    #[test]
+    /// Test simplification of function expressions.
+    fn test_simplify() {
+        let mut model = ModelState::new_with_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("f", "f", 1)
+            .unwrap();
+
+        let cases = [
+            ("a & true", "a"),
+            ("!!a | false", "a"),
+            ("a ^ true", "!a"),
+            ("false => a", "true"),
+            ("a => false", "!a"),
+            ("(a & b) | (a & b)", "a & b"),
+            ("a & !a", "false"),
+            ("a | !a", "true"),
+            ("a & (a | b)", "a"),
+            ("(a & b) | b", "b"),
+            ("!(a <=> a) | b", "b"),
+            ("f(a & true) | false", "f(a & true)"),
+            ("a & f(b)", "a & f(b)"),
+        ];
+        for (expression, expected) in cases {
+            let fn_tree = FnTree::try_from_str(expression, &model, None).unwrap();
+            let simplified = fn_tree.simplify().to_string(&model, None);
+            assert_eq!(simplified.as_str(), expected);
+        }
+    }
+
     #[test]
     /// Test collecting function symbols from function's expression.
     fn test_collect_fns() {
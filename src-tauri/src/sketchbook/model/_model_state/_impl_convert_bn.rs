@@ -1,5 +1,5 @@
 use crate::sketchbook::model::ModelState;
-use biodivine_lib_param_bn::{BooleanNetwork, RegulatoryGraph};
+use biodivine_lib_param_bn::{BooleanNetwork, FnUpdate, RegulatoryGraph};
 
 /// Methods for converting between `ModelState` and `BooleanNetwork` (from the `lib-param-bn`).
 impl ModelState {
@@ -11,6 +11,7 @@ impl ModelState {
     /// - `regulation_types`: include properties of regulations (monotonicity, essentiality)
     /// - `parameters`: include uninterpreted functions as network parameters
     /// - `update_fns`: include update function expressions
+    /// - `constant_inputs`: treat input variables as free constants (see [Self::to_bn_with_plain_regulations])
     ///
     /// It is up to you to make the selection reasonable (e.g., when including update functions
     /// that contain parameters, you must also include parameters, and so on...).
@@ -21,6 +22,7 @@ impl ModelState {
         regulation_types: bool,
         parameters: bool,
         update_fns: bool,
+        constant_inputs: bool,
     ) -> Result<BooleanNetwork, String> {
         // First create the regulatory graph base (variables and regulations).
         let reg_graph = if regulation_types {
@@ -49,7 +51,14 @@ impl ModelState {
             let fn_expressions_mapping = self.propagate_expressions_through_uninterpreted_fns()?;
 
             for (var_id, update_fn) in self.update_fns.iter() {
-                if !update_fn.has_empty_expression() {
+                if constant_inputs && self.get_variable(var_id)?.is_input() {
+                    continue;
+                }
+                if let Some(value) = update_fn.as_constant() {
+                    // Constants are encoded directly as tautology/contradiction (no parameters)
+                    let var = bn.as_graph().find_variable(var_id.as_str()).unwrap();
+                    bn.set_update_function(var, Some(FnUpdate::Const(value)))?;
+                } else if !update_fn.has_empty_expression() {
                     // Substitute all function symbols with their expressions, if they are specified
                     let transformed_update_fn_tree = self
                         .substitute_expressions_to_update_fn(update_fn, &fn_expressions_mapping)?;
@@ -57,6 +66,26 @@ impl ModelState {
                     bn.add_string_update_function(var_id.as_str(), &transformed_expression)?;
                 }
             }
+
+            // Inputs get an identity update function (which requires a self-regulation), so that
+            // their value never changes and they do not introduce any parameters.
+            if constant_inputs {
+                for (var_id, variable) in self.variables.iter() {
+                    if !variable.is_input() {
+                        continue;
+                    }
+                    let var = bn.as_graph().find_variable(var_id.as_str()).unwrap();
+                    if bn.as_graph().find_regulation(var, var).is_none() {
+                        bn.as_graph_mut().add_regulation(
+                            var_id.as_str(),
+                            var_id.as_str(),
+                            false,
+                            None,
+                        )?;
+                    }
+                    bn.set_update_function(var, Some(FnUpdate::Var(var)))?;
+                }
+            }
         }
         Ok(bn)
     }
@@ -73,7 +102,7 @@ impl ModelState {
     /// logical expressions and need to verify that variables/functions are valid.
     pub fn to_bn_with_empty_updates(&self) -> BooleanNetwork {
         // this is a safe combination that cannot result in errors
-        self.to_bn_internal(true, true, false).unwrap()
+        self.to_bn_internal(true, true, false, false).unwrap()
     }
 
     /// Generate a "fake" `BooleanNetwork` containing a set of "placeholder" variables named
@@ -119,7 +148,15 @@ impl ModelState {
     /// Such details might get lost during the conversion.
     pub fn to_bn(&self) -> BooleanNetwork {
         // this is a safe combination that cannot result in errors
-        self.to_bn_internal(true, true, true).unwrap()
+        self.to_bn_internal(true, true, true, false).unwrap()
+    }
+
+    /// Convert the `ModelState` into a corresponding `BooleanNetwork` object, the same way as
+    /// [Self::to_bn], but with input variables treated as free constants (see
+    /// [Self::to_bn_with_plain_regulations]).
+    pub fn to_bn_with_constant_inputs(&self) -> BooleanNetwork {
+        // this is a safe combination that cannot result in errors
+        self.to_bn_internal(true, true, true, true).unwrap()
     }
 
     /// Convert the `ModelState` into the corresponding `BooleanNetwork` object (that will contain
@@ -134,9 +171,14 @@ impl ModelState {
     ///
     /// If some uninterpreted functions have specified expressions, they will be propagated into
     /// update functions directly. This can make some parameters redundant (already substituted).
+    ///
+    /// Input variables (see [crate::sketchbook::model::Variable::is_input]) are treated as free
+    /// Boolean constants. Their update expressions are ignored, and they get an identity update
+    /// function instead (adding a self-regulation if necessary). Thus, inputs do not introduce
+    /// any parameters, and their value is fixed by the initial state.
     pub fn to_bn_with_plain_regulations(&self) -> BooleanNetwork {
         // this is a safe combination that cannot result in errors
-        self.to_bn_internal(false, true, true).unwrap()
+        self.to_bn_internal(false, true, true, true).unwrap()
     }
 }
 
@@ -252,6 +294,57 @@ mod tests {
         assert_eq!(bn.get_update_function(var_b), &None);
     }
 
+    /// Test that input variables are converted into constants with identity update functions,
+    /// and that they do not introduce any parameters.
+    #[test]
+    fn test_to_bn_with_inputs() {
+        let mut model = prepare_test_model_full();
+        let var_b = model.get_var_id("b").unwrap();
+        model.set_var_input(&var_b, true).unwrap();
+        let bn = model.to_bn_with_plain_regulations();
+        let bn_var_a = bn.as_graph().find_variable("a").unwrap();
+        let bn_var_b = bn.as_graph().find_variable("b").unwrap();
+
+        // input `b` depends only on itself (`a -> b` is kept, but not used)
+        let update_b = bn.get_update_function(bn_var_b).as_ref().unwrap();
+        assert_eq!(update_b.to_string(&bn), "b");
+        assert_eq!(bn.regulators(bn_var_b), vec![bn_var_a, bn_var_b]);
+        assert!(bn.get_update_function(bn_var_a).is_some());
+
+        // the same update expression is ignored for inputs
+        let var_a = model.get_var_id("a").unwrap();
+        model.set_var_input(&var_a, true).unwrap();
+        let bn = model
+            .to_bn_with_plain_regulations()
+            .prune_unused_parameters();
+        assert_eq!(bn.num_parameters(), 0);
+        let update_a = bn.get_update_function(bn_var_a).as_ref().unwrap();
+        assert_eq!(update_a.to_string(&bn), "a");
+
+        // the plain conversion is not affected
+        assert_eq!(model.to_bn().get_update_function(bn_var_b), &None);
+    }
+
+    /// Test that constant update functions are converted into constants directly, without
+    /// introducing any parameters.
+    #[test]
+    fn test_to_bn_with_constants() {
+        let mut model = prepare_test_model_full();
+        let var_a = model.get_var_id("a").unwrap();
+        let var_b = model.get_var_id("b").unwrap();
+        model.set_update_fn_constant(&var_a, true).unwrap();
+        model.set_update_fn_constant(&var_b, false).unwrap();
+
+        let bn = model.to_bn().prune_unused_parameters();
+        assert_eq!(bn.num_parameters(), 0);
+        let bn_var_a = bn.as_graph().find_variable("a").unwrap();
+        let bn_var_b = bn.as_graph().find_variable("b").unwrap();
+        let update_a = bn.get_update_function(bn_var_a).as_ref().unwrap();
+        let update_b = bn.get_update_function(bn_var_b).as_ref().unwrap();
+        assert_eq!(update_a.to_string(&bn), "true");
+        assert_eq!(update_b.to_string(&bn), "false");
+    }
+
     #[test]
     fn test_to_fake_bn() {
         let model = prepare_test_model_full();
@@ -0,0 +1,206 @@
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::model::ModelState;
+use biodivine_lib_param_bn::FnUpdate;
+
+/// **(internal)** Identifiers that cannot be used as variable names in `.bnet`, since they are
+/// interpreted as Boolean constants when the file is imported.
+const RESERVED_BNET_NAMES: [&str; 4] = ["true", "True", "false", "False"];
+
+/// Methods for exporting `ModelState` into the `.bnet` format (the BoolNet format).
+impl ModelState {
+    /// Export this model into the `.bnet` format. Each variable is written on a separate line,
+    /// together with its update function expression. Expressions of uninterpreted functions are
+    /// substituted into update functions (same as in [Self::to_bn]).
+    ///
+    /// Since `.bnet` cannot represent parameters, an error is returned if some update function is
+    /// unspecified, or if it uses uninterpreted functions without a concrete expression. To export
+    /// such model anyway, use [Self::to_bnet_with_placeholders]. An error is also returned if some
+    /// variable ID is not a `.bnet`-legal identifier.
+    ///
+    /// Note that `.bnet` does not cover regulation types, names, annotations, or layouts, so this
+    /// information is lost (regulations are reconstructed from update functions during import).
+    pub fn to_bnet(&self) -> Result<String, String> {
+        self.to_bnet_internal(false).map(|(bnet, _)| bnet)
+    }
+
+    /// Export this model into the `.bnet` format, the same way as [Self::to_bnet], but update
+    /// functions that cannot be represented in `.bnet` (unspecified ones, and those using
+    /// uninterpreted functions without a concrete expression) are replaced by an identity
+    /// placeholder (the variable keeps its value).
+    ///
+    /// Returns the `.bnet` string, and a list of messages describing the information lost by
+    /// the substitution (one per affected variable).
+    pub fn to_bnet_with_placeholders(&self) -> Result<(String, Vec<String>), String> {
+        self.to_bnet_internal(true)
+    }
+
+    /// **(internal)** Export this model into the `.bnet` format. If `use_placeholders` is set,
+    /// update functions with parameters are substituted with identities, otherwise an error
+    /// is returned for them. The list of substituted functions is returned with the result.
+    fn to_bnet_internal(&self, use_placeholders: bool) -> Result<(String, Vec<String>), String> {
+        let mut var_ids: Vec<&VarId> = self.variables.keys().collect();
+        var_ids.sort();
+        for var_id in var_ids.iter() {
+            assert_bnet_identifier(var_id.as_str())?;
+        }
+
+        let mut bn = self.to_bn();
+        let mut lost_info = Vec::new();
+        for var_id in var_ids {
+            let var = bn.as_graph().find_variable(var_id.as_str()).unwrap();
+            let issue = match bn.get_update_function(var) {
+                None => Some(format!("Update function of `{var_id}` is unspecified")),
+                Some(update_fn) => {
+                    let mut fn_names: Vec<String> = update_fn
+                        .collect_parameters()
+                        .into_iter()
+                        .map(|p| bn.get_parameter(p).get_name().clone())
+                        .collect();
+                    if fn_names.is_empty() {
+                        None
+                    } else {
+                        fn_names.sort();
+                        Some(format!(
+                            "Update function of `{var_id}` uses uninterpreted functions without \
+                            expression ({})",
+                            fn_names.join(", ")
+                        ))
+                    }
+                }
+            };
+
+            if let Some(issue) = issue {
+                if !use_placeholders {
+                    return Err(format!(
+                        "{issue}, which cannot be represented in `.bnet`. Specify it first, or \
+                        export the model with placeholders."
+                    ));
+                }
+                // the identity placeholder requires a self-regulation
+                if bn.as_graph().find_regulation(var, var).is_none() {
+                    bn.as_graph_mut().add_regulation(
+                        var_id.as_str(),
+                        var_id.as_str(),
+                        false,
+                        None,
+                    )?;
+                }
+                bn.set_update_function(var, Some(FnUpdate::Var(var)))?;
+                lost_info.push(format!("{issue}, replaced by identity."));
+            }
+        }
+
+        // all update functions are now fully specified, so no parameters are used
+        let bnet = bn.prune_unused_parameters().to_bnet(false)?;
+        Ok((bnet, lost_info))
+    }
+}
+
+/// **(internal)** Check that the given variable ID can be used as an identifier in `.bnet`, i.e.,
+/// it is a C-like identifier that cannot be confused with a constant or with the header line.
+fn assert_bnet_identifier(var_id: &str) -> Result<(), String> {
+    let is_c_like = var_id.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && var_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_c_like || RESERVED_BNET_NAMES.contains(&var_id) || var_id.eq_ignore_ascii_case("targets")
+    {
+        return Err(format!(
+            "Variable `{var_id}` cannot be exported to `.bnet`, as it is not a valid `.bnet` \
+            identifier. Please rename it first."
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::model::ModelState;
+    use biodivine_lib_param_bn::BooleanNetwork;
+
+    /// Prepare a fully specified test model with variables `a`, `b`, `c`, and a function
+    /// symbol `f` with an expression.
+    fn prepare_specified_model() -> ModelState {
+        let mut model =
+            ModelState::new_with_vars(vec![("a", "a"), ("b", "b"), ("c", "c")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -> b", "b -> a", "a -| a", "c -> a", "b -| c"])
+            .unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("f", "f", 2)
+            .unwrap();
+        model
+            .set_uninterpreted_fn_expression_by_str("f", "var0 => var1")
+            .unwrap();
+        model
+            .set_update_fn(&model.get_var_id("a").unwrap(), "(b & !a) | f(a, c)")
+            .unwrap();
+        model
+            .set_update_fn(&model.get_var_id("b").unwrap(), "a")
+            .unwrap();
+        model
+            .set_update_fn(&model.get_var_id("c").unwrap(), "!b")
+            .unwrap();
+        model
+    }
+
+    #[test]
+    fn test_to_bnet_round_trip() {
+        let model = prepare_specified_model();
+        let bnet = model.to_bnet().unwrap();
+        assert!(bnet.starts_with("targets,factors\n"));
+
+        // the imported model has the same variables and (equivalent) update functions
+        let bn = BooleanNetwork::try_from_bnet(&bnet).unwrap();
+        let imported_model = ModelState::from_bn(&bn).unwrap();
+        assert_eq!(imported_model.to_bnet().unwrap(), bnet);
+        assert_eq!(imported_model.num_vars(), 3);
+        assert_eq!(imported_model.num_uninterpreted_fns(), 0);
+        let original_bn = model.to_bn();
+        for var in original_bn.variables() {
+            let name = original_bn.get_variable_name(var);
+            let imported_var = bn.as_graph().find_variable(name).unwrap();
+            assert_eq!(
+                original_bn.regulators(var),
+                bn.regulators(imported_var),
+                "regulators of `{name}` differ"
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_bnet_with_parameters() {
+        let mut model = prepare_specified_model();
+        let var_a = model.get_var_id("a").unwrap();
+        let var_c = model.get_var_id("c").unwrap();
+        model
+            .set_uninterpreted_fn_expression_by_str("f", "")
+            .unwrap();
+        model.set_update_fn(&var_c, "").unwrap();
+        assert!(model.to_bnet().is_err());
+
+        let (bnet, lost_info) = model.to_bnet_with_placeholders().unwrap();
+        assert_eq!(lost_info.len(), 2);
+        assert!(lost_info[0].contains("`a`") && lost_info[0].contains("(f)"));
+        assert!(lost_info[1].contains("`c`") && lost_info[1].contains("unspecified"));
+        assert!(bnet.contains("\na, a\n"));
+        assert!(bnet.contains("\nc, c\n"));
+        assert!(BooleanNetwork::try_from_bnet(&bnet).is_ok());
+
+        // once specified again, no placeholders are needed
+        model.set_update_fn(&var_a, "b").unwrap();
+        model.set_update_fn(&var_c, "b").unwrap();
+        let (bnet, lost_info) = model.to_bnet_with_placeholders().unwrap();
+        assert!(lost_info.is_empty());
+        assert_eq!(bnet, model.to_bnet().unwrap());
+    }
+
+    #[test]
+    fn test_to_bnet_invalid_names() {
+        for invalid_id in ["true", "False", "targets", "Targets"] {
+            let model = ModelState::new_with_vars(vec![(invalid_id, "v")]).unwrap();
+            assert!(model.to_bnet().is_err());
+            assert!(model.to_bnet_with_placeholders().is_err());
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity, Regulation};
+
+/// Methods for exporting the regulatory graph of `ModelState` into the Graphviz DOT format.
+impl ModelState {
+    /// Export the regulatory graph of this model into the Graphviz DOT format.
+    ///
+    /// There is one node per variable (keyed by its ID, labeled by its name), and one edge per
+    /// regulation. The arrowhead of an edge is given by the regulation's monotonicity (`normal`
+    /// for activation, `tee` for inhibition, `odot` for dual, and `none` for unknown), essential
+    /// regulations are drawn solid, the rest dashed. Node positions from the default layout are
+    /// embedded as `pos` attributes (with the y-axis flipped, as DOT has it pointing upwards).
+    ///
+    /// Variables and regulations are listed in a sorted order, so the output is deterministic.
+    /// Update functions, uninterpreted functions, and annotations are not included.
+    pub fn to_dot(&self) -> String {
+        let layout = self.get_default_layout();
+        let mut lines = vec!["digraph G {".to_string()];
+
+        let mut variables: Vec<(&VarId, &str)> = self
+            .variables()
+            .map(|(var_id, var)| (var_id, var.get_name()))
+            .collect();
+        variables.sort();
+        for (var_id, name) in variables {
+            let mut attributes = vec![format!("label=\"{}\"", escape_dot_string(name))];
+            if let Ok(position) = layout.get_node_position(var_id) {
+                attributes.push(format!("pos=\"{},{}\"", position.0, 0.0 - position.1));
+            }
+            lines.push(format!(
+                "    \"{}\" [{}];",
+                escape_dot_string(var_id.as_str()),
+                attributes.join(", ")
+            ));
+        }
+
+        let mut regulations: Vec<&Regulation> = self.regulations().collect();
+        regulations.sort_by_key(|r| (r.get_regulator(), r.get_target()));
+        for regulation in regulations {
+            let arrowhead = match regulation.get_sign() {
+                Monotonicity::Activation => "normal",
+                Monotonicity::Inhibition => "tee",
+                Monotonicity::Dual => "odot",
+                Monotonicity::Unknown => "none",
+            };
+            let style = match regulation.get_essentiality() {
+                Essentiality::True => "solid",
+                Essentiality::False | Essentiality::Unknown => "dashed",
+            };
+            lines.push(format!(
+                "    \"{}\" -> \"{}\" [arrowhead={arrowhead}, style={style}];",
+                escape_dot_string(regulation.get_regulator().as_str()),
+                escape_dot_string(regulation.get_target().as_str()),
+            ));
+        }
+
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+/// **(internal)** Escape a string so that it can be used inside a quoted DOT identifier.
+fn escape_dot_string(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::VarId;
+    use crate::sketchbook::model::ModelState;
+
+    /// Test exporting the regulatory graph into DOT, including escaping of names and layout
+    /// positions.
+    #[test]
+    fn test_to_dot() {
+        let mut model = ModelState::new_with_vars(vec![("a", "Var \"A\""), ("b", "b")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -> b", "b -|X a", "a -*? a", "b -?? b"])
+            .unwrap();
+        let var_b = VarId::new("b").unwrap();
+        let layout_id = ModelState::get_default_layout_id();
+        model
+            .update_position(&layout_id, &var_b, 10.5, 20.0)
+            .unwrap();
+
+        let expected = [
+            "digraph G {",
+            "    \"a\" [label=\"Var \\\"A\\\"\", pos=\"0,0\"];",
+            "    \"b\" [label=\"b\", pos=\"10.5,-20\"];",
+            "    \"a\" -> \"a\" [arrowhead=odot, style=dashed];",
+            "    \"a\" -> \"b\" [arrowhead=normal, style=solid];",
+            "    \"b\" -> \"a\" [arrowhead=tee, style=dashed];",
+            "    \"b\" -> \"b\" [arrowhead=none, style=dashed];",
+            "}",
+        ]
+        .join("\n");
+        assert_eq!(model.to_dot(), expected);
+    }
+}
@@ -8,6 +8,8 @@ use crate::sketchbook::model::{
 use crate::sketchbook::utils::assert_ids_unique;
 use std::collections::{HashMap, HashSet};
 
+use super::ParseCache;
+
 /// Methods for safely constructing or mutating instances of `ModelState`.
 ///
 /// These methods focus on general manipulation with variables/regulations.
@@ -25,6 +27,7 @@ impl ModelState {
             update_fns: HashMap::new(),
             uninterpreted_fns: HashMap::new(),
             layouts: HashMap::from([(default_layout_id, default_layout)]),
+            parse_cache: ParseCache::default(),
         }
     }
 
@@ -37,7 +40,9 @@ impl ModelState {
         let mut variables_map = HashMap::with_capacity(model_data.variables.len());
         for var_data in &model_data.variables {
             let var_id = VarId::new(&var_data.id)?;
-            let var_instance = Variable::new(&var_data.name)?.with_annotation(&var_data.annotation);
+            let var_instance = Variable::new(&var_data.name)?
+                .with_annotation(&var_data.annotation)
+                .with_input(var_data.is_input);
             if variables_map.insert(var_id.clone(), var_instance).is_some() {
                 return Err(format!(
                     "Variable with id {var_id} already exists (id must be unique)."
@@ -97,10 +102,16 @@ impl ModelState {
                     "There cant be regulations with invalid variables ({regulator}, {target})."
                 ));
             }
-            regulations_set.insert(Regulation::new(regulator, target, r.essential, r.sign));
+            let regulation = Regulation::new(regulator, target, r.essential, r.sign)
+                .with_annotation(&r.annotation);
+            regulations_set.insert(regulation);
             Ok(())
         })?;
 
+        // Before parsing any expressions, check that all applied function symbols are declared
+        // (with correct arity), so that we can report all such issues at once
+        Self::assert_fn_symbols_declared(model_data)?;
+
         // Build the base model and fill it with the data, before parsing the update
         // function expressions and fully processing uninterpreted functions
         let mut model = ModelState::new_empty();
@@ -114,7 +125,9 @@ impl ModelState {
         // already checked for validity and uniqueness
         for var_data in &model_data.variables {
             let var_id = VarId::new(var_data.id.as_str())?;
-            let parsed_update_fn = UpdateFn::try_from_str(var_data.update_fn.as_str(), &model)?;
+            let parsed = model.parse_expression_cached(&var_data.update_fn, None)?;
+            let mut parsed_update_fn = UpdateFn::from_parsed(parsed);
+            parsed_update_fn.set_frozen(var_data.frozen);
             model.update_fns.insert(var_id, parsed_update_fn);
         }
 
@@ -139,6 +152,53 @@ impl ModelState {
         Ok(model)
     }
 
+    /// **(internal)** Check that all function symbols applied in the update-function and
+    /// uninterpreted-function expressions of `model_data` are declared in its
+    /// `uninterpreted_fns`, with arity matching the number of arguments.
+    ///
+    /// All undeclared and arity-mismatched symbols are collected, and reported together in
+    /// a single error (one issue per line).
+    fn assert_fn_symbols_declared(model_data: &ModelData) -> Result<(), String> {
+        let declared_arities: HashMap<&str, usize> = model_data
+            .uninterpreted_fns
+            .iter()
+            .map(|f| (f.id.as_str(), f.arguments.len()))
+            .collect();
+        let update_expressions = model_data
+            .variables
+            .iter()
+            .map(|v| (format!("update function of `{}`", v.id), &v.update_fn));
+        let fn_expressions = model_data
+            .uninterpreted_fns
+            .iter()
+            .map(|f| (format!("expression of function `{}`", f.id), &f.expression));
+
+        let mut issues = Vec::new();
+        for (location, expression) in update_expressions.chain(fn_expressions) {
+            for (fn_id, num_args) in collect_fn_applications(expression) {
+                let issue = match declared_arities.get(fn_id.as_str()) {
+                    None => format!("The {location} applies undeclared function `{fn_id}`."),
+                    Some(arity) if *arity != num_args => format!(
+                        "The {location} applies function `{fn_id}` to {num_args} arguments, but it has arity {arity}."
+                    ),
+                    Some(_) => continue,
+                };
+                if !issues.contains(&issue) {
+                    issues.push(issue);
+                }
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Invalid function symbols in the model:\n{}",
+                issues.join("\n")
+            ))
+        }
+    }
+
     /// Create new `ModelState` with variables given by provided ID-name pairs (both strings).
     /// All variables have default (empty) update functions and empty annotations.
     /// Resulting model will contain no `UninterpretedFns` or `Regulations`, and a single
@@ -350,14 +410,22 @@ impl ModelState {
     /// The variables in the given string must be valid ID strings for this `ModelState`.
     ///
     /// Returns `Err` when the string does not encode a valid regulation, if the provided variables
-    /// are not valid variable IDs, or when the regulation between the two variables already exists.
+    /// are not valid variable IDs, when the regulation between the two variables already exists,
+    /// or when the list itself contains multiple regulations between the same two variables (even
+    /// if they differ in sign or essentiality).
     pub fn add_multiple_regulations(&mut self, regulations: Vec<&str>) -> Result<(), String> {
         // before making any changes, check that all regulations are actually valid
+        let mut seen_pairs = HashSet::new();
         for regulation_str in regulations.iter() {
             let (reg, _, _, tar) = Regulation::try_components_from_string(regulation_str)?;
             let regulator = VarId::new(reg.as_str())?;
             let target = VarId::new(tar.as_str())?;
-            self.assert_no_regulation(&regulator, &target)?
+            self.assert_no_regulation(&regulator, &target)?;
+            if !seen_pairs.insert((regulator, target)) {
+                return Err(format!(
+                    "Multiple regulations between `{reg}` and `{tar}` in the given list."
+                ));
+            }
         }
 
         for regulation_str in regulations {
@@ -366,6 +434,46 @@ impl ModelState {
         Ok(())
     }
 
+    /// Add regulations derived from the expressions of update functions. For each variable with
+    /// a non-empty update function, every variable referenced in the expression becomes its
+    /// regulator. Missing regulations are added with unknown essentiality and monotonicity,
+    /// existing regulations are left untouched.
+    ///
+    /// Expressions of uninterpreted functions are first substituted into the update functions,
+    /// so that their formal arguments (placeholder variables) are mapped to the actual variables
+    /// the function is applied to. For example, with `f(x, y) = x` and update `A: f(B, C)`, only
+    /// `B -?? A` is added.
+    ///
+    /// Returns the number of added regulations, or `Err` if the expressions of uninterpreted
+    /// functions are invalid (e.g., they contain cycles).
+    pub fn infer_regulations_from_update_fns(&mut self) -> Result<usize, String> {
+        let expression_trees = self.propagate_expressions_through_uninterpreted_fns()?;
+
+        let mut new_regulations = Vec::new();
+        for (target, update_fn) in self.update_fns() {
+            if update_fn.has_empty_expression() {
+                continue;
+            }
+            let fn_tree = self.substitute_expressions_to_update_fn(update_fn, &expression_trees)?;
+            for regulator in fn_tree.collect_variables() {
+                if self.get_regulation(&regulator, target).is_err() {
+                    new_regulations.push(Regulation::new(
+                        regulator,
+                        target.clone(),
+                        Essentiality::Unknown,
+                        Monotonicity::Unknown,
+                    ));
+                }
+            }
+        }
+
+        let num_added = new_regulations.len();
+        for regulation in new_regulations {
+            self.add_regulation_raw(regulation)?;
+        }
+        Ok(num_added)
+    }
+
     /// Set the raw variable data for a variable `var_id`.
     pub fn set_raw_var(&mut self, var_id: &VarId, var_data: Variable) -> Result<(), String> {
         self.assert_valid_variable(var_id)?;
@@ -418,6 +526,18 @@ impl ModelState {
         self.set_var_annot(&var_id, annot)
     }
 
+    /// Mark a network variable given by id `var_id` as an input (or unmark it). Inputs are
+    /// treated as free Boolean constants during the inference (see [Variable::is_input]).
+    ///
+    /// Note that incoming regulations of inputs are not forbidden here, but reported during
+    /// the consistency check of the sketch.
+    pub fn set_var_input(&mut self, var_id: &VarId, is_input: bool) -> Result<(), String> {
+        self.assert_valid_variable(var_id)?;
+        let variable = self.variables.get_mut(var_id).unwrap();
+        variable.set_input(is_input);
+        Ok(())
+    }
+
     /// Set the id of variable with `original_id` to `new_id`.
     ///
     /// Note that this operation may be costly as it affects several components of the state.
@@ -597,11 +717,10 @@ impl ModelState {
         expression: &str,
     ) -> Result<(), String> {
         self.assert_valid_uninterpreted_fn(fn_id)?;
-        let original_fn = self.uninterpreted_fns.get(fn_id).unwrap().clone();
         // this will correctly return error if the expression is invalid
-        let updated_fn =
-            UninterpretedFn::with_new_expression(original_fn, expression, self, fn_id)?;
-        self.uninterpreted_fns.insert(fn_id.clone(), updated_fn);
+        let parsed = self.parse_expression_cached(expression, Some(fn_id))?;
+        let uninterpreted_fn = self.uninterpreted_fns.get_mut(fn_id).unwrap();
+        uninterpreted_fn.set_parsed_expression(parsed);
         Ok(())
     }
 
@@ -703,6 +822,43 @@ impl ModelState {
         Ok(())
     }
 
+    /// Reorder arguments of the uninterpreted function `fn_id` according to the `permutation`,
+    /// i.e., the `i`-th new argument is the `permutation[i]`-th original argument.
+    ///
+    /// Argument properties (monotonicity, essentiality) are moved together with the arguments,
+    /// and the function's own expression is adjusted so that it keeps its meaning. All update
+    /// and uninterpreted functions applying `fn_id` are rewritten so that their arguments follow
+    /// the new order.
+    ///
+    /// Returns `Err` if the function does not exist, or `permutation` is not a valid permutation
+    /// of `0..arity`. Static properties (e.g., those referencing function inputs by index) are
+    /// not modified by this method.
+    pub fn reorder_uninterpreted_fn_args(
+        &mut self,
+        fn_id: &UninterpretedFnId,
+        permutation: &[usize],
+    ) -> Result<(), String> {
+        self.assert_valid_uninterpreted_fn(fn_id)?;
+        let mut reordered_fn = self.get_uninterpreted_fn(fn_id)?.clone();
+        reordered_fn.reorder_arguments(permutation, self)?;
+        self.uninterpreted_fns.insert(fn_id.clone(), reordered_fn);
+
+        // Reorder arguments of this function in all uninterpreted functions' expressions
+        for other_fn_id in self.uninterpreted_fns.clone().keys() {
+            let mut other_fn = self.uninterpreted_fns.remove(other_fn_id).unwrap();
+            other_fn.permute_fn_args(fn_id, permutation, self);
+            self.uninterpreted_fns.insert(other_fn_id.clone(), other_fn);
+        }
+
+        // Reorder arguments of this function in all update functions
+        for var_id in self.variables.keys() {
+            let mut update_fn = self.update_fns.remove(var_id).unwrap();
+            update_fn.permute_fn_args(fn_id, permutation, self);
+            self.update_fns.insert(var_id.clone(), update_fn);
+        }
+        Ok(())
+    }
+
     /// Set the id of an uninterpreted fn given by string `original_id` to `new_id`.
     pub fn set_uninterpreted_fn_id_by_str(
         &mut self,
@@ -778,15 +934,11 @@ impl ModelState {
         new_sign: &Monotonicity,
     ) -> Result<(), String> {
         // all validity checks are performed inside
-        let regulation = self.get_regulation(regulator, target)?.clone();
+        let mut regulation = self.get_regulation(regulator, target)?.clone();
         self.remove_regulation(regulation.get_regulator(), regulation.get_target())?;
-        self.add_regulation(
-            regulator.clone(),
-            target.clone(),
-            *regulation.get_essentiality(),
-            *new_sign,
-        )?;
-        Ok(())
+        // modify the original instance, so that other data (like annotation) are kept
+        regulation.swap_sign(*new_sign);
+        self.add_regulation_raw(regulation)
     }
 
     /// Shorthand to change sign of a `Regulation` pointing from `regulator` to `target`.
@@ -815,15 +967,11 @@ impl ModelState {
         new_essentiality: &Essentiality,
     ) -> Result<(), String> {
         // all validity checks are performed inside
-        let regulation = self.get_regulation(regulator, target)?.clone();
+        let mut regulation = self.get_regulation(regulator, target)?.clone();
         self.remove_regulation(regulation.get_regulator(), regulation.get_target())?;
-        self.add_regulation(
-            regulator.clone(),
-            target.clone(),
-            *new_essentiality,
-            *regulation.get_sign(),
-        )?;
-        Ok(())
+        // modify the original instance, so that other data (like annotation) are kept
+        regulation.swap_essentiality(*new_essentiality);
+        self.add_regulation_raw(regulation)
     }
 
     /// Shorthand to change essentiality of a `Regulation` pointing from `regulator` to `target`.
@@ -841,12 +989,85 @@ impl ModelState {
         self.change_regulation_essentiality(&regulator_id, &target_id, new_essentiality)
     }
 
+    /// Set annotation of a `Regulation` pointing from `regulator` to `target`.
+    ///
+    /// Returns `Err` when one of the variables is invalid, or the regulation between the two
+    /// variables does not exist.
+    pub fn set_regulation_annotation(
+        &mut self,
+        regulator: &VarId,
+        target: &VarId,
+        annot: &str,
+    ) -> Result<(), String> {
+        // all validity checks are performed inside
+        let mut regulation = self.get_regulation(regulator, target)?.clone();
+        regulation.set_annotation(annot);
+        // annotation is not part of the regulation's identity, so we must replace the instance
+        self.regulations.replace(regulation);
+        Ok(())
+    }
+
+    /// Set annotation of a `Regulation` pointing from `regulator` to `target`, given by
+    /// string IDs.
+    ///
+    /// Returns `Err` when one of the variables is invalid, or the regulation between the two
+    /// variables does not exist.
+    pub fn set_regulation_annotation_by_str(
+        &mut self,
+        regulator: &str,
+        target: &str,
+        annot: &str,
+    ) -> Result<(), String> {
+        let regulator_id = VarId::new(regulator)?;
+        let target_id = VarId::new(target)?;
+        self.set_regulation_annotation(&regulator_id, &target_id, annot)
+    }
+
     /// Set update function for a given variable to a provided expression.
     pub fn set_update_fn(&mut self, var_id: &VarId, expression: &str) -> Result<(), String> {
         self.assert_valid_variable(var_id)?;
 
         // this will correctly return error if the expression is invalid
-        let new_update_fn = UpdateFn::try_from_str(expression, self)?;
+        let parsed = self.parse_expression_cached(expression, None)?;
+        self.replace_update_fn(var_id, UpdateFn::from_parsed(parsed))
+    }
+
+    /// Set update function for a given variable to a constant `true` or `false` (see
+    /// [UpdateFn::new_constant]). This is a typed shortcut for clamping the variable, e.g., in
+    /// knockout or over-expression experiments. The frozen flag of the function is kept.
+    pub fn set_update_fn_constant(&mut self, var_id: &VarId, value: bool) -> Result<(), String> {
+        self.replace_update_fn(var_id, UpdateFn::new_constant(value))
+    }
+
+    /// Reset update function of a given variable back to the default "empty" function (see
+    /// [UpdateFn::default]), leaving it fully unspecified. The frozen flag of the function is kept.
+    pub fn clear_update_fn(&mut self, var_id: &VarId) -> Result<(), String> {
+        self.replace_update_fn(var_id, UpdateFn::default())
+    }
+
+    /// Mark the update function of a given variable as frozen (or unfreeze it). Frozen update
+    /// functions must be fixed concrete functions during the inference (the flag only serves as
+    /// a validation, see [UpdateFn] for details).
+    ///
+    /// Note that whether the frozen function is fully specified is not checked here, but during
+    /// the consistency check of the sketch (as the function can still be edited later).
+    pub fn set_update_fn_frozen(&mut self, var_id: &VarId, frozen: bool) -> Result<(), String> {
+        self.assert_valid_variable(var_id)?;
+        // the update fn always exists for a valid variable
+        self.update_fns.get_mut(var_id).unwrap().set_frozen(frozen);
+        Ok(())
+    }
+
+    /// **(internal)** Utility method to replace the update fn of a given variable with a new
+    /// one. The frozen flag is not a part of the function's expression, so the new function
+    /// takes over the flag of the original one.
+    fn replace_update_fn(
+        &mut self,
+        var_id: &VarId,
+        mut new_update_fn: UpdateFn,
+    ) -> Result<(), String> {
+        self.assert_valid_variable(var_id)?;
+        new_update_fn.set_frozen(self.get_update_fn(var_id)?.is_frozen());
         self.update_fns.insert(var_id.clone(), new_update_fn);
         Ok(())
     }
@@ -985,6 +1206,20 @@ impl ModelState {
             .update_all_node_positions(nodes)
     }
 
+    /// Automatically arrange all nodes of a given layout, using regulations of the model as
+    /// edges. See [Layout::auto_arrange] for details.
+    ///
+    /// Return `Err` if the layout does not exist.
+    pub fn auto_arrange_layout(&mut self, layout_id: &LayoutId) -> Result<(), String> {
+        self.assert_valid_layout(layout_id)?;
+
+        self.layouts
+            .get_mut(layout_id)
+            .ok_or(format!("Error accessing layout {layout_id} in layout map"))?
+            .auto_arrange(&self.regulations);
+        Ok(())
+    }
+
     /// **(internal)** Utility method to add a variable node to a given layout.
     /// The node is inserted to a default position x=0,y=0.
     fn insert_to_layout(&mut self, var_id: VarId, layout_id: &LayoutId) -> Result<(), String> {
@@ -1047,7 +1282,7 @@ impl ModelState {
     }
 
     /// **(internal)** Utility method to ensure there is a variable with given Id.
-    fn assert_valid_variable(&self, var_id: &VarId) -> Result<(), String> {
+    pub(super) fn assert_valid_variable(&self, var_id: &VarId) -> Result<(), String> {
         if self.is_valid_var_id(var_id) {
             Ok(())
         } else {
@@ -1094,8 +1329,61 @@ impl ModelState {
     }
 }
 
+/// **(internal)** Collect all function applications in an expression (without parsing it), as
+/// pairs of the function symbol and the number of arguments it is applied to. A function
+/// application is any identifier directly followed by an opening parenthesis (possibly with
+/// whitespace in between).
+///
+/// Applications are listed in the order they appear in the expression (including the nested
+/// ones), and can repeat.
+fn collect_fn_applications(expression: &str) -> Vec<(String, usize)> {
+    let is_identifier_char = |c: char| c.is_alphanumeric() || c == '_';
+    let chars: Vec<char> = expression.chars().collect();
+    let mut applications = Vec::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        if !is_identifier_char(chars[i]) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < chars.len() && is_identifier_char(chars[i]) {
+            i += 1;
+        }
+        let identifier: String = chars[start..i].iter().collect();
+
+        let mut j = i;
+        while j < chars.len() && chars[j].is_whitespace() {
+            j += 1;
+        }
+        if j >= chars.len() || chars[j] != '(' {
+            continue;
+        }
+
+        // count top-level commas and check for an empty argument list
+        let (mut depth, mut num_commas, mut is_empty) = (0, 0, true);
+        for &c in &chars[j..] {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 1 => num_commas += 1,
+                c if !c.is_whitespace() => is_empty = false,
+                _ => {}
+            }
+            if depth == 0 {
+                break;
+            }
+        }
+        let num_args = if is_empty { 0 } else { num_commas + 1 };
+        applications.push((identifier, num_args));
+    }
+    applications
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::sketchbook::data_structs::ModelData;
     use crate::sketchbook::layout::NodePosition;
     use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
 
@@ -1306,6 +1594,47 @@ mod tests {
         assert_eq!(highest_uninterpreted_fn_arity(&model), 0);
     }
 
+    /// Test reordering arguments of an uninterpreted fn, and propagating it to all expressions.
+    #[test]
+    fn test_reorder_uninterpreted_fn_args() {
+        let mut model = ModelState::new_with_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_multiple_uninterpreted_fns(vec![("f", "f", 3), ("g", "g", 2)])
+            .unwrap();
+        model
+            .add_multiple_regulations(vec!["a -> b", "b -> b"])
+            .unwrap();
+        let f = model.get_uninterpreted_fn_id("f").unwrap();
+        let g = model.get_uninterpreted_fn_id("g").unwrap();
+        let b = model.get_var_id("b").unwrap();
+        model
+            .set_uninterpreted_fn_expression(&f, "var0 & !var2")
+            .unwrap();
+        model
+            .set_uninterpreted_fn_monotonicity(&f, Monotonicity::Activation, 0)
+            .unwrap();
+        model
+            .set_uninterpreted_fn_expression(&g, "f(var0, var1, var1)")
+            .unwrap();
+        model.set_update_fn(&b, "f(a, b, true) | g(b, a)").unwrap();
+
+        // new order of arguments of `f` is (var2, var0, var1)
+        model.reorder_uninterpreted_fn_args(&f, &[2, 0, 1]).unwrap();
+        let f_fn = model.get_uninterpreted_fn(&f).unwrap();
+        assert_eq!(f_fn.get_fn_expression(), "var1 & !var0");
+        assert_eq!(f_fn.get_monotonic(1), &Monotonicity::Activation);
+        assert_eq!(f_fn.get_monotonic(0), &Monotonicity::Unknown);
+        let g_fn = model.get_uninterpreted_fn(&g).unwrap();
+        assert_eq!(g_fn.get_fn_expression(), "f(var1, var0, var1)");
+        let b_fn = model.get_update_fn_string(&b).unwrap();
+        assert_eq!(b_fn, "f(true, a, b) | g(b, a)");
+
+        // invalid permutations are rejected
+        assert!(model.reorder_uninterpreted_fn_args(&f, &[0, 1]).is_err());
+        assert!(model.reorder_uninterpreted_fn_args(&f, &[0, 1, 1]).is_err());
+        assert!(model.reorder_uninterpreted_fn_args(&f, &[0, 1, 3]).is_err());
+    }
+
     /// Test manually adding and modifying update functions.
     #[test]
     fn test_update_fns() {
@@ -1320,6 +1649,89 @@ mod tests {
         model.set_update_fn(&var_a, "(a & b) => c").unwrap();
         let modified_expression = model.get_update_fn_string(&var_a).unwrap();
         assert_eq!(modified_expression, expression);
+
+        // constant shortcut is equivalent to setting the literal expression
+        model.set_update_fn_constant(&var_a, false).unwrap();
+        assert_eq!(model.get_update_fn_string(&var_a).unwrap(), "false");
+        assert_eq!(
+            model.get_update_fn(&var_a).unwrap().as_constant(),
+            Some(false)
+        );
+        let mut model_literal = model.clone();
+        model_literal.set_update_fn(&var_a, "false").unwrap();
+        assert_eq!(model, model_literal);
+
+        // clearing the function makes it empty again
+        model.clear_update_fn(&var_a).unwrap();
+        assert_eq!(model.get_update_fn_string(&var_a).unwrap(), "");
+        assert!(model.get_update_fn(&var_a).unwrap().has_empty_expression());
+    }
+
+    /// Test inferring missing regulations from the variables used in update functions.
+    #[test]
+    fn test_infer_regulations_from_update_fns() {
+        let variables = vec![("a", "a"), ("b", "b"), ("c", "c")];
+        let mut model = ModelState::new_with_vars(variables).unwrap();
+        model.add_regulation_by_str("b -| a").unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("f", "f", 2)
+            .unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("g", "g", 1)
+            .unwrap();
+        model
+            .set_uninterpreted_fn_expression_by_str("f", "var0")
+            .unwrap();
+
+        // `c` is only passed to an ignored argument of `f`, `b -| a` already exists
+        let var_a = model.get_var_id("a").unwrap();
+        let var_b = model.get_var_id("b").unwrap();
+        model.set_update_fn(&var_a, "!b | f(a, c)").unwrap();
+        model.set_update_fn(&var_b, "g(c & a)").unwrap();
+        assert_eq!(model.infer_regulations_from_update_fns(), Ok(3));
+        assert_eq!(model.num_regulations(), 4);
+
+        let reg_aa = model.get_regulation_by_str("a", "a").unwrap();
+        assert_eq!(reg_aa.get_sign(), &Monotonicity::Unknown);
+        assert_eq!(reg_aa.get_essentiality(), &Essentiality::Unknown);
+        let reg_ba = model.get_regulation_by_str("b", "a").unwrap();
+        assert_eq!(reg_ba.get_sign(), &Monotonicity::Inhibition);
+        assert!(model.get_regulation_by_str("c", "a").is_err());
+        assert!(model.get_regulation_by_str("a", "b").is_ok());
+        assert!(model.get_regulation_by_str("c", "b").is_ok());
+
+        // running it again does not add anything
+        assert_eq!(model.infer_regulations_from_update_fns(), Ok(0));
+    }
+
+    /// Test that undeclared or arity-mismatched function symbols are reported when importing
+    /// model data.
+    #[test]
+    fn test_undeclared_fn_symbols() {
+        let mut model = ModelState::new_with_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("f", "f", 2)
+            .unwrap();
+        let var_a = model.get_var_id("a").unwrap();
+        model.set_update_fn(&var_a, "f(a, b)").unwrap();
+        let mut model_data = ModelData::from_model(&model);
+        assert!(ModelState::new_from_model_data(&model_data).is_ok());
+
+        // break the update function of `a` and the expression of `f`
+        model_data.variables[0].update_fn = "f(a) | g(f(a, !b), h())".to_string();
+        model_data.variables[1].update_fn = "g(a) & f(a)".to_string();
+        model_data.uninterpreted_fns[0].expression = "var0 & k(var1)".to_string();
+        let expected = [
+            "Invalid function symbols in the model:",
+            "The update function of `a` applies function `f` to 1 arguments, but it has arity 2.",
+            "The update function of `a` applies undeclared function `g`.",
+            "The update function of `a` applies undeclared function `h`.",
+            "The update function of `b` applies undeclared function `g`.",
+            "The update function of `b` applies function `f` to 1 arguments, but it has arity 2.",
+            "The expression of function `f` applies undeclared function `k`.",
+        ]
+        .join("\n");
+        assert_eq!(ModelState::new_from_model_data(&model_data), Err(expected));
     }
 
     /// Test adding invalid variables.
@@ -1367,6 +1779,27 @@ mod tests {
         assert_eq!(model.num_regulations(), 1);
     }
 
+    /// Test that adding a list of regulations with two entries for the same pair of variables
+    /// fails (even when they differ in sign), and that nothing gets added.
+    #[test]
+    fn test_add_conflicting_regs_list() {
+        let variables = vec![("a", "a_name"), ("b", "b_name")];
+        let mut model = ModelState::new_with_vars(variables).unwrap();
+
+        let result = model.add_multiple_regulations(vec!["b -> b", "a -> b", "a -| b"]);
+        assert_eq!(
+            result,
+            Err("Multiple regulations between `a` and `b` in the given list.".to_string())
+        );
+        assert_eq!(model.num_regulations(), 0);
+
+        // the same pair in the opposite direction is a different regulation
+        model
+            .add_multiple_regulations(vec!["a -> b", "b -| a"])
+            .unwrap();
+        assert_eq!(model.num_regulations(), 2);
+    }
+
     /// Test that changing variable's name works correctly.
     #[test]
     fn test_var_name_change() {
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::sketchbook::ids::UninterpretedFnId;
+use crate::sketchbook::ids::{UninterpretedFnId, VarId};
 use crate::sketchbook::model::{FnTree, ModelState, UninterpretedFn, UpdateFn};
 
 impl ModelState {
@@ -247,13 +247,114 @@ impl ModelState {
             .collect();
         Ok(redundant_symbols)
     }
+
+    /// Inline the given uninterpreted functions (all of which must have their expressions
+    /// specified) and remove them from the model. Their expressions are substituted into all
+    /// update functions and into expressions of the remaining uninterpreted functions, with
+    /// the placeholder arguments mapped to the actual arguments of each application.
+    ///
+    /// Expressions of functions that are not inlined are left untouched, i.e., these symbols
+    /// may still appear in the resulting expressions.
+    ///
+    /// Returns `Err` if some of the functions is not valid or has an empty expression, or if
+    /// there are cycles in expressions of uninterpreted functions.
+    pub fn inline_uninterpreted_fns(
+        &mut self,
+        fn_ids: &HashSet<UninterpretedFnId>,
+    ) -> Result<(), String> {
+        for fn_id in fn_ids {
+            if self.get_uninterpreted_fn(fn_id)?.has_empty_expression() {
+                return Err(format!(
+                    "Cannot inline function `{fn_id}` without expression."
+                ));
+            }
+        }
+
+        // Propagate the expressions as if the remaining functions were unspecified, so that
+        // only the inlined symbols get substituted.
+        let mut propagation_model = self.clone();
+        for (fn_id, _) in self.uninterpreted_fns() {
+            if !fn_ids.contains(fn_id) {
+                propagation_model.set_uninterpreted_fn_expression(fn_id, "")?;
+            }
+        }
+        let expression_trees =
+            propagation_model.propagate_expressions_through_uninterpreted_fns()?;
+
+        let mut new_update_fns = Vec::new();
+        for (var_id, update_fn) in self.update_fns() {
+            if update_fn.collect_fn_symbols().is_disjoint(fn_ids) {
+                continue;
+            }
+            let fn_tree = self.substitute_expressions_to_update_fn(update_fn, &expression_trees)?;
+            new_update_fns.push((var_id.clone(), fn_tree.to_string(self, None)));
+        }
+        let mut new_fn_expressions = Vec::new();
+        for (fn_id, uninterpreted_fn) in self.uninterpreted_fns() {
+            if fn_ids.contains(fn_id) || uninterpreted_fn.collect_fn_symbols().is_disjoint(fn_ids) {
+                continue;
+            }
+            let fn_tree = self
+                .substitute_expressions_to_uninterpreted_fn(uninterpreted_fn, &expression_trees)?;
+            let arity = uninterpreted_fn.get_arity();
+            new_fn_expressions.push((fn_id.clone(), fn_tree.to_string(self, Some(arity))));
+        }
+
+        // the changes are made on a copy, so that the model is left untouched if some fails
+        let mut inlined = self.clone();
+        for (var_id, expression) in new_update_fns {
+            inlined.set_update_fn(&var_id, &expression)?;
+        }
+        for (fn_id, expression) in new_fn_expressions {
+            inlined.set_uninterpreted_fn_expression(&fn_id, &expression)?;
+        }
+        // inlined functions may reference each other, so they can only be removed (in any
+        // order) once none of them has an expression
+        for fn_id in fn_ids {
+            inlined.set_uninterpreted_fn_expression(fn_id, "")?;
+        }
+        for fn_id in fn_ids {
+            inlined.remove_uninterpreted_fn(fn_id)?;
+        }
+
+        *self = inlined;
+        Ok(())
+    }
+
+    /// Find all variables with frozen update functions (see [UpdateFn::is_frozen]) that are
+    /// not fully specified. That is, the expression is empty or it still contains some
+    /// uninterpreted function symbols after substituting all function expressions.
+    ///
+    /// The variables are returned sorted. There must not be any cycles in function
+    /// expressions, otherwise this method will return error.
+    pub fn find_non_concrete_frozen_update_fns(&self) -> Result<Vec<VarId>, String> {
+        let expression_trees = self.propagate_expressions_through_uninterpreted_fns()?;
+
+        let mut non_concrete_vars = Vec::new();
+        for (var_id, update_fn) in self.update_fns() {
+            if !update_fn.is_frozen() {
+                continue;
+            }
+            let is_concrete = !update_fn.has_empty_expression()
+                && self
+                    .substitute_expressions_to_update_fn(update_fn, &expression_trees)?
+                    .collect_fn_symbols()
+                    .is_empty();
+            if !is_concrete {
+                non_concrete_vars.push(var_id.clone());
+            }
+        }
+        non_concrete_vars.sort();
+        Ok(non_concrete_vars)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, vec};
+    use std::collections::{HashMap, HashSet};
+    use std::vec;
 
-    use crate::sketchbook::ids::UninterpretedFnId;
+    use crate::sketchbook::ids::{UninterpretedFnId, VarId};
     use crate::sketchbook::model::{FnTree, ModelState};
 
     #[test]
@@ -367,6 +468,107 @@ mod tests {
         assert!(unused_fns.contains(&UninterpretedFnId::new("i").unwrap()));
     }
 
+    #[test]
+    /// Test detecting frozen update functions that are not fully specified.
+    fn test_non_concrete_frozen_fns() {
+        // Example PSBN with `A: f(B); B: g(A) & B; C: ` and functions `f(x) = !x`, `g(x) = ?`.
+        // All update functions are frozen, but only the one for `A` is fully specified.
+        let mut model =
+            ModelState::new_with_vars(vec![("A", "A"), ("B", "B"), ("C", "C")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["A -?? B", "B -?? A", "B -?? B"])
+            .unwrap();
+        model
+            .add_multiple_uninterpreted_fns(vec![("f", "f", 1), ("g", "g", 1)])
+            .unwrap();
+        model
+            .set_uninterpreted_fn_expression_by_str("f", "!var0")
+            .unwrap();
+        let (var_a, var_b, var_c) = (
+            VarId::new("A").unwrap(),
+            VarId::new("B").unwrap(),
+            VarId::new("C").unwrap(),
+        );
+        model.set_update_fn(&var_a, "f(B)").unwrap();
+        model.set_update_fn(&var_b, "g(A) & B").unwrap();
+        assert!(model
+            .find_non_concrete_frozen_update_fns()
+            .unwrap()
+            .is_empty());
+
+        for var in [&var_a, &var_b, &var_c] {
+            model.set_update_fn_frozen(var, true).unwrap();
+        }
+        let non_concrete = model.find_non_concrete_frozen_update_fns().unwrap();
+        assert_eq!(non_concrete, vec![var_b.clone(), var_c]);
+
+        // the frozen flag is kept when the expression changes
+        model.set_update_fn(&var_b, "!A & B").unwrap();
+        assert!(model.get_update_fn(&var_b).unwrap().is_frozen());
+        let non_concrete = model.find_non_concrete_frozen_update_fns().unwrap();
+        assert_eq!(non_concrete.len(), 1);
+    }
+
+    #[test]
+    fn test_inline_fns() {
+        // Example PSBN with `A: f(B) & h(A); B: g(A, B)` and the following functions:
+        // - `f(x) = !x`
+        // - `g(x, y) = f(y) | h(x)`
+        // - `h(x) ... unspecified`
+        let mut model = ModelState::new_with_vars(vec![("A", "A"), ("B", "B")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["A -?? A", "B -?? A", "A -?? B", "B -?? B"])
+            .unwrap();
+        model
+            .add_multiple_uninterpreted_fns(vec![("f", "f", 1), ("g", "g", 2), ("h", "h", 1)])
+            .unwrap();
+        model
+            .set_uninterpreted_fn_expression_by_str("f", "!var0")
+            .unwrap();
+        model
+            .set_uninterpreted_fn_expression_by_str("g", "f(var1) | h(var0)")
+            .unwrap();
+        let (var_a, var_b) = (VarId::new("A").unwrap(), VarId::new("B").unwrap());
+        model.set_update_fn(&var_a, "f(B) & h(A)").unwrap();
+        model.set_update_fn(&var_b, "g(A, B)").unwrap();
+        let model_orig = model.clone();
+
+        // function without an expression cannot be inlined
+        let fn_h = UninterpretedFnId::new("h").unwrap();
+        assert!(model
+            .inline_uninterpreted_fns(&HashSet::from([fn_h]))
+            .is_err());
+        assert_eq!(model, model_orig);
+
+        // inlining just `f` also updates the expression of `g`
+        let fn_f = UninterpretedFnId::new("f").unwrap();
+        let fn_g = UninterpretedFnId::new("g").unwrap();
+        let mut model_f = model.clone();
+        model_f
+            .inline_uninterpreted_fns(&HashSet::from([fn_f.clone()]))
+            .unwrap();
+        assert_eq!(model_f.num_uninterpreted_fns(), 2);
+        let expected = FnTree::try_from_str("!B & h(A)", &model_f, None).unwrap();
+        assert_eq!(
+            model_f.get_update_fn(&var_a).unwrap().get_fn_tree(),
+            &Some(expected)
+        );
+        let expected = FnTree::try_from_str("!var1 | h(var0)", &model_f, Some(&fn_g)).unwrap();
+        let g = model_f.get_uninterpreted_fn(&fn_g).unwrap();
+        assert_eq!(g.get_fn_tree(), &Some(expected));
+
+        // inlining both `f` and `g`
+        model
+            .inline_uninterpreted_fns(&HashSet::from([fn_f, fn_g]))
+            .unwrap();
+        assert_eq!(model.num_uninterpreted_fns(), 1);
+        let expected = FnTree::try_from_str("!B | h(A)", &model, None).unwrap();
+        assert_eq!(
+            model.get_update_fn(&var_b).unwrap().get_fn_tree(),
+            &Some(expected)
+        );
+    }
+
     #[test]
     fn test_cycle_detection_positive() {
         // Example model with the following functions with cycle in expressions:
@@ -0,0 +1,438 @@
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::model::{Essentiality, ModelState, Monotonicity};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Structural analysis of the regulatory graph of `ModelState`.
+impl ModelState {
+    /// Compute strongly connected components of the regulatory (influence) graph, with edges
+    /// given by regulations, using Tarjan's algorithm.
+    ///
+    /// Only components containing a cycle are returned - components with multiple variables,
+    /// and single variables that regulate themselves. Variables within each component are
+    /// sorted, and components are listed in a topological order (a component comes before all
+    /// the components it regulates). For the same model, the output is always the same.
+    pub fn regulation_sccs(&self) -> Vec<Vec<VarId>> {
+        let mut vars: Vec<&VarId> = self.variables.keys().collect();
+        vars.sort();
+        let index_map: HashMap<&VarId, usize> =
+            vars.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+
+        // sorted successors (targets) of each variable
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); vars.len()];
+        for regulation in self.regulations.iter() {
+            let regulator = index_map[regulation.get_regulator()];
+            let target = index_map[regulation.get_target()];
+            successors[regulator].push(target);
+        }
+        for targets in successors.iter_mut() {
+            targets.sort();
+        }
+
+        let components = tarjan_sccs(&successors);
+        components
+            .into_iter()
+            .rev()
+            .filter(|component| {
+                component.len() > 1 || successors[component[0]].contains(&component[0])
+            })
+            .map(|mut component| {
+                component.sort();
+                component.into_iter().map(|i| vars[i].clone()).collect()
+            })
+            .collect()
+    }
+
+    /// Number of regulations targeting the given variable (i.e., the number of its regulators).
+    pub fn in_degree(&self, var_id: &VarId) -> Result<usize, String> {
+        self.assert_valid_variable(var_id)?;
+        let degree = self
+            .regulations
+            .iter()
+            .filter(|r| r.get_target() == var_id)
+            .count();
+        Ok(degree)
+    }
+
+    /// Number of regulations with the given variable as the regulator (i.e., the number of its
+    /// targets).
+    pub fn out_degree(&self, var_id: &VarId) -> Result<usize, String> {
+        self.assert_valid_variable(var_id)?;
+        let degree = self
+            .regulations
+            .iter()
+            .filter(|r| r.get_regulator() == var_id)
+            .count();
+        Ok(degree)
+    }
+
+    /// Return a sorted list of "hub" variables, i.e., variables whose total degree (in-degree
+    /// plus out-degree) exceeds the `threshold`. Self-regulations count to both degrees.
+    pub fn hubs(&self, threshold: usize) -> Vec<VarId> {
+        let degrees = self.total_degrees();
+        let mut hubs: Vec<VarId> = degrees
+            .into_iter()
+            .filter(|(_, degree)| *degree > threshold)
+            .map(|(var_id, _)| var_id.clone())
+            .collect();
+        hubs.sort();
+        hubs
+    }
+
+    /// Compute a histogram of total degrees (in-degree plus out-degree) of all variables. The
+    /// item at index `i` is the number of variables with total degree `i`. The histogram ends
+    /// with the maximal degree (it is empty if there are no variables).
+    pub fn degree_histogram(&self) -> Vec<usize> {
+        let degrees = self.total_degrees();
+        let max_degree = degrees.values().max().copied();
+        let mut histogram = max_degree.map_or(Vec::new(), |max| vec![0; max + 1]);
+        for degree in degrees.into_values() {
+            histogram[degree] += 1;
+        }
+        histogram
+    }
+
+    /// Compute all variables that can (transitively) influence the `target` variable, i.e.,
+    /// all variables from which `target` is reachable via regulations.
+    ///
+    /// The `target` itself is only included if it lies on a cycle (including self-regulations).
+    pub fn ancestors(&self, target: &VarId) -> Result<HashSet<VarId>, String> {
+        self.assert_valid_variable(target)?;
+        Ok(self.reachable_vars(target, true))
+    }
+
+    /// Compute all variables that can be (transitively) influenced by the `regulator` variable,
+    /// i.e., all variables reachable from `regulator` via regulations.
+    ///
+    /// The `regulator` itself is only included if it lies on a cycle (including self-regulations).
+    pub fn descendants(&self, regulator: &VarId) -> Result<HashSet<VarId>, String> {
+        self.assert_valid_variable(regulator)?;
+        Ok(self.reachable_vars(regulator, false))
+    }
+
+    /// **(internal)** Breadth-first search over the regulations, starting with the direct
+    /// neighbours of `start`. Regulations are followed backwards (from targets to regulators) if
+    /// `backwards` is set. Each variable is only expanded once, so cycles are handled fine.
+    fn reachable_vars(&self, start: &VarId, backwards: bool) -> HashSet<VarId> {
+        let mut neighbours: HashMap<&VarId, Vec<&VarId>> = HashMap::new();
+        for regulation in self.regulations.iter() {
+            let (from, to) = if backwards {
+                (regulation.get_target(), regulation.get_regulator())
+            } else {
+                (regulation.get_regulator(), regulation.get_target())
+            };
+            neighbours.entry(from).or_default().push(to);
+        }
+
+        let mut visited: HashSet<&VarId> = HashSet::new();
+        let mut queue: VecDeque<&VarId> = VecDeque::from([start]);
+        while let Some(var_id) = queue.pop_front() {
+            for next in neighbours.get(var_id).into_iter().flatten() {
+                if visited.insert(*next) {
+                    queue.push_back(*next);
+                }
+            }
+        }
+        visited.into_iter().cloned().collect()
+    }
+
+    /// **(internal)** Compute the total degree (in-degree plus out-degree) of each variable,
+    /// iterating the regulations only once.
+    fn total_degrees(&self) -> HashMap<&VarId, usize> {
+        let mut degrees: HashMap<&VarId, usize> = self.variables.keys().map(|v| (v, 0)).collect();
+        for regulation in self.regulations.iter() {
+            *degrees.get_mut(regulation.get_regulator()).unwrap() += 1;
+            *degrees.get_mut(regulation.get_target()).unwrap() += 1;
+        }
+        degrees
+    }
+
+    /// Compute a hash of the signed regulatory graph that is invariant under renaming of the
+    /// variables, i.e., models with isomorphic regulatory graphs (including the monotonicity
+    /// and essentiality of regulations) get the same hash. This can be used to detect sketches
+    /// sharing the same influence structure (e.g., for deduplication).
+    ///
+    /// The canonical form is approximated by iterative color refinement (Weisfeiler-Leman style).
+    /// Each variable starts with the same color, and in each round, its new color is derived from
+    /// its current color and the sorted colors of its regulators and targets (together with the
+    /// regulation types). Refinement stops once the number of distinct colors stops growing, and
+    /// the hash combines the sorted final colors of all variables.
+    ///
+    /// The result is deterministic (it does not depend on the platform or the Rust version), but
+    /// it is a hash - rare collisions are possible, including for some non-isomorphic graphs that
+    /// cannot be distinguished by color refinement (e.g., certain regular graphs).
+    pub fn regulation_graph_canonical_hash(&self) -> u64 {
+        let mut vars: Vec<&VarId> = self.variables.keys().collect();
+        vars.sort();
+        let index_map: HashMap<&VarId, usize> =
+            vars.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+
+        // labeled incoming and outgoing edges of each variable
+        let mut in_edges: Vec<Vec<(u64, usize)>> = vec![Vec::new(); vars.len()];
+        let mut out_edges: Vec<Vec<(u64, usize)>> = vec![Vec::new(); vars.len()];
+        for regulation in self.regulations.iter() {
+            let regulator = index_map[regulation.get_regulator()];
+            let target = index_map[regulation.get_target()];
+            let sign = match regulation.get_sign() {
+                Monotonicity::Activation => 0,
+                Monotonicity::Inhibition => 1,
+                Monotonicity::Dual => 2,
+                Monotonicity::Unknown => 3,
+            };
+            let essentiality = match regulation.get_essentiality() {
+                Essentiality::True => 0,
+                Essentiality::False => 1,
+                Essentiality::Unknown => 2,
+            };
+            let label = sign * 3 + essentiality;
+            in_edges[target].push((label, regulator));
+            out_edges[regulator].push((label, target));
+        }
+
+        let mut colors: Vec<u64> = vec![0; vars.len()];
+        let mut num_colors = usize::from(!vars.is_empty());
+        // a partition of `n` variables can only be refined `n` times
+        for _ in 0..vars.len() {
+            let new_colors: Vec<u64> = (0..vars.len())
+                .map(|i| {
+                    let mut values = vec![colors[i]];
+                    for edges in [&in_edges[i], &out_edges[i]] {
+                        let mut neighbours: Vec<(u64, u64)> = edges
+                            .iter()
+                            .map(|(label, j)| (*label, colors[*j]))
+                            .collect();
+                        neighbours.sort();
+                        values.push(neighbours.len() as u64);
+                        values.extend(neighbours.into_iter().flat_map(|(l, c)| [l, c]));
+                    }
+                    fnv_hash(&values)
+                })
+                .collect();
+            let new_num_colors = new_colors.iter().collect::<HashSet<_>>().len();
+            colors = new_colors;
+            if new_num_colors == num_colors {
+                break;
+            }
+            num_colors = new_num_colors;
+        }
+
+        colors.sort();
+        let mut values = vec![vars.len() as u64, self.regulations.len() as u64];
+        values.extend(colors);
+        fnv_hash(&values)
+    }
+}
+
+/// **(internal)** Iterative version of Tarjan's algorithm. Computes strongly connected
+/// components of a graph given by the lists of successors of each vertex. Components are
+/// returned in the order in which they are completed (reverse topological order).
+fn tarjan_sccs(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let num_vertices = successors.len();
+    let mut index: Vec<Option<usize>> = vec![None; num_vertices];
+    let mut low_link: Vec<usize> = vec![0; num_vertices];
+    let mut on_stack: Vec<bool> = vec![false; num_vertices];
+    let mut stack: Vec<usize> = Vec::new();
+    let mut components = Vec::new();
+    let mut next_index = 0;
+
+    for root in 0..num_vertices {
+        if index[root].is_some() {
+            continue;
+        }
+        // explicit DFS call stack with (vertex, index of the next successor to explore)
+        let mut call_stack: Vec<(usize, usize)> = vec![(root, 0)];
+        index[root] = Some(next_index);
+        low_link[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some((vertex, child_idx)) = call_stack.pop() {
+            if let Some(&successor) = successors[vertex].get(child_idx) {
+                call_stack.push((vertex, child_idx + 1));
+                match index[successor] {
+                    None => {
+                        index[successor] = Some(next_index);
+                        low_link[successor] = next_index;
+                        next_index += 1;
+                        stack.push(successor);
+                        on_stack[successor] = true;
+                        call_stack.push((successor, 0));
+                    }
+                    Some(successor_index) if on_stack[successor] => {
+                        low_link[vertex] = low_link[vertex].min(successor_index);
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            // all successors explored, propagate the low-link to the parent
+            if let Some(&(parent, _)) = call_stack.last() {
+                low_link[parent] = low_link[parent].min(low_link[vertex]);
+            }
+            // vertex is a root of a component, pop the whole component from the stack
+            if Some(low_link[vertex]) == index[vertex] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == vertex {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+/// **(internal)** Compute a 64-bit FNV-1a hash of the given values. Unlike the hashers of the
+/// standard library, the result is guaranteed to be stable across platforms and Rust versions.
+fn fnv_hash(values: &[u64]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in values.iter().flat_map(|value| value.to_le_bytes()) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::VarId;
+    use crate::sketchbook::model::ModelState;
+    use std::collections::HashSet;
+
+    /// Convert a list of string IDs into variable IDs.
+    fn to_ids(ids: &[&str]) -> Vec<VarId> {
+        ids.iter().map(|id| VarId::new(id).unwrap()).collect()
+    }
+
+    #[test]
+    /// Test computing strongly connected components of the regulatory graph.
+    fn test_regulation_sccs() {
+        let vars = vec![
+            ("a", "a"),
+            ("b", "b"),
+            ("c", "c"),
+            ("d", "d"),
+            ("e", "e"),
+            ("f", "f"),
+        ];
+        let mut model = ModelState::new_with_vars(vars).unwrap();
+        let regulations = vec![
+            "c -> a", "a -> b", "b -| c", "c -> d", "d -> d", "e -> f", "d -> e",
+        ];
+        model.add_multiple_regulations(regulations).unwrap();
+
+        // `e` and `f` are trivial components without self-loops, they are not included
+        let expected = vec![to_ids(&["a", "b", "c"]), to_ids(&["d"])];
+        assert_eq!(model.regulation_sccs(), expected);
+
+        // closing the cycle merges everything (except `f`) into a single component
+        model.add_regulation_by_str("e -> b").unwrap();
+        let expected = vec![to_ids(&["a", "b", "c", "d", "e"])];
+        assert_eq!(model.regulation_sccs(), expected);
+
+        assert!(ModelState::new_empty().regulation_sccs().is_empty());
+    }
+
+    #[test]
+    /// Test computing degrees of variables, hubs, and the degree histogram.
+    fn test_degrees_and_hubs() {
+        let vars = vec![("a", "a"), ("b", "b"), ("c", "c"), ("d", "d")];
+        let mut model = ModelState::new_with_vars(vars).unwrap();
+        let regulations = vec!["a -> b", "a -> c", "a -| d", "b -> a", "c -> c"];
+        model.add_multiple_regulations(regulations).unwrap();
+
+        let var = |id: &str| VarId::new(id).unwrap();
+        assert_eq!(model.in_degree(&var("a")).unwrap(), 1);
+        assert_eq!(model.out_degree(&var("a")).unwrap(), 3);
+        assert_eq!(model.in_degree(&var("c")).unwrap(), 2);
+        assert_eq!(model.out_degree(&var("d")).unwrap(), 0);
+        assert!(model.in_degree(&var("e")).is_err());
+
+        // total degrees are `a`: 4, `b`: 2, `c`: 3, `d`: 1
+        assert_eq!(model.hubs(2), to_ids(&["a", "c"]));
+        assert_eq!(model.hubs(4), Vec::<VarId>::new());
+        assert_eq!(model.degree_histogram(), vec![0, 1, 1, 1, 1]);
+        assert!(ModelState::new_empty().degree_histogram().is_empty());
+    }
+
+    #[test]
+    /// Test computing (transitive) ancestors and descendants of variables, including cycles.
+    fn test_ancestors_and_descendants() {
+        let vars = vec![("a", "a"), ("b", "b"), ("c", "c"), ("d", "d"), ("e", "e")];
+        let mut model = ModelState::new_with_vars(vars).unwrap();
+        let regulations = vec!["a -> b", "b -> c", "c -| b", "c -> d", "e -> e"];
+        model.add_multiple_regulations(regulations).unwrap();
+
+        let var = |id: &str| VarId::new(id).unwrap();
+        let to_set = |ids: &[&str]| to_ids(ids).into_iter().collect::<HashSet<_>>();
+
+        assert_eq!(
+            model.ancestors(&var("d")).unwrap(),
+            to_set(&["a", "b", "c"])
+        );
+        // `b` and `c` lie on a cycle, so they are their own ancestors
+        assert_eq!(
+            model.ancestors(&var("b")).unwrap(),
+            to_set(&["a", "b", "c"])
+        );
+        assert_eq!(model.ancestors(&var("a")).unwrap(), to_set(&[]));
+        assert_eq!(
+            model.descendants(&var("a")).unwrap(),
+            to_set(&["b", "c", "d"])
+        );
+        assert_eq!(model.descendants(&var("d")).unwrap(), to_set(&[]));
+        // self-regulation is a cycle as well
+        assert_eq!(model.ancestors(&var("e")).unwrap(), to_set(&["e"]));
+        assert_eq!(model.descendants(&var("e")).unwrap(), to_set(&["e"]));
+
+        assert!(model.ancestors(&var("f")).is_err());
+        assert!(model.descendants(&var("f")).is_err());
+    }
+
+    #[test]
+    /// Test that the canonical hash of the regulatory graph is invariant under variable renaming,
+    /// but distinguishes different structures and regulation types.
+    fn test_regulation_graph_canonical_hash() {
+        let vars = vec![("a", "a"), ("b", "b"), ("c", "c"), ("d", "d")];
+        let mut model = ModelState::new_with_vars(vars).unwrap();
+        let regulations = vec!["a -> b", "b -| c", "c -> a", "c -?? d", "d -> d"];
+        model.add_multiple_regulations(regulations).unwrap();
+
+        // the same graph with permuted variables (and different IDs)
+        let vars = vec![("x", "x"), ("y", "y"), ("z", "z"), ("w", "w")];
+        let mut renamed_model = ModelState::new_with_vars(vars).unwrap();
+        let regulations = vec!["x -> x", "y -?? x", "w -| y", "y -> z", "z -> w"];
+        renamed_model.add_multiple_regulations(regulations).unwrap();
+        let hash = model.regulation_graph_canonical_hash();
+        assert_eq!(hash, renamed_model.regulation_graph_canonical_hash());
+        // the hash is deterministic
+        assert_eq!(hash, model.clone().regulation_graph_canonical_hash());
+
+        // changing a sign of a regulation changes the hash
+        let mut other_model = model.clone();
+        let (var_a, var_b) = (VarId::new("a").unwrap(), VarId::new("b").unwrap());
+        other_model.remove_regulation(&var_a, &var_b).unwrap();
+        other_model.add_regulation_by_str("a -| b").unwrap();
+        assert_ne!(hash, other_model.regulation_graph_canonical_hash());
+
+        // the same for changing the structure (moving the self-loop)
+        let mut other_model = model.clone();
+        let var_d = VarId::new("d").unwrap();
+        other_model.remove_regulation(&var_d, &var_d).unwrap();
+        other_model.add_regulation_by_str("a -> a").unwrap();
+        assert_ne!(hash, other_model.regulation_graph_canonical_hash());
+
+        // graphs without regulations only differ by the number of variables
+        let empty_hash = ModelState::new_empty().regulation_graph_canonical_hash();
+        let single_var_model = ModelState::new_with_vars(vec![("a", "a")]).unwrap();
+        assert_ne!(
+            empty_hash,
+            single_var_model.regulation_graph_canonical_hash()
+        );
+    }
+}
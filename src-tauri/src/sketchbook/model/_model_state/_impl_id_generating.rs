@@ -5,6 +5,8 @@ use crate::sketchbook::Manager;
 /// Methods for safely generating valid instances of identifiers for the current `ModelState`.
 impl ModelState {
     /// Generate valid `VarId` that's currently not used by any variable in this `ModelState`.
+    /// The generated ID also never collides with placeholder variables `var0`, `var1`, ... of
+    /// uninterpreted functions (see [ModelState::is_placeholder_var_id]).
     ///
     /// First, the given `ideal_id` or its transformation by replacing invalid characters are tried.
     /// If they are both invalid (non-unique), a numerical identifier is added at the end.
@@ -17,12 +19,18 @@ impl ModelState {
     pub fn generate_var_id(&self, ideal_id: &str, start_index: Option<usize>) -> VarId {
         self.generate_id(
             ideal_id,
-            &(Self::is_valid_var_id),
+            &(Self::is_taken_var_id),
             self.num_vars(),
             start_index,
         )
     }
 
+    /// **(internal)** Check if the given `VarId` cannot be used for a new variable, i.e., it is
+    /// either used by an existing variable or it is a placeholder variable.
+    fn is_taken_var_id(&self, var_id: &VarId) -> bool {
+        self.is_valid_var_id(var_id) || self.is_placeholder_var_id(var_id)
+    }
+
     /// Generate valid `LayoutId` that's currently not used by layouts in this `ModelState`.
     ///
     /// First, the given `ideal_id` or its transformation by replacing invalid characters are tried.
@@ -108,6 +116,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_var_id_generating_skips_placeholders() {
+        let mut model = ModelState::new_with_vars(vec![("a", "name")]).unwrap();
+        // without uninterpreted fns, there are no placeholder variables
+        assert_eq!(
+            model.generate_var_id("var0", None),
+            VarId::new("var0").unwrap()
+        );
+
+        // function of arity 3 uses placeholders `var0`, `var1`, `var2`
+        model
+            .add_empty_uninterpreted_fn_by_str("f", "f", 3)
+            .unwrap();
+        assert!(model.is_placeholder_var_id(&VarId::new("var2").unwrap()));
+        assert_eq!(
+            model.generate_var_id("var0", None),
+            VarId::new("var0_1").unwrap()
+        );
+        assert_eq!(
+            model.generate_var_id("var2", None),
+            VarId::new("var2_1").unwrap()
+        );
+        // names right outside of the placeholder range can be used
+        assert_eq!(
+            model.generate_var_id("var3", None),
+            VarId::new("var3").unwrap()
+        );
+        assert_eq!(
+            model.generate_var_id("var", Some(1)),
+            VarId::new("var_1").unwrap()
+        );
+    }
+
     #[test]
     fn test_layout_id_generating() {
         let mut model = ModelState::new_empty();
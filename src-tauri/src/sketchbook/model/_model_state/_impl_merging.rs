@@ -0,0 +1,197 @@
+use crate::sketchbook::ids::{UninterpretedFnId, VarId};
+use crate::sketchbook::model::ModelState;
+use crate::sketchbook::Manager;
+use std::collections::HashMap;
+
+/// Methods for combining several instances of `ModelState` together.
+impl ModelState {
+    /// Import all variables, regulations, uninterpreted functions, and update functions of
+    /// the `other` model into this `ModelState`. Layout nodes of the imported variables are
+    /// placed into the default layout at the same positions as in the default layout of the
+    /// `other` model. All other layouts receive nodes at a default position.
+    ///
+    /// If some variable (or uninterpreted function) ID is used in both models, either an error
+    /// is returned (if `rename_on_conflict` is false), or the incoming component gets a fresh
+    /// ID (see [Manager::generate_id]) and all incoming expressions are rewritten accordingly.
+    ///
+    /// Returns the renaming that was applied to the incoming variables (variables that kept
+    /// their original ID are not included). Nothing is changed if `Err` is returned.
+    pub fn merge(
+        &mut self,
+        other: &ModelState,
+        rename_on_conflict: bool,
+    ) -> Result<HashMap<VarId, VarId>, String> {
+        let mut var_conflicts: Vec<VarId> = other
+            .variables
+            .keys()
+            .filter(|var_id| self.is_valid_var_id(var_id))
+            .cloned()
+            .collect();
+        let mut fn_conflicts: Vec<UninterpretedFnId> = other
+            .uninterpreted_fns
+            .keys()
+            .filter(|fn_id| self.is_valid_uninterpreted_fn_id(fn_id))
+            .cloned()
+            .collect();
+        // sort conflicting IDs to make the generated IDs deterministic
+        var_conflicts.sort();
+        fn_conflicts.sort();
+
+        if !rename_on_conflict {
+            if let Some(var_id) = var_conflicts.first() {
+                return Err(format!(
+                    "Variable with id {var_id} already exists in this model."
+                ));
+            }
+            if let Some(fn_id) = fn_conflicts.first() {
+                return Err(format!("UninterpretedFn with id {fn_id} already exists."));
+            }
+        }
+
+        // First, rename all conflicting components within a copy of the incoming model, so that
+        // all its expressions, regulations, and layouts stay consistent. The fresh IDs must be
+        // unique in both models.
+        let mut incoming = other.clone();
+        let mut var_renaming = HashMap::new();
+        for var_id in var_conflicts {
+            let num_indices = self.num_vars() + incoming.num_vars();
+            let is_taken = |model: &ModelState, id: &VarId| {
+                model.is_valid_var_id(id) || incoming.is_valid_var_id(id)
+            };
+            let new_id = self.generate_id(var_id.as_str(), &is_taken, num_indices, None);
+            incoming.set_var_id(&var_id, new_id.clone())?;
+            var_renaming.insert(var_id, new_id);
+        }
+        for fn_id in fn_conflicts {
+            let num_indices = self.num_uninterpreted_fns() + incoming.num_uninterpreted_fns();
+            let is_taken = |model: &ModelState, id: &UninterpretedFnId| {
+                model.is_valid_uninterpreted_fn_id(id) || incoming.is_valid_uninterpreted_fn_id(id)
+            };
+            let new_id = self.generate_id(fn_id.as_str(), &is_taken, num_indices, None);
+            incoming.set_uninterpreted_fn_id(&fn_id, new_id)?;
+        }
+
+        // Now all incoming IDs are fresh, and the components can be moved over directly. All
+        // changes are done on a copy, so that this model is left untouched if anything fails.
+        let mut merged = self.clone();
+        let default_layout_id = ModelState::get_default_layout_id();
+        let incoming_layout = incoming.get_default_layout();
+        for (var_id, variable) in incoming.variables.iter() {
+            merged.variables.insert(var_id.clone(), variable.clone());
+            let update_fn = incoming.get_update_fn(var_id)?.clone();
+            merged.update_fns.insert(var_id.clone(), update_fn);
+
+            for (layout_id, layout) in merged.layouts.iter_mut() {
+                if *layout_id == default_layout_id {
+                    let node = incoming_layout.get_node(var_id)?.clone();
+                    layout.add_node(var_id.clone(), node)?;
+                } else {
+                    layout.add_default_node(var_id.clone())?;
+                }
+            }
+        }
+        for (fn_id, uninterpreted_fn) in incoming.uninterpreted_fns.iter() {
+            merged
+                .uninterpreted_fns
+                .insert(fn_id.clone(), uninterpreted_fn.clone());
+        }
+        for regulation in incoming.regulations.iter() {
+            merged.add_regulation_raw(regulation.clone())?;
+        }
+
+        *self = merged;
+        Ok(var_renaming)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::{LayoutId, UninterpretedFnId, VarId};
+    use crate::sketchbook::layout::NodePosition;
+    use crate::sketchbook::model::ModelState;
+    use std::collections::HashMap;
+
+    /// Prepare a simple module with variables `A`, `B`, uninterpreted fn `f`, regulations
+    /// `A -> B`, `B -| A`, and update functions `A: !B`, `B: f(A)`.
+    fn prepare_module() -> ModelState {
+        let mut model = ModelState::new_with_vars(vec![("A", "A"), ("B", "B")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["A -> B", "B -| A"])
+            .unwrap();
+        model
+            .add_multiple_uninterpreted_fns(vec![("f", "f", 1)])
+            .unwrap();
+        let var_a = VarId::new("A").unwrap();
+        let var_b = VarId::new("B").unwrap();
+        model.set_update_fn(&var_a, "!B").unwrap();
+        model.set_update_fn(&var_b, "f(A)").unwrap();
+        let layout_id = ModelState::get_default_layout_id();
+        model.update_position(&layout_id, &var_b, 1., 2.).unwrap();
+        model
+    }
+
+    /// Test merging a module into a model with no shared IDs (including layout positions).
+    #[test]
+    fn test_merge_without_conflicts() {
+        let mut model = ModelState::new_with_vars(vec![("C", "C")]).unwrap();
+        model.add_regulation_by_str("C -> C").unwrap();
+        let module = prepare_module();
+
+        let renaming = model.merge(&module, false).unwrap();
+        let var = |id: &str| VarId::new(id).unwrap();
+        assert!(renaming.is_empty());
+        assert_eq!(model.num_vars(), 3);
+        assert_eq!(model.num_regulations(), 3);
+        assert_eq!(model.num_uninterpreted_fns(), 1);
+        assert_eq!(model.get_update_fn_string(&var("B")).unwrap(), "f(A)");
+
+        let position = model
+            .get_default_layout()
+            .get_node_position(&var("B"))
+            .unwrap();
+        assert_eq!(position, &NodePosition(1., 2.));
+    }
+
+    /// Test merging a module with clashing IDs, which fails unless conflicting IDs are renamed.
+    #[test]
+    fn test_merge_with_conflicts() {
+        let var = |id: &str| VarId::new(id).unwrap();
+        let module = prepare_module();
+        let mut model = prepare_module();
+        let layout_id = LayoutId::new("other_layout").unwrap();
+        model.add_layout_simple(layout_id.clone(), "other").unwrap();
+
+        // without renaming, the merge fails and nothing changes
+        assert!(model.merge(&module, false).is_err());
+        assert_eq!(model, {
+            let mut expected = prepare_module();
+            expected
+                .add_layout_simple(layout_id.clone(), "other")
+                .unwrap();
+            expected
+        });
+
+        let renaming = model.merge(&module, true).unwrap();
+        let expected_renaming = HashMap::from([(var("A"), var("A_1")), (var("B"), var("B_1"))]);
+        assert_eq!(renaming, expected_renaming);
+        assert_eq!(model.num_vars(), 4);
+        assert_eq!(model.num_regulations(), 4);
+        assert_eq!(model.num_uninterpreted_fns(), 2);
+
+        // incoming expressions and regulations reference the renamed components
+        let fn_id = UninterpretedFnId::new("f_1").unwrap();
+        assert!(model.is_valid_uninterpreted_fn_id(&fn_id));
+        assert_eq!(model.get_update_fn_string(&var("A_1")).unwrap(), "!B_1");
+        assert_eq!(model.get_update_fn_string(&var("B_1")).unwrap(), "f_1(A_1)");
+        assert!(model.get_regulation(&var("A_1"), &var("B_1")).is_ok());
+        assert!(model.get_regulation(&var("B_1"), &var("A_1")).is_ok());
+        // original components are untouched
+        assert_eq!(model.get_update_fn_string(&var("B")).unwrap(), "f(A)");
+
+        // all layouts contain all variables
+        let default_layout = model.get_default_layout();
+        let position = default_layout.get_node_position(&var("B_1")).unwrap();
+        assert_eq!(position, &NodePosition(1., 2.));
+        assert_eq!(model.get_layout(&layout_id).unwrap().get_num_nodes(), 4);
+    }
+}
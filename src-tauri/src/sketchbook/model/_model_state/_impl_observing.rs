@@ -1,11 +1,15 @@
+use crate::sketchbook::data_structs::CompletenessReport;
 use crate::sketchbook::ids::{LayoutId, UninterpretedFnId, VarId};
 use crate::sketchbook::layout::Layout;
 use crate::sketchbook::layout::NodePosition;
 use crate::sketchbook::model::{
-    LayoutIterator, ModelState, Regulation, RegulationIterator, UninterpretedFn,
-    UninterpretedFnIterator, UpdateFn, UpdateFnIterator, Variable, VariableIterator,
+    Essentiality, LayoutIterator, ModelState, Monotonicity, Regulation, RegulationIterator,
+    UninterpretedFn, UninterpretedFnIterator, UpdateFn, UpdateFnIterator, Variable,
+    VariableIterator,
 };
+use crate::sketchbook::observations::Dataset;
 
+use std::collections::{BTreeSet, HashMap};
 use std::str::FromStr;
 
 /// Id (and also name) of the initial default layout.
@@ -38,6 +42,19 @@ impl ModelState {
         self.variables.contains_key(var_id)
     }
 
+    /// Check if the given `var_id` is one of the placeholder variables `var0`, `var1`, ... that
+    /// are currently used as formal arguments in expressions of uninterpreted functions. There
+    /// are as many placeholders as the highest arity of an uninterpreted function in the model.
+    pub fn is_placeholder_var_id(&self, var_id: &VarId) -> bool {
+        let num_placeholders = self
+            .uninterpreted_fns
+            .values()
+            .map(|f| f.get_arity())
+            .max()
+            .unwrap_or(0);
+        (0..num_placeholders).any(|i| var_id.as_str() == format!("var{i}"))
+    }
+
     /// Check if the given `id` corresponds to some variable's valid Id.
     pub fn is_valid_var_id_str(&self, id: &str) -> bool {
         if let Ok(var_id) = VarId::from_str(id) {
@@ -274,6 +291,127 @@ impl ModelState {
         Ok(regulators)
     }
 
+    /// Return a sorted list of regulators of the `target` variable, whose regulations are
+    /// marked as essential (`Essentiality::True`).
+    pub fn essential_regulators(&self, target: &VarId) -> Result<Vec<VarId>, String> {
+        self.regulators_filtered(target, |r| *r.get_essentiality() == Essentiality::True)
+    }
+
+    /// Return a sorted list of regulators of the `target` variable, whose regulations are
+    /// marked as activating (`Monotonicity::Activation`).
+    pub fn activating_regulators(&self, target: &VarId) -> Result<Vec<VarId>, String> {
+        self.regulators_filtered(target, |r| *r.get_sign() == Monotonicity::Activation)
+    }
+
+    /// Return a sorted list of regulators of the `target` variable, whose regulations are
+    /// marked as inhibiting (`Monotonicity::Inhibition`).
+    pub fn inhibiting_regulators(&self, target: &VarId) -> Result<Vec<VarId>, String> {
+        self.regulators_filtered(target, |r| *r.get_sign() == Monotonicity::Inhibition)
+    }
+
+    /// **(internal)** Return a sorted list of regulators of the `target` variable, considering
+    /// only regulations satisfying the given predicate.
+    fn regulators_filtered<F>(&self, target: &VarId, predicate: F) -> Result<Vec<VarId>, String>
+    where
+        F: Fn(&Regulation) -> bool,
+    {
+        if !self.is_valid_var_id(target) {
+            return Err(format!(
+                "Target variable with ID {target} does not exist in this model."
+            ));
+        }
+
+        let mut regulators: Vec<VarId> = self
+            .regulations
+            .iter()
+            .filter(|r| r.get_target() == target && predicate(r))
+            .map(|r| r.get_regulator().clone())
+            .collect();
+        regulators.sort();
+        Ok(regulators)
+    }
+
+    /// Suggest signs for regulations with unknown monotonicity, inferred heuristically from the
+    /// time series given by `dataset` (see [Dataset::infer_monotonicity] for the assumptions).
+    ///
+    /// Returns copies of the affected regulations with the suggested sign set, which the user
+    /// can then choose to accept. Regulations that involve variables not present in the dataset,
+    /// or for which the data gives no clear evidence, are skipped. The list is sorted.
+    pub fn suggest_regulation_signs(&self, dataset: &Dataset) -> Vec<Regulation> {
+        let mut suggestions: Vec<Regulation> = self
+            .regulations
+            .iter()
+            .filter(|r| *r.get_sign() == Monotonicity::Unknown)
+            .filter_map(|r| {
+                let sign = dataset.infer_monotonicity(r.get_regulator(), r.get_target())?;
+                let mut suggestion = r.clone();
+                suggestion.swap_sign(sign);
+                Some(suggestion)
+            })
+            .collect();
+        suggestions.sort_by(|r1, r2| {
+            (r1.get_regulator(), r1.get_target()).cmp(&(r2.get_regulator(), r2.get_target()))
+        });
+        suggestions
+    }
+
+    /// Return a sorted list of regulations (as `(regulator, target)` pairs) whose sign is
+    /// still `Monotonicity::Unknown`.
+    pub fn regulations_with_unknown_sign(&self) -> Vec<(VarId, VarId)> {
+        self.regulation_pairs_filtered(|r| *r.get_sign() == Monotonicity::Unknown)
+    }
+
+    /// Return a sorted list of regulations (as `(regulator, target)` pairs) whose essentiality
+    /// is still `Essentiality::Unknown`.
+    pub fn regulations_with_unknown_essentiality(&self) -> Vec<(VarId, VarId)> {
+        self.regulation_pairs_filtered(|r| *r.get_essentiality() == Essentiality::Unknown)
+    }
+
+    /// **(internal)** Return a sorted list of `(regulator, target)` pairs of regulations
+    /// satisfying the given predicate.
+    fn regulation_pairs_filtered<F>(&self, predicate: F) -> Vec<(VarId, VarId)>
+    where
+        F: Fn(&Regulation) -> bool,
+    {
+        let mut pairs: Vec<(VarId, VarId)> = self
+            .regulations
+            .iter()
+            .filter(|r| predicate(r))
+            .map(|r| (r.get_regulator().clone(), r.get_target().clone()))
+            .collect();
+        pairs.sort();
+        pairs
+    }
+
+    /// Summarize how many signs and essentialities of regulations and of arguments of
+    /// uninterpreted functions are still `Unknown` (see [CompletenessReport]).
+    ///
+    /// Only the `Unknown` variants are counted, as all the other ones (including `Dual` sign
+    /// and `False` essentiality) are enforced during the inference.
+    pub fn completeness_report(&self) -> CompletenessReport {
+        let fn_args: Vec<_> = self
+            .uninterpreted_fns
+            .values()
+            .flat_map(|f| f.get_all_arguments())
+            .collect();
+        let num_fn_args_unknown_sign = fn_args
+            .iter()
+            .filter(|arg| arg.monotonicity == Monotonicity::Unknown)
+            .count();
+        let num_fn_args_unknown_essentiality = fn_args
+            .iter()
+            .filter(|arg| arg.essential == Essentiality::Unknown)
+            .count();
+        CompletenessReport::new(
+            self.num_regulations(),
+            self.regulations_with_unknown_sign().len(),
+            self.regulations_with_unknown_essentiality().len(),
+            fn_args.len(),
+            num_fn_args_unknown_sign,
+            num_fn_args_unknown_essentiality,
+        )
+    }
+
     /// Return a sorted list of variables that are regulated by the given `regulator` variable.
     pub fn targets(&self, regulator: &VarId) -> Result<Vec<&VarId>, String> {
         if !self.is_valid_var_id(regulator) {
@@ -292,6 +430,86 @@ impl ModelState {
         Ok(targets)
     }
 
+    /// Return all variables sorted so that each regulator comes before all its targets. Ties are
+    /// broken by variable IDs, making the order deterministic.
+    ///
+    /// This is only possible if the regulation graph is acyclic. Otherwise, `Err` is returned,
+    /// listing the variables of some cycle (self-regulations are also considered cycles).
+    pub fn topological_order(&self) -> Result<Vec<VarId>, String> {
+        // number of (not yet processed) regulators for each variable
+        let mut num_regulators: HashMap<&VarId, usize> =
+            self.variables.keys().map(|var_id| (var_id, 0)).collect();
+        for regulation in self.regulations.iter() {
+            *num_regulators.get_mut(regulation.get_target()).unwrap() += 1;
+        }
+
+        // Kahn's algorithm, always processing the smallest ready variable first
+        let mut ready: BTreeSet<&VarId> = num_regulators
+            .iter()
+            .filter(|(_, num)| **num == 0)
+            .map(|(var_id, _)| *var_id)
+            .collect();
+        let mut order = Vec::with_capacity(self.num_vars());
+        while let Some(var_id) = ready.pop_first() {
+            for regulation in self.regulations.iter() {
+                if regulation.get_regulator() == var_id {
+                    let target = regulation.get_target();
+                    let num = num_regulators.get_mut(target).unwrap();
+                    *num -= 1;
+                    if *num == 0 {
+                        ready.insert(target);
+                    }
+                }
+            }
+            order.push(var_id.clone());
+        }
+
+        if order.len() == self.num_vars() {
+            return Ok(order);
+        }
+        let remaining: BTreeSet<&VarId> = num_regulators
+            .into_iter()
+            .filter(|(_, num)| *num > 0)
+            .map(|(var_id, _)| var_id)
+            .collect();
+        let cycle = self.find_cycle_within(&remaining);
+        let cycle_str: Vec<String> = cycle.iter().map(|var_id| var_id.to_string()).collect();
+        Err(format!(
+            "Regulation graph contains a cycle: {}.",
+            cycle_str.join(" -> ")
+        ))
+    }
+
+    /// **(internal)** Find a cycle in the regulation graph restricted to the given variables.
+    /// Every variable in the set must have a regulator inside the set (this is the case for the
+    /// variables remaining after a failed topological sort).
+    ///
+    /// The cycle is returned as a sequence of variables starting and ending with the same one.
+    fn find_cycle_within(&self, variables: &BTreeSet<&VarId>) -> Vec<VarId> {
+        // walk backwards over the smallest regulators, until some variable repeats
+        let mut path: Vec<&VarId> = Vec::new();
+        let mut current = *variables.first().unwrap();
+        while !path.contains(&current) {
+            path.push(current);
+            current = self
+                .regulations
+                .iter()
+                .filter(|r| r.get_target() == current && variables.contains(r.get_regulator()))
+                .map(|r| r.get_regulator())
+                .min()
+                .unwrap();
+        }
+        // the cycle is the part of the path since the first visit of the repeated variable
+        let cycle_start = path.iter().position(|var_id| *var_id == current).unwrap();
+        let mut cycle: Vec<VarId> = path[cycle_start..]
+            .iter()
+            .rev()
+            .map(|v| (*v).clone())
+            .collect();
+        cycle.insert(0, current.clone());
+        cycle
+    }
+
     /// Get an update function for the given variable.
     pub fn get_update_fn(&self, var_id: &VarId) -> Result<&UpdateFn, String> {
         let update_fn = self.update_fns.get(var_id).ok_or(format!(
@@ -308,6 +526,12 @@ impl ModelState {
         Ok(update_fn.get_fn_expression())
     }
 
+    /// Get a simplified version of the update function's expression for the given variable.
+    /// See [UpdateFn::simplify] for details.
+    pub fn get_simplified_update_fn(&self, var_id: &VarId) -> Result<String, String> {
+        Ok(self.get_update_fn(var_id)?.simplify(self))
+    }
+
     /// Get a list of variables with "empty" update function.
     /// Returned list contains string ID of each such variable
     pub fn get_vars_with_empty_update(&self) -> Vec<&str> {
@@ -387,3 +611,132 @@ impl ModelState {
         DEFAULT_LAYOUT_ID
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::VarId;
+    use crate::sketchbook::model::{ModelState, Monotonicity, Regulation};
+    use crate::sketchbook::observations::{Dataset, Observation};
+
+    /// Test querying regulators of a variable filtered by essentiality or monotonicity.
+    #[test]
+    fn test_filtered_regulators() {
+        let var_id_name_pairs = vec![("a", "a"), ("b", "b"), ("c", "c"), ("d", "d")];
+        let mut model = ModelState::new_with_vars(var_id_name_pairs).unwrap();
+        let regulations = vec!["d -> a", "b -| a", "c ->? a", "a -|X a", "a -> b"];
+        model.add_multiple_regulations(regulations).unwrap();
+
+        let var = |id: &str| VarId::new(id).unwrap();
+        let a = var("a");
+        assert_eq!(
+            model.essential_regulators(&a).unwrap(),
+            vec![var("b"), var("d")]
+        );
+        assert_eq!(
+            model.activating_regulators(&a).unwrap(),
+            vec![var("c"), var("d")]
+        );
+        assert_eq!(
+            model.inhibiting_regulators(&a).unwrap(),
+            vec![var("a"), var("b")]
+        );
+        assert!(model.inhibiting_regulators(&var("b")).unwrap().is_empty());
+        assert!(model.essential_regulators(&var("x")).is_err());
+    }
+
+    /// Test sorting variables topologically, and detecting cycles.
+    #[test]
+    fn test_topological_order() {
+        let var_id_name_pairs = vec![("a", "a"), ("b", "b"), ("c", "c"), ("d", "d")];
+        let mut model = ModelState::new_with_vars(var_id_name_pairs).unwrap();
+        let regulations = vec!["d -> b", "c -> a", "b -| a"];
+        model.add_multiple_regulations(regulations).unwrap();
+
+        // `c` and `d` are both ready at the start, ties are broken by IDs
+        let order: Vec<String> = model
+            .topological_order()
+            .unwrap()
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        assert_eq!(order, vec!["c", "d", "b", "a"]);
+
+        // adding feedback `a -> d` creates a cycle
+        model.add_regulation_by_str("a -> d").unwrap();
+        let err = model.topological_order().unwrap_err();
+        assert_eq!(err, "Regulation graph contains a cycle: a -> d -> b -> a.");
+
+        // self-loops are cycles too
+        let mut model = ModelState::new_with_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -> b", "b -> b"])
+            .unwrap();
+        let err = model.topological_order().unwrap_err();
+        assert_eq!(err, "Regulation graph contains a cycle: b -> b.");
+    }
+
+    /// Test suggesting signs of regulations with unknown monotonicity from a dataset.
+    #[test]
+    fn test_suggest_regulation_signs() {
+        let var_id_name_pairs = vec![("a", "a"), ("b", "b"), ("c", "c")];
+        let mut model = ModelState::new_with_vars(var_id_name_pairs).unwrap();
+        let regulations = vec!["a -? b", "c -? b", "a -| c", "b -? a"];
+        model.add_multiple_regulations(regulations).unwrap();
+
+        let observations = vec![
+            Observation::try_from_str("100", "o1").unwrap(),
+            Observation::try_from_str("110", "o2").unwrap(),
+            Observation::try_from_str("010", "o3").unwrap(),
+            Observation::try_from_str("000", "o4").unwrap(),
+        ];
+        let dataset = Dataset::new("d", observations, vec!["a", "b", "c"]).unwrap();
+
+        // `a -| c` already has a sign, `c` never changes, and `b` changes in both directions
+        // while `c` is 0 (mixed evidence for `c -? b`)
+        let suggestions = model.suggest_regulation_signs(&dataset);
+        let expected = vec![
+            Regulation::try_from_string("a -> b").unwrap(),
+            Regulation::try_from_string("b -| a").unwrap(),
+        ];
+        assert_eq!(suggestions, expected);
+    }
+
+    /// Test querying regulations and function arguments with unknown sign or essentiality.
+    #[test]
+    fn test_completeness_report() {
+        let var_id_name_pairs = vec![("a", "a"), ("b", "b"), ("c", "c")];
+        let mut model = ModelState::new_with_vars(var_id_name_pairs).unwrap();
+        let regulations = vec!["a -> b", "b -? a", "c -*? a", "a -?? c"];
+        model.add_multiple_regulations(regulations).unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("f", "f", 2)
+            .unwrap();
+        model
+            .set_uninterpreted_fn_monotonicity_by_str("f", Monotonicity::Activation, 0)
+            .unwrap();
+
+        let var = |id: &str| VarId::new(id).unwrap();
+        assert_eq!(
+            model.regulations_with_unknown_sign(),
+            vec![(var("a"), var("c")), (var("b"), var("a"))]
+        );
+        assert_eq!(
+            model.regulations_with_unknown_essentiality(),
+            vec![(var("a"), var("c")), (var("c"), var("a"))]
+        );
+
+        // 12 signs and essentialities in total, 7 of them unknown (`Dual` sign is specified)
+        let report = model.completeness_report();
+        assert_eq!(report.num_regulations, 4);
+        assert_eq!(report.num_fn_args, 2);
+        assert_eq!(report.num_fn_args_unknown_sign, 1);
+        assert_eq!(report.num_fn_args_unknown_essentiality, 2);
+        assert!((report.percent_specified - 500.0 / 12.0).abs() < 1e-9);
+        assert_eq!(
+            ModelState::new_empty()
+                .completeness_report()
+                .percent_specified,
+            100.0
+        );
+    }
+}
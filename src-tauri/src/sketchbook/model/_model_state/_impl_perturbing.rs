@@ -0,0 +1,75 @@
+use crate::sketchbook::ids::VarId;
+use crate::sketchbook::model::{ModelState, UpdateFn};
+use std::collections::HashMap;
+
+/// Methods for deriving perturbed variants of `ModelState` (e.g., for studying interventions).
+impl ModelState {
+    /// Create a perturbed copy of this model. Each variable in `perturbations` is clamped
+    /// to the given value, i.e., it is knocked out (`false`) or over-expressed (`true`). Its
+    /// update function is replaced by the corresponding constant, and all its incoming
+    /// regulations (including a self-regulation) are removed. The frozen flag of the update
+    /// function is kept.
+    ///
+    /// Uninterpreted functions are kept, even if they are not used anymore after replacing
+    /// the update functions (they may still be referenced by static properties).
+    ///
+    /// Returns `Err` if some of the perturbed variables does not exist in this model. The
+    /// original model is never modified.
+    pub fn perturb(&self, perturbations: &HashMap<VarId, bool>) -> Result<ModelState, String> {
+        if let Some(var_id) = perturbations
+            .keys()
+            .find(|var_id| !self.is_valid_var_id(var_id))
+        {
+            return Err(format!(
+                "Variable with id {var_id} does not exist in this model."
+            ));
+        }
+
+        let mut perturbed = self.clone();
+        perturbed
+            .regulations
+            .retain(|r| !perturbations.contains_key(r.get_target()));
+        for (var_id, value) in perturbations {
+            let mut constant_fn = UpdateFn::new_constant(*value);
+            // the update fn always exists for a valid variable
+            constant_fn.set_frozen(self.get_update_fn(var_id)?.is_frozen());
+            perturbed.update_fns.insert(var_id.clone(), constant_fn);
+        }
+        Ok(perturbed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::VarId;
+    use crate::sketchbook::model::ModelState;
+    use std::collections::HashMap;
+
+    /// Test creating perturbed copies of a model by fixing variables to constants.
+    #[test]
+    fn test_perturb() {
+        let mut model = ModelState::new_with_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_multiple_regulations(vec!["a -> b", "b -| a", "a -> a"])
+            .unwrap();
+        let var_a = VarId::new("a").unwrap();
+        let var_b = VarId::new("b").unwrap();
+        model.set_update_fn(&var_a, "!b & a").unwrap();
+        model.set_update_fn(&var_b, "a").unwrap();
+        let model_orig = model.clone();
+
+        // knockout of `a`
+        let perturbations = HashMap::from([(var_a.clone(), false)]);
+        let perturbed = model.perturb(&perturbations).unwrap();
+        assert_eq!(model, model_orig);
+        assert_eq!(perturbed.get_update_fn_string(&var_a).unwrap(), "false");
+        assert_eq!(perturbed.get_update_fn_string(&var_b).unwrap(), "a");
+        assert_eq!(perturbed.num_regulations(), 1);
+        assert!(perturbed.get_regulation(&var_a, &var_b).is_ok());
+        assert_eq!(perturbed.num_vars(), 2);
+
+        // perturbing a non-existing variable fails
+        let invalid_perturbations = HashMap::from([(VarId::new("c").unwrap(), true)]);
+        assert!(model.perturb(&invalid_perturbations).is_err());
+    }
+}
@@ -16,6 +16,8 @@ const ADD_LAYOUT_PATH: &str = "add";
 const UPDATE_POSITION_PATH: &str = "update_position";
 // Update positions of ALL nodes in a particular layout
 const UPDATE_ALL_POSITIONS_PATH: &str = "update_all_positions";
+// Automatically arrange ALL nodes in a particular layout
+const AUTO_ARRANGE_PATH: &str = "auto_arrange";
 // Remove whole layout
 const REMOVE_LAYOUT_PATH: &str = "remove";
 
@@ -110,18 +112,7 @@ impl ModelState {
             let new_nodes_data: Vec<LayoutNodeData> = serde_json::from_str(payload.as_str())?;
 
             // snapshot of original layout (so that we can reverse)
-            let orig_nodes_data: Vec<LayoutNodeData> = self
-                .get_layout(&layout_id)?
-                .layout_nodes()
-                .map(|(var_id, node)| {
-                    LayoutNodeData::new(
-                        layout_id.as_str(),
-                        var_id.as_str(),
-                        node.get_px(),
-                        node.get_py(),
-                    )
-                })
-                .collect();
+            let orig_nodes_data = self.get_layout_nodes_data(&layout_id)?;
 
             // perform the event, prepare the state-change variant (move ID from path to payload)
             self.update_all_positions(&layout_id, new_nodes_data)?;
@@ -134,6 +125,25 @@ impl ModelState {
             let mut reverse_event = event.clone();
             reverse_event.payload = Some(serde_json::to_string(&orig_nodes_data)?);
             Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with(AUTO_ARRANGE_PATH, at_path).is_some() {
+            Self::assert_payload_empty(event, component_name)?;
+
+            // snapshot of original layout (so that we can reverse)
+            let orig_nodes_data = self.get_layout_nodes_data(&layout_id)?;
+
+            // perform the event, prepare the state-change variant (all updated node positions)
+            self.auto_arrange_layout(&layout_id)?;
+            let new_nodes_data = self.get_layout_nodes_data(&layout_id)?;
+            let state_change = Event::build(
+                &["sketch", "model", "layout", "update_all_positions"],
+                Some(&serde_json::to_string(&new_nodes_data)?),
+            );
+
+            // prepare the reverse event (which sets all the original positions back)
+            let reverse_at_path = ["layout", layout_id.as_str(), UPDATE_ALL_POSITIONS_PATH];
+            let reverse_payload = serde_json::to_string(&orig_nodes_data)?;
+            let reverse_event = mk_model_event(&reverse_at_path, Some(&reverse_payload));
+            Ok(make_reversible(state_change, event, reverse_event))
         } else if Self::starts_with(REMOVE_LAYOUT_PATH, at_path).is_some() {
             Self::assert_payload_empty(event, component_name)?;
 
@@ -152,4 +162,21 @@ impl ModelState {
             Self::invalid_path_error_specific(at_path, component_name)
         }
     }
+
+    /// **(internal)** Collect `LayoutNodeData` for all nodes of a given layout.
+    fn get_layout_nodes_data(&self, layout_id: &LayoutId) -> Result<Vec<LayoutNodeData>, String> {
+        let nodes_data = self
+            .get_layout(layout_id)?
+            .layout_nodes()
+            .map(|(var_id, node)| {
+                LayoutNodeData::new(
+                    layout_id.as_str(),
+                    var_id.as_str(),
+                    node.get_px(),
+                    node.get_py(),
+                )
+            })
+            .collect();
+        Ok(nodes_data)
+    }
 }
@@ -29,6 +29,8 @@ const SET_SIGN_RAW_PATH: &str = "set_sign_raw";
 const SET_ESSENTIALITY_PATH: &str = "set_essentiality";
 // set regulation's essentiality (without additional changes to static properties)
 const SET_ESSENTIALITY_RAW_PATH: &str = "set_essentiality_raw";
+// set regulation's annotation
+const SET_ANNOTATION_PATH: &str = "set_annotation";
 
 /// Implementation for events related to `regulations` of the model.
 impl ModelState {
@@ -113,13 +115,8 @@ impl ModelState {
         // parse the payload
         let payload = Self::clone_payload_str(event, component_name)?;
         let reg_data = RegulationData::from_json_str(payload.as_str())?;
-        let regulator_id = self.get_var_id(&reg_data.regulator)?;
-        let target_id = self.get_var_id(&reg_data.target)?;
-        let sign: Monotonicity = reg_data.sign;
-        let essential: Essentiality = reg_data.essential;
-
-        // perform the event
-        self.add_regulation(regulator_id, target_id, essential, sign)?;
+        // perform the event (the regulation is added including its annotation)
+        self.add_regulation_raw(reg_data.to_reg()?)?;
 
         // prepare the state-change and reverse event (which is a remove event)
         let state_change = mk_model_state_change(&["regulation", "add"], &reg_data);
@@ -334,6 +331,25 @@ impl ModelState {
             let mut reverse_event = event.clone();
             reverse_event.payload = Some(orig_essentiality.to_json_str());
             Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with(SET_ANNOTATION_PATH, at_path).is_some() {
+            // get the payload - a string for the "new_annotation"
+            let new_annot = Self::clone_payload_str(event, component_name)?;
+            let original_reg = self.get_regulation(&regulator_id, &target_id)?;
+            let orig_annot = original_reg.get_annotation().to_string();
+            if orig_annot == new_annot {
+                return Ok(Consumed::NoChange);
+            }
+
+            // perform the event, prepare the state-change variant (move IDs from path to payload)
+            self.set_regulation_annotation(&regulator_id, &target_id, &new_annot)?;
+            let new_reg = self.get_regulation(&regulator_id, &target_id)?;
+            let reg_data = RegulationData::from_reg(new_reg);
+            let state_change = mk_model_state_change(&["regulation", "set_annotation"], &reg_data);
+
+            // prepare the reverse event
+            let mut reverse_event = event.clone();
+            reverse_event.payload = Some(orig_annot);
+            Ok(make_reversible(state_change, event, reverse_event))
         } else {
             Self::invalid_path_error_specific(at_path, component_name)
         }
@@ -2,8 +2,8 @@ use crate::app::event::Event;
 use crate::app::state::{Consumed, SessionHelper};
 use crate::app::{AeonError, DynError};
 use crate::sketchbook::data_structs::{
-    ChangeArgEssentialData, ChangeArgMonotoneData, ChangeIdData, ModelData, StatPropertyData,
-    UninterpretedFnData,
+    ChangeArgEssentialData, ChangeArgMonotoneData, ChangeIdData, ModelData, ReorderArgsData,
+    StatPropertyData, UninterpretedFnData,
 };
 use crate::sketchbook::event_utils::{
     make_reversible, mk_model_event, mk_model_state_change, mk_stat_prop_event,
@@ -14,6 +14,7 @@ use crate::sketchbook::model::{
 };
 use crate::sketchbook::properties::shortcuts::*;
 use crate::sketchbook::properties::StatProperty;
+use crate::sketchbook::utils::{assert_valid_permutation, inverse_permutation};
 use crate::sketchbook::JsonSerde;
 
 /* Constants for event path segments in `ModelState` related to uninterpreted functions. */
@@ -38,6 +39,10 @@ const SET_ID_PATH: &str = "set_id";
 const SET_ARITY_RAW_PATH: &str = "set_arity_raw";
 // set function's arity, and also propagate changes into static properties
 const SET_ARITY_PATH: &str = "set_arity";
+// reorder function's arguments, and also propagate changes into static properties
+const REORDER_ARGS_PATH: &str = "reorder_args";
+// reorder function's arguments (without additional changes to static properties)
+const REORDER_ARGS_RAW_PATH: &str = "reorder_args_raw";
 // set function's expression
 const SET_EXPRESSION_PATH: &str = "set_expression";
 // set function's monotonicity, and also propagate changes into static properties
@@ -310,6 +315,49 @@ impl ModelState {
             let reverse_at_path = ["uninterpreted_fn", new_id.as_str(), "set_id_raw"];
             let reverse_event = mk_model_event(&reverse_at_path, Some(fn_id.as_str()));
             Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with(REORDER_ARGS_PATH, at_path).is_some() {
+            // get the payload - json string encoding the permutation
+            let payload = Self::clone_payload_str(event, component_name)?;
+            let permutation: Vec<usize> = serde_json::from_str(&payload)?;
+            let arity = self.get_uninterpreted_fn(&fn_id)?.get_arity();
+            assert_valid_permutation(&permutation, arity)?;
+            if permutation.iter().enumerate().all(|(i, idx)| i == *idx) {
+                return Ok(Consumed::NoChange);
+            }
+
+            // we must handle the event itself, and all potential static property changes
+            let mut event_list = Vec::new();
+            // the raw event of reordering the arguments (payload stays the same)
+            let fn_event_path = ["uninterpreted_fn", fn_id.as_str(), "reorder_args_raw"];
+            let fn_event = mk_model_event(&fn_event_path, Some(&payload));
+            event_list.push(fn_event);
+
+            // event for modifying all affected static properties (we do it via a single special event)
+            let reorder_data = ReorderArgsData::new(fn_id.as_str(), &permutation).to_json_str();
+            let prop_event =
+                mk_stat_prop_event(&["permute_fn_args_everywhere"], Some(&reorder_data));
+            event_list.push(prop_event);
+            event_list.reverse(); // has to be reversed
+            Ok(Consumed::Restart(event_list))
+        } else if Self::starts_with(REORDER_ARGS_RAW_PATH, at_path).is_some() {
+            // get the payload - json string encoding the permutation
+            let payload = Self::clone_payload_str(event, component_name)?;
+            let permutation: Vec<usize> = serde_json::from_str(&payload)?;
+
+            // perform the reordering (which can modify many update/uninterpreted functions)
+            self.reorder_uninterpreted_fn_args(&fn_id, &permutation)?;
+
+            // Similar to the ID change, this can affect many parts of the model, so the event
+            // returns the whole updated model data to the FE.
+            let model_data = ModelData::from_model(self);
+            let state_change =
+                mk_model_state_change(&["uninterpreted_fn", "reorder_args"], &model_data);
+
+            // prepare the reverse event (reordering by the inverse permutation)
+            let reverse_payload = serde_json::to_string(&inverse_permutation(&permutation))?;
+            let mut reverse_event = event.clone();
+            reverse_event.payload = Some(reverse_payload);
+            Ok(make_reversible(state_change, event, reverse_event))
         } else if Self::starts_with(SET_ARITY_PATH, at_path).is_some() {
             // get the payload - string for "new_arity"
             let payload = Self::clone_payload_str(event, component_name)?;
@@ -31,6 +31,14 @@ const SET_ID_PATH: &str = "set_id";
 const SET_ID_RAW_PATH: &str = "set_id_raw";
 // set variable's update fn
 const SET_UPDATE_FN_PATH: &str = "set_update_fn";
+// set variable's update fn to a constant
+const SET_UPDATE_FN_CONSTANT_PATH: &str = "set_update_fn_constant";
+// reset variable's update fn to the default empty one
+const CLEAR_UPDATE_FN_PATH: &str = "clear_update_fn";
+// set whether variable's update fn is frozen
+const SET_UPDATE_FN_FROZEN_PATH: &str = "set_update_fn_frozen";
+// set whether variable is an input (free constant)
+const SET_VAR_INPUT_PATH: &str = "set_var_input";
 
 /// Implementation for events related to `variables` of the model.
 impl ModelState {
@@ -167,6 +175,9 @@ impl ModelState {
             &variable_data.name,
             &variable_data.annotation,
         )?;
+        if variable_data.is_input {
+            self.set_var_input(&VarId::new(&variable_data.id)?, true)?;
+        }
 
         // prepare the state-change and reverse event (which is a remove event)
         let state_change = mk_model_state_change(&["variable", "add"], &variable_data);
@@ -252,8 +263,9 @@ impl ModelState {
             // get the payload - string with modified variable data
             let payload = Self::clone_payload_str(event, component_name)?;
             let new_data = VariableData::from_json_str(&payload)?;
-            let new_var = new_data.to_var()?;
             let original_var = self.get_variable(&var_id)?;
+            // the input flag is changed by a separate event, keep the original one
+            let new_var = new_data.to_var()?.with_input(original_var.is_input());
             let original_data =
                 VariableData::from_var(&var_id, original_var, self.get_update_fn(&var_id)?);
             if &new_var == original_var {
@@ -310,6 +322,93 @@ impl ModelState {
             let reverse_at_path = ["variable", new_id.as_str(), "set_id_raw"];
             let reverse_event = mk_model_event(&reverse_at_path, Some(var_id.as_str()));
             Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with(SET_UPDATE_FN_FROZEN_PATH, at_path).is_some() {
+            // get the payload - a bool flag (serialized) whether the update fn should be frozen
+            let payload = Self::clone_payload_str(event, component_name)?;
+            let frozen: bool = serde_json::from_str(&payload)?;
+            let original_frozen = self.get_update_fn(&var_id)?.is_frozen();
+            if frozen == original_frozen {
+                return Ok(Consumed::NoChange);
+            }
+
+            // perform the event, prepare the state-change variant (move id from path to payload)
+            self.set_update_fn_frozen(&var_id, frozen)?;
+            let var_data = VariableData::from_var(
+                &var_id,
+                self.get_variable(&var_id)?,
+                self.get_update_fn(&var_id)?,
+            );
+            let state_change =
+                mk_model_state_change(&["variable", "set_update_fn_frozen"], &var_data);
+
+            // prepare the reverse event
+            let mut reverse_event = event.clone();
+            reverse_event.payload = Some(serde_json::to_string(&original_frozen)?);
+            Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with(SET_UPDATE_FN_CONSTANT_PATH, at_path).is_some() {
+            // get the payload - a bool value (serialized) of the constant
+            let payload = Self::clone_payload_str(event, component_name)?;
+            let value: bool = serde_json::from_str(&payload)?;
+            let original_expression = self.get_update_fn(&var_id)?.to_string();
+            if self.get_update_fn(&var_id)?.as_constant() == Some(value) {
+                return Ok(Consumed::NoChange);
+            }
+
+            // perform the event, prepare the state-change variant (same as for general update fns)
+            self.set_update_fn_constant(&var_id, value)?;
+            let var_data = VariableData::from_var(
+                &var_id,
+                self.get_variable(&var_id)?,
+                self.get_update_fn(&var_id)?,
+            );
+            let state_change = mk_model_state_change(&["variable", "set_update_fn"], &var_data);
+
+            // the reverse event sets back the original expression
+            let reverse_at_path = ["variable", var_id.as_str(), "set_update_fn"];
+            let reverse_event = mk_model_event(&reverse_at_path, Some(&original_expression));
+            Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with(CLEAR_UPDATE_FN_PATH, at_path).is_some() {
+            Self::assert_payload_empty(event, component_name)?;
+            let original_expression = self.get_update_fn(&var_id)?.to_string();
+            if self.get_update_fn(&var_id)?.has_empty_expression() {
+                return Ok(Consumed::NoChange);
+            }
+
+            // perform the event, prepare the state-change variant (same as for general update fns)
+            self.clear_update_fn(&var_id)?;
+            let var_data = VariableData::from_var(
+                &var_id,
+                self.get_variable(&var_id)?,
+                self.get_update_fn(&var_id)?,
+            );
+            let state_change = mk_model_state_change(&["variable", "set_update_fn"], &var_data);
+
+            // the reverse event sets back the original expression
+            let reverse_at_path = ["variable", var_id.as_str(), "set_update_fn"];
+            let reverse_event = mk_model_event(&reverse_at_path, Some(&original_expression));
+            Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with(SET_VAR_INPUT_PATH, at_path).is_some() {
+            // get the payload - a bool flag (serialized) whether the variable should be an input
+            let payload = Self::clone_payload_str(event, component_name)?;
+            let is_input: bool = serde_json::from_str(&payload)?;
+            let original_is_input = self.get_variable(&var_id)?.is_input();
+            if is_input == original_is_input {
+                return Ok(Consumed::NoChange);
+            }
+
+            // perform the event, prepare the state-change variant (move id from path to payload)
+            self.set_var_input(&var_id, is_input)?;
+            let var_data = VariableData::from_var(
+                &var_id,
+                self.get_variable(&var_id)?,
+                self.get_update_fn(&var_id)?,
+            );
+            let state_change = mk_model_state_change(&["variable", "set_var_input"], &var_data);
+
+            // prepare the reverse event
+            let mut reverse_event = event.clone();
+            reverse_event.payload = Some(serde_json::to_string(&original_is_input)?);
+            Ok(make_reversible(state_change, event, reverse_event))
         } else if Self::starts_with(SET_UPDATE_FN_PATH, at_path).is_some() {
             // get the payload - string for "new_expression"
             let new_expression = Self::clone_payload_str(event, component_name)?;
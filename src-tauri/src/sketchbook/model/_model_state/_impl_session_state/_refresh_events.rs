@@ -69,6 +69,19 @@ impl ModelState {
         make_refresh_event(full_path, regulation_list)
     }
 
+    /// Get a summary of how completely the model is specified (see
+    /// [ModelState::completeness_report]).
+    pub(super) fn refresh_completeness_report(
+        &self,
+        full_path: &[String],
+    ) -> Result<Event, DynError> {
+        let report = self.completeness_report();
+        Ok(Event {
+            path: full_path.to_vec(),
+            payload: Some(report.to_json_str()),
+        })
+    }
+
     /// Get a list of all layouts (just basic information like IDs and names).
     pub(super) fn refresh_layouts(&self, full_path: &[String]) -> Result<Event, DynError> {
         let mut layout_list: Vec<LayoutData> = self
@@ -40,6 +40,8 @@ const REFRESH_REGULATIONS_PATH: &str = "get_regulations";
 const REFRESH_LAYOUTS_PATH: &str = "get_layouts";
 // refresh all nodes in a particular layout
 const REFRESH_LAYOUT_NODES_PATH: &str = "get_layout_nodes";
+// refresh summary of unknown signs and essentialities in the model
+const REFRESH_COMPLETENESS_PATH: &str = "get_completeness_report";
 
 impl SessionHelper for ModelState {}
 
@@ -62,6 +64,7 @@ impl SessionState for ModelState {
             Some(&REFRESH_REGULATIONS_PATH) => self.refresh_regulations(full_path),
             Some(&REFRESH_LAYOUTS_PATH) => self.refresh_layouts(full_path),
             Some(&REFRESH_LAYOUT_NODES_PATH) => self.refresh_layout_nodes(full_path, &at_path[1..]),
+            Some(&REFRESH_COMPLETENESS_PATH) => self.refresh_completeness_report(full_path),
             _ => Self::invalid_path_error_generic(at_path),
         }
     }
@@ -0,0 +1,185 @@
+use crate::sketchbook::ids::{UninterpretedFnId, VarId};
+use crate::sketchbook::model::{FnTree, ModelState};
+use std::collections::HashMap;
+
+/// Maximal number of expressions kept in the cache. Once exceeded, the cache is cleared, so that
+/// it does not grow indefinitely during long editing sessions.
+const MAX_CACHED_EXPRESSIONS: usize = 1024;
+
+/// Cache of parsed function expressions, used to avoid re-parsing identical expressions (e.g.,
+/// when importing models with many repeated update functions).
+///
+/// Entries are keyed by the expression string and the ID of the uninterpreted function whose
+/// expression is parsed (`None` for update functions). A valid expression is parsed the same
+/// way as long as all the symbols it uses still exist (with the same arities), so each entry
+/// keeps these symbols and is only reused if they match the current model. The check only
+/// depends on the size of the expression, not on the size of the model.
+#[derive(Clone, Debug, Default)]
+pub(super) struct ParseCache {
+    entries: HashMap<(String, Option<UninterpretedFnId>), CachedExpression>,
+}
+
+/// **(internal)** A single cached expression - the syntactic tree, its canonical string, and
+/// the symbols the parsing depends on (network variables, and function symbols with arities,
+/// including the function whose expression is parsed).
+#[derive(Clone, Debug)]
+struct CachedExpression {
+    tree: FnTree,
+    canonical_str: String,
+    variables: Vec<VarId>,
+    fn_arities: Vec<(UninterpretedFnId, usize)>,
+}
+
+impl CachedExpression {
+    /// Check whether all symbols used by the expression are still valid in the model.
+    fn is_valid(&self, model: &ModelState) -> bool {
+        self.variables.iter().all(|id| model.is_valid_var_id(id))
+            && self
+                .fn_arities
+                .iter()
+                .all(|(id, arity)| model.get_uninterpreted_fn_arity(id) == Ok(*arity))
+    }
+}
+
+impl ParseCache {
+    /// Number of cached expressions.
+    #[cfg(test)]
+    pub(super) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Parse the expression (with the model as a context), or reuse the cached result. The
+    /// syntactic tree and its canonical string are returned. Invalid expressions are not cached.
+    fn parse(
+        &mut self,
+        expression: &str,
+        model: &ModelState,
+        own_fn: Option<&UninterpretedFnId>,
+    ) -> Result<(FnTree, String), String> {
+        let key = (expression.to_string(), own_fn.cloned());
+        if let Some(cached) = self.entries.get(&key) {
+            if cached.is_valid(model) {
+                return Ok((cached.tree.clone(), cached.canonical_str.clone()));
+            }
+        }
+        if self.entries.len() >= MAX_CACHED_EXPRESSIONS {
+            self.entries.clear();
+        }
+
+        let tree = FnTree::try_from_str(expression, model, own_fn)?;
+        let arity = own_fn
+            .map(|fn_id| model.get_uninterpreted_fn_arity(fn_id))
+            .transpose()?;
+        let canonical_str = tree.to_string(model, arity);
+
+        // placeholder variables of uninterpreted functions are given by the arity
+        let variables = match own_fn {
+            Some(_) => Vec::new(),
+            None => tree.collect_variables().into_iter().collect(),
+        };
+        let mut fn_ids: Vec<UninterpretedFnId> = tree.collect_fn_symbols().into_iter().collect();
+        fn_ids.extend(own_fn.cloned());
+        let fn_arities = fn_ids
+            .into_iter()
+            .map(|id| {
+                let arity = model.get_uninterpreted_fn_arity(&id)?;
+                Ok((id, arity))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let cached = CachedExpression {
+            tree: tree.clone(),
+            canonical_str: canonical_str.clone(),
+            variables,
+            fn_arities,
+        };
+        self.entries.insert(key, cached);
+        Ok((tree, canonical_str))
+    }
+}
+
+impl ModelState {
+    /// **(internal)** Parse the expression of an update function (if `own_fn` is `None`), or of
+    /// the given uninterpreted function, reusing the cached result for identical expressions.
+    ///
+    /// Returns `None` for empty (whitespace-only) expressions, otherwise the syntactic tree and
+    /// its canonical string.
+    pub(super) fn parse_expression_cached(
+        &mut self,
+        expression: &str,
+        own_fn: Option<&UninterpretedFnId>,
+    ) -> Result<Option<(FnTree, String)>, String> {
+        if expression.chars().all(|c| c.is_whitespace()) {
+            return Ok(None);
+        }
+        // temporarily take the cache out, so that the model can be used as a context
+        let mut cache = std::mem::take(&mut self.parse_cache);
+        let result = cache.parse(expression, self, own_fn);
+        self.parse_cache = cache;
+        result.map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::VarId;
+    use crate::sketchbook::model::{ModelState, UpdateFn};
+
+    #[test]
+    /// Test that setting many identical update functions only parses the expression once, with
+    /// the same result as parsing every expression from scratch.
+    fn test_parse_cache_reuse() {
+        let num_vars = 200;
+        let var_names: Vec<String> = (0..num_vars).map(|i| format!("v{i}")).collect();
+        let vars = var_names.iter().map(|v| (v.as_str(), v.as_str())).collect();
+        let mut model = ModelState::new_with_vars(vars).unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("f", "f", 2)
+            .unwrap();
+        let expression = "(v0 & !v1) | f(v2, v3 | v4)";
+        let var_ids: Vec<VarId> = var_names.iter().map(|v| VarId::new(v).unwrap()).collect();
+
+        let uncached: Vec<UpdateFn> = var_ids
+            .iter()
+            .map(|_| UpdateFn::try_from_str(expression, &model).unwrap())
+            .collect();
+        for var_id in var_ids.iter() {
+            model.set_update_fn(var_id, expression).unwrap();
+        }
+
+        // the expression was only parsed once, with the same result
+        assert_eq!(model.parse_cache.len(), 1);
+        for (var_id, update_fn) in var_ids.iter().zip(uncached.iter()) {
+            assert_eq!(model.get_update_fn(var_id).unwrap(), update_fn);
+        }
+    }
+
+    #[test]
+    /// Test that cached expressions are invalidated when variables or function symbols change.
+    fn test_parse_cache_invalidation() {
+        let mut model = ModelState::new_with_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_empty_uninterpreted_fn_by_str("f", "f", 1)
+            .unwrap();
+        let var_a = VarId::new("a").unwrap();
+        let var_b = VarId::new("b").unwrap();
+
+        // after removing `b`, the cached expression must not be valid anymore
+        model.set_update_fn(&var_a, "b").unwrap();
+        model.set_update_fn(&var_a, "").unwrap();
+        model.remove_var(&var_b).unwrap();
+        assert!(model.set_update_fn(&var_a, "b").is_err());
+
+        // the same after changing the arity of `f`
+        model.set_update_fn(&var_a, "f(a)").unwrap();
+        model.set_update_fn(&var_a, "").unwrap();
+        model.set_uninterpreted_fn_arity_by_str("f", 2).unwrap();
+        assert!(model.set_update_fn(&var_a, "f(a)").is_err());
+        assert!(model.set_update_fn(&var_a, "f(a, a)").is_ok());
+
+        // the cache does not affect comparison of models
+        let mut other_model = model.clone();
+        other_model.parse_cache = Default::default();
+        assert_eq!(model, other_model);
+    }
+}
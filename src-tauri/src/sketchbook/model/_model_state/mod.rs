@@ -2,10 +2,15 @@ use crate::sketchbook::ids::{LayoutId, UninterpretedFnId, VarId};
 use crate::sketchbook::layout::Layout;
 use crate::sketchbook::model::{Regulation, UninterpretedFn, UpdateFn, Variable};
 use crate::sketchbook::Manager;
+use _parse_cache::ParseCache;
 use std::collections::{HashMap, HashSet};
 
 /// **(internal)** Methods for converting between `ModelState` and `BooleanNetwork`.
 mod _impl_convert_bn;
+/// **(internal)** Methods for exporting `ModelState` into the `.bnet` format.
+mod _impl_convert_bnet;
+/// **(internal)** Methods for exporting `ModelState` into the Graphviz DOT format.
+mod _impl_convert_dot;
 /// **(internal)** Methods for converting between `ModelState` and `RegulatoryGraph`.
 mod _impl_convert_reg_graph;
 /// **(internal)** Methods for safely constructing or editing instances of `ModelState`.
@@ -13,12 +18,21 @@ mod _impl_editing;
 /// **(internal)** Methods for working with function expressions (e.g., substitution,
 /// propagation, or consistency checking).
 mod _impl_fn_expressions;
+/// **(internal)** Methods for structural analysis of the regulatory graph of `ModelState`.
+mod _impl_graph_analysis;
 /// **(internal)** Implementation of the safe identifier generating.
 mod _impl_id_generating;
+/// **(internal)** Methods for merging several instances of `ModelState` together.
+mod _impl_merging;
 /// **(internal)** Methods for observing instances of `ModelState` (various getters, etc.).
 mod _impl_observing;
+/// **(internal)** Methods for deriving perturbed variants (knockouts, over-expressions) of
+/// `ModelState`.
+mod _impl_perturbing;
 /// **(internal)** Implementation of event-based API for the [crate::app::state::SessionState] trait.
 mod _impl_session_state;
+/// **(internal)** Cache of parsed function expressions.
+mod _parse_cache;
 
 /// Structure representing the state of the "model" part of the sketch. `ModelState`
 /// encompasses information about the PSBN and its regulatory network. Specifically, it
@@ -35,13 +49,27 @@ mod _impl_session_state;
 /// `ModelState` can be observed/edited using its classical Rust API, as well as through
 /// the external events (as it implements the `SessionState` event). Events are used
 /// as a way for communication between Rust backend and TS frontend of the app.
-#[derive(Clone, Debug, PartialEq)]
+///
+/// The model also keeps a cache of parsed function expressions, which is not considered
+/// when comparing models.
+#[derive(Clone, Debug)]
 pub struct ModelState {
     variables: HashMap<VarId, Variable>,
     regulations: HashSet<Regulation>,
     update_fns: HashMap<VarId, UpdateFn>,
     uninterpreted_fns: HashMap<UninterpretedFnId, UninterpretedFn>,
     layouts: HashMap<LayoutId, Layout>,
+    parse_cache: ParseCache,
+}
+
+impl PartialEq for ModelState {
+    fn eq(&self, other: &Self) -> bool {
+        self.variables == other.variables
+            && self.regulations == other.regulations
+            && self.update_fns == other.update_fns
+            && self.uninterpreted_fns == other.uninterpreted_fns
+            && self.layouts == other.layouts
+    }
 }
 
 impl Manager for ModelState {}
@@ -2,6 +2,7 @@ use crate::sketchbook::ids::VarId;
 use crate::sketchbook::model::{Essentiality, Monotonicity};
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Error, Formatter};
+use std::hash::{Hash, Hasher};
 
 use regex::Regex;
 
@@ -46,12 +47,40 @@ lazy_static! {
 /// Finally, an additional `X`, `?` at the end of 'relationship' signifies that the the regulation
 /// is non-essential (non-essential) or the essentiality is unknown, respectively.
 /// Together, this gives the following options:  `->, ->?, -|, -|?, -*, -*?, -?, -??`.
-#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+///
+/// Each regulation can also carry an optional `annotation` (e.g., a citation supporting the
+/// interaction). The annotation is not part of the regulation's identity, i.e., it is ignored
+/// by `PartialEq` and `Hash` (and it is not part of the string representation).
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Regulation {
     regulator: VarId,
     target: VarId,
     essential: Essentiality,
     regulation_sign: Monotonicity,
+    #[serde(default)]
+    annotation: String,
+}
+
+impl PartialEq for Regulation {
+    /// Annotations are ignored, see [Regulation].
+    fn eq(&self, other: &Self) -> bool {
+        self.regulator == other.regulator
+            && self.target == other.target
+            && self.essential == other.essential
+            && self.regulation_sign == other.regulation_sign
+    }
+}
+
+impl Eq for Regulation {}
+
+impl Hash for Regulation {
+    /// Annotations are ignored, see [Regulation].
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.regulator.hash(state);
+        self.target.hash(state);
+        self.essential.hash(state);
+        self.regulation_sign.hash(state);
+    }
 }
 
 /// Methods for safely generating new `Regulations`.
@@ -68,9 +97,16 @@ impl Regulation {
             target,
             essential,
             regulation_sign,
+            annotation: String::new(),
         }
     }
 
+    /// Update the `annotation` property.
+    pub fn with_annotation(mut self, annotation: &str) -> Self {
+        self.annotation = annotation.to_string();
+        self
+    }
+
     /// Try to read the regulation from a given string in the standard format.
     /// Returns error if the string is invalid.
     pub fn try_from_string(regulation_str: &str) -> Result<Regulation, String> {
@@ -82,6 +118,7 @@ impl Regulation {
             target: VarId::new(target.as_str())?,
             regulation_sign,
             essential,
+            annotation: String::new(),
         })
     }
 
@@ -148,6 +185,11 @@ impl Regulation {
     pub fn get_target(&self) -> &VarId {
         &self.target
     }
+
+    /// Get the annotation of the regulation.
+    pub fn get_annotation(&self) -> &str {
+        &self.annotation
+    }
 }
 
 /// Methods for editing `Regulations`.
@@ -171,6 +213,11 @@ impl Regulation {
     pub fn swap_essentiality(&mut self, new_essentiality: Essentiality) {
         self.essential = new_essentiality;
     }
+
+    /// Change annotation of this regulation.
+    pub fn set_annotation(&mut self, annotation: &str) {
+        self.annotation = annotation.to_string();
+    }
 }
 
 impl Display for Regulation {
@@ -194,6 +241,7 @@ impl Display for Regulation {
 #[cfg(test)]
 mod tests {
     use crate::sketchbook::model::{Essentiality, Monotonicity, Regulation};
+    use std::collections::HashSet;
 
     #[test]
     fn regulation_conversion() {
@@ -238,4 +286,21 @@ mod tests {
         assert!(Regulation::try_from_string("-a -> b").is_err());
         assert!(Regulation::try_from_string("a - b").is_err());
     }
+
+    #[test]
+    fn regulation_annotation_identity() {
+        let regulation = Regulation::try_from_string("a -> b").unwrap();
+        let annotated = regulation.clone().with_annotation("Doe et al. (2020)");
+        assert_eq!(annotated.get_annotation(), "Doe et al. (2020)");
+        assert_eq!(annotated.to_string(), regulation.to_string());
+
+        // annotation does not affect the identity of the regulation
+        assert_eq!(annotated, regulation);
+        let set = HashSet::from([regulation.clone()]);
+        assert!(set.contains(&annotated));
+
+        let mut other = annotated.clone();
+        other.swap_sign(Monotonicity::Inhibition);
+        assert_ne!(other, regulation);
+    }
 }
@@ -2,11 +2,20 @@ use crate::sketchbook::ids::{UninterpretedFnId, VarId};
 use crate::sketchbook::model::{
     Essentiality, FnArgumentProperty, FnTree, ModelState, Monotonicity,
 };
-use crate::sketchbook::utils::assert_name_valid;
+use crate::sketchbook::utils::{assert_name_valid, assert_valid_permutation, inverse_permutation};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 
+/// Default maximal arity of functions for which the truth table is enumerated (see
+/// [UninterpretedFn::truth_table]), as the number of rows grows exponentially.
+pub const MAX_TRUTH_TABLE_ARITY: usize = 10;
+
+/// Truth table of a Boolean function, given as a list of rows of argument values (in
+/// lexicographic order, the first argument being the most significant), each with its output
+/// value. Output is `None` if it is not determined.
+pub type TruthTable = Vec<(Vec<bool>, Option<bool>)>;
+
 /// An uninterpreted function of a partially specified model. We sometimes call
 /// them supplementary functions, or function symbols. These functions can be used
 /// in update expressions to denote uncertainty or lack of information to determine
@@ -185,6 +194,22 @@ impl UninterpretedFn {
         Ok(())
     }
 
+    /// **(internal)** Set the function's expression to an already parsed one, given as its syntactic
+    /// tree and canonical string (or `None` for an empty expression). The caller must ensure that
+    /// the tree and the string match.
+    pub(crate) fn set_parsed_expression(&mut self, parsed: Option<(FnTree, String)>) {
+        match parsed {
+            Some((tree, expression)) => {
+                self.tree = Some(tree);
+                self.expression = expression;
+            }
+            None => {
+                self.tree = None;
+                self.expression = String::new();
+            }
+        }
+    }
+
     /// Set properties of this function with respect to the argument on a
     /// given `index` (starting from 0).
     pub fn set_argument_property(
@@ -252,6 +277,53 @@ impl UninterpretedFn {
             self.tree = Some(new_tree);
         }
     }
+
+    /// Reorder arguments of all applications of the given function symbol in the syntactic
+    /// tree, according to the `permutation` (see [FnTree::permute_fn_args]).
+    pub fn permute_fn_args(
+        &mut self,
+        fn_id: &UninterpretedFnId,
+        permutation: &[usize],
+        context: &ModelState,
+    ) {
+        if let Some(tree) = &self.tree {
+            let new_tree = tree.permute_fn_args(fn_id, permutation);
+            self.expression = new_tree.to_string(context, Some(self.get_arity()));
+            self.tree = Some(new_tree);
+        }
+    }
+
+    /// Reorder the arguments of this function according to the `permutation`, i.e., the `i`-th
+    /// new argument is the `permutation[i]`-th original argument. The argument properties are
+    /// reordered, and the placeholder variables in the function's expression are renamed
+    /// accordingly, so that the expression keeps its meaning.
+    ///
+    /// The permutation must be a valid permutation of `0..arity`.
+    pub fn reorder_arguments(
+        &mut self,
+        permutation: &[usize],
+        context: &ModelState,
+    ) -> Result<(), String> {
+        assert_valid_permutation(permutation, self.get_arity())?;
+        self.arguments = permutation
+            .iter()
+            .map(|&idx| self.arguments[idx].clone())
+            .collect();
+
+        if let Some(tree) = &self.tree {
+            // original argument `var{j}` is now on the position given by the inverse permutation
+            let placeholder = |i: usize| VarId::new(&format!("var{i}")).unwrap();
+            let renaming: HashMap<VarId, FnTree> = inverse_permutation(permutation)
+                .into_iter()
+                .enumerate()
+                .map(|(j, i)| (placeholder(j), FnTree::PlaceholderVar(placeholder(i))))
+                .collect();
+            let new_tree = tree.substitute_all_placeholders(&renaming)?;
+            self.expression = new_tree.to_string(context, Some(self.get_arity()));
+            self.tree = Some(new_tree);
+        }
+        Ok(())
+    }
 }
 
 /// Observing `UninterpretedFn` instances.
@@ -348,6 +420,45 @@ impl UninterpretedFn {
             HashSet::new()
         }
     }
+
+    /// Enumerate the truth table of this function, i.e., all `2^arity` rows of argument values
+    /// (in lexicographic order, the first argument being the most significant), each with the
+    /// output value given by the function's expression.
+    ///
+    /// Rows left unconstrained by the expression (e.g., there is no expression at all, or the
+    /// result depends on other uninterpreted functions) have output `None`.
+    ///
+    /// Returns `Err` if the arity exceeds [MAX_TRUTH_TABLE_ARITY], see
+    /// [Self::truth_table_bounded] to use a different limit.
+    pub fn truth_table(&self) -> Result<TruthTable, String> {
+        self.truth_table_bounded(MAX_TRUTH_TABLE_ARITY)
+    }
+
+    /// Enumerate the truth table of this function, see [Self::truth_table]. Returns `Err` if the
+    /// arity exceeds `max_arity`.
+    pub fn truth_table_bounded(&self, max_arity: usize) -> Result<TruthTable, String> {
+        let arity = self.get_arity();
+        if arity > max_arity {
+            return Err(format!(
+                "Cannot enumerate truth table of `{}` with {arity} arguments (at most {max_arity} allowed).",
+                self.name
+            ));
+        }
+
+        let table = (0..1usize << arity)
+            .map(|row_idx| {
+                let arg_values: Vec<bool> = (0..arity)
+                    .map(|i| (row_idx >> (arity - 1 - i)) & 1 == 1)
+                    .collect();
+                let output = self
+                    .tree
+                    .as_ref()
+                    .and_then(|tree| tree.eval_placeholders(&arg_values));
+                (arg_values, output)
+            })
+            .collect();
+        Ok(table)
+    }
 }
 
 impl Display for UninterpretedFn {
@@ -398,4 +509,46 @@ mod tests {
             .unwrap();
         assert_eq!(f.get_fn_expression(), expression);
     }
+
+    #[test]
+    fn uninterpreted_fn_truth_table_test() {
+        let mut context_model = ModelState::new_empty();
+        context_model
+            .add_empty_uninterpreted_fn_by_str("f", "f", 2)
+            .unwrap();
+        context_model
+            .add_empty_uninterpreted_fn_by_str("g", "g", 1)
+            .unwrap();
+        let fn_id = UninterpretedFnId::new("f").unwrap();
+
+        // no expression, all rows are unconstrained
+        let f = UninterpretedFn::new_default("f", 2).unwrap();
+        let table = f.truth_table().unwrap();
+        assert_eq!(table.len(), 4);
+        assert!(table.iter().all(|(_, output)| output.is_none()));
+
+        // fully specified expression
+        let f = f.with_expression("var0 => var1", &context_model, &fn_id);
+        let expected = vec![
+            (vec![false, false], Some(true)),
+            (vec![false, true], Some(true)),
+            (vec![true, false], Some(false)),
+            (vec![true, true], Some(true)),
+        ];
+        assert_eq!(f.unwrap().truth_table().unwrap(), expected);
+
+        // partially specified expression, only rows with `var0` false are determined
+        let f = UninterpretedFn::new_default("f", 2)
+            .unwrap()
+            .with_expression("var0 & g(var1)", &context_model, &fn_id)
+            .unwrap();
+        let outputs: Vec<Option<bool>> =
+            f.truth_table().unwrap().into_iter().map(|r| r.1).collect();
+        assert_eq!(outputs, vec![Some(false), Some(false), None, None]);
+
+        // too large arity is refused
+        let f = UninterpretedFn::new_default("h", 12).unwrap();
+        assert!(f.truth_table().is_err());
+        assert_eq!(f.truth_table_bounded(12).unwrap().len(), 4096);
+    }
 }
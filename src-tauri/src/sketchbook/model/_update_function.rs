@@ -1,5 +1,6 @@
 use crate::sketchbook::ids::{UninterpretedFnId, VarId};
-use crate::sketchbook::model::{FnTree, ModelState};
+use crate::sketchbook::model::{BinaryOp, FnTree, ModelState};
+use biodivine_lib_bdd::{Bdd, BddVariableSet};
 use biodivine_lib_param_bn::{BooleanNetwork, FnUpdate};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -16,10 +17,22 @@ use std::fmt::{Display, Formatter};
 ///   operations and analysis
 ///
 /// If the expression is not specified, we use empty string, and `tree` is None.
+///
+/// The function can also be marked as `frozen`, meaning the modeler is confident about it and
+/// the inference should treat it as a fixed concrete function. Only fully specified functions
+/// (without uninterpreted function symbols) can be frozen, which is checked during the
+/// consistency check of the sketch.
+///
+/// Note that the flag only serves as a validation. Fully specified functions are always
+/// encoded as concrete functions (without parameters), so freezing does not change the
+/// encoding. It guarantees that the function stays fully specified - if it does not (e.g.,
+/// after removing an expression of some function symbol), the sketch becomes inconsistent.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct UpdateFn {
     expression: String,
     tree: Option<FnTree>,
+    #[serde(default)]
+    frozen: bool,
 }
 
 impl Display for UpdateFn {
@@ -34,6 +47,7 @@ impl Default for UpdateFn {
         UpdateFn {
             expression: String::new(),
             tree: None,
+            frozen: false,
         }
     }
 }
@@ -50,15 +64,41 @@ impl UpdateFn {
             Ok(UpdateFn {
                 expression: syntactic_tree.to_string(context, None),
                 tree: Some(syntactic_tree),
+                frozen: false,
             })
         }
     }
 
+    /// **(internal)** Create new `UpdateFn` from an already parsed expression, given as its syntactic
+    /// tree and canonical string (or `None` for an empty expression). The caller must ensure that
+    /// the tree and the string match.
+    pub(crate) fn from_parsed(parsed: Option<(FnTree, String)>) -> UpdateFn {
+        match parsed {
+            Some((tree, expression)) => UpdateFn {
+                expression,
+                tree: Some(tree),
+                frozen: false,
+            },
+            None => UpdateFn::default(),
+        }
+    }
+
     /// Make an "empty" update function (same as [Self::default]).
     pub fn new_empty() -> UpdateFn {
         Self::default()
     }
 
+    /// Create new `UpdateFn` that is a constant `true` or `false` (i.e., a tautology or a
+    /// contradiction). Useful for clamping variables, e.g., in knockout or over-expression
+    /// experiments.
+    pub fn new_constant(value: bool) -> UpdateFn {
+        UpdateFn {
+            expression: value.to_string(),
+            tree: Some(FnTree::Const(value)),
+            frozen: false,
+        }
+    }
+
     /// Get function's expression.
     pub fn get_fn_expression(&self) -> &str {
         &self.expression
@@ -75,7 +115,29 @@ impl UpdateFn {
         self.tree.is_none()
     }
 
+    /// Get the value of the update function if it is a constant (`true` or `false`), or `None`
+    /// otherwise. Note that only literal constants are recognized, expressions like `a | !a`
+    /// are not simplified.
+    pub fn as_constant(&self) -> Option<bool> {
+        match self.tree {
+            Some(FnTree::Const(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Check if the update function is frozen, i.e., it must be a fixed concrete function
+    /// during the inference (see [UpdateFn] for details).
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Mark the update function as frozen (or unfreeze it).
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+    }
+
     /// Set the update function's expression to a given string.
+    /// The `frozen` flag of the function is not affected.
     pub fn set_fn_expression(
         &mut self,
         new_expression: &str,
@@ -92,6 +154,47 @@ impl UpdateFn {
         Ok(())
     }
 
+    /// Get a simplified (but semantically equivalent) version of the function's expression.
+    /// See [FnTree::simplify] for the simplification rules. The model `context` is needed to
+    /// convert the simplified tree back into a string.
+    ///
+    /// Returns an empty string if the expression is empty.
+    pub fn simplify(&self, context: &ModelState) -> String {
+        if let Some(tree) = &self.tree {
+            tree.simplify().to_string(context, None)
+        } else {
+            String::new()
+        }
+    }
+
+    /// Check if this update function is semantically equivalent to the `other` one, i.e., if
+    /// both expressions have the same truth table over the union of their variables (for
+    /// example, `a & b` and `b & a`, or `!(a | b)` and `!a & !b`).
+    ///
+    /// Applications of uninterpreted functions are treated as opaque inputs, two applications
+    /// are only considered equal if they are structurally identical (same symbol, same argument
+    /// expressions). Two empty expressions are equivalent, but an empty expression is never
+    /// equivalent to a non-empty one. The `frozen` flag is ignored.
+    pub fn is_equivalent_to(&self, other: &UpdateFn) -> bool {
+        let (tree, other_tree) = match (&self.tree, &other.tree) {
+            (None, None) => return true,
+            (Some(tree), Some(other_tree)) => (tree, other_tree),
+            _ => return false,
+        };
+        if tree == other_tree {
+            return true;
+        }
+
+        // each distinct input (variable or fn application) gets its own BDD variable
+        let mut inputs = Vec::new();
+        collect_opaque_inputs(tree, &mut inputs);
+        collect_opaque_inputs(other_tree, &mut inputs);
+        let bdd_vars = BddVariableSet::new_anonymous(inputs.len() as u16);
+        let bdd = opaque_tree_to_bdd(tree, &inputs, &bdd_vars);
+        let other_bdd = opaque_tree_to_bdd(other_tree, &inputs, &bdd_vars);
+        bdd == other_bdd
+    }
+
     /// Return a set of all variables that are actually used as inputs in this function.
     pub fn to_fn_update(&self, context: &BooleanNetwork) -> Option<FnUpdate> {
         self.tree.as_ref().map(|tree| tree.to_fn_update(context))
@@ -138,6 +241,21 @@ impl UpdateFn {
         }
     }
 
+    /// Reorder arguments of all applications of the given function symbol in the syntactic
+    /// tree, according to the `permutation` (see [FnTree::permute_fn_args]).
+    pub fn permute_fn_args(
+        &mut self,
+        fn_id: &UninterpretedFnId,
+        permutation: &[usize],
+        context: &ModelState,
+    ) {
+        if let Some(tree) = &self.tree {
+            let new_tree = tree.permute_fn_args(fn_id, permutation);
+            self.expression = new_tree.to_string(context, None);
+            self.tree = Some(new_tree);
+        }
+    }
+
     /// Create update function from another one, substituting all occurrences of a given
     /// function symbol in the syntactic tree. The provided original function object is consumed.
     pub fn with_changed_fn_id(
@@ -162,3 +280,79 @@ impl UpdateFn {
         original_fn
     }
 }
+
+/// **(internal)** Collect all distinct "opaque inputs" of the expression, i.e., its variables
+/// and applications of uninterpreted functions (the arguments of which are not inspected).
+fn collect_opaque_inputs(tree: &FnTree, inputs: &mut Vec<FnTree>) {
+    match tree {
+        FnTree::Const(_) => {}
+        FnTree::Var(_) | FnTree::PlaceholderVar(_) | FnTree::UninterpretedFn(..) => {
+            if !inputs.contains(tree) {
+                inputs.push(tree.clone());
+            }
+        }
+        FnTree::Not(inner) => collect_opaque_inputs(inner, inputs),
+        FnTree::Binary(_, l, r) => {
+            collect_opaque_inputs(l, inputs);
+            collect_opaque_inputs(r, inputs);
+        }
+    }
+}
+
+/// **(internal)** Build a BDD of the expression, where each opaque input (see
+/// [collect_opaque_inputs]) is represented by the BDD variable with the same index.
+///
+/// All opaque inputs of the tree must be present in `inputs`.
+fn opaque_tree_to_bdd(tree: &FnTree, inputs: &[FnTree], bdd_vars: &BddVariableSet) -> Bdd {
+    match tree {
+        FnTree::Const(true) => bdd_vars.mk_true(),
+        FnTree::Const(false) => bdd_vars.mk_false(),
+        FnTree::Var(_) | FnTree::PlaceholderVar(_) | FnTree::UninterpretedFn(..) => {
+            let index = inputs.iter().position(|input| input == tree).unwrap();
+            bdd_vars.mk_var(bdd_vars.variables()[index])
+        }
+        FnTree::Not(inner) => opaque_tree_to_bdd(inner, inputs, bdd_vars).not(),
+        FnTree::Binary(op, l, r) => {
+            let l = opaque_tree_to_bdd(l, inputs, bdd_vars);
+            let r = opaque_tree_to_bdd(r, inputs, bdd_vars);
+            match op {
+                BinaryOp::And => l.and(&r),
+                BinaryOp::Or => l.or(&r),
+                BinaryOp::Xor => l.xor(&r),
+                BinaryOp::Imp => l.imp(&r),
+                BinaryOp::Iff => l.iff(&r),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::model::{ModelState, UpdateFn};
+
+    #[test]
+    /// Test semantic equivalence of update functions (including function symbols).
+    fn test_update_fn_equivalence() {
+        let mut model = ModelState::new_with_vars(vec![("a", "a"), ("b", "b")]).unwrap();
+        model
+            .add_multiple_uninterpreted_fns(vec![("f", "f", 1), ("g", "g", 1)])
+            .unwrap();
+        let update_fn = |expression: &str| UpdateFn::try_from_str(expression, &model).unwrap();
+        let equivalent = |e1: &str, e2: &str| update_fn(e1).is_equivalent_to(&update_fn(e2));
+
+        assert!(equivalent("a & b", "b & a"));
+        assert!(equivalent("!(a | b)", "!a & !b"));
+        assert!(equivalent("a => b", "!a | b"));
+        assert!(equivalent("a | !a", "true"));
+        assert!(equivalent("", "  "));
+        assert!(!equivalent("a & b", "a | b"));
+        assert!(!equivalent("a", "a & b"));
+        assert!(!equivalent("", "true"));
+
+        // fn applications are opaque, and only equal if structurally identical
+        assert!(equivalent("f(a) & b", "b & f(a)"));
+        assert!(equivalent("f(a) | !f(a)", "true"));
+        assert!(!equivalent("f(a)", "g(a)"));
+        assert!(!equivalent("f(a & b)", "f(b & a)"));
+    }
+}
@@ -4,11 +4,16 @@ use std::fmt::{Display, Error, Formatter};
 
 /// A type safe object for a Boolean variable of a model.
 ///
-/// Currently, it only stores the variable's `name` and `annotation`.
+/// Currently, it stores the variable's `name`, `annotation`, and whether it is an input.
+///
+/// Input variables are free Boolean constants - their value is arbitrary, but it never changes
+/// (their update function is identity). Inputs do not introduce any parameters.
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct Variable {
     name: String,
     annotation: String,
+    #[serde(default)]
+    is_input: bool,
 }
 
 impl Variable {
@@ -18,6 +23,7 @@ impl Variable {
         Ok(Variable {
             name: name_str.to_string(),
             annotation: String::new(),
+            is_input: false,
         })
     }
 
@@ -27,6 +33,12 @@ impl Variable {
         self
     }
 
+    /// Update the `is_input` property.
+    pub fn with_input(mut self, is_input: bool) -> Self {
+        self.is_input = is_input;
+        self
+    }
+
     /// Human-readable name of this variable.
     pub fn get_name(&self) -> &str {
         &self.name
@@ -37,6 +49,11 @@ impl Variable {
         &self.annotation
     }
 
+    /// Check whether the variable is an input (a free Boolean constant).
+    pub fn is_input(&self) -> bool {
+        self.is_input
+    }
+
     /// Rename this variable.
     pub fn set_name(&mut self, new_name: &str) -> Result<(), String> {
         assert_name_valid(new_name)?;
@@ -48,6 +65,11 @@ impl Variable {
     pub fn set_annotation(&mut self, annotation: &str) {
         self.annotation = annotation.to_string();
     }
+
+    /// Mark this variable as an input (or unmark it).
+    pub fn set_input(&mut self, is_input: bool) {
+        self.is_input = is_input;
+    }
 }
 
 impl Display for Variable {
@@ -24,11 +24,11 @@ mod _variable;
 
 pub use _binary_op::BinaryOp;
 pub use _essentiality::Essentiality;
-pub use _function_tree::FnTree;
+pub use _function_tree::{FnTree, FnTreeFolder};
 pub use _model_state::ModelState;
 pub use _monotonicity::Monotonicity;
 pub use _regulation::Regulation;
-pub use _uninterpreted_fn::UninterpretedFn;
+pub use _uninterpreted_fn::{TruthTable, UninterpretedFn, MAX_TRUTH_TABLE_ARITY};
 pub use _uninterpreted_fn_arg::FnArgumentProperty;
 pub use _update_function::UpdateFn;
 pub use _variable::Variable;
@@ -0,0 +1,188 @@
+use crate::sketchbook::observations::{Dataset, Observation, VarValue};
+use std::str::FromStr;
+
+/// Name of the (optional) first header column that holds observation IDs.
+const ID_COLUMN_NAME: &str = "ID";
+/// Name of the (optional) header column that holds observation weights. It follows the
+/// variable columns (and precedes the metadata column, if present).
+pub(in crate::sketchbook::observations) const WEIGHT_COLUMN_NAME: &str = "weight";
+/// Name of the (optional) last header column that holds observation metadata.
+pub(in crate::sketchbook::observations) const METADATA_COLUMN_NAME: &str = "metadata";
+
+/// Creating `Dataset` instances from CSV.
+impl Dataset {
+    /// Parse a dataset from a CSV string. The header line specifies variable names, following
+    /// lines represent individual observations with values `0`, `1`, or `*` (unspecified).
+    ///
+    /// If the first header column is named `ID` (case-insensitive), the first column of each row
+    /// is used as the observation's ID. If there is no such column (or the ID cell is empty),
+    /// the ID is generated automatically (`obs_1`, `obs_2`, ...). Similarly, if the last header
+    /// column is named `metadata` (case-insensitive), its cells are used as the observations'
+    /// metadata (empty cells mean no metadata). The last variable column may be followed by
+    /// a column named `weight` (case-insensitive), with the observations' weights (empty cells
+    /// mean no weight, see [Observation::get_weight]).
+    ///
+    /// Blank rows are ignored. Rows with a different number of columns than the header result
+    /// in an error naming the corresponding line.
+    ///
+    /// For example, both of the following are valid CSV strings for a dataset with 2 observations:
+    ///    ID,YOX1,CLN3,YHP1
+    ///    Observation1,0,1,0
+    ///    Observation2,1,0,*
+    ///
+    ///    YOX1,CLN3,YHP1
+    ///    0,1,0
+    ///    1,0,*
+    ///
+    pub fn from_csv(name: &str, csv: &str) -> Result<Dataset, String> {
+        let mut rdr = csv::ReaderBuilder::new()
+            .flexible(true)
+            .from_reader(csv.as_bytes());
+
+        // parse variable names from the header (and strip whitespaces)
+        let header = rdr.headers().map_err(|e| e.to_string())?.clone();
+        let header_cols: Vec<&str> = header.iter().map(|s| s.trim()).collect();
+        let has_id_column = header_cols
+            .first()
+            .is_some_and(|col| col.eq_ignore_ascii_case(ID_COLUMN_NAME));
+        let has_metadata_column = header_cols.len() > usize::from(has_id_column)
+            && header_cols
+                .last()
+                .is_some_and(|col| col.eq_ignore_ascii_case(METADATA_COLUMN_NAME));
+        let first_var_col = usize::from(has_id_column);
+        let metadata_col = header_cols.len() - usize::from(has_metadata_column);
+        let has_weight_column = metadata_col > first_var_col
+            && header_cols[metadata_col - 1].eq_ignore_ascii_case(WEIGHT_COLUMN_NAME);
+        let last_var_col = metadata_col - usize::from(has_weight_column);
+        let variables = header_cols[first_var_col..last_var_col].to_vec();
+        let mut dataset = Dataset::new_empty(name, variables)?;
+
+        // parse all non-blank rows as observations
+        for result in rdr.records() {
+            let record = result.map_err(|e| e.to_string())?;
+            if record.iter().all(|s| s.trim().is_empty()) {
+                continue;
+            }
+            let line = record.position().map_or(0, |pos| pos.line());
+            if record.len() != header_cols.len() {
+                return Err(format!(
+                    "Line {line} has {} columns, but the header has {}.",
+                    record.len(),
+                    header_cols.len()
+                ));
+            }
+
+            let cells: Vec<&str> = record.iter().map(|s| s.trim()).collect();
+            let provided_id = if has_id_column {
+                Some(cells[0]).filter(|id| !id.is_empty())
+            } else {
+                None
+            };
+            let metadata = if has_metadata_column {
+                Some(cells[metadata_col]).filter(|m| !m.is_empty())
+            } else {
+                None
+            };
+            let weight = if has_weight_column {
+                Some(cells[last_var_col]).filter(|w| !w.is_empty())
+            } else {
+                None
+            };
+            let values = cells[first_var_col..last_var_col]
+                .iter()
+                .map(|cell| VarValue::from_str(cell))
+                .collect::<Result<Vec<VarValue>, String>>()
+                .map_err(|e| format!("Error at line {line}: {e}"))?;
+
+            // generate IDs one at a time, right before adding the observation
+            let mut observation = match provided_id {
+                Some(id) => Observation::new(values, id)?,
+                None => {
+                    let id = dataset.generate_obs_id("obs", Some(1));
+                    Observation::new(values, id.as_str())?
+                }
+            };
+            observation.set_metadata(metadata);
+            if let Some(weight) = weight {
+                let weight = f64::from_str(weight)
+                    .map_err(|_| format!("Error at line {line}: Invalid weight `{weight}`."))?;
+                observation
+                    .set_weight(weight)
+                    .map_err(|e| format!("Error at line {line}: {e}"))?;
+            }
+            dataset
+                .push_obs(observation)
+                .map_err(|e| format!("Error at line {line}: {e}"))?;
+        }
+        Ok(dataset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::observations::{Dataset, Observation};
+
+    #[test]
+    /// Test parsing dataset with an ID column, including whitespaces and blank trailing rows.
+    fn test_from_csv_with_ids() {
+        let obs1 = Observation::try_from_str("*11", "o1").unwrap();
+        let obs2 = Observation::try_from_str("000", "o2").unwrap();
+        let expected = Dataset::new("d", vec![obs1, obs2], vec!["a", "b", "c"]).unwrap();
+
+        let csv = "id, a , b , c\no1, *, 1, 1\n o2 ,0,0,0\n\n  \n";
+        assert_eq!(Dataset::from_csv("d", csv).unwrap(), expected);
+    }
+
+    #[test]
+    /// Test parsing dataset without an ID column, generating observation IDs.
+    fn test_from_csv_generated_ids() {
+        let obs1 = Observation::try_from_str("*11", "obs_1").unwrap();
+        let obs2 = Observation::try_from_str("000", "obs_2").unwrap();
+        let expected = Dataset::new("d", vec![obs1, obs2], vec!["a", "b", "c"]).unwrap();
+
+        let csv = "a,b,c\n*,1,1\n0,0,0\n";
+        assert_eq!(Dataset::from_csv("d", csv).unwrap(), expected);
+    }
+
+    #[test]
+    /// Test parsing dataset with a metadata column (with quoted cells and empty metadata).
+    fn test_from_csv_with_metadata() {
+        let obs1 = Observation::try_from_str("*1", "o1")
+            .unwrap()
+            .with_metadata("exp. 1, 2024-03-01");
+        let obs2 = Observation::try_from_str("00", "o2").unwrap();
+        let expected = Dataset::new("d", vec![obs1, obs2], vec!["a", "b"]).unwrap();
+
+        let csv = "ID,a,b,Metadata\no1,*,1,\"exp. 1, 2024-03-01\"\no2,0,0,\n";
+        assert_eq!(Dataset::from_csv("d", csv).unwrap(), expected);
+    }
+
+    #[test]
+    /// Test parsing dataset with a weight column (followed by a metadata column).
+    fn test_from_csv_with_weights() {
+        let obs1 = Observation::try_from_str("*1", "o1")
+            .unwrap()
+            .with_weight(0.5)
+            .unwrap()
+            .with_metadata("exp. 1");
+        let obs2 = Observation::try_from_str("00", "o2").unwrap();
+        let expected = Dataset::new("d", vec![obs1, obs2], vec!["a", "b"]).unwrap();
+
+        let csv = "ID,a,b,Weight,metadata\no1,*,1,0.5,exp. 1\no2,0,0,,\n";
+        assert_eq!(Dataset::from_csv("d", csv).unwrap(), expected);
+
+        let csv = "a,b,weight\n*,1,-2\n";
+        assert!(Dataset::from_csv("d", csv).is_err());
+    }
+
+    #[test]
+    /// Test that invalid CSV rows produce errors naming the line.
+    fn test_from_csv_invalid() {
+        let csv = "a,b,c\n*,1,1\n0,0\n";
+        let err = Dataset::from_csv("d", csv).unwrap_err();
+        assert!(err.contains("Line 3"));
+
+        let csv = "a,b\n*,2\n";
+        assert!(Dataset::from_csv("d", csv).is_err());
+    }
+}
@@ -1,7 +1,8 @@
 use crate::sketchbook::ids::{ObservationId, VarId};
-use crate::sketchbook::observations::{Dataset, Observation, VarValue};
+use crate::sketchbook::model::Monotonicity;
+use crate::sketchbook::observations::{Dataset, DatasetOrdering, Observation, VarValue};
 use crate::sketchbook::utils::{assert_ids_unique, assert_name_valid};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Creating new `Dataset` instances.
 impl Dataset {
@@ -10,7 +11,8 @@ impl Dataset {
     /// Variables must be unique valid identifiers. Number of values in each observation
     /// and number of variables must match. Observation IDs must be valid unique identifiers.
     ///
-    ///  Annotation is left empty, use `with_annotation` modifier if needed.
+    ///  Annotation is left empty, use `with_annotation` modifier if needed. The dataset is
+    ///  considered a time series, use `with_ordering` modifier to change that.
     pub fn new(
         name: &str,
         observations: Vec<Observation>,
@@ -46,6 +48,7 @@ impl Dataset {
         Ok(Self {
             name: name.to_string(),
             annotation: String::new(),
+            ordering: DatasetOrdering::default(),
             observations,
             variables,
             index_map: observation_index_map,
@@ -63,6 +66,46 @@ impl Dataset {
         self
     }
 
+    /// Update the `ordering` property.
+    pub fn with_ordering(mut self, ordering: DatasetOrdering) -> Self {
+        self.ordering = ordering;
+        self
+    }
+
+    /// Create a projection of this dataset onto the given variables. The new dataset contains
+    /// only the columns for `vars` (in the given order), with the same observations (order, IDs,
+    /// names, annotations, weights, and metadata are preserved). The dataset's name, annotation, and ordering are
+    /// kept too.
+    ///
+    /// Returns `Err` if some of the variables is not in this dataset, or if `vars` contains
+    /// duplicates.
+    pub fn project(&self, vars: &[VarId]) -> Result<Dataset, String> {
+        let variables = vars.to_vec();
+        assert_ids_unique(&variables)?;
+        let indices = vars
+            .iter()
+            .map(|var_id| self.get_idx_of_var(var_id))
+            .collect::<Result<Vec<usize>, String>>()?;
+
+        let mut observations = Vec::with_capacity(self.num_observations());
+        for obs in &self.observations {
+            let values = indices
+                .iter()
+                .map(|i| obs.get_values()[*i].clone())
+                .collect();
+            observations.push(obs.with_values(values));
+        }
+
+        Ok(Dataset {
+            name: self.name.clone(),
+            annotation: self.annotation.clone(),
+            ordering: self.ordering,
+            observations,
+            variables,
+            index_map: self.index_map.clone(),
+        })
+    }
+
     /// **(internal)** Try parsing vector of variables string slices into vector of
     /// typesafe `VarId`.
     fn try_convert_var_ids(var_names: &[&str]) -> Result<Vec<VarId>, String> {
@@ -87,6 +130,11 @@ impl Dataset {
         self.annotation = annotation.to_string();
     }
 
+    /// Set whether dataset's observations form a time series, or an unordered collection.
+    pub fn set_ordering(&mut self, ordering: DatasetOrdering) {
+        self.ordering = ordering;
+    }
+
     /// Add observation at the end of the dataset.
     ///
     /// The observation must have the same length as is the number of dataset's variables, and its
@@ -124,6 +172,89 @@ impl Dataset {
         Ok(())
     }
 
+    /// Add a batch of observations to the end of the dataset (in the given order).
+    ///
+    /// All observations are validated first (IDs must be unique and not yet used in the dataset,
+    /// and number of values must match the number of variables), so that either all of them are
+    /// added, or none is (and an error is returned).
+    pub fn push_obs_batch(&mut self, observations: Vec<Observation>) -> Result<(), String> {
+        let mut new_ids = HashSet::new();
+        for obs in observations.iter() {
+            let obs_id = obs.get_id();
+            self.assert_no_obs(obs_id)?;
+            if !new_ids.insert(obs_id) {
+                return Err(format!(
+                    "Observation ID {obs_id} is used twice in the batch."
+                ));
+            }
+            if obs.num_values() != self.num_variables() {
+                return Err(format!(
+                    "Number of values in observation {obs_id} differs from the variable count."
+                ));
+            }
+        }
+
+        for obs in observations {
+            self.push_obs(obs)?;
+        }
+        Ok(())
+    }
+
+    /// Remove a batch of observations with given IDs from the dataset. The order of remaining
+    /// observations is preserved.
+    ///
+    /// All IDs are validated first (they must be valid and unique), so that either all the
+    /// observations are removed, or none is (and an error is returned).
+    pub fn remove_obs_batch(&mut self, ids: &[ObservationId]) -> Result<(), String> {
+        let mut ids_to_remove = HashSet::new();
+        for id in ids {
+            self.assert_valid_obs(id)?;
+            if !ids_to_remove.insert(id) {
+                return Err(format!("Observation ID {id} is used twice in the batch."));
+            }
+        }
+
+        self.observations
+            .retain(|obs| !ids_to_remove.contains(obs.get_id()));
+        // re-index all the remaining observations
+        self.index_map = self
+            .observations
+            .iter()
+            .enumerate()
+            .map(|(i, obs)| (obs.get_id().clone(), i))
+            .collect();
+        Ok(())
+    }
+
+    /// Remove all observations with values identical to some earlier observation in the dataset,
+    /// keeping only the first occurrence. Unspecified values are compared as they are, i.e.,
+    /// `1*0` and `110` are not considered duplicates.
+    ///
+    /// Return the IDs of the removed observations (in their original order), so that the caller
+    /// can handle properties that referenced them.
+    pub fn deduplicate(&mut self) -> Vec<ObservationId> {
+        let mut seen_values: HashSet<Vec<VarValue>> = HashSet::new();
+        let mut removed_ids = Vec::new();
+        let mut kept_observations = Vec::with_capacity(self.observations.len());
+        for obs in self.observations.drain(..) {
+            if seen_values.insert(obs.get_values().clone()) {
+                kept_observations.push(obs);
+            } else {
+                removed_ids.push(obs.get_id().clone());
+            }
+        }
+        self.observations = kept_observations;
+
+        // re-index all the observations that were kept
+        self.index_map = self
+            .observations
+            .iter()
+            .enumerate()
+            .map(|(i, obs)| (obs.get_id().clone(), i))
+            .collect();
+        removed_ids
+    }
+
     /// Add observation to a given index in the dataset.
     ///
     /// This operation might be very costly, as we must reindex all subsequent observations.
@@ -306,6 +437,11 @@ impl Dataset {
         &self.annotation
     }
 
+    /// Ordering of the dataset (time series or unordered collection).
+    pub fn get_ordering(&self) -> DatasetOrdering {
+        self.ordering
+    }
+
     /// Number of observations in the dataset.
     pub fn num_observations(&self) -> usize {
         self.observations.len()
@@ -433,6 +569,104 @@ impl Dataset {
         format!("{len} observations with vars [{var_string}]: [{obs_string}]")
     }
 
+    /// Check that this dataset can represent an asynchronous time series, i.e., that every two
+    /// consecutive observations differ in the value of at most one variable.
+    ///
+    /// Unspecified values (`*`) are compatible with both `0` and `1`, and thus never count as
+    /// a difference. If there is a violating transition, `Err` is returned, containing the index
+    /// of the first observation of the transition.
+    pub fn assert_asynchronous_steps(&self) -> Result<(), String> {
+        for (i, pair) in self.observations.windows(2).enumerate() {
+            let (obs1, obs2) = (&pair[0], &pair[1]);
+            let num_differences = obs1
+                .get_values()
+                .iter()
+                .zip(obs2.get_values())
+                .filter(|(v1, v2)| !v1.matches(v2))
+                .count();
+            if num_differences > 1 {
+                return Err(format!(
+                    "Transition {i} (from observation `{}` to `{}`) changes {num_differences} variables, but at most one change is allowed in an asynchronous time series.",
+                    obs1.get_id(),
+                    obs2.get_id(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Heuristically infer the monotonicity of the regulation `regulator -> target` from this
+    /// dataset, treating it as a time series.
+    ///
+    /// We scan all pairs of consecutive observations in which the target changes its value. For
+    /// each such step, the regulator's value in the first observation (the state from which the
+    /// target was updated) is used as evidence:
+    /// - target increasing while regulator is `1`, or decreasing while it is `0`, suggests
+    ///   activation,
+    /// - target increasing while regulator is `0`, or decreasing while it is `1`, suggests
+    ///   inhibition.
+    ///
+    /// Steps with unspecified values (of the regulator or the target) are skipped. This assumes
+    /// that the observations are ordered in time and that the target responds to the regulator
+    /// values directly preceding its change. The result only reflects correlation in the data,
+    /// not causality, and is meant as a suggestion for the user.
+    ///
+    /// Returns `None` if the evidence is mixed, if there is no evidence at all, if either of the
+    /// variables is not in the dataset, or if `regulator` and `target` are the same variable
+    /// (the direction of a change then always trivially "suggests" inhibition).
+    pub fn infer_monotonicity(&self, regulator: &VarId, target: &VarId) -> Option<Monotonicity> {
+        if regulator == target {
+            return None;
+        }
+        let regulator_idx = self.get_idx_of_var(regulator).ok()?;
+        let target_idx = self.get_idx_of_var(target).ok()?;
+
+        let (mut activating_steps, mut inhibiting_steps) = (0, 0);
+        for pair in self.observations.windows(2) {
+            let values_before = pair[0].get_values();
+            let target_before = values_before[target_idx].try_as_bool();
+            let target_after = pair[1].get_values()[target_idx].try_as_bool();
+            let regulator_value = values_before[regulator_idx].try_as_bool();
+            if let (Some(t1), Some(t2), Some(r)) = (target_before, target_after, regulator_value) {
+                if t1 == t2 {
+                    continue;
+                }
+                // target increasing while regulator is active (or the other way around)
+                if t2 == r {
+                    activating_steps += 1;
+                } else {
+                    inhibiting_steps += 1;
+                }
+            }
+        }
+
+        match (activating_steps, inhibiting_steps) {
+            (0, 0) => None,
+            (_, 0) => Some(Monotonicity::Activation),
+            (0, _) => Some(Monotonicity::Inhibition),
+            _ => None,
+        }
+    }
+
+    /// Find the stored observation closest to `obs` with respect to the Hamming distance (see
+    /// [Observation::hamming_distance]), and return its ID together with the distance.
+    ///
+    /// Stored observations that cannot be compared with `obs` (because they contain unspecified
+    /// values or have a different number of values) are skipped. Ties are resolved in favour of
+    /// the earliest observation. Returns `None` if no observation can be compared.
+    pub fn nearest(&self, obs: &Observation) -> Option<(ObservationId, usize)> {
+        let mut nearest: Option<(ObservationId, usize)> = None;
+        for stored_obs in &self.observations {
+            let Ok(distance) = stored_obs.hamming_distance(obs) else {
+                continue;
+            };
+            if nearest.as_ref().is_none_or(|(_, best)| distance < *best) {
+                nearest = Some((stored_obs.get_id().clone(), distance));
+            }
+        }
+        nearest
+    }
+
     /// **(internal)** Utility method to ensure there is no observation with given ID yet.
     fn assert_no_obs(&self, id: &ObservationId) -> Result<(), String> {
         if self.is_valid_obs(id) {
@@ -476,6 +710,8 @@ impl Dataset {
 
 #[cfg(test)]
 mod tests {
+    use crate::sketchbook::ids::{ObservationId, VarId};
+    use crate::sketchbook::model::Monotonicity;
     use crate::sketchbook::observations::{Dataset, Observation};
 
     #[test]
@@ -553,6 +789,156 @@ mod tests {
         assert_eq!(dataset.observations(), &vec![obs2.clone(), obs1.clone()]);
     }
 
+    #[test]
+    /// Test adding/removing batches of observations (both valid and invalid cases).
+    fn test_observation_batches() {
+        let obs1 = Observation::try_from_str("*1", "o").unwrap();
+        let obs2 = Observation::try_from_str("00", "p").unwrap();
+        let obs3 = Observation::try_from_str("11", "q").unwrap();
+        let mut dataset = Dataset::new("dataset", vec![obs1.clone()], vec!["a", "b"]).unwrap();
+
+        // invalid batches (used ID, duplicate ID, wrong number of values) change nothing
+        let invalid_obs = Observation::try_from_str("111", "r").unwrap();
+        assert!(dataset
+            .push_obs_batch(vec![obs2.clone(), obs1.clone()])
+            .is_err());
+        assert!(dataset
+            .push_obs_batch(vec![obs2.clone(), obs2.clone()])
+            .is_err());
+        assert!(dataset
+            .push_obs_batch(vec![obs2.clone(), invalid_obs])
+            .is_err());
+        assert_eq!(dataset.observations(), &vec![obs1.clone()]);
+
+        // valid batch is added at the end
+        dataset
+            .push_obs_batch(vec![obs2.clone(), obs3.clone()])
+            .unwrap();
+        let all_three_obs = vec![obs1.clone(), obs2.clone(), obs3.clone()];
+        assert_eq!(dataset.observations(), &all_three_obs);
+
+        // removing a batch with an invalid ID changes nothing
+        let ids = [obs1.get_id().clone(), ObservationId::new("x").unwrap()];
+        assert!(dataset.remove_obs_batch(&ids).is_err());
+        assert_eq!(dataset.observations(), &all_three_obs);
+
+        // valid batch is removed, the rest is re-indexed
+        let ids = [obs3.get_id().clone(), obs1.get_id().clone()];
+        dataset.remove_obs_batch(&ids).unwrap();
+        assert_eq!(dataset.observations(), &vec![obs2.clone()]);
+        assert_eq!(dataset.get_obs_index(obs2.get_id()).unwrap(), 0);
+    }
+
+    #[test]
+    /// Test removing duplicate observations from a dataset.
+    fn test_deduplicate() {
+        let observations = vec![
+            Observation::try_from_str("1*0", "o1").unwrap(),
+            Observation::try_from_str("110", "o2").unwrap(),
+            Observation::try_from_str("1*0", "o3").unwrap(),
+            Observation::try_from_str("001", "o4").unwrap(),
+            Observation::try_from_str("110", "o5").unwrap(),
+        ];
+        let mut dataset = Dataset::new("d", observations, vec!["a", "b", "c"]).unwrap();
+
+        let removed = dataset.deduplicate();
+        let removed: Vec<&str> = removed.iter().map(|id| id.as_str()).collect();
+        assert_eq!(removed, vec!["o3", "o5"]);
+        assert_eq!(dataset.num_observations(), 3);
+        let o4 = dataset.get_obs_id_by_str("o4").unwrap();
+        assert_eq!(dataset.get_obs_index(&o4).unwrap(), 2);
+        assert!(dataset.get_obs_id_by_str("o3").is_err());
+
+        // nothing more to remove
+        assert!(dataset.deduplicate().is_empty());
+    }
+
+    #[test]
+    /// Test checking whether consecutive observations differ in at most one variable.
+    fn test_asynchronous_steps() {
+        let obs1 = Observation::try_from_str("001", "o1").unwrap();
+        let obs2 = Observation::try_from_str("*11", "o2").unwrap();
+        let obs3 = Observation::try_from_str("01*", "o3").unwrap();
+        let obs4 = Observation::try_from_str("100", "o4").unwrap();
+        let var_names = vec!["a", "b", "c"];
+
+        // empty dataset or a single observation are trivially fine
+        let dataset = Dataset::new_empty("d", var_names.clone()).unwrap();
+        assert!(dataset.assert_asynchronous_steps().is_ok());
+
+        // unspecified values are compatible with both values
+        let observations = vec![obs1.clone(), obs2.clone(), obs3.clone()];
+        let dataset = Dataset::new("d", observations, var_names.clone()).unwrap();
+        assert!(dataset.assert_asynchronous_steps().is_ok());
+
+        // the last transition (index 2) changes two variables
+        let observations = vec![obs1, obs2, obs3, obs4];
+        let dataset = Dataset::new("d", observations, var_names).unwrap();
+        let err = dataset.assert_asynchronous_steps().unwrap_err();
+        assert!(err.starts_with("Transition 2 "));
+    }
+
+    #[test]
+    /// Test searching for the nearest observation by Hamming distance.
+    fn test_nearest() {
+        let obs1 = Observation::try_from_str("0011", "o1").unwrap();
+        let obs2 = Observation::try_from_str("1*11", "o2").unwrap();
+        let obs3 = Observation::try_from_str("1011", "o3").unwrap();
+        let obs4 = Observation::try_from_str("0001", "o4").unwrap();
+        let var_names = vec!["a", "b", "c", "d"];
+        let dataset = Dataset::new_empty("d", var_names.clone()).unwrap();
+        assert_eq!(dataset.nearest(&obs1), None);
+
+        let observations = vec![obs1, obs2, obs3, obs4];
+        let dataset = Dataset::new("d", observations, var_names).unwrap();
+        let obs_id = |id: &str| ObservationId::new(id).unwrap();
+
+        // observation `o2` has unspecified values and is skipped
+        let query = Observation::try_from_str("1111", "q").unwrap();
+        assert_eq!(dataset.nearest(&query), Some((obs_id("o3"), 1)));
+        // both `o3` and `o4` are in distance 1, the earlier one wins
+        let query = Observation::try_from_str("1001", "q").unwrap();
+        assert_eq!(dataset.nearest(&query), Some((obs_id("o3"), 1)));
+        let query = Observation::try_from_str("0010", "q").unwrap();
+        assert_eq!(dataset.nearest(&query), Some((obs_id("o1"), 1)));
+
+        // incomparable query
+        let query = Observation::try_from_str("00*0", "q").unwrap();
+        assert_eq!(dataset.nearest(&query), None);
+    }
+
+    #[test]
+    /// Test the heuristic inference of regulation monotonicity from a time series.
+    fn test_infer_monotonicity() {
+        let var = |id: &str| VarId::new(id).unwrap();
+        let (a, b, c) = (var("a"), var("b"), var("c"));
+        let observations = vec![
+            Observation::try_from_str("100", "o1").unwrap(),
+            Observation::try_from_str("110", "o2").unwrap(),
+            Observation::try_from_str("010", "o3").unwrap(),
+            Observation::try_from_str("*00", "o4").unwrap(),
+            Observation::try_from_str("*01", "o5").unwrap(),
+        ];
+        let dataset = Dataset::new("d", observations, vec!["a", "b", "c"]).unwrap();
+
+        // `b` rises while `a` is 1, and falls while `a` is 0
+        assert_eq!(
+            dataset.infer_monotonicity(&a, &b),
+            Some(Monotonicity::Activation)
+        );
+        // `c` rises only once, while `b` is 0 (but `a` is unspecified at that point)
+        assert_eq!(
+            dataset.infer_monotonicity(&b, &c),
+            Some(Monotonicity::Inhibition)
+        );
+        assert_eq!(dataset.infer_monotonicity(&a, &c), None);
+        // `b` changes twice while `c` is 0, once rising and once falling (mixed evidence)
+        assert_eq!(dataset.infer_monotonicity(&c, &b), None);
+        // self-regulations and unknown variables give no suggestion
+        assert_eq!(dataset.infer_monotonicity(&b, &b), None);
+        assert_eq!(dataset.infer_monotonicity(&var("x"), &b), None);
+    }
+
     #[test]
     /// Test changing observation's ID (both valid and invalid cases).
     fn test_set_observation_id() {
@@ -600,6 +986,34 @@ mod tests {
         assert_eq!(dataset, dataset_expected);
     }
 
+    #[test]
+    /// Test projecting a dataset onto a subset of its variables.
+    fn test_project() {
+        let name = "dataset";
+        let obs1 = Observation::try_from_str("*10", "o")
+            .unwrap()
+            .with_weight(0.5)
+            .unwrap();
+        let obs2 = Observation::try_from_str("01*", "p").unwrap();
+        let dataset = Dataset::new(name, vec![obs1, obs2], vec!["a", "b", "c"]).unwrap();
+
+        let vars = vec![VarId::new("c").unwrap(), VarId::new("a").unwrap()];
+        let projected = dataset.project(&vars).unwrap();
+        let obs1_expected = Observation::try_from_str("0*", "o")
+            .unwrap()
+            .with_weight(0.5)
+            .unwrap();
+        let obs2_expected = Observation::try_from_str("*0", "p").unwrap();
+        let obs_expected = vec![obs1_expected, obs2_expected];
+        let dataset_expected = Dataset::new(name, obs_expected, vec!["c", "a"]).unwrap();
+        assert_eq!(projected, dataset_expected);
+
+        // invalid or duplicate variables
+        assert!(dataset.project(&[VarId::new("d").unwrap()]).is_err());
+        let vars = vec![VarId::new("a").unwrap(), VarId::new("a").unwrap()];
+        assert!(dataset.project(&vars).is_err());
+    }
+
     #[test]
     /// Test adding variable with default values to a dataset.
     fn test_add_variable_default() {
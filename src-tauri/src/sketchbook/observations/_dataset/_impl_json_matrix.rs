@@ -0,0 +1,98 @@
+use crate::sketchbook::observations::{Dataset, Observation, VarValue};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// **(internal)** Raw structure of a dataset given as a row-major JSON matrix.
+#[derive(Deserialize)]
+struct JsonMatrix {
+    variables: Vec<String>,
+    rows: Vec<Vec<Value>>,
+}
+
+/// Creating `Dataset` instances from JSON matrices.
+impl Dataset {
+    /// Parse a dataset from a JSON object with a list of variable names and a row-major matrix
+    /// of values. Each row represents an observation, with values given as integers `0` and `1`,
+    /// or as a string `"*"` (unspecified).
+    ///
+    /// The order of observations is kept, and their IDs are generated automatically (`obs_1`,
+    /// `obs_2`, ...). Rows with a different length than the number of variables result in an
+    /// error naming the corresponding row.
+    ///
+    /// For example, the following is a valid JSON string for a dataset with 2 observations:
+    ///    { "variables": ["YOX1", "CLN3", "YHP1"], "rows": [[0, 1, 0], [1, 0, "*"]] }
+    ///
+    pub fn from_json_matrix(name: &str, json: &str) -> Result<Dataset, String> {
+        let matrix: JsonMatrix = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let variables: Vec<&str> = matrix.variables.iter().map(|v| v.as_str()).collect();
+        let mut dataset = Dataset::new_empty(name, variables)?;
+
+        for (i, row) in matrix.rows.iter().enumerate() {
+            let row_num = i + 1;
+            if row.len() != matrix.variables.len() {
+                return Err(format!(
+                    "Row {row_num} has {} values, but there are {} variables.",
+                    row.len(),
+                    matrix.variables.len()
+                ));
+            }
+            let values = row
+                .iter()
+                .map(Self::parse_json_value)
+                .collect::<Result<Vec<VarValue>, String>>()
+                .map_err(|e| format!("Error at row {row_num}: {e}"))?;
+
+            let id = dataset.generate_obs_id("obs", Some(1));
+            let observation = Observation::new(values, id.as_str())?;
+            dataset
+                .push_obs(observation)
+                .map_err(|e| format!("Error at row {row_num}: {e}"))?;
+        }
+        Ok(dataset)
+    }
+
+    /// **(internal)** Parse a single value of the JSON matrix - integers `0`/`1`, or `"*"`.
+    fn parse_json_value(value: &Value) -> Result<VarValue, String> {
+        match value {
+            Value::Number(n) if n.as_u64() == Some(0) => Ok(VarValue::False),
+            Value::Number(n) if n.as_u64() == Some(1) => Ok(VarValue::True),
+            Value::String(s) if s == "*" => Ok(VarValue::Any),
+            _ => Err(format!("Invalid value `{value}`.")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::observations::{Dataset, Observation};
+
+    #[test]
+    /// Test parsing dataset from a JSON matrix, generating observation IDs.
+    fn test_from_json_matrix() {
+        let obs1 = Observation::try_from_str("*11", "obs_1").unwrap();
+        let obs2 = Observation::try_from_str("000", "obs_2").unwrap();
+        let expected = Dataset::new("d", vec![obs1, obs2], vec!["a", "b", "c"]).unwrap();
+
+        let json = r#"{ "variables": ["a", "b", "c"], "rows": [["*", 1, 1], [0, 0, 0]] }"#;
+        assert_eq!(Dataset::from_json_matrix("d", json).unwrap(), expected);
+    }
+
+    #[test]
+    /// Test that invalid JSON matrices produce errors.
+    fn test_from_json_matrix_invalid() {
+        // row of a wrong length
+        let json = r#"{ "variables": ["a", "b"], "rows": [[1, 1], [0]] }"#;
+        let err = Dataset::from_json_matrix("d", json).unwrap_err();
+        assert!(err.contains("Row 2"));
+        // invalid values
+        let json = r#"{ "variables": ["a", "b"], "rows": [[1, 2]] }"#;
+        assert!(Dataset::from_json_matrix("d", json).is_err());
+        let json = r#"{ "variables": ["a", "b"], "rows": [[1, "1"]] }"#;
+        assert!(Dataset::from_json_matrix("d", json).is_err());
+        // invalid variable name, or wrong shape
+        let json = r#"{ "variables": ["a b"], "rows": [] }"#;
+        assert!(Dataset::from_json_matrix("d", json).is_err());
+        let json = r#"{ "vars": ["a"], "rows": [] }"#;
+        assert!(Dataset::from_json_matrix("d", json).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+use crate::sketchbook::data_structs::DatasetData;
+use crate::sketchbook::ids::DatasetId;
+use crate::sketchbook::observations::Dataset;
+use crate::sketchbook::JsonSerde;
+
+/// Exporting `Dataset` instances to the native observation JSON.
+impl Dataset {
+    /// Export the dataset to the native JSON format used for datasets across the app (the
+    /// structure of the serialized [DatasetData], with observations as [ObservationData]).
+    ///
+    /// Datasets do not know their own ID, so the `id` under which the dataset is stored must
+    /// be provided. The result can be re-ingested via [DatasetData::from_json_str] and
+    /// [DatasetData::to_dataset].
+    ///
+    /// [ObservationData]: crate::sketchbook::data_structs::ObservationData
+    pub fn to_observation_json(&self, id: &DatasetId) -> String {
+        DatasetData::from_dataset(id, self).to_pretty_json_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::data_structs::DatasetData;
+    use crate::sketchbook::ids::DatasetId;
+    use crate::sketchbook::observations::{Dataset, Observation};
+    use crate::sketchbook::JsonSerde;
+
+    #[test]
+    /// Test exporting dataset to the observation JSON, and loading it back via `DatasetData`.
+    fn test_observation_json_round_trip() {
+        let dataset_id = DatasetId::new("dataset_1").unwrap();
+        let obs1 = Observation::try_from_str("*10", "obs_1")
+            .unwrap()
+            .with_name("first")
+            .with_weight(2.5)
+            .unwrap();
+        let obs2 = Observation::try_from_str("000", "obs_2")
+            .unwrap()
+            .with_annotation("second");
+        let dataset = Dataset::new("d", vec![obs1, obs2], vec!["a", "b", "c"])
+            .unwrap()
+            .with_annotation("some notes");
+
+        let json = dataset.to_observation_json(&dataset_id);
+        let dataset_data = DatasetData::from_json_str(&json).unwrap();
+        assert_eq!(dataset_data.id, "dataset_1");
+        assert_eq!(dataset_data.observations[0].id, "obs_1");
+        assert_eq!(dataset_data.observations[0].values, "*10");
+        assert_eq!(dataset_data.observations[1].dataset, "dataset_1");
+        assert_eq!(dataset_data.to_dataset().unwrap(), dataset);
+    }
+}
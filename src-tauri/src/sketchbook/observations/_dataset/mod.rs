@@ -1,17 +1,27 @@
 use crate::sketchbook::ids::{ObservationId, VarId};
-use crate::sketchbook::observations::Observation;
+use crate::sketchbook::observations::{DatasetOrdering, Observation};
 use crate::sketchbook::Manager;
 use std::collections::HashMap;
 
+/// **(internal)** Parsing `Dataset` instances from CSV.
+mod _impl_csv;
 /// **(internal)** Basic utility methods for `Dataset`.
 mod _impl_dataset;
 /// **(internal)** Implementation of partial event-based API to manipulate observations.
 mod _impl_events;
 /// **(internal)** Implementation of the safe identifier generating.
 mod _impl_id_generating;
+/// **(internal)** Parsing `Dataset` instances from row-major JSON matrices.
+mod _impl_json_matrix;
+/// **(internal)** Exporting `Dataset` instances to the native observation JSON.
+mod _impl_observation_json;
+
+pub(in crate::sketchbook::observations) use _impl_csv::{METADATA_COLUMN_NAME, WEIGHT_COLUMN_NAME};
 
 /// An ordered list of observations for given variables.
 /// The order is important for some datasets, for example, to be able to capture time series.
+/// Whether the order is meaningful is given by the dataset's [DatasetOrdering] (time series
+/// by default).
 ///
 /// `Dataset` provides classical Rust API for modifications. It also manages its observations
 /// through event-based API. However, this API is limited, and only serves as an extension to that
@@ -22,6 +32,8 @@ pub struct Dataset {
     name: String,
     // String with arbitrary custom information.
     annotation: String,
+    /// Whether the observations form a time series, or an unordered collection of states.
+    ordering: DatasetOrdering,
     /// List of binarized observations.
     observations: Vec<Observation>,
     /// Variables captured by the observations.
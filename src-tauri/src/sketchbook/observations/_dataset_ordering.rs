@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Enum describing whether the order of observations in a `Dataset` is meaningful.
+///
+/// For time series, observations are consecutive states of the system (in the given order).
+/// For unordered datasets, observations are just a collection of states, and their order is
+/// arbitrary. Some dynamic property templates (like trajectories) only make sense for one
+/// of these kinds.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum DatasetOrdering {
+    #[default]
+    TimeSeries,
+    Unordered,
+}
+
+impl fmt::Display for DatasetOrdering {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DatasetOrdering::TimeSeries => write!(f, "time series"),
+            DatasetOrdering::Unordered => write!(f, "unordered"),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::sketchbook::ids::DatasetId;
+use crate::sketchbook::observations::_dataset::{METADATA_COLUMN_NAME, WEIGHT_COLUMN_NAME};
 use crate::sketchbook::observations::ObservationManager;
 use std::fs::File;
 use std::io::Write;
@@ -7,26 +8,61 @@ impl ObservationManager {
     /// Convert the dataset to a CSV string.
     ///
     /// See [Self::parse_dataset_from_csv] for the details of the format. The first line contains
-    /// variable names as a header. Each subsequent line represents an observation (ID and values).
-    fn dataset_to_csv_string(&self, dataset_id: &DatasetId) -> Result<String, String> {
+    /// variable names as a header. Each subsequent line represents an observation (values, and
+    /// its ID if `include_ids` is set).
+    ///
+    /// If `include_ids` is set, the first column is named `ID` and contains observation IDs, so
+    /// that the IDs stay stable when the CSV is re-imported via [super::Dataset::from_csv]. Otherwise,
+    /// only the variable columns are written.
+    ///
+    /// If some observation has an explicit weight, an extra column named `weight` is added after
+    /// the variable columns (empty for observations without weight). If some observation has
+    /// metadata, an extra last column named `metadata` is added (with cells quoted if needed).
+    fn dataset_to_csv_string(
+        &self,
+        dataset_id: &DatasetId,
+        include_ids: bool,
+    ) -> Result<String, String> {
         let dataset = self.get_dataset(dataset_id)?;
         let mut csv_string = String::new();
 
         // Add header line with variable names
-        csv_string.push_str("ID");
-        for var_id in dataset.variables() {
-            csv_string.push(',');
-            csv_string.push_str(var_id.as_str());
+        let mut header: Vec<&str> = dataset.variables().iter().map(|v| v.as_str()).collect();
+        if include_ids {
+            header.insert(0, "ID");
+        }
+        let include_metadata = dataset
+            .observations()
+            .iter()
+            .any(|obs| obs.get_metadata().is_some());
+        let include_weights = dataset.observations().iter().any(|obs| obs.has_weight());
+        if include_weights {
+            header.push(WEIGHT_COLUMN_NAME);
         }
+        if include_metadata {
+            header.push(METADATA_COLUMN_NAME);
+        }
+        csv_string.push_str(&header.join(","));
         csv_string.push('\n');
 
-        // Add each observation as a line
+        // Add each observation as a line (unspecified values are written as `*`)
         for obs in dataset.observations() {
-            csv_string.push_str(obs.get_id().as_str());
-            for value in obs.get_values() {
-                csv_string.push(',');
-                csv_string.push_str(&value.to_string());
+            let mut row: Vec<String> = obs
+                .get_values()
+                .iter()
+                .map(|v| v.as_str().to_string())
+                .collect();
+            if include_ids {
+                row.insert(0, obs.get_id().to_string());
+            }
+            if include_weights {
+                let weight = obs.has_weight().then(|| obs.get_weight().to_string());
+                row.push(weight.unwrap_or_default());
             }
+            if include_metadata {
+                row.push(escape_csv_cell(obs.get_metadata().unwrap_or_default()));
+            }
+            csv_string.push_str(&row.join(","));
             csv_string.push('\n');
         }
 
@@ -37,13 +73,14 @@ impl ObservationManager {
     ///
     /// See [Self::parse_dataset_from_csv] for the details of the format. In short, the
     /// header line specifies variable IDs, and each subsequent line represents individual
-    /// observations (id and values).
+    /// observations (values, and its ID if `include_ids` is set).
     pub fn export_dataset_to_csv(
         &self,
         dataset_id: &DatasetId,
         csv_path: &str,
+        include_ids: bool,
     ) -> Result<(), String> {
-        let csv_str = self.dataset_to_csv_string(dataset_id)?;
+        let csv_str = self.dataset_to_csv_string(dataset_id, include_ids)?;
 
         let mut file = File::create(csv_path).map_err(|e| e.to_string())?;
         // write dataset in CSV to the file
@@ -53,6 +90,16 @@ impl ObservationManager {
     }
 }
 
+/// **(internal)** Quote the CSV cell if it contains special characters (delimiters, quotes,
+/// or line breaks). Quotes inside are doubled.
+fn escape_csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,8 +120,77 @@ mod tests {
         manager.add_dataset(dataset_id.clone(), dataset).unwrap();
 
         // Convert dataset to CSV string and compare with expected output
-        let csv_string = manager.dataset_to_csv_string(&dataset_id).unwrap();
+        let csv_string = manager.dataset_to_csv_string(&dataset_id, true).unwrap();
         let expected_csv = "ID,a,b,c\nobs1,*,1,1\nobs2,0,0,0\n";
         assert_eq!(csv_string, expected_csv);
+
+        let csv_string = manager.dataset_to_csv_string(&dataset_id, false).unwrap();
+        let expected_csv = "a,b,c\n*,1,1\n0,0,0\n";
+        assert_eq!(csv_string, expected_csv);
+    }
+
+    #[test]
+    fn test_dataset_csv_round_trip() {
+        let obs1 = Observation::try_from_str("*1*", "first").unwrap();
+        let obs2 = Observation::try_from_str("0*0", "second").unwrap();
+        let dataset = Dataset::new("d", vec![obs1, obs2], vec!["a", "b", "c"]).unwrap();
+        let mut manager = ObservationManager::new_empty();
+        let dataset_id = DatasetId::new("d").unwrap();
+        manager
+            .add_dataset(dataset_id.clone(), dataset.clone())
+            .unwrap();
+
+        // with IDs, the whole dataset (including observation IDs) is preserved
+        let csv_string = manager.dataset_to_csv_string(&dataset_id, true).unwrap();
+        assert_eq!(Dataset::from_csv("d", &csv_string).unwrap(), dataset);
+
+        // without IDs, the values are preserved
+        let csv_string = manager.dataset_to_csv_string(&dataset_id, false).unwrap();
+        let reimported = Dataset::from_csv("d", &csv_string).unwrap();
+        assert_eq!(reimported.observations()[0].to_values_string(), "*1*");
+        assert_eq!(reimported.observations()[1].to_values_string(), "0*0");
+    }
+
+    #[test]
+    /// Test that observation metadata are exported into a quoted `metadata` column and re-imported.
+    fn test_dataset_csv_round_trip_with_metadata() {
+        let obs1 = Observation::try_from_str("*1", "first")
+            .unwrap()
+            .with_metadata("exp \"A\", 2024-03-01");
+        let obs2 = Observation::try_from_str("0*", "second").unwrap();
+        let dataset = Dataset::new("d", vec![obs1, obs2], vec!["a", "b"]).unwrap();
+        let mut manager = ObservationManager::new_empty();
+        let dataset_id = DatasetId::new("d").unwrap();
+        manager
+            .add_dataset(dataset_id.clone(), dataset.clone())
+            .unwrap();
+
+        let csv_string = manager.dataset_to_csv_string(&dataset_id, true).unwrap();
+        let expected_csv =
+            "ID,a,b,metadata\nfirst,*,1,\"exp \"\"A\"\", 2024-03-01\"\nsecond,0,*,\n";
+        assert_eq!(csv_string, expected_csv);
+        assert_eq!(Dataset::from_csv("d", &csv_string).unwrap(), dataset);
+    }
+
+    #[test]
+    /// Test that observation weights are exported into a `weight` column and re-imported.
+    fn test_dataset_csv_round_trip_with_weights() {
+        let obs1 = Observation::try_from_str("*1", "first")
+            .unwrap()
+            .with_weight(0.25)
+            .unwrap()
+            .with_metadata("exp A");
+        let obs2 = Observation::try_from_str("0*", "second").unwrap();
+        let dataset = Dataset::new("d", vec![obs1, obs2], vec!["a", "b"]).unwrap();
+        let mut manager = ObservationManager::new_empty();
+        let dataset_id = DatasetId::new("d").unwrap();
+        manager
+            .add_dataset(dataset_id.clone(), dataset.clone())
+            .unwrap();
+
+        let csv_string = manager.dataset_to_csv_string(&dataset_id, true).unwrap();
+        let expected_csv = "ID,a,b,weight,metadata\nfirst,*,1,0.25,exp A\nsecond,0,*,,\n";
+        assert_eq!(csv_string, expected_csv);
+        assert_eq!(Dataset::from_csv("d", &csv_string).unwrap(), dataset);
     }
 }
@@ -1,4 +1,5 @@
 use crate::sketchbook::observations::{Dataset, Observation, ObservationManager, VarValue};
+use crate::sketchbook::utils::read_maybe_gzipped_file;
 use std::str::FromStr;
 
 impl ObservationManager {
@@ -40,8 +41,11 @@ impl ObservationManager {
 
     /// Load a dataset from a given CSV file. Reads the file into a string and then parses it
     /// into a dataset using [Self::parse_dataset_from_csv].
+    ///
+    /// Gzip-compressed files are detected (by their magic bytes or the `.gz` extension), and
+    /// transparently decompressed.
     pub fn load_dataset(name: &str, csv_path: &str) -> Result<Dataset, String> {
-        let csv_content = std::fs::read_to_string(csv_path).map_err(|e| e.to_string())?;
+        let csv_content = read_maybe_gzipped_file(csv_path)?;
         Self::parse_dataset_from_csv(name, &csv_content)
     }
 
@@ -60,6 +64,7 @@ impl ObservationManager {
 mod tests {
     use super::*;
     use crate::sketchbook::observations::{Dataset, Observation};
+    use crate::sketchbook::utils::gzip_compress;
 
     #[test]
     fn test_dataset_from_csv_string() {
@@ -78,4 +83,25 @@ mod tests {
             ObservationManager::parse_dataset_from_csv(dataset_name, csv_string).unwrap();
         assert_eq!(parsed_dataset, expected_dataset);
     }
+
+    #[test]
+    /// Test that plain and gzip-compressed CSV files are loaded into the same dataset.
+    fn test_load_gzipped_dataset() {
+        let csv_string = "ID,a,b,c\nobs1,*,1,1\nobs2,0,0,0\n";
+        let directory =
+            std::env::temp_dir().join(format!("sketchbook_test_gzip_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let plain_path = directory.join("sketchbook_test_dataset.csv");
+        let gz_path = directory.join("sketchbook_test_dataset.csv.gz");
+        std::fs::write(&plain_path, csv_string).unwrap();
+        std::fs::write(&gz_path, gzip_compress(csv_string).unwrap()).unwrap();
+
+        let plain_dataset =
+            ObservationManager::load_dataset("d", plain_path.to_str().unwrap()).unwrap();
+        let gz_dataset = ObservationManager::load_dataset("d", gz_path.to_str().unwrap()).unwrap();
+        assert_eq!(plain_dataset, gz_dataset);
+        assert_eq!(plain_dataset.num_observations(), 2);
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
 }
@@ -1,5 +1,8 @@
 use crate::sketchbook::ids::{DatasetId, ObservationId, VarId};
-use crate::sketchbook::observations::{Dataset, DatasetIterator, Observation, ObservationManager};
+use crate::sketchbook::observations::{
+    Dataset, DatasetIterator, DatasetOrdering, Observation, ObservationManager, VarValue,
+};
+use crate::sketchbook::utils::assert_ids_unique;
 use std::collections::HashMap;
 use std::str::FromStr;
 
@@ -99,6 +102,18 @@ impl ObservationManager {
         self.set_dataset_annot(&dataset_id, annot)
     }
 
+    /// Set ordering of a dataset with given id (time series or unordered collection).
+    pub fn set_dataset_ordering(
+        &mut self,
+        id: &DatasetId,
+        ordering: DatasetOrdering,
+    ) -> Result<(), String> {
+        self.assert_valid_dataset(id)?;
+        let dataset = self.datasets.get_mut(id).unwrap();
+        dataset.set_ordering(ordering);
+        Ok(())
+    }
+
     /// Set the id of dataset with `original_id` to `new_id`.
     pub fn set_dataset_id(
         &mut self,
@@ -231,6 +246,141 @@ impl ObservationManager {
         let dataset_id = DatasetId::new(id)?;
         self.remove_dataset(&dataset_id)
     }
+
+    /// Merge datasets with given `ids` into a new dataset with ID `new_id` and name `new_name`.
+    /// The source datasets remain unchanged.
+    ///
+    /// The new dataset contains the union of all variables (in the order of their first
+    /// appearance), and the observations of all datasets concatenated (in the order of `ids`).
+    /// Values of variables missing in the original dataset are unspecified (`*`). If all the
+    /// datasets have the same ordering, the new dataset has it too, otherwise it is unordered.
+    ///
+    /// Observations with the same ID in different datasets are considered records of the same
+    /// sample, and they are combined into a single observation (at the position of its first
+    /// record). The combined observation takes all specified values of the records, and the
+    /// name, annotation, metadata, and weight of the first record (the weight of a later record
+    /// is used if the first one has none). If the records disagree on the (specified) value of
+    /// some shared variable, or on their weights, the datasets are not merged and `Err` listing
+    /// all these conflicts is returned.
+    pub fn merge_datasets(
+        &mut self,
+        ids: &[DatasetId],
+        new_id: DatasetId,
+        new_name: &str,
+    ) -> Result<(), String> {
+        if ids.is_empty() {
+            return Err("At least one dataset must be provided for merging.".to_string());
+        }
+        assert_ids_unique(&ids.iter().collect())?;
+        self.assert_no_dataset(&new_id)?;
+        let datasets = ids
+            .iter()
+            .map(|id| self.get_dataset(id))
+            .collect::<Result<Vec<&Dataset>, String>>()?;
+
+        let conflicts = Self::collect_merge_conflicts(ids, &datasets);
+        if !conflicts.is_empty() {
+            return Err(format!(
+                "Datasets can't be merged due to conflicting observations:\n{}",
+                conflicts.join("\n")
+            ));
+        }
+
+        // union of variables, in the order of their first appearance
+        let mut variables: Vec<&VarId> = Vec::new();
+        for dataset in datasets.iter() {
+            for var_id in dataset.variables() {
+                if !variables.contains(&var_id) {
+                    variables.push(var_id);
+                }
+            }
+        }
+        let var_names = variables.iter().map(|v| v.as_str()).collect();
+        let ordering = datasets[0].get_ordering();
+        let ordering = if datasets.iter().all(|d| d.get_ordering() == ordering) {
+            ordering
+        } else {
+            DatasetOrdering::Unordered
+        };
+        let mut merged_dataset = Dataset::new_empty(new_name, var_names)?.with_ordering(ordering);
+
+        // records of the same sample are combined (they were checked for conflicts already)
+        let mut merged_obs: Vec<Observation> = Vec::new();
+        let mut obs_indices: HashMap<&ObservationId, usize> = HashMap::new();
+        for dataset in datasets.iter() {
+            for obs in dataset.observations() {
+                let values: Vec<VarValue> = variables
+                    .iter()
+                    .map(|var_id| match dataset.get_idx_of_var(var_id) {
+                        Ok(idx) => obs.get_values()[idx].clone(),
+                        Err(_) => VarValue::Any,
+                    })
+                    .collect();
+                let Some(&obs_idx) = obs_indices.get(obs.get_id()) else {
+                    obs_indices.insert(obs.get_id(), merged_obs.len());
+                    merged_obs.push(obs.with_values(values));
+                    continue;
+                };
+                let record = &mut merged_obs[obs_idx];
+                for (idx, value) in values.into_iter().enumerate() {
+                    if value != VarValue::Any {
+                        record.set_value(idx, value)?;
+                    }
+                }
+                if !record.has_weight() && obs.has_weight() {
+                    record.set_weight(obs.get_weight())?;
+                }
+            }
+        }
+        merged_dataset.push_obs_batch(merged_obs)?;
+        self.add_dataset(new_id, merged_dataset)
+    }
+
+    /// **(internal)** Collect descriptions of conflicts between observations with the same ID
+    /// in different datasets, i.e., variables whose specified values differ, and different
+    /// (specified) weights.
+    fn collect_merge_conflicts(ids: &[DatasetId], datasets: &[&Dataset]) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        for (i, dataset_1) in datasets.iter().enumerate() {
+            for (j, dataset_2) in datasets.iter().enumerate().skip(i + 1) {
+                for obs_1 in dataset_1.observations() {
+                    let Ok(obs_2) = dataset_2.get_obs(obs_1.get_id()) else {
+                        continue;
+                    };
+                    if obs_1.has_weight()
+                        && obs_2.has_weight()
+                        && obs_1.get_weight() != obs_2.get_weight()
+                    {
+                        conflicts.push(format!(
+                            "Observation `{}` has different weights in datasets `{}` and `{}`.",
+                            obs_1.get_id(),
+                            ids[i],
+                            ids[j]
+                        ));
+                    }
+                    for (idx_1, var_id) in dataset_1.variables().iter().enumerate() {
+                        let Ok(idx_2) = dataset_2.get_idx_of_var(var_id) else {
+                            continue;
+                        };
+                        let value_1 = &obs_1.get_values()[idx_1];
+                        let value_2 = &obs_2.get_values()[idx_2];
+                        if value_1 != &VarValue::Any
+                            && value_2 != &VarValue::Any
+                            && value_1 != value_2
+                        {
+                            conflicts.push(format!(
+                                "Observation `{}` differs in variable `{var_id}` in datasets `{}` and `{}`.",
+                                obs_1.get_id(),
+                                ids[i],
+                                ids[j]
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+        conflicts
+    }
 }
 
 /// Observing the `ObservationManager`.
@@ -331,7 +481,10 @@ impl ObservationManager {
 
 #[cfg(test)]
 mod tests {
-    use crate::sketchbook::observations::{Dataset, Observation, ObservationManager};
+    use crate::sketchbook::ids::DatasetId;
+    use crate::sketchbook::observations::{
+        Dataset, DatasetOrdering, Observation, ObservationManager,
+    };
 
     #[test]
     /// Test that valid manager instances are created correctly, and invalid case is handled.
@@ -404,4 +557,107 @@ mod tests {
         let d1 = manager.get_dataset_id("d1").unwrap();
         assert_eq!(manager.get_dataset(&d1).unwrap(), &new_dataset);
     }
+
+    #[test]
+    /// Test merging datasets, including the conflict detection.
+    fn test_merge_datasets() {
+        let d1 = Dataset::new(
+            "d1",
+            vec![
+                Observation::try_from_str("10", "o1").unwrap(),
+                Observation::try_from_str("0*", "o2").unwrap(),
+            ],
+            vec!["a", "b"],
+        )
+        .unwrap();
+        let d2 = Dataset::new(
+            "d2",
+            vec![
+                Observation::try_from_str("11", "o2").unwrap(),
+                Observation::try_from_str("00", "o3")
+                    .unwrap()
+                    .with_weight(0.5)
+                    .unwrap(),
+            ],
+            vec!["b", "c"],
+        )
+        .unwrap();
+        let dataset_list = vec![("d1", d1.clone()), ("d2", d2.clone())];
+        let mut manager = ObservationManager::from_datasets(dataset_list).unwrap();
+        let ids = [DatasetId::new("d1").unwrap(), DatasetId::new("d2").unwrap()];
+
+        // variables are merged, missing values unspecified, and records of the same sample
+        // (observations with the same ID) combined
+        let merged_id = DatasetId::new("merged").unwrap();
+        manager
+            .merge_datasets(&ids, merged_id.clone(), "merged")
+            .unwrap();
+        let merged = manager.get_dataset(&merged_id).unwrap();
+        assert_eq!(merged.variable_names(), vec!["a", "b", "c"]);
+        assert_eq!(merged.num_observations(), 3);
+        let expected = vec![("o1", "10*"), ("o2", "011"), ("o3", "*00")];
+        for (obs, (id, values)) in merged.observations().iter().zip(expected) {
+            assert_eq!(obs.get_id().as_str(), id);
+            assert_eq!(obs.to_values_string(), values);
+        }
+        assert_eq!(merged.observations()[2].get_weight(), 0.5);
+        assert_eq!(merged.get_ordering(), DatasetOrdering::TimeSeries);
+        assert_eq!(manager.get_dataset(&ids[0]).unwrap(), &d1);
+        assert_eq!(manager.get_dataset(&ids[1]).unwrap(), &d2);
+
+        // conflicting values for the same observation in a shared variable
+        let d3 = Dataset::new(
+            "d3",
+            vec![Observation::try_from_str("00", "o1").unwrap()],
+            vec!["b", "a"],
+        )
+        .unwrap();
+        manager.add_dataset_by_str("d3", d3).unwrap();
+        let ids = [ids[0].clone(), DatasetId::new("d3").unwrap()];
+        let new_id = DatasetId::new("merged_2").unwrap();
+        let err = manager
+            .merge_datasets(&ids, new_id.clone(), "m")
+            .unwrap_err();
+        assert!(err.contains("`o1` differs in variable `a`"));
+        assert!(!manager.is_valid_dataset_id(&new_id));
+
+        // records of the same sample with different weights
+        let d4 = Dataset::new(
+            "d4",
+            vec![Observation::try_from_str("*", "o3")
+                .unwrap()
+                .with_weight(2.)
+                .unwrap()],
+            vec!["d"],
+        )
+        .unwrap();
+        manager.add_dataset_by_str("d4", d4).unwrap();
+        let ids_weights = [DatasetId::new("d2").unwrap(), DatasetId::new("d4").unwrap()];
+        let err = manager
+            .merge_datasets(&ids_weights, new_id.clone(), "m")
+            .unwrap_err();
+        assert!(err.contains("`o3` has different weights"));
+
+        // merging datasets with different orderings results in an unordered dataset
+        let d2_id = DatasetId::new("d2").unwrap();
+        manager
+            .set_dataset_ordering(&d2_id, DatasetOrdering::Unordered)
+            .unwrap();
+        manager
+            .merge_datasets(&ids_weights[..1], new_id.clone(), "m")
+            .unwrap();
+        let merged = manager.get_dataset(&new_id).unwrap();
+        assert_eq!(merged.get_ordering(), DatasetOrdering::Unordered);
+        let mixed_id = DatasetId::new("mixed").unwrap();
+        let ids_mixed = [DatasetId::new("d1").unwrap(), d2_id];
+        manager
+            .merge_datasets(&ids_mixed, mixed_id.clone(), "m")
+            .unwrap();
+        let merged = manager.get_dataset(&mixed_id).unwrap();
+        assert_eq!(merged.get_ordering(), DatasetOrdering::Unordered);
+
+        // invalid inputs
+        assert!(manager.merge_datasets(&[], new_id.clone(), "m").is_err());
+        assert!(manager.merge_datasets(&ids, merged_id, "m").is_err());
+    }
 }
@@ -21,13 +21,13 @@ const ADD_DEFAULT_DATASET_PATH: &str = "add_default";
 const LOAD_DATASET_PATH: &str = "load";
 // remove particular dataset
 const REMOVE_DATASET_PATH: &str = "remove";
-// add a new prepared dataset
+// export particular dataset to a CSV file
 const EXPORT_PATH: &str = "export";
 // set ID of a particular dataset
 const SET_DATASET_ID_PATH: &str = "set_id";
 // set whole content of a particular dataset
 const SET_DATASET_CONTENT_PATH: &str = "set_content";
-// set metadata of a particular dataset (name, variables) - does not set observations
+// set metadata of a particular dataset (name, variables, ordering) - does not set observations
 const SET_DATASET_METADATA_PATH: &str = "set_metadata";
 // remove a particular variable from dataset
 const REMOVE_VARIABLE_PATH: &str = "remove_var";
@@ -37,6 +37,10 @@ const ADD_DEFAULT_VARIABLE_PATH: &str = "add_default_var";
 const SET_VARIABLE_ID_PATH: &str = "set_var_id";
 // push new default empty observation to a dataset
 const PUSH_EMPTY_OBS_PATH: &str = "push_empty_obs";
+// add a batch of new observations to the end of a dataset
+const ADD_OBS_BATCH_PATH: &str = "add_batch";
+// remove a batch of observations from a dataset
+const REMOVE_OBS_BATCH_PATH: &str = "remove_batch";
 // refresh all datasets
 const GET_ALL_DATASETS_PATH: &str = "get_all_datasets";
 // refresh particular dataset
@@ -219,8 +223,18 @@ impl ObservationManager {
                 Ok(make_reversible(state_change, event, reverse_event))
             }
             Some(&EXPORT_PATH) => {
-                let path = Self::clone_payload_str(event, component_name)?;
-                self.export_dataset_to_csv(&dataset_id, &path)?;
+                // get payload and parse the path and whether to include observation IDs
+                let payload = Self::clone_payload_str(event, component_name)?;
+                let payload_json: serde_json::Value = serde_json::from_str(&payload)?;
+                let path = payload_json["path"]
+                    .as_str()
+                    .ok_or("Missing 'path' in payload")?;
+                // observation IDs are included by default
+                let include_ids = match &payload_json["include_ids"] {
+                    serde_json::Value::Null => true,
+                    value => value.as_bool().ok_or("Invalid 'include_ids' in payload")?,
+                };
+                self.export_dataset_to_csv(&dataset_id, path, include_ids)?;
                 Ok(Consumed::NoChange)
             }
             Some(&SET_DATASET_ID_PATH) => {
@@ -263,13 +277,14 @@ impl ObservationManager {
             }
             Some(&SET_DATASET_METADATA_PATH) => {
                 // Get the payload - json string encoding metadata with (potentially) updated name,
-                // annotation, or variable names
+                // annotation, variable names, or ordering
                 let payload = Self::clone_payload_str(event, component_name)?;
                 let new_metadata = DatasetMetaData::from_json_str(&payload)?;
                 let orig_dataset = self.get_dataset(&dataset_id)?;
                 if orig_dataset.get_name() == new_metadata.name
                     && orig_dataset.get_annotation() == new_metadata.annotation
                     && orig_dataset.variable_names() == new_metadata.variables
+                    && orig_dataset.get_ordering() == new_metadata.ordering
                 {
                     return Ok(Consumed::NoChange);
                 }
@@ -278,6 +293,7 @@ impl ObservationManager {
                 let orig_metadata = DatasetMetaData::from_dataset(&dataset_id, orig_dataset);
                 self.set_dataset_name(&dataset_id, &new_metadata.name)?;
                 self.set_dataset_annot(&dataset_id, &new_metadata.annotation)?;
+                self.set_dataset_ordering(&dataset_id, new_metadata.ordering)?;
                 let variables = new_metadata.variables.iter().map(|v| v.as_str()).collect();
                 self.set_all_variables_by_str(dataset_id.as_str(), variables)?;
                 let state_change = mk_obs_state_change(&["set_metadata"], &new_metadata);
@@ -357,6 +373,70 @@ impl ObservationManager {
                 let dataset = self.datasets.get_mut(&dataset_id).unwrap();
                 dataset.event_push_empty_observation(event, dataset_id)
             }
+            Some(&ADD_OBS_BATCH_PATH) => {
+                // Get the payload - json string encoding a list of observation data
+                let payload = Self::clone_payload_str(event, component_name)?;
+                let obs_data_list: Vec<ObservationData> = serde_json::from_str(&payload)?;
+                let observations = obs_data_list
+                    .iter()
+                    .map(|obs_data| obs_data.to_observation())
+                    .collect::<Result<Vec<_>, String>>()?;
+                if observations.is_empty() {
+                    return Ok(Consumed::NoChange);
+                }
+                // Save the original dataset for later (used in the reverse event)
+                let orig_dataset = self.get_dataset(&dataset_id)?;
+                let orig_dataset_data = DatasetData::from_dataset(&dataset_id, orig_dataset);
+
+                // Perform the event (the whole batch is validated first, and either fully
+                // applied, or the event fails)
+                // the ID is valid (checked before), we can unwrap
+                let dataset = self.datasets.get_mut(&dataset_id).unwrap();
+                dataset.push_obs_batch(observations)?;
+
+                // Prepare the state-change variant
+                // Since this is a large change, we basically send the whole dataset data to the frontend
+                let new_dataset_data = DatasetData::from_dataset(&dataset_id, dataset);
+                let state_change = mk_obs_state_change(&["set_content"], &new_dataset_data);
+
+                // Prepare the reverse event, setting the whole original content of the dataset
+                // (this restores the original observation list exactly, including the order)
+                let reverse_at_path = [dataset_id.as_str(), "set_content"];
+                let payload = orig_dataset_data.to_json_str();
+                let reverse_event = mk_obs_event(&reverse_at_path, Some(&payload));
+                Ok(make_reversible(state_change, event, reverse_event))
+            }
+            Some(&REMOVE_OBS_BATCH_PATH) => {
+                // Get the payload - json string encoding a list of observation IDs
+                let payload = Self::clone_payload_str(event, component_name)?;
+                let obs_id_list: Vec<String> = serde_json::from_str(&payload)?;
+                let obs_ids = obs_id_list
+                    .iter()
+                    .map(|id| ObservationId::new(id.as_str()))
+                    .collect::<Result<Vec<_>, String>>()?;
+                if obs_ids.is_empty() {
+                    return Ok(Consumed::NoChange);
+                }
+                // Save the original dataset for later (used in the reverse event)
+                let orig_dataset = self.get_dataset(&dataset_id)?;
+                let orig_dataset_data = DatasetData::from_dataset(&dataset_id, orig_dataset);
+
+                // Perform the event (all IDs are validated first, and the batch is either fully
+                // applied, or the event fails)
+                // the ID is valid (checked before), we can unwrap
+                let dataset = self.datasets.get_mut(&dataset_id).unwrap();
+                dataset.remove_obs_batch(&obs_ids)?;
+
+                // Prepare the state-change variant (whole dataset data, same as for adding)
+                let new_dataset_data = DatasetData::from_dataset(&dataset_id, dataset);
+                let state_change = mk_obs_state_change(&["set_content"], &new_dataset_data);
+
+                // Prepare the reverse event, setting the whole original content of the dataset
+                let reverse_at_path = [dataset_id.as_str(), "set_content"];
+                let payload = orig_dataset_data.to_json_str();
+                let reverse_event = mk_obs_event(&reverse_at_path, Some(&payload));
+                Ok(make_reversible(state_change, event, reverse_event))
+            }
             _ => {
                 // Finally, remaining events must be some kind of modification of a specific observation
                 // The `at_path` must be ["observation_id", <ACTION>]
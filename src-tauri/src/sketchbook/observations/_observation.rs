@@ -3,15 +3,32 @@ use crate::sketchbook::{ids::ObservationId, utils::assert_name_valid};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
+/// Weight of observations that do not have any weight specified.
+const DEFAULT_WEIGHT: f64 = 1.0;
+
 /// A single named observation, i.e., an ordered vector of binarized values.
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+///
+/// Observations may optionally carry a `weight` (1.0 if not specified), expressing how much
+/// we trust them. Weights are only used by the weighted (fuzzy) reachability scoring, all
+/// other properties treat observations as hard constraints.
+///
+/// Observations may also carry optional `metadata` describing their provenance (e.g., an
+/// experiment ID or a date). Metadata are never used by the inference.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Observation {
     id: ObservationId,
     name: String,
     annotation: String,
     values: Vec<VarValue>,
+    #[serde(default)]
+    weight: Option<f64>,
+    #[serde(default)]
+    metadata: Option<String>,
 }
 
+/// Weights are always finite (see [Observation::set_weight]), so the equality is reflexive.
+impl Eq for Observation {}
+
 /// Creating observations.
 impl Observation {
     /// Create `Observation` object from a vector of values, and string ID (which must be
@@ -25,6 +42,8 @@ impl Observation {
             name: id.to_string(),
             annotation: String::new(),
             values,
+            weight: None,
+            metadata: None,
         })
     }
 
@@ -40,6 +59,27 @@ impl Observation {
         self
     }
 
+    /// Modifier to update the `weight` property. The weight must be a non-negative number.
+    pub fn with_weight(mut self, weight: f64) -> Result<Self, String> {
+        self.set_weight(weight)?;
+        Ok(self)
+    }
+
+    /// Modifier to update the `metadata` property.
+    pub fn with_metadata(mut self, metadata: &str) -> Self {
+        self.metadata = Some(metadata.to_string());
+        self
+    }
+
+    /// Create a copy of this observation with a different vector of values (possibly of a
+    /// different length). All other properties, including ID, weight, and metadata, are kept.
+    pub fn with_values(&self, values: Vec<VarValue>) -> Self {
+        Self {
+            values,
+            ..self.clone()
+        }
+    }
+
     /// Create `Observation` encoding a vector of `n` ones.
     /// Name is initialized same as ID, and annotation is empty.
     pub fn new_full_ones(n: usize, id: &str) -> Result<Self, String> {
@@ -65,7 +105,7 @@ impl Observation {
     pub fn try_from_str(observation_str: &str, id: &str) -> Result<Self, String> {
         let mut observation_vec: Vec<VarValue> = Vec::with_capacity(observation_str.len());
         for c in observation_str.chars() {
-            observation_vec.push(VarValue::from_str(&c.to_string())?)
+            observation_vec.push(VarValue::from_char(c)?)
         }
 
         Self::new(observation_vec, id)
@@ -86,6 +126,22 @@ impl Observation {
         self.annotation = annotation.to_string();
     }
 
+    /// Set weight of the observation. The weight must be a non-negative (finite) number.
+    pub fn set_weight(&mut self, weight: f64) -> Result<(), String> {
+        if !weight.is_finite() || weight < 0. {
+            return Err(format!(
+                "Weight of an observation must be a non-negative number, not `{weight}`."
+            ));
+        }
+        self.weight = Some(weight);
+        Ok(())
+    }
+
+    /// Set (or remove, if `None`) the metadata string of the observation.
+    pub fn set_metadata(&mut self, metadata: Option<&str>) {
+        self.metadata = metadata.map(|m| m.to_string());
+    }
+
     /// Set the value at given idx.
     pub fn set_value(&mut self, index: usize, value: VarValue) -> Result<(), String> {
         if index >= self.num_values() {
@@ -117,7 +173,7 @@ impl Observation {
     pub fn set_all_values_by_str(&mut self, values: &str) -> Result<(), String> {
         let mut converted_values: Vec<VarValue> = Vec::with_capacity(values.len());
         for c in values.chars() {
-            converted_values.push(VarValue::from_str(&c.to_string())?)
+            converted_values.push(VarValue::from_char(c)?)
         }
         self.set_all_values(converted_values)
     }
@@ -165,6 +221,21 @@ impl Observation {
         &self.annotation
     }
 
+    /// Weight of the observation (1.0 if no weight was specified).
+    pub fn get_weight(&self) -> f64 {
+        self.weight.unwrap_or(DEFAULT_WEIGHT)
+    }
+
+    /// Check whether the weight of the observation was explicitly specified.
+    pub fn has_weight(&self) -> bool {
+        self.weight.is_some()
+    }
+
+    /// Metadata string of the observation (if specified).
+    pub fn get_metadata(&self) -> Option<&str> {
+        self.metadata.as_deref()
+    }
+
     /// Get reference to observation's vector of values.
     pub fn get_values(&self) -> &Vec<VarValue> {
         &self.values
@@ -211,6 +282,35 @@ impl Observation {
         Ok(&self.values[index])
     }
 
+    /// Compute the Hamming distance between this and the `other` observation, i.e., the number of
+    /// positions at which their values differ.
+    ///
+    /// Both observations must be fully specified (no `*` values) and have the same number of
+    /// values, otherwise `Err` is returned.
+    pub fn hamming_distance(&self, other: &Observation) -> Result<usize, String> {
+        if self.num_values() != other.num_values() {
+            return Err(format!(
+                "Observations `{}` and `{}` have different numbers of values.",
+                self.id, other.id
+            ));
+        }
+        for obs in [self, other] {
+            if obs.num_unspecified_values() > 0 {
+                return Err(format!(
+                    "Observation `{}` contains unspecified values.",
+                    obs.id
+                ));
+            }
+        }
+        let distance = self
+            .values
+            .iter()
+            .zip(other.values.iter())
+            .filter(|(v1, v2)| v1 != v2)
+            .count();
+        Ok(distance)
+    }
+
     /// Make a string with bit-encoding of values of this `Observation`.
     /// Values are encoded using characters `1`, `0`, or `*`.
     pub fn to_values_string(&self) -> String {
@@ -286,6 +386,23 @@ mod tests {
         assert!(obs.value_at_idx(6).is_err());
     }
 
+    #[test]
+    /// Test computing Hamming distance of observations, including invalid cases.
+    fn test_hamming_distance() {
+        let obs1 = Observation::try_from_str("0011", "o1").unwrap();
+        let obs2 = Observation::try_from_str("1010", "o2").unwrap();
+        assert_eq!(obs1.hamming_distance(&obs2), Ok(2));
+        assert_eq!(obs2.hamming_distance(&obs1), Ok(2));
+        assert_eq!(obs1.hamming_distance(&obs1), Ok(0));
+
+        // unspecified values or different lengths are not allowed
+        let obs3 = Observation::try_from_str("00*1", "o3").unwrap();
+        let obs4 = Observation::try_from_str("001", "o4").unwrap();
+        assert!(obs1.hamming_distance(&obs3).is_err());
+        assert!(obs3.hamming_distance(&obs1).is_err());
+        assert!(obs1.hamming_distance(&obs4).is_err());
+    }
+
     #[test]
     /// Test setters (for ID and values).
     fn test_setters() {
@@ -300,6 +417,23 @@ mod tests {
         assert_eq!(obs.to_values_string().as_str(), "111111");
     }
 
+    #[test]
+    /// Test setting weights, including invalid values.
+    fn test_weights() {
+        let mut obs = Observation::try_from_str("10*", "o").unwrap();
+        assert!(!obs.has_weight());
+        assert_eq!(obs.get_weight(), 1.);
+
+        obs.set_weight(2.5).unwrap();
+        assert!(obs.has_weight());
+        assert_eq!(obs.get_weight(), 2.5);
+
+        assert!(obs.set_weight(-1.).is_err());
+        assert!(obs.set_weight(f64::NAN).is_err());
+        assert!(obs.clone().with_weight(f64::INFINITY).is_err());
+        assert_eq!(obs.get_weight(), 2.5);
+    }
+
     #[test]
     /// Test inserting and removing values.
     fn test_insert_remove_value() {
@@ -24,10 +24,9 @@ impl FromStr for VarValue {
 
     /// Try to parse the value. Valid strings are one of the `1`, `0`, or `*`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "1" => Ok(VarValue::True),
-            "0" => Ok(VarValue::False),
-            "*" => Ok(VarValue::Any),
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => VarValue::from_char(c),
             _ => Err("Invalid value string.".to_string()),
         }
     }
@@ -73,6 +72,22 @@ impl VarValue {
         *self != VarValue::Any
     }
 
+    /// Check whether two values are compatible, i.e., they are either equal, or at least one of
+    /// them is unspecified (an unspecified value matches anything, including unspecified value).
+    pub fn matches(&self, other: &VarValue) -> bool {
+        self.is_any() || other.is_any() || self == other
+    }
+
+    /// Try to parse the value from a character. Valid characters are `1`, `0`, or `*`.
+    pub fn from_char(c: char) -> Result<VarValue, String> {
+        match c {
+            '1' => Ok(VarValue::True),
+            '0' => Ok(VarValue::False),
+            '*' => Ok(VarValue::Any),
+            _ => Err(format!("Invalid value character `{c}`.")),
+        }
+    }
+
     /// Return Boolean if value is specified, else None.
     pub fn try_as_bool(&self) -> Option<bool> {
         match self {
@@ -91,3 +106,43 @@ impl VarValue {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::observations::VarValue;
+    use std::str::FromStr;
+
+    #[test]
+    /// Test the tri-state matching of values.
+    fn test_var_value_matches() {
+        let (one, zero, any) = (VarValue::True, VarValue::False, VarValue::Any);
+        assert!(one.matches(&one));
+        assert!(zero.matches(&zero));
+        assert!(!one.matches(&zero));
+        assert!(!zero.matches(&one));
+
+        // unspecified value matches anything, including another unspecified value
+        assert!(any.matches(&one));
+        assert!(one.matches(&any));
+        assert!(any.matches(&zero));
+        assert!(any.matches(&any));
+
+        assert!(one.is_fixed() && zero.is_fixed());
+        assert!(!any.is_fixed());
+    }
+
+    #[test]
+    /// Test parsing values from characters.
+    fn test_var_value_from_char() {
+        assert_eq!(VarValue::from_char('1').unwrap(), VarValue::True);
+        assert_eq!(VarValue::from_char('0').unwrap(), VarValue::False);
+        assert_eq!(VarValue::from_char('*').unwrap(), VarValue::Any);
+        assert!(VarValue::from_char('x').is_err());
+        assert!(VarValue::from_char(' ').is_err());
+
+        // parsing from strings is consistent with parsing from characters
+        assert_eq!(VarValue::from_str("*").unwrap(), VarValue::Any);
+        assert!(VarValue::from_str("10").is_err());
+        assert!(VarValue::from_str("").is_err());
+    }
+}
@@ -2,6 +2,8 @@ use crate::sketchbook::ids::DatasetId;
 
 /// **(internal)** Definition and methods for `Dataset`.
 mod _dataset;
+/// **(internal)** Definition of `DatasetOrdering`.
+mod _dataset_ordering;
 /// **(internal)** Definition and methods for `ObservationManager`.
 mod _manager;
 /// **(internal)** Definition and methods for `Observation`.
@@ -10,6 +12,7 @@ mod _observation;
 mod _var_value;
 
 pub use _dataset::Dataset;
+pub use _dataset_ordering::DatasetOrdering;
 pub use _manager::ObservationManager;
 pub use _observation::Observation;
 pub use _var_value::VarValue;
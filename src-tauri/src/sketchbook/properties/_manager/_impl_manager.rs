@@ -2,12 +2,14 @@ use crate::sketchbook::ids::{
     DatasetId, DynPropertyId, ObservationId, StatPropertyId, UninterpretedFnId, VarId,
 };
 use crate::sketchbook::model::{Essentiality, Monotonicity};
+use crate::sketchbook::observations::ObservationManager;
 use crate::sketchbook::properties::dynamic_props::are_same_dyn_variant;
 use crate::sketchbook::properties::static_props::{are_same_stat_variant, StatPropertyType};
 use crate::sketchbook::properties::{
     DynPropIterator, DynProperty, PropertyManager, StatPropIterator, StatProperty,
 };
-use std::collections::HashMap;
+use crate::sketchbook::utils::inverse_permutation;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
 /// Creating new instances of `PropertyManager`.
@@ -148,6 +150,18 @@ impl PropertyManager {
         prop.set_attr_count(minimal, maximal)
     }
 
+    /// Update dynamic property's sub-field `min_count`, if the property is of
+    /// `ObservationsReachable` variant. If not applicable, return `Err`.
+    pub fn set_dyn_obs_reachable_count(
+        &mut self,
+        id: &DynPropertyId,
+        min_count: usize,
+    ) -> Result<(), String> {
+        self.assert_valid_dynamic(id)?;
+        let prop = self.dyn_properties.get_mut(id).unwrap();
+        prop.set_obs_reachable_count(min_count)
+    }
+
     /// Update dynamic property's sub-fields, if the property is of `ExistsTrapSpace` variant.
     /// If not applicable, return `Err`.
     pub fn set_dyn_trap_space_details(
@@ -367,6 +381,107 @@ impl PropertyManager {
         Ok(())
     }
 
+    /// Update all properties referencing the given variable to reference the new variable ID
+    /// instead. This covers regulation properties (regulators and targets), as well as variables
+    /// referenced in formulas (HCTL propositions and FOL update function symbols).
+    ///
+    /// IDs of the generated regulation properties are not modified, use
+    /// [Self::make_generated_reg_prop_ids_consistent] afterwards to update them.
+    pub fn set_var_id_everywhere(&mut self, old_id: &VarId, new_id: &VarId) -> Result<(), String> {
+        for (prop_id, prop) in self.stat_properties.iter_mut() {
+            // properties that do not reference variables this way are simply skipped
+            let _ = prop.set_var_id_if_present(old_id.clone(), new_id.clone());
+            prop.rename_var_in_formulas(old_id, new_id)
+                .map_err(|e| format!("Can't update property `{prop_id}`: {e}"))?;
+        }
+        for (prop_id, prop) in self.dyn_properties.iter_mut() {
+            prop.rename_var_in_formula(old_id, new_id)
+                .map_err(|e| format!("Can't update property `{prop_id}`: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Update all properties referencing the given dataset to reference the new dataset ID
+    /// instead. This covers dynamic template properties and wild-card propositions in generic
+    /// HCTL formulas (see [DynProperty::rename_dataset]), as well as observation macros in
+    /// generic FOL formulas (see [StatProperty::rename_dataset]).
+    pub fn set_dataset_id_everywhere(
+        &mut self,
+        old_id: &DatasetId,
+        new_id: &DatasetId,
+    ) -> Result<(), String> {
+        for (prop_id, prop) in self.dyn_properties.iter_mut() {
+            prop.rename_dataset(old_id, new_id)
+                .map_err(|e| format!("Can't update property `{prop_id}`: {e}"))?;
+        }
+        for (prop_id, prop) in self.stat_properties.iter_mut() {
+            prop.rename_dataset(old_id, new_id)
+                .map_err(|e| format!("Can't update property `{prop_id}`: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Update all properties referencing arguments of the uninterpreted function `fn_id` after
+    /// its arguments were reordered according to the `permutation` (the `i`-th new argument
+    /// is the `permutation[i]`-th original argument). This covers the function input properties
+    /// (their argument indices), as well as applications of the function inside FOL formulas
+    /// (their argument lists, see [StatProperty::permute_fn_args_in_formulas]). IDs of the
+    /// generated function properties are made consistent afterwards.
+    pub fn permute_fn_args_everywhere(
+        &mut self,
+        fn_id: &UninterpretedFnId,
+        permutation: &[usize],
+    ) -> Result<(), String> {
+        let inverse = inverse_permutation(permutation);
+        for (prop_id, prop) in self.stat_properties.iter_mut() {
+            prop.permute_fn_args_in_formulas(fn_id, permutation)
+                .map_err(|e| format!("Can't update property `{prop_id}`: {e}"))?;
+            // properties relating two inputs have both their indices updated at once
+            if let StatPropertyType::FnInputsMutuallyExclusive(p) = prop.get_prop_data() {
+                if let (Some(target), Some(idx_1), Some(idx_2)) =
+                    (&p.target, p.input_index_1, p.input_index_2)
+                {
+                    if target == fn_id && idx_1 < inverse.len() && idx_2 < inverse.len() {
+                        prop.set_input_indices(inverse[idx_1], inverse[idx_2])?;
+                    }
+                }
+                continue;
+            }
+            // properties that do not reference function inputs are simply skipped
+            if let Ok((Some(target), Some(idx))) = prop.get_function_and_index() {
+                if &target == fn_id && idx < inverse.len() {
+                    prop.set_input_index(inverse[idx])?;
+                }
+            }
+        }
+        self.make_generated_fn_prop_ids_consistent()
+    }
+
+    /// Check that wild-card propositions in all dynamic properties resolve to datasets (and
+    /// observations) declared in `observations`. See [DynProperty::assert_wild_cards_resolvable].
+    ///
+    /// All unresolved wild-cards are collected, and the error lists them (one per line, each
+    /// prefixed with the ID of the property), sorted by the property IDs.
+    pub fn assert_wild_cards_resolvable(
+        &self,
+        observations: &ObservationManager,
+    ) -> Result<(), String> {
+        let mut issues: Vec<String> = self
+            .dyn_properties
+            .iter()
+            .filter_map(|(prop_id, prop)| {
+                prop.assert_wild_cards_resolvable(observations)
+                    .err()
+                    .map(|e| format!("Property `{prop_id}`: {e}"))
+            })
+            .collect();
+        if issues.is_empty() {
+            return Ok(());
+        }
+        issues.sort();
+        Err(issues.join("\n"))
+    }
+
     /// Go through all static properties that are automatically generated from the regulation
     /// graph and make their IDs consistent with the variables they reference.
     ///
@@ -404,11 +519,7 @@ impl PropertyManager {
             }
         }
         // and finally, set the IDs
-        for (current_id, new_id) in id_change_list {
-            self.set_stat_id(&current_id, new_id)
-                .map_err(|e| format!("Can't standardize ID for property `{current_id}`: {e}"))?;
-        }
-        Ok(())
+        self.set_stat_ids_simultaneously(id_change_list)
     }
 
     /// Go through all static properties that are automatically generated from the uninterpreted
@@ -448,10 +559,40 @@ impl PropertyManager {
             }
         }
         // and finally, set the IDs
-        for (current_id, new_id) in id_change_list {
-            self.set_stat_id(&current_id, new_id)
-                .map_err(|e| format!("Can't standardize ID for property `{current_id}`: {e}"))?;
+        self.set_stat_ids_simultaneously(id_change_list)
+    }
+
+    /// **(internal)** Change IDs of several static properties at once. All properties are first
+    /// removed and then re-inserted with their new IDs, so that the changes can form chains or
+    /// cycles (e.g., swapping IDs of two properties).
+    ///
+    /// If any of the new IDs is already taken (by a property that is not being renamed), return
+    /// `Err` and leave the properties unchanged.
+    fn set_stat_ids_simultaneously(
+        &mut self,
+        id_change_list: Vec<(StatPropertyId, StatPropertyId)>,
+    ) -> Result<(), String> {
+        let renamed_ids: HashSet<&StatPropertyId> =
+            id_change_list.iter().map(|(id, _)| id).collect();
+        let mut new_ids = HashSet::new();
+        for (current_id, new_id) in id_change_list.iter() {
+            let is_taken =
+                self.stat_properties.contains_key(new_id) && !renamed_ids.contains(new_id);
+            if is_taken || !new_ids.insert(new_id) {
+                return Err(format!(
+                    "Can't standardize ID for property `{current_id}`: ID `{new_id}` is already in use."
+                ));
+            }
         }
+
+        let removed_props: Vec<(StatPropertyId, StatProperty)> = id_change_list
+            .into_iter()
+            .map(|(current_id, new_id)| {
+                let prop = self.stat_properties.remove(&current_id).unwrap();
+                (new_id, prop)
+            })
+            .collect();
+        self.stat_properties.extend(removed_props);
         Ok(())
     }
 }
@@ -570,4 +711,132 @@ impl PropertyManager {
             .ok_or(format!("Static property with ID {id} does not exist."))?;
         Ok(stat_prop)
     }
+
+    /// Get (sorted) IDs of all dynamic properties referencing the given dataset. This covers
+    /// dataset fields of template properties and wild-card propositions in generic formulas
+    /// (see [DynProperty::references_dataset]).
+    pub fn dyn_properties_using_dataset(&self, id: &DatasetId) -> Vec<DynPropertyId> {
+        let mut prop_ids: Vec<DynPropertyId> = self
+            .dyn_properties
+            .iter()
+            .filter(|(_, prop)| prop.references_dataset(id))
+            .map(|(prop_id, _)| prop_id.clone())
+            .collect();
+        prop_ids.sort();
+        prop_ids
+    }
+
+    /// Get (sorted) IDs of all static properties referencing the given uninterpreted function.
+    /// This covers function input properties and function symbols in generic FOL formulas
+    /// (see [StatProperty::references_fn]).
+    pub fn stat_properties_using_fn(&self, fn_id: &UninterpretedFnId) -> Vec<StatPropertyId> {
+        let mut prop_ids: Vec<StatPropertyId> = self
+            .stat_properties
+            .iter()
+            .filter(|(_, prop)| prop.references_fn(fn_id))
+            .map(|(prop_id, _)| prop_id.clone())
+            .collect();
+        prop_ids.sort();
+        prop_ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::ids::{DatasetId, DynPropertyId, StatPropertyId, UninterpretedFnId};
+    use crate::sketchbook::model::Essentiality;
+    use crate::sketchbook::properties::{DynProperty, PropertyManager, StatProperty};
+
+    #[test]
+    /// Test that reordering function arguments updates both the argument indices of function
+    /// properties and the argument lists of function applications in FOL formulas.
+    fn test_permute_fn_args_everywhere() {
+        let f = UninterpretedFnId::new("f").unwrap();
+        let fn_prop =
+            StatProperty::mk_fn_input_essential("", Some(0), Some(f.clone()), Essentiality::True);
+        let fn_prop_id = StatProperty::get_fn_input_essentiality_prop_id(&f, 0);
+        let formula = "f(x, g(1), f(0, 1, y)) & ff(a, b)";
+        let stat_props = vec![
+            ("s1", StatProperty::try_mk_generic("", formula).unwrap()),
+            (fn_prop_id.as_str(), fn_prop),
+        ];
+        let mut manager = PropertyManager::new_from_properties(vec![], stat_props).unwrap();
+
+        // the new arguments are (third, first, second) of the original ones
+        manager.permute_fn_args_everywhere(&f, &[2, 0, 1]).unwrap();
+        let prop_id = StatPropertyId::new("s1").unwrap();
+        let expected_formula = "f(f(y, 0, 1), x, g(1)) & ff(a, b)";
+        let expected = StatProperty::try_mk_generic("", expected_formula).unwrap();
+        assert_eq!(manager.get_stat_prop(&prop_id).unwrap(), &expected);
+        let prop_id = StatProperty::get_fn_input_essentiality_prop_id(&f, 1);
+        let mut prop = manager.get_stat_prop(&prop_id).unwrap().clone();
+        assert_eq!(
+            prop.get_function_and_index(),
+            Ok((Some(f.clone()), Some(1)))
+        );
+
+        // applications with a different number of arguments can not be reordered
+        assert!(manager.permute_fn_args_everywhere(&f, &[1, 0]).is_err());
+    }
+
+    #[test]
+    /// Test finding properties referencing a dataset or an uninterpreted function.
+    fn test_find_dependent_properties() {
+        let d1 = DatasetId::new("d1").unwrap();
+        let d2 = DatasetId::new("d2").unwrap();
+        let dyn_props = vec![
+            ("p1", DynProperty::mk_trajectory("", Some(d1.clone()))),
+            (
+                "p2",
+                DynProperty::try_mk_generic("", "%fixed_points(d2, o)%").unwrap(),
+            ),
+            (
+                "p3",
+                DynProperty::try_mk_generic("", "%d1, o% & EF %d2, o%").unwrap(),
+            ),
+            (
+                "p4",
+                DynProperty::mk_reachable_from_dataset("", None, Some(d2.clone())),
+            ),
+            ("p5", DynProperty::try_mk_attractor_count("", 1, 2).unwrap()),
+        ];
+        let f = UninterpretedFnId::new("f").unwrap();
+        let fn_prop =
+            StatProperty::mk_fn_input_essential("", Some(0), Some(f.clone()), Essentiality::True);
+        let stat_props = vec![
+            (
+                "s1",
+                StatProperty::try_mk_generic("", "f(1) & @d1:o").unwrap(),
+            ),
+            (
+                "s2",
+                StatProperty::try_mk_generic("", "g(1) | f_A(0)").unwrap(),
+            ),
+            ("s3", fn_prop),
+        ];
+        let manager = PropertyManager::new_from_properties(dyn_props, stat_props).unwrap();
+
+        let to_dyn_ids = |ids: &[&str]| -> Vec<DynPropertyId> {
+            ids.iter().map(|i| DynPropertyId::new(i).unwrap()).collect()
+        };
+        let to_stat_ids = |ids: &[&str]| -> Vec<StatPropertyId> {
+            ids.iter()
+                .map(|i| StatPropertyId::new(i).unwrap())
+                .collect()
+        };
+        assert_eq!(
+            manager.dyn_properties_using_dataset(&d1),
+            to_dyn_ids(&["p1", "p3"])
+        );
+        assert_eq!(
+            manager.dyn_properties_using_dataset(&d2),
+            to_dyn_ids(&["p2", "p3", "p4"])
+        );
+        assert_eq!(
+            manager.stat_properties_using_fn(&f),
+            to_stat_ids(&["s1", "s3"])
+        );
+        let unknown = UninterpretedFnId::new("h").unwrap();
+        assert!(manager.stat_properties_using_fn(&unknown).is_empty());
+    }
 }
@@ -1,7 +1,9 @@
 use crate::app::event::Event;
 use crate::app::state::{Consumed, SessionHelper, SessionState};
 use crate::app::DynError;
-use crate::sketchbook::data_structs::{ChangeIdData, DynPropertyData, StatPropertyData};
+use crate::sketchbook::data_structs::{
+    ChangeIdData, DynPropertyData, ReorderArgsData, StatPropertyData,
+};
 use crate::sketchbook::event_utils::{
     make_refresh_event, make_reversible, mk_dyn_prop_event, mk_dyn_prop_state_change,
     mk_stat_prop_event, mk_stat_prop_state_change,
@@ -10,6 +12,7 @@ use crate::sketchbook::ids::{DynPropertyId, StatPropertyId, UninterpretedFnId, V
 use crate::sketchbook::properties::dynamic_props::SimpleDynPropertyType;
 use crate::sketchbook::properties::static_props::SimpleStatPropertyType;
 use crate::sketchbook::properties::{DynProperty, PropertyManager, StatProperty};
+use crate::sketchbook::utils::{assert_valid_permutation, inverse_permutation};
 use crate::sketchbook::JsonSerde;
 
 /* Constants for event path segments for various events. */
@@ -24,12 +27,16 @@ const ADD_PATH: &str = "add";
 const ADD_DEFAULT_PATH: &str = "add_default";
 // remove a property
 const REMOVE_PATH: &str = "remove";
+// duplicate a property (adding a copy under a fresh ID)
+const DUPLICATE_PATH: &str = "duplicate";
 // set ID of a property
 const SET_ID_PATH: &str = "set_id";
 // change variable ID in all static properties referencing that variable
 const SET_VAR_ID_EVERYWHERE_PATH: &str = "set_var_id_everywhere";
 // change function ID in all static properties referencing that function
 const SET_FN_ID_EVERYWHERE_PATH: &str = "set_fn_id_everywhere";
+// reorder function arguments in all static properties referencing that function
+const PERMUTE_FN_ARGS_EVERYWHERE_PATH: &str = "permute_fn_args_everywhere";
 // set content of a property
 const SET_CONTENT_PATH: &str = "set_content";
 // refresh all dynamic properties
@@ -148,6 +155,42 @@ impl SessionState for PropertyManager {
                     let reverse_event =
                         mk_stat_prop_event(&[SET_FN_ID_EVERYWHERE_PATH], Some(&payload));
 
+                    Ok(make_reversible(state_change, event, reverse_event))
+                } else if Self::starts_with(PERMUTE_FN_ARGS_EVERYWHERE_PATH, at_path).is_some() {
+                    Self::assert_path_length(at_path, 1, component_name)?;
+                    // get the payload - json string encoding the reordering data
+                    let payload = Self::clone_payload_str(event, component_name)?;
+                    let reorder_data = ReorderArgsData::from_json_str(&payload)?;
+                    let fn_id = UninterpretedFnId::new(&reorder_data.fn_id)?;
+                    assert_valid_permutation(
+                        &reorder_data.permutation,
+                        reorder_data.permutation.len(),
+                    )?;
+                    self.permute_fn_args_everywhere(&fn_id, &reorder_data.permutation)?;
+
+                    // the state change is just a list of all static properties
+                    let mut properties_list: Vec<StatPropertyData> = self
+                        .stat_properties
+                        .iter()
+                        .map(|(id, prop)| StatPropertyData::from_property(id, prop))
+                        .collect();
+                    properties_list.sort_by(|a, b| a.id.cmp(&b.id));
+                    let state_change = Event {
+                        path: vec![
+                            "sketch".to_string(),
+                            "properties".to_string(),
+                            "static".to_string(),
+                            "all_static_updated".to_string(),
+                        ],
+                        payload: Some(serde_json::to_string(&properties_list)?),
+                    };
+
+                    // prepare the reverse event (reordering by the inverse permutation)
+                    let inverse = inverse_permutation(&reorder_data.permutation);
+                    let payload = ReorderArgsData::new(fn_id.as_str(), &inverse).to_json_str();
+                    let reverse_event =
+                        mk_stat_prop_event(&[PERMUTE_FN_ARGS_EVERYWHERE_PATH], Some(&payload));
+
                     Ok(make_reversible(state_change, event, reverse_event))
                 } else {
                     Self::assert_path_length(at_path, 2, component_name)?;
@@ -260,6 +303,23 @@ impl PropertyManager {
             let payload = prop_data.to_json_str();
             let reverse_event = mk_dyn_prop_event(&["add"], Some(&payload));
             Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with(DUPLICATE_PATH, at_path).is_some() {
+            Self::assert_payload_empty(event, component_name)?;
+
+            // make a deep copy of the property, with a suffix added to its name
+            let mut new_property = self.get_dyn_prop(&prop_id)?.clone();
+            let new_name = format!("{} (copy)", new_property.get_name());
+            new_property.set_name(&new_name)?;
+            let new_id = self.generate_dyn_property_id(prop_id.as_str(), None);
+            let prop_data = DynPropertyData::from_property(&new_id, &new_property);
+
+            // actually add the property
+            self.add_dynamic(new_id, new_property)?;
+
+            // prepare the state-change (which is add event) and reverse event (which is a remove event)
+            let state_change = mk_dyn_prop_state_change(&["add"], &prop_data);
+            let reverse_event = mk_dyn_prop_event(&[&prop_data.id, "remove"], None);
+            Ok(make_reversible(state_change, event, reverse_event))
         } else if Self::starts_with(SET_ID_PATH, at_path).is_some() {
             // get the payload - string for "new_id"
             let new_id = Self::clone_payload_str(event, component_name)?;
@@ -367,6 +427,23 @@ impl PropertyManager {
             let payload = prop_data.to_json_str();
             let reverse_event = mk_stat_prop_event(&["add"], Some(&payload));
             Ok(make_reversible(state_change, event, reverse_event))
+        } else if Self::starts_with(DUPLICATE_PATH, at_path).is_some() {
+            Self::assert_payload_empty(event, component_name)?;
+
+            // make a deep copy of the property, with a suffix added to its name
+            let mut new_property = self.get_stat_prop(&prop_id)?.clone();
+            let new_name = format!("{} (copy)", new_property.get_name());
+            new_property.set_name(&new_name)?;
+            let new_id = self.generate_stat_property_id(prop_id.as_str(), None);
+            let prop_data = StatPropertyData::from_property(&new_id, &new_property);
+
+            // actually add the property
+            self.add_static(new_id, new_property)?;
+
+            // prepare the state-change (which is add event) and reverse event (which is a remove event)
+            let state_change = mk_stat_prop_state_change(&["add"], &prop_data);
+            let reverse_event = mk_stat_prop_event(&[&prop_data.id, "remove"], None);
+            Ok(make_reversible(state_change, event, reverse_event))
         } else if Self::starts_with(SET_ID_PATH, at_path).is_some() {
             // get the payload - string for "new_id"
             let new_id = Self::clone_payload_str(event, component_name)?;
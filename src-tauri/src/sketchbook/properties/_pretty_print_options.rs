@@ -0,0 +1,94 @@
+/// Options for rendering HCTL and FOL formulas in a human-readable form
+/// (see [crate::sketchbook::properties::HctlFormula::pretty_print] and
+/// [crate::sketchbook::properties::FirstOrderFormula::pretty_print]).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrettyPrintOptions {
+    /// Use Unicode operator symbols (like `∃`, `∀`, `∧`, `∨`) instead of the ASCII ones. Only
+    /// the ASCII output is guaranteed to be parsable again.
+    pub unicode: bool,
+    /// Wrap every compound sub-formula in parentheses. By default, only parentheses required
+    /// by the operator priorities are used.
+    pub explicit_parentheses: bool,
+}
+
+impl PrettyPrintOptions {
+    /// Options for rendering with Unicode operator symbols (and minimal parentheses).
+    pub fn unicode() -> PrettyPrintOptions {
+        PrettyPrintOptions {
+            unicode: true,
+            explicit_parentheses: false,
+        }
+    }
+
+    /// Builder-style method to switch explicit parenthesization on or off.
+    pub fn with_explicit_parentheses(mut self, explicit_parentheses: bool) -> PrettyPrintOptions {
+        self.explicit_parentheses = explicit_parentheses;
+        self
+    }
+
+    /// **(internal)** Recursively render the formula tree (shared by HCTL and FOL formulas).
+    ///
+    /// All binary operators are parsed as right-associative, so the left operand needs
+    /// parentheses already when its priority is the same. Quantifier-like operators cannot be
+    /// directly preceded by other operators, so they are always wrapped when nested in another
+    /// kind of operator (they have the weakest priority).
+    pub(super) fn print_tree<T: PrettyPrintTree>(&self, tree: &T) -> String {
+        let priority = tree.operator_priority();
+        let render_child = |child: &T, required: bool| {
+            let is_compound = child.operator_priority() > 0;
+            self.wrap(self.print_tree(child), is_compound, required)
+        };
+
+        match tree.pretty_node(self) {
+            PrettyNode::Atom(atom) => atom,
+            PrettyNode::Unary(op, child) => {
+                let child_str = render_child(child, child.operator_priority() > priority);
+                format!("{op}{child_str}")
+            }
+            PrettyNode::Binary(op, left, right) => {
+                let left_str = render_child(left, left.operator_priority() >= priority);
+                let right_str = render_child(right, right.operator_priority() > priority);
+                format!("{left_str} {op} {right_str}")
+            }
+            PrettyNode::Quantifier(prefix, child) => {
+                // quantifiers can be chained without parentheses
+                let child_str = render_child(child, false);
+                format!("{prefix}: {child_str}")
+            }
+        }
+    }
+
+    /// **(internal)** Wrap the rendered `sub_formula` in parentheses if it is `compound` and
+    /// either `required` by operator priorities, or explicit parentheses are requested.
+    fn wrap(&self, sub_formula: String, compound: bool, required: bool) -> String {
+        if compound && (required || self.explicit_parentheses) {
+            format!("({sub_formula})")
+        } else {
+            sub_formula
+        }
+    }
+}
+
+/// **(internal)** Top-level node of a formula tree, with operator symbols already rendered
+/// (see [PrettyPrintTree::pretty_node]).
+pub(super) enum PrettyNode<'a, T> {
+    /// Atomic formula, rendered as is.
+    Atom(String),
+    /// Prefix operator symbol (including a potential trailing space) and its operand.
+    Unary(String, &'a T),
+    /// Infix operator symbol and its two operands.
+    Binary(String, &'a T, &'a T),
+    /// Quantifier prefix (including the variable) and the quantified sub-formula.
+    Quantifier(String, &'a T),
+}
+
+/// **(internal)** Formula trees that can be rendered by [PrettyPrintOptions::print_tree].
+pub(super) trait PrettyPrintTree: Sized {
+    /// Priority of the top-level operator of the (sub)formula, as used by the corresponding
+    /// parser. Lower values bind stronger, atomic formulas have priority 0, and quantifiers
+    /// are the weakest.
+    fn operator_priority(&self) -> u8;
+
+    /// Decompose the top-level node of the tree, rendering its operator symbol.
+    fn pretty_node(&self, opts: &PrettyPrintOptions) -> PrettyNode<'_, Self>;
+}
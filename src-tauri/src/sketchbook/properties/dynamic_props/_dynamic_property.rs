@@ -1,7 +1,10 @@
-use super::_wild_card_props::process_wild_card_props;
-use crate::sketchbook::ids::{DatasetId, ObservationId};
+use super::_wild_card_props::{process_wild_card_props, rename_dataset_in_wild_cards};
+use crate::algorithms::eval_dynamic::processed_props::encode_dyn_prop_hctl_str;
+use crate::sketchbook::ids::{DatasetId, ObservationId, VarId};
+use crate::sketchbook::observations::ObservationManager;
 use crate::sketchbook::properties::dynamic_props::*;
-use crate::sketchbook::utils::assert_name_valid;
+use crate::sketchbook::utils::{assert_name_valid, rename_identifier};
+use crate::sketchbook::Sketch;
 use serde::{Deserialize, Serialize};
 
 /// A typesafe representation wrapping various kinds of dynamic properties.
@@ -67,6 +70,22 @@ impl DynProperty {
         Self::new_raw(name, variant)
     }
 
+    /// Create new `DynProperty` instance requiring that a given observation (or all observations
+    /// of a dataset, if `observation` is not specified) is a steady state. Dataset can be left
+    /// out, but it has to be specified before the inference. Annotation is left empty for now.
+    pub fn mk_steady_state(
+        name: &str,
+        dataset: Option<DatasetId>,
+        observation: Option<ObservationId>,
+    ) -> DynProperty {
+        let property = SteadyState {
+            dataset,
+            observation,
+        };
+        let variant = DynPropertyType::SteadyState(property);
+        Self::new_raw(name, variant)
+    }
+
     /// Create new `DynProperty` instance describing existence of a trap space corresponding
     /// to a given observation. Optionally, the trap space might be required to be minimal or
     /// non-percolable. Annotation is left empty for now.
@@ -130,6 +149,57 @@ impl DynProperty {
         Self::new_raw(name, variant)
     }
 
+    /// Create `DynProperty` instance requiring that at least `min_count` observations of a given
+    /// dataset are reachable. Annotation is left empty for now.
+    ///
+    /// The count must be larger than 0. Whether it does not exceed the dataset size can only be
+    /// checked once the dataset is known (during consistency checks).
+    pub fn try_mk_obs_reachable(
+        name: &str,
+        dataset: Option<DatasetId>,
+        min_count: usize,
+    ) -> Result<DynProperty, String> {
+        if min_count == 0 {
+            return Err("Number of reachable observations must be larger than 0.".to_string());
+        }
+        let property = ObservationsReachable { dataset, min_count };
+        let variant = DynPropertyType::ObservationsReachable(property);
+        Ok(Self::new_raw(name, variant))
+    }
+
+    /// Create `DynProperty` instance requiring that every observation of `target_dataset` is
+    /// reachable from some observation of `initial_dataset`. Annotation is left empty for now.
+    pub fn mk_reachable_from_dataset(
+        name: &str,
+        initial_dataset: Option<DatasetId>,
+        target_dataset: Option<DatasetId>,
+    ) -> DynProperty {
+        let property = ReachableFromDataset {
+            initial_dataset,
+            target_dataset,
+        };
+        let variant = DynPropertyType::ReachableFromDataset(property);
+        Self::new_raw(name, variant)
+    }
+
+    /// Create `DynProperty` instance requiring that an observation of a given dataset (or each
+    /// of its observations) is absent from the dynamics, in the sense given by `mode`.
+    /// Annotation is left empty for now.
+    pub fn mk_observation_absent(
+        name: &str,
+        dataset: Option<DatasetId>,
+        observation: Option<ObservationId>,
+        mode: AbsenceMode,
+    ) -> DynProperty {
+        let property = ObservationAbsent {
+            dataset,
+            observation,
+            mode,
+        };
+        let variant = DynPropertyType::ObservationAbsent(property);
+        Self::new_raw(name, variant)
+    }
+
     /// Create default `DynProperty` instance of specified variant.
     pub fn default(variant: SimpleDynPropertyType) -> DynProperty {
         match variant {
@@ -139,6 +209,10 @@ impl DynProperty {
             SimpleDynPropertyType::ExistsTrajectory => Self::default_trajectory(),
             SimpleDynPropertyType::AttractorCount => Self::default_attractor_count(),
             SimpleDynPropertyType::HasAttractor => Self::default_has_attractor(),
+            SimpleDynPropertyType::ObservationsReachable => Self::default_obs_reachable(),
+            SimpleDynPropertyType::SteadyState => Self::default_steady_state(),
+            SimpleDynPropertyType::ReachableFromDataset => Self::default_reachable_from_dataset(),
+            SimpleDynPropertyType::ObservationAbsent => Self::default_observation_absent(),
         }
     }
 
@@ -153,6 +227,12 @@ impl DynProperty {
         Self::mk_fixed_point("New exist fixed points property", None, None)
     }
 
+    /// Create default `DynProperty` instance for the steady state, with empty `dataset` and
+    /// `observation` fields.
+    pub fn default_steady_state() -> DynProperty {
+        Self::mk_steady_state("New steady state property", None, None)
+    }
+
     /// Create default `DynProperty` instance for the existence of a trap space, with empty
     /// `dataset` and `observation` fields, and all flags set to false.
     pub fn default_trap_space() -> DynProperty {
@@ -176,6 +256,25 @@ impl DynProperty {
     pub fn default_has_attractor() -> DynProperty {
         Self::mk_has_attractor("New exist attractors property", None, None)
     }
+
+    /// Create default `DynProperty` instance for the reachability of observations, with an empty
+    /// `dataset` field and default count being 1.
+    pub fn default_obs_reachable() -> DynProperty {
+        Self::try_mk_obs_reachable("New reachable observations property", None, 1).unwrap()
+    }
+
+    /// Create default `DynProperty` instance for the reachability between two datasets, with
+    /// empty `initial_dataset` and `target_dataset` fields.
+    pub fn default_reachable_from_dataset() -> DynProperty {
+        Self::mk_reachable_from_dataset("New reachable from dataset property", None, None)
+    }
+
+    /// Create default `DynProperty` instance for the absence of an observation, with empty
+    /// `dataset` and `observation` fields, and the `Unreachable` mode.
+    pub fn default_observation_absent() -> DynProperty {
+        let mode = AbsenceMode::Unreachable;
+        Self::mk_observation_absent("New absent observation property", None, None, mode)
+    }
 }
 
 /// Editing dynamic properties.
@@ -200,6 +299,9 @@ impl DynProperty {
             DynPropertyType::ExistsTrapSpace(prop) => prop.dataset = new_dataset,
             DynPropertyType::ExistsTrajectory(prop) => prop.dataset = new_dataset,
             DynPropertyType::HasAttractor(prop) => prop.dataset = new_dataset,
+            DynPropertyType::ObservationsReachable(prop) => prop.dataset = new_dataset,
+            DynPropertyType::SteadyState(prop) => prop.dataset = new_dataset,
+            DynPropertyType::ObservationAbsent(prop) => prop.dataset = new_dataset,
             // Other cases do not have a dataset field
             other_variant => {
                 return Err(format!(
@@ -217,6 +319,8 @@ impl DynProperty {
             DynPropertyType::ExistsFixedPoint(prop) => prop.observation = new_obs,
             DynPropertyType::ExistsTrapSpace(prop) => prop.observation = new_obs,
             DynPropertyType::HasAttractor(prop) => prop.observation = new_obs,
+            DynPropertyType::SteadyState(prop) => prop.observation = new_obs,
+            DynPropertyType::ObservationAbsent(prop) => prop.observation = new_obs,
             // Other cases do not have a observation field
             other_variant => {
                 return Err(format!(
@@ -245,6 +349,47 @@ impl DynProperty {
         }
     }
 
+    /// Rename all references to the given variable (atomic propositions) within the generic
+    /// property's formula. Wild-card propositions and HCTL state variables are left unchanged.
+    ///
+    /// This is applicable to all kinds of properties (if the property has no formula, nothing
+    /// changes). Returns `Err` if the modified formula cannot be processed.
+    pub fn rename_var_in_formula(&mut self, old_id: &VarId, new_id: &VarId) -> Result<(), String> {
+        if let DynPropertyType::GenericDynProp(prop) = &self.variant {
+            let new_formula =
+                rename_identifier(&prop.raw_formula, old_id.as_str(), new_id.as_str());
+            if new_formula != prop.raw_formula {
+                self.set_formula(&new_formula)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Rename all references to dataset `old_id` to `new_id`. This covers the `dataset` field
+    /// of template properties, as well as wild-card propositions in generic formulas.
+    ///
+    /// This is applicable to all kinds of properties (if the property does not reference the
+    /// dataset, nothing changes). Returns `Err` if the modified formula cannot be processed.
+    pub fn rename_dataset(&mut self, old_id: &DatasetId, new_id: &DatasetId) -> Result<(), String> {
+        if let DynPropertyType::GenericDynProp(prop) = &self.variant {
+            let new_formula = rename_dataset_in_wild_cards(&prop.raw_formula, old_id, new_id)?;
+            if new_formula != prop.raw_formula {
+                self.set_formula(&new_formula)?;
+            }
+        } else if let DynPropertyType::ReachableFromDataset(prop) = &mut self.variant {
+            for dataset in [&mut prop.initial_dataset, &mut prop.target_dataset] {
+                if dataset.as_ref() == Some(old_id) {
+                    *dataset = Some(new_id.clone());
+                }
+            }
+        } else if let Ok(Some(dataset_id)) = self.get_dataset() {
+            if &dataset_id == old_id {
+                self.set_dataset(new_id.clone())?;
+            }
+        }
+        Ok(())
+    }
+
     /// Update property's sub-field `observation` to None where applicable. If not applicable,
     /// return `Err`.
     pub fn remove_observation(&mut self) -> Result<(), String> {
@@ -274,6 +419,23 @@ impl DynProperty {
         }
     }
 
+    /// Update property's sub-field `min_count`, if the property is of `ObservationsReachable`
+    /// variant. The count must be larger than 0. If not applicable, return `Err`.
+    pub fn set_obs_reachable_count(&mut self, min_count: usize) -> Result<(), String> {
+        if let DynPropertyType::ObservationsReachable(prop) = &mut self.variant {
+            if min_count == 0 {
+                return Err("Number of reachable observations must be larger than 0.".to_string());
+            }
+            prop.min_count = min_count;
+            Ok(())
+        } else {
+            Err(format!(
+                "{:?} does not have a number of reachable observations to update.",
+                self.variant
+            ))
+        }
+    }
+
     /// Update property's sub-fields, if the property is of `ExistsTrapSpace` variant.
     /// If not applicable, return `Err`.
     pub fn set_trap_space_details(
@@ -319,6 +481,9 @@ impl DynProperty {
             DynPropertyType::ExistsTrapSpace(prop) => Ok(prop.dataset.clone()),
             DynPropertyType::ExistsTrajectory(prop) => Ok(prop.dataset.clone()),
             DynPropertyType::HasAttractor(prop) => Ok(prop.dataset.clone()),
+            DynPropertyType::ObservationsReachable(prop) => Ok(prop.dataset.clone()),
+            DynPropertyType::SteadyState(prop) => Ok(prop.dataset.clone()),
+            DynPropertyType::ObservationAbsent(prop) => Ok(prop.dataset.clone()),
             // Other cases do not have a dataset field
             other_variant => Err(format!(
                 "{other_variant:?} does not have a field `dataset`."
@@ -326,6 +491,69 @@ impl DynProperty {
         }
     }
 
+    /// Get all datasets referenced by the property's (filled) dataset fields. For most
+    /// template properties, this is just the `dataset` field. Datasets referenced by wild-card
+    /// propositions are not included.
+    pub fn get_all_datasets(&self) -> Vec<DatasetId> {
+        match &self.variant {
+            DynPropertyType::ReachableFromDataset(prop) => prop
+                .initial_dataset
+                .iter()
+                .chain(prop.target_dataset.iter())
+                .cloned()
+                .collect(),
+            _ => self.get_dataset().ok().flatten().into_iter().collect(),
+        }
+    }
+
+    /// Get all wild-card propositions referenced in the property's formula. Only generic
+    /// properties can contain wild-cards, for other variants the list is empty.
+    pub fn get_wild_cards(&self) -> Vec<&WildCardProposition> {
+        match &self.variant {
+            DynPropertyType::GenericDynProp(prop) => prop.wild_cards.iter().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Check whether the property references the given dataset, either via its dataset fields
+    /// (see [Self::get_all_datasets]) or via wild-card propositions in its formula (including
+    /// those referencing only a single observation of the dataset).
+    pub fn references_dataset(&self, dataset_id: &DatasetId) -> bool {
+        self.get_all_datasets().contains(dataset_id)
+            || self.get_wild_cards().iter().any(|wild_card| {
+                wild_card
+                    .referenced_data()
+                    .is_some_and(|(d, _)| d == dataset_id)
+            })
+    }
+
+    /// Check that all wild-card propositions referenced in the property's formula resolve
+    /// to a dataset (and observation) declared in `observations`.
+    ///
+    /// If some wild-card can not be resolved, return error naming it.
+    pub fn assert_wild_cards_resolvable(
+        &self,
+        observations: &ObservationManager,
+    ) -> Result<(), String> {
+        for wild_card in self.get_wild_cards() {
+            let Some((dataset_id, obs_id)) = wild_card.referenced_data() else {
+                continue;
+            };
+            let wild_card_str = wild_card.orig_string();
+            let dataset = observations.get_dataset(dataset_id).map_err(|_| {
+                format!("Wild-card `%{wild_card_str}%` references unknown dataset `{dataset_id}`.")
+            })?;
+            if let Some(obs_id) = obs_id {
+                if !dataset.is_valid_obs(obs_id) {
+                    return Err(format!(
+                        "Wild-card `%{wild_card_str}%` references unknown observation `{obs_id}` in dataset `{dataset_id}`."
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Check that the property has all required fields filled out. For most kinds of
     /// properties, this is just the `dataset` field in most cases at the moment.
     ///
@@ -353,13 +581,54 @@ impl DynProperty {
                     return Err(missing_field_msg.to_string());
                 }
             }
+            DynPropertyType::ObservationsReachable(p) => {
+                if p.dataset.is_none() {
+                    return Err(missing_field_msg.to_string());
+                }
+            }
+            DynPropertyType::SteadyState(p) => {
+                // only dataset has to be filled, observation ID is optional
+                if p.dataset.is_none() {
+                    return Err(missing_field_msg.to_string());
+                }
+            }
             DynPropertyType::ExistsTrapSpace(p) => {
                 // only dataset has to be filled, observation ID is optional
                 if p.dataset.is_none() {
                     return Err(missing_field_msg.to_string());
                 }
             }
+            DynPropertyType::ReachableFromDataset(p) => {
+                if p.initial_dataset.is_none() || p.target_dataset.is_none() {
+                    return Err(missing_field_msg.to_string());
+                }
+            }
+            DynPropertyType::ObservationAbsent(p) => {
+                // only dataset has to be filled, observation ID is optional
+                if p.dataset.is_none() {
+                    return Err(missing_field_msg.to_string());
+                }
+            }
         }
         Ok(())
     }
 }
+
+/// Expanding dynamic properties into formulas.
+impl DynProperty {
+    /// Expand this property into the HCTL formula it corresponds to during the inference, so
+    /// that it can be shown to the user. Template properties and wild-card propositions are
+    /// encoded via the standard HCTL templates (see [encode_dyn_prop_hctl_str]).
+    ///
+    /// The `sketch` provides the model and the datasets referenced by the property. Datasets are
+    /// pre-processed to match the model variables, the same way as before the inference (see
+    /// [Sketch::with_processed_datasets]). No symbolic computation is performed.
+    ///
+    /// Returns `Err` for properties evaluated by specialized algorithms that have no HCTL
+    /// encoding (such as attractor count).
+    pub fn to_hctl_formula(&self, sketch: &Sketch) -> Result<HctlFormula, String> {
+        let sketch = sketch.with_processed_datasets();
+        let formula = encode_dyn_prop_hctl_str(self, &sketch)?;
+        HctlFormula::try_from_str(&formula)
+    }
+}
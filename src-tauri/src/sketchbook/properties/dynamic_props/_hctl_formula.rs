@@ -1,5 +1,8 @@
 use crate::sketchbook::model::ModelState;
-use biodivine_hctl_model_checker::preprocessing::hctl_tree::HctlTreeNode;
+use crate::sketchbook::properties::_pretty_print_options::{PrettyNode, PrettyPrintTree};
+use crate::sketchbook::properties::PrettyPrintOptions;
+use biodivine_hctl_model_checker::preprocessing::hctl_tree::{HctlTreeNode, NodeType};
+use biodivine_hctl_model_checker::preprocessing::operator_enums::{BinaryOp, HybridOp, UnaryOp};
 use biodivine_hctl_model_checker::preprocessing::parser::{
     parse_and_minimize_extended_formula, parse_extended_formula,
 };
@@ -7,6 +10,11 @@ use biodivine_lib_param_bn::symbolic_async_graph::SymbolicContext;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 
+/// Maximal number of HCTL state variables a formula can require (see
+/// [HctlFormula::count_state_variables]). Each of these variables needs its own set of symbolic
+/// variables during evaluation, which quickly makes the computation intractable.
+pub const MAX_HCTL_STATE_VARS: usize = 6;
+
 /// A typesafe representation of a HCTL formula used in dynamic properties.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct HctlFormula {
@@ -91,6 +99,95 @@ impl HctlFormula {
     pub fn tree(&self) -> &HctlTreeNode {
         &self.tree
     }
+
+    /// Number of HCTL state variables needed to evaluate this formula, i.e., the maximal
+    /// nesting depth of quantifiers (bind, exists, forall).
+    ///
+    /// Variables of quantifiers that are not nested in each other can share the same symbolic
+    /// representation, so this can be lower than the number of distinct variable names.
+    pub fn count_state_variables(&self) -> usize {
+        count_quantifier_depth(&self.tree)
+    }
+
+    /// Render the formula in a human-readable form, using either Unicode or ASCII operator
+    /// symbols, and either minimal or fully explicit parentheses (see [PrettyPrintOptions]).
+    ///
+    /// The ASCII output can be parsed again into an equivalent formula.
+    pub fn pretty_print(&self, opts: &PrettyPrintOptions) -> String {
+        opts.print_tree(&self.tree)
+    }
+}
+
+impl PrettyPrintTree for HctlTreeNode {
+    /// Hybrid operators are the weakest, binary temporal operators bind stronger than the
+    /// Boolean ones.
+    fn operator_priority(&self) -> u8 {
+        match &self.node_type {
+            NodeType::Terminal(_) => 0,
+            NodeType::Unary(..) => 1,
+            NodeType::Binary(op, _, _) => match op {
+                BinaryOp::And => 3,
+                BinaryOp::Xor => 4,
+                BinaryOp::Or => 5,
+                BinaryOp::Imp => 6,
+                BinaryOp::Iff => 7,
+                // binary temporal operators
+                _ => 2,
+            },
+            NodeType::Hybrid(..) => 8,
+        }
+    }
+
+    fn pretty_node(&self, opts: &PrettyPrintOptions) -> PrettyNode<'_, Self> {
+        match &self.node_type {
+            NodeType::Terminal(atom) => PrettyNode::Atom(atom.to_string()),
+            NodeType::Unary(op, child) => {
+                let op_str = match (op, opts.unicode) {
+                    (UnaryOp::Not, true) => "¬".to_string(),
+                    (UnaryOp::Not, false) => op.to_string(),
+                    _ => format!("{op} "),
+                };
+                PrettyNode::Unary(op_str, child)
+            }
+            NodeType::Binary(op, left, right) => {
+                let op_str = match (op, opts.unicode) {
+                    (BinaryOp::And, true) => "∧".to_string(),
+                    (BinaryOp::Or, true) => "∨".to_string(),
+                    (BinaryOp::Xor, true) => "⊕".to_string(),
+                    (BinaryOp::Imp, true) => "⇒".to_string(),
+                    (BinaryOp::Iff, true) => "⇔".to_string(),
+                    _ => op.to_string(),
+                };
+                PrettyNode::Binary(op_str, left, right)
+            }
+            NodeType::Hybrid(op, var, domain, child) => {
+                let op_str = match (op, opts.unicode) {
+                    (HybridOp::Bind, true) => "↓".to_string(),
+                    (HybridOp::Exists, true) => "∃".to_string(),
+                    (HybridOp::Forall, true) => "∀".to_string(),
+                    _ => op.to_string(),
+                };
+                let domain_str = match domain {
+                    Some(domain) => format!(" in %{domain}%"),
+                    None => String::new(),
+                };
+                PrettyNode::Quantifier(format!("{op_str}{{{var}}}{domain_str}"), child)
+            }
+        }
+    }
+}
+
+/// **(internal)** Compute the maximal nesting depth of quantifiers in the formula tree.
+fn count_quantifier_depth(tree: &HctlTreeNode) -> usize {
+    match &tree.node_type {
+        NodeType::Terminal(_) => 0,
+        NodeType::Unary(_, child) => count_quantifier_depth(child),
+        NodeType::Binary(_, left, right) => {
+            usize::max(count_quantifier_depth(left), count_quantifier_depth(right))
+        }
+        NodeType::Hybrid(HybridOp::Jump, _, _, child) => count_quantifier_depth(child),
+        NodeType::Hybrid(_, _, _, child) => count_quantifier_depth(child) + 1,
+    }
 }
 
 /// Static methods (to check validity of formula strings).
@@ -134,3 +231,68 @@ impl HctlFormula {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::properties::{HctlFormula, PrettyPrintOptions};
+
+    #[test]
+    /// Test computing the number of state variables needed to evaluate a formula.
+    fn test_count_state_variables() {
+        let formula = HctlFormula::try_from_str("AG EF (a & b)").unwrap();
+        assert_eq!(formula.count_state_variables(), 0);
+
+        // jumps do not introduce new variables
+        let formula = HctlFormula::try_from_str("3{x}: @{x}: AX {x}").unwrap();
+        assert_eq!(formula.count_state_variables(), 1);
+
+        // non-nested quantifiers can reuse the same variables
+        let formula = HctlFormula::try_from_str("(3{x}: @{x}: a) & (V{y}: @{y}: b)").unwrap();
+        assert_eq!(formula.count_state_variables(), 1);
+
+        let formula =
+            HctlFormula::try_from_str("3{x}: 3{y}: (@{x}: ~{y} & (!{z}: AX {z}))").unwrap();
+        assert_eq!(formula.count_state_variables(), 3);
+    }
+
+    #[test]
+    /// Test rendering HCTL formulas with ASCII or Unicode symbols, and with minimal or
+    /// explicit parentheses.
+    fn test_pretty_print() {
+        let formula =
+            HctlFormula::try_from_str("3{x}: @{x}: (~a & (b | c)) => AF (!{y}: AX {y})").unwrap();
+
+        let ascii = PrettyPrintOptions::default();
+        let expected = "3{x}: @{x}: ~a & (b | c) => AF (!{y}: AX {y})";
+        assert_eq!(formula.pretty_print(&ascii), expected);
+
+        let unicode = PrettyPrintOptions::unicode();
+        let expected = "∃{x}: @{x}: ¬a ∧ (b ∨ c) ⇒ AF (↓{y}: AX {y})";
+        assert_eq!(formula.pretty_print(&unicode), expected);
+
+        let explicit = ascii.with_explicit_parentheses(true);
+        let expected = "3{x}: (@{x}: (((~a) & (b | c)) => (AF (!{y}: (AX {y})))))";
+        assert_eq!(formula.pretty_print(&explicit), expected);
+    }
+
+    #[test]
+    /// Test that pretty-printed HCTL formulas parse back into the same formula.
+    fn test_pretty_print_round_trip() {
+        let formulas = [
+            "(a & b) & c",
+            "a => (b => c)",
+            "(a => b) => c",
+            "~(a EU b) AU (c EW d)",
+            "(a <=> b) ^ ~~EX %p%",
+            "V{x} in %s%: 3{y}: (@{x}: {y} & True) | (!{z}: False)",
+        ];
+        for formula_str in formulas {
+            let formula = HctlFormula::try_from_str(formula_str).unwrap();
+            for explicit in [false, true] {
+                let opts = PrettyPrintOptions::default().with_explicit_parentheses(explicit);
+                let printed = formula.pretty_print(&opts);
+                assert_eq!(HctlFormula::try_from_str(&printed).unwrap(), formula);
+            }
+        }
+    }
+}
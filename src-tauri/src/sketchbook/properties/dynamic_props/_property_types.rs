@@ -31,6 +31,16 @@ pub struct ExistsFixedPoint {
     pub observation: Option<ObservationId>,
 }
 
+/// Variant of `DynProperty` requiring that a particular `observation` of a particular `dataset`
+/// (or all its observations) is a steady state, i.e., no update function flips the values of
+/// the observation. For partially specified observations, only the specified variables are
+/// constrained (in all states corresponding to the observation).
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct SteadyState {
+    pub dataset: Option<DatasetId>,
+    pub observation: Option<ObservationId>,
+}
+
 /// Variant of `DynProperty` requiring existence of a trap space corresponding to
 /// a particular `observation` of a particular `dataset`.
 /// Optionally, the required trap space might be required to be `minimal` or `non-percolable`.
@@ -67,6 +77,45 @@ pub struct HasAttractor {
     pub observation: Option<ObservationId>,
 }
 
+/// Variant of `DynProperty` requiring that at least `min_count` observations of a particular
+/// `dataset` are reachable, i.e., that there is a state from which each of these observations
+/// can be reached. This is useful for noisy data, where requiring all observations is too strict.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct ObservationsReachable {
+    pub dataset: Option<DatasetId>,
+    pub min_count: usize,
+}
+
+/// Variant of `DynProperty` requiring that every observation of the `target_dataset` is
+/// reachable from some observation of the `initial_dataset`. Partially specified observations
+/// correspond to sub-spaces (reaching or starting in any of their states is sufficient).
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct ReachableFromDataset {
+    pub initial_dataset: Option<DatasetId>,
+    pub target_dataset: Option<DatasetId>,
+}
+
+/// Mode of the `ObservationAbsent` property, specifying in which sense an observation must be
+/// absent from the dynamics.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum AbsenceMode {
+    /// The observation can not be reached from any state outside of it.
+    Unreachable,
+    /// The observation does not contain any attractor state.
+    NotAttractor,
+}
+
+/// Variant of `DynProperty` requiring that a particular `observation` of a particular `dataset`
+/// (or each of its observations) is absent from the dynamics. Depending on the `mode`, the
+/// observation must be either unreachable from all states outside of it, or it must not contain
+/// any attractor state. This is the complement of the reachability and attractor templates.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct ObservationAbsent {
+    pub dataset: Option<DatasetId>,
+    pub observation: Option<ObservationId>,
+    pub mode: AbsenceMode,
+}
+
 // Two versions of the enum to cover all variants of the dynamic properties.
 // One contains the property data inside, the other one only the discriminants.
 generate_property_enums!(
@@ -79,7 +128,11 @@ generate_property_enums!(
         ExistsTrapSpace(ExistsTrapSpace),
         ExistsTrajectory(ExistsTrajectory),
         AttractorCount(AttractorCount),
-        HasAttractor(HasAttractor)
+        HasAttractor(HasAttractor),
+        ObservationsReachable(ObservationsReachable),
+        SteadyState(SteadyState),
+        ReachableFromDataset(ReachableFromDataset),
+        ObservationAbsent(ObservationAbsent)
     }
 );
 
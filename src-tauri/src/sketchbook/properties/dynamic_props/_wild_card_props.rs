@@ -333,6 +333,57 @@ impl WildCardProposition {
     pub fn get_prop_data(&self) -> &WildCardType {
         &self.wild_card_type
     }
+
+    /// Create a copy of this wild-card proposition, with references to dataset `old_id` changed
+    /// to `new_id` (both in the data and the original string). If the wild-card does not
+    /// reference `old_id`, an unchanged copy is returned.
+    pub fn with_renamed_dataset(&self, old_id: &DatasetId, new_id: &DatasetId) -> Self {
+        match self.referenced_data() {
+            Some((dataset_id, _)) if dataset_id == old_id => {}
+            _ => return self.clone(),
+        }
+
+        // dataset is always the first argument (possibly after the opening parenthesis)
+        let args_start = self.orig_str.find('(').map(|i| i + 1).unwrap_or(0);
+        let args = &self.orig_str[args_start..];
+        let dataset_start = args_start + args.len() - args.trim_start().len();
+        let dataset_end = dataset_start + old_id.as_str().len();
+        let orig_str = format!(
+            "{}{new_id}{}",
+            &self.orig_str[..dataset_start],
+            &self.orig_str[dataset_end..]
+        );
+
+        let new_id = new_id.clone();
+        let wild_card_type = match &self.wild_card_type {
+            WildCardType::Observation(_, obs_id) => {
+                WildCardType::Observation(new_id, obs_id.clone())
+            }
+            WildCardType::Trajectory(_) => WildCardType::Trajectory(new_id),
+            WildCardType::Attractors(_, obs_id) => WildCardType::Attractors(new_id, obs_id.clone()),
+            WildCardType::FixedPoints(_, obs_id) => {
+                WildCardType::FixedPoints(new_id, obs_id.clone())
+            }
+            WildCardType::TrapSpaces(_, obs_id, is_min, is_non_perc) => {
+                WildCardType::TrapSpaces(new_id, obs_id.clone(), *is_min, *is_non_perc)
+            }
+            WildCardType::AttractorCount(..) => unreachable!(),
+        };
+        Self::new_raw(&orig_str, wild_card_type)
+    }
+
+    /// Get the dataset (and optionally the observation) this wild-card proposition references.
+    /// Returns `None` for wild-cards that do not reference any data (attractor count).
+    pub fn referenced_data(&self) -> Option<(&DatasetId, Option<&ObservationId>)> {
+        match &self.wild_card_type {
+            WildCardType::Observation(dat_id, obs_id) => Some((dat_id, Some(obs_id))),
+            WildCardType::Trajectory(dat_id) => Some((dat_id, None)),
+            WildCardType::Attractors(dat_id, obs_id)
+            | WildCardType::FixedPoints(dat_id, obs_id)
+            | WildCardType::TrapSpaces(dat_id, obs_id, _, _) => Some((dat_id, obs_id.as_ref())),
+            WildCardType::AttractorCount(..) => None,
+        }
+    }
 }
 
 /// Collect all wild-card proposition strings from the formula (all strings enclosed
@@ -368,6 +419,33 @@ pub fn process_wild_card_props(
     Ok((result, wild_cards))
 }
 
+/// Rename all references to dataset `old_id` within wild-card propositions of the formula to
+/// `new_id`. Other parts of the formula (including other wild-card propositions) are unchanged.
+pub fn rename_dataset_in_wild_cards(
+    formula: &str,
+    old_id: &DatasetId,
+    new_id: &DatasetId,
+) -> Result<String, String> {
+    let mut result = String::new();
+    let mut rest = formula;
+
+    while let Some(start) = rest.find('%') {
+        if let Some(end) = rest[start + 1..].find('%') {
+            let end = start + 1 + end;
+            result.push_str(&rest[..start]);
+            let wild_card = WildCardProposition::try_from_str(&rest[start + 1..end])?;
+            let renamed = wild_card.with_renamed_dataset(old_id, new_id);
+            result.push_str(&format!("%{}%", renamed.orig_string()));
+            rest = &rest[end + 1..];
+        } else {
+            return Err("Unmatched '%' in the formula".to_string());
+        }
+    }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -647,4 +725,23 @@ mod tests {
         let result = process_wild_card_props("AG %ds1-obs1%");
         assert!(result.is_err());
     }
+
+    #[test]
+    /// Test renaming a dataset in wild-card propositions, both in a formula and individually.
+    fn test_rename_dataset_in_wild_cards() {
+        let old_id = DatasetId::new("ds1").unwrap();
+        let new_id = DatasetId::new("data").unwrap();
+        let formula =
+            "%ds1, ds1% & %trajectory( ds1 )% & %fixed_points(ds10)% & %attractor_count(1, 2)%";
+        let renamed = rename_dataset_in_wild_cards(formula, &old_id, &new_id).unwrap();
+        assert_eq!(
+            renamed,
+            "%data, ds1% & %trajectory( data )% & %fixed_points(ds10)% & %attractor_count(1, 2)%"
+        );
+
+        let prop = WildCardProposition::try_from_str("min_trap_spaces(ds1, o)").unwrap();
+        let renamed = prop.with_renamed_dataset(&old_id, &new_id);
+        assert_eq!(renamed.orig_string(), "min_trap_spaces(data, o)");
+        assert_eq!(renamed.processed_string(), "min_trap_spaces_data_o");
+    }
 }
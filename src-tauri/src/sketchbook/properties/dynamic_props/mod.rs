@@ -8,6 +8,6 @@ mod _property_types;
 mod _wild_card_props;
 
 pub use _dynamic_property::DynProperty;
-pub use _hctl_formula::HctlFormula;
+pub use _hctl_formula::{HctlFormula, MAX_HCTL_STATE_VARS};
 pub use _property_types::*;
 pub use _wild_card_props::*;
@@ -2,6 +2,8 @@ use crate::sketchbook::ids::{DynPropertyId, StatPropertyId};
 
 /// **(internal)** Definition and methods for `PropertyManager`.
 mod _manager;
+/// **(internal)** Definition of `PrettyPrintOptions` used to render formulas.
+mod _pretty_print_options;
 /// Structs and utilities regarding dynamic properties.
 pub mod dynamic_props;
 /// Utilities and shortcuts for simpler property generating, mainly for tests.
@@ -12,6 +14,7 @@ pub mod static_props;
 mod _macros;
 
 pub use _manager::PropertyManager;
+pub use _pretty_print_options::PrettyPrintOptions;
 pub use dynamic_props::{DynProperty, HctlFormula};
 pub use static_props::{FirstOrderFormula, StatProperty};
 
@@ -1,7 +1,10 @@
-use crate::algorithms::fo_logic::fol_tree::FolTreeNode;
+use crate::algorithms::fo_logic::fol_tree::{FolTreeNode, NodeType};
+use crate::algorithms::fo_logic::operator_enums::{BinaryOp, Quantifier, UnaryOp};
 use crate::algorithms::fo_logic::parser::{parse_and_minimize_fol_formula, parse_fol_formula};
-use crate::algorithms::fo_logic::utils::get_var_from_implicit;
+use crate::algorithms::fo_logic::utils::find_invalid_fn_symbols;
 use crate::sketchbook::model::ModelState;
+use crate::sketchbook::properties::_pretty_print_options::{PrettyNode, PrettyPrintTree};
+use crate::sketchbook::properties::PrettyPrintOptions;
 
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
@@ -70,6 +73,72 @@ impl FirstOrderFormula {
     pub fn tree(&self) -> &FolTreeNode {
         &self.tree
     }
+
+    /// Render the formula in a human-readable form, using either Unicode or ASCII operator
+    /// symbols, and either minimal or fully explicit parentheses (see [PrettyPrintOptions]).
+    ///
+    /// The ASCII output can be parsed again into an equivalent formula.
+    pub fn pretty_print(&self, opts: &PrettyPrintOptions) -> String {
+        opts.print_tree(&self.tree)
+    }
+}
+
+impl PrettyPrintTree for FolTreeNode {
+    /// Quantifiers are the weakest, function applications are atomic.
+    fn operator_priority(&self) -> u8 {
+        match &self.node_type {
+            NodeType::Terminal(_) | NodeType::Function(..) => 0,
+            NodeType::Unary(..) => 1,
+            NodeType::Binary(op, _, _) => match op {
+                BinaryOp::And => 2,
+                BinaryOp::Xor => 3,
+                BinaryOp::Or => 4,
+                BinaryOp::Imp => 5,
+                BinaryOp::Iff => 6,
+            },
+            NodeType::Quantifier(..) => 7,
+        }
+    }
+
+    fn pretty_node(&self, opts: &PrettyPrintOptions) -> PrettyNode<'_, Self> {
+        match &self.node_type {
+            NodeType::Terminal(atom) => PrettyNode::Atom(atom.to_string()),
+            NodeType::Function(symbol, args) => {
+                // arguments are delimited by commas, they never need parentheses
+                let args_str: Vec<String> = args
+                    .iter()
+                    .map(|arg| opts.print_tree(arg.as_ref()))
+                    .collect();
+                PrettyNode::Atom(format!("{symbol}({})", args_str.join(", ")))
+            }
+            NodeType::Unary(op, child) => {
+                let op_str = match (op, opts.unicode) {
+                    (UnaryOp::Not, true) => "¬".to_string(),
+                    (UnaryOp::Not, false) => op.to_string(),
+                };
+                PrettyNode::Unary(op_str, child)
+            }
+            NodeType::Binary(op, left, right) => {
+                let op_str = match (op, opts.unicode) {
+                    (BinaryOp::And, true) => "∧".to_string(),
+                    (BinaryOp::Or, true) => "∨".to_string(),
+                    (BinaryOp::Xor, true) => "⊕".to_string(),
+                    (BinaryOp::Imp, true) => "⇒".to_string(),
+                    (BinaryOp::Iff, true) => "⇔".to_string(),
+                    (_, false) => op.to_string(),
+                };
+                PrettyNode::Binary(op_str, left, right)
+            }
+            NodeType::Quantifier(op, var, child) => {
+                let op_str = match (op, opts.unicode) {
+                    (Quantifier::Exists, true) => "∃".to_string(),
+                    (Quantifier::Forall, true) => "∀".to_string(),
+                    (_, false) => op.to_string(),
+                };
+                PrettyNode::Quantifier(format!("{op_str} {var}"), child)
+            }
+        }
+    }
 }
 
 /// Static methods (to check validity of formula strings).
@@ -92,41 +161,56 @@ impl FirstOrderFormula {
         let tree = parse_and_minimize_fol_formula(formula, "PLACEHOLDER")?;
 
         // Check if all used functions symbols are valid for the model. A function symbol is valid
-        // if it references an uninterpreted function or some update function.
-        let function_symbols = tree.collect_unique_fn_symbols()?;
-        for (fn_name, arity) in function_symbols.iter() {
-            // Check if the name corresponds to an (implicit) update function symbol for some variable
-            if let Ok(var) = get_var_from_implicit(fn_name) {
-                // If this is an update fn symbol, the corresponding variable must exist,
-                // and that the arity of the function is correct.
-                if let Ok(valid_var_id) = model.get_var_id(&var) {
-                    let update_fn_arity = model.regulators(&valid_var_id).unwrap().len(); // safe to unwrap
-                    if update_fn_arity != *arity {
-                        return Err(format!(
-                            "Update function symbol `{fn_name}` is used with incorrect arity."
-                        ));
-                    }
-                } else {
-                    return Err(format!(
-                        "There is no variable corresponding to update function `{fn_name}`."
-                    ));
-                }
-            } else {
-                // If this is not update fn symbol, it must be correspond to an uninterpreted function.
-                if let Ok(valid_fn_id) = model.get_uninterpreted_fn_id(fn_name) {
-                    let fn_arity = model.get_uninterpreted_fn_arity(&valid_fn_id).unwrap(); // safe to unwrap
-                    if fn_arity != *arity {
-                        return Err(format!(
-                            "Function symbol `{fn_name}` is used with incorrect arity."
-                        ));
-                    }
-                } else {
-                    return Err(format!(
-                        "Function `{fn_name}` with arity {arity} not found in the model."
-                    ));
-                }
-            }
+        // if it references an uninterpreted function or some update function. All the issues
+        // are reported at once.
+        let issues = find_invalid_fn_symbols(&tree, model);
+        if !issues.is_empty() {
+            return Err(issues.join(" "));
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::sketchbook::properties::{FirstOrderFormula, PrettyPrintOptions};
+
+    #[test]
+    /// Test rendering FOL formulas with ASCII or Unicode symbols, and with minimal or
+    /// explicit parentheses.
+    fn test_pretty_print() {
+        let formula =
+            FirstOrderFormula::try_from_str("\\exists x: V y: (!f(x, y) | 1) & g(x)").unwrap();
+
+        let ascii = PrettyPrintOptions::default();
+        let expected = "\\exists x: \\forall y: (!f(x, y) | 1) & g(x)";
+        assert_eq!(formula.pretty_print(&ascii), expected);
+
+        let unicode = PrettyPrintOptions::unicode();
+        let expected = "∃ x: ∀ y: (¬f(x, y) ∨ 1) ∧ g(x)";
+        assert_eq!(formula.pretty_print(&unicode), expected);
+
+        let explicit = ascii.with_explicit_parentheses(true);
+        let expected = "\\exists x: (\\forall y: (((!f(x, y)) | 1) & g(x)))";
+        assert_eq!(formula.pretty_print(&explicit), expected);
+    }
+
+    #[test]
+    /// Test that pretty-printed FOL formulas parse back into the same formula.
+    fn test_pretty_print_round_trip() {
+        let formulas = [
+            "(a & b) & c",
+            "(a => b) => !(c <=> d)",
+            "f(x) ^ (\\exists x: f(x)) ^ @d1:o1",
+            "V x, y: (3 z: f(x, z)) | f_A(y, 0)",
+        ];
+        for formula_str in formulas {
+            let formula = FirstOrderFormula::try_from_str(formula_str).unwrap();
+            for explicit in [false, true] {
+                let opts = PrettyPrintOptions::default().with_explicit_parentheses(explicit);
+                let printed = formula.pretty_print(&opts);
+                assert_eq!(FirstOrderFormula::try_from_str(&printed).unwrap(), formula);
+            }
+        }
+    }
+}
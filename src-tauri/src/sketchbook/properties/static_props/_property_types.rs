@@ -55,6 +55,15 @@ pub struct FnInputMonotonic {
     pub context: Option<String>,
 }
 
+/// Variant of `StatProperty` requiring that two inputs (on specified `input_index_1` and
+/// `input_index_2`) of a `target` function are not both essential at the same time.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct FnInputsMutuallyExclusive {
+    pub input_index_1: Option<usize>,
+    pub input_index_2: Option<usize>,
+    pub target: Option<UninterpretedFnId>,
+}
+
 // Two versions of the enum to cover all variants of the dynamic properties.
 // One contains the property data inside, the other one only the discriminants.
 generate_property_enums!(
@@ -66,6 +75,7 @@ generate_property_enums!(
         FnInputMonotonic(FnInputMonotonic),
         FnInputEssentialContext(FnInputEssential),
         FnInputMonotonicContext(FnInputMonotonic),
+        FnInputsMutuallyExclusive(FnInputsMutuallyExclusive),
         RegulationEssential(RegulationEssential),
         RegulationMonotonic(RegulationMonotonic),
         RegulationEssentialContext(RegulationEssential),
@@ -1,8 +1,14 @@
-use crate::sketchbook::ids::{StatPropertyId, UninterpretedFnId, VarId};
+use crate::algorithms::eval_static::processed_props::expand_static_prop_fol;
+use crate::algorithms::fo_logic::utils::get_implicit_function_name;
+use crate::sketchbook::ids::{DatasetId, StatPropertyId, UninterpretedFnId, VarId};
 use crate::sketchbook::model::{Essentiality, Monotonicity};
 use crate::sketchbook::properties::static_props::*;
 use crate::sketchbook::properties::FirstOrderFormula;
-use crate::sketchbook::utils::assert_name_valid;
+use crate::sketchbook::utils::{
+    assert_name_valid, permute_fn_args_in_formula, rename_dataset_in_observation_macros,
+    rename_identifier,
+};
+use crate::sketchbook::Sketch;
 use serde::{Deserialize, Serialize};
 
 /// A typesafe representation of various kinds of static properties.
@@ -195,6 +201,23 @@ impl StatProperty {
         Self::new_raw(name, variant)
     }
 
+    /// Create `StatProperty` instance describing that two inputs of an uninterpreted function
+    /// cannot be both essential. Annotation is left empty for now.
+    pub fn mk_fn_inputs_mutually_exclusive(
+        name: &str,
+        input_index_1: Option<usize>,
+        input_index_2: Option<usize>,
+        target: Option<UninterpretedFnId>,
+    ) -> StatProperty {
+        let property = FnInputsMutuallyExclusive {
+            input_index_1,
+            input_index_2,
+            target,
+        };
+        let variant = StatPropertyType::FnInputsMutuallyExclusive(property);
+        Self::new_raw(name, variant)
+    }
+
     /// Create default `StatProperty` instance of specified variant.
     pub fn default(variant: SimpleStatPropertyType) -> StatProperty {
         match variant {
@@ -215,6 +238,9 @@ impl StatProperty {
             SimpleStatPropertyType::FnInputMonotonicContext => {
                 Self::default_fn_input_monotonic_context()
             }
+            SimpleStatPropertyType::FnInputsMutuallyExclusive => {
+                Self::default_fn_inputs_mutually_exclusive()
+            }
         }
     }
 
@@ -314,6 +340,17 @@ impl StatProperty {
             "true".to_string(),
         )
     }
+
+    /// Create default `StatProperty` instance for mutually exclusive function inputs (with
+    /// empty `target` and both input index fields).
+    pub fn default_fn_inputs_mutually_exclusive() -> StatProperty {
+        Self::mk_fn_inputs_mutually_exclusive(
+            "New mutually exclusive fn inputs property",
+            None,
+            None,
+            None,
+        )
+    }
 }
 
 /// Editing static properties.
@@ -366,6 +403,24 @@ impl StatProperty {
         Ok(())
     }
 
+    /// Update property's sub-fields for both indices of inputs (of an uninterpreted fn), where
+    /// applicable. This is only relevant for properties relating two function inputs.
+    /// If not applicable, return `Err`.
+    pub fn set_input_indices(&mut self, new_idx_1: usize, new_idx_2: usize) -> Result<(), String> {
+        match &mut self.variant {
+            StatPropertyType::FnInputsMutuallyExclusive(prop) => {
+                prop.input_index_1 = Some(new_idx_1);
+                prop.input_index_2 = Some(new_idx_2);
+            }
+            other_variant => {
+                return Err(format!(
+                    "{other_variant:?} does not have fields for two input indices."
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Update property's sub-field for target uninterpreted fn, where applicable.
     /// If not applicable, return `Err`.
     pub fn set_target_fn(&mut self, new_target: UninterpretedFnId) -> Result<(), String> {
@@ -375,6 +430,7 @@ impl StatProperty {
             StatPropertyType::FnInputMonotonic(prop) => prop.target = new_target,
             StatPropertyType::FnInputEssentialContext(prop) => prop.target = new_target,
             StatPropertyType::FnInputMonotonicContext(prop) => prop.target = new_target,
+            StatPropertyType::FnInputsMutuallyExclusive(prop) => prop.target = new_target,
             other_variant => {
                 return Err(format!(
                     "{other_variant:?} does not have a field for target uninterpreted fn."
@@ -490,6 +546,86 @@ impl StatProperty {
         Ok(())
     }
 
+    /// Rename all references to the given variable within the property's formulas, i.e., the
+    /// implicit update function symbols (`f_VAR`) in the generic formula or the context formula.
+    ///
+    /// This is applicable to all kinds of properties (if the property has no formula, nothing
+    /// changes). Returns `Err` if the modified formula cannot be parsed.
+    pub fn rename_var_in_formulas(&mut self, old_id: &VarId, new_id: &VarId) -> Result<(), String> {
+        let old_fn = get_implicit_function_name(old_id.as_str());
+        let new_fn = get_implicit_function_name(new_id.as_str());
+        let context = match &mut self.variant {
+            StatPropertyType::GenericStatProp(prop) => {
+                let new_formula = rename_identifier(&prop.raw_formula, &old_fn, &new_fn);
+                if new_formula != prop.raw_formula {
+                    self.set_formula(&new_formula)?;
+                }
+                return Ok(());
+            }
+            StatPropertyType::FnInputEssentialContext(prop) => &mut prop.context,
+            StatPropertyType::FnInputMonotonicContext(prop) => &mut prop.context,
+            StatPropertyType::RegulationEssentialContext(prop) => &mut prop.context,
+            StatPropertyType::RegulationMonotonicContext(prop) => &mut prop.context,
+            _ => return Ok(()),
+        };
+        if let Some(context) = context {
+            *context = rename_identifier(context, &old_fn, &new_fn);
+        }
+        Ok(())
+    }
+
+    /// Reorder arguments of all applications of the given function within the property's
+    /// formulas (the generic formula or the context formula), according to the `permutation`
+    /// (the `i`-th new argument is the `permutation[i]`-th original argument).
+    ///
+    /// This is applicable to all kinds of properties (if the property has no formula, nothing
+    /// changes). Returns `Err` if some application does not match the permutation, or if the
+    /// modified formula cannot be parsed.
+    pub fn permute_fn_args_in_formulas(
+        &mut self,
+        fn_id: &UninterpretedFnId,
+        permutation: &[usize],
+    ) -> Result<(), String> {
+        let context = match &mut self.variant {
+            StatPropertyType::GenericStatProp(prop) => {
+                let new_formula =
+                    permute_fn_args_in_formula(&prop.raw_formula, fn_id.as_str(), permutation)?;
+                if new_formula != prop.raw_formula {
+                    self.set_formula(&new_formula)?;
+                }
+                return Ok(());
+            }
+            StatPropertyType::FnInputEssentialContext(prop) => &mut prop.context,
+            StatPropertyType::FnInputMonotonicContext(prop) => &mut prop.context,
+            StatPropertyType::RegulationEssentialContext(prop) => &mut prop.context,
+            StatPropertyType::RegulationMonotonicContext(prop) => &mut prop.context,
+            _ => return Ok(()),
+        };
+        if let Some(context) = context {
+            *context = permute_fn_args_in_formula(context, fn_id.as_str(), permutation)?;
+        }
+        Ok(())
+    }
+
+    /// Rename all references to the given dataset within observation macros of the property's
+    /// generic formula (`@dataset:observation`).
+    ///
+    /// This is applicable to all kinds of properties (if the property has no generic formula,
+    /// nothing changes). Returns `Err` if the modified formula cannot be parsed.
+    pub fn rename_dataset(&mut self, old_id: &DatasetId, new_id: &DatasetId) -> Result<(), String> {
+        if let StatPropertyType::GenericStatProp(prop) = &self.variant {
+            let new_formula = rename_dataset_in_observation_macros(
+                &prop.raw_formula,
+                old_id.as_str(),
+                new_id.as_str(),
+            );
+            if new_formula != prop.raw_formula {
+                self.set_formula(&new_formula)?;
+            }
+        }
+        Ok(())
+    }
+
     /// If the property is referencing the given function, set its ID to the new value.
     ///
     /// This is applicable to all kinds of unintepreted fn properties.
@@ -499,7 +635,10 @@ impl StatProperty {
         old_id: UninterpretedFnId,
         new_id: UninterpretedFnId,
     ) -> Result<(), String> {
-        let (function, _) = self.get_function_and_index()?;
+        let function = match &self.variant {
+            StatPropertyType::FnInputsMutuallyExclusive(prop) => prop.target.clone(),
+            _ => self.get_function_and_index()?.0,
+        };
         if let Some(fn_id) = function {
             if fn_id == old_id {
                 self.set_target_fn(new_id)?;
@@ -526,6 +665,49 @@ impl StatProperty {
         &self.variant
     }
 
+    /// Check whether the property references the given uninterpreted function, either as
+    /// a target of a function property, or via a function symbol in its FOL formula.
+    pub fn references_fn(&self, fn_id: &UninterpretedFnId) -> bool {
+        match &self.variant {
+            StatPropertyType::GenericStatProp(prop) => prop
+                .processed_formula
+                .tree()
+                .collect_unique_fn_symbols()
+                .is_ok_and(|fn_symbols| fn_symbols.contains_key(fn_id.as_str())),
+            StatPropertyType::FnInputEssential(prop)
+            | StatPropertyType::FnInputEssentialContext(prop) => {
+                prop.target.as_ref() == Some(fn_id)
+            }
+            StatPropertyType::FnInputMonotonic(prop)
+            | StatPropertyType::FnInputMonotonicContext(prop) => {
+                prop.target.as_ref() == Some(fn_id)
+            }
+            StatPropertyType::FnInputsMutuallyExclusive(prop) => {
+                prop.target.as_ref() == Some(fn_id)
+            }
+            _ => false,
+        }
+    }
+
+    /// Get all datasets referenced by observation macros (`@dataset:observation`) in the
+    /// property's formula. Only generic properties can contain macros, for other variants
+    /// the list is empty.
+    pub fn get_all_datasets(&self) -> Vec<DatasetId> {
+        let StatPropertyType::GenericStatProp(prop) = &self.variant else {
+            return Vec::new();
+        };
+        let mut datasets: Vec<DatasetId> = prop
+            .processed_formula
+            .tree()
+            .collect_observation_macros()
+            .into_iter()
+            .filter_map(|(dataset_id, _)| DatasetId::new(&dataset_id).ok())
+            .collect();
+        datasets.sort();
+        datasets.dedup();
+        datasets
+    }
+
     /// Check that the property has all required fields filled out.
     /// If some of the required field is set to None, return error.
     pub fn assert_fully_filled(&self) -> Result<(), String> {
@@ -545,6 +727,11 @@ impl StatProperty {
                     return Err(missing_field_msg.to_string());
                 }
             }
+            StatPropertyType::FnInputsMutuallyExclusive(p) => {
+                if p.input_index_1.is_none() || p.input_index_2.is_none() || p.target.is_none() {
+                    return Err(missing_field_msg.to_string());
+                }
+            }
             StatPropertyType::RegulationEssential(p)
             | StatPropertyType::RegulationEssentialContext(p) => {
                 if p.input.is_none() || p.target.is_none() {
@@ -608,6 +795,22 @@ impl StatProperty {
     }
 }
 
+/// Expanding static properties into formulas.
+impl StatProperty {
+    /// Expand this property into the FOL formula it corresponds to during the inference, so
+    /// that it can be shown to the user. Templates are encoded into FOL (including the context
+    /// formula) and observation macros are expanded (see [expand_static_prop_fol]).
+    ///
+    /// The `sketch` provides the model and the datasets referenced by the property. Datasets are
+    /// pre-processed to match the model variables, the same way as before the inference (see
+    /// [Sketch::with_processed_datasets]). No symbolic computation is performed.
+    pub fn to_fol_formula(&self, sketch: &Sketch) -> Result<FirstOrderFormula, String> {
+        let sketch = sketch.with_processed_datasets();
+        let tree = expand_static_prop_fol(self, &sketch)?;
+        FirstOrderFormula::try_from_str(&tree.to_string())
+    }
+}
+
 /// Static methods to create standard IDs for automatically generated static properties.
 impl StatProperty {
     /// Get ID of a static property that describes monotonicity of a regulation
@@ -1,8 +1,15 @@
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::io::{Read, Write};
 use std::str::FromStr;
 
+/// Magic bytes at the start of every gzip-compressed file.
+const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+
 /// Check if a name string is valid, return Error otherwise.
 ///
 /// Currently, all names that do not contain newlines are valid.
@@ -22,6 +29,191 @@ pub(crate) fn assert_ids_unique<T: Eq + Hash + Debug>(id_list: &Vec<T>) -> Resul
     Ok(())
 }
 
+/// Rename all occurrences of identifier `old_id` to `new_id` in a formula string, keeping the
+/// rest of the formula (including its formatting) intact.
+///
+/// Only whole identifiers (maximal sequences of alphanumeric characters and underscores) are
+/// renamed. Parts of the formula enclosed in `%...%` (wild-card propositions) or `{...}` (HCTL
+/// state variables) are left untouched, as these never reference network variables directly.
+pub(crate) fn rename_identifier(formula: &str, old_id: &str, new_id: &str) -> String {
+    let mut result = String::with_capacity(formula.len());
+    let mut current_word = String::new();
+    let mut in_wild_card = false;
+    let mut in_braces = false;
+
+    let flush_word = |word: &mut String, result: &mut String, skip: bool| {
+        if !skip && word.as_str() == old_id {
+            result.push_str(new_id);
+        } else {
+            result.push_str(word);
+        }
+        word.clear();
+    };
+
+    for c in formula.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            current_word.push(c);
+            continue;
+        }
+        flush_word(&mut current_word, &mut result, in_wild_card || in_braces);
+        match c {
+            '%' => in_wild_card = !in_wild_card,
+            '{' => in_braces = true,
+            '}' => in_braces = false,
+            _ => {}
+        }
+        result.push(c);
+    }
+    flush_word(&mut current_word, &mut result, in_wild_card || in_braces);
+    result
+}
+
+/// Rename dataset `old_id` to `new_id` in all observation macros (`@dataset:observation`) of
+/// a FOL formula string, keeping the rest of the formula (including its formatting) intact.
+pub(crate) fn rename_dataset_in_observation_macros(
+    formula: &str,
+    old_id: &str,
+    new_id: &str,
+) -> String {
+    let mut result = String::with_capacity(formula.len());
+    let mut rest = formula;
+    while let Some(start) = rest.find('@') {
+        result.push_str(&rest[..=start]);
+        rest = &rest[start + 1..];
+        let name_len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let (name, after) = rest.split_at(name_len);
+        if name == old_id && after.starts_with(':') {
+            result.push_str(new_id);
+        } else {
+            result.push_str(name);
+        }
+        rest = after;
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Reorder arguments of all applications of function `fn_id` in a FOL formula string according
+/// to the `permutation` (the `i`-th new argument is the `permutation[i]`-th original argument).
+/// Nested applications are reordered as well, and the rest of the formula (including its
+/// formatting) is kept intact.
+///
+/// Returns `Err` if some application of `fn_id` has unmatched parentheses, or its number of
+/// arguments does not match the permutation.
+pub(crate) fn permute_fn_args_in_formula(
+    formula: &str,
+    fn_id: &str,
+    permutation: &[usize],
+) -> Result<String, String> {
+    let is_name_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut result = String::with_capacity(formula.len());
+    let mut rest = formula;
+    while let Some(start) = rest.find(is_name_char) {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let name_len = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+        let (name, after) = rest.split_at(name_len);
+        result.push_str(name);
+        rest = after;
+
+        let after_whitespace = after.trim_start();
+        if name != fn_id || !after_whitespace.starts_with('(') {
+            continue;
+        }
+        result.push_str(&after[..after.len() - after_whitespace.len()]);
+        let (args, remaining) = split_fn_arguments(&after_whitespace[1..])?;
+        if args.len() != permutation.len() {
+            return Err(format!(
+                "Function `{fn_id}` is applied to {} arguments, but expected {}.",
+                args.len(),
+                permutation.len()
+            ));
+        }
+        let args = args
+            .iter()
+            .map(|arg| permute_fn_args_in_formula(arg, fn_id, permutation))
+            .collect::<Result<Vec<String>, String>>()?;
+        // each argument slot keeps its surrounding whitespace, only the content is moved
+        let permuted_args: Vec<String> = args
+            .iter()
+            .zip(permutation)
+            .map(|(slot, original_idx)| {
+                let leading_len = slot.len() - slot.trim_start().len();
+                let trailing_len = slot.len() - slot.trim_end().len();
+                let leading = &slot[..leading_len];
+                let trailing = &slot[slot.len() - trailing_len..];
+                format!("{leading}{}{trailing}", args[*original_idx].trim())
+            })
+            .collect();
+        result.push('(');
+        result.push_str(&permuted_args.join(","));
+        result.push(')');
+        rest = remaining;
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// **(internal)** Split the arguments of a function application, given the part of the formula
+/// right after the opening parenthesis. Returns the list of (unprocessed) arguments, and the
+/// rest of the formula after the closing parenthesis.
+fn split_fn_arguments(input: &str) -> Result<(Vec<&str>, &str), String> {
+    let mut args = Vec::new();
+    let mut depth = 0;
+    let mut arg_start = 0;
+    for (idx, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth > 0 => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(&input[arg_start..idx]);
+                arg_start = idx + 1;
+            }
+            ')' => {
+                let last_arg = &input[arg_start..idx];
+                // function without arguments (e.g., `f()`)
+                if !(args.is_empty() && last_arg.trim().is_empty()) {
+                    args.push(last_arg);
+                }
+                return Ok((args, &input[idx + 1..]));
+            }
+            _ => {}
+        }
+    }
+    Err("Unmatched '(' in a function application.".to_string())
+}
+
+/// Check that `permutation` is a valid permutation of indices `0..len`.
+pub(crate) fn assert_valid_permutation(permutation: &[usize], len: usize) -> Result<(), String> {
+    let mut seen = vec![false; len];
+    if permutation.len() != len {
+        return Err(format!(
+            "Permutation {permutation:?} must contain exactly {len} indices."
+        ));
+    }
+    for &idx in permutation {
+        if idx >= len || seen[idx] {
+            return Err(format!(
+                "Permutation {permutation:?} is not a valid permutation of 0..{len}."
+            ));
+        }
+        seen[idx] = true;
+    }
+    Ok(())
+}
+
+/// Compute the inverse of a (valid) `permutation`, i.e., a permutation `inverse` such that
+/// `inverse[permutation[i]] == i` for every index `i`.
+pub(crate) fn inverse_permutation(permutation: &[usize]) -> Vec<usize> {
+    let mut inverse = vec![0; permutation.len()];
+    for (i, &idx) in permutation.iter().enumerate() {
+        inverse[idx] = i;
+    }
+    inverse
+}
+
 /// Convert keys of the `HashMap` to `String`, and then order the map by converting it into
 /// a sorted `BTreeMap`.
 ///
@@ -49,3 +241,35 @@ where
         .collect::<Result<HashMap<K, V>, _>>()?;
     Ok(transformed_map)
 }
+
+/// Decode contents of a (possibly gzip-compressed) text file into a string.
+///
+/// The data is decompressed if they start with the gzip magic bytes, or if `gz_hint` is set
+/// (e.g., for files with the `.gz` extension). Otherwise, they are read as plain UTF-8 text.
+pub(crate) fn decode_maybe_gzipped(data: &[u8], gz_hint: bool) -> Result<String, String> {
+    if gz_hint || data.starts_with(&GZIP_MAGIC_BYTES) {
+        let mut content = String::new();
+        GzDecoder::new(data)
+            .read_to_string(&mut content)
+            .map_err(|e| format!("Cannot decompress gzip data: {e}"))?;
+        Ok(content)
+    } else {
+        String::from_utf8(data.to_vec()).map_err(|e| e.to_string())
+    }
+}
+
+/// Read a (possibly gzip-compressed) text file into a string. See [decode_maybe_gzipped] for
+/// details, the `.gz` extension of the path is used as a hint.
+pub(crate) fn read_maybe_gzipped_file(path: &str) -> Result<String, String> {
+    let data = std::fs::read(path).map_err(|e| e.to_string())?;
+    decode_maybe_gzipped(&data, path.ends_with(".gz"))
+}
+
+/// Compress the string using gzip (with the default compression level).
+pub(crate) fn gzip_compress(content: &str) -> Result<Vec<u8>, String> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(content.as_bytes())
+        .map_err(|e| e.to_string())?;
+    encoder.finish().map_err(|e| e.to_string())
+}
@@ -11,12 +11,18 @@ import {
   type DynamicProperty,
   type StaticProperty,
   type DynamicPropertyType,
-  type StaticPropertyType
+  type StaticPropertyType,
+  type DatasetOrdering
 } from './html/util/data-interfaces'
 
 import {
   type InferenceStatusReport,
-  type InferenceResults
+  type InferenceResults,
+  type PropertyTimeoutConfig,
+  type RunsComparison,
+  type CandidateAttractors,
+  type CandidateAssignment,
+  type CandidateRanking
 } from './html/util/analysis-interfaces'
 
 /** An object representing all relevant parts of the whole sketch. */
@@ -26,6 +32,7 @@ export interface SketchData {
   dyn_properties: DynamicProperty[]
   stat_properties: StaticProperty[]
   annotation: string
+  initial_subspace?: Record<string, boolean> // no restriction if not specified
 }
 
 /** An object representing all relevant parts of a model. */
@@ -42,6 +49,51 @@ export interface VariableData {
   name: string
   annotation: string
   update_fn: string
+  /** Whether the update function is frozen (treated as fixed during inference). */
+  frozen?: boolean
+  /** Whether the variable is an input (treated as a free constant during inference). */
+  is_input?: boolean
+}
+
+/** Summary of how many signs and essentialities of regulations and function arguments are still unknown. */
+export interface CompletenessReport {
+  num_regulations: number
+  num_regulations_unknown_sign: number
+  num_regulations_unknown_essentiality: number
+  num_fn_args: number
+  num_fn_args_unknown_sign: number
+  num_fn_args_unknown_essentiality: number
+  /** Percentage of all signs and essentialities that are specified. */
+  percent_specified: number
+}
+
+/** Aggregate statistics about all components of a sketch. Maps only contain non-zero counts. */
+export interface SketchStats {
+  num_variables: number
+  num_regulations: number
+  /** Numbers of regulations by their sign (monotonicity). */
+  regulations_by_sign: Partial<Record<Monotonicity, number>>
+  num_uninterpreted_fns: number
+  /** Numbers of uninterpreted functions by their arity. */
+  fns_by_arity: Record<number, number>
+  num_datasets: number
+  /** Numbers of observations by dataset ID. */
+  observations_by_dataset: Record<string, number>
+  num_dyn_properties: number
+  /** Numbers of dynamic properties by their template kind. */
+  dyn_properties_by_kind: Partial<Record<DynamicPropertyType, number>>
+  num_stat_properties: number
+  /** Numbers of static properties by their template kind. */
+  stat_properties_by_kind: Partial<Record<StaticPropertyType, number>>
+  num_free_params: number
+}
+
+/** Configuration of the periodic autosave of the edited sketch. */
+export interface AutosaveConfig {
+  /** Directory for the autosave files (autosave is disabled if `null`). */
+  directory: string | null
+  /** Minimal number of seconds between two consecutive autosaves. */
+  interval_secs: number
 }
 
 /** An object representing basic information regarding a model's uninterpreted function. */
@@ -59,6 +111,7 @@ export interface RegulationData {
   target: string
   sign: Monotonicity
   essential: Essentiality
+  annotation: string
 }
 
 /** An object representing basic information regarding a model layout. */
@@ -93,6 +146,8 @@ export interface ObservationData {
   annotation: string
   dataset: string
   values: string // string with `0`/`1`/`*`, for instance: "0001**110"
+  weight?: number | null // weight for fuzzy reachability scoring (1.0 if not specified)
+  metadata?: string | null // provenance of the observation (e.g., experiment ID or date)
 }
 
 /** An object representing all information regarding a whole dataset. */
@@ -102,6 +157,7 @@ export interface DatasetData {
   annotation: string
   observations: ObservationData[]
   variables: string[]
+  ordering?: DatasetOrdering // time series if not specified
 }
 
 /**
@@ -113,6 +169,7 @@ export interface DatasetMetaData {
   name: string
   annotation: string
   variables: string[]
+  ordering?: DatasetOrdering // time series if not specified
 }
 
 /** An object representing information needed for loading a dataset. */
@@ -153,6 +210,10 @@ interface AeonState {
     undo: () => void
     /** Try to redo an action. Emits an error if no actions can be redone. */
     redo: () => void
+    /** Start a group of actions that are undone/redone in a single step (groups can be nested). */
+    beginGroup: () => void
+    /** Close the most recently started group of actions. */
+    endGroup: () => void
   }
 
   /** The state of the main navigation tab-bar. */
@@ -195,14 +256,33 @@ interface AeonState {
     setAnnotation: (annotation: string) => void
     /** Annotation of the whole sketch was changed. */
     annotationChanged: Observable<string>
+    /** Set initial subspace (values of some variables) restricting initial states of reachability
+     * properties. Empty object removes the restriction. */
+    setInitialSubspace: (subspace: Record<string, boolean>) => void
+    /** Initial subspace of the sketch was changed. */
+    initialSubspaceChanged: Observable<Record<string, boolean>>
     /** Run the explicit consistency check on the sketch. */
     checkConsistency: () => void
     /** Results of an explicit consistency check (a summary message). */
     consistencyResults: Observable<string>
+    /** Set whether issues regarding declared regulators of variables are reported as warnings
+     * or errors (blocking the inference). */
+    setRegulatorCheckSeverity: (severity: 'Warning' | 'Error') => void
+    /** Severity of issues regarding declared regulators of variables was changed. */
+    regulatorCheckSeverityChanged: Observable<'Warning' | 'Error'>
     /** Fetch the number of parameters of the PSBN component. */
     fetchNumPSBNParams: () => void
     /** Number of parameters of the PSBN component. */
     numPSBNParamsFetched: Observable<number>
+    /** Number of free parameters (function table entries not fixed by the monotonicity and
+     * essentiality of regulations). */
+    numFreeParamsRefreshed: Observable<number>
+    /** Refresh the number of free parameters. */
+    refreshNumFreeParams: () => void
+    /** Aggregate statistics of all sketch components. */
+    statisticsRefreshed: Observable<SketchStats>
+    /** Refresh the aggregate statistics of all sketch components. */
+    refreshStatistics: () => void
 
     /** The state of the main model. */
     model: {
@@ -232,6 +312,10 @@ interface AeonState {
       layoutNodesRefreshed: Observable<LayoutNodeData[]>
       /** Refresh the nodes in a given layout. */
       refreshLayoutNodes: (layoutId: string) => void
+      /** Summary of unknown signs and essentialities in the model. */
+      completenessReportRefreshed: Observable<CompletenessReport>
+      /** Refresh the summary of unknown signs and essentialities. */
+      refreshCompletenessReport: () => void
 
       /** Variable-related setter events: */
 
@@ -258,6 +342,20 @@ interface AeonState {
       variableUpdateFnChanged: Observable<VariableData>
       /** Set an expression of update function for variable with given ID. */
       setVariableUpdateFn: (varId: string, newExpression: string) => void
+      /** Set update function for variable with given ID to a constant (`true` or `false`).
+       * The result is reported via `variableUpdateFnChanged`. */
+      setVariableUpdateFnConstant: (varId: string, value: boolean) => void
+      /** Reset update function for variable with given ID to an empty expression.
+       * The result is reported via `variableUpdateFnChanged`. */
+      clearVariableUpdateFn: (varId: string) => void
+      /** VariableData (with updated `frozen` flag) for a variable with (un)frozen update function. */
+      variableUpdateFnFrozenChanged: Observable<VariableData>
+      /** Set whether the update function of variable with given ID is frozen. */
+      setVariableUpdateFnFrozen: (varId: string, frozen: boolean) => void
+      /** VariableData (with updated `is_input` flag) for a variable that was (un)marked as input. */
+      variableInputChanged: Observable<VariableData>
+      /** Set whether the variable with given ID is an input (a free constant). */
+      setVariableInput: (varId: string, isInput: boolean) => void
 
       /** Uninterpreted function-related setter events: */
 
@@ -313,8 +411,12 @@ interface AeonState {
       setRegulationSign: (regulatorId: string, targetId: string, newSign: Monotonicity) => void
       /** RegulationData (with updated `essentiality`) of a modified regulation. */
       regulationEssentialityChanged: Observable<RegulationData>
+      /** RegulationData (with updated `annotation`) of a modified regulation. */
+      regulationAnnotationChanged: Observable<RegulationData>
       /** Set essentiality of a regulation specified by its regulator and target. */
       setRegulationEssentiality: (regulatorId: string, targetId: string, newEssentiality: Essentiality) => void
+      /** Set annotation of a regulation specified by its regulator and target. */
+      setRegulationAnnotation: (regulatorId: string, targetId: string, newAnnotation: string) => void
 
       /** Layout-related setter events: */
 
@@ -332,6 +434,9 @@ interface AeonState {
       /** Change a position of all variables in a layout to new coordinates.
        * This should change exacty all nodes in the network. */
       changeLayoutPositions: (layoutId: string, newCoordinates: LayoutNodeData[]) => void
+      /** Automatically arrange all nodes of a layout (using a force-directed algorithm).
+       * The new positions are sent via `layoutPositionsChanged`. */
+      autoArrangeLayout: (layoutId: string) => void
       /** LayoutNodeData (with new `px` and `py`) for a modified layout node. */
       nodePositionChanged: Observable<LayoutNodeData>
       /** Change a position of a variable in a layout to new coordinates. */
@@ -391,8 +496,9 @@ interface AeonState {
       removeDatasetVariable: (datasetId: string, varId: string) => void
       /** Add (placeholder) variable to a specified dataset (adding an empty column to a dataset's table). */
       addDatasetVariable: (datasetId: string) => void
-      /** Export dataset with given ID to a given file. */
-      exportDataset: (id: string, path: string) => void
+      /** Export dataset with given ID to a given file (in CSV format). Observation IDs are
+       * included unless `includeIds` is false. */
+      exportDataset: (id: string, path: string, includeIds?: boolean) => void
 
       /** ObservationData for a newly pushed observation (also contains corresponding dataset ID). */
       observationPushed: Observable<ObservationData>
@@ -403,6 +509,12 @@ interface AeonState {
       observationRemoved: Observable<ObservationData>
       /** Remove any observation from a specified dataset. */
       removeObservation: (datasetId: string, observationId: string) => void
+      /** Add a batch of observations to the end of a specified dataset. Either all of them are added,
+       * or none (if any of them is invalid). Modified dataset is sent via `datasetContentChanged`. */
+      addObservationBatch: (datasetId: string, observations: ObservationData[]) => void
+      /** Remove a batch of observations from a specified dataset. Either all of them are removed,
+       * or none (if any ID is invalid). Modified dataset is sent via `datasetContentChanged`. */
+      removeObservationBatch: (datasetId: string, observationIds: string[]) => void
       /** Object with `original_id` of a observation, its `new_id`. Dataset's ID is in the field `metadata`. */
       observationIdChanged: Observable<ObservationIdUpdateData>
       /** Set ID of observation (in a specified dataset) with given original ID to a new id. */
@@ -487,12 +599,36 @@ interface AeonState {
     startStaticInference: () => void
     /** Start the inference with dynamic properties only. */
     startDynamicInference: () => void
+    /** Start the inference with dynamic properties restricted to trap-space properties. */
+    startTrapSpaceInference: () => void
     /** Information that async inference was successfully started. */
     inferenceStarted: Observable<boolean>
     /** Fully reset the inference and start again. The same sketch will be used. */
     resetInference: () => void
     /** Information that inference was reset. */
     inferenceReset: Observable<boolean>
+    /** Cancel the running inference computation (the solver finishes with `Cancelled` status). */
+    cancelInference: () => void
+    /** Information that cancellation of the running inference was requested. */
+    cancellationRequested: Observable<boolean>
+    /** Set the soft limit on the number of BDD nodes used by the solver (`null` disables it).
+     * Exceeding the limit during computation results in a warning. */
+    setBddSizeLimit: (limit: number | null) => void
+    /** Information that the BDD size limit was changed. */
+    bddSizeLimitChanged: Observable<number | null>
+    /** Set the timeout for evaluation of a single property (`null` disables it). Properties
+     * exceeding it are skipped, or the computation is aborted (if configured). */
+    setPropertyTimeout: (timeout: PropertyTimeoutConfig | null) => void
+    /** Information that the property timeout was changed. */
+    propertyTimeoutChanged: Observable<PropertyTimeoutConfig | null>
+    /** Set whether static properties are evaluated in parallel (sequential by default). */
+    setParallelStaticEval: (parallel: boolean) => void
+    /** Information that the parallel static evaluation setting was changed. */
+    parallelStaticEvalChanged: Observable<boolean>
+    /** Set whether attractors are computed in parallel (parallel by default). */
+    setParallelAttractors: (parallel: boolean) => void
+    /** Information that the parallel attractor computation setting was changed. */
+    parallelAttractorsChanged: Observable<boolean>
     /** Ping backend to see if the results are ready. Can be used regardless of
      * what inference type is running. */
     pingForInferenceResults: () => void
@@ -505,12 +641,48 @@ interface AeonState {
 
     /** Inference results. */
     inferenceResultsReceived: Observable<InferenceResults>
+    /** Exact number of candidate networks satisfying the sketch (as a string, since it can be
+     * arbitrarily large). */
+    numCandidatesRefreshed: Observable<string>
+    /** Refresh the number of candidates (computed symbolically, without enumerating networks).
+     * Can only be used after the inference results were fetched. */
+    refreshNumCandidates: () => void
+    /** A single (deterministically chosen) witness network satisfying the sketch, in AEON format. */
+    witnessNetworkRefreshed: Observable<string>
+    /** Refresh the witness network (only one network is materialized, nothing is enumerated).
+     * Can only be used after the inference results were fetched. */
+    refreshWitnessNetwork: () => void
+    /** Interpretation of the function symbols of the witness network (see `witnessNetworkRefreshed`). */
+    candidateAssignmentRefreshed: Observable<CandidateAssignment>
+    /** Refresh the candidate assignment of the witness network.
+     * Can only be used after the inference results were fetched. */
+    refreshCandidateAssignment: () => void
+    /** Compare the candidates of the current inference run with the previous successful run
+     * (over the same model), materializing given number of example networks removed by the
+     * current run. Can only be used after the inference results were fetched. */
+    compareWithPreviousRun: (numExamples: number) => void
+    /** Comparison of candidate sets of the current and the previous inference run. */
+    runsCompared: Observable<RunsComparison>
+    /** Compute attractors of a single (fully specified) candidate network given in AEON format,
+     * listing at most `maxStates` of their states in total. */
+    computeCandidateAttractors: (network: string, maxStates: number) => void
+    /** Attractors of a candidate network, with their (bounded number of) states. */
+    candidateAttractorsComputed: Observable<CandidateAttractors>
+    /** Rank the candidates by weighted (fuzzy) reachability from observations of the initial
+     * dataset to (weighted) observations of the target dataset. Can only be used after the
+     * inference results were fetched. */
+    rankByWeightedReachability: (initialDataset: string, targetDataset: string) => void
+    /** Ranking of candidates by weighted reachability. */
+    weightedRankingComputed: Observable<CandidateRanking>
     /** Sample given number of Boolean networks from the results, either dereministically
      * or randomly. The networks are saved in a zip archive at given path. */
     sampleNetworks: (count: number, seed: number | null, path: string) => void
     /** Dump archive with results (including the sketch, the converted aeon BN used for inference, and
      * a BDD with all satisfying colors) to the given path. */
     dumpFullResults: (path: string) => void
+    /** Export all candidate networks into a zip archive at the given path (one aeon file per
+     * network). The networks are written in batches, so this works even for large results. */
+    exportAllCandidates: (path: string) => void
   }
 
   /** The information about errors occurring when processing events on backend. */
@@ -530,6 +702,21 @@ interface AeonState {
     /** Create a new inference session. */
     createNewInferenceSession: () => void
   }
+
+  /** The state of the periodic autosave of the edited sketch. */
+  autosave: {
+    /** AutosaveConfig after it was changed. */
+    configChanged: Observable<AutosaveConfig>
+    /** Path to an autosave file newer than the last explicit save (offered for recovery). */
+    recoveryAvailable: Observable<string>
+    /** Set the autosave configuration (autosave is disabled if the directory is `null`). */
+    setConfig: (config: AutosaveConfig) => void
+    /** Check whether there is an autosave newer than the last explicit save. This is also done
+     * automatically when the autosave is enabled for the first time. */
+    checkRecovery: () => void
+    /** Write the changes that were not autosaved yet due to throttling (if there are any). */
+    flush: () => void
+  }
 }
 /**
  * A singleton state management object for the current Aeon session.
@@ -549,6 +736,18 @@ export const aeonState: AeonState = {
         path: ['undo_stack', 'redo'],
         payload: null
       })
+    },
+    beginGroup () {
+      aeonEvents.emitAction({
+        path: ['undo_stack', 'begin_group'],
+        payload: null
+      })
+    },
+    endGroup () {
+      aeonEvents.emitAction({
+        path: ['undo_stack', 'end_group'],
+        payload: null
+      })
     }
   },
   tabBar: {
@@ -585,16 +784,49 @@ export const aeonState: AeonState = {
       })
     }
   },
+  autosave: {
+    configChanged: new Observable<AutosaveConfig>(['autosave', 'config']),
+    recoveryAvailable: new Observable<string>(['autosave', 'recovery_available']),
+
+    setConfig (config: AutosaveConfig): void {
+      aeonEvents.emitAction({
+        path: ['autosave', 'set_config'],
+        payload: JSON.stringify(config)
+      })
+    },
+    checkRecovery (): void {
+      aeonEvents.emitAction({
+        path: ['autosave', 'check_recovery'],
+        payload: null
+      })
+    },
+    flush (): void {
+      aeonEvents.emitAction({
+        path: ['autosave', 'flush'],
+        payload: null
+      })
+    }
+  },
   sketch: {
     sketchRefreshed: new Observable<SketchData>(['sketch', 'get_whole_sketch']),
     consistencyResults: new Observable<string>(['sketch', 'consistency_results']),
+    regulatorCheckSeverityChanged: new Observable<'Warning' | 'Error'>(['sketch', 'regulator_check_severity']),
     numPSBNParamsFetched: new Observable<number>(['sketch', 'num_psbn_params']),
+    numFreeParamsRefreshed: new Observable<number>(['sketch', 'get_num_free_params']),
+    statisticsRefreshed: new Observable<SketchStats>(['sketch', 'get_statistics']),
     sketchReplaced: new Observable<SketchData>(['sketch', 'set_all']),
     annotationChanged: new Observable<string>(['sketch', 'set_annotation']),
+    initialSubspaceChanged: new Observable<Record<string, boolean>>(['sketch', 'set_initial_subspace']),
 
     refreshSketch (): void {
       aeonEvents.refresh(['sketch', 'get_whole_sketch'])
     },
+    refreshNumFreeParams (): void {
+      aeonEvents.refresh(['sketch', 'get_num_free_params'])
+    },
+    refreshStatistics (): void {
+      aeonEvents.refresh(['sketch', 'get_statistics'])
+    },
     exportSketch (path: string): void {
       aeonEvents.emitAction({
         path: ['sketch', 'export_sketch'],
@@ -643,6 +875,12 @@ export const aeonState: AeonState = {
         payload: null
       })
     },
+    setRegulatorCheckSeverity (severity: 'Warning' | 'Error'): void {
+      aeonEvents.emitAction({
+        path: ['sketch', 'set_regulator_check_severity'],
+        payload: JSON.stringify(severity)
+      })
+    },
     fetchNumPSBNParams (): void {
       aeonEvents.emitAction({
         path: ['sketch', 'get_num_psbn_params'],
@@ -655,6 +893,12 @@ export const aeonState: AeonState = {
         payload: annotation
       })
     },
+    setInitialSubspace (subspace: Record<string, boolean>): void {
+      aeonEvents.emitAction({
+        path: ['sketch', 'set_initial_subspace'],
+        payload: JSON.stringify(subspace)
+      })
+    },
 
     model: {
       modelRefreshed: new Observable<ModelData>(['sketch', 'model', 'get_whole_model']),
@@ -681,12 +925,18 @@ export const aeonState: AeonState = {
       refreshLayoutNodes (layoutId: string): void {
         aeonEvents.refresh(['sketch', 'model', 'get_layout_nodes', layoutId])
       },
+      completenessReportRefreshed: new Observable<CompletenessReport>(['sketch', 'model', 'get_completeness_report']),
+      refreshCompletenessReport (): void {
+        aeonEvents.refresh(['sketch', 'model', 'get_completeness_report'])
+      },
 
       variableCreated: new Observable<VariableData>(['sketch', 'model', 'variable', 'add']),
       variableRemoved: new Observable<VariableData>(['sketch', 'model', 'variable', 'remove']),
       variableDataChanged: new Observable<VariableData>(['sketch', 'model', 'variable', 'set_data']),
       variableIdChanged: new Observable<ModelData>(['sketch', 'model', 'variable', 'set_id']),
       variableUpdateFnChanged: new Observable<VariableData>(['sketch', 'model', 'variable', 'set_update_fn']),
+      variableUpdateFnFrozenChanged: new Observable<VariableData>(['sketch', 'model', 'variable', 'set_update_fn_frozen']),
+      variableInputChanged: new Observable<VariableData>(['sketch', 'model', 'variable', 'set_var_input']),
 
       uninterpretedFnCreated: new Observable<UninterpretedFnData>(['sketch', 'model', 'uninterpreted_fn', 'add']),
       uninterpretedFnRemoved: new Observable<UninterpretedFnData>(['sketch', 'model', 'uninterpreted_fn', 'remove']),
@@ -701,6 +951,7 @@ export const aeonState: AeonState = {
       regulationRemoved: new Observable<RegulationData>(['sketch', 'model', 'regulation', 'remove']),
       regulationSignChanged: new Observable<RegulationData>(['sketch', 'model', 'regulation', 'set_sign']),
       regulationEssentialityChanged: new Observable<RegulationData>(['sketch', 'model', 'regulation', 'set_essentiality']),
+      regulationAnnotationChanged: new Observable<RegulationData>(['sketch', 'model', 'regulation', 'set_annotation']),
 
       layoutCreated: new Observable<LayoutData>(['sketch', 'model', 'layout', 'add']),
       layoutRemoved: new Observable<LayoutData>(['sketch', 'model', 'layout', 'remove']),
@@ -742,6 +993,30 @@ export const aeonState: AeonState = {
           payload: newExpression
         })
       },
+      setVariableUpdateFnConstant (varId: string, value: boolean): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'model', 'variable', varId, 'set_update_fn_constant'],
+          payload: JSON.stringify(value)
+        })
+      },
+      clearVariableUpdateFn (varId: string): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'model', 'variable', varId, 'clear_update_fn'],
+          payload: null
+        })
+      },
+      setVariableUpdateFnFrozen (varId: string, frozen: boolean): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'model', 'variable', varId, 'set_update_fn_frozen'],
+          payload: JSON.stringify(frozen)
+        })
+      },
+      setVariableInput (varId: string, isInput: boolean): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'model', 'variable', varId, 'set_var_input'],
+          payload: JSON.stringify(isInput)
+        })
+      },
       addDefaultUninterpretedFn (): void {
         aeonEvents.emitAction({
           path: ['sketch', 'model', 'uninterpreted_fn', 'add_default'],
@@ -819,6 +1094,12 @@ export const aeonState: AeonState = {
           payload: JSON.stringify(newEssentiality)
         })
       },
+      setRegulationAnnotation (regulatorId: string, targetId: string, newAnnotation: string): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'model', 'regulation', regulatorId, targetId, 'set_annotation'],
+          payload: newAnnotation
+        })
+      },
       addLayout (layoutId: string, layoutName: string): void {
         aeonEvents.emitAction({
           path: ['sketch', 'model', 'layout', 'add'],
@@ -837,6 +1118,12 @@ export const aeonState: AeonState = {
           payload: JSON.stringify(newCoordinates)
         })
       },
+      autoArrangeLayout (layoutId: string): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'model', 'layout', layoutId, 'auto_arrange'],
+          payload: null
+        })
+      },
       changeNodePosition (layoutId: string, varId: string, newX: number, newY: number): void {
         aeonEvents.emitAction({
           path: ['sketch', 'model', 'layout', layoutId, 'update_position'],
@@ -925,10 +1212,10 @@ export const aeonState: AeonState = {
           payload: null
         })
       },
-      exportDataset (id: string, path: string): void {
+      exportDataset (id: string, path: string, includeIds = true): void {
         aeonEvents.emitAction({
           path: ['sketch', 'observations', id, 'export'],
-          payload: path
+          payload: JSON.stringify({ path, include_ids: includeIds })
         })
       },
       pushDefaultObservation (datasetId: string): void {
@@ -943,6 +1230,18 @@ export const aeonState: AeonState = {
           payload: null
         })
       },
+      addObservationBatch (datasetId: string, observations: ObservationData[]): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'observations', datasetId, 'add_batch'],
+          payload: JSON.stringify(observations)
+        })
+      },
+      removeObservationBatch (datasetId: string, observationIds: string[]): void {
+        aeonEvents.emitAction({
+          path: ['sketch', 'observations', datasetId, 'remove_batch'],
+          payload: JSON.stringify(observationIds)
+        })
+      },
       setObservationId (datasetId: string, originalId: string, newId: string): void {
         aeonEvents.emitAction({
           path: ['sketch', 'observations', datasetId, originalId, 'set_id'],
@@ -1029,28 +1328,102 @@ export const aeonState: AeonState = {
   analysis: {
     sketchRefreshed: new Observable<SketchData>(['inference', 'get_sketch']),
     inferenceReset: new Observable<boolean>(['inference', 'inference_reset']),
+    cancellationRequested: new Observable<boolean>(['inference', 'cancellation_requested']),
+    bddSizeLimitChanged: new Observable<number | null>(['inference', 'bdd_size_limit']),
+    propertyTimeoutChanged: new Observable<PropertyTimeoutConfig | null>(['inference', 'property_timeout']),
+    parallelStaticEvalChanged: new Observable<boolean>(['inference', 'parallel_static_eval']),
+    parallelAttractorsChanged: new Observable<boolean>(['inference', 'parallel_attractors']),
 
     refreshSketch (): void {
       aeonEvents.refresh(['inference', 'get_sketch'])
     },
+    numCandidatesRefreshed: new Observable<string>(['inference', 'num_candidates']),
+    refreshNumCandidates (): void {
+      aeonEvents.refresh(['inference', 'num_candidates'])
+    },
+    witnessNetworkRefreshed: new Observable<string>(['inference', 'witness_network']),
+    refreshWitnessNetwork (): void {
+      aeonEvents.refresh(['inference', 'witness_network'])
+    },
+    candidateAssignmentRefreshed: new Observable<CandidateAssignment>(['inference', 'candidate_assignment']),
+    refreshCandidateAssignment (): void {
+      aeonEvents.refresh(['inference', 'candidate_assignment'])
+    },
     resetInference () {
       aeonEvents.emitAction({
         path: ['inference', 'reset_inference'],
         payload: null
       })
     },
+    cancelInference () {
+      aeonEvents.emitAction({
+        path: ['inference', 'cancel'],
+        payload: null
+      })
+    },
+    setBddSizeLimit (limit: number | null): void {
+      aeonEvents.emitAction({
+        path: ['inference', 'set_bdd_size_limit'],
+        payload: JSON.stringify(limit)
+      })
+    },
+    setPropertyTimeout (timeout: PropertyTimeoutConfig | null): void {
+      aeonEvents.emitAction({
+        path: ['inference', 'set_property_timeout'],
+        payload: JSON.stringify(timeout)
+      })
+    },
+    setParallelStaticEval (parallel: boolean): void {
+      aeonEvents.emitAction({
+        path: ['inference', 'set_parallel_static_eval'],
+        payload: JSON.stringify(parallel)
+      })
+    },
+    setParallelAttractors (parallel: boolean): void {
+      aeonEvents.emitAction({
+        path: ['inference', 'set_parallel_attractors'],
+        payload: JSON.stringify(parallel)
+      })
+    },
     sampleNetworks (count: number, seed: number | null, path: string): void {
       aeonEvents.emitAction({
         path: ['inference', 'sample_networks'],
         payload: JSON.stringify({ count, seed, path })
       })
     },
+    runsCompared: new Observable<RunsComparison>(['inference', 'runs_comparison']),
+    compareWithPreviousRun (numExamples: number): void {
+      aeonEvents.emitAction({
+        path: ['inference', 'compare_with_previous_run'],
+        payload: JSON.stringify(numExamples)
+      })
+    },
+    candidateAttractorsComputed: new Observable<CandidateAttractors>(['inference', 'candidate_attractors']),
+    computeCandidateAttractors (network: string, maxStates: number): void {
+      aeonEvents.emitAction({
+        path: ['inference', 'candidate_attractors'],
+        payload: JSON.stringify({ network, max_states: maxStates })
+      })
+    },
+    weightedRankingComputed: new Observable<CandidateRanking>(['inference', 'weighted_ranking']),
+    rankByWeightedReachability (initialDataset: string, targetDataset: string): void {
+      aeonEvents.emitAction({
+        path: ['inference', 'rank_by_weighted_reachability'],
+        payload: JSON.stringify({ initial_dataset: initialDataset, target_dataset: targetDataset })
+      })
+    },
     dumpFullResults (path: string): void {
       aeonEvents.emitAction({
         path: ['inference', 'dump_full_results'],
         payload: path
       })
     },
+    exportAllCandidates (path: string): void {
+      aeonEvents.emitAction({
+        path: ['inference', 'export_all_candidates'],
+        payload: path
+      })
+    },
 
     inferenceResultsReceived: new Observable<InferenceResults>(['inference', 'inference_results']),
     inferenceStarted: new Observable<boolean>(['inference', 'inference_running']),
@@ -1075,6 +1448,12 @@ export const aeonState: AeonState = {
         payload: null
       })
     },
+    startTrapSpaceInference (): void {
+      aeonEvents.emitAction({
+        path: ['inference', 'run_trap_space_inference'],
+        payload: null
+      })
+    },
     pingForInferenceResults (): void {
       aeonEvents.emitAction({
         path: ['inference', 'get_inference_results'],
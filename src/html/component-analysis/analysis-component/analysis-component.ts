@@ -6,6 +6,7 @@ import {
   type SketchData
 } from '../../../aeon_state'
 import {
+  type CandidateRefinement,
   type InferenceStatusReport,
   InferenceType,
   type InferenceResults
@@ -38,6 +39,10 @@ export default class AnalysisComponent extends LitElement {
   @state() staticDone: number = 0
   /** Number of already evaluated dynamic properties */
   @state() dynamicDone: number = 0
+  /** Latest reported size (number of BDD nodes) of the symbolic sets processed by the solver */
+  @state() bddSize: number | null = null
+  /** Latest reported refinement of the candidate set (by the most recently evaluated property) */
+  @state() lastRefinement: CandidateRefinement | null = null
 
   constructor () {
     super()
@@ -138,6 +143,13 @@ export default class AnalysisComponent extends LitElement {
     if (this.selected_inference === InferenceType.FullInference) {
       message += `- processed ${this.dynamicDone} dynamic properties (out of ${dynamicTotal})<br>`
     }
+    if (this.bddSize !== null) {
+      message += `- current BDD size: ${this.bddSize} nodes<br>`
+    }
+    if (this.lastRefinement !== null) {
+      const refinement = this.lastRefinement
+      message += `- property \`${refinement.prop_id}\` refined candidates from ${refinement.num_candidates_before} to ${refinement.num_candidates_after}<br>`
+    }
     return message
   }
 
@@ -151,6 +163,12 @@ export default class AnalysisComponent extends LitElement {
       if (typeof progressUpdate.status === 'object' && 'EvaluatedDynamic' in progressUpdate.status) {
         this.dynamicDone += 1
       }
+      if (progressUpdate.bdd_size !== null) {
+        this.bddSize = progressUpdate.bdd_size
+      }
+      if (progressUpdate.refinement !== null) {
+        this.lastRefinement = progressUpdate.refinement
+      }
       this.waitingMainMessage = this.formatWaitingOverview()
       this.waitingProgressReport += progressUpdate.message + '\n'
     })
@@ -279,6 +297,8 @@ export default class AnalysisComponent extends LitElement {
     this.results = null
     this.staticDone = 0
     this.dynamicDone = 0
+    this.bddSize = null
+    this.lastRefinement = null
   }
 
   /** Invoke the backend to sample and export BNs according to user selected parameters and paths. */
@@ -4,6 +4,9 @@ import style_less from './analysis-tab.less?inline'
 import { ContentData } from '../../util/data-interfaces'
 import { aeonState } from '../../../aeon_state'
 
+/** Number of free parameters above which we warn the user about the size of the search space. */
+const FREE_PARAMS_WARNING_LIMIT = 64
+
 /** Component responsible for the analysis tab of the editor session. */
 @customElement('analysis-tab')
 export class AnalysisTab extends LitElement {
@@ -13,6 +16,8 @@ export class AnalysisTab extends LitElement {
   @state() consistencyResults: string | null = null
   /** Number of params of the PSBN component (null if currently not fetched). */
   @state() numPSBNParams: number | null = null
+  /** Number of free params of the PSBN, given regulation types (null if currently not fetched). */
+  @state() numFreeParams: number | null = null
 
   constructor () {
     super()
@@ -25,6 +30,10 @@ export class AnalysisTab extends LitElement {
     aeonState.sketch.numPSBNParamsFetched.addEventListener(
       this.#onPSBNParamsNumFetched.bind(this)
     )
+    // listen for the refreshed number of free params
+    aeonState.sketch.numFreeParamsRefreshed.addEventListener(
+      this.#onFreeParamsNumRefreshed.bind(this)
+    )
   }
 
   protected updated (_changedProperties: PropertyValues): void {
@@ -35,6 +44,7 @@ export class AnalysisTab extends LitElement {
     if (_changedProperties.has('contentData')) {
       this.consistencyResults = null
       this.numPSBNParams = null
+      this.numFreeParams = null
     }
   }
 
@@ -61,6 +71,7 @@ export class AnalysisTab extends LitElement {
   /** Request backend to send num of PSBN params. */
   getPSBNParamsNum (): void {
     aeonState.sketch.fetchNumPSBNParams()
+    aeonState.sketch.refreshNumFreeParams()
   }
 
   /** Process and save the number of psbn params. */
@@ -69,6 +80,26 @@ export class AnalysisTab extends LitElement {
     console.log('Received number of PSBN params.')
   }
 
+  /** Process and save the number of free psbn params. */
+  #onFreeParamsNumRefreshed (num: number): void {
+    this.numFreeParams = num
+    console.log('Received number of free PSBN params.')
+  }
+
+  /** Render the number of free params, with a warning if the search space is too large. */
+  renderFreeParams (): TemplateResult {
+    if (this.numFreeParams === null) {
+      return html`-`
+    }
+    if (this.numFreeParams > FREE_PARAMS_WARNING_LIMIT) {
+      return html`<span class="uk-text-warning"
+                        title="The space of candidates is very large, the inference may take very long.">
+                    ${this.numFreeParams} (!)
+                  </span>`
+    }
+    return html`${this.numFreeParams}`
+  }
+
   /** Compute maximal node indegree in the influence graph. */
   getMaxNetworkIndegree (): number {
     let maxIndegree = 0
@@ -147,8 +178,8 @@ export class AnalysisTab extends LitElement {
                           `
 : html`2^${this.numPSBNParams}`}
                     </td>     
-                    <td></td>    
-                    <td></td>          
+                    <td>Free parameters: </td>    
+                    <td class="value">${this.renderFreeParams()}</td>          
                   </tr>
                 </tbody>
               </table>
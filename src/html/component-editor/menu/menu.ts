@@ -112,6 +112,9 @@ export default class Menu extends LitElement {
       filters: [{
         name: '*.json',
         extensions: ['json']
+      }, {
+        name: '*.json.gz',
+        extensions: ['gz']
       }],
       defaultPath: 'project_name_here'
     })
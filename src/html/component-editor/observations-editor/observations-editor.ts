@@ -65,6 +65,9 @@ export default class ObservationsEditor extends LitElement {
     // refresh-event listeners
     aeonState.sketch.observations.datasetsRefreshed.addEventListener(this.#onDatasetsRefreshed.bind(this))
 
+    // initial subspace of the sketch, given as a partial observation over model variables
+    aeonState.sketch.initialSubspaceChanged.addEventListener(this.#onInitialSubspaceChanged.bind(this))
+
     // note that the refresh events are automatically triggered or handled (after app refresh) directly
     // from the root component (due to some dependency issues between different components)
   }
@@ -91,6 +94,10 @@ export default class ObservationsEditor extends LitElement {
           name: 'Tab-separated values',
           extensions: ['tsv', 'tab']
         },
+        {
+          name: 'Compressed (gzip) values',
+          extensions: ['gz']
+        },
         {
           name: 'All',
           extensions: ['*']
@@ -215,6 +222,7 @@ export default class ObservationsEditor extends LitElement {
           selected: obs.selected,
           name: obs.name,
           annotation: obs.annotation,
+          metadata: obs.metadata,
           id: obs.id
         }
 
@@ -235,14 +243,16 @@ export default class ObservationsEditor extends LitElement {
         name: data.name,
         annotation: data.annotation,
         variables: data.variables,
+        ordering: data.ordering,
         observations
       }
     } else {
-      // otherwise only change the name and annotation
+      // otherwise only change the name, annotation, and ordering
       datasets[datasetIndex] = {
         ...datasets[datasetIndex],
         name: data.name,
-        annotation: data.annotation
+        annotation: data.annotation,
+        ordering: data.ordering
       }
     }
     this.updateObservations(datasets)
@@ -347,7 +357,8 @@ export default class ObservationsEditor extends LitElement {
       id: datasetData.id,
       annotation: datasetData.annotation,
       name: datasetData.name,
-      variables: datasetData.variables
+      variables: datasetData.variables,
+      ordering: datasetData.ordering
     }
 
     // ID might have changed
@@ -450,11 +461,61 @@ export default class ObservationsEditor extends LitElement {
     aeonState.sketch.observations.exportDataset(detail.id, filePath)
   }
 
+  #onInitialSubspaceChanged (initialSubspace: Record<string, boolean>): void {
+    // propagate the new subspace via event that will be captured by root component
+    this.dispatchEvent(new CustomEvent('save-initial-subspace', {
+      bubbles: true,
+      composed: true,
+      detail: { initialSubspace }
+    }))
+  }
+
+  /** Format the initial subspace as a partial observation string (values of model variables). */
+  private formatInitialSubspace (): string {
+    return this.contentData.variables.map(variable => {
+      const value = this.contentData.initialSubspace[variable.id]
+      if (value === undefined) return '*'
+      return value ? '1' : '0'
+    }).join('')
+  }
+
+  /** Parse the partial observation string and inform backend about the new initial subspace. */
+  private changeInitialSubspace (event: Event): void {
+    const target = event.target as HTMLInputElement
+    const values = target.value.trim()
+    const variables = this.contentData.variables
+    if (values.length !== variables.length || !/^[01*]*$/.test(values)) {
+      void dialog.message(`Initial subspace must contain 0, 1, or * for each of the ${variables.length} variables.`, {
+        type: 'error',
+        title: 'Invalid initial subspace'
+      })
+      target.value = this.formatInitialSubspace()
+      return
+    }
+    const subspace: Record<string, boolean> = {}
+    variables.forEach((variable, index) => {
+      if (values[index] !== '*') subspace[variable.id] = values[index] === '1'
+    })
+    aeonState.sketch.setInitialSubspace(subspace)
+  }
+
   render (): TemplateResult {
     return html`
       <!-- Single-section container to limit the max width of the tab content. -->
       <div class="width-container">
         <div class="width-section">
+          <div class="initial-subspace uk-margin-bottom">
+            <div class="header uk-background-primary uk-margin-bottom">
+              <h3 class="uk-heading-bullet uk-margin-remove-bottom">Initial subspace</h3>
+            </div>
+            <div class="uk-margin-small-left uk-margin-small-right">
+              <span class="uk-text-small">${this.contentData.variables.map(v => v.id).join(', ')}</span>
+              <input class="uk-input uk-form-small"
+                .value="${this.formatInitialSubspace()}"
+                @focusout="${this.changeInitialSubspace}"
+                placeholder="No variables in the model">
+            </div>
+          </div>
           <div class="observations">
             <div class="header uk-background-primary uk-margin-bottom">
               <h3 class="uk-heading-bullet uk-margin-remove-bottom ">Observations</h3>
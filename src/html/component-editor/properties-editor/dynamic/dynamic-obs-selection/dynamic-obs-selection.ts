@@ -2,7 +2,9 @@ import { css, html, type PropertyValues, type TemplateResult, unsafeCSS } from '
 import { customElement, property, query } from 'lit/decorators.js'
 import style_less from './dynamic-obs-selection.less?inline'
 import {
+  type AbsenceMode,
   DynamicPropertyType,
+  type IObservationAbsentDynamicProperty,
   type IObservationSet,
   type ITrapSpaceDynamicProperty
 } from '../../../../util/data-interfaces'
@@ -15,7 +17,7 @@ const ALL = '*'
 @customElement('dynamic-obs-selection')
 export default class DynamicObsSelection extends AbstractDynamicProperty {
   static styles = css`${unsafeCSS(style_less)}`
-  @property() declare property: ITrapSpaceDynamicProperty
+  @property() declare property: ITrapSpaceDynamicProperty & IObservationAbsentDynamicProperty
   @property() declare observations: IObservationSet[]
   @query('#dataset') declare datasetSelector: HTMLSelectElement
   @query('#observation') declare observationSelector: HTMLSelectElement
@@ -54,6 +56,13 @@ export default class DynamicObsSelection extends AbstractDynamicProperty {
     })
   }
 
+  modeChanged (event: Event): void {
+    this.updateProperty({
+      ...this.property,
+      mode: (event.target as HTMLSelectElement).value as AbsenceMode
+    })
+  }
+
   protected updated (_changedProperties: PropertyValues): void {
     super.updated(_changedProperties)
     const obsIndex = this.observations.findIndex(dataset => dataset.id === this.property.dataset)
@@ -108,6 +117,18 @@ export default class DynamicObsSelection extends AbstractDynamicProperty {
               <label class="pointer" for="non-percolable">non-percolable</label>
             </div>
           </div>`)}
+
+        ${when(this.property.variant === DynamicPropertyType.ObservationAbsent, () => html`
+          <div class="uk-flex uk-flex-row uk-flex-around uk-flex-middle uk-margin-small-top">
+            <label for="mode">Mode:</label>
+            <div class="uk-width-1-2">
+              <select class="uk-select uk-margin-small-left" name="mode" id="mode"
+                      .value=${this.property.mode} @change=${this.modeChanged}>
+                <option value="Unreachable">unreachable</option>
+                <option value="NotAttractor">not an attractor</option>
+              </select>
+            </div>
+          </div>`)}
       </div>
     `
   }
@@ -52,9 +52,11 @@ export default class PropertiesEditor extends LitElement {
   addDynamicPropertyMenu: DynamicPropertyType[] = [
     DynamicPropertyType.TrapSpace,
     DynamicPropertyType.FixedPoint,
+    DynamicPropertyType.SteadyState,
     DynamicPropertyType.ExistsTrajectory,
     DynamicPropertyType.AttractorCount,
     DynamicPropertyType.HasAttractor,
+    DynamicPropertyType.ObservationAbsent,
     DynamicPropertyType.Generic
   ]
 
@@ -580,7 +582,9 @@ export default class PropertiesEditor extends LitElement {
                 let result = html``
                 switch (prop.variant) {
                   case DynamicPropertyType.FixedPoint:
+                  case DynamicPropertyType.SteadyState:
                   case DynamicPropertyType.HasAttractor:
+                  case DynamicPropertyType.ObservationAbsent:
                   case DynamicPropertyType.TrapSpace:
                   case DynamicPropertyType.ExistsTrajectory:
                     result = html`
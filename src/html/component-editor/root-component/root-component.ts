@@ -23,7 +23,7 @@ import {
   convertToIFunction, convertToILayout, convertToIVariable,
   convertToIObservationSet, convertToIRegulation
 } from '../../util/utilities'
-import { resolveResource } from '@tauri-apps/api/path'
+import { appDataDir, join, resolveResource } from '@tauri-apps/api/path'
 
 const exampleModelPath = await resolveResource('resources/tlgl.json')
 
@@ -71,6 +71,10 @@ export default class RootComponent extends LitElement {
     window.addEventListener('start-import-sbml', (e) => { void this.startImportSbml(e) })
     window.addEventListener('start-import-example', this.startImportExample.bind(this))
 
+    // Autosave of the sketch, and recovery of autosaves newer than the last explicit save
+    aeonState.autosave.recoveryAvailable.addEventListener((path) => { void this.#onRecoveryAvailable(path) })
+    void this.initAutosave()
+
     // Listeners to import events from editor menu
     window.addEventListener('import-json', (e) => { void this.importJson(e) })
     window.addEventListener('import-aeon', (e) => { void this.importAeon(e) })
@@ -133,6 +137,7 @@ export default class RootComponent extends LitElement {
     this.addEventListener('save-dynamic-properties', this.saveDynamicPropertyData.bind(this))
     this.addEventListener('save-static-properties', this.saveStaticPropertyData.bind(this))
     this.addEventListener('save-annotation', this.saveAnnotationData.bind(this))
+    this.addEventListener('save-initial-subspace', this.saveInitialSubspaceData.bind(this))
 
     // Load variable editorStarted from session storage (so it survives refresh)
     const storedEditorStarted = sessionStorage.getItem('editorStarted')
@@ -179,6 +184,23 @@ export default class RootComponent extends LitElement {
     this.adjustRegEditor()
   }
 
+  /** Enable the autosave (to the app data directory). Autosaves to recover are offered by the
+   * backend once the autosave is enabled. Throttled changes are flushed periodically. */
+  private async initAutosave (): Promise<void> {
+    const directory = await join(await appDataDir(), 'autosave')
+    const intervalSecs = 30
+    aeonState.autosave.setConfig({ directory, interval_secs: intervalSecs })
+    setInterval(() => { aeonState.autosave.flush() }, intervalSecs * 1000)
+  }
+
+  /** Offer to recover an autosaved sketch, and import it if the user agrees. */
+  async #onRecoveryAvailable (path: string): Promise<void> {
+    const message = 'Found an autosaved sketch newer than the last saved one. Do you want to recover it?'
+    if (!await dialog.ask(message, { title: 'Recover sketch', type: 'warning' })) return
+    aeonState.sketch.importSketch(path)
+    this.startEditor()
+  }
+
   /** Utility to set the flag for editor rendering (and save to session storage). */
   private startEditor (): void {
     this.editorStarted = true
@@ -244,6 +266,9 @@ export default class RootComponent extends LitElement {
       filters: [{
         name: '*.json',
         extensions: ['json']
+      }, {
+        name: '*.json.gz',
+        extensions: ['gz']
       }]
     })
     if (selected === null) return false
@@ -346,6 +371,13 @@ export default class RootComponent extends LitElement {
     this.saveAnnotation(annotation)
   }
 
+  /** Save initial subspace data sent from one of the sub-components. */
+  saveInitialSubspaceData (event: Event): void {
+    // update initial subspace propagated from ObservationsEditor
+    const initialSubspace: Record<string, boolean> = (event as CustomEvent).detail.initialSubspace
+    this.data = this.data.copy({ initialSubspace })
+  }
+
   /** Save dynamic properties data sent from backend. */
   private saveDynamicProperties (dynamicProperties: DynamicProperty[]): void {
     dynamicProperties.sort((a, b) => (a.id > b.id ? 1 : -1))
@@ -416,7 +448,8 @@ export default class RootComponent extends LitElement {
     observations: IObservationSet[],
     staticProperties: StaticProperty[],
     dynamicProperties: DynamicProperty[],
-    annotation: string
+    annotation: string,
+    initialSubspace: Record<string, boolean>
   ): void {
     functions.sort((a, b) => (a.id > b.id ? 1 : -1))
     variables.sort((a, b) => (a.id > b.id ? 1 : -1))
@@ -432,7 +465,8 @@ export default class RootComponent extends LitElement {
       staticProperties,
       dynamicProperties,
       observations,
-      annotation
+      annotation,
+      initialSubspace
     })
   }
 
@@ -706,7 +740,8 @@ export default class RootComponent extends LitElement {
       datasets,
       sketch.stat_properties,
       sketch.dyn_properties,
-      sketch.annotation
+      sketch.annotation,
+      sketch.initial_subspace ?? {}
     )
   }
 
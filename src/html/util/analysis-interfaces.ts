@@ -2,7 +2,8 @@
 export enum InferenceType {
   FullInference = 'FullInference',
   StaticInference = 'StaticInference',
-  DynamicInference = 'DynamicInference'
+  DynamicInference = 'DynamicInference',
+  TrapSpaceInference = 'TrapSpaceInference'
 }
 
 /** Typesafe representation of statuses of the inference computation. */
@@ -16,16 +17,32 @@ export type InferenceStatus =
     | { EvaluatedDynamic: string } // EvaluatedDynamic(String)
     | 'EvaluatedAllDynamic'
     | { InternalProgress: string } // InternalProgress(String)
+    | { BddSizeLimitExceeded: number } // BddSizeLimitExceeded(usize)
+    | { PropertyTimeout: string } // PropertyTimeout(String)
     | 'DetectedUnsat'
     | 'FinishedSuccessfully'
+    | 'Error'
+    | 'Cancelled'
 
 /** Report with a summary of the inference computaiton. */
+/** Record on how evaluating a single property refined the set of candidates. */
+export interface CandidateRefinement {
+  prop_id: string
+  // These values are represented as strings, since they can exceed the maximum number size in JavaScript
+  num_candidates_before: string
+  num_candidates_after: string
+}
+
 export interface InferenceStatusReport {
   status: InferenceStatus
   // This value is represented as string, since it can exceed the maximum number size in JavaScript
   num_candidates: string | null
   comp_time: number
   message: string
+  // Approximate number of BDD nodes currently processed by the solver (memory usage estimate)
+  bdd_size: number | null
+  // How the set of candidates was refined by the property that was just evaluated (if relevant)
+  refinement: CandidateRefinement | null
 }
 
 /** Structure representing all information regarding inference results. */
@@ -37,3 +54,58 @@ export interface InferenceResults {
   progress_statuses: InferenceStatusReport[]
   num_update_fns_per_var: Record<string, number>
 }
+
+/** Comparison of candidate sets of two consecutive inference runs. The counts are strings,
+ * since they can be arbitrarily large. */
+export interface RunsComparison {
+  num_added: string
+  num_removed: string
+  num_retained: string
+  // Several example networks (in AEON format) removed by the current run
+  removed_examples: string[]
+}
+
+/** States of a single attractor of a candidate network. Each state lists values of variables
+ * (in the order given by `CandidateAttractors.variables`). Only a bounded number of states
+ * is listed, the total number is a string, since it can be arbitrarily large. */
+export interface AttractorStates {
+  num_states: string
+  states: boolean[][]
+}
+
+/** All attractors of a candidate network, with their (bounded number of) states. */
+export interface CandidateAttractors {
+  variables: string[]
+  attractors: AttractorStates[]
+}
+
+/** Truth table of a function, given as rows of argument values (in lexicographic order, the
+ * first argument being the most significant), each with its output (`null` if undetermined). */
+export type TruthTable = Array<[boolean[], boolean | null]>
+
+/** Interpretation of all parameters of a single candidate network. Uninterpreted functions are
+ * resolved into truth tables (keyed by function IDs), free update functions into expressions
+ * (keyed by variable IDs). */
+export interface CandidateAssignment {
+  uninterpreted_fns: Record<string, TruthTable>
+  update_fns: Record<string, string>
+}
+
+/** A class of candidates with the same weighted reachability score. The number of candidates
+ * is a string, since it can be arbitrarily large. */
+export interface ScoreClass {
+  score: number
+  num_candidates: string
+}
+
+/** Ranking of candidates by weighted reachability (classes sorted by decreasing score). */
+export interface CandidateRanking {
+  classes: ScoreClass[]
+}
+
+/** Configuration of the timeout for evaluation of a single property. */
+export interface PropertyTimeoutConfig {
+  timeout_ms: number
+  // Abort the whole computation once the timeout is exceeded (otherwise the property is skipped)
+  abort_on_timeout: boolean
+}
@@ -56,6 +56,7 @@ export class ContentData extends Data {
   dynamicProperties: DynamicProperty[] = []
   staticProperties: StaticProperty[] = []
   annotation: string = ''
+  initialSubspace: Record<string, boolean> = {}
 }
 
 /** Internally used structure to represent update and uninterpreted functions. */
@@ -73,6 +74,7 @@ export interface IObservation {
   id: string
   name: string
   annotation: string
+  metadata: string // empty if the observation has no metadata
 
   [key: string]: string | number | boolean
 }
@@ -84,8 +86,12 @@ export interface IObservationSet {
   annotation: string
   observations: IObservation[]
   variables: string[]
+  ordering?: DatasetOrdering
 }
 
+/** Whether observations of a dataset form a time series, or an unordered collection of states. */
+export type DatasetOrdering = 'TimeSeries' | 'Unordered'
+
 /** Enum representing all supported types of static properties. */
 export enum StaticPropertyType {
   Generic = 'GenericStatProp',
@@ -96,7 +102,8 @@ export enum StaticPropertyType {
   FunctionInputMonotonic = 'FnInputMonotonic',
   FunctionInputMonotonicWithCondition = 'FnInputMonotonicContext',
   VariableRegulationMonotonic = 'RegulationMonotonic',
-  VariableRegulationMonotonicWithCondition = 'RegulationMonotonicContext'
+  VariableRegulationMonotonicWithCondition = 'RegulationMonotonicContext',
+  FunctionInputsMutuallyExclusive = 'FnInputsMutuallyExclusive'
 }
 
 /** Enum representing all supported types of dynamic properties. */
@@ -106,7 +113,11 @@ export enum DynamicPropertyType {
   TrapSpace = 'ExistsTrapSpace',
   ExistsTrajectory = 'ExistsTrajectory',
   AttractorCount = 'AttractorCount',
-  HasAttractor = 'HasAttractor'
+  HasAttractor = 'HasAttractor',
+  ObservationsReachable = 'ObservationsReachable',
+  SteadyState = 'SteadyState',
+  ReachableFromDataset = 'ReachableFromDataset',
+  ObservationAbsent = 'ObservationAbsent'
 }
 
 /** Typesafe representation of property template types. */
@@ -151,6 +162,34 @@ export interface IHasAttractorDynamicProperty extends IProperty {
   observation: string | null
 }
 
+/** Template dynamic property requiring at least `min_count` observations to be reachable. */
+export interface IObservationsReachableDynamicProperty extends IProperty {
+  dataset: string | null
+  min_count: number
+}
+
+/** Template dynamic property requiring all target observations to be reachable from initial ones. */
+export interface IReachableFromDatasetDynamicProperty extends IProperty {
+  initial_dataset: string | null
+  target_dataset: string | null
+}
+
+/** Mode in which an observation must be absent from the dynamics. */
+export type AbsenceMode = 'Unreachable' | 'NotAttractor'
+
+/** Template dynamic property requiring observations to be unreachable or not in an attractor. */
+export interface IObservationAbsentDynamicProperty extends IProperty {
+  dataset: string | null
+  observation: string | null
+  mode: AbsenceMode
+}
+
+/** Template dynamic property requiring observations to be steady states. */
+export interface ISteadyStateDynamicProperty extends IProperty {
+  dataset: string | null
+  observation: string | null
+}
+
 /** Generic dynamic property given by an HCTL formula. */
 export interface IGenericDynamicProperty extends IProperty {
   formula: string
@@ -163,6 +202,10 @@ export type DynamicProperty =
   | IExistsTrajectoryDynamicProperty
   | IAttractorCountDynamicProperty
   | IHasAttractorDynamicProperty
+  | IObservationsReachableDynamicProperty
+  | ISteadyStateDynamicProperty
+  | IReachableFromDatasetDynamicProperty
+  | IObservationAbsentDynamicProperty
   | IGenericDynamicProperty
 
 /** Template static property for essentiality of a function's input. */
@@ -181,6 +224,13 @@ export interface IFunctionInputMonotonicStaticProperty extends IProperty {
   context: string | undefined
 }
 
+/** Template static property requiring that two function's inputs are not both essential. */
+export interface IFunctionInputsMutuallyExclusiveStaticProperty extends IProperty {
+  input_1: string | null
+  input_2: string | null
+  target: string | null
+}
+
 /** Generic static property given by a FOL formula. */
 export interface IGenericStaticProperty extends IProperty {
   formula: string
@@ -208,4 +258,5 @@ export type StaticProperty =
   | IFunctionInputMonotonicStaticProperty
   | IVariableRegulatorMonotonicStaticProperty
   | IVariableRegulatorEssentialStaticProperty
+  | IFunctionInputsMutuallyExclusiveStaticProperty
   | IGenericStaticProperty
@@ -106,6 +106,7 @@ export function convertToIObservation (observationData: ObservationData, variabl
     id: observationData.id,
     name: observationData.name,
     annotation: observationData.annotation,
+    metadata: observationData.metadata ?? '',
     selected: false
   }
   variables.forEach(((v, idx) => {
@@ -129,7 +130,8 @@ export function convertFromIObservation (
     name: observation.name,
     annotation: observation.annotation,
     dataset: datasetId,
-    values: valueString
+    values: valueString,
+    metadata: (observation.metadata === '') ? null : observation.metadata
   }
 }
 
@@ -143,7 +145,8 @@ export function convertToIObservationSet (datasetData: DatasetData): IObservatio
     name: datasetData.name,
     annotation: datasetData.annotation,
     observations,
-    variables: datasetData.variables
+    variables: datasetData.variables,
+    ordering: datasetData.ordering
   }
 }
 
@@ -157,7 +160,8 @@ export function convertFromIObservationSet (dataset: IObservationSet): DatasetDa
     name: dataset.name,
     annotation: dataset.annotation,
     observations,
-    variables: dataset.variables
+    variables: dataset.variables,
+    ordering: dataset.ordering
   }
 }
 
@@ -208,6 +212,8 @@ export function formatTemplateName (propertyType: PropertyType): string {
     case StaticPropertyType.VariableRegulationMonotonic:
     case StaticPropertyType.VariableRegulationMonotonicWithCondition:
       return 'Regulation monotonic'
+    case StaticPropertyType.FunctionInputsMutuallyExclusive:
+      return 'Function inputs mutually exclusive'
     case StaticPropertyType.Generic:
       return 'Generic static property'
     case DynamicPropertyType.AttractorCount:
@@ -220,6 +226,14 @@ export function formatTemplateName (propertyType: PropertyType): string {
       return 'Exist trap spaces'
     case DynamicPropertyType.HasAttractor:
       return 'Exist attractors'
+    case DynamicPropertyType.ObservationsReachable:
+      return 'Reachable observations'
+    case DynamicPropertyType.SteadyState:
+      return 'Steady states'
+    case DynamicPropertyType.ReachableFromDataset:
+      return 'Reachable from dataset'
+    case DynamicPropertyType.ObservationAbsent:
+      return 'Absent observations'
     case DynamicPropertyType.Generic:
       return 'Generic dynamic property'
   }
@@ -242,6 +256,8 @@ export function getTemplateHelpText (propertyType: PropertyType): string {
     case StaticPropertyType.VariableRegulationMonotonic:
     case StaticPropertyType.VariableRegulationMonotonicWithCondition:
       return 'Specifies whether regulation has monotonic effect.'
+    case StaticPropertyType.FunctionInputsMutuallyExclusive:
+      return 'Specifies that two inputs cannot be both essential.'
     case StaticPropertyType.Generic:
       return 'A generic static property defined by the user.'
     case DynamicPropertyType.AttractorCount:
@@ -254,6 +270,14 @@ export function getTemplateHelpText (propertyType: PropertyType): string {
       return 'Each selected observation must correspond to a trap space.'
     case DynamicPropertyType.HasAttractor:
       return 'Each selected observation must correspond to an attractor state.'
+    case DynamicPropertyType.ObservationsReachable:
+      return 'At least the given number of observations must be reachable from some state.'
+    case DynamicPropertyType.SteadyState:
+      return 'No update function may change specified values of selected observations.'
+    case DynamicPropertyType.ReachableFromDataset:
+      return 'Every target observation must be reachable from some initial observation.'
+    case DynamicPropertyType.ObservationAbsent:
+      return 'Selected observations must be unreachable from outside, or not contain an attractor state.'
     case DynamicPropertyType.Generic:
       return 'A generic HCTL property defined by the user.'
   }
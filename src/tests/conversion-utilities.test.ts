@@ -77,13 +77,15 @@ const mockObservationData: ObservationData = {
   name: 'Observation 1',
   annotation: 'Annotation for observation 1',
   dataset: 'dataset1',
-  values: '01*'
+  values: '01*',
+  metadata: null
 }
 
 const mockIObservation: IObservation = {
   id: 'obs1',
   name: 'Observation 1',
   annotation: 'Annotation for observation 1',
+  metadata: '',
   selected: false,
   var0: '0',
   var1: '1',